@@ -3,8 +3,53 @@
 //! This module provides the main event loop that coordinates task and microtask
 //! execution following the JavaScript event loop model.
 
-use crate::task_queue::{MicroTask, MicrotaskQueue, Task, TaskQueue};
-use core_types::JsError;
+use crate::promise::Function;
+use crate::task_queue::{self, MicroTask, Task, TaskQueue};
+use core_types::{ErrorKind, JsError, Value};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::Thread;
+use std::time::{Duration, Instant};
+
+/// What a call to [`EventLoop::step`] did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepResult {
+    /// A task, timer callback or microtask ran
+    Ran,
+    /// Nothing was runnable; timers that are not yet due and host futures
+    /// may still be pending
+    Idle,
+}
+
+/// Identifies a timer scheduled with [`EventLoop::set_timeout`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TimerId(u64);
+
+/// A host future spawned onto the event loop, paired with the JS function
+/// that receives its output.
+struct PendingFuture {
+    future: Pin<Box<dyn Future<Output = Value>>>,
+    resolver: Function,
+}
+
+impl std::fmt::Debug for PendingFuture {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "PendingFuture {{ ... }}")
+    }
+}
+
+/// Waker that unparks the thread running the event loop.
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
 
 /// The JavaScript event loop.
 ///
@@ -29,7 +74,17 @@ use core_types::JsError;
 #[derive(Debug, Default)]
 pub struct EventLoop {
     task_queue: TaskQueue,
-    microtask_queue: MicrotaskQueue,
+    futures: Vec<PendingFuture>,
+    current_task: Option<String>,
+    /// Pending timer deadlines, earliest first; ties go to the lower (older) id
+    timer_heap: BinaryHeap<Reverse<(Instant, TimerId)>>,
+    /// Callbacks of timers that have neither fired nor been cleared
+    timers: HashMap<TimerId, Task>,
+    next_timer_id: u64,
+    /// Set while draining microtasks, so a nested checkpoint is a no-op
+    performing_microtask_checkpoint: bool,
+    /// Most microtasks one checkpoint may run before giving up
+    microtask_limit: Option<usize>,
 }
 
 impl EventLoop {
@@ -37,32 +92,240 @@ impl EventLoop {
     pub fn new() -> Self {
         Self {
             task_queue: TaskQueue::new(),
-            microtask_queue: MicrotaskQueue::new(),
+            futures: Vec::new(),
+            current_task: None,
+            timer_heap: BinaryHeap::new(),
+            timers: HashMap::new(),
+            next_timer_id: 0,
+            performing_microtask_checkpoint: false,
+            microtask_limit: None,
         }
     }
 
-    /// Runs the event loop until all tasks and microtasks are processed.
+    /// Caps how many microtasks a single checkpoint may run.
+    ///
+    /// Microtasks that keep enqueueing microtasks would otherwise starve
+    /// every task and timer forever. Once a checkpoint exceeds the limit it
+    /// fails with an `InternalError`, leaving the rest of the queue in place.
+    /// `None`, the default, drains without a limit.
+    pub fn set_microtask_limit(&mut self, limit: Option<usize>) {
+        self.microtask_limit = limit;
+    }
+
+    /// Returns the name of the task or microtask currently running, if it
+    /// was given one.
+    pub fn current_task_name(&self) -> Option<&str> {
+        self.current_task.as_deref()
+    }
+
+    /// Runs a task, tracking its name for the duration.
+    fn run_task(&mut self, task: Task) -> Result<Value, JsError> {
+        self.current_task = task.name().map(str::to_string);
+        let result = task.run_on(Some(self));
+        self.current_task = None;
+        result
+    }
+
+    /// Runs a microtask, tracking its name for the duration.
+    fn run_microtask(&mut self, microtask: MicroTask) -> Result<Value, JsError> {
+        self.current_task = microtask.name().map(str::to_string);
+        let result = microtask.run_on(Some(self));
+        self.current_task = None;
+        result
+    }
+
+    /// Runs the event loop until all tasks, microtasks and timers are processed.
     ///
     /// This is similar to `run_until_complete` but doesn't require a VM.
+    /// Timers that are due fire in deadline order, with the microtask queue
+    /// drained after each callback. When only timers and host futures
+    /// remain, the loop sleeps until the next deadline or wake-up.
     ///
     /// # Returns
     ///
     /// `Ok(())` if all tasks completed successfully, or an error if any task failed.
     pub fn run_until_done(&mut self) -> Result<(), JsError> {
-        // Process all tasks
-        while !self.task_queue.is_empty() || !self.microtask_queue.is_empty() {
+        loop {
+            // Deliver any host futures that have completed
+            let completed = self.poll_futures()?;
+
             // Execute one task (if available)
             if let Some(task) = self.task_queue.dequeue() {
-                task.run()?;
+                self.run_task(task)?;
             }
 
             // Drain all microtasks
             self.run_all_microtasks()?;
+
+            let fired = self.run_due_timers()?;
+
+            if self.task_queue.is_empty() && self.is_microtask_queue_empty() {
+                let next_deadline = self.next_timer_deadline();
+                if self.futures.is_empty() && next_deadline.is_none() {
+                    break;
+                }
+                // Only timers and host futures remain; sleep until the next
+                // deadline or until a future wakes us
+                if completed == 0 && fired == 0 {
+                    match next_deadline {
+                        Some(deadline) => std::thread::park_timeout(
+                            deadline.saturating_duration_since(Instant::now()),
+                        ),
+                        None => std::thread::park(),
+                    }
+                }
+            }
         }
 
         Ok(())
     }
 
+    /// Schedules `task` to run once `delay` has elapsed (`setTimeout`).
+    ///
+    /// Timers with the same deadline fire in the order they were scheduled.
+    pub fn set_timeout(&mut self, delay: Duration, task: Task) -> TimerId {
+        let id = TimerId(self.next_timer_id);
+        self.next_timer_id += 1;
+        self.timer_heap.push(Reverse((Instant::now() + delay, id)));
+        self.timers.insert(id, task);
+        id
+    }
+
+    /// Cancels a timer that has not fired yet (`clearTimeout`).
+    ///
+    /// # Returns
+    ///
+    /// `true` if the timer was pending, `false` if it already fired, was
+    /// already cleared, or never existed.
+    pub fn clear_timeout(&mut self, id: TimerId) -> bool {
+        self.timers.remove(&id).is_some()
+    }
+
+    /// Returns the number of timers that have neither fired nor been cleared.
+    pub fn pending_timer_count(&self) -> usize {
+        self.timers.len()
+    }
+
+    /// Deadline of the earliest pending timer, discarding cleared ones.
+    fn next_timer_deadline(&mut self) -> Option<Instant> {
+        while let Some(Reverse((deadline, id))) = self.timer_heap.peek() {
+            if self.timers.contains_key(id) {
+                return Some(*deadline);
+            }
+            self.timer_heap.pop();
+        }
+        None
+    }
+
+    /// Fires every timer whose deadline has passed, draining microtasks after each.
+    ///
+    /// # Returns
+    ///
+    /// The number of timers that fired.
+    fn run_due_timers(&mut self) -> Result<usize, JsError> {
+        let now = Instant::now();
+        let mut fired = 0;
+        while self.fire_due_timer(now)? {
+            self.run_all_microtasks()?;
+            fired += 1;
+        }
+        Ok(fired)
+    }
+
+    /// Waits for the earliest pending timer and runs its callback.
+    ///
+    /// Unlike [`EventLoop::run_until_done`] this does not drain microtasks
+    /// afterwards, so an embedder that runs its own callbacks can perform the
+    /// checkpoint itself.
+    ///
+    /// # Returns
+    ///
+    /// The timer that fired, or `None` if no timer is pending.
+    pub fn run_next_timer(&mut self) -> Result<Option<TimerId>, JsError> {
+        loop {
+            let Some(deadline) = self.next_timer_deadline() else {
+                return Ok(None);
+            };
+            let now = Instant::now();
+            if deadline > now {
+                std::thread::park_timeout(deadline - now);
+                continue;
+            }
+            let Some(Reverse((_, id))) = self.timer_heap.pop() else {
+                return Ok(None);
+            };
+            if let Some(task) = self.timers.remove(&id) {
+                self.run_task(task)?;
+                return Ok(Some(id));
+            }
+        }
+    }
+
+    /// Runs the callback of the earliest timer due by `now`, if there is one.
+    fn fire_due_timer(&mut self, now: Instant) -> Result<bool, JsError> {
+        match self.next_timer_deadline() {
+            Some(deadline) if deadline <= now => {}
+            _ => return Ok(false),
+        }
+        let Some(Reverse((_, id))) = self.timer_heap.pop() else {
+            return Ok(false);
+        };
+        match self.timers.remove(&id) {
+            Some(task) => {
+                self.run_task(task)?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Spawns a host future onto the event loop.
+    ///
+    /// The future is polled on each turn of the loop. When it completes, its
+    /// output is passed to `resolver` (typically the resolve function of a
+    /// JS promise), bridging native async work such as file reads into JS.
+    pub fn spawn_future<F>(&mut self, future: F, resolver: Function)
+    where
+        F: Future<Output = Value> + 'static,
+    {
+        self.futures.push(PendingFuture {
+            future: Box::pin(future),
+            resolver,
+        });
+    }
+
+    /// Returns the number of spawned host futures that have not completed.
+    pub fn pending_future_count(&self) -> usize {
+        self.futures.len()
+    }
+
+    /// Polls every spawned host future once, invoking the resolver of each
+    /// one that completed.
+    ///
+    /// # Returns
+    ///
+    /// The number of futures that completed during this poll.
+    pub fn poll_futures(&mut self) -> Result<usize, JsError> {
+        if self.futures.is_empty() {
+            return Ok(0);
+        }
+
+        let waker = Waker::from(Arc::new(ThreadWaker(std::thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        let mut completed = 0;
+        let mut i = 0;
+        while i < self.futures.len() {
+            if let Poll::Ready(value) = self.futures[i].future.as_mut().poll(&mut cx) {
+                let mut pending = self.futures.remove(i);
+                pending.resolver.call(vec![value])?;
+                completed += 1;
+            } else {
+                i += 1;
+            }
+        }
+        Ok(completed)
+    }
+
     /// Adds a task to the task queue.
     ///
     /// The task will be executed in the next available iteration of the event loop.
@@ -73,8 +336,10 @@ impl EventLoop {
     /// Adds a microtask to the microtask queue.
     ///
     /// The microtask will be executed after the current task completes.
+    /// Microtasks share one FIFO with promise jobs, so a microtask queued
+    /// after a promise reaction runs after it.
     pub fn enqueue_microtask(&mut self, microtask: MicroTask) {
-        self.microtask_queue.enqueue(microtask);
+        task_queue::enqueue_job(microtask);
     }
 
     /// Returns true if the task queue is empty.
@@ -84,18 +349,93 @@ impl EventLoop {
 
     /// Returns true if the microtask queue is empty.
     pub fn is_microtask_queue_empty(&self) -> bool {
-        self.microtask_queue.is_empty()
+        task_queue::pending_jobs() == 0
     }
 
     /// Runs all microtasks in the queue until empty.
     ///
-    /// This drains the microtask queue completely. New microtasks added during
-    /// execution will also be processed before this method returns.
+    /// This drains the microtask queue completely, promise jobs included.
+    /// New microtasks added during execution will also be processed before
+    /// this method returns.
+    ///
+    /// Called from inside a microtask, this returns immediately: the
+    /// checkpoint already in progress runs the rest of the queue in order.
     pub fn run_all_microtasks(&mut self) -> Result<(), JsError> {
-        while let Some(microtask) = self.microtask_queue.dequeue() {
-            microtask.run()?;
+        self.drain_microtasks().map(|_| ())
+    }
+
+    /// Runs the oldest queued microtask or promise job, if there is one.
+    ///
+    /// # Returns
+    ///
+    /// `true` if a microtask ran.
+    pub fn run_next_microtask(&mut self) -> Result<bool, JsError> {
+        match task_queue::dequeue_job() {
+            Some(microtask) => self.run_microtask(microtask).map(|_| true),
+            None => Ok(false),
         }
-        Ok(())
+    }
+
+    /// Drains microtasks and promise jobs, returning how many ran.
+    fn drain_microtasks(&mut self) -> Result<usize, JsError> {
+        if self.performing_microtask_checkpoint {
+            return Ok(0);
+        }
+        self.performing_microtask_checkpoint = true;
+        let result = self.drain_microtasks_unguarded();
+        self.performing_microtask_checkpoint = false;
+        result
+    }
+
+    fn drain_microtasks_unguarded(&mut self) -> Result<usize, JsError> {
+        let mut count = 0;
+        while let Some(microtask) = task_queue::dequeue_job() {
+            self.run_microtask(microtask)?;
+            count += 1;
+            self.check_microtask_limit(count)?;
+        }
+        Ok(count)
+    }
+
+    fn check_microtask_limit(&self, count: usize) -> Result<(), JsError> {
+        match self.microtask_limit {
+            Some(limit) if count > limit => Err(JsError {
+                kind: ErrorKind::InternalError,
+                message: format!("Microtask queue did not drain after {} microtasks", limit),
+                stack: vec![],
+                source_position: None,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the number of tasks waiting in the task queue.
+    pub fn pending_task_count(&self) -> usize {
+        self.task_queue.len()
+    }
+
+    /// Returns the number of microtasks waiting to run, including queued
+    /// promise jobs.
+    pub fn pending_microtask_count(&self) -> usize {
+        task_queue::pending_jobs()
+    }
+
+    /// Runs one macrotask followed by a full microtask drain.
+    ///
+    /// The macrotask is the oldest queued task or, if there is none, the
+    /// earliest timer that is due. Completed host futures are delivered
+    /// first. Unlike [`EventLoop::run_until_done`] this never sleeps, so a
+    /// test can drive the loop one turn at a time.
+    pub fn step(&mut self) -> Result<StepResult, JsError> {
+        let mut ran = self.poll_futures()? > 0;
+        if let Some(task) = self.task_queue.dequeue() {
+            self.run_task(task)?;
+            ran = true;
+        } else if self.fire_due_timer(Instant::now())? {
+            ran = true;
+        }
+        ran |= self.drain_microtasks()? > 0;
+        Ok(if ran { StepResult::Ran } else { StepResult::Idle })
     }
 
     /// Runs all tasks in the queue (without processing microtasks between them).
@@ -103,7 +443,7 @@ impl EventLoop {
     /// This is primarily for testing purposes.
     pub fn run_all_tasks(&mut self) -> Result<(), JsError> {
         while let Some(task) = self.task_queue.dequeue() {
-            task.run()?;
+            self.run_task(task)?;
         }
         Ok(())
     }
@@ -114,7 +454,7 @@ impl EventLoop {
     pub fn process_one_cycle(&mut self) -> Result<(), JsError> {
         // Execute one task if available
         if let Some(task) = self.task_queue.dequeue() {
-            task.run()?;
+            self.run_task(task)?;
         }
 
         // Drain all microtasks
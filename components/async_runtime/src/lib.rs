@@ -56,7 +56,7 @@ pub mod promise;
 pub mod task_queue;
 
 // Re-export main types at crate root
-pub use event_loop::EventLoop;
+pub use event_loop::{EventLoop, StepResult, TimerId};
 pub use module::{ExportEntry, ImportEntry, Module, ModuleStatus};
-pub use promise::{Function, Promise, PromiseReaction, PromiseState};
-pub use task_queue::{MicroTask, MicrotaskQueue, Task, TaskQueue};
+pub use promise::{AggregateError, Function, Promise, PromiseReaction, PromiseState, SettledResult};
+pub use task_queue::{attach_async_frame, MicroTask, MicrotaskQueue, Task, TaskQueue};
@@ -3,7 +3,27 @@
 //! This module provides a JavaScript Promise implementation with proper
 //! state management and chaining support.
 
-use core_types::{JsError, Value};
+use crate::task_queue::{self, MicroTask};
+use core_types::{ErrorKind, JsError, ObjectView, Value};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+/// Queues a promise job (a reaction or a thenable resolution) on the same
+/// FIFO as event loop microtasks.
+#[track_caller]
+fn enqueue_job(job: impl FnOnce() + 'static) {
+    task_queue::enqueue_job(MicroTask::new(move || {
+        job();
+        Ok(Value::Undefined)
+    }));
+}
+
+/// How a promise settled, as seen by the reactions it triggers.
+#[derive(Clone)]
+enum Settlement {
+    Fulfilled(Value),
+    Rejected(JsError, Option<Value>),
+}
 
 /// The state of a Promise.
 ///
@@ -22,15 +42,16 @@ pub enum PromiseState {
 /// A function that can be called with arguments and returns a Result.
 ///
 /// This represents a JavaScript function that can be used as a Promise handler.
+/// Like [`Value`], it is not `Send`, so it may capture JavaScript values.
 pub struct Function {
-    callback: Box<dyn FnMut(Vec<Value>) -> Result<Value, JsError> + Send>,
+    callback: Box<dyn FnMut(Vec<Value>) -> Result<Value, JsError>>,
 }
 
 impl Function {
     /// Creates a new Function from a closure.
     pub fn new<F>(f: F) -> Self
     where
-        F: FnMut(Vec<Value>) -> Result<Value, JsError> + Send + 'static,
+        F: FnMut(Vec<Value>) -> Result<Value, JsError> + 'static,
     {
         Self {
             callback: Box::new(f),
@@ -49,13 +70,123 @@ impl std::fmt::Debug for Function {
     }
 }
 
+/// The outcome of one input promise, as reported by `Promise.allSettled`.
+///
+/// Mirrors the `{ status: "fulfilled", value }` and
+/// `{ status: "rejected", reason }` result objects.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SettledResult {
+    /// The input promise fulfilled with this value
+    Fulfilled(Value),
+    /// The input promise rejected with this reason
+    Rejected(Value),
+}
+
+impl SettledResult {
+    /// The `status` property: `"fulfilled"` or `"rejected"`.
+    pub fn status(&self) -> &'static str {
+        match self {
+            SettledResult::Fulfilled(_) => "fulfilled",
+            SettledResult::Rejected(_) => "rejected",
+        }
+    }
+
+    /// Reads a property of the result object (`status`, `value` or `reason`).
+    pub fn get(&self, key: &str) -> Option<Value> {
+        match (self, key) {
+            (_, "status") => Some(Value::String(self.status().to_string())),
+            (SettledResult::Fulfilled(value), "value") => Some(value.clone()),
+            (SettledResult::Rejected(reason), "reason") => Some(reason.clone()),
+            _ => None,
+        }
+    }
+
+    /// The result object, with `status` first.
+    fn view(&self) -> ObjectView {
+        let (key, value) = match self {
+            SettledResult::Fulfilled(value) => ("value", value),
+            SettledResult::Rejected(reason) => ("reason", reason),
+        };
+        ObjectView::Object(vec![
+            ("status".to_string(), Value::String(self.status().to_string())),
+            (key.to_string(), value.clone()),
+        ])
+    }
+
+    /// Reads back a result object built from [`SettledResult::view`].
+    fn from_value(value: &Value) -> Option<SettledResult> {
+        let ObjectView::Object(entries) = value.object_view()? else {
+            return None;
+        };
+        let property = |key: &str| {
+            entries.iter().find(|(name, _)| name == key).map(|(_, value)| value.clone())
+        };
+        match property("status")? {
+            Value::String(status) if status == "fulfilled" => {
+                Some(SettledResult::Fulfilled(property("value")?))
+            }
+            Value::String(status) if status == "rejected" => {
+                Some(SettledResult::Rejected(property("reason")?))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The rejection reason of `Promise.any` once every input has rejected.
+///
+/// Carried in the rejected Promise's `result`, alongside a [`JsError`] of
+/// kind [`ErrorKind::AggregateError`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct AggregateError {
+    /// The message, as in `new AggregateError(errors, message)`
+    pub message: String,
+    /// Each input's rejection reason, in input order
+    pub errors: Vec<Value>,
+}
+
+impl AggregateError {
+    /// Creates an AggregateError with the given rejection reasons.
+    pub fn new(errors: Vec<Value>, message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            errors,
+        }
+    }
+
+    /// The [`JsError`] a Promise rejected with this AggregateError holds.
+    pub fn to_error(&self) -> JsError {
+        JsError {
+            kind: ErrorKind::AggregateError,
+            message: self.message.clone(),
+            stack: vec![],
+            source_position: None,
+        }
+    }
+
+    /// Wraps the AggregateError in a [`Value`] for use as a rejection reason.
+    pub fn to_value(&self) -> Value {
+        Value::NativeObject(Rc::new(RefCell::new(self.clone())))
+    }
+
+    /// Returns the AggregateError held by `value`, if it holds one.
+    pub fn from_value(value: &Value) -> Option<AggregateError> {
+        match value {
+            Value::NativeObject(object) => {
+                object.borrow().downcast_ref::<AggregateError>().cloned()
+            }
+            _ => None,
+        }
+    }
+}
+
 /// A reaction to be triggered when a Promise settles.
 ///
 /// This represents the handlers registered via `.then()`.
 #[derive(Debug)]
 pub struct PromiseReaction {
     /// The promise that will be resolved/rejected based on this reaction
-    pub promise: Promise,
+    pub promise: Rc<RefCell<Promise>>,
     /// Handler for fulfilled state
     pub on_fulfilled: Option<Function>,
     /// Handler for rejected state
@@ -90,6 +221,8 @@ pub struct Promise {
     pub result: Option<Value>,
     /// The error if rejected
     pub error: Option<JsError>,
+    /// Reaction jobs queued for this Promise that have not run yet
+    queued_reactions: Rc<Cell<usize>>,
 }
 
 impl Promise {
@@ -104,12 +237,54 @@ impl Promise {
             reactions: Vec::new(),
             result: None,
             error: None,
+            queued_reactions: Rc::new(Cell::new(0)),
         }
     }
 
+    /// Creates a pending Promise with functions that settle it
+    /// (`Promise.withResolvers()`).
+    ///
+    /// The Promise is shared with the two functions, so it is returned behind
+    /// an `Rc<RefCell<_>>`. The first call to either function settles it; every
+    /// later call is ignored. Rejecting keeps the reason in `result` as well as
+    /// turning it into `error`.
+    ///
+    /// # Returns
+    ///
+    /// `(promise, resolve, reject)`
+    pub fn with_resolvers() -> (Rc<RefCell<Promise>>, Function, Function) {
+        let promise = Rc::new(RefCell::new(Promise::new()));
+        // Set by whichever function runs first; a promise that is still
+        // adopting another promise's state is pending but already resolved.
+        let already_resolved = Rc::new(Cell::new(false));
+
+        let target = Rc::clone(&promise);
+        let resolved = Rc::clone(&already_resolved);
+        let resolve = Function::new(move |args| {
+            if !resolved.replace(true) {
+                let value = args.into_iter().next().unwrap_or(Value::Undefined);
+                Promise::resolve_shared(&target, value);
+            }
+            Ok(Value::Undefined)
+        });
+
+        let target = Rc::clone(&promise);
+        let reject = Function::new(move |args| {
+            let reason = args.into_iter().next().unwrap_or(Value::Undefined);
+            if !already_resolved.replace(true) {
+                target.borrow_mut().reject_with_reason(reason);
+            }
+            Ok(Value::Undefined)
+        });
+
+        (promise, resolve, reject)
+    }
+
     /// Resolves the Promise with a value.
     ///
     /// If the Promise is already settled (Fulfilled or Rejected), this is a no-op.
+    /// The value is taken as-is; use [`Promise::resolve_shared`] to adopt the
+    /// state of a promise value instead.
     ///
     /// # Arguments
     ///
@@ -117,9 +292,8 @@ impl Promise {
     pub fn resolve(&mut self, value: Value) {
         if matches!(self.state, PromiseState::Pending) {
             self.state = PromiseState::Fulfilled;
-            self.result = Some(value);
-            // Trigger reactions would happen here in a full implementation
-            self.trigger_reactions();
+            self.result = Some(value.clone());
+            self.trigger_reactions(Settlement::Fulfilled(value));
         }
     }
 
@@ -133,15 +307,40 @@ impl Promise {
     pub fn reject(&mut self, error: JsError) {
         if matches!(self.state, PromiseState::Pending) {
             self.state = PromiseState::Rejected;
-            self.error = Some(error);
-            // Trigger reactions would happen here in a full implementation
-            self.trigger_reactions();
+            self.error = Some(error.clone());
+            let reason = self.result.clone();
+            self.trigger_reactions(Settlement::Rejected(error, reason));
+        }
+    }
+
+    /// Rejects the Promise with a JavaScript value as its reason.
+    ///
+    /// The reason is kept as-is in `result`, so reactions and `await` see the
+    /// original value; `error` holds a [`JsError`] describing it.
+    /// If the Promise is already settled, this is a no-op.
+    pub fn reject_with_reason(&mut self, reason: Value) {
+        if matches!(self.state, PromiseState::Pending) {
+            let message = match &reason {
+                Value::String(s) => s.clone(),
+                other => format!("{:?}", other),
+            };
+            let error = JsError {
+                kind: ErrorKind::TypeError,
+                message,
+                stack: vec![],
+                source_position: None,
+            };
+            // Set first so the reactions reject with the original reason too
+            self.result = Some(reason);
+            self.reject(error);
         }
     }
 
     /// Adds handlers for fulfillment and/or rejection.
     ///
     /// Returns a new Promise that will be resolved based on the handlers' results.
+    /// The returned Promise is not shared with the reaction, so it never
+    /// settles; use [`Promise::then_shared`] to build a chain that does.
     ///
     /// # Arguments
     ///
@@ -156,33 +355,383 @@ impl Promise {
         on_fulfilled: Option<Function>,
         on_rejected: Option<Function>,
     ) -> Promise {
-        let chained = Promise::new();
-
         let reaction = PromiseReaction {
-            promise: Promise::new(), // placeholder for the chained promise
+            promise: Rc::new(RefCell::new(Promise::new())),
             on_fulfilled,
             on_rejected,
         };
 
-        self.reactions.push(reaction);
+        match self.settlement() {
+            Some(settlement) => self.queue_reaction(reaction, settlement),
+            None => self.reactions.push(reaction),
+        }
 
-        chained
+        Promise::new()
     }
 
-    /// Checks if there are pending reactions.
-    pub fn has_pending_reactions(&self) -> bool {
-        !self.reactions.is_empty()
+    /// Adds handlers to a shared Promise (`PerformPromiseThen`).
+    ///
+    /// If the Promise is still pending the reaction waits for it to settle;
+    /// otherwise a reaction job is queued immediately. Either way the handler
+    /// runs from [`Promise::run_jobs`], never synchronously.
+    ///
+    /// # Returns
+    ///
+    /// The derived Promise, settled with the handler's outcome.
+    pub fn then_shared(
+        this: &Rc<RefCell<Promise>>,
+        on_fulfilled: Option<Function>,
+        on_rejected: Option<Function>,
+    ) -> Rc<RefCell<Promise>> {
+        let derived = Rc::new(RefCell::new(Promise::new()));
+        this.borrow_mut().add_reaction(PromiseReaction {
+            promise: Rc::clone(&derived),
+            on_fulfilled,
+            on_rejected,
+        });
+        derived
+    }
+
+    /// Resolves a shared Promise the way its `resolve` function would.
+    ///
+    /// Resolving with another promise does not settle `this` right away: a
+    /// resolve-thenable job is queued, which subscribes to the other promise,
+    /// whose reaction then settles `this`. That costs two extra microtask
+    /// turns compared with resolving to a plain value, as the spec requires.
+    /// Resolving a Promise with itself rejects it with a `TypeError`.
+    pub fn resolve_shared(this: &Rc<RefCell<Promise>>, resolution: Value) {
+        if !matches!(this.borrow().state, PromiseState::Pending) {
+            return;
+        }
+
+        let Some(thenable) = Promise::from_value(&resolution) else {
+            this.borrow_mut().resolve(resolution);
+            return;
+        };
+
+        if Rc::ptr_eq(&thenable, this) {
+            this.borrow_mut().reject(JsError {
+                kind: ErrorKind::TypeError,
+                message: "Chaining cycle detected for promise".to_string(),
+                stack: vec![],
+                source_position: None,
+            });
+            return;
+        }
+
+        let target = Rc::clone(this);
+        enqueue_job(move || {
+            let on_fulfilled = {
+                let target = Rc::clone(&target);
+                Function::new(move |args| {
+                    let value = args.into_iter().next().unwrap_or(Value::Undefined);
+                    Promise::resolve_shared(&target, value);
+                    Ok(Value::Undefined)
+                })
+            };
+            let on_rejected = {
+                let source = Rc::clone(&thenable);
+                Function::new(move |_args| {
+                    let (error, reason) = {
+                        let source = source.borrow();
+                        (source.error.clone(), source.result.clone())
+                    };
+                    if let Some(error) = error {
+                        reject_shared(&target, error, reason);
+                    }
+                    Ok(Value::Undefined)
+                })
+            };
+            Promise::then_shared(&thenable, Some(on_fulfilled), Some(on_rejected));
+        });
+    }
+
+    /// Implements `Promise.resolve(value)`.
+    ///
+    /// A promise value is returned unchanged, without wrapping it in a new
+    /// Promise; anything else becomes a Promise resolved with it.
+    pub fn promise_resolve(value: Value) -> Rc<RefCell<Promise>> {
+        if let Some(promise) = Promise::from_value(&value) {
+            return promise;
+        }
+        let promise = Rc::new(RefCell::new(Promise::new()));
+        Promise::resolve_shared(&promise, value);
+        promise
+    }
+
+    /// Implements `Promise.allSettled(promises)`.
+    ///
+    /// The returned Promise never rejects. Once every input has settled it
+    /// fulfills with an array of `{ status, value }` and `{ status, reason }`
+    /// objects in input order; read them back with
+    /// [`Promise::settled_results`]. An empty input fulfills right away.
+    ///
+    /// The array is built with the engine's registered
+    /// [`ObjectBuilder`](core_types::ObjectBuilder). Without one, the
+    /// [`SettledResult`]s are wrapped in a [`Value`] as they are.
+    pub fn all_settled(promises: Vec<Rc<RefCell<Promise>>>) -> Rc<RefCell<Promise>> {
+        let combined = Rc::new(RefCell::new(Promise::new()));
+        if promises.is_empty() {
+            combined.borrow_mut().resolve(settled_results_value(Vec::new()));
+            return combined;
+        }
+
+        let results = Rc::new(RefCell::new(vec![None; promises.len()]));
+        let remaining = Rc::new(Cell::new(promises.len()));
+        for (index, promise) in promises.iter().enumerate() {
+            let record = |wrap: fn(Value) -> SettledResult| {
+                let results = Rc::clone(&results);
+                let remaining = Rc::clone(&remaining);
+                let combined = Rc::clone(&combined);
+                Function::new(move |args| {
+                    let value = args.into_iter().next().unwrap_or(Value::Undefined);
+                    results.borrow_mut()[index] = Some(wrap(value));
+                    remaining.set(remaining.get() - 1);
+                    if remaining.get() == 0 {
+                        let settled = results.borrow_mut().drain(..).flatten().collect();
+                        combined.borrow_mut().resolve(settled_results_value(settled));
+                    }
+                    Ok(Value::Undefined)
+                })
+            };
+            Promise::then_shared(
+                promise,
+                Some(record(SettledResult::Fulfilled)),
+                Some(record(SettledResult::Rejected)),
+            );
+        }
+        combined
+    }
+
+    /// Implements `Promise.any(promises)`.
+    ///
+    /// The returned Promise fulfills with the value of the first input to
+    /// fulfill. It rejects only once every input has rejected, with an
+    /// [`AggregateError`] holding the reasons in input order as its
+    /// rejection reason. An empty input rejects right away.
+    pub fn any(promises: Vec<Rc<RefCell<Promise>>>) -> Rc<RefCell<Promise>> {
+        let combined = Rc::new(RefCell::new(Promise::new()));
+        if promises.is_empty() {
+            reject_aggregate(&combined, Vec::new());
+            return combined;
+        }
+
+        let reasons = Rc::new(RefCell::new(vec![None; promises.len()]));
+        let remaining = Rc::new(Cell::new(promises.len()));
+        for (index, promise) in promises.iter().enumerate() {
+            let on_fulfilled = {
+                let combined = Rc::clone(&combined);
+                Function::new(move |args| {
+                    let value = args.into_iter().next().unwrap_or(Value::Undefined);
+                    Promise::resolve_shared(&combined, value);
+                    Ok(Value::Undefined)
+                })
+            };
+            let on_rejected = {
+                let reasons = Rc::clone(&reasons);
+                let remaining = Rc::clone(&remaining);
+                let combined = Rc::clone(&combined);
+                Function::new(move |args| {
+                    let reason = args.into_iter().next().unwrap_or(Value::Undefined);
+                    reasons.borrow_mut()[index] = Some(reason);
+                    remaining.set(remaining.get() - 1);
+                    if remaining.get() == 0 {
+                        let errors = reasons.borrow_mut().drain(..).flatten().collect();
+                        reject_aggregate(&combined, errors);
+                    }
+                    Ok(Value::Undefined)
+                })
+            };
+            Promise::then_shared(promise, Some(on_fulfilled), Some(on_rejected));
+        }
+        combined
+    }
+
+    /// Implements `Promise.race(promises)`.
+    ///
+    /// The returned Promise settles the same way as the first input to
+    /// settle; later settlements are ignored. An input that has already
+    /// settled is adopted on the next microtask turn, not synchronously, and
+    /// an empty input never settles.
+    pub fn race(promises: Vec<Rc<RefCell<Promise>>>) -> Rc<RefCell<Promise>> {
+        let combined = Rc::new(RefCell::new(Promise::new()));
+        for promise in &promises {
+            // Without handlers the reaction passes the settlement straight on
+            promise.borrow_mut().add_reaction(PromiseReaction {
+                promise: Rc::clone(&combined),
+                on_fulfilled: None,
+                on_rejected: None,
+            });
+        }
+        combined
+    }
+
+    /// Returns the results held by the fulfillment value of
+    /// [`Promise::all_settled`].
+    ///
+    /// A built array is read through the registered object inspector, so
+    /// `None` is returned if the inspector does not list every element.
+    pub fn settled_results(value: &Value) -> Option<Vec<SettledResult>> {
+        let Value::NativeObject(object) = value else {
+            return None;
+        };
+        if let Some(results) = object.borrow().downcast_ref::<Vec<SettledResult>>() {
+            return Some(results.clone());
+        }
+        match value.object_view()? {
+            ObjectView::Array { elements, length } if elements.len() == length => {
+                elements.iter().map(SettledResult::from_value).collect()
+            }
+            _ => None,
+        }
+    }
+
+    /// Wraps a shared Promise in a [`Value`] so it can be passed around as a
+    /// resolution or handler result.
+    pub fn to_value(this: &Rc<RefCell<Promise>>) -> Value {
+        Value::NativeObject(Rc::new(RefCell::new(Rc::clone(this))))
     }
 
-    /// Triggers all registered reactions.
+    /// Returns the shared Promise held by `value`, if it holds one.
+    pub fn from_value(value: &Value) -> Option<Rc<RefCell<Promise>>> {
+        match value {
+            Value::NativeObject(object) => object
+                .borrow()
+                .downcast_ref::<Rc<RefCell<Promise>>>()
+                .cloned(),
+            _ => None,
+        }
+    }
+
+    /// Runs queued jobs until none remain, without an event loop.
+    ///
+    /// Promise jobs share a queue with event loop microtasks, so microtasks
+    /// queued on this thread run here too, in order. Jobs queued while
+    /// running are processed in the same call, after the ones already queued.
     ///
-    /// In a real implementation, this would enqueue microtasks.
-    fn trigger_reactions(&mut self) {
-        // In a full implementation, this would:
-        // 1. For each reaction, create a microtask
-        // 2. Enqueue the microtask in the event loop
-        // 3. The microtask would call the appropriate handler
-        // For now, we just keep the reactions pending
+    /// # Returns
+    ///
+    /// The number of jobs that ran.
+    pub fn run_jobs() -> usize {
+        let mut count = 0;
+        while let Some(job) = task_queue::dequeue_job() {
+            // Promise jobs settle promises rather than returning errors
+            let _ = job.run();
+            count += 1;
+        }
+        count
+    }
+
+    /// Returns the number of jobs queued for the next microtask checkpoint,
+    /// including event loop microtasks.
+    pub fn pending_job_count() -> usize {
+        task_queue::pending_jobs()
+    }
+
+    /// Checks if there are pending reactions, either still waiting for the
+    /// Promise to settle or queued as jobs that have not run yet.
+    pub fn has_pending_reactions(&self) -> bool {
+        !self.reactions.is_empty() || self.queued_reactions.get() > 0
+    }
+
+    fn settlement(&self) -> Option<Settlement> {
+        match self.state {
+            PromiseState::Pending => None,
+            PromiseState::Fulfilled => Some(Settlement::Fulfilled(
+                self.result.clone().unwrap_or(Value::Undefined),
+            )),
+            PromiseState::Rejected => Some(Settlement::Rejected(
+                self.error.clone().unwrap_or_else(|| JsError {
+                    kind: ErrorKind::TypeError,
+                    message: String::new(),
+                    stack: vec![],
+                    source_position: None,
+                }),
+                self.result.clone(),
+            )),
+        }
+    }
+
+    /// Registers a reaction, queueing its job right away if already settled.
+    fn add_reaction(&mut self, reaction: PromiseReaction) {
+        match self.settlement() {
+            Some(settlement) => self.queue_reaction(reaction, settlement),
+            None => self.reactions.push(reaction),
+        }
+    }
+
+    /// Queues a reaction job for every registered reaction.
+    fn trigger_reactions(&mut self, settlement: Settlement) {
+        for reaction in std::mem::take(&mut self.reactions) {
+            self.queue_reaction(reaction, settlement.clone());
+        }
+    }
+
+    fn queue_reaction(&self, reaction: PromiseReaction, settlement: Settlement) {
+        let queued = Rc::clone(&self.queued_reactions);
+        queued.set(queued.get() + 1);
+        let job = reaction_job(reaction, settlement);
+        enqueue_job(move || {
+            queued.set(queued.get() - 1);
+            job();
+        });
+    }
+}
+
+/// Builds the `Promise.allSettled` array of result objects, or wraps the
+/// results as they are if no object builder is registered.
+fn settled_results_value(results: Vec<SettledResult>) -> Value {
+    let objects: Option<Vec<Value>> = results.iter().map(|r| r.view().build()).collect();
+    let array = objects.and_then(|elements| {
+        let length = elements.len();
+        ObjectView::Array { elements, length }.build()
+    });
+    array.unwrap_or_else(|| Value::NativeObject(Rc::new(RefCell::new(results))))
+}
+
+/// Rejects a shared Promise, keeping the original rejection reason.
+fn reject_shared(this: &Rc<RefCell<Promise>>, error: JsError, reason: Option<Value>) {
+    let mut promise = this.borrow_mut();
+    if matches!(promise.state, PromiseState::Pending) {
+        // Set first so the reactions reject with the original reason too
+        promise.result = reason;
+        promise.reject(error);
+    }
+}
+
+/// Rejects a shared Promise with an [`AggregateError`] of `errors`.
+fn reject_aggregate(this: &Rc<RefCell<Promise>>, errors: Vec<Value>) {
+    let aggregate = AggregateError::new(errors, "All promises were rejected");
+    reject_shared(this, aggregate.to_error(), Some(aggregate.to_value()));
+}
+
+/// Builds the job that runs one reaction's handler (`PromiseReactionJob`).
+fn reaction_job(reaction: PromiseReaction, settlement: Settlement) -> impl FnOnce() {
+    move || {
+        let PromiseReaction {
+            promise,
+            on_fulfilled,
+            on_rejected,
+        } = reaction;
+
+        let outcome = match settlement {
+            Settlement::Fulfilled(value) => match on_fulfilled {
+                Some(mut handler) => handler.call(vec![value]).map_err(|e| (e, None)),
+                None => Ok(value),
+            },
+            Settlement::Rejected(error, reason) => match on_rejected {
+                Some(mut handler) => {
+                    let reason = reason.unwrap_or_else(|| Value::String(error.message.clone()));
+                    handler.call(vec![reason]).map_err(|e| (e, None))
+                }
+                None => Err((error, reason)),
+            },
+        };
+
+        match outcome {
+            Ok(value) => Promise::resolve_shared(&promise, value),
+            Err((error, reason)) => reject_shared(&promise, error, reason),
+        }
     }
 }
 
@@ -2,16 +2,101 @@
 //!
 //! This module provides the task and microtask queues used by the event loop.
 //! Tasks are executed one at a time, with all microtasks draining after each task.
+//!
+//! Promise jobs and event loop microtasks share one FIFO per thread, the
+//! agent's job queue, so they run in the order they were queued.
+//!
+//! Every task records the site that created it. When a task fails, that site
+//! is appended to the error's stack as an async frame, so the trace shows
+//! where the failing work was scheduled as well as where it threw.
 
-use core_types::{JsError, Value};
+use crate::EventLoop;
+use core_types::{ErrorKind, JsError, StackFrame, Value};
+use std::cell::RefCell;
 use std::collections::VecDeque;
+use std::panic::Location;
+
+thread_local! {
+    /// The agent's job queue: microtasks and promise jobs, in FIFO order.
+    static JOB_QUEUE: RefCell<MicrotaskQueue> = RefCell::new(MicrotaskQueue::new());
+}
+
+/// Adds a microtask to the end of this thread's job queue.
+pub(crate) fn enqueue_job(microtask: MicroTask) {
+    JOB_QUEUE.with(|queue| queue.borrow_mut().enqueue(microtask));
+}
+
+/// Removes the oldest microtask from this thread's job queue.
+pub(crate) fn dequeue_job() -> Option<MicroTask> {
+    JOB_QUEUE.with(|queue| queue.borrow_mut().dequeue())
+}
+
+/// Returns the number of microtasks in this thread's job queue.
+pub(crate) fn pending_jobs() -> usize {
+    JOB_QUEUE.with(|queue| queue.borrow().len())
+}
+
+/// A callback that receives the event loop running it.
+type LoopCallback = Box<dyn FnOnce(&mut EventLoop) -> Result<Value, JsError>>;
+
+/// The body of a task or microtask.
+enum Callback {
+    /// Runs on its own
+    Standalone(Box<dyn FnOnce() -> Result<Value, JsError>>),
+    /// Receives the event loop running it, so it can schedule more work
+    WithEventLoop(LoopCallback),
+}
+
+impl Callback {
+    fn call(self, event_loop: Option<&mut EventLoop>) -> Result<Value, JsError> {
+        match (self, event_loop) {
+            (Callback::Standalone(f), _) => f(),
+            (Callback::WithEventLoop(f), Some(event_loop)) => f(event_loop),
+            (Callback::WithEventLoop(_), None) => Err(JsError {
+                kind: ErrorKind::InternalError,
+                message: "Task requires an event loop to run".to_string(),
+                stack: vec![],
+                source_position: None,
+            }),
+        }
+    }
+}
+
+/// Builds a frame pointing at the code that created a task.
+fn creation_site(location: &Location<'_>) -> StackFrame {
+    StackFrame {
+        function_name: None,
+        source_url: Some(location.file().to_string()),
+        line: location.line(),
+        column: location.column(),
+    }
+}
+
+/// Appends the async origin of a failed task to the error's stack.
+///
+/// The frame is named `async <name>`, or plain `async` for unnamed tasks.
+/// Embedders that run a task's work outside its closure use this to report
+/// errors the same way.
+pub fn attach_async_frame(mut error: JsError, name: Option<String>, parent: StackFrame) -> JsError {
+    let label = match name {
+        Some(name) => format!("async {}", name),
+        None => "async".to_string(),
+    };
+    error.stack.push(StackFrame {
+        function_name: Some(label),
+        ..parent
+    });
+    error
+}
 
 /// A task to be executed by the event loop.
 ///
 /// Tasks represent work to be done in the next iteration of the event loop.
 /// Examples include setTimeout callbacks, I/O completions, and DOM events.
 pub struct Task {
-    callback: Box<dyn FnOnce() -> Result<Value, JsError> + Send>,
+    callback: Callback,
+    name: Option<String>,
+    async_parent: StackFrame,
 }
 
 impl Task {
@@ -20,42 +105,90 @@ impl Task {
     /// # Arguments
     ///
     /// * `f` - The function to execute when the task runs
+    ///
+    /// The caller's location is captured as the task's async parent frame.
+    #[track_caller]
     pub fn new<F>(f: F) -> Self
     where
-        F: FnOnce() -> Result<Value, JsError> + Send + 'static,
+        F: FnOnce() -> Result<Value, JsError> + 'static,
     {
         Self {
-            callback: Box::new(f),
+            callback: Callback::Standalone(Box::new(f)),
+            name: None,
+            async_parent: creation_site(Location::caller()),
         }
     }
 
+    /// Names the task after the logical operation it belongs to.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Replaces the captured creation site, e.g. with the JS frame that
+    /// scheduled the task.
+    pub fn with_async_parent(mut self, frame: StackFrame) -> Self {
+        self.async_parent = frame;
+        self
+    }
+
+    /// Returns the task's name, if it has one.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the frame that created the task.
+    pub fn async_parent(&self) -> &StackFrame {
+        &self.async_parent
+    }
+
     /// Creates a new Task that has access to the event loop.
     ///
     /// This allows tasks to enqueue more tasks or microtasks.
-    pub fn with_event_loop<F>(_f: F) -> Self
+    #[track_caller]
+    pub fn with_event_loop<F>(f: F) -> Self
     where
-        F: FnOnce(&mut crate::EventLoop) -> Result<Value, JsError> + Send + 'static,
+        F: FnOnce(&mut EventLoop) -> Result<Value, JsError> + 'static,
     {
-        // This is a simplified implementation that doesn't actually pass the event loop
-        // In a full implementation, we'd need to handle this properly
         Self {
-            callback: Box::new(|| Ok(Value::Undefined)),
+            callback: Callback::WithEventLoop(Box::new(f)),
+            name: None,
+            async_parent: creation_site(Location::caller()),
         }
     }
 
     /// Executes the task.
     ///
+    /// A task created with [`Task::with_event_loop`] fails with an
+    /// `InternalError` here, since there is no loop to give it.
+    ///
     /// # Returns
     ///
-    /// The result of the task execution.
+    /// The result of the task execution. Errors carry the task's async
+    /// origin as their last stack frame.
     pub fn run(self) -> Result<Value, JsError> {
-        (self.callback)()
+        self.run_on(None)
+    }
+
+    /// Executes the task as part of `event_loop`.
+    pub(crate) fn run_on(self, event_loop: Option<&mut EventLoop>) -> Result<Value, JsError> {
+        let Self {
+            callback,
+            name,
+            async_parent,
+        } = self;
+        callback
+            .call(event_loop)
+            .map_err(|error| attach_async_frame(error, name, async_parent))
     }
 }
 
 impl std::fmt::Debug for Task {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Task {{ ... }}")
+        match &self.name {
+            Some(name) => write!(f, "Task {{ name: {:?}, ... }}", name),
+            None => write!(f, "Task {{ ... }}"),
+        }
     }
 }
 
@@ -64,7 +197,9 @@ impl std::fmt::Debug for Task {
 /// Microtasks are executed after each task and before rendering.
 /// Examples include Promise reactions and MutationObserver callbacks.
 pub struct MicroTask {
-    callback: Box<dyn FnOnce() -> Result<Value, JsError> + Send>,
+    callback: Callback,
+    name: Option<String>,
+    async_parent: StackFrame,
 }
 
 impl MicroTask {
@@ -73,28 +208,90 @@ impl MicroTask {
     /// # Arguments
     ///
     /// * `f` - The function to execute when the microtask runs
+    ///
+    /// The caller's location is captured as the microtask's async parent frame.
+    #[track_caller]
     pub fn new<F>(f: F) -> Self
     where
-        F: FnOnce() -> Result<Value, JsError> + Send + 'static,
+        F: FnOnce() -> Result<Value, JsError> + 'static,
     {
         Self {
-            callback: Box::new(f),
+            callback: Callback::Standalone(Box::new(f)),
+            name: None,
+            async_parent: creation_site(Location::caller()),
+        }
+    }
+
+    /// Names the microtask after the logical operation it belongs to.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Replaces the captured creation site, e.g. with the JS frame that
+    /// scheduled the microtask.
+    pub fn with_async_parent(mut self, frame: StackFrame) -> Self {
+        self.async_parent = frame;
+        self
+    }
+
+    /// Returns the microtask's name, if it has one.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the frame that created the microtask.
+    pub fn async_parent(&self) -> &StackFrame {
+        &self.async_parent
+    }
+
+    /// Creates a new MicroTask that has access to the event loop.
+    ///
+    /// This allows microtasks to enqueue more tasks or microtasks.
+    #[track_caller]
+    pub fn with_event_loop<F>(f: F) -> Self
+    where
+        F: FnOnce(&mut EventLoop) -> Result<Value, JsError> + 'static,
+    {
+        Self {
+            callback: Callback::WithEventLoop(Box::new(f)),
+            name: None,
+            async_parent: creation_site(Location::caller()),
         }
     }
 
     /// Executes the microtask.
     ///
+    /// A microtask created with [`MicroTask::with_event_loop`] fails with an
+    /// `InternalError` here, since there is no loop to give it.
+    ///
     /// # Returns
     ///
-    /// The result of the microtask execution.
+    /// The result of the microtask execution. Errors carry the microtask's
+    /// async origin as their last stack frame.
     pub fn run(self) -> Result<Value, JsError> {
-        (self.callback)()
+        self.run_on(None)
+    }
+
+    /// Executes the microtask as part of `event_loop`.
+    pub(crate) fn run_on(self, event_loop: Option<&mut EventLoop>) -> Result<Value, JsError> {
+        let Self {
+            callback,
+            name,
+            async_parent,
+        } = self;
+        callback
+            .call(event_loop)
+            .map_err(|error| attach_async_frame(error, name, async_parent))
     }
 }
 
 impl std::fmt::Debug for MicroTask {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "MicroTask {{ ... }}")
+        match &self.name {
+            Some(name) => write!(f, "MicroTask {{ name: {:?}, ... }}", name),
+            None => write!(f, "MicroTask {{ ... }}"),
+        }
     }
 }
 
@@ -202,6 +399,40 @@ mod tests {
         assert_eq!(result.unwrap(), Value::Boolean(true));
     }
 
+    #[test]
+    fn test_microtask_error_stitches_async_parent() {
+        use core_types::ErrorKind;
+
+        let parent = StackFrame {
+            function_name: Some("schedule".to_string()),
+            source_url: Some("app.js".to_string()),
+            line: 3,
+            column: 7,
+        };
+        let microtask = MicroTask::new(|| {
+            Err(JsError {
+                kind: ErrorKind::RangeError,
+                message: "bad".to_string(),
+                stack: vec![],
+                source_position: None,
+            })
+        })
+        .with_name("then")
+        .with_async_parent(parent);
+        assert_eq!(microtask.name(), Some("then"));
+
+        let error = microtask.run().unwrap_err();
+        assert_eq!(
+            error.stack,
+            vec![StackFrame {
+                function_name: Some("async then".to_string()),
+                source_url: Some("app.js".to_string()),
+                line: 3,
+                column: 7,
+            }]
+        );
+    }
+
     #[test]
     fn test_task_queue_fifo() {
         let mut queue = TaskQueue::new();
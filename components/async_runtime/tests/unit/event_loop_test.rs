@@ -1,6 +1,7 @@
 //! Unit tests for EventLoop
 
-use async_runtime::{EventLoop, MicroTask, Task};
+use async_runtime::{EventLoop, Function, MicroTask, Promise, PromiseState, StepResult, Task};
+use std::time::Duration;
 use core_types::Value;
 
 #[test]
@@ -165,3 +166,352 @@ fn multiple_tasks_with_microtasks() {
     // Task1 (1), then microtask1 (2), then Task2 (3)
     assert_eq!(*results.lock().unwrap(), vec![1, 2, 3]);
 }
+
+#[test]
+fn spawned_ready_future_fulfills_promise_during_run() {
+    let mut event_loop = EventLoop::new();
+    let (promise, resolve, _reject) = Promise::with_resolvers();
+
+    event_loop.spawn_future(async { Value::Smi(7) }, resolve);
+    assert_eq!(event_loop.pending_future_count(), 1);
+    assert!(matches!(promise.borrow().state, PromiseState::Pending));
+
+    event_loop.run_until_done().unwrap();
+    assert_eq!(event_loop.pending_future_count(), 0);
+    assert!(matches!(promise.borrow().state, PromiseState::Fulfilled));
+    assert_eq!(promise.borrow().result, Some(Value::Smi(7)));
+}
+
+#[test]
+fn spawned_future_woken_from_another_thread_completes() {
+    use std::sync::mpsc;
+
+    let mut event_loop = EventLoop::new();
+    let (promise, resolve, _reject) = Promise::with_resolvers();
+    let (tx, rx) = mpsc::channel();
+
+    // A future that stays pending until a worker thread delivers its result
+    let future = std::future::poll_fn(move |cx| {
+        if let Ok(n) = rx.try_recv() {
+            return std::task::Poll::Ready(Value::Smi(n));
+        }
+        let waker = cx.waker().clone();
+        let tx = tx.clone();
+        std::thread::spawn(move || {
+            let _ = tx.send(42);
+            waker.wake();
+        });
+        std::task::Poll::Pending
+    });
+
+    event_loop.spawn_future(future, resolve);
+    event_loop.run_until_done().unwrap();
+    assert_eq!(promise.borrow().result, Some(Value::Smi(42)));
+}
+
+#[test]
+fn named_task_error_includes_async_origin() {
+    use core_types::{ErrorKind, JsError};
+
+    let mut event_loop = EventLoop::new();
+    let task = Task::new(|| {
+        Err(JsError {
+            kind: ErrorKind::TypeError,
+            message: "boom".to_string(),
+            stack: vec![],
+            source_position: None,
+        })
+    })
+    .with_name("loadConfig");
+    event_loop.enqueue_task(task);
+
+    let error = event_loop.run_until_done().unwrap_err();
+    assert_eq!(error.message, "boom");
+    let origin = error.stack.last().expect("async frame appended");
+    assert_eq!(origin.function_name.as_deref(), Some("async loadConfig"));
+    assert!(origin
+        .source_url
+        .as_deref()
+        .unwrap()
+        .ends_with("event_loop_test.rs"));
+    assert!(event_loop.current_task_name().is_none());
+}
+
+#[test]
+fn timers_fire_in_deadline_order() {
+    let mut event_loop = EventLoop::new();
+    let order = std::sync::Arc::new(std::sync::Mutex::new(vec![]));
+
+    for (label, delay) in [("slow", 30), ("fast", 5), ("medium", 15), ("fast-second", 5)] {
+        let order = order.clone();
+        event_loop.set_timeout(
+            Duration::from_millis(delay),
+            Task::new(move || {
+                order.lock().unwrap().push(label);
+                Ok(Value::Undefined)
+            }),
+        );
+    }
+    assert_eq!(event_loop.pending_timer_count(), 4);
+
+    event_loop.run_until_done().unwrap();
+
+    // Equal delays keep insertion order
+    assert_eq!(*order.lock().unwrap(), vec!["fast", "fast-second", "medium", "slow"]);
+    assert_eq!(event_loop.pending_timer_count(), 0);
+}
+
+#[test]
+fn microtasks_drain_between_timer_callbacks() {
+    let mut event_loop = EventLoop::new();
+    let order = std::sync::Arc::new(std::sync::Mutex::new(vec![]));
+
+    for label in ["first", "second"] {
+        let order = order.clone();
+        event_loop.set_timeout(
+            Duration::ZERO,
+            Task::new(move || {
+                order.lock().unwrap().push(label);
+                let order = order.clone();
+                let (promise, _resolve, _reject) = Promise::with_resolvers();
+                Promise::then_shared(
+                    &promise,
+                    Some(Function::new(move |_| {
+                        order.lock().unwrap().push("microtask");
+                        Ok(Value::Undefined)
+                    })),
+                    None,
+                );
+                promise.borrow_mut().resolve(Value::Undefined);
+                Ok(Value::Undefined)
+            }),
+        );
+    }
+
+    event_loop.run_until_done().unwrap();
+    assert_eq!(*order.lock().unwrap(), vec!["first", "microtask", "second", "microtask"]);
+}
+
+#[test]
+fn cleared_timer_never_fires() {
+    let mut event_loop = EventLoop::new();
+    let order = std::sync::Arc::new(std::sync::Mutex::new(vec![]));
+
+    let kept_order = order.clone();
+    event_loop.set_timeout(
+        Duration::from_millis(10),
+        Task::new(move || {
+            kept_order.lock().unwrap().push("kept");
+            Ok(Value::Undefined)
+        }),
+    );
+    let cleared_order = order.clone();
+    let cleared = event_loop.set_timeout(
+        Duration::from_millis(1),
+        Task::new(move || {
+            cleared_order.lock().unwrap().push("cleared");
+            Ok(Value::Undefined)
+        }),
+    );
+
+    assert!(event_loop.clear_timeout(cleared));
+    // Clearing twice reports that nothing was pending
+    assert!(!event_loop.clear_timeout(cleared));
+
+    event_loop.run_until_done().unwrap();
+    assert_eq!(*order.lock().unwrap(), vec!["kept"]);
+}
+
+#[test]
+fn step_runs_a_tasks_microtasks_before_the_next_task() {
+    let mut event_loop = EventLoop::new();
+    let order = std::sync::Arc::new(std::sync::Mutex::new(vec![]));
+
+    let first_order = order.clone();
+    event_loop.enqueue_task(Task::new(move || {
+        first_order.lock().unwrap().push("task 1");
+        let order = first_order.clone();
+        let promise = Promise::promise_resolve(Value::Undefined);
+        Promise::then_shared(
+            &promise,
+            Some(Function::new(move |_| {
+                order.lock().unwrap().push("microtask from task 1");
+                Ok(Value::Undefined)
+            })),
+            None,
+        );
+        Ok(Value::Undefined)
+    }));
+    let second_order = order.clone();
+    event_loop.enqueue_task(Task::new(move || {
+        second_order.lock().unwrap().push("task 2");
+        Ok(Value::Undefined)
+    }));
+    event_loop.enqueue_microtask(MicroTask::new(|| Ok(Value::Undefined)));
+    assert_eq!(event_loop.pending_task_count(), 2);
+    assert_eq!(event_loop.pending_microtask_count(), 1);
+
+    assert_eq!(event_loop.step().unwrap(), StepResult::Ran);
+    assert_eq!(*order.lock().unwrap(), vec!["task 1", "microtask from task 1"]);
+    assert_eq!(event_loop.pending_task_count(), 1);
+    assert_eq!(event_loop.pending_microtask_count(), 0);
+
+    assert_eq!(event_loop.step().unwrap(), StepResult::Ran);
+    assert_eq!(
+        *order.lock().unwrap(),
+        vec!["task 1", "microtask from task 1", "task 2"]
+    );
+    assert_eq!(event_loop.step().unwrap(), StepResult::Idle);
+}
+
+#[test]
+fn step_is_idle_while_timer_is_not_due() {
+    let mut event_loop = EventLoop::new();
+    event_loop.set_timeout(Duration::from_secs(60), Task::new(|| Ok(Value::Undefined)));
+    assert_eq!(event_loop.step().unwrap(), StepResult::Idle);
+    assert_eq!(event_loop.pending_timer_count(), 1);
+
+    event_loop.set_timeout(Duration::ZERO, Task::new(|| Ok(Value::Undefined)));
+    assert_eq!(event_loop.step().unwrap(), StepResult::Ran);
+    assert_eq!(event_loop.pending_timer_count(), 1);
+}
+
+#[test]
+fn nested_microtasks_drain_before_the_next_task() {
+    let mut event_loop = EventLoop::new();
+    let order = std::sync::Arc::new(std::sync::Mutex::new(vec![]));
+
+    fn enqueue_countdown(
+        event_loop: &mut EventLoop,
+        order: std::sync::Arc<std::sync::Mutex<Vec<String>>>,
+        remaining: u32,
+    ) {
+        event_loop.enqueue_microtask(MicroTask::with_event_loop(move |event_loop| {
+            order.lock().unwrap().push(format!("microtask {}", remaining));
+            if remaining > 0 {
+                // Scheduling a task first must not let it overtake the microtask
+                let task_order = order.clone();
+                event_loop.enqueue_task(Task::new(move || {
+                    task_order.lock().unwrap().push(format!("task {}", remaining));
+                    Ok(Value::Undefined)
+                }));
+                enqueue_countdown(event_loop, order, remaining - 1);
+            }
+            Ok(Value::Undefined)
+        }));
+    }
+
+    let first_order = order.clone();
+    event_loop.enqueue_task(Task::with_event_loop(move |event_loop| {
+        first_order.lock().unwrap().push("first task".to_string());
+        enqueue_countdown(event_loop, first_order, 3);
+        Ok(Value::Undefined)
+    }));
+
+    event_loop.run_until_done().unwrap();
+    assert_eq!(
+        *order.lock().unwrap(),
+        vec![
+            "first task",
+            "microtask 3",
+            "microtask 2",
+            "microtask 1",
+            "microtask 0",
+            "task 3",
+            "task 2",
+            "task 1",
+        ]
+    );
+}
+
+#[test]
+fn nested_microtask_checkpoint_is_a_no_op() {
+    let mut event_loop = EventLoop::new();
+    let order = std::sync::Arc::new(std::sync::Mutex::new(vec![]));
+
+    let outer_order = order.clone();
+    event_loop.enqueue_microtask(MicroTask::with_event_loop(move |event_loop| {
+        let inner_order = outer_order.clone();
+        event_loop.enqueue_microtask(MicroTask::new(move || {
+            inner_order.lock().unwrap().push("inner");
+            Ok(Value::Undefined)
+        }));
+        event_loop.run_all_microtasks()?;
+        outer_order.lock().unwrap().push("outer done");
+        Ok(Value::Undefined)
+    }));
+
+    event_loop.run_all_microtasks().unwrap();
+    assert_eq!(*order.lock().unwrap(), vec!["outer done", "inner"]);
+}
+
+#[test]
+fn microtask_limit_stops_an_endless_microtask_loop() {
+    fn enqueue_forever(event_loop: &mut EventLoop) {
+        event_loop.enqueue_microtask(MicroTask::with_event_loop(|event_loop| {
+            enqueue_forever(event_loop);
+            Ok(Value::Undefined)
+        }));
+    }
+
+    let mut event_loop = EventLoop::new();
+    event_loop.set_microtask_limit(Some(100));
+    enqueue_forever(&mut event_loop);
+
+    let error = event_loop.run_all_microtasks().unwrap_err();
+    assert!(error.message.contains("100 microtasks"));
+    assert_eq!(event_loop.pending_microtask_count(), 1);
+}
+
+#[test]
+fn event_loop_task_without_loop_fails() {
+    let task = Task::with_event_loop(|_| Ok(Value::Undefined));
+    assert!(task.run().is_err());
+}
+
+#[test]
+fn promise_jobs_interleave_with_microtasks_in_queue_order() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    let order = Rc::new(RefCell::new(Vec::new()));
+    let push = |tag: &'static str| {
+        let order = Rc::clone(&order);
+        move || {
+            order.borrow_mut().push(tag);
+            Ok(Value::Undefined)
+        }
+    };
+
+    let mut event_loop = EventLoop::new();
+    let promise = Rc::new(RefCell::new(Promise::new()));
+    promise.borrow_mut().resolve(Value::Undefined);
+
+    // queueMicrotask(A); p.then(B); queueMicrotask(C)
+    event_loop.enqueue_microtask(MicroTask::new(push("A")));
+    let b = push("B");
+    Promise::then_shared(&promise, Some(Function::new(move |_| b())), None);
+    event_loop.enqueue_microtask(MicroTask::new(push("C")));
+
+    event_loop.run_all_microtasks().unwrap();
+    assert_eq!(*order.borrow(), vec!["A", "B", "C"]);
+}
+
+#[test]
+fn run_next_timer_fires_one_timer_without_draining_microtasks() {
+    let mut event_loop = EventLoop::new();
+    let idle = Task::new(|| Ok(Value::Undefined));
+    let later = event_loop.set_timeout(Duration::from_millis(5), idle);
+    let first = event_loop.set_timeout(
+        Duration::ZERO,
+        Task::with_event_loop(|event_loop| {
+            event_loop.enqueue_microtask(MicroTask::new(|| Ok(Value::Undefined)));
+            Ok(Value::Undefined)
+        }),
+    );
+
+    assert_eq!(event_loop.run_next_timer().unwrap(), Some(first));
+    assert_eq!(event_loop.pending_microtask_count(), 1);
+    assert_eq!(event_loop.run_next_timer().unwrap(), Some(later));
+    assert_eq!(event_loop.run_next_timer().unwrap(), None);
+}
@@ -1,7 +1,9 @@
 //! Unit tests for Promise
 
-use async_runtime::{Promise, PromiseState};
+use async_runtime::{AggregateError, Function, Promise, PromiseState, SettledResult};
 use core_types::{ErrorKind, JsError, Value};
+use std::cell::RefCell;
+use std::rc::Rc;
 
 #[test]
 fn new_promise_is_pending() {
@@ -166,3 +168,265 @@ fn resolve_with_double() {
     promise.resolve(Value::Double(3.14));
     assert_eq!(promise.result, Some(Value::Double(3.14)));
 }
+
+#[test]
+fn with_resolvers_resolve_settles_promise_once() {
+    let (promise, mut resolve, mut reject) = Promise::with_resolvers();
+    assert!(matches!(promise.borrow().state, PromiseState::Pending));
+
+    resolve.call(vec![Value::Smi(7)]).unwrap();
+    resolve.call(vec![Value::Smi(8)]).unwrap();
+    reject.call(vec![Value::String("late".to_string())]).unwrap();
+
+    let promise = promise.borrow();
+    assert!(matches!(promise.state, PromiseState::Fulfilled));
+    assert_eq!(promise.result, Some(Value::Smi(7)));
+    assert!(promise.error.is_none());
+}
+
+#[test]
+fn with_resolvers_reject_keeps_reason() {
+    let (promise, mut resolve, mut reject) = Promise::with_resolvers();
+
+    reject.call(vec![Value::String("boom".to_string())]).unwrap();
+    resolve.call(vec![Value::Smi(1)]).unwrap();
+
+    let promise = promise.borrow();
+    assert!(matches!(promise.state, PromiseState::Rejected));
+    assert_eq!(promise.result, Some(Value::String("boom".to_string())));
+    assert_eq!(promise.error.as_ref().unwrap().message, "boom");
+}
+
+/// Registers a handler on `promise` that records `label` and returns its input.
+fn record(
+    promise: &Rc<RefCell<Promise>>,
+    log: &Rc<RefCell<Vec<&'static str>>>,
+    label: &'static str,
+) -> Rc<RefCell<Promise>> {
+    let log = Rc::clone(log);
+    let handler = Function::new(move |args| {
+        log.borrow_mut().push(label);
+        Ok(args.into_iter().next().unwrap_or(Value::Undefined))
+    });
+    Promise::then_shared(promise, Some(handler), None)
+}
+
+#[test]
+fn promise_resolve_returns_promise_argument_unchanged() {
+    let promise = Promise::promise_resolve(Value::Smi(1));
+    let same = Promise::promise_resolve(Promise::to_value(&promise));
+    assert!(Rc::ptr_eq(&promise, &same));
+    assert!(matches!(promise.borrow().state, PromiseState::Fulfilled));
+}
+
+#[test]
+fn then_shared_runs_handlers_as_jobs() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let promise = Promise::promise_resolve(Value::Smi(5));
+    let derived = record(&promise, &log, "a");
+
+    assert!(log.borrow().is_empty());
+    assert_eq!(Promise::run_jobs(), 1);
+    assert_eq!(*log.borrow(), vec!["a"]);
+    assert_eq!(derived.borrow().result, Some(Value::Smi(5)));
+}
+
+#[test]
+fn resolving_with_promise_costs_two_extra_ticks() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let fulfilled = Promise::promise_resolve(Value::Smi(0));
+
+    // Chain a: one reaction per tick.
+    let mut a = Rc::clone(&fulfilled);
+    for label in ["a1", "a2", "a3", "a4"] {
+        a = record(&a, &log, label);
+    }
+
+    // Promise.resolve(p) returns p itself, so b runs on the first tick.
+    record(&Promise::promise_resolve(Promise::to_value(&fulfilled)), &log, "b");
+
+    // new Promise(r => r(p)) adopts p through a thenable job and a reaction
+    // job, so c runs two ticks after b.
+    let (adopting, mut resolve, _reject) = Promise::with_resolvers();
+    resolve.call(vec![Promise::to_value(&fulfilled)]).unwrap();
+    record(&adopting, &log, "c");
+
+    Promise::run_jobs();
+    assert_eq!(*log.borrow(), vec!["a1", "b", "a2", "a3", "c", "a4"]);
+    assert_eq!(adopting.borrow().result, Some(Value::Smi(0)));
+}
+
+#[test]
+fn resolving_promise_with_itself_rejects() {
+    let (promise, mut resolve, _reject) = Promise::with_resolvers();
+    resolve.call(vec![Promise::to_value(&promise)]).unwrap();
+    let promise = promise.borrow();
+    assert!(matches!(promise.state, PromiseState::Rejected));
+    assert_eq!(promise.error.as_ref().unwrap().kind, ErrorKind::TypeError);
+}
+
+#[test]
+fn rejection_passes_through_chain_to_handler() {
+    let (source, _resolve, mut reject) = Promise::with_resolvers();
+    let passthrough = Promise::then_shared(&source, None, None);
+
+    let seen = Rc::new(RefCell::new(None));
+    let sink = Rc::clone(&seen);
+    let on_rejected = Function::new(move |args| {
+        *sink.borrow_mut() = args.into_iter().next();
+        Ok(Value::Smi(1))
+    });
+    let recovered = Promise::then_shared(&passthrough, None, Some(on_rejected));
+
+    reject.call(vec![Value::String("boom".to_string())]).unwrap();
+    Promise::run_jobs();
+
+    assert_eq!(*seen.borrow(), Some(Value::String("boom".to_string())));
+    assert_eq!(recovered.borrow().result, Some(Value::Smi(1)));
+    assert!(matches!(recovered.borrow().state, PromiseState::Fulfilled));
+}
+
+#[test]
+fn all_settled_reports_each_outcome_in_order() {
+    let (rejected, _resolve, mut reject) = Promise::with_resolvers();
+    let (fulfilled, mut resolve, _reject) = Promise::with_resolvers();
+    let combined = Promise::all_settled(vec![Rc::clone(&rejected), Rc::clone(&fulfilled)]);
+
+    reject.call(vec![Value::String("nope".to_string())]).unwrap();
+    Promise::run_jobs();
+    // Still waiting on the second input
+    assert!(matches!(combined.borrow().state, PromiseState::Pending));
+
+    resolve.call(vec![Value::Smi(7)]).unwrap();
+    Promise::run_jobs();
+
+    let combined = combined.borrow();
+    assert!(matches!(combined.state, PromiseState::Fulfilled));
+    let results = Promise::settled_results(combined.result.as_ref().unwrap()).unwrap();
+    assert_eq!(
+        results,
+        vec![
+            SettledResult::Rejected(Value::String("nope".to_string())),
+            SettledResult::Fulfilled(Value::Smi(7)),
+        ]
+    );
+    assert_eq!(results[0].get("status"), Some(Value::String("rejected".to_string())));
+    assert_eq!(results[0].get("reason"), Some(Value::String("nope".to_string())));
+    assert_eq!(results[1].get("value"), Some(Value::Smi(7)));
+    assert_eq!(results[1].get("reason"), None);
+}
+
+#[test]
+fn all_settled_with_no_inputs_fulfills_immediately() {
+    let combined = Promise::all_settled(Vec::new());
+    let combined = combined.borrow();
+    assert!(matches!(combined.state, PromiseState::Fulfilled));
+    let results = Promise::settled_results(combined.result.as_ref().unwrap()).unwrap();
+    assert!(results.is_empty());
+}
+
+#[test]
+fn any_rejects_with_aggregate_error_once_all_reject() {
+    let (first, _resolve, mut reject_first) = Promise::with_resolvers();
+    let (second, _resolve, mut reject_second) = Promise::with_resolvers();
+    let combined = Promise::any(vec![Rc::clone(&first), Rc::clone(&second)]);
+    let seen = Rc::new(RefCell::new(None));
+    let seen_in_handler = Rc::clone(&seen);
+    Promise::then_shared(
+        &combined,
+        None,
+        Some(Function::new(move |args| {
+            *seen_in_handler.borrow_mut() = args.into_iter().next();
+            Ok(Value::Undefined)
+        })),
+    );
+
+    // Settle out of order; the errors still follow input order
+    reject_second.call(vec![Value::String("b".to_string())]).unwrap();
+    Promise::run_jobs();
+    assert!(matches!(combined.borrow().state, PromiseState::Pending));
+
+    reject_first.call(vec![Value::String("a".to_string())]).unwrap();
+    Promise::run_jobs();
+
+    let combined = combined.borrow();
+    assert!(matches!(combined.state, PromiseState::Rejected));
+    assert_eq!(combined.error.as_ref().unwrap().kind, ErrorKind::AggregateError);
+    let aggregate = AggregateError::from_value(combined.result.as_ref().unwrap()).unwrap();
+    assert_eq!(
+        aggregate.errors,
+        vec![Value::String("a".to_string()), Value::String("b".to_string())]
+    );
+    let seen = seen.borrow();
+    assert_eq!(AggregateError::from_value(seen.as_ref().unwrap()), Some(aggregate));
+}
+
+#[test]
+fn any_fulfills_with_first_fulfillment() {
+    let (rejected, _resolve, mut reject) = Promise::with_resolvers();
+    let (fulfilled, mut resolve, _reject) = Promise::with_resolvers();
+    let (pending, _resolve, _reject) = Promise::with_resolvers();
+    let combined = Promise::any(vec![rejected, Rc::clone(&fulfilled), pending]);
+
+    reject.call(vec![Value::String("nope".to_string())]).unwrap();
+    resolve.call(vec![Value::Smi(3)]).unwrap();
+    Promise::run_jobs();
+
+    // Fulfilled without waiting on the still-pending input
+    let combined = combined.borrow();
+    assert!(matches!(combined.state, PromiseState::Fulfilled));
+    assert_eq!(combined.result, Some(Value::Smi(3)));
+}
+
+#[test]
+fn any_with_no_inputs_rejects_immediately() {
+    let combined = Promise::any(Vec::new());
+    let combined = combined.borrow();
+    assert!(matches!(combined.state, PromiseState::Rejected));
+    let aggregate = AggregateError::from_value(combined.result.as_ref().unwrap()).unwrap();
+    assert!(aggregate.errors.is_empty());
+}
+
+#[test]
+fn race_rejects_when_faster_input_rejects() {
+    let (slow, mut resolve_slow, _reject) = Promise::with_resolvers();
+    let (fast, _resolve, mut reject_fast) = Promise::with_resolvers();
+    let combined = Promise::race(vec![Rc::clone(&slow), Rc::clone(&fast)]);
+
+    reject_fast.call(vec![Value::String("fast".to_string())]).unwrap();
+    Promise::run_jobs();
+    // Later settlements are ignored
+    resolve_slow.call(vec![Value::Smi(1)]).unwrap();
+    Promise::run_jobs();
+
+    let combined = combined.borrow();
+    assert!(matches!(combined.state, PromiseState::Rejected));
+    assert_eq!(combined.result, Some(Value::String("fast".to_string())));
+}
+
+#[test]
+fn race_adopts_already_settled_input_on_next_microtask() {
+    let mut settled = Promise::new();
+    settled.reject(JsError {
+        kind: ErrorKind::RangeError,
+        message: "early".to_string(),
+        stack: vec![],
+        source_position: None,
+    });
+    let (pending, _resolve, _reject) = Promise::with_resolvers();
+    let combined = Promise::race(vec![pending, Rc::new(RefCell::new(settled))]);
+    assert!(matches!(combined.borrow().state, PromiseState::Pending));
+
+    Promise::run_jobs();
+
+    let combined = combined.borrow();
+    assert!(matches!(combined.state, PromiseState::Rejected));
+    assert_eq!(combined.error.as_ref().unwrap().kind, ErrorKind::RangeError);
+}
+
+#[test]
+fn race_with_no_inputs_never_settles() {
+    let combined = Promise::race(Vec::new());
+    Promise::run_jobs();
+    assert!(matches!(combined.borrow().state, PromiseState::Pending));
+}
@@ -25,7 +25,9 @@ pub mod micro;
 pub mod runner;
 pub mod sunspider;
 
-pub use runner::{Benchmark, BenchmarkResult, BenchmarkSuite};
+pub use runner::{
+    Benchmark, BenchmarkComparison, BenchmarkResult, BenchmarkSuite, ComparisonReport,
+};
 
 #[cfg(test)]
 mod tests {
@@ -4,8 +4,15 @@
 
 use js_cli::Runtime;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
 use std::time::Instant;
 
+/// Default slowdown (as a fraction of the baseline) tolerated before a
+/// benchmark is reported as a regression
+pub const DEFAULT_REGRESSION_THRESHOLD: f64 = 0.05;
+
 /// A single benchmark test
 #[derive(Debug, Clone)]
 pub struct Benchmark {
@@ -103,6 +110,103 @@ pub struct BenchmarkSuite {
     pub name: String,
     /// Benchmarks in this suite
     pub benchmarks: Vec<Benchmark>,
+    /// Fractional slowdown over the baseline that counts as a regression
+    pub regression_threshold: f64,
+}
+
+/// Comparison of a single benchmark against its baseline
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchmarkComparison {
+    /// Name of the benchmark
+    pub name: String,
+    /// Baseline duration in milliseconds
+    pub baseline_ms: f64,
+    /// Current duration in milliseconds
+    pub current_ms: f64,
+    /// Relative change, e.g. `0.10` for 10% slower, `-0.10` for 10% faster
+    pub change: f64,
+    /// Whether the slowdown exceeds the suite's threshold
+    pub regressed: bool,
+}
+
+/// Result of comparing a suite run against a saved baseline
+#[derive(Debug, Clone)]
+pub struct ComparisonReport {
+    /// Threshold the comparisons were judged against
+    pub threshold: f64,
+    /// Benchmarks present in both the run and the baseline
+    pub comparisons: Vec<BenchmarkComparison>,
+    /// Successful benchmarks with no baseline entry
+    pub new_benchmarks: Vec<String>,
+    /// Results of the current run, suitable for saving as the next baseline
+    pub updated_baseline: Vec<BenchmarkResult>,
+}
+
+impl ComparisonReport {
+    /// Compare `results` against `baseline` using `threshold`
+    ///
+    /// Failed runs on either side are not compared.
+    pub fn compare(
+        results: &[BenchmarkResult],
+        baseline: &[BenchmarkResult],
+        threshold: f64,
+    ) -> Self {
+        let baseline: HashMap<&str, &BenchmarkResult> = baseline
+            .iter()
+            .filter(|b| b.success)
+            .map(|b| (b.name.as_str(), b))
+            .collect();
+
+        let mut comparisons = Vec::new();
+        let mut new_benchmarks = Vec::new();
+        for result in results.iter().filter(|r| r.success) {
+            let Some(base) = baseline.get(result.name.as_str()) else {
+                new_benchmarks.push(result.name.clone());
+                continue;
+            };
+            let change = if base.duration_ms > 0.0 {
+                (result.duration_ms - base.duration_ms) / base.duration_ms
+            } else {
+                0.0
+            };
+            comparisons.push(BenchmarkComparison {
+                name: result.name.clone(),
+                baseline_ms: base.duration_ms,
+                current_ms: result.duration_ms,
+                change,
+                regressed: change > threshold,
+            });
+        }
+
+        Self {
+            threshold,
+            comparisons,
+            new_benchmarks,
+            updated_baseline: results.to_vec(),
+        }
+    }
+
+    /// Comparisons that exceeded the threshold
+    pub fn regressions(&self) -> Vec<&BenchmarkComparison> {
+        self.comparisons.iter().filter(|c| c.regressed).collect()
+    }
+
+    /// Whether any benchmark regressed
+    pub fn has_regressions(&self) -> bool {
+        self.comparisons.iter().any(|c| c.regressed)
+    }
+
+    /// Write the current results to `path` as the new baseline
+    pub fn save_baseline(&self, path: &Path) -> io::Result<()> {
+        let json = format_results_json(&self.updated_baseline).map_err(io::Error::other)?;
+        std::fs::write(path, json)
+    }
+}
+
+/// Load results previously written by [`format_results_json`]
+pub fn load_baseline(path: &Path) -> io::Result<Vec<BenchmarkResult>> {
+    let json = std::fs::read_to_string(path)?;
+    serde_json::from_str(&json).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
 }
 
 impl BenchmarkSuite {
@@ -111,9 +215,16 @@ impl BenchmarkSuite {
         Self {
             name,
             benchmarks: Vec::new(),
+            regression_threshold: DEFAULT_REGRESSION_THRESHOLD,
         }
     }
 
+    /// Set the fractional slowdown tolerated by [`run_and_compare`](Self::run_and_compare)
+    pub fn with_regression_threshold(mut self, threshold: f64) -> Self {
+        self.regression_threshold = threshold;
+        self
+    }
+
     /// Add a benchmark to this suite
     pub fn add(&mut self, benchmark: Benchmark) {
         self.benchmarks.push(benchmark);
@@ -137,6 +248,24 @@ impl BenchmarkSuite {
             .map(|b| b.run_multiple(runtime, iterations))
             .collect()
     }
+
+    /// Run the suite and compare it against the baseline saved at `baseline_path`
+    ///
+    /// The report's `updated_baseline` holds this run's results; call
+    /// [`ComparisonReport::save_baseline`] to persist it.
+    pub fn run_and_compare(
+        &self,
+        runtime: &mut Runtime,
+        baseline_path: &Path,
+    ) -> io::Result<ComparisonReport> {
+        let baseline = load_baseline(baseline_path)?;
+        let results = self.run(runtime);
+        Ok(ComparisonReport::compare(
+            &results,
+            &baseline,
+            self.regression_threshold,
+        ))
+    }
 }
 
 /// Format benchmark results as a human-readable table
@@ -286,6 +415,62 @@ mod tests {
         assert!(json.contains("\"success\": true"));
     }
 
+    fn timed(name: &str, duration_ms: f64) -> BenchmarkResult {
+        BenchmarkResult {
+            name: name.to_string(),
+            description: String::new(),
+            duration_ms,
+            ops_per_sec: None,
+            success: true,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_compare_flags_only_regressions() {
+        let baseline = vec![timed("slower", 100.0), timed("faster", 100.0), timed("noise", 100.0)];
+        let current = vec![timed("slower", 120.0), timed("faster", 80.0), timed("noise", 104.0)];
+
+        let report = ComparisonReport::compare(&current, &baseline, DEFAULT_REGRESSION_THRESHOLD);
+        let regressions = report.regressions();
+        assert_eq!(regressions.len(), 1);
+        assert_eq!(regressions[0].name, "slower");
+        assert!((regressions[0].change - 0.2).abs() < 1e-9);
+        assert!(report.has_regressions());
+        assert_eq!(report.updated_baseline.len(), 3);
+    }
+
+    #[test]
+    fn test_run_and_compare_with_saved_baseline() {
+        let mut suite = BenchmarkSuite::new("Compare".to_string()).with_regression_threshold(0.5);
+        suite.add(Benchmark {
+            name: "regressed".to_string(),
+            description: String::new(),
+            code: "1 + 1".to_string(),
+        });
+        suite.add(Benchmark {
+            name: "improved".to_string(),
+            description: String::new(),
+            code: "1 + 1".to_string(),
+        });
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("baseline.json");
+        let baseline = vec![timed("regressed", 1.0e-9), timed("improved", 1.0e9)];
+        std::fs::write(&path, format_results_json(&baseline).unwrap()).unwrap();
+
+        let mut runtime = Runtime::new(false);
+        let report = suite.run_and_compare(&mut runtime, &path).unwrap();
+        let regressions = report.regressions();
+        assert_eq!(regressions.len(), 1);
+        assert_eq!(regressions[0].name, "regressed");
+
+        report.save_baseline(&path).unwrap();
+        let saved = load_baseline(&path).unwrap();
+        assert_eq!(saved.len(), 2);
+        assert!(saved[1].duration_ms < 1.0e9);
+    }
+
     #[test]
     fn test_benchmark_run_multiple() {
         let mut runtime = Runtime::new(false);
@@ -1,14 +1,31 @@
 //! Array.prototype methods and static methods
 
-use crate::value::{JsError, JsResult, JsValue};
+use crate::symbol::SymbolConstructor;
+use crate::value::{ConstructorData, JsError, JsResult, JsValue};
+use std::cell::RefCell;
+use std::rc::Rc;
 
 /// Array static methods
 pub struct Array;
 
 impl Array {
     /// Array.isArray(value) - Check if value is an array
-    pub fn is_array(value: &JsValue) -> bool {
-        value.is_array()
+    ///
+    /// Proxies are looked through to their target, so a Proxy of an array
+    /// is an array. A revoked Proxy is a TypeError.
+    pub fn is_array(value: &JsValue) -> JsResult<bool> {
+        match value {
+            JsValue::Array(_) => Ok(true),
+            JsValue::Proxy(proxy) => {
+                if proxy.is_revoked() {
+                    return Err(JsError::type_error(
+                        "Cannot perform 'IsArray' on a proxy that has been revoked",
+                    ));
+                }
+                Self::is_array(&proxy.target())
+            }
+            _ => Ok(false),
+        }
     }
 
     /// Array.of(...items) - Create array from arguments
@@ -61,6 +78,104 @@ impl Array {
 pub struct ArrayPrototype;
 
 impl ArrayPrototype {
+    /// The constructor ArraySpeciesCreate would use for a result derived
+    /// from `original`, or `None` when a plain Array should be created.
+    ///
+    /// The constructor's `Symbol.species` is consulted; `undefined` or
+    /// `null` there falls back to Array.
+    fn species_constructor(original: &JsValue) -> JsResult<Option<Rc<RefCell<ConstructorData>>>> {
+        let constructor = match original {
+            JsValue::Array(array_data) => match &array_data.borrow().constructor {
+                Some(constructor) => constructor.clone(),
+                None => return Ok(None),
+            },
+            _ => return Ok(None),
+        };
+
+        let species = match &constructor {
+            JsValue::Undefined => JsValue::Undefined,
+            JsValue::Null
+            | JsValue::Boolean(_)
+            | JsValue::Number(_)
+            | JsValue::String(_)
+            | JsValue::Symbol(_)
+            | JsValue::BigInt(_) => {
+                return Err(JsError::type_error("Array constructor is not an object"))
+            }
+            _ => constructor
+                .get_symbol(&SymbolConstructor::species())
+                .unwrap_or(JsValue::Undefined),
+        };
+
+        match species {
+            JsValue::Undefined | JsValue::Null => Ok(None),
+            JsValue::Constructor(ctor) => Ok(Some(ctor)),
+            _ => Err(JsError::type_error("Symbol.species is not a constructor")),
+        }
+    }
+
+    /// Builds the result of a derived-array method: a plain sparse array,
+    /// or an object from the species constructor called with `length`.
+    ///
+    /// `set_length` writes the final `length` to the constructed object,
+    /// as `slice` does.
+    fn species_create(
+        original: &JsValue,
+        length: usize,
+        items: Vec<Option<JsValue>>,
+        set_length: bool,
+    ) -> JsResult<JsValue> {
+        let Some(ctor) = Self::species_constructor(original)? else {
+            return Ok(JsValue::array_with_holes(items));
+        };
+
+        let count = items.len();
+        let result = (ctor.borrow().func)(vec![JsValue::number(length as f64)])?;
+        for (i, item) in items.into_iter().enumerate() {
+            if let Some(value) = item {
+                Self::create_data_property(&result, i, value)?;
+            }
+        }
+        if set_length {
+            match &result {
+                JsValue::Array(array_data) => {
+                    let mut data = array_data.borrow_mut();
+                    let old_len = data.elements.len();
+                    data.elements.resize(count, JsValue::undefined());
+                    data.holes.extend(old_len..count);
+                    data.holes.retain(|&i| i < count);
+                }
+                _ => result.set("length", JsValue::number(count as f64)),
+            }
+        }
+        Ok(result)
+    }
+
+    /// CreateDataPropertyOrThrow for an integer index
+    fn create_data_property(target: &JsValue, index: usize, value: JsValue) -> JsResult<()> {
+        match target {
+            JsValue::Array(array_data) => {
+                let mut data = array_data.borrow_mut();
+                if index >= data.elements.len() {
+                    let old_len = data.elements.len();
+                    data.elements.resize(index + 1, JsValue::undefined());
+                    data.holes.extend(old_len..index);
+                }
+                data.holes.remove(&index);
+                data.elements[index] = value;
+                Ok(())
+            }
+            JsValue::Object(_) => {
+                target.set(&index.to_string(), value);
+                Ok(())
+            }
+            _ => Err(JsError::type_error(format!(
+                "Cannot define property {} on species result",
+                index
+            ))),
+        }
+    }
+
     /// Array.prototype.push(element)
     pub fn push(arr: &JsValue, element: JsValue) -> JsResult<JsValue> {
         if let JsValue::Array(array_data) = arr {
@@ -76,6 +191,8 @@ impl ArrayPrototype {
     pub fn pop(arr: &JsValue) -> JsResult<JsValue> {
         if let JsValue::Array(array_data) = arr {
             let mut data = array_data.borrow_mut();
+            let last = data.elements.len().saturating_sub(1);
+            data.holes.remove(&last);
             Ok(data.elements.pop().unwrap_or(JsValue::undefined()))
         } else {
             Err(JsError::type_error("pop called on non-array"))
@@ -89,6 +206,7 @@ impl ArrayPrototype {
             if data.elements.is_empty() {
                 Ok(JsValue::undefined())
             } else {
+                data.reindex_holes(|i| i.checked_sub(1));
                 Ok(data.elements.remove(0))
             }
         } else {
@@ -101,6 +219,7 @@ impl ArrayPrototype {
         if let JsValue::Array(array_data) = arr {
             let mut data = array_data.borrow_mut();
             data.elements.insert(0, element);
+            data.reindex_holes(|i| Some(i + 1));
             Ok(JsValue::number(data.elements.len() as f64))
         } else {
             Err(JsError::type_error("unshift called on non-array"))
@@ -125,13 +244,12 @@ impl ArrayPrototype {
                 None => len as usize,
             };
 
-            let sliced: Vec<JsValue> = if start_idx < end_idx {
-                data.elements[start_idx..end_idx].to_vec()
-            } else {
-                Vec::new()
-            };
+            let sliced: Vec<Option<JsValue>> = (start_idx..end_idx)
+                .map(|i| (!data.is_hole(i)).then(|| data.elements[i].clone()))
+                .collect();
+            drop(data);
 
-            Ok(JsValue::array_from(sliced))
+            Self::species_create(arr, sliced.len(), sliced, true)
         } else {
             Err(JsError::type_error("slice called on non-array"))
         }
@@ -155,16 +273,29 @@ impl ArrayPrototype {
             };
 
             let actual_delete = delete_count.min(data.elements.len() - start_idx);
-
-            // Remove elements
-            let removed: Vec<JsValue> = data.elements.drain(start_idx..start_idx + actual_delete).collect();
+            let inserted = items.len();
+
+            // Remove elements, keeping their holes
+            let removed: Vec<Option<JsValue>> = (start_idx..start_idx + actual_delete)
+                .map(|i| (!data.is_hole(i)).then(|| data.elements[i].clone()))
+                .collect();
+            data.elements.drain(start_idx..start_idx + actual_delete);
+            data.reindex_holes(|i| {
+                if i < start_idx {
+                    Some(i)
+                } else if i < start_idx + actual_delete {
+                    None
+                } else {
+                    Some(i - actual_delete + inserted)
+                }
+            });
 
             // Insert new elements
             for (i, item) in items.into_iter().enumerate() {
                 data.elements.insert(start_idx + i, item);
             }
 
-            Ok(JsValue::array_from(removed))
+            Ok(JsValue::array_with_holes(removed))
         } else {
             Err(JsError::type_error("splice called on non-array"))
         }
@@ -177,11 +308,14 @@ impl ArrayPrototype {
     {
         if let JsValue::Array(array_data) = arr {
             let data = array_data.borrow();
-            let mut result = Vec::new();
-            for element in &data.elements {
-                result.push(callback(element.clone())?);
+            // Holes are skipped but kept, so the result has the same length
+            let mut result = vec![None; data.elements.len()];
+            for (i, element) in data.present() {
+                result[i] = Some(callback(element.clone())?);
             }
-            Ok(JsValue::array_from(result))
+            let len = data.elements.len();
+            drop(data);
+            Self::species_create(arr, len, result, false)
         } else {
             Err(JsError::type_error("map called on non-array"))
         }
@@ -195,34 +329,67 @@ impl ArrayPrototype {
         if let JsValue::Array(array_data) = arr {
             let data = array_data.borrow();
             let mut result = Vec::new();
-            for element in &data.elements {
+            for (_, element) in data.present() {
                 if callback(element)? {
-                    result.push(element.clone());
+                    result.push(Some(element.clone()));
                 }
             }
-            Ok(JsValue::array_from(result))
+            drop(data);
+            Self::species_create(arr, 0, result, false)
         } else {
             Err(JsError::type_error("filter called on non-array"))
         }
     }
 
     /// Array.prototype.reduce(callback, initialValue)
-    pub fn reduce<F>(arr: &JsValue, initial: JsValue, callback: F) -> JsResult<JsValue>
+    ///
+    /// Without an initial value the first element seeds the accumulator and
+    /// the fold starts at index 1; an empty array is then a TypeError.
+    pub fn reduce<F>(arr: &JsValue, initial: Option<JsValue>, callback: F) -> JsResult<JsValue>
     where
         F: Fn(JsValue, JsValue) -> JsResult<JsValue>,
     {
         if let JsValue::Array(array_data) = arr {
             let data = array_data.borrow();
-            let mut accumulator = initial;
-            for element in &data.elements {
-                accumulator = callback(accumulator, element.clone())?;
-            }
-            Ok(accumulator)
+            Self::fold(data.present().map(|(_, e)| e.clone()), initial, callback)
         } else {
             Err(JsError::type_error("reduce called on non-array"))
         }
     }
 
+    /// Array.prototype.reduceRight(callback, initialValue)
+    ///
+    /// Like [`ArrayPrototype::reduce`], but folds from the last element to the first.
+    pub fn reduce_right<F>(arr: &JsValue, initial: Option<JsValue>, callback: F) -> JsResult<JsValue>
+    where
+        F: Fn(JsValue, JsValue) -> JsResult<JsValue>,
+    {
+        if let JsValue::Array(array_data) = arr {
+            let data = array_data.borrow();
+            Self::fold(data.present().rev().map(|(_, e)| e.clone()), initial, callback)
+        } else {
+            Err(JsError::type_error("reduceRight called on non-array"))
+        }
+    }
+
+    /// Shared fold for reduce and reduceRight, visiting present elements in iteration order
+    fn fold<I, F>(mut elements: I, initial: Option<JsValue>, callback: F) -> JsResult<JsValue>
+    where
+        I: Iterator<Item = JsValue>,
+        F: Fn(JsValue, JsValue) -> JsResult<JsValue>,
+    {
+        let mut accumulator = match initial {
+            Some(value) => value,
+            None => elements
+                .next()
+                .ok_or_else(|| JsError::type_error("Reduce of empty array with no initial value"))?,
+        };
+        for element in elements {
+            accumulator = callback(accumulator, element)?;
+        }
+        Ok(accumulator)
+    }
+
     /// Array.prototype.forEach(callback)
     pub fn for_each<F>(arr: &JsValue, callback: F) -> JsResult<()>
     where
@@ -230,7 +397,7 @@ impl ArrayPrototype {
     {
         if let JsValue::Array(array_data) = arr {
             let data = array_data.borrow();
-            for element in &data.elements {
+            for (_, element) in data.present() {
                 callback(element)?;
             }
             Ok(())
@@ -289,7 +456,8 @@ impl ArrayPrototype {
     pub fn index_of(arr: &JsValue, value: &JsValue) -> Option<usize> {
         if let JsValue::Array(array_data) = arr {
             let data = array_data.borrow();
-            data.elements.iter().position(|e| e.equals(value))
+            let index = data.present().find(|(_, e)| e.equals(value)).map(|(i, _)| i);
+            index
         } else {
             None
         }
@@ -310,10 +478,17 @@ impl ArrayPrototype {
     pub fn sort(arr: &JsValue) -> JsResult<JsValue> {
         if let JsValue::Array(array_data) = arr {
             let mut data = array_data.borrow_mut();
+            let len = data.elements.len();
+            let mut values: Vec<JsValue> = data.present().map(|(_, e)| e.clone()).collect();
             // Sort by string conversion (default JS behavior)
-            data.elements.sort_by(|a, b| {
+            values.sort_by(|a, b| {
                 a.to_js_string().cmp(&b.to_js_string())
             });
+            // Holes sort after every present element
+            let present = values.len();
+            values.resize(len, JsValue::undefined());
+            data.elements = values;
+            data.holes = (present..len).collect();
             Ok(arr.clone())
         } else {
             Err(JsError::type_error("sort called on non-array"))
@@ -324,17 +499,150 @@ impl ArrayPrototype {
     pub fn reverse(arr: &JsValue) -> JsResult<JsValue> {
         if let JsValue::Array(array_data) = arr {
             let mut data = array_data.borrow_mut();
+            let last = data.elements.len().saturating_sub(1);
             data.elements.reverse();
+            data.reindex_holes(|i| Some(last - i));
             Ok(arr.clone())
         } else {
             Err(JsError::type_error("reverse called on non-array"))
         }
     }
+
+    /// Array.prototype.fill(value, start, end)
+    pub fn fill(arr: &JsValue, value: JsValue, start: i32, end: Option<i32>) -> JsResult<JsValue> {
+        if let JsValue::Array(array_data) = arr {
+            let mut data = array_data.borrow_mut();
+            let len = data.elements.len();
+            let start_idx = relative_index(start, len);
+            let end_idx = end.map_or(len, |e| relative_index(e, len));
+
+            if start_idx < end_idx {
+                for element in &mut data.elements[start_idx..end_idx] {
+                    *element = value.clone();
+                }
+                data.reindex_holes(|i| (i < start_idx || i >= end_idx).then_some(i));
+            }
+            Ok(arr.clone())
+        } else {
+            Err(JsError::type_error("fill called on non-array"))
+        }
+    }
+
+    /// Array.prototype.copyWithin(target, start, end)
+    pub fn copy_within(arr: &JsValue, target: i32, start: i32, end: Option<i32>) -> JsResult<JsValue> {
+        if let JsValue::Array(array_data) = arr {
+            let mut data = array_data.borrow_mut();
+            let len = data.elements.len();
+            let to = relative_index(target, len);
+            let from = relative_index(start, len);
+            let end_idx = end.map_or(len, |e| relative_index(e, len));
+
+            let count = end_idx.saturating_sub(from).min(len - to);
+            // Snapshot the source range first so overlapping copies read original values
+            let source: Vec<JsValue> = data.elements[from..from + count].to_vec();
+            let source_holes: Vec<bool> = (from..from + count).map(|i| data.is_hole(i)).collect();
+            data.elements[to..to + count].clone_from_slice(&source);
+            for (k, is_hole) in source_holes.into_iter().enumerate() {
+                if is_hole {
+                    data.holes.insert(to + k);
+                } else {
+                    data.holes.remove(&(to + k));
+                }
+            }
+            Ok(arr.clone())
+        } else {
+            Err(JsError::type_error("copyWithin called on non-array"))
+        }
+    }
+
+    /// Array.prototype.flat(depth)
+    ///
+    /// Holes are dropped at every level that is flattened.
+    pub fn flat(arr: &JsValue, depth: usize) -> JsResult<JsValue> {
+        if let JsValue::Array(_) = arr {
+            let mut result = Vec::new();
+            Self::flatten_into(arr, depth, &mut result);
+            Ok(JsValue::array_from(result))
+        } else {
+            Err(JsError::type_error("flat called on non-array"))
+        }
+    }
+
+    /// Array.prototype.flatMap(callback)
+    pub fn flat_map<F>(arr: &JsValue, callback: F) -> JsResult<JsValue>
+    where
+        F: Fn(JsValue) -> JsResult<JsValue>,
+    {
+        if let JsValue::Array(_) = arr {
+            let mapped = Self::map(arr, callback)?;
+            Self::flat(&mapped, 1)
+        } else {
+            Err(JsError::type_error("flatMap called on non-array"))
+        }
+    }
+
+    /// Append the present elements of `arr`, flattening nested arrays up to `depth`
+    fn flatten_into(arr: &JsValue, depth: usize, out: &mut Vec<JsValue>) {
+        if let JsValue::Array(array_data) = arr {
+            let data = array_data.borrow();
+            for (_, element) in data.present() {
+                if depth > 0 && element.is_array() {
+                    Self::flatten_into(element, depth - 1, out);
+                } else {
+                    out.push(element.clone());
+                }
+            }
+        }
+    }
+}
+
+/// Normalize a relative index (negative counts from the end) into `0..=len`
+fn relative_index(index: i32, len: usize) -> usize {
+    if index < 0 {
+        len.saturating_sub(index.unsigned_abs() as usize)
+    } else {
+        (index as usize).min(len)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::proxy::{ProxyHandler, ProxyObject};
+
+    #[test]
+    fn test_is_array() {
+        assert!(Array::is_array(&JsValue::array()).unwrap());
+        assert!(!Array::is_array(&JsValue::object()).unwrap());
+
+        let array_proxy = ProxyObject::new(JsValue::array(), ProxyHandler::default()).unwrap();
+        assert!(Array::is_array(&JsValue::Proxy(array_proxy)).unwrap());
+        let object_proxy = ProxyObject::new(JsValue::object(), ProxyHandler::default()).unwrap();
+        assert!(!Array::is_array(&JsValue::Proxy(object_proxy)).unwrap());
+    }
+
+    #[test]
+    fn test_map_uses_species_constructor() {
+        // A subclass whose Symbol.species builds plain objects instead of arrays
+        let subclass = JsValue::object();
+        subclass.set_symbol(
+            &SymbolConstructor::species(),
+            JsValue::constructor(|_| Ok(JsValue::object())),
+        );
+        let arr = JsValue::array_from(vec![JsValue::number(1.0), JsValue::number(2.0)]);
+        if let JsValue::Array(data) = &arr {
+            data.borrow_mut().constructor = Some(subclass);
+        }
+
+        let result = ArrayPrototype::map(&arr, |v| Ok(JsValue::number(v.as_number().unwrap() * 10.0))).unwrap();
+        assert!(result.is_object());
+        assert_eq!(result.get("0").unwrap().as_number(), Some(10.0));
+        assert_eq!(result.get("1").unwrap().as_number(), Some(20.0));
+
+        // Without a species the result is a plain Array
+        let plain = JsValue::array_from(vec![JsValue::number(1.0)]);
+        assert!(ArrayPrototype::map(&plain, Ok).unwrap().is_array());
+    }
 
     #[test]
     fn test_push() {
@@ -405,13 +713,60 @@ mod tests {
             JsValue::number(2.0),
             JsValue::number(3.0),
         ]);
-        let result = ArrayPrototype::reduce(&arr, JsValue::number(0.0), |acc, v| {
+        let result = ArrayPrototype::reduce(&arr, Some(JsValue::number(0.0)), |acc, v| {
+            Ok(JsValue::number(
+                acc.as_number().unwrap() + v.as_number().unwrap(),
+            ))
+        })
+        .unwrap();
+        assert_eq!(result.as_number().unwrap(), 6.0);
+    }
+
+    #[test]
+    fn test_reduce_empty_without_initial_throws() {
+        let arr = JsValue::array_from(vec![]);
+        let result = ArrayPrototype::reduce(&arr, None, |acc, _| Ok(acc));
+        assert!(result.is_err());
+
+        let seeded = ArrayPrototype::reduce(&arr, Some(JsValue::number(5.0)), |acc, _| Ok(acc));
+        assert_eq!(seeded.unwrap().as_number().unwrap(), 5.0);
+    }
+
+    #[test]
+    fn test_reduce_without_initial_seeds_with_first_element() {
+        let arr = JsValue::array_from(vec![
+            JsValue::number(1.0),
+            JsValue::number(2.0),
+            JsValue::number(3.0),
+        ]);
+        let calls = std::cell::Cell::new(0);
+        let result = ArrayPrototype::reduce(&arr, None, |acc, v| {
+            calls.set(calls.get() + 1);
             Ok(JsValue::number(
                 acc.as_number().unwrap() + v.as_number().unwrap(),
             ))
         })
         .unwrap();
         assert_eq!(result.as_number().unwrap(), 6.0);
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[test]
+    fn test_reduce_right_concatenates_in_reverse() {
+        let arr = JsValue::array_from(vec![
+            JsValue::string("a"),
+            JsValue::string("b"),
+            JsValue::string("c"),
+        ]);
+        let result = ArrayPrototype::reduce_right(&arr, None, |acc, v| {
+            Ok(JsValue::string(format!(
+                "{}{}",
+                acc.as_string().unwrap(),
+                v.as_string().unwrap()
+            )))
+        })
+        .unwrap();
+        assert_eq!(result.as_string().unwrap(), "cba");
     }
 
     #[test]
@@ -441,4 +796,115 @@ mod tests {
         let result = ArrayPrototype::join(&arr, ",").unwrap();
         assert_eq!(result, "a,b,c");
     }
+
+    fn numbers(arr: &JsValue) -> Vec<f64> {
+        match arr {
+            JsValue::Array(data) => data
+                .borrow()
+                .elements
+                .iter()
+                .map(|v| v.as_number().unwrap())
+                .collect(),
+            _ => panic!("expected array"),
+        }
+    }
+
+    fn array_of(values: &[f64]) -> JsValue {
+        JsValue::array_from(values.iter().map(|&n| JsValue::number(n)).collect())
+    }
+
+    #[test]
+    fn test_fill_range() {
+        let arr = array_of(&[0.0, 0.0, 0.0, 0.0, 0.0]);
+        ArrayPrototype::fill(&arr, JsValue::number(5.0), 1, Some(3)).unwrap();
+        assert_eq!(numbers(&arr), vec![0.0, 5.0, 5.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_fill_negative_indices() {
+        let arr = array_of(&[0.0, 0.0, 0.0, 0.0, 0.0]);
+        ArrayPrototype::fill(&arr, JsValue::number(1.0), -3, Some(-1)).unwrap();
+        assert_eq!(numbers(&arr), vec![0.0, 0.0, 1.0, 1.0, 0.0]);
+
+        ArrayPrototype::fill(&arr, JsValue::number(2.0), -10, None).unwrap();
+        assert_eq!(numbers(&arr), vec![2.0; 5]);
+    }
+
+    #[test]
+    fn test_copy_within_overlapping() {
+        let arr = array_of(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+        ArrayPrototype::copy_within(&arr, 1, 0, Some(3)).unwrap();
+        assert_eq!(numbers(&arr), vec![1.0, 1.0, 2.0, 3.0, 5.0]);
+
+        let arr = array_of(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+        ArrayPrototype::copy_within(&arr, 0, 3, None).unwrap();
+        assert_eq!(numbers(&arr), vec![4.0, 5.0, 3.0, 4.0, 5.0]);
+
+        let arr = array_of(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+        ArrayPrototype::copy_within(&arr, -2, -4, Some(-3)).unwrap();
+        assert_eq!(numbers(&arr), vec![1.0, 2.0, 3.0, 2.0, 5.0]);
+    }
+
+    fn sparse() -> JsValue {
+        JsValue::array_with_holes(vec![Some(JsValue::number(1.0)), None, Some(JsValue::number(3.0))])
+    }
+
+    #[test]
+    fn test_map_preserves_holes_and_length() {
+        let calls = std::cell::Cell::new(0);
+        let result = ArrayPrototype::map(&sparse(), |v| {
+            calls.set(calls.get() + 1);
+            Ok(JsValue::number(v.as_number().unwrap() * 10.0))
+        })
+        .unwrap();
+        assert_eq!(calls.get(), 2);
+        assert_eq!(result.array_length(), 3);
+        if let JsValue::Array(data) = &result {
+            let data = data.borrow();
+            assert!(data.is_hole(1));
+            assert!(!data.is_hole(0) && !data.is_hole(2));
+            assert_eq!(data.elements[2].as_number(), Some(30.0));
+        }
+    }
+
+    #[test]
+    fn test_callbacks_skip_holes() {
+        let visited = std::cell::RefCell::new(Vec::new());
+        ArrayPrototype::for_each(&sparse(), |v| {
+            visited.borrow_mut().push(v.as_number().unwrap());
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(*visited.borrow(), vec![1.0, 3.0]);
+
+        let filtered = ArrayPrototype::filter(&sparse(), |_| Ok(true)).unwrap();
+        assert_eq!(numbers(&filtered), vec![1.0, 3.0]);
+
+        let sum = ArrayPrototype::reduce(&sparse(), None, |acc, v| {
+            Ok(JsValue::number(acc.as_number().unwrap() + v.as_number().unwrap()))
+        })
+        .unwrap();
+        assert_eq!(sum.as_number(), Some(4.0));
+    }
+
+    #[test]
+    fn test_flat_drops_holes() {
+        let nested = JsValue::array_with_holes(vec![
+            Some(JsValue::number(0.0)),
+            None,
+            Some(sparse()),
+            Some(array_of(&[4.0])),
+        ]);
+        let flat = ArrayPrototype::flat(&nested, 1).unwrap();
+        assert_eq!(numbers(&flat), vec![0.0, 1.0, 3.0, 4.0]);
+
+        let shallow = ArrayPrototype::flat(&nested, 0).unwrap();
+        assert_eq!(shallow.array_length(), 3);
+
+        let doubled = ArrayPrototype::flat_map(&array_of(&[1.0, 2.0]), |v| {
+            Ok(JsValue::array_from(vec![v.clone(), v]))
+        })
+        .unwrap();
+        assert_eq!(numbers(&doubled), vec![1.0, 1.0, 2.0, 2.0]);
+    }
 }
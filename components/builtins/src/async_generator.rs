@@ -255,6 +255,11 @@ impl AsyncGeneratorObject {
         }
     }
 
+    /// Whether both handles refer to the same async generator instance
+    pub fn ptr_eq(&self, other: &AsyncGeneratorObject) -> bool {
+        Rc::ptr_eq(&self.data, &other.data)
+    }
+
     /// Get the current state
     pub fn state(&self) -> AsyncGeneratorState {
         self.data.borrow().state
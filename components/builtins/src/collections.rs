@@ -635,6 +635,57 @@ mod tests {
             assert_eq!(value.as_string().unwrap(), "zero");
         }
 
+        #[test]
+        fn test_map_object_keys_use_identity() {
+            let map = MapObject::new();
+            let a = JsValue::object();
+            let b = JsValue::object();
+            MapObject::set(&map, a.clone(), JsValue::string("a"));
+            MapObject::set(&map, b.clone(), JsValue::string("b"));
+
+            // Structurally equal but distinct objects are different keys
+            assert_eq!(MapObject::size(&map), 2);
+            assert_eq!(MapObject::get(&map, &a).unwrap().as_string().unwrap(), "a");
+            assert_eq!(MapObject::get(&map, &b).unwrap().as_string().unwrap(), "b");
+            assert!(!MapObject::has(&map, &JsValue::object()));
+        }
+
+        #[test]
+        fn test_map_proxy_and_generator_keys_use_identity() {
+            use crate::iterator::GeneratorObject;
+            use crate::proxy::{ProxyHandler, ProxyObject};
+
+            let map = MapObject::new();
+            let proxy = JsValue::Proxy(
+                ProxyObject::new(JsValue::object(), ProxyHandler::default()).unwrap(),
+            );
+            let generator = JsValue::Generator(GeneratorObject::new(vec![]));
+            MapObject::set(&map, proxy.clone(), JsValue::number(1.0));
+            MapObject::set(&map, generator.clone(), JsValue::number(2.0));
+            MapObject::set(&map, proxy.clone(), JsValue::number(3.0));
+
+            assert_eq!(MapObject::size(&map), 2);
+            assert_eq!(MapObject::get(&map, &proxy).unwrap().as_number().unwrap(), 3.0);
+            assert!(MapObject::has(&map, &generator));
+            assert!(!MapObject::has(&map, &JsValue::Generator(GeneratorObject::new(vec![]))));
+        }
+
+        #[test]
+        fn test_map_primitive_keys_follow_same_value_zero() {
+            let map = MapObject::new();
+            MapObject::set(&map, JsValue::number(f64::NAN), JsValue::string("first nan"));
+            MapObject::set(&map, JsValue::number((-1.0f64).sqrt()), JsValue::string("second nan"));
+            MapObject::set(&map, JsValue::number(0.0), JsValue::string("+0"));
+            MapObject::set(&map, JsValue::number(-0.0), JsValue::string("-0"));
+
+            assert_eq!(MapObject::size(&map), 2);
+            let nan = MapObject::get(&map, &JsValue::number(f64::NAN)).unwrap();
+            assert_eq!(nan.as_string().unwrap(), "second nan");
+            let zero = MapObject::get(&map, &JsValue::number(0.0)).unwrap();
+            assert_eq!(zero.as_string().unwrap(), "-0");
+            assert!(!MapObject::has(&map, &JsValue::string("0")));
+        }
+
         #[test]
         fn test_map_update_preserves_order() {
             let map = MapObject::new();
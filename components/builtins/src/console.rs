@@ -1,17 +1,31 @@
 //! Console object methods
 
-use crate::value::JsValue;
+use crate::value::{ArrayData, JsValue, ObjectData};
+use core_types::{ObjectView, Value, MAX_ARRAY_ITEMS};
+use std::any::Any;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
 use std::time::Instant;
 
+/// Default depth to which `console.dir` expands nested objects
+pub const DEFAULT_DIR_DEPTH: usize = 2;
+
 /// Console output writer trait
 pub trait ConsoleWriter {
     /// Write a message to the console output
     fn write(&self, message: &str);
 }
 
+/// Observer notified of every console API call
+///
+/// Lets tools such as a debugger front-end see the original argument values
+/// rather than the formatted text sent to the writer.
+pub trait ConsoleObserver {
+    /// Called with the CDP call type ("log", "warning", ...) and the arguments
+    fn console_api_called(&self, call_type: &str, args: &[JsValue]);
+}
+
 /// Default console writer that prints to stdout
 struct StdoutWriter;
 
@@ -26,6 +40,7 @@ pub struct ConsoleObject {
     output: Rc<RefCell<Vec<String>>>,
     timers: RefCell<HashMap<String, Instant>>,
     writer: Box<dyn ConsoleWriter>,
+    observer: Option<Rc<dyn ConsoleObserver>>,
 }
 
 impl ConsoleObject {
@@ -35,6 +50,7 @@ impl ConsoleObject {
             output: Rc::new(RefCell::new(Vec::new())),
             timers: RefCell::new(HashMap::new()),
             writer: Box::new(StdoutWriter),
+            observer: None,
         }
     }
 
@@ -45,6 +61,18 @@ impl ConsoleObject {
             output,
             timers: RefCell::new(HashMap::new()),
             writer: Box::new(CaptureWriter { output: captured_output }),
+            observer: None,
+        }
+    }
+
+    /// Set (or clear) the observer notified of each console call
+    pub fn set_observer(&mut self, observer: Option<Rc<dyn ConsoleObserver>>) {
+        self.observer = observer;
+    }
+
+    fn notify(&self, call_type: &str, values: &[JsValue]) {
+        if let Some(observer) = &self.observer {
+            observer.console_api_called(call_type, values);
         }
     }
 
@@ -59,6 +87,7 @@ impl ConsoleObject {
 
     /// console.log(...values)
     pub fn log(&self, values: &[JsValue]) {
+        self.notify("log", values);
         let message = Self::format_values(values);
         self.output.borrow_mut().push(message.clone());
         self.writer.write(&message);
@@ -66,6 +95,7 @@ impl ConsoleObject {
 
     /// console.error(...values)
     pub fn error(&self, values: &[JsValue]) {
+        self.notify("error", values);
         let message = format!("Error: {}", Self::format_values(values));
         self.output.borrow_mut().push(message.clone());
         self.writer.write(&message);
@@ -73,6 +103,7 @@ impl ConsoleObject {
 
     /// console.warn(...values)
     pub fn warn(&self, values: &[JsValue]) {
+        self.notify("warning", values);
         let message = format!("Warning: {}", Self::format_values(values));
         self.output.borrow_mut().push(message.clone());
         self.writer.write(&message);
@@ -80,6 +111,7 @@ impl ConsoleObject {
 
     /// console.info(...values)
     pub fn info(&self, values: &[JsValue]) {
+        self.notify("info", values);
         let message = format!("Info: {}", Self::format_values(values));
         self.output.borrow_mut().push(message.clone());
         self.writer.write(&message);
@@ -87,18 +119,44 @@ impl ConsoleObject {
 
     /// console.debug(...values)
     pub fn debug(&self, values: &[JsValue]) {
+        self.notify("debug", values);
         let message = format!("Debug: {}", Self::format_values(values));
         self.output.borrow_mut().push(message.clone());
         self.writer.write(&message);
     }
 
-    /// console.assert(condition, message)
-    pub fn assert(&self, condition: bool, message: &str) {
-        if !condition {
-            let msg = format!("Assertion failed: {}", message);
-            self.output.borrow_mut().push(msg.clone());
-            self.writer.write(&msg);
+    /// console.assert(condition, ...values)
+    ///
+    /// Writes "Assertion failed" and the values when
+    /// `condition` is falsy; does nothing otherwise.
+    pub fn assert(&self, condition: &JsValue, values: &[JsValue]) {
+        if condition.to_boolean() {
+            return;
         }
+        let message = if values.is_empty() {
+            "Assertion failed".to_string()
+        } else {
+            format!("Assertion failed: {}", Self::format_values(values))
+        };
+        self.notify("assert", values);
+        self.output.borrow_mut().push(message.clone());
+        self.writer.write(&message);
+    }
+
+    /// console.dir(value, { depth })
+    ///
+    /// Inspects `value`, expanding nested objects and arrays up to `depth`
+    /// levels ([`DEFAULT_DIR_DEPTH`] when absent, unlimited when `null`).
+    pub fn dir(&self, value: &JsValue, options: Option<&JsValue>) {
+        let depth = match options.and_then(|options| options.get("depth")) {
+            Some(JsValue::Null) => usize::MAX,
+            Some(JsValue::Number(n)) if n >= 0.0 => n as usize,
+            _ => DEFAULT_DIR_DEPTH,
+        };
+        self.notify("dir", std::slice::from_ref(value));
+        let message = inspect(value, depth);
+        self.output.borrow_mut().push(message.clone());
+        self.writer.write(&message);
     }
 
     /// console.time(label)
@@ -117,6 +175,44 @@ impl ConsoleObject {
     }
 }
 
+/// Render `value` for `console.dir` with the shared [`core_types`]
+/// inspector, expanding objects and arrays up to `depth` levels
+fn inspect(value: &JsValue, depth: usize) -> String {
+    to_core_value(value).inspect_with(depth, inspect_payload)
+}
+
+/// Map a builtins value onto [`Value`] for rendering; objects and arrays
+/// keep their allocation so cycles are still detected
+fn to_core_value(value: &JsValue) -> Value {
+    match value {
+        JsValue::Undefined => Value::Undefined,
+        JsValue::Null => Value::Null,
+        JsValue::Boolean(b) => Value::Boolean(*b),
+        JsValue::Number(n) => Value::Double(*n),
+        JsValue::Object(obj) => Value::NativeObject(obj.clone()),
+        JsValue::Array(arr) => Value::NativeObject(arr.clone()),
+        other => Value::String(other.to_js_string()),
+    }
+}
+
+fn inspect_payload(payload: &dyn Any) -> Option<ObjectView> {
+    if let Some(obj) = payload.downcast_ref::<ObjectData>() {
+        // Property storage is unordered, so sort keys for stable output
+        let mut entries: Vec<(String, Value)> = obj
+            .properties
+            .iter()
+            .map(|(key, v)| (key.clone(), to_core_value(v)))
+            .collect();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        Some(ObjectView::Object(entries))
+    } else {
+        let arr = payload.downcast_ref::<ArrayData>()?;
+        let items = arr.elements.iter().take(MAX_ARRAY_ITEMS);
+        let elements: Vec<Value> = items.map(to_core_value).collect();
+        Some(ObjectView::Array { elements, length: arr.elements.len() })
+    }
+}
+
 impl Default for ConsoleObject {
     fn default() -> Self {
         Self::new()
@@ -206,7 +302,7 @@ mod tests {
         let output = Rc::new(RefCell::new(Vec::new()));
         let console = ConsoleObject::new_with_output(output.clone());
 
-        console.assert(true, "should not print");
+        console.assert(&JsValue::boolean(true), &[JsValue::string("should not print")]);
 
         assert_eq!(output.borrow().len(), 0);
     }
@@ -216,12 +312,62 @@ mod tests {
         let output = Rc::new(RefCell::new(Vec::new()));
         let console = ConsoleObject::new_with_output(output.clone());
 
-        console.assert(false, "assertion failed");
+        console.assert(&JsValue::boolean(false), &[JsValue::string("assertion failed")]);
 
         assert!(output.borrow().len() > 0);
         assert!(output.borrow()[0].contains("assertion failed"));
     }
 
+    #[test]
+    fn test_assert_uses_truthiness_and_formats_values() {
+        let output = Rc::new(RefCell::new(Vec::new()));
+        let console = ConsoleObject::new_with_output(output.clone());
+
+        console.assert(&JsValue::string("non-empty"), &[JsValue::string("hidden")]);
+        console.assert(&JsValue::number(1.0), &[]);
+        assert!(output.borrow().is_empty());
+
+        console.assert(&JsValue::number(0.0), &[JsValue::string("x is"), JsValue::number(0.0)]);
+        console.assert(&JsValue::Undefined, &[]);
+        assert_eq!(*output.borrow(), vec!["Assertion failed: x is 0", "Assertion failed"]);
+    }
+
+    #[test]
+    fn test_dir_depth_truncates_nested_objects() {
+        let output = Rc::new(RefCell::new(Vec::new()));
+        let console = ConsoleObject::new_with_output(output.clone());
+
+        let inner = JsValue::object();
+        inner.set("deep", JsValue::boolean(true));
+        let middle = JsValue::object();
+        middle.set("inner", inner);
+        let outer = JsValue::object();
+        outer.set("middle", middle);
+        outer.set("list", JsValue::array_from(vec![JsValue::number(1.0), JsValue::string("a")]));
+
+        let options = JsValue::object();
+        options.set("depth", JsValue::number(1.0));
+        console.dir(&outer, Some(&options));
+        console.dir(&outer, None);
+
+        assert_eq!(output.borrow()[0], "{ list: [ 1, 'a' ], middle: { inner: [Object] } }");
+        assert_eq!(output.borrow()[1], "{ list: [ 1, 'a' ], middle: { inner: { deep: true } } }");
+    }
+
+    #[test]
+    fn test_dir_marks_circular_references() {
+        let output = Rc::new(RefCell::new(Vec::new()));
+        let console = ConsoleObject::new_with_output(output.clone());
+
+        let obj = JsValue::object();
+        obj.set("self", obj.clone());
+        console.dir(&obj, None);
+
+        assert_eq!(output.borrow()[0], "{ self: [Circular] }");
+        // Break the cycle so the test does not leak
+        obj.set("self", JsValue::Null);
+    }
+
     #[test]
     fn test_time_and_time_end() {
         let output = Rc::new(RefCell::new(Vec::new()));
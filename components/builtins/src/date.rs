@@ -9,6 +9,7 @@
 use chrono::{
     Datelike, Local, NaiveDate, NaiveDateTime, TimeZone, Timelike, Utc, FixedOffset,
 };
+use crate::value::{JsError, JsResult};
 use std::cell::RefCell;
 use std::rc::Rc;
 
@@ -37,11 +38,7 @@ impl JsDate {
 
     /// Create a Date from milliseconds since epoch
     pub fn from_timestamp(ms: f64) -> Self {
-        let time_value = if ms.is_nan() || ms.is_infinite() {
-            f64::NAN
-        } else {
-            ms.trunc()
-        };
+        let time_value = time_clip(ms);
         JsDate {
             inner: Rc::new(RefCell::new(DateObject { time_value })),
         }
@@ -100,6 +97,30 @@ impl JsDate {
         }
     }
 
+    /// Create a Date from local-time components that may be out of range
+    ///
+    /// Components carry into the next larger unit as MakeDay and MakeTime
+    /// specify: month 12 is January of the next year and day 0 the last day
+    /// of the previous month. Years 0-99 map to 1900-1999, and times more
+    /// than 8.64e15 ms from the epoch give an invalid date.
+    pub fn from_local_components(
+        year: f64,
+        month: f64,
+        day: f64,
+        hours: f64,
+        minutes: f64,
+        seconds: f64,
+        ms: f64,
+    ) -> Self {
+        let year = if (0.0..100.0).contains(&year.trunc()) {
+            1900.0 + year.trunc()
+        } else {
+            year
+        };
+        let local = make_date(make_day(year, month, day), make_time(hours, minutes, seconds, ms));
+        JsDate::from_timestamp(local_to_utc(local))
+    }
+
     /// Parse an ISO 8601 date string
     pub fn from_string(s: &str) -> Self {
         let time_value = DateConstructor::parse(s);
@@ -124,11 +145,7 @@ impl JsDate {
 
     /// Set the internal time value
     pub fn set_time(&self, ms: f64) -> f64 {
-        let time_value = if ms.is_nan() || ms.is_infinite() {
-            f64::NAN
-        } else {
-            ms.trunc()
-        };
+        let time_value = time_clip(ms);
         self.inner.borrow_mut().time_value = time_value;
         time_value
     }
@@ -145,7 +162,7 @@ impl JsDate {
             return None;
         }
         let secs = (ms / 1000.0).floor() as i64;
-        let nsecs = ((ms % 1000.0) * 1_000_000.0) as u32;
+        let nsecs = (ms.rem_euclid(1000.0) * 1_000_000.0) as u32;
         chrono::DateTime::from_timestamp(secs, nsecs).map(|dt| dt.naive_utc())
     }
 
@@ -523,30 +540,8 @@ impl JsDate {
     pub fn to_string(&self) -> String {
         if let Some(dt) = self.to_local_datetime() {
             // Format: "Tue Nov 05 2024 14:30:00 GMT+0000 (Coordinated Universal Time)"
-            let day_name = match dt.weekday() {
-                chrono::Weekday::Mon => "Mon",
-                chrono::Weekday::Tue => "Tue",
-                chrono::Weekday::Wed => "Wed",
-                chrono::Weekday::Thu => "Thu",
-                chrono::Weekday::Fri => "Fri",
-                chrono::Weekday::Sat => "Sat",
-                chrono::Weekday::Sun => "Sun",
-            };
-            let month_name = match dt.month() {
-                1 => "Jan",
-                2 => "Feb",
-                3 => "Mar",
-                4 => "Apr",
-                5 => "May",
-                6 => "Jun",
-                7 => "Jul",
-                8 => "Aug",
-                9 => "Sep",
-                10 => "Oct",
-                11 => "Nov",
-                12 => "Dec",
-                _ => "???",
-            };
+            let day_name = weekday_name(dt.weekday());
+            let month_name = month_name(dt.month());
             let offset_seconds = dt.offset().local_minus_utc();
             let offset_hours = offset_seconds.abs() / 3600;
             let offset_minutes = (offset_seconds.abs() % 3600) / 60;
@@ -573,30 +568,8 @@ impl JsDate {
     /// Convert to date string (like JavaScript's Date.prototype.toDateString)
     pub fn to_date_string(&self) -> String {
         if let Some(dt) = self.to_local_datetime() {
-            let day_name = match dt.weekday() {
-                chrono::Weekday::Mon => "Mon",
-                chrono::Weekday::Tue => "Tue",
-                chrono::Weekday::Wed => "Wed",
-                chrono::Weekday::Thu => "Thu",
-                chrono::Weekday::Fri => "Fri",
-                chrono::Weekday::Sat => "Sat",
-                chrono::Weekday::Sun => "Sun",
-            };
-            let month_name = match dt.month() {
-                1 => "Jan",
-                2 => "Feb",
-                3 => "Mar",
-                4 => "Apr",
-                5 => "May",
-                6 => "Jun",
-                7 => "Jul",
-                8 => "Aug",
-                9 => "Sep",
-                10 => "Oct",
-                11 => "Nov",
-                12 => "Dec",
-                _ => "???",
-            };
+            let day_name = weekday_name(dt.weekday());
+            let month_name = month_name(dt.month());
             format!("{} {} {:02} {:04}", day_name, month_name, dt.day(), dt.year())
         } else {
             "Invalid Date".to_string()
@@ -626,28 +599,60 @@ impl JsDate {
     }
 
     /// Convert to ISO 8601 string (like JavaScript's Date.prototype.toISOString)
-    pub fn to_iso_string(&self) -> Result<String, String> {
+    ///
+    /// Years outside 0000-9999 use the six-digit extended form, e.g.
+    /// `+010000-01-01T00:00:00.000Z`. Throws a RangeError for invalid dates.
+    pub fn to_iso_string(&self) -> JsResult<String> {
+        let dt = self
+            .to_utc_datetime()
+            .ok_or_else(|| JsError::range_error("Invalid time value"))?;
+        let year = match dt.year() {
+            year @ 0..=9999 => format!("{:04}", year),
+            year if year < 0 => format!("-{:06}", -year),
+            year => format!("+{:06}", year),
+        };
+        Ok(format!(
+            "{}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+            year,
+            dt.month(),
+            dt.day(),
+            dt.hour(),
+            dt.minute(),
+            dt.second(),
+            dt.and_utc().timestamp_subsec_millis()
+        ))
+    }
+
+    /// Convert to JSON (same as toISOString or null for invalid)
+    pub fn to_json(&self) -> Option<String> {
+        self.to_iso_string().ok()
+    }
+
+    /// Convert to the RFC 7231 / RFC 1123 HTTP date form (like
+    /// JavaScript's Date.prototype.toUTCString), e.g.
+    /// `Thu, 15 Jun 2023 12:30:00 GMT`
+    pub fn to_utc_string(&self) -> String {
         if let Some(dt) = self.to_utc_datetime() {
-            Ok(format!(
-                "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
-                dt.year(),
-                dt.month(),
+            let year = if dt.year() < 0 {
+                format!("-{:04}", -dt.year())
+            } else {
+                format!("{:04}", dt.year())
+            };
+            format!(
+                "{}, {:02} {} {} {:02}:{:02}:{:02} GMT",
+                weekday_name(dt.weekday()),
                 dt.day(),
+                month_name(dt.month()),
+                year,
                 dt.hour(),
                 dt.minute(),
-                dt.second(),
-                (self.get_milliseconds() as u32)
-            ))
+                dt.second()
+            )
         } else {
-            Err("Invalid Date".to_string())
+            "Invalid Date".to_string()
         }
     }
 
-    /// Convert to JSON (same as toISOString or null for invalid)
-    pub fn to_json(&self) -> Option<String> {
-        self.to_iso_string().ok()
-    }
-
     /// Convert to locale string (simplified implementation)
     pub fn to_locale_string(&self) -> String {
         if let Some(dt) = self.to_local_datetime() {
@@ -689,6 +694,107 @@ impl JsDate {
     }
 }
 
+/// Three-letter English day name used by the Date string forms
+/// Milliseconds in a day
+const MS_PER_DAY: f64 = 86_400_000.0;
+
+/// Days since the epoch of the first of `month` (0-based) in `year`, in the
+/// proleptic Gregorian calendar
+fn days_from_civil(year: i64, month: i64) -> i64 {
+    let year = if month < 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 10) % 12) + 2) / 5;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// MakeDay: days since the epoch, with month and date carried into the year
+fn make_day(year: f64, month: f64, date: f64) -> f64 {
+    if !(year.is_finite() && month.is_finite() && date.is_finite()) {
+        return f64::NAN;
+    }
+    let year = year.trunc() + (month.trunc() / 12.0).floor();
+    // Far enough out that TimeClip rejects the result anyway
+    if year.abs() > 1_000_000.0 {
+        return f64::NAN;
+    }
+    let month = month.trunc().rem_euclid(12.0);
+    days_from_civil(year as i64, month as i64) as f64 + date.trunc() - 1.0
+}
+
+/// MakeTime: milliseconds into a day, with each unit allowed to overflow
+fn make_time(hours: f64, minutes: f64, seconds: f64, ms: f64) -> f64 {
+    if !(hours.is_finite() && minutes.is_finite() && seconds.is_finite() && ms.is_finite()) {
+        return f64::NAN;
+    }
+    hours.trunc() * 3_600_000.0 + minutes.trunc() * 60_000.0 + seconds.trunc() * 1_000.0
+        + ms.trunc()
+}
+
+/// MakeDate: combine a day number and a time within the day
+fn make_date(day: f64, time: f64) -> f64 {
+    let date = day * MS_PER_DAY + time;
+    if date.is_finite() { date } else { f64::NAN }
+}
+
+/// TimeClip: times more than 8.64e15 ms from the epoch are invalid
+fn time_clip(time: f64) -> f64 {
+    if time.is_finite() && time.abs() <= 8.64e15 {
+        time.trunc() + 0.0
+    } else {
+        f64::NAN
+    }
+}
+
+/// Convert a local time value to UTC using the local offset at that time
+fn local_to_utc(local: f64) -> f64 {
+    if local.abs() > 8.64e15 + MS_PER_DAY || local.is_nan() {
+        return f64::NAN;
+    }
+    let Some(naive) = chrono::DateTime::from_timestamp_millis(local as i64) else {
+        return f64::NAN;
+    };
+    let naive = naive.naive_utc();
+    // In a gap the offset from before the transition applies
+    let offset = match Local.from_local_datetime(&naive).earliest() {
+        Some(dt) => dt.offset().local_minus_utc(),
+        None => Local.from_utc_datetime(&naive).offset().local_minus_utc(),
+    };
+    local - offset as f64 * 1000.0
+}
+
+fn weekday_name(weekday: chrono::Weekday) -> &'static str {
+    match weekday {
+        chrono::Weekday::Mon => "Mon",
+        chrono::Weekday::Tue => "Tue",
+        chrono::Weekday::Wed => "Wed",
+        chrono::Weekday::Thu => "Thu",
+        chrono::Weekday::Fri => "Fri",
+        chrono::Weekday::Sat => "Sat",
+        chrono::Weekday::Sun => "Sun",
+    }
+}
+
+/// Three-letter English month name for a 1-based month
+fn month_name(month: u32) -> &'static str {
+    match month {
+        1 => "Jan",
+        2 => "Feb",
+        3 => "Mar",
+        4 => "Apr",
+        5 => "May",
+        6 => "Jun",
+        7 => "Jul",
+        8 => "Aug",
+        9 => "Sep",
+        10 => "Oct",
+        11 => "Nov",
+        12 => "Dec",
+        _ => "???",
+    }
+}
+
 impl Default for JsDate {
     fn default() -> Self {
         JsDate::new()
@@ -1022,6 +1128,30 @@ mod tests {
         assert!(date.get_month().is_nan());
     }
 
+    #[test]
+    fn test_from_local_components_carries_out_of_range_fields() {
+        let local = |y, mo, d, h| {
+            let date = JsDate::from_local_components(y, mo, d, h, 0.0, 0.0, 0.0);
+            (date.get_full_year(), date.get_month(), date.get_date(), date.get_hours())
+        };
+        assert_eq!(local(2020.0, 0.0, 0.0, 0.0), (2019.0, 11.0, 31.0, 0.0));
+        assert_eq!(local(2020.0, -1.0, 1.0, 0.0), (2019.0, 11.0, 1.0, 0.0));
+        assert_eq!(local(2020.0, 13.0, 1.0, 0.0), (2021.0, 1.0, 1.0, 0.0));
+        assert_eq!(local(2020.0, 0.0, 1.0, 25.0), (2020.0, 0.0, 2.0, 1.0));
+        assert_eq!(local(99.0, 0.0, 1.0, 0.0), (1999.0, 0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn test_time_clip() {
+        assert_eq!(JsDate::from_timestamp(8.64e15).get_time(), 8.64e15);
+        assert_eq!(JsDate::from_timestamp(-8.64e15).get_time(), -8.64e15);
+        assert!(JsDate::from_timestamp(8.64e15 + 1.0).get_time().is_nan());
+        assert!(JsDate::new().set_time(-8.64e15 - 1.0).is_nan());
+        assert!(JsDate::from_local_components(275_761.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0)
+            .get_time()
+            .is_nan());
+    }
+
     // ===== Static Method Tests =====
 
     #[test]
@@ -1252,6 +1382,36 @@ mod tests {
         let date = JsDate::invalid();
         let result = date.to_iso_string();
         assert!(result.is_err());
+        assert!(result.unwrap_err().message.starts_with("RangeError"));
+    }
+
+    #[test]
+    fn test_to_iso_string_known_epoch() {
+        let date = JsDate::from_timestamp(1686832200000.0);
+        assert_eq!(date.to_iso_string().unwrap(), "2023-06-15T12:30:00.000Z");
+    }
+
+    #[test]
+    fn test_to_iso_string_extended_years() {
+        let date = JsDate::from_timestamp(253402300800000.0);
+        assert_eq!(date.to_iso_string().unwrap(), "+010000-01-01T00:00:00.000Z");
+        let date = JsDate::from_timestamp(-62198755200000.0);
+        assert_eq!(date.to_iso_string().unwrap(), "-000001-01-01T00:00:00.000Z");
+    }
+
+    #[test]
+    fn test_to_iso_string_before_epoch() {
+        let date = JsDate::from_timestamp(-1.0);
+        assert_eq!(date.to_iso_string().unwrap(), "1969-12-31T23:59:59.999Z");
+    }
+
+    #[test]
+    fn test_to_utc_string() {
+        let date = JsDate::from_timestamp(1686832200000.0);
+        assert_eq!(date.to_utc_string(), "Thu, 15 Jun 2023 12:30:00 GMT");
+        let date = JsDate::from_timestamp(0.0);
+        assert_eq!(date.to_utc_string(), "Thu, 01 Jan 1970 00:00:00 GMT");
+        assert_eq!(JsDate::invalid().to_utc_string(), "Invalid Date");
     }
 
     #[test]
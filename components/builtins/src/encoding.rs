@@ -0,0 +1,364 @@
+//! TextEncoder and TextDecoder implementation
+//!
+//! This module provides the WHATWG Encoding API's `TextEncoder`, which turns
+//! strings into UTF-8 bytes, and `TextDecoder`, which turns bytes held in a
+//! TypedArray back into a string.
+
+use crate::typed_arrays::{ArrayBuffer, TypedArray, TypedArrayKind};
+use crate::value::{JsError, JsResult};
+
+/// TextEncoder - encodes strings as UTF-8
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TextEncoder;
+
+impl TextEncoder {
+    /// Create a new TextEncoder
+    pub fn new() -> Self {
+        TextEncoder
+    }
+
+    /// The encoding used by this encoder, which is always "utf-8"
+    pub fn encoding(&self) -> &'static str {
+        "utf-8"
+    }
+
+    /// Encode `input` as UTF-8, returning a Uint8Array of the bytes
+    pub fn encode(&self, input: &str) -> JsResult<TypedArray> {
+        let bytes = input.as_bytes().to_vec();
+        let length = bytes.len();
+        TypedArray::from_buffer(
+            ArrayBuffer::from_bytes(bytes),
+            TypedArrayKind::Uint8,
+            None,
+            Some(length),
+        )
+    }
+}
+
+/// Encodings understood by TextDecoder
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextEncoding {
+    /// UTF-8
+    Utf8,
+    /// UTF-16, little-endian
+    Utf16Le,
+    /// UTF-16, big-endian
+    Utf16Be,
+}
+
+impl TextEncoding {
+    /// Resolve an encoding label such as "utf8" or "UTF-16LE"
+    ///
+    /// Labels are matched case-insensitively after trimming whitespace.
+    pub fn from_label(label: &str) -> Option<Self> {
+        match label.trim().to_ascii_lowercase().as_str() {
+            "utf-8" | "utf8" | "unicode-1-1-utf-8" | "unicode11utf8" | "unicode20utf8"
+            | "x-unicode20utf8" => Some(TextEncoding::Utf8),
+            "utf-16le" | "utf-16" | "ucs-2" | "unicode" | "unicodefeff" | "csunicode"
+            | "iso-10646-ucs-2" => Some(TextEncoding::Utf16Le),
+            "utf-16be" | "unicodefffe" => Some(TextEncoding::Utf16Be),
+            _ => None,
+        }
+    }
+
+    /// The canonical name of the encoding
+    pub fn name(&self) -> &'static str {
+        match self {
+            TextEncoding::Utf8 => "utf-8",
+            TextEncoding::Utf16Le => "utf-16le",
+            TextEncoding::Utf16Be => "utf-16be",
+        }
+    }
+}
+
+/// Options accepted by the TextDecoder constructor
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TextDecoderOptions {
+    /// Throw a TypeError on malformed input instead of inserting U+FFFD
+    pub fatal: bool,
+    /// Keep a leading byte order mark in the output
+    pub ignore_bom: bool,
+}
+
+/// Options accepted by `TextDecoder::decode`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TextDecodeOptions {
+    /// More input follows, so an incomplete trailing sequence is kept for the
+    /// next call instead of being treated as an error
+    pub stream: bool,
+}
+
+/// TextDecoder - decodes bytes in a given encoding into a string
+#[derive(Debug, Clone)]
+pub struct TextDecoder {
+    encoding: TextEncoding,
+    fatal: bool,
+    ignore_bom: bool,
+    /// Bytes of an incomplete sequence left over from a streaming decode
+    pending: Vec<u8>,
+    /// Whether the start of the current stream has been decoded yet
+    bom_seen: bool,
+}
+
+impl TextDecoder {
+    /// Create a decoder for `label`, defaulting to UTF-8
+    ///
+    /// Unknown labels are a RangeError.
+    pub fn new(label: Option<&str>, options: TextDecoderOptions) -> JsResult<Self> {
+        let encoding = match label {
+            Some(label) => TextEncoding::from_label(label).ok_or_else(|| {
+                JsError::range_error(format!(
+                    "The encoding label provided ('{}') is invalid",
+                    label
+                ))
+            })?,
+            None => TextEncoding::Utf8,
+        };
+        Ok(TextDecoder {
+            encoding,
+            fatal: options.fatal,
+            ignore_bom: options.ignore_bom,
+            pending: Vec::new(),
+            bom_seen: false,
+        })
+    }
+
+    /// The canonical name of the decoder's encoding
+    pub fn encoding(&self) -> &'static str {
+        self.encoding.name()
+    }
+
+    /// Whether malformed input throws
+    pub fn fatal(&self) -> bool {
+        self.fatal
+    }
+
+    /// Whether a leading byte order mark is kept
+    pub fn ignore_bom(&self) -> bool {
+        self.ignore_bom
+    }
+
+    /// Decode the bytes viewed by `input`
+    ///
+    /// Passing no input flushes a previous streaming decode. Unless
+    /// `ignore_bom` is set, a byte order mark at the start of the stream is
+    /// dropped. In fatal mode malformed input is a TypeError; otherwise each
+    /// malformed sequence becomes U+FFFD.
+    pub fn decode(
+        &mut self,
+        input: Option<&TypedArray>,
+        options: TextDecodeOptions,
+    ) -> JsResult<String> {
+        let mut bytes = std::mem::take(&mut self.pending);
+        if let Some(input) = input {
+            let data = input.buffer().get_data();
+            let data = data.borrow();
+            let start = input.byte_offset();
+            bytes.extend_from_slice(&data[start..start + input.byte_length()]);
+        }
+
+        let result = match self.encoding {
+            TextEncoding::Utf8 => self.decode_utf8(&bytes, options.stream),
+            TextEncoding::Utf16Le => self.decode_utf16(&bytes, options.stream, u16::from_le_bytes),
+            TextEncoding::Utf16Be => self.decode_utf16(&bytes, options.stream, u16::from_be_bytes),
+        };
+        let mut output = match result {
+            Ok(output) => output,
+            Err(error) => {
+                self.reset();
+                return Err(error);
+            }
+        };
+
+        if !self.bom_seen && !output.is_empty() {
+            self.bom_seen = true;
+            if !self.ignore_bom && output.starts_with('\u{FEFF}') {
+                output.remove(0);
+            }
+        }
+        if !options.stream {
+            self.reset();
+        }
+        Ok(output)
+    }
+
+    fn reset(&mut self) {
+        self.pending.clear();
+        self.bom_seen = false;
+    }
+
+    fn malformed(&self, output: &mut String) -> JsResult<()> {
+        if self.fatal {
+            return Err(JsError::type_error(format!(
+                "The encoded data was not valid for encoding {}",
+                self.encoding.name()
+            )));
+        }
+        output.push('\u{FFFD}');
+        Ok(())
+    }
+
+    fn decode_utf8(&mut self, mut bytes: &[u8], stream: bool) -> JsResult<String> {
+        let mut output = String::with_capacity(bytes.len());
+        loop {
+            match std::str::from_utf8(bytes) {
+                Ok(valid) => {
+                    output.push_str(valid);
+                    return Ok(output);
+                }
+                Err(error) => {
+                    let (valid, rest) = bytes.split_at(error.valid_up_to());
+                    // The prefix was just validated by `from_utf8`.
+                    output.push_str(std::str::from_utf8(valid).unwrap_or_default());
+                    match error.error_len() {
+                        Some(len) => {
+                            self.malformed(&mut output)?;
+                            bytes = &rest[len..];
+                        }
+                        None if stream => {
+                            self.pending = rest.to_vec();
+                            return Ok(output);
+                        }
+                        None => {
+                            self.malformed(&mut output)?;
+                            return Ok(output);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn decode_utf16(
+        &mut self,
+        bytes: &[u8],
+        stream: bool,
+        unit: fn([u8; 2]) -> u16,
+    ) -> JsResult<String> {
+        let mut units: Vec<u16> = bytes
+            .chunks_exact(2)
+            .map(|pair| unit([pair[0], pair[1]]))
+            .collect();
+        let odd_byte = bytes.len() % 2 == 1;
+
+        // A lead surrogate at the end of a streamed chunk may be completed
+        // by the next chunk, so hold it back with any odd trailing byte.
+        let split = bytes.len() - bytes.len() % 2;
+        let mut keep_from = split;
+        if stream && matches!(units.last(), Some(0xD800..=0xDBFF)) {
+            units.pop();
+            keep_from -= 2;
+        }
+
+        let mut output = String::with_capacity(units.len());
+        for decoded in char::decode_utf16(units) {
+            match decoded {
+                Ok(c) => output.push(c),
+                Err(_) => self.malformed(&mut output)?,
+            }
+        }
+
+        if stream {
+            self.pending = bytes[keep_from..].to_vec();
+        } else if odd_byte {
+            self.malformed(&mut output)?;
+        }
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bytes(values: &[u8]) -> TypedArray {
+        let length = values.len();
+        TypedArray::from_buffer(
+            ArrayBuffer::from_bytes(values.to_vec()),
+            TypedArrayKind::Uint8,
+            None,
+            Some(length),
+        )
+        .unwrap()
+    }
+
+    fn utf8_decoder(options: TextDecoderOptions) -> TextDecoder {
+        TextDecoder::new(None, options).unwrap()
+    }
+
+    #[test]
+    fn test_encode_decode_round_trip() {
+        let encoded = TextEncoder::new().encode("h\u{e9}llo \u{1F600}").unwrap();
+        assert_eq!(encoded.kind(), TypedArrayKind::Uint8);
+        assert_eq!(encoded.length(), 11);
+        assert_eq!(encoded.get(1).unwrap().as_f64(), 0xC3 as f64);
+        assert_eq!(encoded.get(2).unwrap().as_f64(), 0xA9 as f64);
+
+        let mut decoder = utf8_decoder(TextDecoderOptions::default());
+        assert_eq!(decoder.encoding(), "utf-8");
+        let decoded = decoder.decode(Some(&encoded), TextDecodeOptions::default()).unwrap();
+        assert_eq!(decoded, "h\u{e9}llo \u{1F600}");
+    }
+
+    #[test]
+    fn test_decode_invalid_utf8() {
+        let invalid = bytes(&[0x61, 0xFF, 0x62, 0xE2, 0x82]);
+
+        let mut lenient = utf8_decoder(TextDecoderOptions::default());
+        let decoded = lenient.decode(Some(&invalid), TextDecodeOptions::default()).unwrap();
+        assert_eq!(decoded, "a\u{FFFD}b\u{FFFD}");
+
+        let mut fatal = utf8_decoder(TextDecoderOptions {
+            fatal: true,
+            ..Default::default()
+        });
+        assert!(fatal.fatal());
+        let error = fatal
+            .decode(Some(&invalid), TextDecodeOptions::default())
+            .unwrap_err();
+        assert!(error.message.starts_with("TypeError"));
+    }
+
+    #[test]
+    fn test_decode_bom_handling() {
+        let with_bom = bytes(&[0xEF, 0xBB, 0xBF, 0x68, 0x69]);
+
+        let mut decoder = utf8_decoder(TextDecoderOptions::default());
+        let decoded = decoder.decode(Some(&with_bom), TextDecodeOptions::default()).unwrap();
+        assert_eq!(decoded, "hi");
+
+        let mut keep_bom = utf8_decoder(TextDecoderOptions {
+            ignore_bom: true,
+            ..Default::default()
+        });
+        let decoded = keep_bom.decode(Some(&with_bom), TextDecodeOptions::default()).unwrap();
+        assert_eq!(decoded, "\u{FEFF}hi");
+
+        let mut utf16 = TextDecoder::new(Some("UTF-16LE"), Default::default()).unwrap();
+        let decoded = utf16
+            .decode(Some(&bytes(&[0xFF, 0xFE, 0x68, 0x00])), TextDecodeOptions::default())
+            .unwrap();
+        assert_eq!(decoded, "h");
+    }
+
+    #[test]
+    fn test_decode_streaming_split_sequence() {
+        let stream = TextDecodeOptions { stream: true };
+        let mut decoder = utf8_decoder(TextDecoderOptions {
+            fatal: true,
+            ..Default::default()
+        });
+        assert_eq!(decoder.decode(Some(&bytes(&[0x61, 0xE2, 0x82])), stream).unwrap(), "a");
+        assert_eq!(decoder.decode(Some(&bytes(&[0xAC])), stream).unwrap(), "\u{20AC}");
+        assert_eq!(decoder.decode(None, TextDecodeOptions::default()).unwrap(), "");
+    }
+
+    #[test]
+    fn test_decoder_labels() {
+        let options = TextDecoderOptions::default();
+        assert_eq!(TextDecoder::new(Some(" UTF8 "), options).unwrap().encoding(), "utf-8");
+        assert_eq!(TextDecoder::new(Some("utf-16be"), options).unwrap().encoding(), "utf-16be");
+        let error = TextDecoder::new(Some("klingon"), options).unwrap_err();
+        assert!(error.message.starts_with("RangeError"));
+    }
+}
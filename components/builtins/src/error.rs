@@ -6,9 +6,20 @@
 //! - Stack trace generation and formatting
 //! - Error.prototype methods
 
+use std::cell::Cell;
 use std::fmt;
 use std::rc::Rc;
 
+use crate::value::{JsResult, JsValue};
+
+/// Default value of `Error.stackTraceLimit`
+pub const DEFAULT_STACK_TRACE_LIMIT: usize = 10;
+
+thread_local! {
+    /// Current `Error.stackTraceLimit`, applied to errors created afterwards
+    static STACK_TRACE_LIMIT: Cell<usize> = const { Cell::new(DEFAULT_STACK_TRACE_LIMIT) };
+}
+
 /// The kind of JavaScript error
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ErrorKind {
@@ -138,7 +149,7 @@ impl JsErrorObject {
             stack_frames: Vec::new(),
             cause: None,
             errors: None,
-            stack_trace_limit: 10,
+            stack_trace_limit: ErrorConstructor::stack_trace_limit(),
         }
     }
 
@@ -186,10 +197,19 @@ impl JsErrorObject {
     }
 
     /// Capture stack trace from provided frames
-    pub fn capture_stack_trace(&mut self, frames: Vec<StackFrame>) {
+    ///
+    /// Frames are ordered innermost first and truncated to the error's
+    /// stack trace limit.
+    pub fn capture_stack_trace(&mut self, mut frames: Vec<StackFrame>) {
+        frames.truncate(self.stack_trace_limit);
         self.stack_frames = frames;
     }
 
+    /// Get the captured stack frames
+    pub fn stack_frames(&self) -> &[StackFrame] {
+        &self.stack_frames
+    }
+
     /// Get the cause of this error if any
     pub fn cause(&self) -> Option<&JsErrorObject> {
         self.cause.as_ref().map(|rc| rc.as_ref())
@@ -278,6 +298,59 @@ impl ErrorConstructor {
         error
     }
 
+    /// Get `Error.stackTraceLimit`
+    pub fn stack_trace_limit() -> usize {
+        STACK_TRACE_LIMIT.with(Cell::get)
+    }
+
+    /// Set `Error.stackTraceLimit` for errors created afterwards
+    pub fn set_stack_trace_limit(limit: usize) {
+        STACK_TRACE_LIMIT.with(|cell| cell.set(limit));
+    }
+
+    /// Error.captureStackTrace(target, constructorOpt)
+    ///
+    /// Sets `target.stack` from `frames` (innermost first). When
+    /// `constructor_opt` names a function, that frame and every frame above
+    /// it are omitted, hiding framework internals; if it is not on the stack
+    /// no frames are kept. At most `Error.stackTraceLimit` frames are kept.
+    pub fn capture_stack_trace(
+        target: &JsValue,
+        frames: Vec<StackFrame>,
+        constructor_opt: Option<&str>,
+    ) -> JsResult<()> {
+        let frames = match constructor_opt {
+            Some(name) => match frames.iter().position(|f| f.function_name() == name) {
+                Some(index) => frames[index + 1..].to_vec(),
+                None => Vec::new(),
+            },
+            None => frames,
+        };
+
+        match target {
+            JsValue::Error(error) => {
+                let mut error = error.borrow_mut();
+                error.stack_trace_limit = Self::stack_trace_limit();
+                error.capture_stack_trace(frames);
+                Ok(())
+            }
+            JsValue::Object(_) => {
+                let name = target.get("name").map(|v| v.to_js_string());
+                let message = target.get("message").map(|v| v.to_js_string());
+                let mut header = JsErrorObject::new(ErrorKind::Error, message.unwrap_or_default());
+                if let Some(name) = name {
+                    header.set_name(name);
+                }
+                header.capture_stack_trace(frames);
+                target.set("stack", JsValue::string(header.stack()));
+                Ok(())
+            }
+            _ => Err(crate::value::JsError::type_error(
+                "Error.captureStackTrace called on non-object",
+            )),
+        }
+    }
+
     /// Create an error with a cause
     pub fn with_cause(
         kind: ErrorKind,
@@ -366,6 +439,52 @@ mod tests {
         assert_eq!(lines.len(), 11);
     }
 
+    fn frames(names: &[&str]) -> Vec<StackFrame> {
+        names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| StackFrame::new(name.to_string(), "test.js".to_string(), i as u32, 1))
+            .collect()
+    }
+
+    #[test]
+    fn test_stack_trace_limit_truncates_captured_frames() {
+        ErrorConstructor::set_stack_trace_limit(2);
+        let mut error = JsErrorObject::new(ErrorKind::Error, "deep".to_string());
+        error.capture_stack_trace(frames(&["f0", "f1", "f2", "f3"]));
+        ErrorConstructor::set_stack_trace_limit(DEFAULT_STACK_TRACE_LIMIT);
+
+        assert_eq!(error.stack_frames().len(), 2);
+        let stack = error.stack();
+        assert_eq!(stack.lines().count(), 3);
+        assert!(stack.contains("at f1"));
+        assert!(!stack.contains("at f2"));
+    }
+
+    #[test]
+    fn test_capture_stack_trace_omits_frames_above_constructor() {
+        let target = JsValue::object();
+        target.set("message", JsValue::string("boom"));
+        let stack = frames(&["helper", "MyError", "caller", "main"]);
+        ErrorConstructor::capture_stack_trace(&target, stack.clone(), Some("MyError")).unwrap();
+
+        let text = target.get("stack").unwrap().to_js_string();
+        assert!(text.starts_with("Error: boom"));
+        assert!(!text.contains("at helper"));
+        assert!(!text.contains("at MyError"));
+        assert!(text.contains("at caller"));
+        assert!(text.contains("at main"));
+
+        let error = JsValue::from_error(JsErrorObject::new(ErrorKind::TypeError, "t".to_string()));
+        ErrorConstructor::capture_stack_trace(&error, stack, None).unwrap();
+        if let JsValue::Error(inner) = &error {
+            assert_eq!(inner.borrow().stack_frames().len(), 4);
+        }
+
+        let primitive = JsValue::number(1.0);
+        assert!(ErrorConstructor::capture_stack_trace(&primitive, vec![], None).is_err());
+    }
+
     #[test]
     fn test_error_constructor_type_error() {
         let error = ErrorConstructor::type_error(Some("not callable".to_string()));
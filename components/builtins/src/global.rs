@@ -0,0 +1,149 @@
+//! Global object and URI-handling global functions
+
+use crate::value::{JsError, JsResult};
+
+/// The global object, exposed to scripts as `globalThis`
+pub struct GlobalObject;
+
+/// Characters left unescaped by every URI encoding function (RFC 3986 unreserved,
+/// plus the marks ECMAScript keeps for compatibility)
+const URI_UNESCAPED: &str = "-_.!~*'()";
+
+/// Reserved characters plus `#`, which encodeURI leaves intact
+const URI_RESERVED: &str = ";/?:@&=+$,#";
+
+/// encodeURIComponent(uri)
+pub fn encode_uri_component(uri: &str) -> JsResult<String> {
+    encode_uri_component_utf16(&uri.encode_utf16().collect::<Vec<_>>())
+}
+
+/// encodeURIComponent(uri) over UTF-16 code units, so lone surrogates can be rejected
+pub fn encode_uri_component_utf16(uri: &[u16]) -> JsResult<String> {
+    encode(uri, "")
+}
+
+/// encodeURI(uri)
+pub fn encode_uri(uri: &str) -> JsResult<String> {
+    encode_uri_utf16(&uri.encode_utf16().collect::<Vec<_>>())
+}
+
+/// encodeURI(uri) over UTF-16 code units, so lone surrogates can be rejected
+pub fn encode_uri_utf16(uri: &[u16]) -> JsResult<String> {
+    encode(uri, URI_RESERVED)
+}
+
+/// decodeURIComponent(encodedURIComponent)
+pub fn decode_uri_component(encoded: &str) -> JsResult<String> {
+    decode(encoded, "")
+}
+
+/// decodeURI(encodedURI)
+pub fn decode_uri(encoded: &str) -> JsResult<String> {
+    decode(encoded, URI_RESERVED)
+}
+
+/// Percent-encode everything except alphanumerics, unescaped marks and `keep`
+fn encode(units: &[u16], keep: &str) -> JsResult<String> {
+    let mut result = String::with_capacity(units.len());
+
+    for decoded in char::decode_utf16(units.iter().copied()) {
+        let c = decoded.map_err(|_| JsError::uri_error("URI malformed"))?;
+        if c.is_ascii_alphanumeric() || URI_UNESCAPED.contains(c) || keep.contains(c) {
+            result.push(c);
+        } else {
+            let mut buf = [0u8; 4];
+            for byte in c.encode_utf8(&mut buf).bytes() {
+                result.push_str(&format!("%{:02X}", byte));
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// Decode percent-escapes, leaving escapes for characters in `keep` untouched
+fn decode(encoded: &str, keep: &str) -> JsResult<String> {
+    let bytes = encoded.as_bytes();
+    let mut result = String::with_capacity(encoded.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] != b'%' {
+            // Copy the whole (possibly multi-byte) character through unchanged
+            let c = encoded[i..].chars().next().unwrap();
+            result.push(c);
+            i += c.len_utf8();
+            continue;
+        }
+
+        let lead = hex_byte(bytes, i)?;
+        if lead < 0x80 {
+            let c = lead as char;
+            if keep.contains(c) {
+                result.push_str(&encoded[i..i + 3]);
+            } else {
+                result.push(c);
+            }
+            i += 3;
+            continue;
+        }
+
+        // Multi-byte UTF-8 sequence: every byte must itself be an escape
+        let len = match lead {
+            0xC0..=0xDF => 2,
+            0xE0..=0xEF => 3,
+            0xF0..=0xF7 => 4,
+            _ => return Err(JsError::uri_error("URI malformed")),
+        };
+        let mut seq = vec![lead];
+        for k in 1..len {
+            seq.push(hex_byte(bytes, i + k * 3)?);
+        }
+        let s = std::str::from_utf8(&seq).map_err(|_| JsError::uri_error("URI malformed"))?;
+        result.push_str(s);
+        i += len * 3;
+    }
+
+    Ok(result)
+}
+
+/// Parse the `%XX` escape starting at `at`
+fn hex_byte(bytes: &[u8], at: usize) -> JsResult<u8> {
+    if at + 3 > bytes.len() || bytes[at] != b'%' {
+        return Err(JsError::uri_error("URI malformed"));
+    }
+    std::str::from_utf8(&bytes[at + 1..at + 3])
+        .ok()
+        .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        .ok_or_else(|| JsError::uri_error("URI malformed"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_uri_component_escapes_reserved() {
+        assert_eq!(encode_uri_component("a b&c=d").unwrap(), "a%20b%26c%3Dd");
+        assert_eq!(encode_uri_component("é").unwrap(), "%C3%A9");
+    }
+
+    #[test]
+    fn test_encode_uri_keeps_reserved() {
+        assert_eq!(encode_uri("http://x.y/a b?q=1#h").unwrap(), "http://x.y/a%20b?q=1#h");
+    }
+
+    #[test]
+    fn test_decode_uri_keeps_reserved_escapes() {
+        assert_eq!(decode_uri("%2F%20").unwrap(), "%2F ");
+        assert_eq!(decode_uri_component("%2F%20").unwrap(), "/ ");
+    }
+
+    #[test]
+    fn test_decode_malformed() {
+        assert!(decode_uri_component("%").is_err());
+        assert!(decode_uri_component("%zz").is_err());
+        assert!(decode_uri_component("%C3").is_err());
+        assert!(decode_uri_component("%C3%28").is_err());
+    }
+}
@@ -1822,12 +1822,12 @@ impl RelativeTimeFormat {
     /// Format a relative time value
     pub fn format(&self, value: f64, unit: RelativeTimeUnit) -> String {
         let abs_value = value.abs();
-        let is_future = value > 0.0;
-        let integer_value = abs_value.round() as i64;
+        // Negative zero reads as past ("0 days ago"), positive zero as future
+        let is_future = !value.is_sign_negative();
 
         // Check for special cases when using auto numeric
         if matches!(self.options.numeric, RelativeTimeNumeric::Auto) {
-            if let Some(special) = self.get_special_phrase(integer_value, unit, is_future) {
+            if let Some(special) = self.get_special_phrase(value, unit) {
                 return special;
             }
         }
@@ -1850,22 +1850,45 @@ impl RelativeTimeFormat {
         }
     }
 
-    /// Get special phrase for common relative times
-    fn get_special_phrase(&self, value: i64, unit: RelativeTimeUnit, is_future: bool) -> Option<String> {
-        if value != 1 {
+    /// Get the en-US phrase used by `numeric: "auto"` for -1, 0 and 1
+    ///
+    /// Only exact values qualify, so 1.5 days stays "in 1.5 days".
+    fn get_special_phrase(&self, value: f64, unit: RelativeTimeUnit) -> Option<String> {
+        let offset = if value == -1.0 {
+            -1
+        } else if value == 0.0 {
+            0
+        } else if value == 1.0 {
+            1
+        } else {
             return None;
-        }
+        };
 
-        match (unit, is_future) {
-            (RelativeTimeUnit::Day, false) => Some("yesterday".to_string()),
-            (RelativeTimeUnit::Day, true) => Some("tomorrow".to_string()),
-            (RelativeTimeUnit::Week, false) => Some("last week".to_string()),
-            (RelativeTimeUnit::Week, true) => Some("next week".to_string()),
-            (RelativeTimeUnit::Month, false) => Some("last month".to_string()),
-            (RelativeTimeUnit::Month, true) => Some("next month".to_string()),
-            (RelativeTimeUnit::Year, false) => Some("last year".to_string()),
-            (RelativeTimeUnit::Year, true) => Some("next year".to_string()),
-            _ => None,
+        let name = match unit {
+            RelativeTimeUnit::Day => {
+                let phrase = match offset {
+                    -1 => "yesterday",
+                    0 => "today",
+                    _ => "tomorrow",
+                };
+                return Some(phrase.to_string());
+            }
+            RelativeTimeUnit::Second => return (offset == 0).then(|| "now".to_string()),
+            RelativeTimeUnit::Minute => "minute",
+            RelativeTimeUnit::Hour => "hour",
+            RelativeTimeUnit::Week => "week",
+            RelativeTimeUnit::Month => "month",
+            RelativeTimeUnit::Quarter => "quarter",
+            RelativeTimeUnit::Year => "year",
+        };
+
+        // Hours and minutes only have a phrase for the current one
+        let is_clock_unit = matches!(unit, RelativeTimeUnit::Hour | RelativeTimeUnit::Minute);
+        match offset {
+            0 => Some(format!("this {}", name)),
+            _ if is_clock_unit => None,
+            -1 => Some(format!("last {}", name)),
+            _ => Some(format!("next {}", name)),
         }
     }
 
@@ -2469,6 +2492,35 @@ mod tests {
         assert_eq!(formatter.format(1.0, RelativeTimeUnit::Day), "tomorrow");
     }
 
+    #[test]
+    fn test_relative_time_format_auto_all_units() {
+        let options = RelativeTimeFormatOptions {
+            numeric: RelativeTimeNumeric::Auto,
+            ..Default::default()
+        };
+        let formatter = RelativeTimeFormat::new(Locale::new("en-US").unwrap(), options);
+
+        assert_eq!(formatter.format(0.0, RelativeTimeUnit::Day), "today");
+        assert_eq!(formatter.format(0.0, RelativeTimeUnit::Second), "now");
+        assert_eq!(formatter.format(0.0, RelativeTimeUnit::Hour), "this hour");
+        assert_eq!(formatter.format(-1.0, RelativeTimeUnit::Quarter), "last quarter");
+        assert_eq!(formatter.format(1.0, RelativeTimeUnit::Year), "next year");
+        assert_eq!(formatter.format(-1.0, RelativeTimeUnit::Minute), "1 minute ago");
+        assert_eq!(formatter.format(1.0, RelativeTimeUnit::Second), "in 1 second");
+        assert_eq!(formatter.format(3.0, RelativeTimeUnit::Day), "in 3 days");
+        assert_eq!(formatter.format(1.5, RelativeTimeUnit::Day), "in 1.5 days");
+    }
+
+    #[test]
+    fn test_relative_time_format_zero_sign() {
+        let formatter = RelativeTimeFormat::with_locale(Locale::new("en-US").unwrap());
+
+        assert_eq!(formatter.format(0.0, RelativeTimeUnit::Day), "in 0 days");
+        assert_eq!(formatter.format(-0.0, RelativeTimeUnit::Day), "0 days ago");
+        assert_eq!(formatter.format(-2.0, RelativeTimeUnit::Week), "2 weeks ago");
+        assert_eq!(formatter.format(4.0, RelativeTimeUnit::Month), "in 4 months");
+    }
+
     #[test]
     fn test_relative_time_format_narrow() {
         let options = RelativeTimeFormatOptions {
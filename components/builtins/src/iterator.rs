@@ -125,6 +125,11 @@ impl GeneratorObject {
         }
     }
 
+    /// Whether both handles refer to the same generator instance
+    pub fn ptr_eq(&self, other: &GeneratorObject) -> bool {
+        Rc::ptr_eq(&self.data, &other.data)
+    }
+
     /// Get the current state
     pub fn state(&self) -> GeneratorState {
         self.data.borrow().state
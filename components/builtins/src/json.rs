@@ -120,6 +120,8 @@ impl JSONObject {
                 Ok(JsValue::array_from(elements?))
             }
             serde_json::Value::Object(obj) => {
+                // Every key, including "__proto__", becomes an own data property
+                // of a fresh object; parsing never touches a prototype
                 let js_obj = JsValue::object();
                 for (key, val) in obj {
                     js_obj.set(key, Self::json_to_js_value(val)?);
@@ -21,7 +21,7 @@
 //!     JsValue::number(3.0),
 //! ]);
 //!
-//! let sum = ArrayPrototype::reduce(&arr, JsValue::number(0.0), |acc, v| {
+//! let sum = ArrayPrototype::reduce(&arr, Some(JsValue::number(0.0)), |acc, v| {
 //!     Ok(JsValue::number(acc.as_number().unwrap() + v.as_number().unwrap()))
 //! }).unwrap();
 //!
@@ -40,7 +40,9 @@ pub mod bigint;
 pub mod collections;
 pub mod console;
 pub mod date;
+pub mod encoding;
 pub mod error;
+pub mod global;
 pub mod intl;
 pub mod iterator;
 pub mod json;
@@ -66,7 +68,16 @@ pub use bigint::{BigIntConstructor, BigIntPrototype};
 pub use collections::{MapIterator, MapObject, SetIterator, SetObject, WeakMapObject, WeakSetObject};
 pub use console::ConsoleObject;
 pub use date::{DateConstructor, JsDate};
-pub use error::{ErrorConstructor, ErrorKind, JsErrorObject, StackFrame};
+pub use encoding::{
+    TextDecodeOptions, TextDecoder, TextDecoderOptions, TextEncoder, TextEncoding,
+};
+pub use error::{
+    ErrorConstructor, ErrorKind, JsErrorObject, StackFrame, DEFAULT_STACK_TRACE_LIMIT,
+};
+pub use global::{
+    decode_uri, decode_uri_component, encode_uri, encode_uri_component,
+    encode_uri_component_utf16, encode_uri_utf16, GlobalObject,
+};
 pub use intl::{
     CaseFirst, Collator, CollatorOptions, CollatorSensitivity, CollatorUsage,
     CompactDisplay, CurrencyDisplay, DateTimeFormat, DateTimeFormatOptions, DateTimeStyle,
@@ -87,14 +98,14 @@ pub use object::ObjectPrototype;
 pub use proxy::{ProxyHandler, ProxyObject};
 pub use reflect::ReflectObject;
 pub use regexp::{RegExpMatch, RegExpObject};
-pub use string::StringPrototype;
+pub use string::{SplitSeparator, StringPrototype};
 pub use symbol::{SymbolConstructor, SymbolValue};
 pub use typed_arrays::{
     ArrayBuffer, BigInt64Array, BigUint64Array, DataView, Float32Array, Float64Array, Int16Array,
     Int32Array, Int8Array, TypedArray, TypedArrayKind, TypedArrayValue, Uint16Array, Uint32Array,
     Uint8Array, Uint8ClampedArray,
 };
-pub use value::{BigIntValue, JsError, JsResult, JsValue};
+pub use value::{BigIntValue, JsError, JsResult, JsValue, PreferredType};
 pub use weakref::{
     FinalizationRegistryData, FinalizationRegistryObject, WeakObjectRef, WeakRefData,
     WeakRefObject,
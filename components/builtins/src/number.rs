@@ -50,6 +50,26 @@ impl NumberObject {
         Self::is_integer(value) && value.abs() <= Self::MAX_SAFE_INTEGER
     }
 
+    /// Number.isNaN(value) on an arbitrary value - non-numbers are never NaN
+    pub fn is_nan_value(value: &JsValue) -> bool {
+        value.as_number().is_some_and(Self::is_nan)
+    }
+
+    /// Number.isFinite(value) on an arbitrary value - no coercion
+    pub fn is_finite_value(value: &JsValue) -> bool {
+        value.as_number().is_some_and(Self::is_finite)
+    }
+
+    /// Number.isInteger(value) on an arbitrary value - no coercion
+    pub fn is_integer_value(value: &JsValue) -> bool {
+        value.as_number().is_some_and(Self::is_integer)
+    }
+
+    /// Number.isSafeInteger(value) on an arbitrary value - no coercion
+    pub fn is_safe_integer_value(value: &JsValue) -> bool {
+        value.as_number().is_some_and(Self::is_safe_integer)
+    }
+
     /// Number.parseInt(string, radix) - same as global parseInt
     pub fn parse_int(s: &str, radix: Option<u32>) -> f64 {
         let radix = radix.unwrap_or(10);
@@ -121,13 +141,13 @@ impl NumberObject {
 }
 
 /// Global isNaN function (coerces to number first, unlike Number.isNaN)
-pub fn global_is_nan(value: f64) -> bool {
-    value.is_nan()
+pub fn global_is_nan(value: &JsValue) -> JsResult<bool> {
+    Ok(value.to_number()?.is_nan())
 }
 
 /// Global isFinite function (coerces to number first, unlike Number.isFinite)
-pub fn global_is_finite(value: f64) -> bool {
-    value.is_finite()
+pub fn global_is_finite(value: &JsValue) -> JsResult<bool> {
+    Ok(value.to_number()?.is_finite())
 }
 
 /// Number.prototype methods
@@ -333,6 +353,31 @@ mod tests {
         assert_eq!(NumberPrototype::value_of(&val).unwrap(), 42.0);
     }
 
+    #[test]
+    fn test_is_nan_does_not_coerce() {
+        assert!(!NumberObject::is_nan_value(&JsValue::string("NaN")));
+        assert!(NumberObject::is_nan_value(&JsValue::number(f64::NAN)));
+        assert!(global_is_nan(&JsValue::string("NaN")).unwrap());
+        assert!(!global_is_nan(&JsValue::string("42")).unwrap());
+    }
+
+    #[test]
+    fn test_is_finite_does_not_coerce() {
+        assert!(!NumberObject::is_finite_value(&JsValue::string("12")));
+        assert!(global_is_finite(&JsValue::string("12")).unwrap());
+        assert!(!global_is_finite(&JsValue::undefined()).unwrap());
+    }
+
+    #[test]
+    fn test_safe_integer_bounds() {
+        assert!(NumberObject::is_safe_integer(NumberObject::MAX_SAFE_INTEGER));
+        assert!(NumberObject::is_safe_integer(NumberObject::MIN_SAFE_INTEGER));
+        assert!(!NumberObject::is_safe_integer(2f64.powi(53)));
+        assert!(!NumberObject::is_safe_integer_value(&JsValue::string("1")));
+        assert!(NumberObject::is_integer_value(&JsValue::number(5.0)));
+        assert!(!NumberObject::is_integer_value(&JsValue::number(5.5)));
+    }
+
     #[test]
     fn test_special_values() {
         assert_eq!(NumberPrototype::to_string(f64::NAN, None).unwrap(), "NaN");
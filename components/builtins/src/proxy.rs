@@ -167,6 +167,11 @@ impl ProxyObject {
         })
     }
 
+    /// Whether both handles refer to the same proxy instance
+    pub fn ptr_eq(&self, other: &ProxyObject) -> bool {
+        Rc::ptr_eq(&self.data, &other.data)
+    }
+
     /// Create a revocable proxy
     pub fn revocable(
         target: JsValue,
@@ -182,6 +187,21 @@ impl ProxyObject {
         Ok((proxy, revoke))
     }
 
+    /// `Proxy.revocable(target, handler)`, returning the `{ proxy, revoke }` object
+    pub fn revocable_object(target: JsValue, handler: ProxyHandler) -> JsResult<JsValue> {
+        let (proxy, revoke) = ProxyObject::revocable(target, handler)?;
+        let result = JsValue::object();
+        result.set("proxy", JsValue::from_proxy(proxy));
+        result.set(
+            "revoke",
+            JsValue::function(move |_this, _args| {
+                revoke();
+                Ok(JsValue::undefined())
+            }),
+        );
+        Ok(result)
+    }
+
     /// Check if proxy has been revoked
     pub fn is_revoked(&self) -> bool {
         self.data.borrow().revoked
@@ -199,6 +219,21 @@ impl ProxyObject {
         }
     }
 
+    /// The value of `key` if it is a non-configurable, non-writable data
+    /// property of `target`, which get and set traps must not misreport
+    fn frozen_value(target: &JsValue, key: &str) -> JsResult<Option<JsValue>> {
+        let descriptor = ReflectObject::get_own_property_descriptor(target, key)?;
+        Ok(descriptor.and_then(|descriptor| {
+            let frozen = descriptor.configurable == Some(false)
+                && descriptor.writable == Some(false);
+            if frozen {
+                descriptor.value
+            } else {
+                None
+            }
+        }))
+    }
+
     /// Get the target object
     pub fn target(&self) -> JsValue {
         self.data.borrow().target.clone()
@@ -210,7 +245,18 @@ impl ProxyObject {
 
         let data = self.data.borrow();
         if let Some(ref trap) = data.handler.get {
-            trap(&data.target, key, None)
+            let value = trap(&data.target, key, None)?;
+            if let Some(expected) = Self::frozen_value(&data.target, key)? {
+                if !value.same_value(&expected) {
+                    return Err(JsError::type_error(format!(
+                        "'get' on proxy: property '{}' is a read-only and non-configurable \
+                         data property on the proxy target but the proxy did not return \
+                         its actual value",
+                        key
+                    )));
+                }
+            }
+            Ok(value)
         } else {
             ReflectObject::get(&data.target, key, None)
         }
@@ -222,7 +268,20 @@ impl ProxyObject {
 
         let data = self.data.borrow();
         if let Some(ref trap) = data.handler.set {
-            trap(&data.target, key, value, None)
+            let success = trap(&data.target, key, value.clone(), None)?;
+            if success {
+                if let Some(current) = Self::frozen_value(&data.target, key)? {
+                    if !value.same_value(&current) {
+                        return Err(JsError::type_error(format!(
+                            "'set' on proxy: trap returned truish for property '{}' which \
+                             exists in the proxy target as a non-configurable and \
+                             non-writable data property with a different value",
+                            key
+                        )));
+                    }
+                }
+            }
+            Ok(success)
         } else {
             ReflectObject::set(&data.target, key, value, None)
         }
@@ -587,4 +646,62 @@ mod tests {
         proxy.prevent_extensions().unwrap();
         assert!(!proxy.is_extensible().unwrap());
     }
+
+    fn frozen_target() -> JsValue {
+        let target = JsValue::object();
+        let descriptor = PropertyDescriptor {
+            value: Some(JsValue::number(1.0)),
+            writable: Some(false),
+            configurable: Some(false),
+            ..PropertyDescriptor::default()
+        };
+        assert!(ReflectObject::define_property(&target, "fixed", &descriptor).unwrap());
+        target
+    }
+
+    #[test]
+    fn test_proxy_revocable_object() {
+        let target = JsValue::object();
+        target.set("data", JsValue::number(1.0));
+        let result = ProxyObject::revocable_object(target, ProxyHandler::default()).unwrap();
+
+        let Some(JsValue::Proxy(proxy)) = result.get("proxy") else {
+            panic!("expected a proxy");
+        };
+        assert_eq!(proxy.get("data").unwrap().as_number(), Some(1.0));
+
+        let revoke = result.get("revoke").unwrap();
+        ReflectObject::apply(&revoke, &JsValue::undefined(), &[]).unwrap();
+
+        let err = proxy.get("data").unwrap_err();
+        assert!(err.message.contains("revoked"));
+        assert!(proxy.set("data", JsValue::number(2.0)).is_err());
+    }
+
+    #[test]
+    fn test_proxy_get_trap_invariant() {
+        let handler = ProxyHandler {
+            get: Some(Box::new(|_target, _key, _receiver| Ok(JsValue::number(2.0)))),
+            ..ProxyHandler::default()
+        };
+        let proxy = ProxyObject::new(frozen_target(), handler).unwrap();
+
+        let err = proxy.get("fixed").unwrap_err();
+        assert!(err.message.contains("non-configurable"));
+        // Other properties may report anything
+        assert_eq!(proxy.get("other").unwrap().as_number(), Some(2.0));
+    }
+
+    #[test]
+    fn test_proxy_set_trap_invariant() {
+        let handler = ProxyHandler {
+            set: Some(Box::new(|_target, _key, _value, _receiver| Ok(true))),
+            ..ProxyHandler::default()
+        };
+        let proxy = ProxyObject::new(frozen_target(), handler).unwrap();
+
+        assert!(proxy.set("fixed", JsValue::number(5.0)).is_err());
+        assert!(proxy.set("fixed", JsValue::number(1.0)).unwrap());
+        assert!(!ReflectObject::set(&proxy.target(), "fixed", JsValue::number(5.0), None).unwrap());
+    }
 }
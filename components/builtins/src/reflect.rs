@@ -4,7 +4,7 @@
 //! All methods are static and match the proxy handler traps.
 
 use crate::proxy::PropertyDescriptor;
-use crate::value::{JsError, JsResult, JsValue};
+use crate::value::{JsError, JsResult, JsValue, PropertyAttributes};
 
 /// Reflect object - provides static methods for object operations
 pub struct ReflectObject;
@@ -61,6 +61,9 @@ impl ReflectObject {
                 if is_new && !obj.borrow().extensible.unwrap_or(true) {
                     return Ok(false);
                 }
+                if !obj.borrow().attributes_of(key).writable {
+                    return Ok(false);
+                }
                 obj.borrow_mut().properties.insert(key.to_string(), value);
                 Ok(true)
             }
@@ -129,7 +132,12 @@ impl ReflectObject {
 
         match target {
             JsValue::Object(obj) => {
-                obj.borrow_mut().properties.remove(key);
+                let mut borrowed = obj.borrow_mut();
+                if !borrowed.attributes_of(key).configurable {
+                    return Ok(false);
+                }
+                borrowed.properties.remove(key);
+                borrowed.attributes.remove(key);
                 Ok(true)
             }
             JsValue::Array(arr) => {
@@ -222,7 +230,9 @@ impl ReflectObject {
 
     /// Reflect.defineProperty(target, propertyKey, attributes)
     ///
-    /// Defines a new property directly on an object.
+    /// Defines a new property directly on an object. Attributes the
+    /// descriptor leaves out keep their current values, or are false for a
+    /// new property.
     pub fn define_property(
         target: &JsValue,
         key: &str,
@@ -238,10 +248,52 @@ impl ReflectObject {
                     return Ok(false);
                 }
 
+                // Omitted attributes of a new property default to false
+                let current = if is_new {
+                    PropertyAttributes {
+                        writable: false,
+                        enumerable: false,
+                        configurable: false,
+                    }
+                } else {
+                    obj.borrow().attributes_of(key)
+                };
+
+                // A non-configurable property can't become configurable, and a
+                // non-writable one can't become writable or change value
+                if !is_new && !current.configurable {
+                    let same_value = match &descriptor.value {
+                        Some(value) => obj
+                            .borrow()
+                            .properties
+                            .get(key)
+                            .is_some_and(|old| old.same_value(value)),
+                        None => true,
+                    };
+                    if descriptor.configurable == Some(true)
+                        || descriptor.enumerable.is_some_and(|e| e != current.enumerable)
+                        || (!current.writable
+                            && (descriptor.writable == Some(true) || !same_value))
+                    {
+                        return Ok(false);
+                    }
+                }
+
+                let mut borrowed = obj.borrow_mut();
                 if let Some(value) = &descriptor.value {
-                    obj.borrow_mut()
-                        .properties
-                        .insert(key.to_string(), value.clone());
+                    borrowed.properties.insert(key.to_string(), value.clone());
+                } else if is_new {
+                    borrowed.properties.insert(key.to_string(), JsValue::undefined());
+                }
+                let attributes = PropertyAttributes {
+                    writable: descriptor.writable.unwrap_or(current.writable),
+                    enumerable: descriptor.enumerable.unwrap_or(current.enumerable),
+                    configurable: descriptor.configurable.unwrap_or(current.configurable),
+                };
+                if attributes == PropertyAttributes::default() {
+                    borrowed.attributes.remove(key);
+                } else {
+                    borrowed.attributes.insert(key.to_string(), attributes);
                 }
                 Ok(true)
             }
@@ -260,12 +312,14 @@ impl ReflectObject {
 
         match target {
             JsValue::Object(obj) => {
-                if let Some(value) = obj.borrow().properties.get(key) {
+                let borrowed = obj.borrow();
+                if let Some(value) = borrowed.properties.get(key) {
+                    let attributes = borrowed.attributes_of(key);
                     Ok(Some(PropertyDescriptor {
                         value: Some(value.clone()),
-                        writable: Some(true),
-                        enumerable: Some(true),
-                        configurable: Some(true),
+                        writable: Some(attributes.writable),
+                        enumerable: Some(attributes.enumerable),
+                        configurable: Some(attributes.configurable),
                         get: None,
                         set: None,
                     }))
@@ -318,18 +372,25 @@ impl ReflectObject {
 
     /// Reflect.construct(target, argumentsList [, newTarget])
     ///
-    /// Calls a constructor function with arguments.
+    /// Calls a constructor function with arguments. The result's prototype
+    /// comes from `newTarget.prototype`; `newTarget` defaults to `target`.
     pub fn construct(
         target: &JsValue,
         args: &[JsValue],
-        _new_target: Option<&JsValue>,
+        new_target: Option<&JsValue>,
     ) -> JsResult<JsValue> {
-        match target {
-            JsValue::Constructor(c) => (c.borrow().func)(args.to_vec()),
-            _ => Err(JsError::type_error(
+        if !target.is_constructor() {
+            return Err(JsError::type_error(
                 "Reflect.construct called on non-constructor",
-            )),
+            ));
+        }
+        let new_target = new_target.unwrap_or(target);
+        if !new_target.is_constructor() {
+            return Err(JsError::type_error(
+                "Reflect.construct newTarget is not a constructor",
+            ));
         }
+        target.construct(args.to_vec(), new_target)
     }
 
     /// Helper: Validate that target is an object
@@ -476,6 +537,32 @@ mod tests {
         assert_eq!(obj.get("defined").unwrap().as_number(), Some(100.0));
     }
 
+    #[test]
+    fn test_reflect_define_property_defaults_new_attributes_to_false() {
+        let obj = JsValue::object();
+        let desc = PropertyDescriptor {
+            value: Some(JsValue::number(1.0)),
+            ..PropertyDescriptor::default()
+        };
+        assert!(ReflectObject::define_property(&obj, "fixed", &desc).unwrap());
+
+        let defined = ReflectObject::get_own_property_descriptor(&obj, "fixed")
+            .unwrap()
+            .unwrap();
+        assert_eq!(defined.writable, Some(false));
+        assert_eq!(defined.enumerable, Some(false));
+        assert_eq!(defined.configurable, Some(false));
+
+        // Existing properties keep the attributes a descriptor leaves out
+        obj.set("open", JsValue::number(2.0));
+        assert!(ReflectObject::define_property(&obj, "open", &desc).unwrap());
+        let open = ReflectObject::get_own_property_descriptor(&obj, "open")
+            .unwrap()
+            .unwrap();
+        assert_eq!(open.writable, Some(true));
+        assert_eq!(open.configurable, Some(true));
+    }
+
     #[test]
     fn test_reflect_get_own_property_descriptor() {
         let obj = JsValue::object();
@@ -600,33 +600,44 @@ impl RegExpObject {
     }
 
     /// Symbol.split with limit
+    ///
+    /// Follows `RegExp.prototype[@@split]`: an empty match directly after the
+    /// previous split point (or at the end of the string) does not split.
+    /// Capturing groups that did not participate become empty strings.
     pub fn symbol_split_with_limit(&self, string: &str, limit: usize) -> JsResult<Vec<String>> {
         if limit == 0 {
             return Ok(vec![]);
         }
 
+        if string.is_empty() {
+            // An empty string splits into nothing only if the pattern matches it
+            return Ok(if self.regex.is_match(string) {
+                vec![]
+            } else {
+                vec![String::new()]
+            });
+        }
+
         let mut result = Vec::new();
         let mut last_end = 0;
 
         for caps in self.regex.captures_iter(string) {
-            if result.len() >= limit {
-                break;
-            }
-
             let mat = caps.get(0).unwrap();
+            if mat.end() == last_end || mat.start() == string.len() {
+                continue;
+            }
 
             // Add portion before match
-            if result.len() < limit {
-                result.push(string[last_end..mat.start()].to_string());
+            result.push(string[last_end..mat.start()].to_string());
+            if result.len() == limit {
+                return Ok(result);
             }
 
             // Add captured groups
             for i in 1..caps.len() {
-                if result.len() >= limit {
-                    break;
-                }
-                if let Some(group) = caps.get(i) {
-                    result.push(group.as_str().to_string());
+                result.push(caps.get(i).map(|g| g.as_str().to_string()).unwrap_or_default());
+                if result.len() == limit {
+                    return Ok(result);
                 }
             }
 
@@ -634,12 +645,7 @@ impl RegExpObject {
         }
 
         // Add remaining portion
-        if result.len() < limit {
-            result.push(string[last_end..].to_string());
-        }
-
-        // Trim to limit
-        result.truncate(limit);
+        result.push(string[last_end..].to_string());
 
         Ok(result)
     }
@@ -1,8 +1,30 @@
 //! String.prototype methods
 
+use crate::regexp::RegExpObject;
 use crate::value::{JsError, JsResult};
 use regex::Regex;
 
+/// Separator for [`StringPrototype::split`]
+#[derive(Debug, Clone, Copy)]
+pub enum SplitSeparator<'a> {
+    /// Split on every occurrence of a string
+    String(&'a str),
+    /// Split on regex matches, splicing in capturing groups
+    RegExp(&'a RegExpObject),
+}
+
+impl<'a> From<&'a str> for SplitSeparator<'a> {
+    fn from(separator: &'a str) -> Self {
+        SplitSeparator::String(separator)
+    }
+}
+
+impl<'a> From<&'a RegExpObject> for SplitSeparator<'a> {
+    fn from(separator: &'a RegExpObject) -> Self {
+        SplitSeparator::RegExp(separator)
+    }
+}
+
 /// String.prototype methods
 pub struct StringPrototype;
 
@@ -51,13 +73,25 @@ impl StringPrototype {
         }
     }
 
-    /// String.prototype.split(separator)
-    pub fn split(s: &str, separator: &str) -> JsResult<Vec<String>> {
-        if separator.is_empty() {
-            // Split into individual characters
-            Ok(s.chars().map(|c| c.to_string()).collect())
-        } else {
-            Ok(s.split(separator).map(|part| part.to_string()).collect())
+    /// String.prototype.split(separator, limit)
+    ///
+    /// An empty string separator splits into code points. `limit` caps the
+    /// number of parts; `None` means no limit (2^32 - 1).
+    pub fn split<'a>(
+        s: &str,
+        separator: impl Into<SplitSeparator<'a>>,
+        limit: Option<u32>,
+    ) -> JsResult<Vec<String>> {
+        let limit = limit.unwrap_or(u32::MAX) as usize;
+
+        match separator.into() {
+            SplitSeparator::RegExp(re) => re.symbol_split_with_limit(s, limit),
+            SplitSeparator::String("") => Ok(s.chars().take(limit).map(|c| c.to_string()).collect()),
+            SplitSeparator::String(separator) => Ok(s
+                .split(separator)
+                .take(limit)
+                .map(|part| part.to_string())
+                .collect()),
         }
     }
 
@@ -176,6 +210,32 @@ impl StringPrototype {
     pub fn includes(s: &str, search_string: &str) -> bool {
         s.contains(search_string)
     }
+
+    /// String.prototype.isWellFormed()
+    ///
+    /// A `&str` cannot hold lone surrogates, so this is always true; see
+    /// [`StringPrototype::is_well_formed_utf16`] for raw code units.
+    pub fn is_well_formed(s: &str) -> bool {
+        Self::is_well_formed_utf16(&s.encode_utf16().collect::<Vec<_>>())
+    }
+
+    /// String.prototype.isWellFormed() over UTF-16 code units
+    pub fn is_well_formed_utf16(units: &[u16]) -> bool {
+        char::decode_utf16(units.iter().copied()).all(|c| c.is_ok())
+    }
+
+    /// String.prototype.toWellFormed()
+    pub fn to_well_formed(s: &str) -> String {
+        s.to_string()
+    }
+
+    /// String.prototype.toWellFormed() over UTF-16 code units, replacing each
+    /// lone surrogate with U+FFFD
+    pub fn to_well_formed_utf16(units: &[u16]) -> String {
+        char::decode_utf16(units.iter().copied())
+            .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -208,10 +268,37 @@ mod tests {
 
     #[test]
     fn test_split() {
-        let parts = StringPrototype::split("a,b,c", ",").unwrap();
+        let parts = StringPrototype::split("a,b,c", ",", None).unwrap();
         assert_eq!(parts, vec!["a", "b", "c"]);
     }
 
+    #[test]
+    fn test_split_regexp_splices_captures() {
+        let re = RegExpObject::new("\\s*(-)\\s*", "").unwrap();
+        let parts = StringPrototype::split("a - b-c", &re, None).unwrap();
+        assert_eq!(parts, vec!["a", "-", "b", "-", "c"]);
+    }
+
+    #[test]
+    fn test_split_empty_separator_keeps_code_points() {
+        let parts = StringPrototype::split("a\u{1F600}b", "", None).unwrap();
+        assert_eq!(parts, vec!["a", "\u{1F600}", "b"]);
+
+        let re = RegExpObject::new("", "u").unwrap();
+        let parts = StringPrototype::split("\u{1F600}\u{1F601}", &re, None).unwrap();
+        assert_eq!(parts, vec!["\u{1F600}", "\u{1F601}"]);
+    }
+
+    #[test]
+    fn test_split_limit_truncates() {
+        assert_eq!(StringPrototype::split("a,b,c", ",", Some(2)).unwrap(), vec!["a", "b"]);
+        assert_eq!(StringPrototype::split("abc", "", Some(1)).unwrap(), vec!["a"]);
+        assert!(StringPrototype::split("a,b", ",", Some(0)).unwrap().is_empty());
+
+        let re = RegExpObject::new("(,)", "").unwrap();
+        assert_eq!(StringPrototype::split("a,b,c", &re, Some(3)).unwrap(), vec!["a", ",", "b"]);
+    }
+
     #[test]
     fn test_replace() {
         assert_eq!(
@@ -280,4 +367,23 @@ mod tests {
         assert!(StringPrototype::includes("hello world", "lo wo"));
         assert!(!StringPrototype::includes("hello world", "foo"));
     }
+
+    #[test]
+    fn test_well_formed_lone_surrogates() {
+        // "a" + lone high surrogate + "b" + lone low surrogate
+        let units = [0x61, 0xD83D, 0x62, 0xDE00];
+        assert!(!StringPrototype::is_well_formed_utf16(&units));
+        assert_eq!(StringPrototype::to_well_formed_utf16(&units), "a\u{FFFD}b\u{FFFD}");
+
+        // A surrogate pair is a single well-formed code point
+        let pair: Vec<u16> = "\u{1F600}".encode_utf16().collect();
+        assert!(StringPrototype::is_well_formed_utf16(&pair));
+        assert_eq!(StringPrototype::to_well_formed_utf16(&pair), "\u{1F600}");
+    }
+
+    #[test]
+    fn test_well_formed_normal_string_unchanged() {
+        assert!(StringPrototype::is_well_formed("héllo \u{1F600}"));
+        assert_eq!(StringPrototype::to_well_formed("héllo \u{1F600}"), "héllo \u{1F600}");
+    }
 }
@@ -68,6 +68,13 @@ impl ArrayBuffer {
     pub(crate) fn get_data(&self) -> Rc<RefCell<Vec<u8>>> {
         self.data.clone()
     }
+
+    /// Create an ArrayBuffer that takes ownership of `bytes`
+    pub(crate) fn from_bytes(bytes: Vec<u8>) -> Self {
+        ArrayBuffer {
+            data: Rc::new(RefCell::new(bytes)),
+        }
+    }
 }
 
 /// TypedArray element kind
@@ -3,7 +3,7 @@
 //! This module provides a high-level JavaScript value type for use with built-in methods.
 
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::fmt;
 use std::rc::Rc;
 
@@ -11,7 +11,7 @@ use crate::async_generator::AsyncGeneratorObject;
 use crate::error::JsErrorObject;
 use crate::iterator::GeneratorObject;
 use crate::regexp::RegExpObject;
-use crate::symbol::SymbolValue;
+use crate::symbol::{SymbolConstructor, SymbolValue};
 use crate::weakref::{FinalizationRegistryData, WeakRefData};
 use num_bigint::BigInt as NumBigInt;
 
@@ -76,11 +76,59 @@ impl JsError {
     pub fn range_error(message: impl Into<String>) -> Self {
         JsError::new(format!("RangeError: {}", message.into()))
     }
+
+    /// Create a URIError
+    pub fn uri_error(message: impl Into<String>) -> Self {
+        JsError::new(format!("URIError: {}", message.into()))
+    }
 }
 
 /// Result type for JavaScript operations
 pub type JsResult<T> = Result<T, JsError>;
 
+/// Conversion hint passed to `to_primitive` and `Symbol.toPrimitive` methods
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreferredType {
+    /// No preference, as for `+` and `==`
+    Default,
+    /// Prefer a number, as for unary `+` and arithmetic
+    Number,
+    /// Prefer a string, as for template literals and `String()`
+    String,
+}
+
+impl PreferredType {
+    /// Hint string passed to a `Symbol.toPrimitive` method
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PreferredType::Default => "default",
+            PreferredType::Number => "number",
+            PreferredType::String => "string",
+        }
+    }
+}
+
+/// Attributes of an own data property
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PropertyAttributes {
+    /// Whether the value can be changed
+    pub writable: bool,
+    /// Whether the property shows up in for...in loops
+    pub enumerable: bool,
+    /// Whether the property can be deleted or its attributes changed
+    pub configurable: bool,
+}
+
+impl Default for PropertyAttributes {
+    fn default() -> Self {
+        PropertyAttributes {
+            writable: true,
+            enumerable: true,
+            configurable: true,
+        }
+    }
+}
+
 /// Internal object data
 #[derive(Debug, Clone)]
 pub struct ObjectData {
@@ -92,13 +140,55 @@ pub struct ObjectData {
     pub prototype: Option<Box<JsValue>>,
     /// Whether object is extensible (None means default true)
     pub extensible: Option<bool>,
+    /// Attributes of properties that differ from the defaults
+    pub attributes: HashMap<String, PropertyAttributes>,
+}
+
+impl ObjectData {
+    /// Attributes of the own property `key`
+    pub fn attributes_of(&self, key: &str) -> PropertyAttributes {
+        self.attributes.get(key).copied().unwrap_or_default()
+    }
 }
 
 /// Internal array data
-#[derive(Debug, Clone)]
+///
+/// Sparse arrays keep one entry per index up to `length`; a hole's entry
+/// holds undefined and its index is recorded in `holes`. Callback methods
+/// skip holes, while `length` always counts them.
+#[derive(Debug, Clone, Default)]
 pub struct ArrayData {
     /// Array elements
     pub elements: Vec<JsValue>,
+    /// Indices with no element (holes)
+    pub holes: BTreeSet<usize>,
+    /// The `constructor` of an Array subclass instance; `None` for plain
+    /// arrays, whose constructor is the intrinsic Array
+    pub constructor: Option<JsValue>,
+}
+
+impl ArrayData {
+    /// Check if the index is a hole
+    pub fn is_hole(&self, index: usize) -> bool {
+        self.holes.contains(&index)
+    }
+
+    /// Iterate over the elements that are present, with their indices
+    pub fn present(&self) -> impl DoubleEndedIterator<Item = (usize, &JsValue)> {
+        self.elements
+            .iter()
+            .enumerate()
+            .filter(move |(i, _)| !self.holes.contains(i))
+    }
+
+    /// Move holes to new indices after elements have been shifted;
+    /// `None` drops the hole
+    pub fn reindex_holes<F>(&mut self, map: F)
+    where
+        F: Fn(usize) -> Option<usize>,
+    {
+        self.holes = self.holes.iter().filter_map(|&i| map(i)).collect();
+    }
 }
 
 /// Internal map data - preserves insertion order
@@ -153,6 +243,8 @@ impl Clone for FunctionData {
 pub struct ConstructorData {
     /// The constructor implementation
     pub func: Box<dyn Fn(Vec<JsValue>) -> JsResult<JsValue>>,
+    /// The constructor's `prototype`, inherited by the objects it constructs
+    pub prototype: JsValue,
 }
 
 impl std::fmt::Debug for ConstructorData {
@@ -254,6 +346,7 @@ impl JsValue {
             symbol_properties: HashMap::new(),
             prototype: None,
             extensible: None, // Default to true
+            attributes: HashMap::new(),
         })))
     }
 
@@ -264,6 +357,7 @@ impl JsValue {
             symbol_properties: HashMap::new(),
             prototype: Some(Box::new(proto.clone())),
             extensible: None, // Default to true
+            attributes: HashMap::new(),
         })))
     }
 
@@ -284,6 +378,7 @@ impl JsValue {
     {
         JsValue::Constructor(Rc::new(RefCell::new(ConstructorData {
             func: Box::new(func),
+            prototype: JsValue::object(),
         })))
     }
 
@@ -294,14 +389,31 @@ impl JsValue {
 
     /// Create empty array
     pub fn array() -> Self {
-        JsValue::Array(Rc::new(RefCell::new(ArrayData {
-            elements: Vec::new(),
-        })))
+        JsValue::Array(Rc::new(RefCell::new(ArrayData::default())))
     }
 
     /// Create array from values
     pub fn array_from(values: Vec<JsValue>) -> Self {
-        JsValue::Array(Rc::new(RefCell::new(ArrayData { elements: values })))
+        JsValue::Array(Rc::new(RefCell::new(ArrayData {
+            elements: values,
+            holes: BTreeSet::new(),
+            constructor: None,
+        })))
+    }
+
+    /// Create a sparse array; `None` entries become holes
+    pub fn array_with_holes(slots: Vec<Option<JsValue>>) -> Self {
+        let mut data = ArrayData::default();
+        for (i, slot) in slots.into_iter().enumerate() {
+            match slot {
+                Some(value) => data.elements.push(value),
+                None => {
+                    data.elements.push(JsValue::undefined());
+                    data.holes.insert(i);
+                }
+            }
+        }
+        JsValue::Array(Rc::new(RefCell::new(data)))
     }
 
     /// Create an error value from a JsErrorObject
@@ -580,9 +692,15 @@ impl JsValue {
     }
 
     /// Set object property
+    ///
+    /// On a constructor only `prototype` can be set.
     pub fn set(&self, key: &str, value: JsValue) {
-        if let JsValue::Object(obj) = self {
-            obj.borrow_mut().properties.insert(key.to_string(), value);
+        match self {
+            JsValue::Object(obj) => {
+                obj.borrow_mut().properties.insert(key.to_string(), value);
+            }
+            JsValue::Constructor(ctor) if key == "prototype" => ctor.borrow_mut().prototype = value,
+            _ => {}
         }
     }
 
@@ -590,10 +708,39 @@ impl JsValue {
     pub fn get(&self, key: &str) -> Option<JsValue> {
         match self {
             JsValue::Object(obj) => obj.borrow().properties.get(key).cloned(),
+            JsValue::Constructor(ctor) if key == "prototype" => {
+                Some(ctor.borrow().prototype.clone())
+            }
             _ => None,
         }
     }
 
+    /// [[Construct]]: `new self(...args)` with `new.target` set to `new_target`
+    ///
+    /// A constructed object inherits from `new_target.prototype`, and a
+    /// constructed array records `new_target` as its subclass constructor, so
+    /// a subclass can reuse the base constructor's initialization.
+    pub fn construct(&self, args: Vec<JsValue>, new_target: &JsValue) -> JsResult<JsValue> {
+        let JsValue::Constructor(ctor) = self else {
+            return Err(JsError::type_error("Value is not a constructor"));
+        };
+        let JsValue::Constructor(new_target_ctor) = new_target else {
+            return Err(JsError::type_error("newTarget is not a constructor"));
+        };
+        let result = (ctor.borrow().func)(args)?;
+        match &result {
+            JsValue::Object(obj) => {
+                let prototype = new_target_ctor.borrow().prototype.clone();
+                obj.borrow_mut().prototype = Some(Box::new(prototype));
+            }
+            JsValue::Array(array) if !Rc::ptr_eq(ctor, new_target_ctor) => {
+                array.borrow_mut().constructor = Some(new_target.clone());
+            }
+            _ => {}
+        }
+        Ok(result)
+    }
+
     /// Check if object has own property
     pub fn has_own(&self, key: &str) -> bool {
         match self {
@@ -685,6 +832,187 @@ impl JsValue {
         }
     }
 
+    /// Convert to number (ECMAScript ToNumber)
+    ///
+    /// Symbols and BigInts cannot be implicitly converted and produce a TypeError.
+    pub fn to_number(&self) -> JsResult<f64> {
+        match self {
+            JsValue::Undefined => Ok(f64::NAN),
+            JsValue::Null => Ok(0.0),
+            JsValue::Boolean(b) => Ok(if *b { 1.0 } else { 0.0 }),
+            JsValue::Number(n) => Ok(*n),
+            JsValue::String(s) => Ok(string_to_number(s)),
+            JsValue::Symbol(_) => Err(JsError::type_error(
+                "Cannot convert a Symbol value to a number",
+            )),
+            JsValue::BigInt(_) => Err(JsError::type_error(
+                "Cannot convert a BigInt value to a number",
+            )),
+            // Objects convert through their primitive value, so [] is 0 and [7] is 7
+            _ => self.to_primitive(PreferredType::Number)?.to_number(),
+        }
+    }
+
+    /// Convert to boolean (ECMAScript ToBoolean)
+    pub fn to_boolean(&self) -> bool {
+        match self {
+            JsValue::Undefined | JsValue::Null => false,
+            JsValue::Boolean(b) => *b,
+            JsValue::Number(n) => !(n.is_nan() || *n == 0.0),
+            JsValue::String(s) => !s.is_empty(),
+            JsValue::BigInt(n) => n.inner().sign() != num_bigint::Sign::NoSign,
+            _ => true,
+        }
+    }
+
+    /// Check whether the value is a primitive rather than an object
+    pub fn is_primitive(&self) -> bool {
+        matches!(
+            self,
+            JsValue::Undefined
+                | JsValue::Null
+                | JsValue::Boolean(_)
+                | JsValue::Number(_)
+                | JsValue::String(_)
+                | JsValue::Symbol(_)
+                | JsValue::BigInt(_)
+        )
+    }
+
+    /// Convert to a primitive value (ECMAScript ToPrimitive)
+    ///
+    /// Objects consult their `Symbol.toPrimitive` method first, passing the
+    /// hint. Without one, `valueOf` and `toString` are tried in hint order:
+    /// `toString` first for a string hint, `valueOf` first otherwise. An
+    /// object without its own `toString` uses its built-in string form.
+    pub fn to_primitive(&self, hint: PreferredType) -> JsResult<JsValue> {
+        if self.is_primitive() {
+            return Ok(self.clone());
+        }
+
+        let exotic = self.get_symbol_in_chain(&SymbolConstructor::to_primitive());
+        match exotic {
+            None | Some(JsValue::Undefined) | Some(JsValue::Null) => {}
+            Some(method) => {
+                let result = self
+                    .call_method(&method, vec![JsValue::string(hint.as_str())])
+                    .ok_or_else(|| JsError::type_error("Symbol.toPrimitive is not a function"))??;
+                if result.is_primitive() {
+                    return Ok(result);
+                }
+                return Err(JsError::type_error("Cannot convert object to primitive value"));
+            }
+        }
+
+        // OrdinaryToPrimitive
+        let order = match hint {
+            PreferredType::String => ["toString", "valueOf"],
+            PreferredType::Default | PreferredType::Number => ["valueOf", "toString"],
+        };
+        for name in order {
+            match self.get_in_chain(name) {
+                Some(method) => {
+                    if let Some(result) = self.call_method(&method, Vec::new()) {
+                        let result = result?;
+                        if result.is_primitive() {
+                            return Ok(result);
+                        }
+                    }
+                }
+                // Built-in toString; the built-in valueOf returns the object itself
+                None if name == "toString" => return Ok(JsValue::string(self.to_js_string())),
+                None => {}
+            }
+        }
+        Err(JsError::type_error("Cannot convert object to primitive value"))
+    }
+
+    /// Convert to a string (ECMAScript ToString)
+    ///
+    /// Unlike `to_js_string`, objects convert through `to_primitive` with a
+    /// string hint and symbols produce a TypeError.
+    pub fn coerce_to_string(&self) -> JsResult<String> {
+        match self {
+            JsValue::Symbol(sym) => sym.to_string_implicit(),
+            JsValue::BigInt(n) => Ok(n.to_string()),
+            value if value.is_primitive() => Ok(value.to_js_string()),
+            _ => self.to_primitive(PreferredType::String)?.coerce_to_string(),
+        }
+    }
+
+    /// Apply the binary `+` operator
+    ///
+    /// Both operands convert with the default hint; if either primitive is a
+    /// string the result is their concatenation, otherwise their sum.
+    pub fn add(&self, other: &JsValue) -> JsResult<JsValue> {
+        let left = self.to_primitive(PreferredType::Default)?;
+        let right = other.to_primitive(PreferredType::Default)?;
+        match (&left, &right) {
+            (JsValue::String(_), _) | (_, JsValue::String(_)) => Ok(JsValue::string(format!(
+                "{}{}",
+                left.coerce_to_string()?,
+                right.coerce_to_string()?
+            ))),
+            (JsValue::BigInt(a), JsValue::BigInt(b)) => {
+                Ok(JsValue::bigint(BigIntValue::new(a.inner() + b.inner())))
+            }
+            (JsValue::BigInt(_), _) | (_, JsValue::BigInt(_)) => Err(JsError::type_error(
+                "Cannot mix BigInt and other types, use explicit conversions",
+            )),
+            _ => Ok(JsValue::number(left.to_number()? + right.to_number()?)),
+        }
+    }
+
+    /// Compare with the `==` operator (ECMAScript IsLooselyEqual)
+    ///
+    /// An object compared with a primitive converts with the default hint.
+    pub fn loose_equals(&self, other: &JsValue) -> JsResult<bool> {
+        match (self, other) {
+            (JsValue::Undefined | JsValue::Null, JsValue::Undefined | JsValue::Null) => Ok(true),
+            (JsValue::Undefined | JsValue::Null, _) | (_, JsValue::Undefined | JsValue::Null) => {
+                Ok(false)
+            }
+            (JsValue::Number(_), JsValue::String(_)) | (JsValue::String(_), JsValue::Number(_)) => {
+                Ok(JsValue::number(self.to_number()?).equals(&JsValue::number(other.to_number()?)))
+            }
+            (JsValue::Boolean(_), _) => JsValue::number(self.to_number()?).loose_equals(other),
+            (_, JsValue::Boolean(_)) => self.loose_equals(&JsValue::number(other.to_number()?)),
+            (a, b) if a.is_primitive() == b.is_primitive() => Ok(a.equals(b)),
+            (a, b) if a.is_primitive() => a.loose_equals(&b.to_primitive(PreferredType::Default)?),
+            (a, b) => a.to_primitive(PreferredType::Default)?.loose_equals(b),
+        }
+    }
+
+    /// Look up `key` on the object or its prototype chain
+    fn get_in_chain(&self, key: &str) -> Option<JsValue> {
+        let mut current = self.clone();
+        loop {
+            if let Some(value) = current.get(key) {
+                return Some(value);
+            }
+            current = current.get_prototype()?;
+        }
+    }
+
+    /// Look up symbol-keyed `sym` on the object or its prototype chain
+    fn get_symbol_in_chain(&self, sym: &SymbolValue) -> Option<JsValue> {
+        let mut current = self.clone();
+        loop {
+            if let Some(value) = current.get_symbol(sym) {
+                return Some(value);
+            }
+            current = current.get_prototype()?;
+        }
+    }
+
+    /// Call `method` with this value as `this`; `None` if it is not callable
+    fn call_method(&self, method: &JsValue, args: Vec<JsValue>) -> Option<JsResult<JsValue>> {
+        match method {
+            JsValue::Function(f) => Some((f.borrow().func)(self.clone(), args)),
+            _ => None,
+        }
+    }
+
     /// Get the type of the value (as JavaScript typeof would return)
     pub fn type_of(&self) -> &'static str {
         match self {
@@ -737,12 +1065,11 @@ impl JsValue {
             (JsValue::RegExp(a), JsValue::RegExp(b)) => Rc::ptr_eq(a, b),
             (JsValue::Function(a), JsValue::Function(b)) => Rc::ptr_eq(a, b),
             (JsValue::Constructor(a), JsValue::Constructor(b)) => Rc::ptr_eq(a, b),
-            // Proxy comparison is by reference to the proxy data
-            (JsValue::Proxy(_), JsValue::Proxy(_)) => false, // Proxies are compared by internal data ref
+            (JsValue::Proxy(a), JsValue::Proxy(b)) => a.ptr_eq(b),
             (JsValue::WeakMap(a), JsValue::WeakMap(b)) => Rc::ptr_eq(a, b),
             (JsValue::WeakSet(a), JsValue::WeakSet(b)) => Rc::ptr_eq(a, b),
-            (JsValue::Generator(_), JsValue::Generator(_)) => false, // Generators are not equal
-            (JsValue::AsyncGenerator(_), JsValue::AsyncGenerator(_)) => false, // AsyncGenerators are not equal
+            (JsValue::Generator(a), JsValue::Generator(b)) => a.ptr_eq(b),
+            (JsValue::AsyncGenerator(a), JsValue::AsyncGenerator(b)) => a.ptr_eq(b),
             (JsValue::BigInt(a), JsValue::BigInt(b)) => a == b,
             (JsValue::WeakRef(a), JsValue::WeakRef(b)) => Rc::ptr_eq(a, b),
             (JsValue::FinalizationRegistry(a), JsValue::FinalizationRegistry(b)) => {
@@ -752,6 +1079,18 @@ impl JsValue {
         }
     }
 
+    /// SameValue comparison (`Object.is`)
+    ///
+    /// Like same_value_zero() but distinguishes -0 from +0.
+    pub fn same_value(&self, other: &JsValue) -> bool {
+        match (self, other) {
+            (JsValue::Number(a), JsValue::Number(b)) if *a == 0.0 && *b == 0.0 => {
+                a.is_sign_negative() == b.is_sign_negative()
+            }
+            _ => self.same_value_zero(other),
+        }
+    }
+
     /// SameValueZero comparison for Map/Set key equality
     ///
     /// Like equals() but treats NaN === NaN and -0 === +0
@@ -780,11 +1119,11 @@ impl JsValue {
             (JsValue::RegExp(a), JsValue::RegExp(b)) => Rc::ptr_eq(a, b),
             (JsValue::Function(a), JsValue::Function(b)) => Rc::ptr_eq(a, b),
             (JsValue::Constructor(a), JsValue::Constructor(b)) => Rc::ptr_eq(a, b),
-            (JsValue::Proxy(_), JsValue::Proxy(_)) => false,
+            (JsValue::Proxy(a), JsValue::Proxy(b)) => a.ptr_eq(b),
             (JsValue::WeakMap(a), JsValue::WeakMap(b)) => Rc::ptr_eq(a, b),
             (JsValue::WeakSet(a), JsValue::WeakSet(b)) => Rc::ptr_eq(a, b),
-            (JsValue::Generator(_), JsValue::Generator(_)) => false,
-            (JsValue::AsyncGenerator(_), JsValue::AsyncGenerator(_)) => false,
+            (JsValue::Generator(a), JsValue::Generator(b)) => a.ptr_eq(b),
+            (JsValue::AsyncGenerator(a), JsValue::AsyncGenerator(b)) => a.ptr_eq(b),
             (JsValue::BigInt(a), JsValue::BigInt(b)) => a == b,
             (JsValue::WeakRef(a), JsValue::WeakRef(b)) => Rc::ptr_eq(a, b),
             (JsValue::FinalizationRegistry(a), JsValue::FinalizationRegistry(b)) => {
@@ -802,6 +1141,40 @@ impl PartialEq for JsValue {
     }
 }
 
+/// StringToNumber: parse a string using the StringNumericLiteral grammar
+fn string_to_number(s: &str) -> f64 {
+    let s = s.trim();
+    if s.is_empty() {
+        return 0.0;
+    }
+
+    match s {
+        "Infinity" | "+Infinity" => return f64::INFINITY,
+        "-Infinity" => return f64::NEG_INFINITY,
+        _ => {}
+    }
+
+    // Prefixed integer literals are unsigned in the string grammar
+    let lower = s.to_ascii_lowercase();
+    for (prefix, radix) in [("0x", 16), ("0o", 8), ("0b", 2)] {
+        if let Some(digits) = lower.strip_prefix(prefix) {
+            if digits.is_empty() {
+                return f64::NAN;
+            }
+            return digits.chars().try_fold(0.0, |acc, c| {
+                c.to_digit(radix).map(|d| acc * radix as f64 + d as f64)
+            })
+            .unwrap_or(f64::NAN);
+        }
+    }
+
+    // Rust accepts "inf"/"nan" spellings that JavaScript does not
+    if s.chars().any(|c| c.is_ascii_alphabetic() && c != 'e' && c != 'E') {
+        return f64::NAN;
+    }
+    s.parse::<f64>().unwrap_or(f64::NAN)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -860,4 +1233,76 @@ mod tests {
         assert!(arr.is_array());
         assert_eq!(arr.array_length(), 2);
     }
+
+    #[test]
+    fn test_to_number() {
+        assert!(JsValue::undefined().to_number().unwrap().is_nan());
+        assert_eq!(JsValue::null().to_number().unwrap(), 0.0);
+        assert_eq!(JsValue::boolean(true).to_number().unwrap(), 1.0);
+        assert_eq!(JsValue::string("  12.5 ").to_number().unwrap(), 12.5);
+        assert_eq!(JsValue::string("").to_number().unwrap(), 0.0);
+        assert_eq!(JsValue::string("0x1F").to_number().unwrap(), 31.0);
+        assert_eq!(JsValue::string("-Infinity").to_number().unwrap(), f64::NEG_INFINITY);
+        assert!(JsValue::string("inf").to_number().unwrap().is_nan());
+        assert!(JsValue::string("12px").to_number().unwrap().is_nan());
+        assert_eq!(JsValue::array_from(vec![]).to_number().unwrap(), 0.0);
+        assert!(JsValue::object().to_number().unwrap().is_nan());
+    }
+
+    #[test]
+    fn test_symbol_to_primitive_receives_hint() {
+        let obj = JsValue::object();
+        obj.set_symbol(
+            &SymbolConstructor::to_primitive(),
+            JsValue::function(|_this, args| {
+                Ok(match args[0].as_string().as_deref() {
+                    Some("number") => JsValue::number(42.0),
+                    Some("string") => JsValue::string("str"),
+                    _ => JsValue::string("default"),
+                })
+            }),
+        );
+
+        // `${obj}`, +obj and obj + ""
+        assert_eq!(obj.coerce_to_string().unwrap(), "str");
+        assert_eq!(obj.to_number().unwrap(), 42.0);
+        assert_eq!(obj.add(&JsValue::string("")).unwrap(), JsValue::string("default"));
+        assert!(obj.loose_equals(&JsValue::string("default")).unwrap());
+    }
+
+    #[test]
+    fn test_symbol_to_primitive_must_return_primitive() {
+        let obj = JsValue::object();
+        obj.set_symbol(
+            &SymbolConstructor::to_primitive(),
+            JsValue::function(|this, _args| Ok(this)),
+        );
+        assert!(obj.to_number().is_err());
+
+        let obj = JsValue::object();
+        obj.set_symbol(&SymbolConstructor::to_primitive(), JsValue::number(1.0));
+        assert!(obj.coerce_to_string().is_err());
+    }
+
+    #[test]
+    fn test_ordinary_to_primitive_order() {
+        let obj = JsValue::object();
+        obj.set("valueOf", JsValue::function(|_this, _args| Ok(JsValue::number(7.0))));
+        obj.set("toString", JsValue::function(|_this, _args| Ok(JsValue::string("seven"))));
+
+        assert_eq!(obj.to_number().unwrap(), 7.0);
+        assert_eq!(obj.coerce_to_string().unwrap(), "seven");
+        assert_eq!(obj.add(&JsValue::number(1.0)).unwrap(), JsValue::number(8.0));
+
+        // A valueOf returning an object falls through to toString
+        let obj = JsValue::object();
+        obj.set("valueOf", JsValue::function(|this, _args| Ok(this)));
+        obj.set("toString", JsValue::function(|_this, _args| Ok(JsValue::string("5"))));
+        assert_eq!(obj.to_number().unwrap(), 5.0);
+
+        // Without its own toString, an object uses its built-in string form
+        let obj = JsValue::object();
+        obj.set("valueOf", JsValue::function(|this, _args| Ok(this)));
+        assert_eq!(obj.coerce_to_string().unwrap(), "[object Object]");
+    }
 }
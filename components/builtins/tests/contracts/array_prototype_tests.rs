@@ -107,7 +107,7 @@ fn test_array_reduce() {
         JsValue::number(3.0),
     ]);
 
-    let result = ArrayPrototype::reduce(&arr, JsValue::number(0.0), |acc, v| {
+    let result = ArrayPrototype::reduce(&arr, Some(JsValue::number(0.0)), |acc, v| {
         Ok(JsValue::number(acc.as_number().unwrap() + v.as_number().unwrap()))
     });
     assert!(result.is_ok());
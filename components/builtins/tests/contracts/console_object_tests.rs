@@ -60,7 +60,7 @@ fn test_console_assert_passing() {
     let output = Rc::new(RefCell::new(Vec::new()));
     let console = ConsoleObject::new_with_output(output.clone());
 
-    console.assert(true, "should not print");
+    console.assert(&JsValue::boolean(true), &[JsValue::string("should not print")]);
 
     // Passing assertion should not output anything
     assert_eq!(output.borrow().len(), 0);
@@ -71,7 +71,7 @@ fn test_console_assert_failing() {
     let output = Rc::new(RefCell::new(Vec::new()));
     let console = ConsoleObject::new_with_output(output.clone());
 
-    console.assert(false, "assertion failed");
+    console.assert(&JsValue::boolean(false), &[JsValue::string("assertion failed")]);
 
     // Failing assertion should output
     assert!(output.borrow().len() > 0);
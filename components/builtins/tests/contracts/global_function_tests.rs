@@ -0,0 +1,48 @@
+//! Contract tests for URI-handling global functions
+
+use builtins::{
+    decode_uri, decode_uri_component, encode_uri, encode_uri_component,
+    encode_uri_component_utf16,
+};
+
+#[test]
+fn test_encode_uri_component_space_slash_question() {
+    assert_eq!(encode_uri_component(" /?").unwrap(), "%20%2F%3F");
+}
+
+#[test]
+fn test_encode_uri_component_leaves_unreserved() {
+    let unreserved = "AZaz09-_.!~*'()";
+    assert_eq!(encode_uri_component(unreserved).unwrap(), unreserved);
+}
+
+#[test]
+fn test_decode_uri_component_roundtrip() {
+    let original = "name=Jörg & co/π?x#1 😀";
+    let encoded = encode_uri_component(original).unwrap();
+    assert_eq!(decode_uri_component(&encoded).unwrap(), original);
+
+    let encoded = encode_uri(original).unwrap();
+    assert_eq!(decode_uri(&encoded).unwrap(), original);
+}
+
+#[test]
+fn test_encode_uri_component_lone_surrogate_is_uri_error() {
+    let err = encode_uri_component_utf16(&[0x61, 0xD800, 0x62]).unwrap_err();
+    assert!(err.message.starts_with("URIError"), "got {}", err.message);
+
+    let err = encode_uri_component_utf16(&[0xDC00]).unwrap_err();
+    assert!(err.message.starts_with("URIError"));
+
+    // A proper surrogate pair is fine
+    assert_eq!(
+        encode_uri_component_utf16(&[0xD83D, 0xDE00]).unwrap(),
+        "%F0%9F%98%80"
+    );
+}
+
+#[test]
+fn test_decode_uri_component_malformed_is_uri_error() {
+    let err = decode_uri_component("%E0%A4%A").unwrap_err();
+    assert!(err.message.starts_with("URIError"));
+}
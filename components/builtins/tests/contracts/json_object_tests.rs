@@ -100,3 +100,26 @@ fn test_json_stringify_undefined() {
     assert!(result.is_ok());
     assert_eq!(result.unwrap(), "undefined");
 }
+
+#[test]
+fn test_json_parse_proto_key_is_own_property() {
+    let result = JSONObject::parse(r#"{"__proto__": {"polluted": true}}"#).unwrap();
+
+    assert!(result.has_own("__proto__"));
+    assert!(result.get_prototype().is_none());
+    let proto_prop = result.get("__proto__").unwrap();
+    assert_eq!(proto_prop.get("polluted"), Some(JsValue::boolean(true)));
+
+    // Nothing leaked onto the object itself or onto other objects
+    assert!(!result.has_own("polluted"));
+    let fresh = JsValue::object();
+    assert!(fresh.get("polluted").is_none());
+    assert!(fresh.get_prototype().is_none());
+}
+
+#[test]
+fn test_json_parse_duplicate_proto_keys_last_wins() {
+    let result = JSONObject::parse(r#"{"__proto__": 1, "__proto__": 2}"#).unwrap();
+    assert_eq!(result.get("__proto__"), Some(JsValue::number(2.0)));
+    assert!(result.get_prototype().is_none());
+}
@@ -19,3 +19,4 @@ mod bigint_tests;
 mod weak_collections_tests;
 mod iterator_generator_tests;
 mod weakref_finalization_registry_tests;
+mod global_function_tests;
@@ -1,6 +1,6 @@
 //! Contract tests for NumberPrototype
 
-use builtins::{NumberPrototype, JsValue};
+use builtins::{global_is_nan, JsValue, NumberObject, NumberPrototype};
 
 #[test]
 fn test_number_to_string() {
@@ -37,3 +37,30 @@ fn test_number_value_of() {
     assert!(result.is_ok());
     assert_eq!(result.unwrap(), 42.0);
 }
+
+#[test]
+fn test_number_is_nan_vs_global_is_nan() {
+    // Number.isNaN does not coerce; the global isNaN does
+    assert!(!NumberObject::is_nan_value(&JsValue::string("NaN")));
+    assert!(global_is_nan(&JsValue::string("NaN")).unwrap());
+}
+
+#[test]
+fn test_number_is_safe_integer() {
+    assert!(!NumberObject::is_safe_integer(2f64.powi(53)));
+    assert!(NumberObject::is_safe_integer(2f64.powi(53) - 1.0));
+    assert_eq!(NumberObject::MAX_SAFE_INTEGER, 9007199254740991.0);
+    assert_eq!(NumberObject::MIN_SAFE_INTEGER, -9007199254740991.0);
+}
+
+#[test]
+fn test_number_epsilon() {
+    assert_eq!(NumberObject::EPSILON, 2f64.powi(-52));
+}
+
+#[test]
+fn test_number_parse_int_and_float() {
+    assert_eq!(NumberObject::parse_int("42px", None), 42.0);
+    assert_eq!(NumberObject::parse_int("ff", Some(16)), 255.0);
+    assert_eq!(NumberObject::parse_float("3.5"), 3.5);
+}
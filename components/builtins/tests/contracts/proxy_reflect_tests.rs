@@ -190,6 +190,65 @@ mod reflect_tests {
         assert!(result.is_object());
         assert_eq!(result.get("name").unwrap().as_string(), Some("test".to_string()));
     }
+
+    #[test]
+    fn test_reflect_construct_uses_new_target_prototype() {
+        let base = JsValue::constructor(|_args| {
+            let obj = JsValue::object();
+            obj.set("fromBase", JsValue::boolean(true));
+            Ok(obj)
+        });
+        let derived = JsValue::constructor(|_args| Ok(JsValue::object()));
+        let derived_proto = derived.get("prototype").unwrap();
+        derived_proto.set("greet", JsValue::string("hi"));
+
+        let result = ReflectObject::construct(&base, &[], Some(&derived)).unwrap();
+
+        assert!(result.get_prototype().unwrap().equals(&derived_proto));
+        assert!(!result.get_prototype().unwrap().equals(&base.get("prototype").unwrap()));
+        assert_eq!(result.get("fromBase").unwrap().as_boolean(), Some(true));
+    }
+
+    #[test]
+    fn test_reflect_construct_default_new_target_matches_new() {
+        let ctor = JsValue::constructor(|args| {
+            let obj = JsValue::object();
+            obj.set("arg", args.first().cloned().unwrap_or_else(JsValue::undefined));
+            Ok(obj)
+        });
+
+        let reflected = ReflectObject::construct(&ctor, &[JsValue::number(1.0)], None).unwrap();
+        let constructed = ctor.construct(vec![JsValue::number(1.0)], &ctor).unwrap();
+
+        let prototype = ctor.get("prototype").unwrap();
+        assert!(reflected.get_prototype().unwrap().equals(&prototype));
+        assert!(constructed.get_prototype().unwrap().equals(&prototype));
+        assert_eq!(reflected.get("arg").unwrap().as_number(), Some(1.0));
+    }
+
+    #[test]
+    fn test_reflect_construct_array_subclass() {
+        let array_ctor = JsValue::constructor(|_args| Ok(JsValue::array()));
+        let subclass = JsValue::constructor(|_args| Ok(JsValue::array()));
+
+        let instance = ReflectObject::construct(&array_ctor, &[], Some(&subclass)).unwrap();
+        let plain = ReflectObject::construct(&array_ctor, &[], None).unwrap();
+
+        match (&instance, &plain) {
+            (JsValue::Array(instance), JsValue::Array(plain)) => {
+                assert!(instance.borrow().constructor.as_ref().unwrap().equals(&subclass));
+                assert!(plain.borrow().constructor.is_none());
+            }
+            _ => panic!("expected arrays"),
+        }
+    }
+
+    #[test]
+    fn test_reflect_construct_rejects_non_constructor_new_target() {
+        let ctor = JsValue::constructor(|_args| Ok(JsValue::object()));
+        let result = ReflectObject::construct(&ctor, &[], Some(&JsValue::object()));
+        assert!(result.is_err());
+    }
 }
 
 mod proxy_tests {
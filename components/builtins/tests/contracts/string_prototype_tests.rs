@@ -21,7 +21,7 @@ fn test_string_slice() {
 #[test]
 fn test_string_split() {
     let s = "a,b,c";
-    let result = StringPrototype::split(s, ",");
+    let result = StringPrototype::split(s, ",", None);
     assert!(result.is_ok());
     let parts = result.unwrap();
     assert_eq!(parts, vec!["a", "b", "c"]);
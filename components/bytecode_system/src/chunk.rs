@@ -4,11 +4,12 @@
 
 use crate::instruction::{Instruction, SourcePosition};
 use crate::opcode::{Opcode, RegisterId, UpvalueDescriptor};
+use crate::counters::InstructionCounters;
 use crate::optimizer::Optimizer;
 use crate::value::Value;
 
 /// A compiled bytecode chunk containing instructions and constants
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
 pub struct BytecodeChunk {
     /// Sequence of bytecode instructions
     pub instructions: Vec<Instruction>,
@@ -18,9 +19,19 @@ pub struct BytecodeChunk {
     pub register_count: u32,
     /// Nested function bytecode chunks (for closures)
     pub nested_functions: Vec<BytecodeChunk>,
+    /// Per-instruction execution counters, present only while profiling
+    pub counters: Option<InstructionCounters>,
+    /// Source names of the registers holding variables, for debuggers; like
+    /// the upvalue names, not written by `to_bytes`
+    pub local_names: Vec<(RegisterId, String)>,
+    /// Source names of the captured upvalues, by upvalue index, for debuggers
+    pub upvalue_names: Vec<String>,
 }
 
 impl BytecodeChunk {
+    /// Binary format version written by `to_bytes`
+    pub const FORMAT_VERSION: u8 = 2;
+
     /// Create a new empty bytecode chunk
     pub fn new() -> Self {
         Self {
@@ -28,6 +39,9 @@ impl BytecodeChunk {
             constants: Vec::new(),
             register_count: 0,
             nested_functions: Vec::new(),
+            counters: None,
+            local_names: Vec::new(),
+            upvalue_names: Vec::new(),
         }
     }
 
@@ -36,6 +50,13 @@ impl BytecodeChunk {
         &self.nested_functions
     }
 
+    /// Attach zeroed execution counters, unless counters are already attached
+    pub fn enable_counters(&mut self) {
+        if self.counters.is_none() {
+            self.counters = Some(InstructionCounters::new(self.instructions.len()));
+        }
+    }
+
     /// Add a nested function and return its index
     pub fn add_nested_function(&mut self, chunk: BytecodeChunk) -> usize {
         let idx = self.nested_functions.len();
@@ -92,7 +113,7 @@ impl BytecodeChunk {
         bytes.extend_from_slice(b"BCNK");
 
         // Version
-        bytes.push(1);
+        bytes.push(Self::FORMAT_VERSION);
 
         // Register count
         bytes.extend_from_slice(&self.register_count.to_le_bytes());
@@ -113,6 +134,14 @@ impl BytecodeChunk {
             bytes.extend_from_slice(&inst_bytes);
         }
 
+        // Nested functions, each length-prefixed so they can be decoded recursively
+        bytes.extend_from_slice(&(self.nested_functions.len() as u32).to_le_bytes());
+        for nested in &self.nested_functions {
+            let nested_bytes = nested.to_bytes();
+            bytes.extend_from_slice(&(nested_bytes.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(&nested_bytes);
+        }
+
         bytes
     }
 
@@ -127,9 +156,10 @@ impl BytecodeChunk {
             return Err("Invalid magic number".to_string());
         }
 
-        // Check version
-        if bytes[4] != 1 {
-            return Err(format!("Unsupported version: {}", bytes[4]));
+        // Check version (version 1 predates nested function serialization)
+        let version = bytes[4];
+        if version != 1 && version != Self::FORMAT_VERSION {
+            return Err(format!("Unsupported version: {}", version));
         }
 
         let mut offset = 5;
@@ -164,11 +194,39 @@ impl BytecodeChunk {
             offset += consumed;
         }
 
+        let mut nested_functions = Vec::new();
+        if version >= 2 {
+            if offset + 4 > bytes.len() {
+                return Err("Not enough bytes for nested function count".to_string());
+            }
+            let nested_count =
+                u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap()) as usize;
+            offset += 4;
+
+            nested_functions.reserve(nested_count);
+            for _ in 0..nested_count {
+                if offset + 4 > bytes.len() {
+                    return Err("Not enough bytes for nested function length".to_string());
+                }
+                let len =
+                    u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap()) as usize;
+                offset += 4;
+                if offset + len > bytes.len() {
+                    return Err("Not enough bytes for nested function".to_string());
+                }
+                nested_functions.push(Self::from_bytes(&bytes[offset..offset + len])?);
+                offset += len;
+            }
+        }
+
         Ok(Self {
             instructions,
             constants,
             register_count,
-            nested_functions: Vec::new(), // TODO: Serialize nested functions
+            nested_functions,
+            counters: None,
+            local_names: Vec::new(),
+            upvalue_names: Vec::new(),
         })
     }
 
@@ -197,6 +255,13 @@ impl BytecodeChunk {
         bytes
     }
 
+    /// Length-prefixed UTF-8 operand
+    fn encode_str(s: &str) -> Vec<u8> {
+        let mut data = (s.len() as u32).to_le_bytes().to_vec();
+        data.extend_from_slice(s.as_bytes());
+        data
+    }
+
     /// Encode opcode to tag and data bytes
     fn encode_opcode(&self, opcode: &Opcode) -> (u8, Vec<u8>) {
         match opcode {
@@ -225,6 +290,13 @@ impl BytecodeChunk {
             Opcode::Div => (12, vec![]),
             Opcode::Mod => (13, vec![]),
             Opcode::Exp => (200, vec![]),
+            Opcode::BitAnd => (78, vec![]),
+            Opcode::BitOr => (79, vec![]),
+            Opcode::BitXor => (80, vec![]),
+            Opcode::BitNot => (81, vec![]),
+            Opcode::ShiftLeft => (82, vec![]),
+            Opcode::ShiftRight => (83, vec![]),
+            Opcode::ShiftRightUnsigned => (84, vec![]),
             Opcode::Neg => (14, vec![]),
             Opcode::Not => (44, vec![]),
             Opcode::Equal => (15, vec![]),
@@ -238,6 +310,12 @@ impl BytecodeChunk {
             Opcode::Jump(offset) => (23, (*offset as u32).to_le_bytes().to_vec()),
             Opcode::JumpIfTrue(offset) => (24, (*offset as u32).to_le_bytes().to_vec()),
             Opcode::JumpIfFalse(offset) => (25, (*offset as u32).to_le_bytes().to_vec()),
+            Opcode::OptionalChainStart(reg) => (85, reg.0.to_le_bytes().to_vec()),
+            Opcode::OptionalChainShortCircuit(reg, offset) => {
+                let mut data = reg.0.to_le_bytes().to_vec();
+                data.extend_from_slice(&(*offset as u32).to_le_bytes());
+                (86, data)
+            }
             Opcode::Return => (26, vec![]),
             Opcode::CreateObject => (27, vec![]),
             Opcode::LoadProperty(s) => {
@@ -264,6 +342,7 @@ impl BytecodeChunk {
                 (30, data)
             }
             Opcode::Call(argc) => (31, vec![*argc]),
+            Opcode::TailCall(argc) => (58, vec![*argc]),
             Opcode::LoadUpvalue(idx) => (32, idx.to_le_bytes().to_vec()),
             Opcode::StoreUpvalue(idx) => (33, idx.to_le_bytes().to_vec()),
             Opcode::CloseUpvalue => (34, vec![]),
@@ -286,8 +365,32 @@ impl BytecodeChunk {
                 }
                 (42, data)
             }
+            Opcode::InitialYield => (74, vec![]),
+            Opcode::Yield => (75, vec![]),
+            Opcode::GetIterator => (76, vec![]),
+            Opcode::YieldDelegate => (77, vec![]),
+            Opcode::IteratorNext => (89, vec![]),
+            Opcode::Debugger => (90, vec![]),
             Opcode::GetIndex => (45, vec![]),
             Opcode::SetIndex => (46, vec![]),
+            Opcode::CopyDataProperties => (57, vec![]),
+            Opcode::DefineGetter => (91, vec![]),
+            Opcode::DefineSetter => (92, vec![]),
+            Opcode::LoadPrivate(s) => (64, Self::encode_str(s)),
+            Opcode::StorePrivate(s) => (65, Self::encode_str(s)),
+            Opcode::DefinePrivate(s) => (66, Self::encode_str(s)),
+            Opcode::LoadNewTarget => (67, vec![]),
+            Opcode::AddAssignLocal(reg) => (68, reg.0.to_le_bytes().to_vec()),
+            Opcode::SubAssignLocal(reg) => (69, reg.0.to_le_bytes().to_vec()),
+            Opcode::MulAssignLocal(reg) => (70, reg.0.to_le_bytes().to_vec()),
+            Opcode::DivAssignLocal(reg) => (71, reg.0.to_le_bytes().to_vec()),
+            Opcode::ModAssignLocal(reg) => (72, reg.0.to_le_bytes().to_vec()),
+            Opcode::ExpAssignLocal(reg) => (73, reg.0.to_le_bytes().to_vec()),
+            Opcode::ArrayPush => (59, vec![]),
+            Opcode::ArraySpread => (60, vec![]),
+            Opcode::CallSpread => (61, vec![]),
+            Opcode::CallMethodSpread => (62, vec![]),
+            Opcode::CallNewSpread => (63, vec![]),
             Opcode::CreateArray(count) => (47, (*count as u32).to_le_bytes().to_vec()),
             Opcode::CreateRegExp(pattern_idx, flags_idx) => {
                 let mut data = (*pattern_idx as u32).to_le_bytes().to_vec();
@@ -298,6 +401,8 @@ impl BytecodeChunk {
             Opcode::CallNew(argc) => (49, vec![*argc]),
             Opcode::Typeof => (50, vec![]),
             Opcode::Void => (51, vec![]),
+            Opcode::ToNumber => (87, vec![]),
+            Opcode::ToString => (88, vec![]),
             Opcode::Instanceof => (52, vec![]),
             Opcode::In => (53, vec![]),
             Opcode::DeleteProperty(ref s) => {
@@ -530,8 +635,80 @@ impl BytecodeChunk {
                 Opcode::CreateAsyncFunction(idx, upvalues)
             }
             43 => Opcode::Dup,
+            74 => Opcode::InitialYield,
+            75 => Opcode::Yield,
+            76 => Opcode::GetIterator,
+            77 => Opcode::YieldDelegate,
+            89 => Opcode::IteratorNext,
+            90 => Opcode::Debugger,
+            78 => Opcode::BitAnd,
+            79 => Opcode::BitOr,
+            80 => Opcode::BitXor,
+            81 => Opcode::BitNot,
+            82 => Opcode::ShiftLeft,
+            83 => Opcode::ShiftRight,
+            84 => Opcode::ShiftRightUnsigned,
             45 => Opcode::GetIndex,
             46 => Opcode::SetIndex,
+            57 => Opcode::CopyDataProperties,
+            91 => Opcode::DefineGetter,
+            92 => Opcode::DefineSetter,
+            64..=66 => {
+                if offset + 4 > bytes.len() {
+                    return Err("Not enough bytes for private name length".to_string());
+                }
+                let len =
+                    u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap()) as usize;
+                offset += 4;
+                if offset + len > bytes.len() {
+                    return Err("Not enough bytes for private name".to_string());
+                }
+                let s = String::from_utf8(bytes[offset..offset + len].to_vec())
+                    .map_err(|e| format!("Invalid UTF-8: {}", e))?;
+                offset += len;
+                match tag {
+                    64 => Opcode::LoadPrivate(s),
+                    65 => Opcode::StorePrivate(s),
+                    _ => Opcode::DefinePrivate(s),
+                }
+            }
+            67 => Opcode::LoadNewTarget,
+            85 => {
+                let reg =
+                    RegisterId(u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap()));
+                offset += 4;
+                Opcode::OptionalChainStart(reg)
+            }
+            86 => {
+                let reg =
+                    RegisterId(u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap()));
+                let off =
+                    u32::from_le_bytes(bytes[offset + 4..offset + 8].try_into().unwrap()) as usize;
+                offset += 8;
+                Opcode::OptionalChainShortCircuit(reg, off)
+            }
+            68..=73 => {
+                let reg = RegisterId(u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap()));
+                offset += 4;
+                match tag {
+                    68 => Opcode::AddAssignLocal(reg),
+                    69 => Opcode::SubAssignLocal(reg),
+                    70 => Opcode::MulAssignLocal(reg),
+                    71 => Opcode::DivAssignLocal(reg),
+                    72 => Opcode::ModAssignLocal(reg),
+                    _ => Opcode::ExpAssignLocal(reg),
+                }
+            }
+            59 => Opcode::ArrayPush,
+            60 => Opcode::ArraySpread,
+            61 => Opcode::CallSpread,
+            62 => Opcode::CallMethodSpread,
+            63 => Opcode::CallNewSpread,
+            58 => {
+                let argc = bytes[offset];
+                offset += 1;
+                Opcode::TailCall(argc)
+            }
             47 => {
                 let count =
                     u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap()) as usize;
@@ -550,6 +727,8 @@ impl BytecodeChunk {
             }
             50 => Opcode::Typeof,
             51 => Opcode::Void,
+            87 => Opcode::ToNumber,
+            88 => Opcode::ToString,
             52 => Opcode::Instanceof,
             53 => Opcode::In,
             54 => {
@@ -587,6 +766,16 @@ impl BytecodeChunk {
     }
 }
 
+/// Counters are instrumentation, not chunk contents, so they never affect equality
+impl PartialEq for BytecodeChunk {
+    fn eq(&self, other: &Self) -> bool {
+        self.instructions == other.instructions
+            && self.constants == other.constants
+            && self.register_count == other.register_count
+            && self.nested_functions == other.nested_functions
+    }
+}
+
 impl Default for BytecodeChunk {
     fn default() -> Self {
         Self::new()
@@ -628,4 +817,108 @@ mod tests {
         assert_eq!(chunk.instructions.len(), restored.instructions.len());
         assert_eq!(chunk.register_count, restored.register_count);
     }
+
+    #[test]
+    fn test_chunk_serialization_nested_functions() {
+        let mut inner = BytecodeChunk::new();
+        inner.emit(Opcode::LoadTrue);
+        inner.emit_with_position(Opcode::Return, SourcePosition::new(2, 3, 14));
+
+        let mut chunk = BytecodeChunk::new();
+        let idx = chunk.add_nested_function(inner);
+        chunk.emit(Opcode::CreateClosure(idx, vec![]));
+        chunk.emit(Opcode::Return);
+
+        let restored = BytecodeChunk::from_bytes(&chunk.to_bytes()).unwrap();
+        assert_eq!(restored, chunk);
+    }
+
+    #[test]
+    fn test_chunk_serialization_optional_chain_opcodes() {
+        let mut chunk = BytecodeChunk::new();
+        chunk.emit(Opcode::OptionalChainStart(RegisterId(3)));
+        chunk.emit(Opcode::LoadNull);
+        chunk.emit(Opcode::OptionalChainShortCircuit(RegisterId(3), 4));
+        chunk.emit(Opcode::LoadProperty("b".to_string()));
+        chunk.emit(Opcode::Return);
+
+        let restored = BytecodeChunk::from_bytes(&chunk.to_bytes()).unwrap();
+        assert_eq!(restored, chunk);
+    }
+
+    #[test]
+    fn test_chunk_serialization_generator_opcodes() {
+        let mut chunk = BytecodeChunk::new();
+        chunk.emit(Opcode::InitialYield);
+        chunk.emit(Opcode::LoadUndefined);
+        chunk.emit(Opcode::GetIterator);
+        chunk.emit(Opcode::LoadUndefined);
+        chunk.emit(Opcode::YieldDelegate);
+        chunk.emit(Opcode::IteratorNext);
+        chunk.emit(Opcode::Yield);
+        chunk.emit(Opcode::Return);
+
+        let restored = BytecodeChunk::from_bytes(&chunk.to_bytes()).unwrap();
+        assert_eq!(restored, chunk);
+    }
+
+    #[test]
+    fn test_chunk_serialization_debugger_opcode() {
+        let mut chunk = BytecodeChunk::new();
+        chunk.emit(Opcode::Debugger);
+        chunk.emit(Opcode::Return);
+
+        let restored = BytecodeChunk::from_bytes(&chunk.to_bytes()).unwrap();
+        assert_eq!(restored, chunk);
+    }
+
+    #[test]
+    fn test_chunk_serialization_accessor_opcodes() {
+        let mut chunk = BytecodeChunk::new();
+        chunk.emit(Opcode::DefineGetter);
+        chunk.emit(Opcode::DefineSetter);
+        chunk.emit(Opcode::Return);
+
+        let restored = BytecodeChunk::from_bytes(&chunk.to_bytes()).unwrap();
+        assert_eq!(restored, chunk);
+    }
+
+    #[test]
+    fn test_chunk_serialization_conversion_opcodes() {
+        let mut chunk = BytecodeChunk::new();
+        chunk.emit(Opcode::LoadUndefined);
+        chunk.emit(Opcode::ToNumber);
+        chunk.emit(Opcode::ToString);
+        chunk.emit(Opcode::Return);
+
+        let restored = BytecodeChunk::from_bytes(&chunk.to_bytes()).unwrap();
+        assert_eq!(restored, chunk);
+    }
+
+    #[test]
+    fn test_truncated_private_name_is_rejected() {
+        assert!(BytecodeChunk::decode_opcode(&[64, 1, 0]).is_err());
+        assert!(BytecodeChunk::decode_opcode(&[65, 5, 0, 0, 0, b'a']).is_err());
+        let (opcode, _) = BytecodeChunk::decode_opcode(&[66, 1, 0, 0, 0, b'x']).unwrap();
+        assert_eq!(opcode, Opcode::DefinePrivate("x".to_string()));
+    }
+
+    #[test]
+    fn test_chunk_serialization_bitwise_opcodes() {
+        let mut chunk = BytecodeChunk::new();
+        for opcode in [
+            Opcode::BitAnd,
+            Opcode::BitOr,
+            Opcode::BitXor,
+            Opcode::BitNot,
+            Opcode::ShiftLeft,
+            Opcode::ShiftRight,
+            Opcode::ShiftRightUnsigned,
+        ] {
+            chunk.emit(opcode);
+        }
+
+        let restored = BytecodeChunk::from_bytes(&chunk.to_bytes()).unwrap();
+        assert_eq!(restored, chunk);
+    }
 }
@@ -0,0 +1,74 @@
+//! Per-instruction execution counters
+//!
+//! Counters are shared between clones of a chunk, so every copy the VM makes
+//! while calling a function adds to the same totals.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Execution counts for each instruction offset of a chunk
+#[derive(Debug, Clone)]
+pub struct InstructionCounters {
+    counts: Arc<[AtomicU64]>,
+}
+
+impl InstructionCounters {
+    /// Create zeroed counters for `len` instructions
+    pub fn new(len: usize) -> Self {
+        Self {
+            counts: (0..len).map(|_| AtomicU64::new(0)).collect(),
+        }
+    }
+
+    /// Record one execution of the instruction at `offset`
+    ///
+    /// Offsets past the end (e.g. after the chunk was re-optimized) are ignored.
+    pub fn increment(&self, offset: usize) {
+        if let Some(count) = self.counts.get(offset) {
+            count.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Get the count for the instruction at `offset`
+    pub fn get(&self, offset: usize) -> u64 {
+        self.counts
+            .get(offset)
+            .map(|count| count.load(Ordering::Relaxed))
+            .unwrap_or(0)
+    }
+
+    /// Get `(offset, count)` for every instruction
+    pub fn snapshot(&self) -> Vec<(usize, u64)> {
+        self.counts
+            .iter()
+            .enumerate()
+            .map(|(offset, count)| (offset, count.load(Ordering::Relaxed)))
+            .collect()
+    }
+
+    /// Get the number of instructions covered
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// Check whether no instructions are covered
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clones_share_counts() {
+        let counters = InstructionCounters::new(2);
+        let clone = counters.clone();
+        counters.increment(1);
+        clone.increment(1);
+        clone.increment(5);
+
+        assert_eq!(counters.snapshot(), vec![(0, 0), (1, 2)]);
+    }
+}
@@ -36,6 +36,7 @@
 #![warn(clippy::all)]
 
 pub mod chunk;
+pub mod counters;
 pub mod instruction;
 pub mod opcode;
 pub mod optimizer;
@@ -43,6 +44,7 @@ pub mod value;
 
 // Re-export main types at crate root
 pub use chunk::BytecodeChunk;
+pub use counters::InstructionCounters;
 pub use instruction::{Instruction, SourcePosition};
 pub use opcode::{Opcode, RegisterId, UpvalueDescriptor};
 pub use optimizer::Optimizer;
@@ -46,6 +46,19 @@ pub enum Opcode {
     LoadLocal(RegisterId),
     /// Store to local variable in register
     StoreLocal(RegisterId),
+    /// Fused `local += value`: pop value, add it to the register in place,
+    /// and push the result
+    AddAssignLocal(RegisterId),
+    /// Fused `local -= value` (see `AddAssignLocal`)
+    SubAssignLocal(RegisterId),
+    /// Fused `local *= value` (see `AddAssignLocal`)
+    MulAssignLocal(RegisterId),
+    /// Fused `local /= value` (see `AddAssignLocal`)
+    DivAssignLocal(RegisterId),
+    /// Fused `local %= value` (see `AddAssignLocal`)
+    ModAssignLocal(RegisterId),
+    /// Fused `local **= value` (see `AddAssignLocal`)
+    ExpAssignLocal(RegisterId),
 
     // Upvalue operations for closures
     /// Load captured variable by upvalue index
@@ -68,6 +81,20 @@ pub enum Opcode {
     Mod,
     /// Exponentiation (second-top ** top)
     Exp,
+    /// Bitwise AND of the ToInt32 of the top two values
+    BitAnd,
+    /// Bitwise OR of the ToInt32 of the top two values
+    BitOr,
+    /// Bitwise XOR of the ToInt32 of the top two values
+    BitXor,
+    /// Bitwise NOT of the ToInt32 of the top value
+    BitNot,
+    /// Left shift (second-top << top)
+    ShiftLeft,
+    /// Sign-propagating right shift (second-top >> top)
+    ShiftRight,
+    /// Zero-fill right shift (second-top >>> top)
+    ShiftRightUnsigned,
     /// Negate top value
     Neg,
     /// Logical NOT (invert truthiness)
@@ -76,6 +103,10 @@ pub enum Opcode {
     Typeof,
     /// void operator - evaluate expression, push undefined
     Void,
+    /// Unary plus - convert top value with ToNumber
+    ToNumber,
+    /// Convert top value with ToString, as for template substitutions
+    ToString,
     /// delete property from object (object on stack, property name as operand)
     DeleteProperty(String),
     /// delete global variable
@@ -110,6 +141,12 @@ pub enum Opcode {
     JumpIfTrue(usize),
     /// Jump to offset if top of stack is falsy
     JumpIfFalse(usize),
+    /// Begin an optional chain: save the operand stack height in the register
+    OptionalChainStart(RegisterId),
+    /// If the top value is null or undefined, drop the chain's values back to
+    /// the height saved by `OptionalChainStart` in the register, push
+    /// undefined and jump to the offset (the end of the chain)
+    OptionalChainShortCircuit(RegisterId, usize),
     /// Return from current function
     Return,
 
@@ -124,10 +161,33 @@ pub enum Opcode {
     GetIndex,
     /// Set value at computed index (for array[index] = value)
     SetIndex,
+    /// Load class private field (object on stack, private name as operand);
+    /// throws TypeError if the object does not have the field
+    LoadPrivate(String),
+    /// Store to an existing class private field (stack: [obj, value], pushes value);
+    /// throws TypeError if the object does not have the field
+    StorePrivate(String),
+    /// Add a class private field to a new instance (stack: [obj, value], pushes value)
+    DefinePrivate(String),
+    /// Push `new.target`: the constructor when the running function was invoked
+    /// with `new` (see `CallNew`), otherwise undefined
+    LoadNewTarget,
+    /// Copy own enumerable properties of the top value onto the object below it
+    /// (object spread); pops the source and leaves the target on the stack
+    CopyDataProperties,
+    /// Define a getter on an object (stack: [obj, key, function], leaves obj)
+    DefineGetter,
+    /// Define a setter on an object (stack: [obj, key, function], leaves obj)
+    DefineSetter,
 
     // Array operations
     /// Create array with given number of elements (elements are on stack)
     CreateArray(usize),
+    /// Pop a value and append it to the array below it, leaving the array
+    ArrayPush,
+    /// Pop an iterable and append each of its elements to the array below it
+    /// (array spread), leaving the array; throws TypeError for non-iterables
+    ArraySpread,
 
     // RegExp operations
     /// Create RegExp object with pattern and flags (both stored as constant pool indices)
@@ -138,10 +198,18 @@ pub enum Opcode {
     CreateClosure(usize, Vec<UpvalueDescriptor>),
     /// Call function with given number of arguments
     Call(u8),
+    /// Call function in tail position, reusing the current frame, and return its result
+    TailCall(u8),
     /// Call method on object with this binding (argc includes this)
     CallMethod(u8),
     /// Call constructor with new (creates instance)
     CallNew(u8),
+    /// Call function with arguments collected in an array (stack: [callee, args])
+    CallSpread,
+    /// Call method with arguments collected in an array (stack: [this, method, args])
+    CallMethodSpread,
+    /// Call constructor with arguments collected in an array (stack: [constructor, args])
+    CallNewSpread,
 
     // Exception handling
     /// Pop value from stack and throw as exception
@@ -150,9 +218,10 @@ pub enum Opcode {
     PushTry(usize),
     /// Pop exception handler from try stack
     PopTry,
-    /// Push finally block offset onto current handler
+    /// Push a handler that runs the finally block at the offset when an
+    /// exception or a generator return unwinds through it
     PushFinally(usize),
-    /// Pop finally handler and re-throw if exception pending
+    /// End a finally block, resuming a pending exception or generator return
     PopFinally,
     /// Pop value from stack (for discarding exception when not needed)
     Pop,
@@ -164,6 +233,23 @@ pub enum Opcode {
     Await,
     /// Create async function wrapper from function at index
     CreateAsyncFunction(usize, Vec<UpvalueDescriptor>),
+
+    // Generator operations
+    /// Suspend a freshly called generator function, returning its generator object
+    InitialYield,
+    /// Suspend the running generator, yielding the top of stack; resumes with the sent value
+    Yield,
+    /// Replace the iterable on top of stack with an iterator over it
+    GetIterator,
+    /// Drive the iterator below the received value until it is done (`yield*`)
+    YieldDelegate,
+    /// Step the iterator on top of stack, replacing it with the value it
+    /// produced and whether it is done (`for-of`)
+    IteratorNext,
+
+    // Debugging
+    /// Pause in the attached debugger, if any (`debugger`)
+    Debugger,
 }
 
 impl Opcode {
@@ -175,6 +261,7 @@ impl Opcode {
                 | Opcode::Jump(_)
                 | Opcode::JumpIfTrue(_)
                 | Opcode::JumpIfFalse(_)
+                | Opcode::OptionalChainShortCircuit(_, _)
                 | Opcode::Throw
         )
     }
@@ -191,6 +278,21 @@ impl Opcode {
             Opcode::Add | Opcode::Sub | Opcode::Mul | Opcode::Div | Opcode::Mod
         )
     }
+
+    /// Fused in-place form of a binary arithmetic opcode on a local register
+    ///
+    /// Returns `None` for opcodes without a compound assignment form.
+    pub fn assign_local(&self, reg: RegisterId) -> Option<Opcode> {
+        Some(match self {
+            Opcode::Add => Opcode::AddAssignLocal(reg),
+            Opcode::Sub => Opcode::SubAssignLocal(reg),
+            Opcode::Mul => Opcode::MulAssignLocal(reg),
+            Opcode::Div => Opcode::DivAssignLocal(reg),
+            Opcode::Mod => Opcode::ModAssignLocal(reg),
+            Opcode::Exp => Opcode::ExpAssignLocal(reg),
+            _ => return None,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -222,4 +324,12 @@ mod tests {
         assert!(Opcode::Mod.is_binary_arithmetic());
         assert!(!Opcode::Neg.is_binary_arithmetic());
     }
+
+    #[test]
+    fn test_opcode_assign_local() {
+        let reg = RegisterId(3);
+        assert_eq!(Opcode::Add.assign_local(reg), Some(Opcode::AddAssignLocal(reg)));
+        assert_eq!(Opcode::Exp.assign_local(reg), Some(Opcode::ExpAssignLocal(reg)));
+        assert_eq!(Opcode::LessThan.assign_local(reg), None);
+    }
 }
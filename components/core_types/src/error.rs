@@ -22,6 +22,8 @@ pub enum ErrorKind {
     EvalError,
     /// Error in URI handling functions
     URIError,
+    /// Several errors at once, as from `Promise.any`
+    AggregateError,
     /// Internal engine error
     InternalError,
 }
@@ -0,0 +1,301 @@
+//! Human-readable, cycle-safe rendering of JavaScript values.
+//!
+//! `core_types` does not know how objects are laid out on the heap, so the
+//! engine registers an [`ObjectInspector`] that turns a native object payload
+//! into an [`ObjectView`]. [`Value::inspect`] then walks those views, which is
+//! what the REPL and `console.log` use to print objects.
+//!
+//! Going the other way, the engine registers an [`ObjectBuilder`] so crates
+//! below it can create real objects and arrays with [`ObjectView::build`].
+
+use crate::value::Value;
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+/// Arrays longer than this are truncated with `... N more items`.
+pub const MAX_ARRAY_ITEMS: usize = 100;
+
+/// Structural view of an object, produced by an [`ObjectInspector`].
+#[derive(Debug, Clone)]
+pub enum ObjectView {
+    /// Plain object with its own enumerable properties
+    Object(Vec<(String, Value)>),
+    /// Array with its leading elements in index order
+    ///
+    /// Inspectors may stop after [`MAX_ARRAY_ITEMS`] elements; `length` is
+    /// the array's full length.
+    Array {
+        /// Elements from index 0, at most `length` of them
+        elements: Vec<Value>,
+        /// Length of the whole array
+        length: usize,
+    },
+}
+
+/// Converts the payload of a `Value::NativeObject` into an [`ObjectView`].
+///
+/// Returns `None` for payloads the inspector does not understand; those are
+/// rendered as `[object Object]`.
+pub type ObjectInspector = fn(&dyn Any) -> Option<ObjectView>;
+
+/// Creates an engine object from an [`ObjectView`], the inverse of an
+/// [`ObjectInspector`].
+pub type ObjectBuilder = Box<dyn Fn(ObjectView) -> Value>;
+
+thread_local! {
+    static OBJECT_INSPECTOR: Cell<Option<ObjectInspector>> = const { Cell::new(None) };
+    static OBJECT_BUILDER: RefCell<Option<ObjectBuilder>> = const { RefCell::new(None) };
+}
+
+/// Registers the inspector used by [`Value::inspect`] on the current thread.
+pub fn set_object_inspector(inspector: ObjectInspector) {
+    OBJECT_INSPECTOR.with(|cell| cell.set(Some(inspector)));
+}
+
+/// Registers the builder used by [`ObjectView::build`] on the current thread.
+pub fn set_object_builder(builder: ObjectBuilder) {
+    OBJECT_BUILDER.with(|cell| *cell.borrow_mut() = Some(builder));
+}
+
+impl ObjectView {
+    /// Creates the object or array this view describes, with the builder
+    /// registered on the current thread.
+    ///
+    /// Returns `None` if no builder is registered.
+    pub fn build(self) -> Option<Value> {
+        OBJECT_BUILDER.with(|cell| cell.borrow().as_ref().map(|build| build(self)))
+    }
+}
+
+impl Value {
+    /// Renders the value for display, expanding objects and arrays up to
+    /// `depth` levels of nesting.
+    ///
+    /// Objects deeper than `depth` are shown as `[Object]` or `[Array]`, an
+    /// object that contains itself is shown as `[Circular]`, and arrays
+    /// longer than [`MAX_ARRAY_ITEMS`] end in `... N more items`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core_types::Value;
+    ///
+    /// assert_eq!(Value::Smi(42).inspect(2), "42");
+    /// assert_eq!(Value::String("hi".to_string()).inspect(2), "hi");
+    /// ```
+    pub fn inspect(&self, depth: usize) -> String {
+        self.render(depth, OBJECT_INSPECTOR.with(|cell| cell.get()))
+    }
+
+    /// Like [`Value::inspect`], but reads objects through `inspector`
+    /// instead of the one registered for the current thread.
+    ///
+    /// Lets a second object model, such as the builtins library, share this
+    /// renderer without displacing the engine's inspector.
+    pub fn inspect_with(&self, depth: usize, inspector: ObjectInspector) -> String {
+        self.render(depth, Some(inspector))
+    }
+
+    /// Structural view of an object, read with the inspector registered on
+    /// the current thread.
+    ///
+    /// Returns `None` for non-objects and payloads the inspector does not
+    /// understand.
+    pub fn object_view(&self) -> Option<ObjectView> {
+        let inspector = OBJECT_INSPECTOR.with(|cell| cell.get())?;
+        match self {
+            Value::NativeObject(obj) => inspector(&*obj.borrow()),
+            _ => None,
+        }
+    }
+
+    fn render(&self, depth: usize, inspector: Option<ObjectInspector>) -> String {
+        match self {
+            // Top-level strings print as-is, like console.log
+            Value::String(s) => s.clone(),
+            _ => {
+                let mut ancestors = Vec::new();
+                inspect_value(self, 0, depth, inspector, &mut ancestors)
+            }
+        }
+    }
+}
+
+fn inspect_value(
+    value: &Value,
+    level: usize,
+    depth: usize,
+    inspector: Option<ObjectInspector>,
+    ancestors: &mut Vec<*const ()>,
+) -> String {
+    let obj = match value {
+        Value::String(s) => return format!("'{}'", s),
+        Value::NativeFunction(name) => return format!("[Function: {}]", name),
+        Value::NativeObject(obj) => obj,
+        other => return other.to_string(),
+    };
+
+    let id = Rc::as_ptr(obj) as *const ();
+    if ancestors.contains(&id) {
+        return "[Circular]".to_string();
+    }
+
+    let view = match inspector.and_then(|inspect| inspect(&*obj.borrow())) {
+        Some(view) => view,
+        None => return "[object Object]".to_string(),
+    };
+
+    if level > depth {
+        return match view {
+            ObjectView::Object(_) => "[Object]".to_string(),
+            ObjectView::Array { .. } => "[Array]".to_string(),
+        };
+    }
+
+    ancestors.push(id);
+    let rendered = match view {
+        ObjectView::Object(entries) => {
+            let parts: Vec<String> = entries
+                .iter()
+                .map(|(key, v)| {
+                    format!("{}: {}", key, inspect_value(v, level + 1, depth, inspector, ancestors))
+                })
+                .collect();
+            wrap("{", parts, "}")
+        }
+        ObjectView::Array { elements, length } => {
+            let mut parts: Vec<String> = elements
+                .iter()
+                .take(MAX_ARRAY_ITEMS.min(length))
+                .map(|v| inspect_value(v, level + 1, depth, inspector, ancestors))
+                .collect();
+            if length > parts.len() {
+                parts.push(format!("... {} more items", length - parts.len()));
+            }
+            wrap("[", parts, "]")
+        }
+    };
+    ancestors.pop();
+    rendered
+}
+
+fn wrap(open: &str, parts: Vec<String>, close: &str) -> String {
+    if parts.is_empty() {
+        format!("{}{}", open, close)
+    } else {
+        format!("{} {} {}", open, parts.join(", "), close)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    /// Minimal object payload standing in for the engine's heap objects
+    enum TestObject {
+        Object(Vec<(String, Value)>),
+        Array(Vec<Value>),
+    }
+
+    fn test_inspector(payload: &dyn Any) -> Option<ObjectView> {
+        match payload.downcast_ref::<TestObject>()? {
+            TestObject::Object(entries) => Some(ObjectView::Object(entries.clone())),
+            TestObject::Array(elements) => Some(ObjectView::Array {
+                elements: elements.clone(),
+                length: elements.len(),
+            }),
+        }
+    }
+
+    fn object(entries: Vec<(&str, Value)>) -> Value {
+        let entries = entries.into_iter().map(|(k, v)| (k.to_string(), v)).collect();
+        Value::NativeObject(Rc::new(RefCell::new(TestObject::Object(entries))))
+    }
+
+    fn array(elements: Vec<Value>) -> Value {
+        Value::NativeObject(Rc::new(RefCell::new(TestObject::Array(elements))))
+    }
+
+    fn push_entry(target: &Value, key: &str, value: Value) {
+        if let Value::NativeObject(obj) = target {
+            if let Some(TestObject::Object(entries)) = obj.borrow_mut().downcast_mut::<TestObject>() {
+                entries.push((key.to_string(), value));
+            }
+        }
+    }
+
+    #[test]
+    fn test_inspect_nested_values() {
+        set_object_inspector(test_inspector);
+        let value = object(vec![
+            ("a", Value::Smi(1)),
+            ("b", Value::String("x".to_string())),
+            ("c", array(vec![Value::Boolean(true), Value::Null])),
+            ("d", object(vec![])),
+        ]);
+        assert_eq!(value.inspect(2), "{ a: 1, b: 'x', c: [ true, null ], d: {} }");
+    }
+
+    #[test]
+    fn test_inspect_self_reference_is_circular() {
+        set_object_inspector(test_inspector);
+        let value = object(vec![("name", Value::String("root".to_string()))]);
+        push_entry(&value, "self", value.clone());
+        assert_eq!(value.inspect(2), "{ name: 'root', self: [Circular] }");
+
+        // A shared, non-cyclic reference is rendered in full each time
+        let shared = object(vec![("n", Value::Smi(1))]);
+        let pair = array(vec![shared.clone(), shared]);
+        assert_eq!(pair.inspect(2), "[ { n: 1 }, { n: 1 } ]");
+    }
+
+    #[test]
+    fn test_inspect_truncates_at_depth_limit() {
+        set_object_inspector(test_inspector);
+        let innermost = object(vec![("d", Value::Smi(4))]);
+        let value = object(vec![(
+            "a",
+            object(vec![("b", object(vec![("c", innermost)]))]),
+        )]);
+        assert_eq!(value.inspect(1), "{ a: { b: [Object] } }");
+        assert_eq!(value.inspect(0), "{ a: [Object] }");
+        assert_eq!(value.inspect(3), "{ a: { b: { c: { d: 4 } } } }");
+    }
+
+    #[test]
+    fn test_inspect_truncates_long_arrays() {
+        set_object_inspector(test_inspector);
+        let value = array((0..105).map(Value::Smi).collect());
+        let rendered = value.inspect(2);
+        assert!(rendered.starts_with("[ 0, 1, 2,"));
+        assert!(rendered.ends_with("99, ... 5 more items ]"));
+    }
+
+    fn test_builder(view: ObjectView) -> Value {
+        match view {
+            ObjectView::Object(entries) => {
+                Value::NativeObject(Rc::new(RefCell::new(TestObject::Object(entries))))
+            }
+            ObjectView::Array { elements, .. } => array(elements),
+        }
+    }
+
+    #[test]
+    fn test_build_round_trips_through_inspector() {
+        set_object_inspector(test_inspector);
+        set_object_builder(Box::new(test_builder));
+        let entry = ObjectView::Object(vec![("a".to_string(), Value::Smi(1))]).build().unwrap();
+        let value = ObjectView::Array { elements: vec![entry], length: 1 }.build().unwrap();
+        assert_eq!(value.inspect(2), "[ { a: 1 } ]");
+        assert!(matches!(value.object_view(), Some(ObjectView::Array { length: 1, .. })));
+        assert!(Value::Smi(1).object_view().is_none());
+    }
+
+    #[test]
+    fn test_inspect_without_inspector_is_opaque() {
+        let value = Value::NativeObject(Rc::new(RefCell::new(42u8)));
+        assert_eq!(value.inspect(2), "[object Object]");
+    }
+}
@@ -0,0 +1,126 @@
+//! Interned string tables.
+//!
+//! Each VM owns an [`Interner`] so that hot paths such as inline caches can
+//! compare property names by id instead of by content. The strings are freed
+//! together with the table.
+
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Names interned up front so their ids are stable and cheap to obtain.
+const PREDEFINED: &[&str] = &[
+    "",
+    "length",
+    "prototype",
+    "constructor",
+    "__proto__",
+    "name",
+    "message",
+    "value",
+    "done",
+    "next",
+    "toString",
+    "valueOf",
+    "get",
+    "set",
+    "then",
+    "undefined",
+    "null",
+    "true",
+    "false",
+];
+
+/// Handle to a string stored in an [`Interner`].
+///
+/// Two handles from the same interner are equal exactly when their strings
+/// are equal, so equality and hashing are O(1) regardless of string length.
+///
+/// # Examples
+///
+/// ```
+/// use core_types::Interner;
+///
+/// let mut interner = Interner::new();
+/// let a = interner.intern("foo");
+/// let b = interner.intern(&String::from("foo"));
+/// assert_eq!(a, b);
+/// assert_eq!(interner.resolve(a), "foo");
+/// assert_ne!(a, interner.intern("bar"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct InternedString(u32);
+
+impl InternedString {
+    /// Returns the numeric id of this string within its intern table.
+    pub fn id(self) -> u32 {
+        self.0
+    }
+}
+
+/// Table of interned strings.
+///
+/// Common property names are interned up front, so their ids are the same
+/// in every table.
+#[derive(Debug, Clone)]
+pub struct Interner {
+    ids: HashMap<Rc<str>, InternedString>,
+    strings: Vec<Rc<str>>,
+}
+
+impl Interner {
+    /// Creates a table holding the predefined names.
+    pub fn new() -> Self {
+        let mut interner = Interner {
+            ids: HashMap::new(),
+            strings: Vec::new(),
+        };
+        for name in PREDEFINED {
+            interner.intern(name);
+        }
+        interner
+    }
+
+    /// Interns `s`, returning the handle for its contents.
+    ///
+    /// Interning the same contents again always returns an equal handle.
+    pub fn intern(&mut self, s: &str) -> InternedString {
+        if let Some(&id) = self.ids.get(s) {
+            return id;
+        }
+        let stored: Rc<str> = Rc::from(s);
+        let id = InternedString(self.strings.len() as u32);
+        self.strings.push(Rc::clone(&stored));
+        self.ids.insert(stored, id);
+        id
+    }
+
+    /// Returns the handle for `s` if it has already been interned.
+    pub fn lookup(&self, s: &str) -> Option<InternedString> {
+        self.ids.get(s).copied()
+    }
+
+    /// Returns the contents of `name`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` came from a different table with more strings.
+    pub fn resolve(&self, name: InternedString) -> &str {
+        &self.strings[name.0 as usize]
+    }
+
+    /// Number of strings in the table.
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Whether the table is empty; never true, given the predefined names.
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+}
+
+impl Default for Interner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -9,7 +9,10 @@
 //! - [`JsError`] - JavaScript errors with stack traces
 //! - [`ErrorKind`] - Types of JavaScript errors
 //! - [`SourcePosition`] - Source code location
+//! - [`SourceSpan`] - Range of source code between two positions
 //! - [`StackFrame`] - Call stack frame information
+//! - [`ObjectView`] - Structural view of objects used by [`Value::inspect`]
+//! - [`Interner`] - Per-VM table of interned strings, compared by [`InternedString`] id
 //!
 //! # Examples
 //!
@@ -35,11 +38,18 @@
 #![deny(unsafe_code)]
 
 mod error;
+mod inspect;
+mod intern;
 mod profile;
 mod source;
 mod value;
 
 pub use error::{ErrorKind, JsError};
+pub use inspect::{
+    set_object_builder, set_object_inspector, ObjectBuilder, ObjectInspector, ObjectView,
+    MAX_ARRAY_ITEMS,
+};
+pub use intern::{InternedString, Interner};
 pub use profile::{BranchOutcome, ProfileData, TypeInfo};
-pub use source::{SourcePosition, StackFrame};
+pub use source::{SourcePosition, SourceSpan, StackFrame};
 pub use value::Value;
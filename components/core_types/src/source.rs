@@ -30,6 +30,48 @@ pub struct SourcePosition {
     pub offset: usize,
 }
 
+/// A range of source code, from `start` up to (but not including) `end`.
+///
+/// Spans let diagnostics underline a whole expression rather than point at
+/// its first character.
+///
+/// # Examples
+///
+/// ```
+/// use core_types::{SourcePosition, SourceSpan};
+///
+/// let span = SourceSpan::new(
+///     SourcePosition { line: 1, column: 4, offset: 4 },
+///     SourcePosition { line: 1, column: 9, offset: 9 },
+/// );
+///
+/// assert!(span.contains(&SourcePosition { line: 1, column: 6, offset: 6 }));
+/// assert!(!span.contains(&span.end));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceSpan {
+    /// First position covered by the span
+    pub start: SourcePosition,
+    /// Position just past the end of the span
+    pub end: SourcePosition,
+}
+
+impl SourceSpan {
+    /// Create a span from `start` to `end`
+    pub fn new(start: SourcePosition, end: SourcePosition) -> Self {
+        Self { start, end }
+    }
+
+    /// Check whether `pos` lies within the span
+    ///
+    /// Positions are compared by line, then column, so spans work across
+    /// multiple lines.
+    pub fn contains(&self, pos: &SourcePosition) -> bool {
+        let key = (pos.line, pos.column);
+        (self.start.line, self.start.column) <= key && key < (self.end.line, self.end.column)
+    }
+}
+
 /// Represents a single frame in a JavaScript call stack.
 ///
 /// Contains information about where in the code execution occurred,
@@ -77,6 +119,43 @@ mod tests {
         assert_eq!(pos.offset, 150);
     }
 
+    fn pos(line: u32, column: u32) -> SourcePosition {
+        SourcePosition {
+            line,
+            column,
+            offset: 0,
+        }
+    }
+
+    #[test]
+    fn test_source_span_contains_within_line() {
+        let span = SourceSpan::new(pos(3, 4), pos(3, 10));
+        assert!(span.contains(&pos(3, 4)));
+        assert!(span.contains(&pos(3, 9)));
+        assert!(!span.contains(&pos(3, 10)));
+        assert!(!span.contains(&pos(3, 3)));
+        assert!(!span.contains(&pos(2, 6)));
+    }
+
+    #[test]
+    fn test_source_span_contains_across_lines() {
+        let span = SourceSpan::new(pos(2, 8), pos(4, 3));
+        // Later columns on the first line and earlier ones on the last line
+        assert!(span.contains(&pos(2, 20)));
+        assert!(span.contains(&pos(3, 0)));
+        assert!(span.contains(&pos(3, 100)));
+        assert!(span.contains(&pos(4, 2)));
+        assert!(!span.contains(&pos(2, 7)));
+        assert!(!span.contains(&pos(4, 3)));
+        assert!(!span.contains(&pos(5, 0)));
+    }
+
+    #[test]
+    fn test_empty_source_span_contains_nothing() {
+        let span = SourceSpan::new(pos(1, 5), pos(1, 5));
+        assert!(!span.contains(&pos(1, 5)));
+    }
+
     #[test]
     fn test_stack_frame_creation() {
         let frame = StackFrame {
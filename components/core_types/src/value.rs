@@ -8,6 +8,7 @@ use num_traits::Zero;
 use std::any::Any;
 use std::cell::RefCell;
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::rc::Rc;
 
 /// Represents any JavaScript value.
@@ -78,24 +79,54 @@ impl fmt::Debug for Value {
     }
 }
 
+/// `==` on `Value` is SameValueZero, the equality used for Map and Set keys.
+///
+/// - Numbers compare by value regardless of representation (`Smi(1) == Double(1.0)`)
+/// - `NaN` equals `NaN`, whatever its bit pattern
+/// - `+0` equals `-0`
+///
+/// Use [`Value::same_value`] for `Object.is`, which also tells `+0` from `-0`.
+/// Neither is `===`: strict equality treats `NaN` as unequal to itself.
 impl PartialEq for Value {
     fn eq(&self, other: &Self) -> bool {
-        match (self, other) {
-            (Value::Undefined, Value::Undefined) => true,
-            (Value::Null, Value::Null) => true,
-            (Value::Boolean(a), Value::Boolean(b)) => a == b,
-            (Value::Smi(a), Value::Smi(b)) => a == b,
-            (Value::HeapObject(a), Value::HeapObject(b)) => a == b,
-            (Value::Double(a), Value::Double(b)) => a == b,
-            (Value::String(a), Value::String(b)) => a == b,
-            (Value::NativeObject(a), Value::NativeObject(b)) => Rc::ptr_eq(a, b),
-            (Value::NativeFunction(a), Value::NativeFunction(b)) => a == b,
-            (Value::BigInt(a), Value::BigInt(b)) => a == b,
-            _ => false,
+        self.same_value_zero(other)
+    }
+}
+
+/// SameValueZero is reflexive, since `NaN` equals itself
+impl Eq for Value {}
+
+/// Hashing agrees with SameValueZero: all NaNs hash alike, as do `+0` and `-0`,
+/// and a `Smi` hashes like the equal `Double`.
+impl Hash for Value {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.hash_tag().hash(state);
+        match self {
+            Value::Undefined | Value::Null => {}
+            Value::Boolean(b) => b.hash(state),
+            Value::Smi(n) => hash_number(*n as f64, state),
+            Value::Double(n) => hash_number(*n, state),
+            Value::HeapObject(id) => id.hash(state),
+            Value::String(s) => s.hash(state),
+            Value::NativeObject(obj) => (Rc::as_ptr(obj) as *const () as usize).hash(state),
+            Value::NativeFunction(name) => name.hash(state),
+            Value::BigInt(n) => n.hash(state),
         }
     }
 }
 
+/// Hash a number so that every NaN, and both zeros, land on the same bits
+fn hash_number<H: Hasher>(n: f64, state: &mut H) {
+    let canonical = if n.is_nan() {
+        f64::NAN
+    } else if n == 0.0 {
+        0.0
+    } else {
+        n
+    };
+    canonical.to_bits().hash(state);
+}
+
 impl Value {
     /// Returns whether this value is truthy in JavaScript semantics.
     ///
@@ -139,6 +170,88 @@ impl Value {
         }
     }
 
+    /// SameValueZero comparison, the key equality of Map, Set and `includes`.
+    ///
+    /// `NaN` equals `NaN` and `+0` equals `-0`. This is what `==` on `Value` does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core_types::Value;
+    ///
+    /// assert!(Value::Double(f64::NAN).same_value_zero(&Value::Double(f64::NAN)));
+    /// assert!(Value::Double(-0.0).same_value_zero(&Value::Smi(0)));
+    /// ```
+    pub fn same_value_zero(&self, other: &Value) -> bool {
+        match (self.as_number(), other.as_number()) {
+            (Some(a), Some(b)) => (a.is_nan() && b.is_nan()) || a == b,
+            (None, None) => self.same_non_number(other),
+            _ => false,
+        }
+    }
+
+    /// SameValue comparison, as used by `Object.is`.
+    ///
+    /// Like [`Value::same_value_zero`], except that `+0` and `-0` differ.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core_types::Value;
+    ///
+    /// assert!(Value::Double(f64::NAN).same_value(&Value::Double(f64::NAN)));
+    /// assert!(!Value::Double(-0.0).same_value(&Value::Smi(0)));
+    /// ```
+    pub fn same_value(&self, other: &Value) -> bool {
+        match (self.as_number(), other.as_number()) {
+            (Some(a), Some(b)) => {
+                (a.is_nan() && b.is_nan())
+                    || (a == b && a.is_sign_negative() == b.is_sign_negative())
+            }
+            (None, None) => self.same_non_number(other),
+            _ => false,
+        }
+    }
+
+    /// The numeric value of a `Smi` or `Double`
+    fn as_number(&self) -> Option<f64> {
+        match self {
+            Value::Smi(n) => Some(*n as f64),
+            Value::Double(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// Equality shared by SameValue and SameValueZero for non-number values
+    fn same_non_number(&self, other: &Value) -> bool {
+        match (self, other) {
+            (Value::Undefined, Value::Undefined) => true,
+            (Value::Null, Value::Null) => true,
+            (Value::Boolean(a), Value::Boolean(b)) => a == b,
+            (Value::HeapObject(a), Value::HeapObject(b)) => a == b,
+            (Value::String(a), Value::String(b)) => a == b,
+            (Value::NativeObject(a), Value::NativeObject(b)) => Rc::ptr_eq(a, b),
+            (Value::NativeFunction(a), Value::NativeFunction(b)) => a == b,
+            (Value::BigInt(a), Value::BigInt(b)) => a == b,
+            _ => false,
+        }
+    }
+
+    /// Type tag for hashing; both number representations share one tag
+    fn hash_tag(&self) -> u8 {
+        match self {
+            Value::Undefined => 0,
+            Value::Null => 1,
+            Value::Boolean(_) => 2,
+            Value::Smi(_) | Value::Double(_) => 3,
+            Value::HeapObject(_) => 4,
+            Value::String(_) => 5,
+            Value::NativeObject(_) => 6,
+            Value::NativeFunction(_) => 7,
+            Value::BigInt(_) => 8,
+        }
+    }
+
     /// Returns the JavaScript typeof result for this value.
     ///
     /// This follows JavaScript's `typeof` operator behavior:
@@ -1,5 +1,6 @@
 //! Unit test modules for core_types
 
 mod test_error;
+mod test_intern;
 mod test_source;
 mod test_value;
@@ -0,0 +1,44 @@
+//! Unit tests for the interned string table
+
+use core_types::Interner;
+
+#[test]
+fn test_interning_same_string_twice_yields_equal_ids() {
+    let mut interner = Interner::new();
+    let first = interner.intern("intern_test_property");
+    let owned = String::from("intern_test_property");
+    let second = interner.intern(&owned);
+
+    assert_eq!(first, second);
+    assert_eq!(first.id(), second.id());
+    assert_eq!(interner.resolve(first), "intern_test_property");
+}
+
+#[test]
+fn test_distinct_strings_have_distinct_ids() {
+    let mut interner = Interner::new();
+    assert_ne!(interner.intern("intern_test_a"), interner.intern("intern_test_b"));
+}
+
+#[test]
+fn test_common_names_are_predefined() {
+    let mut interner = Interner::new();
+    assert!(interner.lookup("length").is_some());
+    assert!(interner.lookup("prototype").is_some());
+    assert_eq!(interner.lookup("length"), Some(interner.intern("length")));
+    assert!(interner.lookup("intern_test_never_interned").is_none());
+
+    // Predefined names have the same id in every table
+    assert_eq!(Interner::new().lookup("length"), interner.lookup("length"));
+}
+
+#[test]
+fn test_tables_are_independent() {
+    let mut first = Interner::new();
+    let before = first.len();
+    first.intern("intern_test_only_in_first");
+
+    let second = Interner::new();
+    assert_eq!(second.len(), before);
+    assert!(second.lookup("intern_test_only_in_first").is_none());
+}
@@ -334,3 +334,93 @@ mod value_clone_and_debug_tests {
         assert_ne!(Value::Smi(1), Value::Smi(2));
     }
 }
+
+#[cfg(test)]
+mod value_equality_tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn numbers() -> Vec<Value> {
+        vec![
+            Value::Smi(0),
+            Value::Double(0.0),
+            Value::Double(-0.0),
+            Value::Double(f64::NAN),
+            Value::Double(-f64::NAN),
+            Value::Double(f64::from_bits(0x7ff8_0000_0000_0001)),
+            Value::Smi(1),
+            Value::Double(1.0),
+            Value::Double(f64::INFINITY),
+        ]
+    }
+
+    fn is_nan(value: &Value) -> bool {
+        matches!(value, Value::Double(n) if n.is_nan())
+    }
+
+    fn is_zero(value: &Value) -> bool {
+        matches!(value, Value::Smi(0)) || matches!(value, Value::Double(n) if *n == 0.0)
+    }
+
+    fn is_negative_zero(value: &Value) -> bool {
+        matches!(value, Value::Double(n) if *n == 0.0 && n.is_sign_negative())
+    }
+
+    #[test]
+    fn test_same_value_and_same_value_zero_disagree_only_on_signed_zero() {
+        for a in numbers() {
+            for b in numbers() {
+                let signed_zero_pair =
+                    is_zero(&a) && is_zero(&b) && is_negative_zero(&a) != is_negative_zero(&b);
+                assert_eq!(
+                    a.same_value(&b) != a.same_value_zero(&b),
+                    signed_zero_pair,
+                    "{:?} vs {:?}",
+                    a,
+                    b
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_both_modes_treat_every_nan_as_equal() {
+        for a in numbers().iter().filter(|v| is_nan(v)) {
+            for b in numbers().iter().filter(|v| is_nan(v)) {
+                assert!(a.same_value(b));
+                assert!(a.same_value_zero(b));
+                assert_eq!(a, b);
+            }
+            assert!(!a.same_value(&Value::Smi(0)));
+        }
+    }
+
+    #[test]
+    fn test_partial_eq_is_same_value_zero() {
+        assert_eq!(Value::Double(-0.0), Value::Double(0.0));
+        assert_eq!(Value::Smi(1), Value::Double(1.0));
+        assert_ne!(Value::Smi(1), Value::String("1".to_string()));
+        assert!(!Value::Double(-0.0).same_value(&Value::Double(0.0)));
+        assert!(!Value::Double(-0.0).same_value(&Value::Smi(0)));
+    }
+
+    #[test]
+    fn test_nan_bits_survive_in_value() {
+        let bits = 0x7ff8_0000_dead_beef;
+        match Value::Double(f64::from_bits(bits)).clone() {
+            Value::Double(n) => assert_eq!(n.to_bits(), bits),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    #[allow(clippy::mutable_key_type)] // NativeObject hashes by pointer, not contents
+    fn test_hash_agrees_with_same_value_zero() {
+        let keys: HashSet<Value> = numbers().into_iter().collect();
+        // {0, NaN, 1, Infinity}
+        assert_eq!(keys.len(), 4);
+        assert!(keys.contains(&Value::Double(-0.0)));
+        assert!(keys.contains(&Value::Double(f64::NAN)));
+        assert!(keys.contains(&Value::Smi(1)));
+    }
+}
@@ -9,3 +9,6 @@ mod test_error;
 
 #[path = "unit/test_value.rs"]
 mod test_value;
+
+#[path = "unit/test_intern.rs"]
+mod test_intern;
@@ -0,0 +1,25 @@
+//! Bound functions created by `Function.prototype.bind`
+
+use core_types::Value;
+
+/// A function with a fixed `this` and leading arguments
+///
+/// Calling it ignores the caller's `this`; `new` constructs the target instead.
+#[derive(Debug, Clone)]
+pub struct BoundFunction {
+    /// The function being wrapped
+    pub target: Value,
+    /// The `this` value passed to the target on plain calls
+    pub bound_this: Value,
+    /// Arguments prepended to every call
+    pub bound_args: Vec<Value>,
+}
+
+impl BoundFunction {
+    /// Prepend the bound arguments to `args`
+    pub fn full_args(&self, args: Vec<Value>) -> Vec<Value> {
+        let mut full = self.bound_args.clone();
+        full.extend(args);
+        full
+    }
+}
@@ -0,0 +1,159 @@
+//! Time sources for `performance.now()` and `Date.now()`
+//!
+//! Embedders can swap in their own [`Clock`] (e.g. a fake one for
+//! deterministic tests) through `VM::set_clock`.
+
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// A source of monotonic and wall-clock time
+pub trait Clock {
+    /// Milliseconds since the clock's time origin, with sub-millisecond precision
+    ///
+    /// Must not go backwards.
+    fn monotonic_ms(&self) -> f64;
+
+    /// Milliseconds since the Unix epoch
+    fn wall_clock_ms(&self) -> f64;
+}
+
+/// The host's real clocks, with the time origin at creation
+#[derive(Debug, Clone, Copy)]
+pub struct SystemClock {
+    origin: Instant,
+}
+
+impl SystemClock {
+    /// Create a clock whose monotonic time starts now
+    pub fn new() -> Self {
+        Self {
+            origin: Instant::now(),
+        }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn monotonic_ms(&self) -> f64 {
+        self.origin.elapsed().as_secs_f64() * 1000.0
+    }
+
+    fn wall_clock_ms(&self) -> f64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs_f64() * 1000.0)
+            .unwrap_or(0.0)
+    }
+}
+
+/// A clock that only moves when advanced, for reproducible runs
+///
+/// Clones share one time, so an embedder can keep a clone to advance the
+/// clock it handed to the VM.
+#[derive(Debug, Clone)]
+pub struct ManualClock {
+    epoch_ms: f64,
+    elapsed_ms: Rc<Cell<f64>>,
+}
+
+impl ManualClock {
+    /// Create a clock whose wall time starts at `epoch_ms` since the Unix epoch
+    pub fn new(epoch_ms: f64) -> Self {
+        Self {
+            epoch_ms,
+            elapsed_ms: Rc::new(Cell::new(0.0)),
+        }
+    }
+
+    /// Move the clock forward by `ms` milliseconds; negative steps are ignored
+    pub fn advance(&self, ms: f64) {
+        if ms > 0.0 {
+            self.elapsed_ms.set(self.elapsed_ms.get() + ms);
+        }
+    }
+}
+
+impl Clock for ManualClock {
+    fn monotonic_ms(&self) -> f64 {
+        self.elapsed_ms.get()
+    }
+
+    fn wall_clock_ms(&self) -> f64 {
+        self.epoch_ms + self.elapsed_ms.get()
+    }
+}
+
+/// Seeded generator behind `Math.random()` in deterministic runs (SplitMix64)
+#[derive(Debug, Clone, Copy)]
+pub struct SeededRandom {
+    state: u64,
+}
+
+impl SeededRandom {
+    /// Create a generator; equal seeds produce equal sequences
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Next value in `[0, 1)`
+    pub fn next_f64(&mut self) -> f64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        (z >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// The `performance` global object
+pub struct PerformanceObject;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_system_clock_is_monotonic() {
+        let clock = SystemClock::new();
+        let first = clock.monotonic_ms();
+        let second = clock.monotonic_ms();
+        assert!(first >= 0.0);
+        assert!(second >= first);
+    }
+
+    #[test]
+    fn test_system_clock_wall_time_is_after_2020() {
+        assert!(SystemClock::new().wall_clock_ms() > 1_577_836_800_000.0);
+    }
+
+    #[test]
+    fn test_manual_clock_moves_only_when_advanced() {
+        let clock = ManualClock::new(1000.0);
+        let handle = clock.clone();
+        assert_eq!(clock.wall_clock_ms(), 1000.0);
+
+        handle.advance(250.0);
+        handle.advance(-100.0);
+        assert_eq!(clock.monotonic_ms(), 250.0);
+        assert_eq!(clock.wall_clock_ms(), 1250.0);
+    }
+
+    #[test]
+    fn test_seeded_random_is_reproducible() {
+        let mut a = SeededRandom::new(7);
+        let mut b = SeededRandom::new(7);
+        for _ in 0..100 {
+            let value = a.next_f64();
+            assert!((0.0..1.0).contains(&value));
+            assert_eq!(value, b.next_f64());
+        }
+        assert_ne!(SeededRandom::new(8).next_f64(), SeededRandom::new(7).next_f64());
+    }
+}
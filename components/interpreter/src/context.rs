@@ -1,5 +1,6 @@
 //! Execution context for VM
 
+use crate::inline_cache::PropertySlots;
 use bytecode_system::BytecodeChunk;
 use core_types::Value;
 
@@ -15,6 +16,11 @@ pub struct ExecutionContext {
     pub instruction_pointer: usize,
     /// The bytecode being executed
     pub bytecode: BytecodeChunk,
+    /// `new.target`: the constructor for `new` invocations, otherwise undefined
+    pub new_target: Value,
+    /// Inline caches of the bytecode's property reads; empty until the
+    /// dispatcher loads the chunk, in which case reads go uncached
+    pub property_slots: PropertySlots,
 }
 
 impl ExecutionContext {
@@ -25,12 +31,17 @@ impl ExecutionContext {
             registers: vec![Value::Undefined; register_count],
             instruction_pointer: 0,
             bytecode,
+            new_target: Value::Undefined,
+            property_slots: PropertySlots::default(),
         }
     }
 
     /// Advance instruction pointer and return current instruction
     pub fn fetch(&mut self) -> Option<&bytecode_system::Instruction> {
         if self.instruction_pointer < self.bytecode.instructions.len() {
+            if let Some(counters) = &self.bytecode.counters {
+                counters.increment(self.instruction_pointer);
+            }
             let inst = &self.bytecode.instructions[self.instruction_pointer];
             self.instruction_pointer += 1;
             Some(inst)
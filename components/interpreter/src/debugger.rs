@@ -0,0 +1,26 @@
+//! Pausing on `debugger` statements
+//!
+//! The VM does not stop on its own: when a [`DebuggerHook`] is attached, each
+//! `debugger` statement hands it a [`FrameSnapshot`] of the running frame,
+//! and execution carries on once the hook returns.
+
+use builtins::value::JsValue;
+
+/// Variables of the frame that reached a `debugger` statement
+#[derive(Debug, Clone)]
+pub struct FrameSnapshot {
+    /// Line of the `debugger` statement (1-based), or 0 if unknown
+    pub line: u32,
+    /// Column of the `debugger` statement (1-based), or 0 if unknown
+    pub column: u32,
+    /// Register-backed locals with their source names, in register order
+    pub locals: Vec<(String, JsValue)>,
+    /// Variables the frame's closure captured, in upvalue order
+    pub closure: Vec<(String, JsValue)>,
+}
+
+/// Receives the paused frame each time a script reaches `debugger`
+pub trait DebuggerHook {
+    /// Called with the paused frame and the globals the script defined
+    fn paused(&self, frame: &FrameSnapshot, globals: &[(String, JsValue)]);
+}
@@ -2,24 +2,88 @@
 //!
 //! Handles individual opcode execution.
 
-use async_runtime::PromiseState;
+use async_runtime::{EventLoop, PromiseState};
 use bytecode_system::{BytecodeChunk, Opcode, UpvalueDescriptor};
-use builtins::{BigIntValue, ConsoleObject, JSONObject, JsValue as BuiltinValue, MathObject, NumberObject};
-use core_types::{ErrorKind, JsError, Value};
+use builtins::console::{ConsoleObserver, DEFAULT_DIR_DEPTH};
+use builtins::error::DEFAULT_STACK_TRACE_LIMIT;
+use builtins::{
+    BigIntValue, ConsoleObject, GlobalObject, JSONObject, JsDate, JsValue as BuiltinValue,
+    MathObject, NumberObject, PreferredType, SymbolConstructor, SymbolValue,
+};
+use core_types::{ErrorKind, Interner, JsError, StackFrame, Value};
 use num_traits::Zero;
 use std::any::Any;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
 use std::rc::Rc;
 
+use crate::bound_function::BoundFunction;
+use crate::clock::{Clock, PerformanceObject, SeededRandom, SystemClock};
 use crate::context::ExecutionContext;
-use crate::gc_integration::{GCObject, VMHeap};
+use crate::debugger::{DebuggerHook, FrameSnapshot};
+use crate::gc_integration::{GCObject, VMHeap, SYMBOL_KEY_PREFIX};
+use crate::generator::{GeneratorFrame, GeneratorObject, GeneratorState, ListIterator, ResumeMode};
+use crate::host_jobs::{HostJob, HostJobs};
+use crate::inline_cache::{PropertySlot, PropertySlots};
+use crate::private_fields::PrivateFieldTable;
 use crate::promise_integration::{PromiseConstructor, PromiseObject};
 use crate::upvalue::{new_upvalue_handle, Upvalue, UpvalueHandle};
 
+/// Methods resolved on arrays by `LoadProperty`
+const ARRAY_PROTOTYPE_METHODS: &[&str] = &[
+    "map", "filter", "forEach", "reduce", "reduceRight", "find", "findIndex", "some", "every",
+    "includes", "indexOf", "lastIndexOf", "push", "pop", "shift", "unshift", "slice", "splice",
+    "concat", "join", "reverse", "sort", "flat",
+];
+
+/// Methods resolved on Date objects by `LoadProperty`
+const DATE_PROTOTYPE_METHODS: &[&str] = &[
+    "getTime", "valueOf", "setTime", "getFullYear", "getMonth", "getDate", "getDay", "getHours",
+    "getMinutes", "getSeconds", "getMilliseconds", "getTimezoneOffset", "getUTCFullYear",
+    "getUTCMonth", "getUTCDate", "getUTCDay", "getUTCHours", "getUTCMinutes", "getUTCSeconds",
+    "getUTCMilliseconds", "toISOString", "toJSON", "toString", "toDateString", "toTimeString",
+    "toUTCString",
+];
+
+/// Methods resolved on plain objects by `LoadProperty`
+const OBJECT_PROTOTYPE_METHODS: &[&str] = &[
+    "toString", "valueOf", "hasOwnProperty", "propertyIsEnumerable", "isPrototypeOf",
+    "toLocaleString",
+];
+
+/// Methods resolved on functions by `LoadProperty`
+const FUNCTION_PROTOTYPE_METHODS: &[&str] = &["call", "apply", "bind"];
+
+/// Methods resolved on `console` by `LoadProperty`
+const CONSOLE_METHODS: &[&str] = &["log", "error", "warn", "info", "assert", "dir"];
+
+/// Methods resolved on `Math` by `LoadProperty`
+const MATH_METHODS: &[&str] = &[
+    "abs", "ceil", "floor", "round", "sqrt", "pow", "sin", "cos", "tan", "random", "max", "min",
+];
+
+/// Constants resolved on `Math` by `LoadProperty`
+const MATH_CONSTANTS: &[(&str, f64)] = &[("PI", MathObject::PI), ("E", MathObject::E)];
+
+/// Methods resolved on `JSON` by `LoadProperty`
+const JSON_METHODS: &[&str] = &["stringify", "parse"];
+
+/// Methods resolved on `performance` by `LoadProperty`
+const PERFORMANCE_METHODS: &[&str] = &["now"];
+
+/// Static methods resolved on `Promise` by `LoadProperty`
+const PROMISE_METHODS: &[&str] = &["resolve", "reject"];
+
+/// Constructors with a `prototype` object of their own in each realm;
+/// parents come before the constructors inheriting from them
+const REALM_CONSTRUCTORS: &[&str] = &[
+    "Object", "Array", "Error", "TypeError", "ReferenceError", "SyntaxError", "RangeError",
+    "URIError", "EvalError",
+];
+
 /// Exception handler for try/catch/finally blocks
 #[derive(Debug, Clone)]
-struct TryHandler {
+pub(crate) struct TryHandler {
     /// Offset to jump to for catch block (if any)
     catch_offset: Option<usize>,
     /// Offset to jump to for finally block (if any)
@@ -44,12 +108,51 @@ pub struct Dispatcher {
     try_stack: Vec<TryHandler>,
     /// Currently thrown exception (if any)
     current_exception: Option<Value>,
+    /// Value of a generator `return()` waiting on the finally blocks it unwinds
+    pending_return: Option<Value>,
+    /// Running user functions, outermost first, with the site each was called from
+    call_stack: Vec<(usize, StackFrame)>,
+    /// `Error.stackTraceLimit`: how many frames an error's `stack` lists
+    stack_trace_limit: usize,
     /// GC heap for JavaScript object allocation (shared with VM)
     heap: Option<Rc<VMHeap>>,
     /// Registry of closures: maps closure ID to (function_index, captured_upvalues)
     closure_registry: HashMap<usize, (usize, Vec<UpvalueHandle>)>,
     /// Next available closure ID
     next_closure_id: usize,
+    /// Microtasks and timers scheduled by the running script
+    host_jobs: RefCell<HostJobs>,
+    /// Source position of the native call being made, if the compiler recorded one
+    call_site: Option<bytecode_system::SourcePosition>,
+    /// Errors thrown by host jobs, waiting to be reported by the embedder
+    uncaught_errors: Vec<JsError>,
+    /// Time source for `performance.now()` and `Date.now()`
+    clock: Box<dyn Clock>,
+    /// Latest `performance.now()` result, so it never goes backwards
+    last_performance_now: Cell<f64>,
+    /// Seeded source for `Math.random()`; `None` uses the host's randomness
+    random: Cell<Option<SeededRandom>>,
+    /// Class private fields, keyed by object identity
+    private_fields: PrivateFieldTable,
+    /// `prototype` objects of user-defined functions, keyed by function ID
+    function_prototypes: HashMap<usize, Value>,
+    /// Globals of each realm; the running realm's are in `globals`, leaving
+    /// its slot here empty
+    realms: Vec<HashMap<String, Value>>,
+    /// Index of the realm whose globals are in `globals`
+    current_realm: usize,
+    /// Realm each user function was created in, if not the first
+    function_realms: HashMap<usize, usize>,
+    /// Set when a generator body suspends; `true` if it suspended in `yield*`
+    suspended_in_delegate: Option<bool>,
+    /// How a generator paused in `yield*` is being resumed
+    delegate_resume_mode: Option<ResumeMode>,
+    /// Notified when a script reaches a `debugger` statement
+    debugger_hook: Option<Rc<dyn DebuggerHook>>,
+    /// Property names read by loaded chunks, interned for the inline caches
+    interner: Interner,
+    /// Inline cache slots of each function, built the first time it is called
+    function_slots: HashMap<usize, PropertySlots>,
 }
 
 impl std::fmt::Debug for Dispatcher {
@@ -64,6 +167,7 @@ impl std::fmt::Debug for Dispatcher {
             .field("has_exception", &self.current_exception.is_some())
             .field("has_heap", &self.heap.is_some())
             .field("closure_registry_size", &self.closure_registry.len())
+            .field("host_jobs", &self.host_jobs)
             .finish()
     }
 }
@@ -71,7 +175,62 @@ impl std::fmt::Debug for Dispatcher {
 impl Dispatcher {
     /// Create a new dispatcher
     pub fn new() -> Self {
+        core_types::set_object_inspector(crate::gc_integration::inspect_gc_object);
         let console = Rc::new(RefCell::new(ConsoleObject::new()));
+        let globals = Self::intrinsic_globals(&console);
+
+        Self {
+            globals,
+            stack: Vec::with_capacity(256),
+            console,
+            open_upvalues: HashMap::new(),
+            current_upvalues: Vec::new(),
+            try_stack: Vec::new(),
+            current_exception: None,
+            pending_return: None,
+            call_stack: Vec::new(),
+            stack_trace_limit: DEFAULT_STACK_TRACE_LIMIT,
+            heap: None,
+            closure_registry: HashMap::new(),
+            next_closure_id: 0,
+            host_jobs: RefCell::new(HostJobs::new()),
+            call_site: None,
+            uncaught_errors: Vec::new(),
+            clock: Box::new(SystemClock::new()),
+            last_performance_now: Cell::new(0.0),
+            random: Cell::new(None),
+            private_fields: PrivateFieldTable::new(),
+            function_prototypes: HashMap::new(),
+            realms: vec![HashMap::new()],
+            current_realm: 0,
+            function_realms: HashMap::new(),
+            suspended_in_delegate: None,
+            delegate_resume_mode: None,
+            debugger_hook: None,
+            interner: Interner::new(),
+            function_slots: HashMap::new(),
+        }
+    }
+
+    /// Set the GC heap reference
+    ///
+    /// This should be called before executing bytecode to enable GC-managed object creation.
+    pub fn set_heap(&mut self, heap: Rc<VMHeap>) {
+        // Initialize the prototype objects now that we have a heap
+        Self::install_prototypes(&mut self.globals, &heap);
+        heap.set_array_prototype(self.globals.get("Array.prototype").cloned());
+        let builder_heap = Rc::clone(&heap);
+        core_types::set_object_builder(Box::new(move |view| {
+            crate::gc_integration::build_gc_object(&builder_heap, view)
+        }));
+        self.heap = Some(heap);
+    }
+
+    /// Global bindings every realm starts with
+    ///
+    /// Prototype objects are added separately by `install_prototypes`, once
+    /// a heap is available.
+    fn intrinsic_globals(console: &Rc<RefCell<ConsoleObject>>) -> HashMap<String, Value> {
         let mut globals = HashMap::new();
 
         // Inject console global
@@ -187,39 +346,150 @@ impl Dispatcher {
             "parseFloat".to_string(),
             Value::NativeFunction("parseFloat".to_string()),
         );
-
-        Self {
-            globals,
-            stack: Vec::with_capacity(256),
-            console,
-            open_upvalues: HashMap::new(),
-            current_upvalues: Vec::new(),
-            try_stack: Vec::new(),
-            current_exception: None,
-            heap: None,
-            closure_registry: HashMap::new(),
-            next_closure_id: 0,
+        for uri_fn in ["encodeURIComponent", "decodeURIComponent", "encodeURI", "decodeURI"] {
+            globals.insert(uri_fn.to_string(), Value::NativeFunction(uri_fn.to_string()));
+        }
+        for job_fn in ["queueMicrotask", "setTimeout", "clearTimeout"] {
+            globals.insert(job_fn.to_string(), Value::NativeFunction(job_fn.to_string()));
         }
-    }
 
-    /// Set the GC heap reference
-    ///
-    /// This should be called before executing bytecode to enable GC-managed object creation.
-    pub fn set_heap(&mut self, heap: Rc<VMHeap>) {
-        self.heap = Some(heap.clone());
+        // Inject performance and Date, both read from the dispatcher's clock
+        globals.insert(
+            "performance".to_string(),
+            Value::NativeObject(Rc::new(RefCell::new(PerformanceObject)) as Rc<RefCell<dyn Any>>),
+        );
+        globals.insert("Date".to_string(), Value::NativeFunction("Date".to_string()));
+
+        // Inject Symbol with its well-known symbols
+        globals.insert("Symbol".to_string(), Value::NativeFunction("Symbol".to_string()));
+        globals.insert(
+            "Symbol.toPrimitive".to_string(),
+            Value::NativeObject(
+                Rc::new(RefCell::new(SymbolConstructor::to_primitive())) as Rc<RefCell<dyn Any>>
+            ),
+        );
+
+        // Inject globalThis - property access reads and writes the globals table
+        globals.insert(
+            "globalThis".to_string(),
+            Value::NativeObject(Rc::new(RefCell::new(GlobalObject)) as Rc<RefCell<dyn Any>>),
+        );
 
-        // Initialize Error.prototype objects now that we have a heap
-        let error_types = ["Error", "TypeError", "ReferenceError", "SyntaxError",
-                          "RangeError", "URIError", "EvalError"];
+        globals
+    }
 
-        for error_type in &error_types {
-            let proto_obj = heap.create_object();
-            let proto_key = format!("{}.prototype", error_type);
+    /// Create each realm-specific prototype object on `heap`
+    ///
+    /// `Array.prototype` and `Error.prototype` inherit from `Object.prototype`,
+    /// and the other error prototypes from `Error.prototype`.
+    fn install_prototypes(globals: &mut HashMap<String, Value>, heap: &VMHeap) {
+        for &constructor in REALM_CONSTRUCTORS {
+            let mut proto_obj = heap.create_object();
+            let parent = match constructor {
+                "Object" => None,
+                "Array" | "Error" => Some("Object.prototype"),
+                _ => Some("Error.prototype"),
+            };
+            if let Some(parent) = parent.and_then(|key| globals.get(key)) {
+                proto_obj.set_prototype_value(parent.clone());
+            }
+            let proto_key = format!("{}.prototype", constructor);
             let boxed: Box<dyn Any> = Box::new(proto_obj);
             let proto_value = Value::NativeObject(
                 Rc::new(RefCell::new(boxed)) as Rc<RefCell<dyn Any>>
             );
-            self.globals.insert(proto_key, proto_value);
+            globals.insert(proto_key, proto_value);
+        }
+    }
+
+    /// Create a realm with fresh builtins and prototypes, returning its index
+    ///
+    /// The realm shares this dispatcher's console, heap and well-known
+    /// symbols. Its constructors are named `Array@1` and so on, so they are
+    /// distinct values from those of every other realm.
+    pub fn create_realm(&mut self) -> usize {
+        let realm = self.realms.len();
+        let mut globals = Self::intrinsic_globals(&self.console);
+        for &constructor in REALM_CONSTRUCTORS {
+            let tagged = format!("{}@{}", constructor, realm);
+            globals.insert(constructor.to_string(), Value::NativeFunction(tagged));
+        }
+        // Well-known symbols are shared by all realms
+        if let Some(symbol) = self.globals.get("Symbol.toPrimitive") {
+            globals.insert("Symbol.toPrimitive".to_string(), symbol.clone());
+        }
+        if let Some(heap) = &self.heap {
+            Self::install_prototypes(&mut globals, heap);
+        }
+        self.realms.push(globals);
+        realm
+    }
+
+    /// Number of realms, including the one the dispatcher starts in
+    pub fn realm_count(&self) -> usize {
+        self.realms.len()
+    }
+
+    /// Index of the realm whose globals scripts currently see
+    pub fn current_realm(&self) -> usize {
+        self.current_realm
+    }
+
+    /// Make `realm` the running one, returning the previous realm
+    ///
+    /// `realm` must be below `realm_count()`.
+    pub fn enter_realm(&mut self, realm: usize) -> usize {
+        let previous = self.current_realm;
+        if realm != previous {
+            let globals = std::mem::take(&mut self.realms[realm]);
+            self.realms[previous] = std::mem::replace(&mut self.globals, globals);
+            self.current_realm = realm;
+            if let Some(heap) = &self.heap {
+                heap.set_array_prototype(self.globals.get("Array.prototype").cloned());
+            }
+        }
+        previous
+    }
+
+    /// A global of `realm`, whether or not it is the running realm
+    fn realm_global(&self, realm: usize, name: &str) -> Option<Value> {
+        let globals = if realm == self.current_realm {
+            &self.globals
+        } else {
+            self.realms.get(realm)?
+        };
+        globals.get(name).cloned()
+    }
+
+    /// Set a global of `realm`, whether or not it is the running realm
+    fn set_realm_global(&mut self, realm: usize, name: String, value: Value) {
+        if realm == self.current_realm {
+            self.globals.insert(name, value);
+        } else if let Some(globals) = self.realms.get_mut(realm) {
+            globals.insert(name, value);
+        }
+    }
+
+    /// Record that user function `id` was created in the running realm
+    fn bind_function_realm(&mut self, id: usize) {
+        if self.current_realm != 0 {
+            self.function_realms.entry(id).or_insert(self.current_realm);
+        }
+    }
+
+    /// The realm user function `id` was created in
+    fn function_realm(&self, id: usize) -> usize {
+        self.function_realms.get(&id).copied().unwrap_or(0)
+    }
+
+    /// Name of a native constructor without its realm tag, and that realm
+    ///
+    /// Constructors of realms created by `create_realm` are tagged, as in
+    /// `Array@1`; untagged names belong to the first realm.
+    fn split_realm_tag(name: &str) -> (&str, usize) {
+        match name.split_once('@') {
+            Some((base, realm)) => (base, realm.parse().unwrap_or(0)),
+            None => (name, 0),
         }
     }
 
@@ -266,6 +536,8 @@ impl Dispatcher {
         ctx: &mut ExecutionContext,
     ) -> Result<(), JsError> {
         self.current_exception = Some(value.clone());
+        // An exception replaces a generator return in progress
+        self.pending_return = None;
 
         // Find nearest catch handler
         while let Some(handler) = self.try_stack.pop() {
@@ -288,6 +560,7 @@ impl Dispatcher {
         }
 
         // No handler found - propagate error as uncaught exception
+        self.current_exception = None;
         Err(JsError {
             kind: ErrorKind::InternalError,
             message: format!("Uncaught exception: {:?}", value),
@@ -296,6 +569,23 @@ impl Dispatcher {
         })
     }
 
+    /// Start returning `value` from a generator body, running its finally blocks
+    ///
+    /// Jumps to the innermost pending finally block and returns `None`; its
+    /// `PopFinally` continues the return. With no finally block left, returns
+    /// the value the body completes with.
+    fn unwind_return(&mut self, value: Value, ctx: &mut ExecutionContext) -> Option<Value> {
+        while let Some(handler) = self.try_stack.pop() {
+            if let Some(finally_offset) = handler.finally_offset {
+                self.stack.truncate(handler.stack_height);
+                ctx.instruction_pointer = finally_offset;
+                self.pending_return = Some(value);
+                return None;
+            }
+        }
+        Some(value)
+    }
+
     /// Convert bytecode_system::Value to core_types::Value
     fn convert_bc_value(bc_value: &bytecode_system::Value) -> Value {
         match bc_value {
@@ -377,6 +667,31 @@ impl Dispatcher {
                     let value = self.stack.pop().unwrap_or(Value::Undefined);
                     ctx.set_register(reg_id.0 as usize, value);
                 }
+                Opcode::AddAssignLocal(reg_id)
+                | Opcode::SubAssignLocal(reg_id)
+                | Opcode::MulAssignLocal(reg_id)
+                | Opcode::DivAssignLocal(reg_id)
+                | Opcode::ModAssignLocal(reg_id)
+                | Opcode::ExpAssignLocal(reg_id) => {
+                    let b = self.stack.pop().unwrap_or(Value::Undefined);
+                    let a = ctx.get_register(reg_id.0 as usize);
+                    let hint = match inst.opcode {
+                        Opcode::AddAssignLocal(_) => PreferredType::Default,
+                        _ => PreferredType::Number,
+                    };
+                    let a = self.to_primitive(a, hint, functions)?;
+                    let b = self.to_primitive(b, hint, functions)?;
+                    let result = match inst.opcode {
+                        Opcode::AddAssignLocal(_) => self.add(a, b)?,
+                        Opcode::SubAssignLocal(_) => self.sub(a, b)?,
+                        Opcode::MulAssignLocal(_) => self.mul(a, b)?,
+                        Opcode::DivAssignLocal(_) => self.div(a, b)?,
+                        Opcode::ModAssignLocal(_) => self.modulo(a, b)?,
+                        _ => self.exponentiate(a, b)?,
+                    };
+                    ctx.set_register(reg_id.0 as usize, result.clone());
+                    self.stack.push(result);
+                }
                 Opcode::LoadUpvalue(idx) => {
                     // Load value from captured upvalue
                     if let Some(upvalue_handle) = self.current_upvalues.get(idx as usize) {
@@ -403,41 +718,70 @@ impl Dispatcher {
                 Opcode::Add => {
                     let b = self.stack.pop().unwrap_or(Value::Undefined);
                     let a = self.stack.pop().unwrap_or(Value::Undefined);
+                    let a = self.to_primitive(a, PreferredType::Default, functions)?;
+                    let b = self.to_primitive(b, PreferredType::Default, functions)?;
                     let result = self.add(a, b)?;
                     self.stack.push(result);
                 }
                 Opcode::Sub => {
                     let b = self.stack.pop().unwrap_or(Value::Undefined);
                     let a = self.stack.pop().unwrap_or(Value::Undefined);
+                    let a = self.to_primitive(a, PreferredType::Number, functions)?;
+                    let b = self.to_primitive(b, PreferredType::Number, functions)?;
                     let result = self.sub(a, b)?;
                     self.stack.push(result);
                 }
                 Opcode::Mul => {
                     let b = self.stack.pop().unwrap_or(Value::Undefined);
                     let a = self.stack.pop().unwrap_or(Value::Undefined);
+                    let a = self.to_primitive(a, PreferredType::Number, functions)?;
+                    let b = self.to_primitive(b, PreferredType::Number, functions)?;
                     let result = self.mul(a, b)?;
                     self.stack.push(result);
                 }
                 Opcode::Div => {
                     let b = self.stack.pop().unwrap_or(Value::Undefined);
                     let a = self.stack.pop().unwrap_or(Value::Undefined);
+                    let a = self.to_primitive(a, PreferredType::Number, functions)?;
+                    let b = self.to_primitive(b, PreferredType::Number, functions)?;
                     let result = self.div(a, b)?;
                     self.stack.push(result);
                 }
                 Opcode::Mod => {
                     let b = self.stack.pop().unwrap_or(Value::Undefined);
                     let a = self.stack.pop().unwrap_or(Value::Undefined);
+                    let a = self.to_primitive(a, PreferredType::Number, functions)?;
+                    let b = self.to_primitive(b, PreferredType::Number, functions)?;
                     let result = self.modulo(a, b)?;
                     self.stack.push(result);
                 }
                 Opcode::Exp => {
                     let b = self.stack.pop().unwrap_or(Value::Undefined);
                     let a = self.stack.pop().unwrap_or(Value::Undefined);
+                    let a = self.to_primitive(a, PreferredType::Number, functions)?;
+                    let b = self.to_primitive(b, PreferredType::Number, functions)?;
                     let result = self.exponentiate(a, b)?;
                     self.stack.push(result);
                 }
+                Opcode::BitAnd
+                | Opcode::BitOr
+                | Opcode::BitXor
+                | Opcode::ShiftLeft
+                | Opcode::ShiftRight
+                | Opcode::ShiftRightUnsigned => {
+                    let b = self.stack.pop().unwrap_or(Value::Undefined);
+                    let a = self.stack.pop().unwrap_or(Value::Undefined);
+                    let result = self.bitwise(&inst.opcode, &a, &b);
+                    self.stack.push(result);
+                }
+                Opcode::BitNot => {
+                    let a = self.stack.pop().unwrap_or(Value::Undefined);
+                    let result = Value::Smi(!Self::to_int32(self.to_number(&a)));
+                    self.stack.push(result);
+                }
                 Opcode::Neg => {
                     let a = self.stack.pop().unwrap_or(Value::Undefined);
+                    let a = self.to_primitive(a, PreferredType::Number, functions)?;
                     let result = self.neg(a)?;
                     self.stack.push(result);
                 }
@@ -450,13 +794,47 @@ impl Dispatcher {
                 Opcode::Typeof => {
                     let a = self.stack.pop().unwrap_or(Value::Undefined);
                     // typeof operator - returns type as string
-                    self.stack.push(Value::String(a.type_of()));
+                    let type_name = if Self::is_bound_function(&a) {
+                        // Bound functions are native objects, but callable
+                        "function".to_string()
+                    } else if Self::symbol_value(&a).is_some() {
+                        "symbol".to_string()
+                    } else {
+                        a.type_of()
+                    };
+                    self.stack.push(Value::String(type_name));
                 }
                 Opcode::Void => {
                     // void operator - discard value and push undefined
                     let _discarded = self.stack.pop();
                     self.stack.push(Value::Undefined);
                 }
+                Opcode::ToNumber => {
+                    let a = self.stack.pop().unwrap_or(Value::Undefined);
+                    let primitive = self.to_primitive(a, PreferredType::Number, functions)?;
+                    let result = match primitive {
+                        Value::Smi(_) | Value::Double(_) => primitive,
+                        Value::BigInt(_) => {
+                            let message = "Cannot convert a BigInt value to a number";
+                            return Err(Self::type_error(message));
+                        }
+                        _ if Self::symbol_value(&primitive).is_some() => {
+                            let message = "Cannot convert a Symbol value to a number";
+                            return Err(Self::type_error(message));
+                        }
+                        other => Value::Double(self.to_number(&other)),
+                    };
+                    self.stack.push(result);
+                }
+                Opcode::ToString => {
+                    let a = self.stack.pop().unwrap_or(Value::Undefined);
+                    let primitive = self.to_primitive(a, PreferredType::String, functions)?;
+                    if Self::symbol_value(&primitive).is_some() {
+                        return Err(Self::type_error("Cannot convert a Symbol value to a string"));
+                    }
+                    let result = Value::String(self.to_string_value(&primitive));
+                    self.stack.push(result);
+                }
                 Opcode::Equal => {
                     let b = self.stack.pop().unwrap_or(Value::Undefined);
                     let a = self.stack.pop().unwrap_or(Value::Undefined);
@@ -544,6 +922,18 @@ impl Dispatcher {
                         ctx.instruction_pointer = target;
                     }
                 }
+                Opcode::OptionalChainStart(reg_id) => {
+                    ctx.set_register(reg_id.0 as usize, Value::Smi(self.stack.len() as i32));
+                }
+                Opcode::OptionalChainShortCircuit(reg_id, target) => {
+                    if matches!(self.stack.last(), Some(Value::Undefined | Value::Null)) {
+                        if let Value::Smi(height) = ctx.get_register(reg_id.0 as usize) {
+                            self.stack.truncate(height as usize);
+                        }
+                        self.stack.push(Value::Undefined);
+                        ctx.instruction_pointer = target;
+                    }
+                }
                 Opcode::Return => {
                     let return_value = self.stack.pop().unwrap_or(Value::Undefined);
                     return Ok(return_value);
@@ -570,122 +960,77 @@ impl Dispatcher {
                             if let Some(gc_obj) = borrowed.downcast_ref::<Box<dyn Any>>() {
                                 // Check if it's a GCObject wrapped in Box<dyn Any>
                                 if let Some(gc_object) = gc_obj.downcast_ref::<GCObject>() {
-                                    // Check if this is an array (has numeric "length" property)
-                                    let is_array = matches!(gc_object.get("length"), Value::Smi(_));
-
-                                    let value = if is_array {
-                                        // Check for array prototype methods first
-                                        match name.as_str() {
-                                            "map" => Value::NativeFunction("Array.prototype.map".to_string()),
-                                            "filter" => Value::NativeFunction("Array.prototype.filter".to_string()),
-                                            "forEach" => Value::NativeFunction("Array.prototype.forEach".to_string()),
-                                            "reduce" => Value::NativeFunction("Array.prototype.reduce".to_string()),
-                                            "find" => Value::NativeFunction("Array.prototype.find".to_string()),
-                                            "findIndex" => Value::NativeFunction("Array.prototype.findIndex".to_string()),
-                                            "some" => Value::NativeFunction("Array.prototype.some".to_string()),
-                                            "every" => Value::NativeFunction("Array.prototype.every".to_string()),
-                                            "includes" => Value::NativeFunction("Array.prototype.includes".to_string()),
-                                            "indexOf" => Value::NativeFunction("Array.prototype.indexOf".to_string()),
-                                            "push" => Value::NativeFunction("Array.prototype.push".to_string()),
-                                            "pop" => Value::NativeFunction("Array.prototype.pop".to_string()),
-                                            "shift" => Value::NativeFunction("Array.prototype.shift".to_string()),
-                                            "unshift" => Value::NativeFunction("Array.prototype.unshift".to_string()),
-                                            "slice" => Value::NativeFunction("Array.prototype.slice".to_string()),
-                                            "splice" => Value::NativeFunction("Array.prototype.splice".to_string()),
-                                            "concat" => Value::NativeFunction("Array.prototype.concat".to_string()),
-                                            "join" => Value::NativeFunction("Array.prototype.join".to_string()),
-                                            "reverse" => Value::NativeFunction("Array.prototype.reverse".to_string()),
-                                            "sort" => Value::NativeFunction("Array.prototype.sort".to_string()),
-                                            _ => gc_object.get(&name), // Regular property access
-                                        }
+                                    let is_array = gc_object.is_array();
+
+                                    let (methods, prefix) = if is_array {
+                                        (ARRAY_PROTOTYPE_METHODS, "Array.prototype")
                                     } else {
-                                        // Not an array - check for Object.prototype methods first
-                                        match name.as_str() {
-                                            "toString" => Value::NativeFunction("Object.prototype.toString".to_string()),
-                                            "valueOf" => Value::NativeFunction("Object.prototype.valueOf".to_string()),
-                                            "hasOwnProperty" => Value::NativeFunction("Object.prototype.hasOwnProperty".to_string()),
-                                            "propertyIsEnumerable" => Value::NativeFunction("Object.prototype.propertyIsEnumerable".to_string()),
-                                            "isPrototypeOf" => Value::NativeFunction("Object.prototype.isPrototypeOf".to_string()),
-                                            "toLocaleString" => Value::NativeFunction("Object.prototype.toLocaleString".to_string()),
-                                            _ => gc_object.get(&name)  // Regular property access
+                                        (OBJECT_PROTOTYPE_METHODS, "Object.prototype")
+                                    };
+                                    // Prototype methods first, then regular property access
+                                    let value = match Self::native_method(methods, prefix, &name) {
+                                        Some(method) => method,
+                                        None => {
+                                            let slot =
+                                                ctx.property_slots.get(ctx.instruction_pointer - 1);
+                                            Self::cached_get(slot, gc_object, &name)
                                         }
                                     };
+                                    // Accessor properties hold undefined as their data value
+                                    let getter = match value {
+                                        Value::Undefined => gc_object
+                                            .accessor(&name)
+                                            .and_then(|accessor| accessor.get),
+                                        _ => None,
+                                    };
                                     drop(borrowed);
+                                    let value = match getter {
+                                        Some(getter) => {
+                                            let this = Value::NativeObject(native_obj.clone());
+                                            self.call_value(getter, Some(this), vec![], functions)?
+                                        }
+                                        None => value,
+                                    };
                                     self.stack.push(value);
                                 } else {
                                     drop(borrowed);
                                     self.stack.push(Value::Undefined);
                                 }
                             } else if borrowed.is::<ConsoleObject>() {
-                                // Return method based on property name
-                                match name.as_str() {
-                                    "log" => self
-                                        .stack
-                                        .push(Value::NativeFunction("console.log".to_string())),
-                                    "error" => self
-                                        .stack
-                                        .push(Value::NativeFunction("console.error".to_string())),
-                                    "warn" => self
-                                        .stack
-                                        .push(Value::NativeFunction("console.warn".to_string())),
-                                    "info" => self
-                                        .stack
-                                        .push(Value::NativeFunction("console.info".to_string())),
-                                    _ => self.stack.push(Value::Undefined),
-                                }
+                                let method = Self::native_method(CONSOLE_METHODS, "console", &name);
+                                self.stack.push(method.unwrap_or(Value::Undefined));
                             } else if borrowed.is::<MathObject>() {
+                                let value = MATH_CONSTANTS
+                                    .iter()
+                                    .find(|(constant, _)| *constant == name)
+                                    .map(|(_, value)| Value::Double(*value))
+                                    .or_else(|| Self::native_method(MATH_METHODS, "Math", &name));
+                                self.stack.push(value.unwrap_or(Value::Undefined));
+                            } else if borrowed.is::<GlobalObject>() {
+                                let value = self.globals.get(&name).cloned().unwrap_or(Value::Undefined);
+                                self.stack.push(value);
+                            } else if borrowed.is::<BoundFunction>() {
+                                self.stack.push(Self::function_prototype_property(&name));
+                            } else if borrowed.is::<GeneratorObject>() {
                                 match name.as_str() {
-                                    "abs" => self
-                                        .stack
-                                        .push(Value::NativeFunction("Math.abs".to_string())),
-                                    "ceil" => self
-                                        .stack
-                                        .push(Value::NativeFunction("Math.ceil".to_string())),
-                                    "floor" => self
-                                        .stack
-                                        .push(Value::NativeFunction("Math.floor".to_string())),
-                                    "round" => self
-                                        .stack
-                                        .push(Value::NativeFunction("Math.round".to_string())),
-                                    "sqrt" => self
-                                        .stack
-                                        .push(Value::NativeFunction("Math.sqrt".to_string())),
-                                    "pow" => self
-                                        .stack
-                                        .push(Value::NativeFunction("Math.pow".to_string())),
-                                    "sin" => self
-                                        .stack
-                                        .push(Value::NativeFunction("Math.sin".to_string())),
-                                    "cos" => self
-                                        .stack
-                                        .push(Value::NativeFunction("Math.cos".to_string())),
-                                    "tan" => self
-                                        .stack
-                                        .push(Value::NativeFunction("Math.tan".to_string())),
-                                    "random" => self
-                                        .stack
-                                        .push(Value::NativeFunction("Math.random".to_string())),
-                                    "max" => self
-                                        .stack
-                                        .push(Value::NativeFunction("Math.max".to_string())),
-                                    "min" => self
-                                        .stack
-                                        .push(Value::NativeFunction("Math.min".to_string())),
-                                    "PI" => self.stack.push(Value::Double(MathObject::PI)),
-                                    "E" => self.stack.push(Value::Double(MathObject::E)),
+                                    "next" | "return" | "throw" => {
+                                        let method = format!("Generator.prototype.{}", name);
+                                        self.stack.push(Value::NativeFunction(method));
+                                    }
                                     _ => self.stack.push(Value::Undefined),
                                 }
+                            } else if borrowed.is::<PerformanceObject>() {
+                                let method =
+                                    Self::native_method(PERFORMANCE_METHODS, "performance", &name);
+                                self.stack.push(method.unwrap_or(Value::Undefined));
                             } else if borrowed.is::<JSONObject>() {
-                                // Return JSON method based on property name
-                                match name.as_str() {
-                                    "stringify" => self
-                                        .stack
-                                        .push(Value::NativeFunction("JSON.stringify".to_string())),
-                                    "parse" => self
-                                        .stack
-                                        .push(Value::NativeFunction("JSON.parse".to_string())),
-                                    _ => self.stack.push(Value::Undefined),
-                                }
+                                let method = Self::native_method(JSON_METHODS, "JSON", &name);
+                                self.stack.push(method.unwrap_or(Value::Undefined));
+                            } else if borrowed.is::<JsDate>() {
+                                let prefix = "Date.prototype";
+                                let method =
+                                    Self::native_method(DATE_PROTOTYPE_METHODS, prefix, &name);
+                                self.stack.push(method.unwrap_or(Value::Undefined));
                             } else {
                                 // Unknown NativeObject type
                                 self.stack.push(Value::Undefined);
@@ -693,30 +1038,32 @@ impl Dispatcher {
                         }
                         Value::NativeFunction(fn_name) => {
                             // Handle static properties on constructor functions
+                            let (fn_name, realm) = Self::split_realm_tag(&fn_name);
                             if fn_name == "Promise" {
-                                match name.as_str() {
-                                    "resolve" => self
-                                        .stack
-                                        .push(Value::NativeFunction("Promise.resolve".to_string())),
-                                    "reject" => self
-                                        .stack
-                                        .push(Value::NativeFunction("Promise.reject".to_string())),
-                                    _ => self.stack.push(Value::Undefined),
-                                }
-                            } else if matches!(fn_name.as_str(), "Error" | "TypeError" | "ReferenceError" |
+                                let method = Self::native_method(PROMISE_METHODS, "Promise", &name);
+                                self.stack.push(method.unwrap_or(Value::Undefined));
+                            } else if matches!(fn_name, "Error" | "TypeError" | "ReferenceError" |
                                              "SyntaxError" | "RangeError" | "URIError" | "EvalError") {
                                 // Handle Error constructor properties
                                 match name.as_str() {
                                     "prototype" => {
                                         // Return the pre-initialized prototype object
                                         let proto_key = format!("{}.prototype", fn_name);
-                                        if let Some(proto) = self.globals.get(&proto_key) {
-                                            self.stack.push(proto.clone());
+                                        if let Some(proto) = self.realm_global(realm, &proto_key) {
+                                            self.stack.push(proto);
                                         } else {
                                             // Prototype should have been initialized in set_heap()
                                             self.stack.push(Value::Undefined);
                                         }
                                     }
+                                    "captureStackTrace" if fn_name == "Error" => {
+                                        let method = "Error.captureStackTrace".to_string();
+                                        self.stack.push(Value::NativeFunction(method));
+                                    }
+                                    "stackTraceLimit" if fn_name == "Error" => {
+                                        let limit = self.stack_trace_limit as f64;
+                                        self.stack.push(Value::Double(limit));
+                                    }
                                     _ => self.stack.push(Value::Undefined),
                                 }
                             } else if fn_name == "Number" {
@@ -738,26 +1085,56 @@ impl Dispatcher {
                                     "parseFloat" => self.stack.push(Value::NativeFunction("Number.parseFloat".to_string())),
                                     _ => self.stack.push(Value::Undefined),
                                 }
+                            } else if fn_name == "Symbol" {
+                                let value = match name.as_str() {
+                                    "toPrimitive" => self.globals.get("Symbol.toPrimitive"),
+                                    _ => None,
+                                };
+                                let value = value.cloned();
+                                self.stack.push(value.unwrap_or(Value::Undefined));
+                            } else if fn_name == "Date" {
+                                match name.as_str() {
+                                    "now" => self.stack.push(Value::NativeFunction("Date.now".to_string())),
+                                    _ => self.stack.push(Value::Undefined),
+                                }
                             } else if fn_name == "Array" {
                                 // Handle Array constructor properties
                                 match name.as_str() {
                                     "isArray" => self.stack.push(Value::NativeFunction("Array.isArray".to_string())),
                                     "of" => self.stack.push(Value::NativeFunction("Array.of".to_string())),
                                     "from" => self.stack.push(Value::NativeFunction("Array.from".to_string())),
+                                    "prototype" => {
+                                        let proto = self.realm_global(realm, "Array.prototype");
+                                        self.stack.push(proto.unwrap_or(Value::Undefined));
+                                    }
                                     _ => self.stack.push(Value::Undefined),
                                 }
                             } else if fn_name == "Object" {
                                 // Handle Object constructor properties
                                 match name.as_str() {
                                     "keys" => self.stack.push(Value::NativeFunction("Object.keys".to_string())),
+                                    "is" => self.stack.push(Value::NativeFunction("Object.is".to_string())),
                                     "values" => self.stack.push(Value::NativeFunction("Object.values".to_string())),
                                     "entries" => self.stack.push(Value::NativeFunction("Object.entries".to_string())),
                                     "assign" => self.stack.push(Value::NativeFunction("Object.assign".to_string())),
+                                    "prototype" => {
+                                        let proto = self.realm_global(realm, "Object.prototype");
+                                        self.stack.push(proto.unwrap_or(Value::Undefined));
+                                    }
                                     _ => self.stack.push(Value::Undefined),
                                 }
 
                             } else {
-                                self.stack.push(Value::Undefined);
+                                self.stack.push(Self::function_prototype_property(&name));
+                            }
+                        }
+                        Value::HeapObject(id) => {
+                            // User-defined function - its own `prototype`, then Function.prototype methods
+                            if name == "prototype" {
+                                let prototype = self.function_prototype(id);
+                                self.stack.push(prototype);
+                            } else {
+                                self.stack.push(Self::function_prototype_property(&name));
                             }
                         }
                         Value::String(s) => {
@@ -784,6 +1161,8 @@ impl Dispatcher {
                                 "repeat" => Value::NativeFunction("String.prototype.repeat".to_string()),
                                 "padStart" => Value::NativeFunction("String.prototype.padStart".to_string()),
                                 "padEnd" => Value::NativeFunction("String.prototype.padEnd".to_string()),
+                                "isWellFormed" => Value::NativeFunction("String.prototype.isWellFormed".to_string()),
+                                "toWellFormed" => Value::NativeFunction("String.prototype.toWellFormed".to_string()),
                                 _ => Value::Undefined,
                             };
                             self.stack.push(value);
@@ -817,24 +1196,53 @@ impl Dispatcher {
                     match obj {
                         Value::NativeObject(native_obj) => {
                             let mut borrowed = native_obj.borrow_mut();
+                            let mut setter = None;
                             if let Some(gc_obj) = borrowed.downcast_mut::<Box<dyn Any>>() {
                                 // Check if it's a GCObject wrapped in Box<dyn Any>
                                 if let Some(gc_object) = gc_obj.downcast_mut::<GCObject>() {
-                                    gc_object.set(name, value.clone());
+                                    // Storing to an accessor without a setter does nothing
+                                    match gc_object.accessor(&name) {
+                                        Some(accessor) => setter = accessor.set,
+                                        None => gc_object.set(name, value.clone()),
+                                    }
                                 }
+                            } else if borrowed.is::<GlobalObject>() {
+                                self.globals.insert(name, value.clone());
                             }
                             // For other NativeObjects, we just ignore the store (non-extensible)
+                            drop(borrowed);
+                            if let Some(setter) = setter {
+                                let this = Value::NativeObject(native_obj.clone());
+                                let args = vec![value.clone()];
+                                self.call_value(setter, Some(this), args, functions)?;
+                            }
                         }
-                        Value::NativeFunction(ref fn_name) if matches!(fn_name.as_str(),
+                        Value::NativeFunction(ref fn_name)
+                            if matches!(Self::split_realm_tag(fn_name).0,
                             "Error" | "TypeError" | "ReferenceError" | "SyntaxError" |
                             "RangeError" | "URIError" | "EvalError") => {
                             // Handle Error constructor property assignment (e.g., Error.prototype = ...)
+                            let (fn_name, realm) = Self::split_realm_tag(fn_name);
                             if name == "prototype" {
                                 let proto_key = format!("{}.prototype", fn_name);
-                                self.globals.insert(proto_key, value.clone());
+                                self.set_realm_global(realm, proto_key, value.clone());
+                            } else if name == "stackTraceLimit" && fn_name == "Error" {
+                                // A limit that is not a number captures no frames
+                                let limit = match value {
+                                    Value::Smi(_) | Value::Double(_) => self.to_number(&value),
+                                    _ => 0.0,
+                                };
+                                self.stack_trace_limit = if limit.is_nan() {
+                                    0
+                                } else {
+                                    limit.max(0.0) as usize
+                                };
                             }
                             // Ignore other property stores on Error constructors
                         }
+                        Value::HeapObject(id) if name == "prototype" => {
+                            self.function_prototypes.insert(id, value.clone());
+                        }
                         _ => {
                             // Ignore stores to non-objects
                         }
@@ -849,17 +1257,31 @@ impl Dispatcher {
 
                     let result = match obj {
                         Value::NativeObject(native_obj) => {
+                            // Convert index to string key (may call a user toString)
+                            let key = self.coerce_property_key(index, functions)?;
                             let borrowed = native_obj.borrow();
-                            if let Some(gc_obj) = borrowed.downcast_ref::<Box<dyn Any>>() {
-                                if let Some(gc_object) = gc_obj.downcast_ref::<GCObject>() {
-                                    // Convert index to string key
-                                    let key = self.to_property_key(&index);
-                                    gc_object.get(&key)
-                                } else {
-                                    Value::Undefined
+                            let gc_object = borrowed
+                                .downcast_ref::<Box<dyn Any>>()
+                                .and_then(|gc_obj| gc_obj.downcast_ref::<GCObject>());
+                            let (value, getter) = match gc_object {
+                                Some(gc_object) => match gc_object.get(&key) {
+                                    Value::Undefined => {
+                                        let getter = gc_object
+                                            .accessor(&key)
+                                            .and_then(|accessor| accessor.get);
+                                        (Value::Undefined, getter)
+                                    }
+                                    value => (value, None),
+                                },
+                                None => (Value::Undefined, None),
+                            };
+                            drop(borrowed);
+                            match getter {
+                                Some(getter) => {
+                                    let this = Value::NativeObject(native_obj.clone());
+                                    self.call_value(getter, Some(this), vec![], functions)?
                                 }
-                            } else {
-                                Value::Undefined
+                                None => value,
                             }
                         }
                         Value::String(s) => {
@@ -900,13 +1322,23 @@ impl Dispatcher {
 
                     match obj {
                         Value::NativeObject(native_obj) => {
+                            let key = self.coerce_property_key(index, functions)?;
                             let mut borrowed = native_obj.borrow_mut();
+                            let mut setter = None;
                             if let Some(gc_obj) = borrowed.downcast_mut::<Box<dyn Any>>() {
                                 if let Some(gc_object) = gc_obj.downcast_mut::<GCObject>() {
-                                    let key = self.to_property_key(&index);
-                                    gc_object.set(key, value.clone());
+                                    match gc_object.accessor(&key) {
+                                        Some(accessor) => setter = accessor.set,
+                                        None => gc_object.set(key, value.clone()),
+                                    }
                                 }
                             }
+                            drop(borrowed);
+                            if let Some(setter) = setter {
+                                let this = Value::NativeObject(native_obj.clone());
+                                let args = vec![value.clone()];
+                                self.call_value(setter, Some(this), args, functions)?;
+                            }
                         }
                         _ => {
                             // Ignore index stores to non-objects
@@ -915,10 +1347,125 @@ impl Dispatcher {
                     // Push the assigned value back - assignment expressions return the assigned value
                     self.stack.push(value);
                 }
+                Opcode::LoadPrivate(key) => {
+                    let obj = self.stack.pop().unwrap_or(Value::Undefined);
+                    let value = match &obj {
+                        Value::NativeObject(native_obj) => self.private_fields.get(native_obj, &key),
+                        _ => None,
+                    };
+                    match value {
+                        Some(value) => self.stack.push(value),
+                        None => {
+                            return Err(Self::private_brand_error(format!(
+                                "Cannot read private member {} from an object whose class did not declare it",
+                                Self::private_name(&key)
+                            )))
+                        }
+                    }
+                }
+                Opcode::StorePrivate(key) => {
+                    let value = self.stack.pop().unwrap_or(Value::Undefined);
+                    let obj = self.stack.pop().unwrap_or(Value::Undefined);
+                    let stored = match &obj {
+                        Value::NativeObject(native_obj) => {
+                            self.private_fields.set(native_obj, &key, value.clone())
+                        }
+                        _ => false,
+                    };
+                    if !stored {
+                        return Err(Self::private_brand_error(format!(
+                            "Cannot write private member {} to an object whose class did not declare it",
+                            Self::private_name(&key)
+                        )));
+                    }
+                    self.stack.push(value);
+                }
+                Opcode::LoadNewTarget => {
+                    self.stack.push(ctx.new_target.clone());
+                }
+                Opcode::DefinePrivate(key) => {
+                    let value = self.stack.pop().unwrap_or(Value::Undefined);
+                    let obj = self.stack.pop().unwrap_or(Value::Undefined);
+                    let defined = match &obj {
+                        Value::NativeObject(native_obj) => {
+                            self.private_fields.define(native_obj, &key, value.clone())
+                        }
+                        _ => false,
+                    };
+                    if !defined {
+                        return Err(Self::private_brand_error(format!(
+                            "Cannot initialize {} twice on the same object",
+                            Self::private_name(&key)
+                        )));
+                    }
+                    self.stack.push(value);
+                }
+                op @ (Opcode::DefineGetter | Opcode::DefineSetter) => {
+                    // Object literal `get key() {}` / `set key(v) {}`
+                    let function = self.stack.pop().unwrap_or(Value::Undefined);
+                    let key = self.stack.pop().unwrap_or(Value::Undefined);
+                    let obj = self.stack.last().cloned().unwrap_or(Value::Undefined);
+                    if let Value::NativeObject(native_obj) = obj {
+                        let key = self.coerce_property_key(key, functions)?;
+                        let mut borrowed = native_obj.borrow_mut();
+                        if let Some(gc_object) = borrowed
+                            .downcast_mut::<Box<dyn Any>>()
+                            .and_then(|gc_obj| gc_obj.downcast_mut::<GCObject>())
+                        {
+                            if matches!(op, Opcode::DefineGetter) {
+                                gc_object.define_accessor(key, Some(function), None);
+                            } else {
+                                gc_object.define_accessor(key, None, Some(function));
+                            }
+                        }
+                    }
+                }
+                Opcode::CopyDataProperties => {
+                    // Object spread: copy own enumerable properties of source onto target
+                    let source = self.stack.pop().unwrap_or(Value::Undefined);
+                    let entries: Vec<(String, Value)> = match source {
+                        Value::NativeObject(ref native_obj) => {
+                            let borrowed = native_obj.borrow();
+                            match borrowed
+                                .downcast_ref::<Box<dyn Any>>()
+                                .and_then(|b| b.downcast_ref::<GCObject>())
+                            {
+                                Some(gc_object) => gc_object
+                                    .keys()
+                                    .into_iter()
+                                    .map(|k| {
+                                        let v = gc_object.get(&k);
+                                        (k, v)
+                                    })
+                                    .collect(),
+                                None => Vec::new(),
+                            }
+                        }
+                        Value::String(ref s) => s
+                            .chars()
+                            .enumerate()
+                            .map(|(i, c)| (i.to_string(), Value::String(c.to_string())))
+                            .collect(),
+                        // null, undefined and primitives contribute no properties
+                        _ => Vec::new(),
+                    };
+
+                    if let Some(Value::NativeObject(target)) = self.stack.last() {
+                        let mut borrowed = target.borrow_mut();
+                        if let Some(gc_object) = borrowed
+                            .downcast_mut::<Box<dyn Any>>()
+                            .and_then(|b| b.downcast_mut::<GCObject>())
+                        {
+                            for (key, value) in entries {
+                                gc_object.set(key, value);
+                            }
+                        }
+                    }
+                }
                 Opcode::CreateArray(count) => {
                     // Create array with elements from stack
                     if let Some(ref heap) = self.heap {
-                        let gc_object = heap.create_object();
+                        let gc_object = heap.create_array();
                         let boxed: Box<dyn Any> = Box::new(gc_object);
                         let obj_ref =
                             Rc::new(RefCell::new(boxed)) as Rc<RefCell<dyn Any>>;
@@ -957,6 +1504,19 @@ impl Dispatcher {
                         self.stack.push(Value::HeapObject(0));
                     }
                 }
+                Opcode::ArrayPush => {
+                    let value = self.stack.pop().unwrap_or(Value::Undefined);
+                    if let Some(array) = self.stack.last() {
+                        Self::append_to_array(array, vec![value]);
+                    }
+                }
+                Opcode::ArraySpread => {
+                    let iterable = self.stack.pop().unwrap_or(Value::Undefined);
+                    let elements = self.iterable_to_vec(iterable, functions)?;
+                    if let Some(array) = self.stack.last() {
+                        Self::append_to_array(array, elements);
+                    }
+                }
                 Opcode::CreateRegExp(pattern_idx, flags_idx) => {
                     // Create a RegExp object
                     // For now, store pattern and flags as a string representation
@@ -997,6 +1557,7 @@ impl Dispatcher {
                     // Create a closure by capturing upvalues from the current scope
                     if upvalue_descs.is_empty() {
                         // No captured variables, just push the function index
+                        self.bind_function_realm(idx);
                         self.stack.push(Value::HeapObject(idx));
                     } else {
                         // Capture upvalues based on descriptors
@@ -1015,6 +1576,7 @@ impl Dispatcher {
                         // Push a closure ID (with high bit set to distinguish from plain function index)
                         // We encode closure IDs starting from 1_000_000 to avoid collision with function indices
                         let encoded_id = 1_000_000 + closure_id;
+                        self.bind_function_realm(encoded_id);
                         self.stack.push(Value::HeapObject(encoded_id));
                     }
                 }
@@ -1028,6 +1590,7 @@ impl Dispatcher {
 
                     // Pop the callee (function) from stack
                     let callee = self.stack.pop().unwrap_or(Value::Undefined);
+                    self.call_site = inst.source_position;
 
                     match callee {
                         Value::NativeFunction(name) => {
@@ -1039,6 +1602,60 @@ impl Dispatcher {
                             let result = self.call_function_with_args(idx, args, functions)?;
                             self.stack.push(result);
                         }
+                        Value::NativeObject(_) if Self::is_bound_function(&callee) => {
+                            let result = self.call_value(callee, None, args, functions)?;
+                            self.stack.push(result);
+                        }
+                        _ => {
+                            return Err(JsError {
+                                kind: ErrorKind::TypeError,
+                                message: format!("{:?} is not a function", callee),
+                                stack: vec![],
+                                source_position: None,
+                            });
+                        }
+                    }
+                }
+                Opcode::TailCall(argc) => {
+                    let mut args = Vec::with_capacity(argc as usize);
+                    for _ in 0..argc {
+                        args.push(self.stack.pop().unwrap_or(Value::Undefined));
+                    }
+                    args.reverse();
+
+                    let callee = self.stack.pop().unwrap_or(Value::Undefined);
+                    self.call_site = inst.source_position;
+
+                    match callee {
+                        Value::NativeFunction(name) => {
+                            // No frame to reuse - call and return the result directly
+                            return self.call_native_function(&name, args);
+                        }
+                        Value::HeapObject(idx)
+                            if self.function_realm(idx) != self.current_realm =>
+                        {
+                            // The callee runs with its own realm's globals
+                            return self.call_function_with_args(idx, args, functions);
+                        }
+                        Value::HeapObject(idx) => {
+                            // Replace the current frame with the callee's so proper
+                            // tail calls run in constant stack space
+                            let (fn_ctx, closure_upvalues) =
+                                self.resolve_function(idx, functions)?;
+                            *ctx = fn_ctx;
+                            for (i, arg) in args.into_iter().enumerate() {
+                                ctx.set_register(i, arg);
+                            }
+                            self.open_upvalues.clear();
+                            self.current_upvalues = closure_upvalues.unwrap_or_default();
+                            let frame = self.call_site_frame();
+                            if let Some(top) = self.call_stack.last_mut() {
+                                *top = (idx, frame);
+                            }
+                        }
+                        Value::NativeObject(_) if Self::is_bound_function(&callee) => {
+                            return self.call_value(callee, None, args, functions);
+                        }
                         _ => {
                             return Err(JsError {
                                 kind: ErrorKind::TypeError,
@@ -1061,32 +1678,23 @@ impl Dispatcher {
                     let method = self.stack.pop().unwrap_or(Value::Undefined);
                     // Pop the receiver (this) from stack
                     let receiver = self.stack.pop().unwrap_or(Value::Undefined);
+                    self.call_site = inst.source_position;
 
                     match method {
                         Value::NativeFunction(name) => {
-                            // Check if this is a prototype method that needs the receiver
-                            if name.starts_with("Array.prototype.") {
-                                let result = self.call_array_prototype_method(&name, receiver, args, functions)?;
-                                self.stack.push(result);
-                            } else if name.starts_with("Object.prototype.") {
-                                let result = self.call_object_prototype_method(&name, receiver, args)?;
-                                self.stack.push(result);
-                            } else if name.starts_with("String.prototype.") {
-                                let result = self.call_string_prototype_method(&name, receiver, args)?;
-                                self.stack.push(result);
-                            } else if name.starts_with("Number.prototype.") {
-                                let result = self.call_number_prototype_method(&name, receiver)?;
-                                self.stack.push(result);
-                            } else {
-                                let result = self.call_native_function(&name, args)?;
-                                self.stack.push(result);
-                            }
+                            let result = self.call_native_method(&name, receiver, args, functions)?;
+                            self.stack.push(result);
                         }
                         Value::HeapObject(idx) => {
                             // User-defined method - call with this binding
                             let result = self.call_method_with_this(idx, receiver, args, functions)?;
                             self.stack.push(result);
                         }
+                        Value::NativeObject(_) if Self::is_bound_function(&method) => {
+                            // The bound `this` wins over the receiver
+                            let result = self.call_value(method, Some(receiver), args, functions)?;
+                            self.stack.push(result);
+                        }
                         _ => {
                             return Err(JsError {
                                 kind: ErrorKind::TypeError,
@@ -1110,26 +1718,33 @@ impl Dispatcher {
 
                     // Now pop the constructor (it's below the arguments)
                     let constructor = self.stack.pop().unwrap_or(Value::Undefined);
+                    self.call_site = inst.source_position;
 
-                    match constructor {
-                        Value::NativeFunction(name) => {
-                            let result = self.call_native_function(&name, args)?;
-                            self.stack.push(result);
-                        }
-                        Value::HeapObject(idx) => {
-                            // User-defined constructor - call with new instance as this
-                            let result = self.call_constructor(idx, args, functions)?;
-                            self.stack.push(result);
-                        }
-                        _ => {
-                            return Err(JsError {
-                                kind: ErrorKind::TypeError,
-                                message: format!("{:?} is not a constructor", constructor),
-                                stack: vec![],
-                                source_position: None,
-                            });
-                        }
-                    }
+                    let result = self.construct_value(constructor, args, functions)?;
+                    self.stack.push(result);
+                }
+
+                Opcode::CallSpread => {
+                    let args = self.stack.pop().unwrap_or(Value::Undefined);
+                    let args = self.array_like_to_vec(args)?;
+                    let callee = self.stack.pop().unwrap_or(Value::Undefined);
+                    let result = self.call_value(callee, None, args, functions)?;
+                    self.stack.push(result);
+                }
+                Opcode::CallMethodSpread => {
+                    let args = self.stack.pop().unwrap_or(Value::Undefined);
+                    let args = self.array_like_to_vec(args)?;
+                    let method = self.stack.pop().unwrap_or(Value::Undefined);
+                    let receiver = self.stack.pop().unwrap_or(Value::Undefined);
+                    let result = self.call_value(method, Some(receiver), args, functions)?;
+                    self.stack.push(result);
+                }
+                Opcode::CallNewSpread => {
+                    let args = self.stack.pop().unwrap_or(Value::Undefined);
+                    let args = self.array_like_to_vec(args)?;
+                    let constructor = self.stack.pop().unwrap_or(Value::Undefined);
+                    let result = self.construct_value(constructor, args, functions)?;
+                    self.stack.push(result);
                 }
 
                 // Exception handling opcodes
@@ -1151,17 +1766,21 @@ impl Dispatcher {
                 }
 
                 Opcode::PushFinally(finally_offset) => {
-                    if let Some(handler) = self.try_stack.last_mut() {
-                        handler.finally_offset = Some(finally_offset);
-                    }
+                    self.try_stack.push(TryHandler {
+                        catch_offset: None,
+                        finally_offset: Some(finally_offset),
+                        stack_height: self.stack.len(),
+                    });
                 }
 
                 Opcode::PopFinally => {
-                    if let Some(handler) = self.try_stack.last_mut() {
-                        handler.finally_offset = None;
-                    }
+                    // If we're still returning, run the next finally out
+                    if let Some(value) = self.pending_return.take() {
+                        if let Some(value) = self.unwind_return(value, ctx) {
+                            return Ok(value);
+                        }
                     // If we're still throwing, re-throw after finally completes
-                    if let Some(exc) = self.current_exception.take() {
+                    } else if let Some(exc) = self.current_exception.take() {
                         self.throw_exception(exc, ctx)?;
                     }
                 }
@@ -1192,8 +1811,12 @@ impl Dispatcher {
                                         self.stack.push(result);
                                     }
                                     PromiseState::Rejected => {
-                                        // Promise is rejected - throw the error
-                                        if let Some(error) = promise_obj.error() {
+                                        // Promise is rejected - throw its reason, or the
+                                        // error when it was rejected from Rust without one
+                                        if let Some(reason) = promise_obj.value().cloned() {
+                                            drop(borrowed);
+                                            self.throw_exception(reason, ctx)?;
+                                        } else if let Some(error) = promise_obj.error() {
                                             return Err(error.clone());
                                         } else {
                                             let error_value = Value::Undefined;
@@ -1222,6 +1845,78 @@ impl Dispatcher {
                     }
                 }
 
+                // Generator opcodes
+                Opcode::InitialYield => {
+                    let frame = GeneratorFrame {
+                        ctx: ctx.clone(),
+                        stack: Vec::new(),
+                        try_handlers: Vec::new(),
+                        upvalues: self.current_upvalues.clone(),
+                        open_upvalues: self.open_upvalues.clone(),
+                        this_value: self.globals.get("this").cloned(),
+                        delegating: false,
+                        pending_return: None,
+                        pending_exception: None,
+                    };
+                    let generator = GeneratorObject::new(frame);
+                    return Ok(Value::NativeObject(
+                        Rc::new(RefCell::new(generator)) as Rc<RefCell<dyn Any>>
+                    ));
+                }
+                Opcode::Yield => {
+                    let value = self.stack.pop().unwrap_or(Value::Undefined);
+                    self.suspended_in_delegate = Some(false);
+                    return Ok(value);
+                }
+                Opcode::GetIterator => {
+                    let iterable = self.stack.pop().unwrap_or(Value::Undefined);
+                    let iterator = if Self::is_generator(&iterable) {
+                        iterable
+                    } else {
+                        let values = self.iterable_to_vec(iterable, functions)?.into();
+                        Value::NativeObject(
+                            Rc::new(RefCell::new(ListIterator { values })) as Rc<RefCell<dyn Any>>
+                        )
+                    };
+                    self.stack.push(iterator);
+                }
+                Opcode::IteratorNext => {
+                    let iterator = self.stack.pop().unwrap_or(Value::Undefined);
+                    let (value, done) = self.resume_iterator(
+                        &iterator,
+                        ResumeMode::Next,
+                        Value::Undefined,
+                        functions,
+                    )?;
+                    self.stack.push(value);
+                    self.stack.push(Value::Boolean(done));
+                }
+                Opcode::Debugger => {
+                    if let Some(hook) = self.debugger_hook.clone() {
+                        let frame = self.capture_frame(ctx, inst.source_position);
+                        hook.paused(&frame, &self.script_globals());
+                    }
+                }
+                Opcode::YieldDelegate => {
+                    let received = self.stack.pop().unwrap_or(Value::Undefined);
+                    let iterator = self.stack.last().cloned().unwrap_or(Value::Undefined);
+                    let mode = self.delegate_resume_mode.take().unwrap_or(ResumeMode::Next);
+                    let (value, done) = self.resume_iterator(&iterator, mode, received, functions)?;
+                    if !done {
+                        // Run this instruction again with whatever the caller sends next
+                        ctx.instruction_pointer -= 1;
+                        self.suspended_in_delegate = Some(true);
+                        return Ok(value);
+                    }
+                    self.stack.pop();
+                    if mode != ResumeMode::Return {
+                        self.stack.push(value);
+                    } else if let Some(value) = self.unwind_return(value, ctx) {
+                        // The delegate returned, so the generator returns too
+                        return Ok(value);
+                    }
+                }
+
                 Opcode::CreateAsyncFunction(idx, ref upvalue_descs) => {
                     // Create an async function wrapper
                     // In a full implementation, this would create a special async function
@@ -1230,6 +1925,7 @@ impl Dispatcher {
                         // No captured variables - use function index directly
                         // Mark it as async by using a special encoding (high bit set)
                         let async_marker = 0x8000_0000;
+                        self.bind_function_realm(idx | async_marker);
                         self.stack.push(Value::HeapObject(idx | async_marker));
                     } else {
                         // With captured upvalues
@@ -1239,6 +1935,7 @@ impl Dispatcher {
                             captured_upvalues.push(upvalue_handle);
                         }
                         let async_marker = 0x8000_0000;
+                        self.bind_function_realm(idx | async_marker);
                         self.stack.push(Value::HeapObject(idx | async_marker));
                     }
                 }
@@ -1248,28 +1945,55 @@ impl Dispatcher {
 
     /// Call a native function by name
     fn call_native_function(&self, name: &str, args: Vec<Value>) -> Result<Value, JsError> {
+        let (name, realm) = Self::split_realm_tag(name);
         match name {
             // Console methods
             "console.log" => {
-                let builtin_args: Vec<BuiltinValue> = args.iter().map(Self::to_builtin_value).collect();
+                let builtin_args = Self::to_console_args(&args);
                 self.console.borrow().log(&builtin_args);
                 Ok(Value::Undefined)
             }
             "console.error" => {
-                let builtin_args: Vec<BuiltinValue> = args.iter().map(Self::to_builtin_value).collect();
+                let builtin_args = Self::to_console_args(&args);
                 self.console.borrow().error(&builtin_args);
                 Ok(Value::Undefined)
             }
             "console.warn" => {
-                let builtin_args: Vec<BuiltinValue> = args.iter().map(Self::to_builtin_value).collect();
+                let builtin_args = Self::to_console_args(&args);
                 self.console.borrow().warn(&builtin_args);
                 Ok(Value::Undefined)
             }
             "console.info" => {
-                let builtin_args: Vec<BuiltinValue> = args.iter().map(Self::to_builtin_value).collect();
+                let builtin_args = Self::to_console_args(&args);
                 self.console.borrow().info(&builtin_args);
                 Ok(Value::Undefined)
             }
+            "console.assert" => {
+                let condition = BuiltinValue::boolean(args.first().is_some_and(Value::is_truthy));
+                let builtin_args = Self::to_console_args(args.get(1..).unwrap_or_default());
+                self.console.borrow().assert(&condition, &builtin_args);
+                Ok(Value::Undefined)
+            }
+            "console.dir" => {
+                let options = args.get(1);
+                let depth = match options.and_then(|options| Self::own_property(options, "depth")) {
+                    Some(Value::Null) => usize::MAX,
+                    Some(Value::Smi(n)) if n >= 0 => n as usize,
+                    Some(Value::Double(n)) if n >= 0.0 => n as usize,
+                    _ => DEFAULT_DIR_DEPTH,
+                };
+                // Objects are rendered here, where the heap layout is known; the
+                // console prints the resulting string as it is
+                let value = match args.first() {
+                    Some(value @ Value::NativeObject(_)) => {
+                        BuiltinValue::string(value.inspect(depth))
+                    }
+                    Some(other) => Self::to_builtin_value(other),
+                    None => BuiltinValue::undefined(),
+                };
+                self.console.borrow().dir(&value, None);
+                Ok(Value::Undefined)
+            }
             // Math methods
             "Math.abs" => {
                 if let Some(n) = args.first().map(|v| self.to_number(v)) {
@@ -1336,7 +2060,14 @@ impl Dispatcher {
                     Ok(Value::Double(f64::NAN))
                 }
             }
-            "Math.random" => Ok(Value::Double(MathObject::random())),
+            "Math.random" => match self.random.get() {
+                Some(mut random) => {
+                    let value = random.next_f64();
+                    self.random.set(Some(random));
+                    Ok(Value::Double(value))
+                }
+                None => Ok(Value::Double(MathObject::random())),
+            },
             "Math.max" => {
                 let nums: Vec<f64> = args.iter().map(|v| self.to_number(v)).collect();
                 Ok(Value::Double(MathObject::max(&nums)))
@@ -1357,15 +2088,9 @@ impl Dispatcher {
                 Ok(PromiseConstructor::resolve(value))
             }
             "Promise.reject" => {
-                // Create a rejected promise with the given error
+                // The reason is kept as given, not converted to an error
                 let reason = args.first().cloned().unwrap_or(Value::Undefined);
-                let error = JsError {
-                    kind: ErrorKind::TypeError,
-                    message: format!("{:?}", reason),
-                    stack: vec![],
-                    source_position: None,
-                };
-                Ok(PromiseConstructor::reject(error))
+                Ok(PromiseConstructor::reject_with_reason(reason))
             }
             // JSON methods
             "JSON.stringify" => {
@@ -1402,10 +2127,10 @@ impl Dispatcher {
             "Array.prototype.map" | "Array.prototype.filter" | "Array.prototype.forEach" |
             "Array.prototype.reduce" | "Array.prototype.find" | "Array.prototype.findIndex" |
             "Array.prototype.some" | "Array.prototype.every" | "Array.prototype.includes" |
-            "Array.prototype.indexOf" | "Array.prototype.pop" | "Array.prototype.shift" |
-            "Array.prototype.unshift" | "Array.prototype.slice" | "Array.prototype.splice" |
-            "Array.prototype.concat" | "Array.prototype.join" | "Array.prototype.reverse" |
-            "Array.prototype.sort" => {
+            "Array.prototype.indexOf" | "Array.prototype.lastIndexOf" | "Array.prototype.pop" |
+            "Array.prototype.shift" | "Array.prototype.unshift" | "Array.prototype.slice" |
+            "Array.prototype.splice" | "Array.prototype.concat" | "Array.prototype.join" |
+            "Array.prototype.reverse" | "Array.prototype.sort" | "Array.prototype.flat" => {
                 // Array prototype methods require callback integration which
                 // needs the call stack context. Return a descriptive error for now.
                 Err(JsError {
@@ -1433,6 +2158,73 @@ impl Dispatcher {
                 let s = args.first().map(|v| self.to_string_value(v)).unwrap_or_default();
                 Ok(Value::Double(NumberObject::parse_float(&s)))
             }
+            "encodeURIComponent" | "decodeURIComponent" | "encodeURI" | "decodeURI" => {
+                let s = args
+                    .first()
+                    .map(|v| self.to_string_value(v))
+                    .unwrap_or_else(|| "undefined".to_string());
+                let result = match name {
+                    "encodeURIComponent" => builtins::encode_uri_component(&s),
+                    "decodeURIComponent" => builtins::decode_uri_component(&s),
+                    "encodeURI" => builtins::encode_uri(&s),
+                    _ => builtins::decode_uri(&s),
+                };
+                result.map(Value::String).map_err(|e| JsError {
+                    kind: ErrorKind::URIError,
+                    message: e
+                        .message
+                        .trim_start_matches("URIError: ")
+                        .to_string(),
+                    stack: vec![],
+                    source_position: None,
+                })
+            }
+            "performance.now" => {
+                let now = self.clock.monotonic_ms().max(self.last_performance_now.get());
+                self.last_performance_now.set(now);
+                Ok(Value::Double(now))
+            }
+            "Date.now" => Ok(Value::Double(self.clock.wall_clock_ms().floor())),
+            "Date" => {
+                let now = JsDate::from_timestamp(self.clock.wall_clock_ms());
+                Ok(Value::String(now.to_string()))
+            }
+            "queueMicrotask" => {
+                let callback = Self::expect_callback(name, args.first())?;
+                let job = HostJob {
+                    callback,
+                    args: vec![],
+                    name: "queueMicrotask",
+                    async_parent: self.call_site_frame(),
+                };
+                self.host_jobs.borrow_mut().enqueue_microtask(job);
+                Ok(Value::Undefined)
+            }
+            "setTimeout" => {
+                let callback = Self::expect_callback(name, args.first())?;
+                let delay = args.get(1).map(|v| self.to_number(v)).unwrap_or(0.0);
+                // NaN, negative and out-of-range (> 2^31 - 1 ms) delays run as soon as possible
+                let delay = if delay > 0.0 && delay <= i32::MAX as f64 { delay } else { 0.0 };
+                let job = HostJob {
+                    callback,
+                    args: args.into_iter().skip(2).collect(),
+                    name: "setTimeout",
+                    async_parent: self.call_site_frame(),
+                };
+                let id = self
+                    .host_jobs
+                    .borrow_mut()
+                    .set_timer(job, std::time::Duration::from_secs_f64(delay / 1000.0));
+                Ok(Value::Smi(id as i32))
+            }
+            "clearTimeout" => {
+                if let Some(id) = args.first().map(|v| self.to_number(v)) {
+                    if id >= 1.0 && id <= i32::MAX as f64 {
+                        self.host_jobs.borrow_mut().clear_timer(id as u32);
+                    }
+                }
+                Ok(Value::Undefined)
+            }
             // Number constructor and methods
             "Number" => {
                 // Number() type conversion
@@ -1458,7 +2250,7 @@ impl Dispatcher {
                 // Array() constructor
                 // Create an array using the heap if available
                 if let Some(ref heap) = self.heap {
-                    let mut arr_obj = heap.create_object();
+                    let mut arr_obj = heap.create_array();
                     if args.is_empty() {
                         // Empty array - just set length
                         arr_obj.set("length".to_string(), Value::Smi(0));
@@ -1553,12 +2345,12 @@ impl Dispatcher {
             // Error constructors
             "Array.isArray" => {
                 if let Some(value) = args.first() {
-                    // Check if the value is a NativeObject with a "length" property (array)
+                    // Array-likes such as `{ length: 1 }` are not arrays
                     let is_array = if let Value::NativeObject(obj_ref) = value {
                         let borrowed = obj_ref.borrow();
                         if let Some(gc_obj) = borrowed.downcast_ref::<Box<dyn Any>>() {
                             if let Some(gc_object) = gc_obj.downcast_ref::<GCObject>() {
-                                matches!(gc_object.get("length"), Value::Smi(_))
+                                gc_object.is_array()
                             } else {
                                 false
                             }
@@ -1576,7 +2368,7 @@ impl Dispatcher {
             "Array.of" => {
                 // Create array from arguments
                 if let Some(ref heap) = self.heap {
-                    let mut gc_object = heap.create_object();
+                    let mut gc_object = heap.create_array();
 
                     // Store all arguments as array elements
                     for (i, arg) in args.iter().enumerate() {
@@ -1600,7 +2392,7 @@ impl Dispatcher {
                 // Basic implementation: handle arrays and strings
                 if let Some(array_like) = args.first() {
                     if let Some(ref heap) = self.heap {
-                        let mut gc_object = heap.create_object();
+                        let mut gc_object = heap.create_array();
                         let mut elements = Vec::new();
 
                         match array_like {
@@ -1650,6 +2442,11 @@ impl Dispatcher {
                 }
             }
             // Object static methods
+            "Object.is" => {
+                let a = args.first().cloned().unwrap_or(Value::Undefined);
+                let b = args.get(1).cloned().unwrap_or(Value::Undefined);
+                Ok(Value::Boolean(a.same_value(&b)))
+            }
             "Object.keys" => {
                 if let Some(value) = args.first() {
                     match value {
@@ -1663,7 +2460,7 @@ impl Dispatcher {
                                     
                                     // Create an array with the keys
                                     if let Some(ref heap) = self.heap {
-                                        let mut result_obj = heap.create_object();
+                                        let mut result_obj = heap.create_array();
                                         for (i, key) in keys.iter().enumerate() {
                                             result_obj.set(i.to_string(), Value::String(key.clone()));
                                         }
@@ -1678,7 +2475,7 @@ impl Dispatcher {
                             }
                             // Empty array fallback
                             if let Some(ref heap) = self.heap {
-                                let mut empty = heap.create_object();
+                                let mut empty = heap.create_array();
                                 empty.set("length".to_string(), Value::Smi(0));
                                 let boxed: Box<dyn Any> = Box::new(empty);
                                 Ok(Value::NativeObject(
@@ -1691,7 +2488,7 @@ impl Dispatcher {
                         _ => {
                             // Empty array for non-objects
                             if let Some(ref heap) = self.heap {
-                                let mut empty = heap.create_object();
+                                let mut empty = heap.create_array();
                                 empty.set("length".to_string(), Value::Smi(0));
                                 let boxed: Box<dyn Any> = Box::new(empty);
                                 Ok(Value::NativeObject(
@@ -1727,7 +2524,7 @@ impl Dispatcher {
                                     
                                     // Create an array with the values
                                     if let Some(ref heap) = self.heap {
-                                        let mut result_obj = heap.create_object();
+                                        let mut result_obj = heap.create_array();
                                         for (i, val) in values.iter().enumerate() {
                                             result_obj.set(i.to_string(), val.clone());
                                         }
@@ -1742,7 +2539,7 @@ impl Dispatcher {
                             }
                             // Empty array fallback
                             if let Some(ref heap) = self.heap {
-                                let mut empty = heap.create_object();
+                                let mut empty = heap.create_array();
                                 empty.set("length".to_string(), Value::Smi(0));
                                 let boxed: Box<dyn Any> = Box::new(empty);
                                 Ok(Value::NativeObject(
@@ -1755,7 +2552,7 @@ impl Dispatcher {
                         _ => {
                             // Empty array for non-objects
                             if let Some(ref heap) = self.heap {
-                                let mut empty = heap.create_object();
+                                let mut empty = heap.create_array();
                                 empty.set("length".to_string(), Value::Smi(0));
                                 let boxed: Box<dyn Any> = Box::new(empty);
                                 Ok(Value::NativeObject(
@@ -1791,10 +2588,10 @@ impl Dispatcher {
                                     
                                     // Create an array with [key, value] pairs
                                     if let Some(ref heap) = self.heap {
-                                        let mut result_obj = heap.create_object();
+                                        let mut result_obj = heap.create_array();
                                         for (i, (key, val)) in entries.iter().enumerate() {
                                             // Create inner array for [key, value]
-                                            let mut pair_obj = heap.create_object();
+                                            let mut pair_obj = heap.create_array();
                                             pair_obj.set("0".to_string(), Value::String(key.clone()));
                                             pair_obj.set("1".to_string(), val.clone());
                                             pair_obj.set("length".to_string(), Value::Smi(2));
@@ -1816,7 +2613,7 @@ impl Dispatcher {
                             }
                             // Empty array fallback
                             if let Some(ref heap) = self.heap {
-                                let mut empty = heap.create_object();
+                                let mut empty = heap.create_array();
                                 empty.set("length".to_string(), Value::Smi(0));
                                 let boxed: Box<dyn Any> = Box::new(empty);
                                 Ok(Value::NativeObject(
@@ -1829,7 +2626,7 @@ impl Dispatcher {
                         _ => {
                             // Empty array for non-objects
                             if let Some(ref heap) = self.heap {
-                                let mut empty = heap.create_object();
+                                let mut empty = heap.create_array();
                                 empty.set("length".to_string(), Value::Smi(0));
                                 let boxed: Box<dyn Any> = Box::new(empty);
                                 Ok(Value::NativeObject(
@@ -1893,13 +2690,37 @@ impl Dispatcher {
                 
                 Ok(target)
             }
-            "Error" => self.create_error_object("Error", args),
-            "TypeError" => self.create_error_object("TypeError", args),
-            "ReferenceError" => self.create_error_object("ReferenceError", args),
-            "SyntaxError" => self.create_error_object("SyntaxError", args),
-            "RangeError" => self.create_error_object("RangeError", args),
-            "URIError" => self.create_error_object("URIError", args),
-            "EvalError" => self.create_error_object("EvalError", args),
+            "Error" => self.create_error_object("Error", realm, args),
+            "Error.captureStackTrace" => {
+                // captureStackTrace(target, constructorOpt)
+                let constructor = match args.get(1) {
+                    Some(Value::HeapObject(id)) => Some(*id),
+                    _ => None,
+                };
+                if let Some(Value::NativeObject(obj)) = args.first() {
+                    let mut borrowed = obj.borrow_mut();
+                    if let Some(gc_obj) = borrowed.downcast_mut::<Box<dyn Any>>() {
+                        if let Some(gc_object) = gc_obj.downcast_mut::<GCObject>() {
+                            let header = Self::error_header(gc_object);
+                            let stack = self.stack_trace(header, constructor);
+                            gc_object.set("stack".to_string(), Value::String(stack));
+                            return Ok(Value::Undefined);
+                        }
+                    }
+                }
+                Err(JsError {
+                    kind: ErrorKind::TypeError,
+                    message: "Error.captureStackTrace called on non-object".to_string(),
+                    stack: vec![],
+                    source_position: None,
+                })
+            }
+            "TypeError" => self.create_error_object("TypeError", realm, args),
+            "ReferenceError" => self.create_error_object("ReferenceError", realm, args),
+            "SyntaxError" => self.create_error_object("SyntaxError", realm, args),
+            "RangeError" => self.create_error_object("RangeError", realm, args),
+            "URIError" => self.create_error_object("URIError", realm, args),
+            "EvalError" => self.create_error_object("EvalError", realm, args),
             _ => Err(JsError {
                 kind: ErrorKind::TypeError,
                 message: format!("{} is not a function", name),
@@ -1914,12 +2735,18 @@ impl Dispatcher {
     /// # Arguments
     ///
     /// * `error_name` - The name of the error type (e.g., "Error", "TypeError")
+    /// * `realm` - The realm whose `{error_name}.prototype` the error inherits from
     /// * `args` - Constructor arguments (first arg is the message)
     ///
     /// # Returns
     ///
     /// A NativeObject wrapping an error with `name` and `message` properties
-    fn create_error_object(&self, error_name: &str, args: Vec<Value>) -> Result<Value, JsError> {
+    fn create_error_object(
+        &self,
+        error_name: &str,
+        realm: usize,
+        args: Vec<Value>,
+    ) -> Result<Value, JsError> {
         // Extract message from arguments
         let message = args.first()
             .map(|v| self.to_string_value(v))
@@ -1928,8 +2755,17 @@ impl Dispatcher {
         // Create error object using heap if available
         if let Some(ref heap) = self.heap {
             let mut error_obj = heap.create_object();
+            let proto_key = format!("{}.prototype", error_name);
+            if let Some(prototype) = self.realm_global(realm, &proto_key) {
+                error_obj.set_prototype_value(prototype);
+            }
             error_obj.set("name".to_string(), Value::String(error_name.to_string()));
             error_obj.set("message".to_string(), Value::String(message));
+            if let Some(cause) = Self::error_cause(args.get(1)) {
+                error_obj.set("cause".to_string(), cause);
+            }
+            let stack = self.stack_trace(Self::error_header(&error_obj), None);
+            error_obj.set("stack".to_string(), Value::String(stack));
 
             // Wrap the GCObject in Box<dyn Any> then in NativeObject
             let boxed: Box<dyn Any> = Box::new(error_obj);
@@ -1956,6 +2792,63 @@ impl Dispatcher {
         }
     }
 
+    /// First line of an error's `stack`: `name: message`, or just the name
+    fn error_header(error: &GCObject) -> String {
+        let name = match error.get("name") {
+            Value::Undefined => "Error".to_string(),
+            name => name.to_string(),
+        };
+        match error.get("message") {
+            Value::Undefined => name,
+            Value::String(message) if message.is_empty() => name,
+            message => format!("{}: {}", name, message),
+        }
+    }
+
+    /// `header` followed by a line per frame of the current call stack
+    ///
+    /// Frames run innermost first, from the native call being made out to
+    /// the script's top level, and at most `Error.stackTraceLimit` are
+    /// listed. With `constructor` set, the frames of that function and of
+    /// everything it called are left out; if it is not running, none are kept.
+    fn stack_trace(&self, header: String, constructor: Option<usize>) -> String {
+        // Each position lies in the function called from the next one out
+        let mut positions = vec![self.call_site_frame()];
+        positions.extend(self.call_stack.iter().rev().map(|(_, site)| site.clone()));
+        let skip = match constructor {
+            Some(id) => match self.call_stack.iter().rev().position(|(callee, _)| *callee == id) {
+                Some(index) => index + 1,
+                None => positions.len(),
+            },
+            None => 0,
+        };
+
+        let mut stack = header;
+        for frame in positions.into_iter().skip(skip).take(self.stack_trace_limit) {
+            let frame = builtins::error::StackFrame::new(
+                frame.function_name.unwrap_or_else(|| "<anonymous>".to_string()),
+                frame.source_url.unwrap_or_else(|| "<anonymous>".to_string()),
+                frame.line,
+                frame.column,
+            );
+            stack.push('\n');
+            stack.push_str(&frame.to_string());
+        }
+        stack
+    }
+
+    /// Read the `cause` option of an Error constructor, if the options object has one
+    fn error_cause(options: Option<&Value>) -> Option<Value> {
+        let Some(Value::NativeObject(obj)) = options else {
+            return None;
+        };
+        let borrowed = obj.borrow();
+        let gc_object = borrowed
+            .downcast_ref::<Box<dyn Any>>()
+            .and_then(|gc_obj| gc_obj.downcast_ref::<GCObject>())?;
+        gc_object.has_own("cause").then(|| gc_object.get("cause"))
+    }
+
     /// Call an Array prototype method with receiver and callback support
     fn call_array_prototype_method(
         &mut self,
@@ -2025,7 +2918,7 @@ impl Dispatcher {
 
                 // Create result array
                 let result_array = if let Some(ref heap) = self.heap {
-                    let gc_object = heap.create_object();
+                    let gc_object = heap.create_array();
                     let boxed: Box<dyn Any> = Box::new(gc_object);
                     Rc::new(RefCell::new(boxed)) as Rc<RefCell<dyn Any>>
                 } else {
@@ -2039,18 +2932,9 @@ impl Dispatcher {
 
                 // Iterate over array elements and call callback
                 for i in 0..array_len {
-                    // Get element from source array
-                    let element = {
-                        let borrowed = array_ref.borrow();
-                        if let Some(gc_obj) = borrowed.downcast_ref::<Box<dyn Any>>() {
-                            if let Some(gc_object) = gc_obj.downcast_ref::<GCObject>() {
-                                gc_object.get(&i.to_string())
-                            } else {
-                                Value::Undefined
-                            }
-                        } else {
-                            Value::Undefined
-                        }
+                    // Holes are skipped and stay holes in the result
+                    let Some(element) = Self::array_element(&array_ref, i) else {
+                        continue;
                     };
 
                     // Call callback(element, index, array)
@@ -2096,7 +2980,7 @@ impl Dispatcher {
                 };
 
                 let result_array = if let Some(ref heap) = self.heap {
-                    let gc_object = heap.create_object();
+                    let gc_object = heap.create_array();
                     let boxed: Box<dyn Any> = Box::new(gc_object);
                     Rc::new(RefCell::new(boxed)) as Rc<RefCell<dyn Any>>
                 } else {
@@ -2110,17 +2994,9 @@ impl Dispatcher {
 
                 let mut result_idx = 0;
                 for i in 0..array_len {
-                    let element = {
-                        let borrowed = array_ref.borrow();
-                        if let Some(gc_obj) = borrowed.downcast_ref::<Box<dyn Any>>() {
-                            if let Some(gc_object) = gc_obj.downcast_ref::<GCObject>() {
-                                gc_object.get(&i.to_string())
-                            } else {
-                                Value::Undefined
-                            }
-                        } else {
-                            Value::Undefined
-                        }
+                    // Holes are skipped
+                    let Some(element) = Self::array_element(&array_ref, i) else {
+                        continue;
                     };
 
                     let callback_args = vec![element.clone(), Value::Smi(i as i32), receiver.clone()];
@@ -2165,17 +3041,9 @@ impl Dispatcher {
                 };
 
                 for i in 0..array_len {
-                    let element = {
-                        let borrowed = array_ref.borrow();
-                        if let Some(gc_obj) = borrowed.downcast_ref::<Box<dyn Any>>() {
-                            if let Some(gc_object) = gc_obj.downcast_ref::<GCObject>() {
-                                gc_object.get(&i.to_string())
-                            } else {
-                                Value::Undefined
-                            }
-                        } else {
-                            Value::Undefined
-                        }
+                    // Holes are skipped
+                    let Some(element) = Self::array_element(&array_ref, i) else {
+                        continue;
                     };
 
                     let callback_args = vec![element, Value::Smi(i as i32), receiver.clone()];
@@ -2185,64 +3053,85 @@ impl Dispatcher {
                 Ok(Value::Undefined)
             }
 
-            "Array.prototype.reduce" => {
+            "Array.prototype.flat" => {
+                // flat(depth = 1); a NaN depth flattens nothing
+                let depth = match args.first() {
+                    None | Some(Value::Undefined) => 1.0,
+                    Some(depth) => {
+                        let depth = self.to_number(depth);
+                        if depth.is_nan() { 0.0 } else { depth.trunc() }
+                    }
+                };
+                let mut flattened = Vec::new();
+                Self::flatten_into(&array_ref, depth, &mut flattened);
+
+                let Some(ref heap) = self.heap else {
+                    return Err(JsError {
+                        kind: ErrorKind::InternalError,
+                        message: "Heap not initialized".to_string(),
+                        stack: vec![],
+                        source_position: None,
+                    });
+                };
+                let boxed: Box<dyn Any> = Box::new(heap.create_array());
+                let result =
+                    Value::NativeObject(Rc::new(RefCell::new(boxed)) as Rc<RefCell<dyn Any>>);
+                Self::append_to_array(&result, flattened);
+                Ok(result)
+            }
+
+            "Array.prototype.reduce" | "Array.prototype.reduceRight" => {
                 let callback = args.first().cloned().unwrap_or(Value::Undefined);
                 let callback_idx = match callback {
                     Value::HeapObject(idx) => idx,
                     _ => {
                         return Err(JsError {
                             kind: ErrorKind::TypeError,
-                            message: "Array.prototype.reduce callback must be a function".to_string(),
+                            message: format!("{} callback must be a function", name),
                             stack: vec![],
                             source_position: None,
                         });
                     }
                 };
 
-                let mut accumulator = args.get(1).cloned();
-                let start_idx = if accumulator.is_some() { 0 } else { 1 };
-
-                // If no initial value, use first element
-                if accumulator.is_none() {
-                    if array_len == 0 {
-                        return Err(JsError {
-                            kind: ErrorKind::TypeError,
-                            message: "Reduce of empty array with no initial value".to_string(),
-                            stack: vec![],
-                            source_position: None,
-                        });
+                // Holes (missing indices) are skipped entirely
+                let present: Vec<(usize, Value)> = {
+                    let borrowed = array_ref.borrow();
+                    match borrowed
+                        .downcast_ref::<Box<dyn Any>>()
+                        .and_then(|gc_obj| gc_obj.downcast_ref::<GCObject>())
+                    {
+                        Some(gc_object) => (0..array_len)
+                            .filter(|i| gc_object.has_own(&i.to_string()))
+                            .map(|i| (i, gc_object.get(&i.to_string())))
+                            .collect(),
+                        None => Vec::new(),
                     }
-                    let first_elem = {
-                        let borrowed = array_ref.borrow();
-                        if let Some(gc_obj) = borrowed.downcast_ref::<Box<dyn Any>>() {
-                            if let Some(gc_object) = gc_obj.downcast_ref::<GCObject>() {
-                                gc_object.get("0")
-                            } else {
-                                Value::Undefined
-                            }
-                        } else {
-                            Value::Undefined
-                        }
+                };
+                let mut elements: Box<dyn Iterator<Item = (usize, Value)>> =
+                    if name == "Array.prototype.reduceRight" {
+                        Box::new(present.into_iter().rev())
+                    } else {
+                        Box::new(present.into_iter())
                     };
-                    accumulator = Some(first_elem);
-                }
-
-                let mut acc = accumulator.unwrap();
 
-                for i in start_idx..array_len {
-                    let element = {
-                        let borrowed = array_ref.borrow();
-                        if let Some(gc_obj) = borrowed.downcast_ref::<Box<dyn Any>>() {
-                            if let Some(gc_object) = gc_obj.downcast_ref::<GCObject>() {
-                                gc_object.get(&i.to_string())
-                            } else {
-                                Value::Undefined
-                            }
-                        } else {
-                            Value::Undefined
+                // If no initial value, the first present element seeds the accumulator
+                let mut acc = match args.get(1) {
+                    Some(initial) => initial.clone(),
+                    None => match elements.next() {
+                        Some((_, first)) => first,
+                        None => {
+                            return Err(JsError {
+                                kind: ErrorKind::TypeError,
+                                message: "Reduce of empty array with no initial value".to_string(),
+                                stack: vec![],
+                                source_position: None,
+                            });
                         }
-                    };
+                    },
+                };
 
+                for (i, element) in elements {
                     // callback(accumulator, currentValue, currentIndex, array)
                     let callback_args = vec![acc, element, Value::Smi(i as i32), receiver.clone()];
                     acc = self.call_function_with_args(callback_idx, callback_args, functions)?;
@@ -2464,7 +3353,8 @@ impl Dispatcher {
                         }
                     };
 
-                    if element == search_element {
+                    // indexOf uses strict equality, so NaN is never found
+                    if self.strict_equal(element, search_element.clone()) == Value::Boolean(true) {
                         return Ok(Value::Smi(i as i32));
                     }
                 }
@@ -2472,6 +3362,44 @@ impl Dispatcher {
                 Ok(Value::Smi(-1))
             }
 
+            "Array.prototype.lastIndexOf" => {
+                if array_len == 0 {
+                    return Ok(Value::Smi(-1));
+                }
+                let search_element = args.first().cloned().unwrap_or(Value::Undefined);
+                let from_index = args
+                    .get(1)
+                    .map(|v| self.to_number(v) as i32)
+                    .unwrap_or(array_len as i32 - 1);
+
+                let start = if from_index < 0 {
+                    array_len as i32 + from_index
+                } else {
+                    from_index.min(array_len as i32 - 1)
+                };
+
+                for i in (0..=start).rev() {
+                    let element = {
+                        let borrowed = array_ref.borrow();
+                        if let Some(gc_obj) = borrowed.downcast_ref::<Box<dyn Any>>() {
+                            if let Some(gc_object) = gc_obj.downcast_ref::<GCObject>() {
+                                gc_object.get(&i.to_string())
+                            } else {
+                                Value::Undefined
+                            }
+                        } else {
+                            Value::Undefined
+                        }
+                    };
+
+                    if self.strict_equal(element, search_element.clone()) == Value::Boolean(true) {
+                        return Ok(Value::Smi(i));
+                    }
+                }
+
+                Ok(Value::Smi(-1))
+            }
+
             "Array.prototype.push" => {
                 // Get current length and add new elements
                 let new_len = array_len + args.len();
@@ -2600,7 +3528,7 @@ impl Dispatcher {
                 };
 
                 let result_array = if let Some(ref heap) = self.heap {
-                    let gc_object = heap.create_object();
+                    let gc_object = heap.create_array();
                     let boxed: Box<dyn Any> = Box::new(gc_object);
                     Rc::new(RefCell::new(boxed)) as Rc<RefCell<dyn Any>>
                 } else {
@@ -2650,7 +3578,7 @@ impl Dispatcher {
 
             "Array.prototype.concat" => {
                 let result_array = if let Some(ref heap) = self.heap {
-                    let gc_object = heap.create_object();
+                    let gc_object = heap.create_array();
                     let boxed: Box<dyn Any> = Box::new(gc_object);
                     Rc::new(RefCell::new(boxed)) as Rc<RefCell<dyn Any>>
                 } else {
@@ -2782,6 +3710,16 @@ impl Dispatcher {
         }
     }
 
+    /// Convert console arguments, rendering objects with `Value::inspect`
+    fn to_console_args(args: &[Value]) -> Vec<BuiltinValue> {
+        args.iter()
+            .map(|value| match value {
+                Value::NativeObject(_) => BuiltinValue::string(value.inspect(2)),
+                other => Self::to_builtin_value(other),
+            })
+            .collect()
+    }
+
     /// Convert core_types::Value to builtins::JsValue
     fn to_builtin_value(value: &Value) -> BuiltinValue {
         match value {
@@ -2793,7 +3731,10 @@ impl Dispatcher {
             Value::HeapObject(_) => BuiltinValue::object(),
             Value::String(s) => BuiltinValue::string(s.clone()),
             Value::NativeObject(_) => BuiltinValue::object(),
-            Value::NativeFunction(name) => BuiltinValue::string(format!("function {}() {{ [native code] }}", name)),
+            Value::NativeFunction(name) => {
+                let name = Self::split_realm_tag(name).0;
+                BuiltinValue::string(format!("function {}() {{ [native code] }}", name))
+            }
             Value::BigInt(n) => BuiltinValue::bigint(BigIntValue::new(n.clone())),
         }
     }
@@ -2881,7 +3822,7 @@ impl Dispatcher {
         // Handle arrays (simplified - single-level)
         if trimmed.starts_with('[') && trimmed.ends_with(']') {
             if let Some(ref heap) = self.heap {
-                let gc_object = heap.create_object();
+                let gc_object = heap.create_array();
                 let boxed: Box<dyn Any> = Box::new(gc_object);
                 let obj_ref = Rc::new(RefCell::new(boxed)) as Rc<RefCell<dyn Any>>;
 
@@ -2986,43 +3927,14 @@ impl Dispatcher {
             });
         }
 
-        // Determine the actual function index and closure upvalues
-        let (fn_idx, closure_upvalues) = if func_idx_or_closure >= 1_000_000 {
-            // This is a closure - decode and get upvalues from registry
-            let closure_id = func_idx_or_closure - 1_000_000;
-            match self.closure_registry.get(&closure_id) {
-                Some((func_idx, upvalues)) => (*func_idx, Some(upvalues.clone())),
-                None => {
+        let (mut fn_ctx, closure_upvalues) =
+            match self.resolve_function(func_idx_or_closure, functions) {
+                Ok(resolved) => resolved,
+                Err(err) => {
                     CALL_DEPTH.fetch_sub(1, std::sync::atomic::Ordering::SeqCst);
-                    return Err(JsError {
-                        kind: ErrorKind::ReferenceError,
-                        message: format!("Invalid closure ID: {}", closure_id),
-                        stack: vec![],
-                        source_position: None,
-                    });
+                    return Err(err);
                 }
-            }
-        } else {
-            // Plain function index
-            (func_idx_or_closure, None)
-        };
-
-        // Get the function bytecode
-        let fn_bytecode = match functions.get(fn_idx) {
-            Some(chunk) => chunk.clone(),
-            None => {
-                CALL_DEPTH.fetch_sub(1, std::sync::atomic::Ordering::SeqCst);
-                return Err(JsError {
-                    kind: ErrorKind::ReferenceError,
-                    message: format!("Invalid function index: {}", fn_idx),
-                    stack: vec![],
-                    source_position: None,
-                });
-            }
-        };
-
-        // Create new execution context for the function
-        let mut fn_ctx = ExecutionContext::new(fn_bytecode);
+            };
 
         // Set arguments as registers (parameter passing)
         // Register 0 = first argument, Register 1 = second argument, etc.
@@ -3038,9 +3950,17 @@ impl Dispatcher {
             self.current_upvalues = upvalues;
         }
 
-        // Recursively execute the function
+        // Recursively execute the function, in the realm it was created in
         // This enables nested calls and recursion
+        let caller_realm = self.enter_realm(self.function_realm(func_idx_or_closure));
+        let stack_height = self.stack.len();
+        let frame = self.call_site_frame();
+        self.call_stack.push((func_idx_or_closure, frame));
         let result = self.execute(&mut fn_ctx, functions);
+        self.call_stack.pop();
+        // Drop any operands the body left behind
+        self.stack.truncate(stack_height);
+        self.enter_realm(caller_realm);
 
         // Restore previous upvalues
         self.current_upvalues = saved_upvalues;
@@ -3052,43 +3972,884 @@ impl Dispatcher {
         result
     }
 
-    /// Execute a method call with `this` binding
-    ///
-    /// # Arguments
-    /// * `func_idx_or_closure` - The function index or closure encoded ID
-    /// * `receiver` - The receiver object (this binding)
-    /// * `args` - The function arguments
-    /// * `functions` - The function registry
+    /// Replace the time source behind `performance.now()` and `Date.now()`
+    pub fn set_clock(&mut self, clock: Box<dyn Clock>) {
+        self.clock = clock;
+        self.last_performance_now.set(0.0);
+    }
+
+    /// Make `Math.random()` a generator seeded with `seed`
+    pub fn set_random_seed(&mut self, seed: u64) {
+        self.random.set(Some(SeededRandom::new(seed)));
+    }
+
+    /// Run scheduled host jobs until none remain
     ///
-    /// # Returns
-    /// The return value of the method
-    fn call_method_with_this(
-        &mut self,
-        func_idx_or_closure: usize,
-        receiver: Value,
-        args: Vec<Value>,
-        functions: &[BytecodeChunk],
-    ) -> Result<Value, JsError> {
-        // Check for recursion depth
-        static CALL_DEPTH: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
-        let depth = CALL_DEPTH.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-        if depth > 10000 {
-            CALL_DEPTH.fetch_sub(1, std::sync::atomic::Ordering::SeqCst);
+    /// The microtask queue is drained first, then each timer runs as its own task
+    /// followed by another microtask checkpoint. Errors thrown by a job are kept
+    /// for [`Dispatcher::take_uncaught_errors`] and do not stop the remaining jobs.
+    pub fn run_host_jobs(&mut self, functions: &[BytecodeChunk]) {
+        loop {
+            loop {
+                let job = self.host_jobs.borrow_mut().next_microtask();
+                match job {
+                    Some(Ok(job)) => self.run_host_job(job, functions),
+                    Some(Err(err)) => self.uncaught_errors.push(err),
+                    None => break,
+                }
+            }
+
+            let timer = self.host_jobs.borrow_mut().next_timer();
+            match timer {
+                Some(Ok(job)) => self.run_host_job(job, functions),
+                Some(Err(err)) => self.uncaught_errors.push(err),
+                None => break,
+            }
+        }
+    }
+
+    /// Take the errors thrown by host jobs since the last call
+    pub fn take_uncaught_errors(&mut self) -> Vec<JsError> {
+        std::mem::take(&mut self.uncaught_errors)
+    }
+
+    /// The event loop holding script timers
+    pub fn event_loop(&mut self) -> &mut EventLoop {
+        self.host_jobs.get_mut().event_loop()
+    }
+
+    /// Invoke one host job's callback, recording any error it throws
+    fn run_host_job(&mut self, job: HostJob, functions: &[BytecodeChunk]) {
+        let stack_height = self.stack.len();
+        let result = self.call_value(job.callback, None, job.args, functions);
+
+        if let Err(err) = result {
+            // A job that throws may leave operands and handlers behind
+            self.stack.truncate(stack_height);
+            self.try_stack.clear();
+            self.current_exception = None;
+            let name = Some(job.name.to_string());
+            let err = async_runtime::attach_async_frame(err, name, job.async_parent);
+            self.uncaught_errors.push(err);
+        }
+    }
+
+    /// Stack frame for the script location of the native call being made
+    fn call_site_frame(&self) -> StackFrame {
+        let (line, column) = self.call_site.map_or((0, 0), |pos| (pos.line, pos.column));
+        StackFrame {
+            function_name: None,
+            source_url: None,
+            line,
+            column,
+        }
+    }
+
+    /// Check that a host function argument is callable
+    fn expect_callback(name: &str, value: Option<&Value>) -> Result<Value, JsError> {
+        match value {
+            Some(callback @ (Value::HeapObject(_) | Value::NativeFunction(_))) => Ok(callback.clone()),
+            Some(callback) if Self::is_bound_function(callback) => Ok(callback.clone()),
+            _ => Err(JsError {
+                kind: ErrorKind::TypeError,
+                message: format!("{}: callback is not a function", name),
+                stack: vec![],
+                source_position: None,
+            }),
+        }
+    }
+
+    /// Get a `Function.prototype` method by name
+    fn function_prototype_property(name: &str) -> Value {
+        Self::native_method(FUNCTION_PROTOTYPE_METHODS, "Function.prototype", name)
+            .unwrap_or(Value::Undefined)
+    }
+
+    /// The native function `prefix.name` when `name` is listed in `methods`
+    fn native_method(methods: &[&str], prefix: &str, name: &str) -> Option<Value> {
+        methods
+            .contains(&name)
+            .then(|| Value::NativeFunction(format!("{}.{}", prefix, name)))
+    }
+
+    /// Check whether a value was created by `Function.prototype.bind`
+    fn is_bound_function(value: &Value) -> bool {
+        matches!(value, Value::NativeObject(obj) if obj.borrow().is::<BoundFunction>())
+    }
+
+    /// Get a copy of the symbol held by a symbol value
+    fn symbol_value(value: &Value) -> Option<SymbolValue> {
+        match value {
+            Value::NativeObject(obj) => obj.borrow().downcast_ref::<SymbolValue>().cloned(),
+            _ => None,
+        }
+    }
+
+    /// Whether `value` is callable: a function, native function or bound function
+    fn is_callable(value: &Value) -> bool {
+        matches!(value, Value::HeapObject(_) | Value::NativeFunction(_))
+            || Self::is_bound_function(value)
+    }
+
+    /// Get a copy of a bound function's target, `this` and arguments
+    fn bound_function(value: &Value) -> Option<BoundFunction> {
+        match value {
+            Value::NativeObject(obj) => obj.borrow().downcast_ref::<BoundFunction>().cloned(),
+            _ => None,
+        }
+    }
+
+    /// Call a native function as a method of `receiver`
+    fn call_native_method(
+        &mut self,
+        name: &str,
+        receiver: Value,
+        args: Vec<Value>,
+        functions: &[BytecodeChunk],
+    ) -> Result<Value, JsError> {
+        // Prototype methods need the receiver
+        if name.starts_with("Array.prototype.") {
+            self.call_array_prototype_method(name, receiver, args, functions)
+        } else if name.starts_with("Object.prototype.") {
+            self.call_object_prototype_method(name, receiver, args)
+        } else if name.starts_with("String.prototype.") {
+            self.call_string_prototype_method(name, receiver, args)
+        } else if name.starts_with("Number.prototype.") {
+            self.call_number_prototype_method(name, receiver)
+        } else if name.starts_with("Function.prototype.") {
+            self.call_function_prototype_method(name, receiver, args, functions)
+        } else if let Some(method) = name.strip_prefix("Generator.prototype.") {
+            self.call_generator_method(method, receiver, args, functions)
+        } else if let Some(method) = name.strip_prefix("Date.prototype.") {
+            self.call_date_method(method, receiver, args)
+        } else {
+            self.call_native_function(name, args)
+        }
+    }
+
+    /// `new Date(...)`: the current time, a time value, a date string, or
+    /// local date components
+    fn construct_date(&self, args: Vec<Value>) -> Value {
+        let date = match args.as_slice() {
+            [] => JsDate::from_timestamp(self.clock.wall_clock_ms()),
+            [Value::String(s)] => JsDate::from_string(s),
+            [Value::NativeObject(obj)] if obj.borrow().is::<JsDate>() => {
+                let time = obj.borrow().downcast_ref::<JsDate>().map(JsDate::get_time);
+                JsDate::from_timestamp(time.unwrap_or(f64::NAN))
+            }
+            [value] => JsDate::from_timestamp(self.to_number(value)),
+            [year, components @ ..] => {
+                let year = self.to_number(year);
+                let parts: Vec<f64> = components.iter().map(|v| self.to_number(v)).collect();
+                // Absent fields default to the start of their unit
+                let part = |i: usize, default: f64| parts.get(i).copied().unwrap_or(default);
+                JsDate::from_local_components(
+                    year,
+                    part(0, 0.0),
+                    part(1, 1.0),
+                    part(2, 0.0),
+                    part(3, 0.0),
+                    part(4, 0.0),
+                    part(5, 0.0),
+                )
+            }
+        };
+        Value::NativeObject(Rc::new(RefCell::new(date)) as Rc<RefCell<dyn Any>>)
+    }
+
+    /// Call a `Date.prototype` method on a Date object
+    fn call_date_method(
+        &self,
+        method: &str,
+        receiver: Value,
+        args: Vec<Value>,
+    ) -> Result<Value, JsError> {
+        let date = match &receiver {
+            Value::NativeObject(obj) => obj.borrow().downcast_ref::<JsDate>().cloned(),
+            _ => None,
+        };
+        let Some(date) = date else {
             return Err(JsError {
+                kind: ErrorKind::TypeError,
+                message: format!("Date.prototype.{} called on a non-Date object", method),
+                stack: vec![],
+                source_position: None,
+            });
+        };
+
+        let number = |n: f64| Ok(Value::Double(n));
+        match method {
+            "getTime" | "valueOf" => number(date.get_time()),
+            "setTime" => {
+                let time = args.first().map_or(f64::NAN, |v| self.to_number(v));
+                number(date.set_time(time))
+            }
+            "getFullYear" => number(date.get_full_year()),
+            "getMonth" => number(date.get_month()),
+            "getDate" => number(date.get_date()),
+            "getDay" => number(date.get_day()),
+            "getHours" => number(date.get_hours()),
+            "getMinutes" => number(date.get_minutes()),
+            "getSeconds" => number(date.get_seconds()),
+            "getMilliseconds" => number(date.get_milliseconds()),
+            "getTimezoneOffset" => number(date.get_timezone_offset()),
+            "getUTCFullYear" => number(date.get_utc_full_year()),
+            "getUTCMonth" => number(date.get_utc_month()),
+            "getUTCDate" => number(date.get_utc_date()),
+            "getUTCDay" => number(date.get_utc_day()),
+            "getUTCHours" => number(date.get_utc_hours()),
+            "getUTCMinutes" => number(date.get_utc_minutes()),
+            "getUTCSeconds" => number(date.get_utc_seconds()),
+            "getUTCMilliseconds" => number(date.get_utc_milliseconds()),
+            "toISOString" => date.to_iso_string().map(Value::String).map_err(|_| JsError {
                 kind: ErrorKind::RangeError,
-                message: "Maximum call stack size exceeded".to_string(),
+                message: "Invalid time value".to_string(),
+                stack: vec![],
+                source_position: None,
+            }),
+            "toJSON" => Ok(date.to_json().map_or(Value::Null, Value::String)),
+            "toString" => Ok(Value::String(date.to_string())),
+            "toDateString" => Ok(Value::String(date.to_date_string())),
+            "toTimeString" => Ok(Value::String(date.to_time_string())),
+            "toUTCString" => Ok(Value::String(date.to_utc_string())),
+            _ => Err(JsError {
+                kind: ErrorKind::TypeError,
+                message: format!("Date.prototype.{} is not a function", method),
+                stack: vec![],
+                source_position: None,
+            }),
+        }
+    }
+
+    /// Whether `value` is a generator object
+    fn is_generator(value: &Value) -> bool {
+        matches!(value, Value::NativeObject(obj) if obj.borrow().is::<GeneratorObject>())
+    }
+
+    /// `next`, `return` or `throw` on a generator object, returning an
+    /// iterator result object
+    fn call_generator_method(
+        &mut self,
+        method: &str,
+        receiver: Value,
+        args: Vec<Value>,
+        functions: &[BytecodeChunk],
+    ) -> Result<Value, JsError> {
+        let mode = match method {
+            "next" => ResumeMode::Next,
+            "return" => ResumeMode::Return,
+            _ => ResumeMode::Throw,
+        };
+        let Value::NativeObject(generator) = receiver else {
+            return Err(JsError {
+                kind: ErrorKind::TypeError,
+                message: format!("Generator.prototype.{} called on {:?}", method, receiver),
+                stack: vec![],
+                source_position: None,
+            });
+        };
+        let sent = args.into_iter().next().unwrap_or(Value::Undefined);
+        let (value, done) = self.resume_generator(&generator, mode, sent, functions)?;
+        Ok(self.iterator_result(value, done))
+    }
+
+    /// Create an `{ value, done }` iterator result object
+    fn iterator_result(&self, value: Value, done: bool) -> Value {
+        match &self.heap {
+            Some(heap) => {
+                let mut result = heap.create_object();
+                result.set("value".to_string(), value);
+                result.set("done".to_string(), Value::Boolean(done));
+                let boxed: Box<dyn Any> = Box::new(result);
+                Value::NativeObject(Rc::new(RefCell::new(boxed)) as Rc<RefCell<dyn Any>>)
+            }
+            None => value,
+        }
+    }
+
+    /// Step an iterator used by `yield*`, returning `(value, done)`
+    fn resume_iterator(
+        &mut self,
+        iterator: &Value,
+        mode: ResumeMode,
+        sent: Value,
+        functions: &[BytecodeChunk],
+    ) -> Result<(Value, bool), JsError> {
+        let Value::NativeObject(obj) = iterator else {
+            return Err(JsError {
+                kind: ErrorKind::TypeError,
+                message: format!("{:?} is not an iterator", iterator),
                 stack: vec![],
                 source_position: None,
             });
+        };
+        if obj.borrow().is::<GeneratorObject>() {
+            return self.resume_generator(obj, mode, sent, functions);
+        }
+
+        let mut borrowed = obj.borrow_mut();
+        let Some(list) = borrowed.downcast_mut::<ListIterator>() else {
+            return Err(JsError {
+                kind: ErrorKind::TypeError,
+                message: format!("{:?} is not an iterator", iterator),
+                stack: vec![],
+                source_position: None,
+            });
+        };
+        match mode {
+            ResumeMode::Next => Ok(match list.values.pop_front() {
+                Some(value) => (value, false),
+                None => (Value::Undefined, true),
+            }),
+            ResumeMode::Return => {
+                list.values.clear();
+                Ok((sent, true))
+            }
+            ResumeMode::Throw => {
+                list.values.clear();
+                Err(JsError {
+                    kind: ErrorKind::TypeError,
+                    message: "The iterator does not provide a 'throw' method".to_string(),
+                    stack: vec![],
+                    source_position: None,
+                })
+            }
+        }
+    }
+
+    /// Resume a generator's body, returning `(value, done)`
+    ///
+    /// The body runs on top of the current operand stack with its own try
+    /// handlers, upvalues and `this`, all of which are saved again if it
+    /// suspends. `return` completes the generator without running pending
+    /// `finally` blocks, unless it is paused in `yield*`, where the return is
+    /// forwarded to the delegate first.
+    fn resume_generator(
+        &mut self,
+        generator: &Rc<RefCell<dyn Any>>,
+        mode: ResumeMode,
+        sent: Value,
+        functions: &[BytecodeChunk],
+    ) -> Result<(Value, bool), JsError> {
+        let (state, frame) = {
+            let mut borrowed = generator.borrow_mut();
+            let Some(object) = borrowed.downcast_mut::<GeneratorObject>() else {
+                return Err(JsError {
+                    kind: ErrorKind::TypeError,
+                    message: "Generator method called on incompatible receiver".to_string(),
+                    stack: vec![],
+                    source_position: None,
+                });
+            };
+            let state = object.state;
+            match state {
+                GeneratorState::Executing => {
+                    return Err(JsError {
+                        kind: ErrorKind::TypeError,
+                        message: "Generator is already running".to_string(),
+                        stack: vec![],
+                        source_position: None,
+                    });
+                }
+                GeneratorState::Completed => (state, None),
+                _ => {
+                    object.state = GeneratorState::Executing;
+                    (state, object.frame.take())
+                }
+            }
+        };
+
+        let Some(frame) = frame else {
+            // A completed generator stays completed
+            return match mode {
+                ResumeMode::Next => Ok((Value::Undefined, true)),
+                ResumeMode::Return => Ok((sent, true)),
+                ResumeMode::Throw => {
+                    let mut ctx = ExecutionContext::new(BytecodeChunk::new());
+                    let saved_try = std::mem::take(&mut self.try_stack);
+                    let result = self.throw_exception(sent, &mut ctx);
+                    self.try_stack = saved_try;
+                    result.map(|()| (Value::Undefined, true))
+                }
+            };
+        };
+
+        // Install the generator's frame on top of the caller's state
+        let base = self.stack.len();
+        let GeneratorFrame {
+            mut ctx,
+            stack,
+            try_handlers,
+            upvalues,
+            open_upvalues,
+            this_value,
+            delegating,
+            pending_return,
+            pending_exception,
+        } = frame;
+        self.stack.extend(stack);
+        let handlers = try_handlers
+            .into_iter()
+            .map(|handler| TryHandler {
+                stack_height: handler.stack_height + base,
+                ..handler
+            })
+            .collect();
+        let saved_try = std::mem::replace(&mut self.try_stack, handlers);
+        let saved_upvalues = std::mem::replace(&mut self.current_upvalues, upvalues);
+        let saved_open_upvalues = std::mem::replace(&mut self.open_upvalues, open_upvalues);
+        let saved_return = std::mem::replace(&mut self.pending_return, pending_return);
+        let saved_exception = std::mem::replace(&mut self.current_exception, pending_exception);
+        let saved_this = this_value
+            .clone()
+            .map(|this| self.globals.insert("this".to_string(), this));
+
+        let started = state != GeneratorState::SuspendedStart;
+        let result = match mode {
+            ResumeMode::Next | ResumeMode::Return | ResumeMode::Throw if delegating => {
+                self.stack.push(sent);
+                self.delegate_resume_mode = Some(mode);
+                self.execute(&mut ctx, functions)
+            }
+            ResumeMode::Next => {
+                // The first `next` starts the body; its argument is ignored
+                if started {
+                    self.stack.push(sent);
+                }
+                self.execute(&mut ctx, functions)
+            }
+            ResumeMode::Return => match self.unwind_return(sent, &mut ctx) {
+                Some(value) => Ok(value),
+                None => self.execute(&mut ctx, functions),
+            },
+            ResumeMode::Throw => match self.throw_exception(sent, &mut ctx) {
+                Ok(()) => self.execute(&mut ctx, functions),
+                Err(error) => Err(error),
+            },
+        };
+        let suspended_in_delegate = self.suspended_in_delegate.take();
+        self.delegate_resume_mode = None;
+
+        // Save the generator's state and restore the caller's
+        let stack = self.stack.split_off(base.min(self.stack.len()));
+        let try_handlers = std::mem::replace(&mut self.try_stack, saved_try)
+            .into_iter()
+            .map(|handler| TryHandler {
+                stack_height: handler.stack_height.saturating_sub(base),
+                ..handler
+            })
+            .collect();
+        let upvalues = std::mem::replace(&mut self.current_upvalues, saved_upvalues);
+        let open_upvalues = std::mem::replace(&mut self.open_upvalues, saved_open_upvalues);
+        let pending_return = std::mem::replace(&mut self.pending_return, saved_return);
+        let pending_exception = std::mem::replace(&mut self.current_exception, saved_exception);
+        match saved_this {
+            Some(Some(previous)) => {
+                self.globals.insert("this".to_string(), previous);
+            }
+            Some(None) => {
+                self.globals.remove("this");
+            }
+            None => {}
+        }
+
+        let mut borrowed = generator.borrow_mut();
+        let object = borrowed
+            .downcast_mut::<GeneratorObject>()
+            .expect("generator object changed type while running");
+        match (result, suspended_in_delegate) {
+            (Ok(value), Some(delegating)) => {
+                object.state = GeneratorState::SuspendedYield;
+                object.frame = Some(GeneratorFrame {
+                    ctx,
+                    stack,
+                    try_handlers,
+                    upvalues,
+                    open_upvalues,
+                    this_value,
+                    delegating,
+                    pending_return,
+                    pending_exception,
+                });
+                Ok((value, false))
+            }
+            (Ok(value), None) => {
+                object.state = GeneratorState::Completed;
+                Ok((value, true))
+            }
+            (Err(error), _) => {
+                object.state = GeneratorState::Completed;
+                Err(error)
+            }
+        }
+    }
+
+    /// Call any callable value, with `this` bound to `this_value` when given
+    fn call_value(
+        &mut self,
+        callee: Value,
+        this_value: Option<Value>,
+        args: Vec<Value>,
+        functions: &[BytecodeChunk],
+    ) -> Result<Value, JsError> {
+        if let Some(bound) = Self::bound_function(&callee) {
+            let args = bound.full_args(args);
+            return self.call_value(bound.target, Some(bound.bound_this), args, functions);
+        }
+
+        match (callee, this_value) {
+            (Value::NativeFunction(name), Some(receiver)) => {
+                self.call_native_method(&name, receiver, args, functions)
+            }
+            (Value::NativeFunction(name), None) => self.call_native_function(&name, args),
+            (Value::HeapObject(idx), Some(receiver)) => {
+                self.call_method_with_this(idx, receiver, args, functions)
+            }
+            (Value::HeapObject(idx), None) => self.call_function_with_args(idx, args, functions),
+            (other, _) => Err(JsError {
+                kind: ErrorKind::TypeError,
+                message: format!("{:?} is not a function", other),
+                stack: vec![],
+                source_position: None,
+            }),
+        }
+    }
+
+    /// Construct an instance with `new`, forwarding bound functions to their target
+    fn construct_value(
+        &mut self,
+        constructor: Value,
+        args: Vec<Value>,
+        functions: &[BytecodeChunk],
+    ) -> Result<Value, JsError> {
+        if let Some(bound) = Self::bound_function(&constructor) {
+            let args = bound.full_args(args);
+            return self.construct_value(bound.target, args, functions);
+        }
+
+        match constructor {
+            // Called as a function, Date returns a string instead of an object
+            Value::NativeFunction(name) if name == "Date" => Ok(self.construct_date(args)),
+            Value::NativeFunction(name) => self.call_native_function(&name, args),
+            // User-defined constructor - call with new instance as this
+            Value::HeapObject(idx) => self.call_constructor(idx, args, functions),
+            _ => Err(JsError {
+                kind: ErrorKind::TypeError,
+                message: format!("{:?} is not a constructor", constructor),
+                stack: vec![],
+                source_position: None,
+            }),
+        }
+    }
+
+    /// Execute Function.prototype methods with the target function as receiver
+    fn call_function_prototype_method(
+        &mut self,
+        name: &str,
+        target: Value,
+        args: Vec<Value>,
+        functions: &[BytecodeChunk],
+    ) -> Result<Value, JsError> {
+        let is_callable = matches!(target, Value::NativeFunction(_) | Value::HeapObject(_))
+            || Self::is_bound_function(&target);
+        if !is_callable {
+            return Err(JsError {
+                kind: ErrorKind::TypeError,
+                message: format!("{} called on {:?}, which is not a function", name, target),
+                stack: vec![],
+                source_position: None,
+            });
+        }
+
+        let mut args = args.into_iter();
+        let this_arg = args.next().unwrap_or(Value::Undefined);
+
+        match name {
+            "Function.prototype.call" => self.call_value(target, Some(this_arg), args.collect(), functions),
+            "Function.prototype.apply" => {
+                let spread = self.array_like_to_vec(args.next().unwrap_or(Value::Undefined))?;
+                self.call_value(target, Some(this_arg), spread, functions)
+            }
+            "Function.prototype.bind" => {
+                let bound = BoundFunction {
+                    target,
+                    bound_this: this_arg,
+                    bound_args: args.collect(),
+                };
+                Ok(Value::NativeObject(Rc::new(RefCell::new(bound)) as Rc<RefCell<dyn Any>>))
+            }
+            _ => Err(JsError {
+                kind: ErrorKind::TypeError,
+                message: format!("Unknown Function.prototype method: {}", name),
+                stack: vec![],
+                source_position: None,
+            }),
+        }
+    }
+
+    /// Collect the elements of an array for `apply`; `null` and `undefined` give no elements
+    fn array_like_to_vec(&self, value: Value) -> Result<Vec<Value>, JsError> {
+        if let Value::NativeObject(obj) = &value {
+            let borrowed = obj.borrow();
+            if let Some(gc_object) = borrowed
+                .downcast_ref::<Box<dyn Any>>()
+                .and_then(|gc_obj| gc_obj.downcast_ref::<GCObject>())
+            {
+                if let Value::Smi(len) = gc_object.get("length") {
+                    return Ok((0..len.max(0)).map(|i| gc_object.get(&i.to_string())).collect());
+                }
+            }
+        }
+
+        match value {
+            Value::Undefined | Value::Null => Ok(Vec::new()),
+            other => Err(JsError {
+                kind: ErrorKind::TypeError,
+                message: format!("CreateListFromArrayLike called on {:?}", other),
+                stack: vec![],
+                source_position: None,
+            }),
+        }
+    }
+
+    /// Collect the values produced by iterating `value` for spread; arrays,
+    /// strings and generators are iterable, anything else is a TypeError
+    ///
+    /// A generator is run to completion, so its body executes here.
+    fn iterable_to_vec(
+        &mut self,
+        value: Value,
+        functions: &[BytecodeChunk],
+    ) -> Result<Vec<Value>, JsError> {
+        match value {
+            Value::String(s) => Ok(s.chars().map(|c| Value::String(c.to_string())).collect()),
+            Value::NativeObject(ref obj) if obj.borrow().is::<GeneratorObject>() => {
+                let mut values = Vec::new();
+                loop {
+                    let (value, done) =
+                        self.resume_generator(obj, ResumeMode::Next, Value::Undefined, functions)?;
+                    if done {
+                        return Ok(values);
+                    }
+                    values.push(value);
+                }
+            }
+            // Array-likes such as `{ length: 2 }` are not iterable
+            Value::NativeObject(ref obj)
+                if obj
+                    .borrow()
+                    .downcast_ref::<Box<dyn Any>>()
+                    .and_then(|gc_obj| gc_obj.downcast_ref::<GCObject>())
+                    .is_some_and(GCObject::is_array) =>
+            {
+                self.array_like_to_vec(value)
+            }
+            other => Err(JsError {
+                kind: ErrorKind::TypeError,
+                message: format!("{:?} is not iterable", other),
+                stack: vec![],
+                source_position: None,
+            }),
+        }
+    }
+
+    /// TypeError with a fixed message
+    fn type_error(message: &str) -> JsError {
+        JsError {
+            kind: ErrorKind::TypeError,
+            message: message.to_string(),
+            stack: vec![],
+            source_position: None,
+        }
+    }
+
+    /// TypeError for a failed private field brand check
+    fn private_brand_error(message: String) -> JsError {
+        JsError {
+            kind: ErrorKind::TypeError,
+            message,
+            stack: vec![],
+            source_position: None,
+        }
+    }
+
+    /// Source name of a private field key; keys are `#name@class`
+    fn private_name(key: &str) -> &str {
+        key.split('@').next().unwrap_or(key)
+    }
+
+    /// Element `index` of an array, or `None` for a hole
+    fn array_element(array_ref: &Rc<RefCell<dyn Any>>, index: usize) -> Option<Value> {
+        let borrowed = array_ref.borrow();
+        let gc_object = borrowed.downcast_ref::<Box<dyn Any>>()?.downcast_ref::<GCObject>()?;
+        let key = index.to_string();
+        gc_object.has_own(&key).then(|| gc_object.get(&key))
+    }
+
+    /// Append the elements of an array to `out`, flattening nested arrays
+    /// `depth` levels deep and skipping holes
+    fn flatten_into(array_ref: &Rc<RefCell<dyn Any>>, depth: f64, out: &mut Vec<Value>) {
+        let len = match Self::array_length(array_ref) {
+            Some(len) => len,
+            None => return,
+        };
+        for i in 0..len {
+            let Some(element) = Self::array_element(array_ref, i) else {
+                continue;
+            };
+            match &element {
+                Value::NativeObject(inner)
+                    if depth >= 1.0 && Self::array_length(inner).is_some() =>
+                {
+                    Self::flatten_into(inner, depth - 1.0, out)
+                }
+                _ => out.push(element),
+            }
+        }
+    }
+
+    /// Length of a real array, or `None` for anything else
+    fn array_length(array_ref: &Rc<RefCell<dyn Any>>) -> Option<usize> {
+        let borrowed = array_ref.borrow();
+        let gc_object = borrowed.downcast_ref::<Box<dyn Any>>()?.downcast_ref::<GCObject>()?;
+        match gc_object.get("length") {
+            Value::Smi(len) if gc_object.is_array() => Some(len.max(0) as usize),
+            _ => None,
+        }
+    }
+
+    /// Read `name` from `gc_object`, finding own properties through the
+    /// reading instruction's inline cache slot, if it has one
+    fn cached_get(slot: Option<&PropertySlot>, gc_object: &GCObject, name: &str) -> Value {
+        let Some(slot) = slot else {
+            return gc_object.get(name);
+        };
+        let shape = gc_object.shape();
+        let cached = slot.lookup(shape);
+        if let Some(value) = cached.and_then(|offset| gc_object.property_at(offset, name)) {
+            return value;
+        }
+        if let Some(offset) = gc_object.property_offset(name) {
+            slot.update(shape, offset);
+        }
+        gc_object.get(name)
+    }
+
+    /// Set (or clear) the observer notified of each `console` call
+    pub fn set_console_observer(&mut self, observer: Option<Rc<dyn ConsoleObserver>>) {
+        self.console.borrow_mut().set_observer(observer);
+    }
+
+    /// Set (or clear) the hook notified at each `debugger` statement
+    pub fn set_debugger_hook(&mut self, hook: Option<Rc<dyn DebuggerHook>>) {
+        self.debugger_hook = hook;
+    }
+
+    /// Snapshot of the running frame: its named registers and the upvalues
+    /// of the closure being executed
+    fn capture_frame(
+        &self,
+        ctx: &ExecutionContext,
+        position: Option<bytecode_system::SourcePosition>,
+    ) -> FrameSnapshot {
+        let (line, column) = position.map_or((0, 0), |pos| (pos.line, pos.column));
+        let locals = ctx
+            .bytecode
+            .local_names
+            .iter()
+            .map(|(reg, name)| {
+                (name.clone(), Self::to_builtin_value(&ctx.get_register(reg.0 as usize)))
+            })
+            .collect();
+        let closure = ctx
+            .bytecode
+            .upvalue_names
+            .iter()
+            .zip(&self.current_upvalues)
+            .map(|(name, upvalue)| {
+                (name.clone(), Self::to_builtin_value(&upvalue.borrow().get(&[])))
+            })
+            .collect();
+        FrameSnapshot { line, column, locals, closure }
+    }
+
+    /// Globals the running script defined, leaving out the builtins, sorted
+    /// by name
+    fn script_globals(&self) -> Vec<(String, BuiltinValue)> {
+        let intrinsics = Self::intrinsic_globals(&self.console);
+        let mut globals: Vec<_> = self
+            .globals
+            .iter()
+            .filter(|(name, _)| {
+                !intrinsics.contains_key(*name) && !name.contains('.') && *name != "this"
+            })
+            .map(|(name, value)| (name.clone(), Self::to_builtin_value(value)))
+            .collect();
+        globals.sort_by(|a, b| a.0.cmp(&b.0));
+        globals
+    }
+
+    /// Inline cache slot of the `LoadProperty` at `offset` in function `fn_idx`,
+    /// once the function has been called
+    pub fn property_slot(&self, fn_idx: usize, offset: usize) -> Option<&PropertySlot> {
+        self.function_slots.get(&fn_idx)?.get(offset)
+    }
+
+    /// Property names interned for the inline caches
+    pub fn interner(&self) -> &Interner {
+        &self.interner
+    }
+
+    /// Create the context that runs `chunk`, building its inline cache slots
+    pub fn load_chunk(&mut self, chunk: BytecodeChunk) -> ExecutionContext {
+        let mut ctx = ExecutionContext::new(chunk);
+        ctx.property_slots = PropertySlots::new(&ctx.bytecode, &mut self.interner);
+        ctx
+    }
+
+    /// Own property `key` of a plain object or array, if it has one
+    fn own_property(value: &Value, key: &str) -> Option<Value> {
+        let Value::NativeObject(obj) = value else {
+            return None;
+        };
+        let borrowed = obj.borrow();
+        let gc_object = borrowed.downcast_ref::<Box<dyn Any>>()?.downcast_ref::<GCObject>()?;
+        gc_object.has_own(key).then(|| gc_object.get(key))
+    }
+
+    /// Append `values` to the end of an array, keeping its length in step
+    fn append_to_array(array: &Value, values: Vec<Value>) {
+        if let Value::NativeObject(obj) = array {
+            let mut borrowed = obj.borrow_mut();
+            if let Some(gc_object) = borrowed
+                .downcast_mut::<Box<dyn Any>>()
+                .and_then(|gc_obj| gc_obj.downcast_mut::<GCObject>())
+            {
+                let mut len = match gc_object.get("length") {
+                    Value::Smi(n) => n.max(0),
+                    _ => 0,
+                };
+                for value in values {
+                    gc_object.set(len.to_string(), value);
+                    len += 1;
+                }
+                gc_object.set("length".to_string(), Value::Smi(len));
+            }
         }
+    }
 
-        // Determine the actual function index and closure upvalues
+    /// Create the execution context and look up the captured upvalues for a
+    /// function index or closure ID
+    fn resolve_function(
+        &mut self,
+        func_idx_or_closure: usize,
+        functions: &[BytecodeChunk],
+    ) -> Result<(ExecutionContext, Option<Vec<UpvalueHandle>>), JsError> {
         let (fn_idx, closure_upvalues) = if func_idx_or_closure >= 1_000_000 {
             let closure_id = func_idx_or_closure - 1_000_000;
             match self.closure_registry.get(&closure_id) {
                 Some((func_idx, upvalues)) => (*func_idx, Some(upvalues.clone())),
                 None => {
-                    CALL_DEPTH.fetch_sub(1, std::sync::atomic::Ordering::SeqCst);
                     return Err(JsError {
                         kind: ErrorKind::ReferenceError,
                         message: format!("Invalid closure ID: {}", closure_id),
@@ -3101,22 +4862,68 @@ impl Dispatcher {
             (func_idx_or_closure, None)
         };
 
-        // Get the function bytecode
-        let fn_bytecode = match functions.get(fn_idx) {
-            Some(chunk) => chunk.clone(),
-            None => {
-                CALL_DEPTH.fetch_sub(1, std::sync::atomic::Ordering::SeqCst);
-                return Err(JsError {
-                    kind: ErrorKind::ReferenceError,
-                    message: format!("Invalid function index: {}", fn_idx),
-                    stack: vec![],
-                    source_position: None,
-                });
+        match functions.get(fn_idx) {
+            Some(chunk) => {
+                // Names are interned once per function; later calls share its slots
+                let slots = match self.function_slots.get(&fn_idx) {
+                    Some(slots) if slots.len() == chunk.instructions.len() => slots.clone(),
+                    _ => {
+                        let slots = PropertySlots::new(chunk, &mut self.interner);
+                        self.function_slots.insert(fn_idx, slots.clone());
+                        slots
+                    }
+                };
+                let mut ctx = ExecutionContext::new(chunk.clone());
+                ctx.property_slots = slots;
+                Ok((ctx, closure_upvalues))
             }
-        };
+            None => Err(JsError {
+                kind: ErrorKind::ReferenceError,
+                message: format!("Invalid function index: {}", fn_idx),
+                stack: vec![],
+                source_position: None,
+            }),
+        }
+    }
+
+    /// Execute a method call with `this` binding
+    ///
+    /// # Arguments
+    /// * `func_idx_or_closure` - The function index or closure encoded ID
+    /// * `receiver` - The receiver object (this binding)
+    /// * `args` - The function arguments
+    /// * `functions` - The function registry
+    ///
+    /// # Returns
+    /// The return value of the method
+    fn call_method_with_this(
+        &mut self,
+        func_idx_or_closure: usize,
+        receiver: Value,
+        args: Vec<Value>,
+        functions: &[BytecodeChunk],
+    ) -> Result<Value, JsError> {
+        // Check for recursion depth
+        static CALL_DEPTH: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+        let depth = CALL_DEPTH.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        if depth > 10000 {
+            CALL_DEPTH.fetch_sub(1, std::sync::atomic::Ordering::SeqCst);
+            return Err(JsError {
+                kind: ErrorKind::RangeError,
+                message: "Maximum call stack size exceeded".to_string(),
+                stack: vec![],
+                source_position: None,
+            });
+        }
 
-        // Create new execution context
-        let mut fn_ctx = ExecutionContext::new(fn_bytecode);
+        let (mut fn_ctx, closure_upvalues) =
+            match self.resolve_function(func_idx_or_closure, functions) {
+                Ok(resolved) => resolved,
+                Err(err) => {
+                    CALL_DEPTH.fetch_sub(1, std::sync::atomic::Ordering::SeqCst);
+                    return Err(err);
+                }
+            };
 
         // Set arguments in registers starting from 0 (matching parser's parameter allocation)
         for (i, arg) in args.into_iter().enumerate() {
@@ -3124,7 +4931,9 @@ impl Dispatcher {
         }
 
         // Save current globals state and set `this` as a global variable
-        // The parser emits LoadGlobal("this") for `this` expressions
+        // The parser emits LoadGlobal("this") for `this` expressions, which
+        // reads the globals of the realm the function was created in
+        let caller_realm = self.enter_realm(self.function_realm(func_idx_or_closure));
         let saved_this = self.globals.get("this").cloned();
         self.globals.insert("this".to_string(), receiver);
 
@@ -3135,7 +4944,13 @@ impl Dispatcher {
             self.current_upvalues = upvalues;
         }
 
+        let stack_height = self.stack.len();
+        let frame = self.call_site_frame();
+        self.call_stack.push((func_idx_or_closure, frame));
         let result = self.execute(&mut fn_ctx, functions);
+        self.call_stack.pop();
+        // Drop any operands the body left behind
+        self.stack.truncate(stack_height);
 
         // Restore previous `this` binding
         if let Some(prev_this) = saved_this {
@@ -3143,6 +4958,7 @@ impl Dispatcher {
         } else {
             self.globals.remove("this");
         }
+        self.enter_realm(caller_realm);
 
         self.current_upvalues = saved_upvalues;
         self.open_upvalues = saved_open_upvalues;
@@ -3179,42 +4995,22 @@ impl Dispatcher {
             });
         }
 
-        // Determine the actual function index and closure upvalues
-        let (fn_idx, closure_upvalues) = if func_idx_or_closure >= 1_000_000 {
-            let closure_id = func_idx_or_closure - 1_000_000;
-            match self.closure_registry.get(&closure_id) {
-                Some((func_idx, upvalues)) => (*func_idx, Some(upvalues.clone())),
-                None => {
+        let (mut fn_ctx, closure_upvalues) =
+            match self.resolve_function(func_idx_or_closure, functions) {
+                Ok(resolved) => resolved,
+                Err(err) => {
                     CALL_DEPTH.fetch_sub(1, std::sync::atomic::Ordering::SeqCst);
-                    return Err(JsError {
-                        kind: ErrorKind::ReferenceError,
-                        message: format!("Invalid closure ID: {}", closure_id),
-                        stack: vec![],
-                        source_position: None,
-                    });
+                    return Err(err);
                 }
-            }
-        } else {
-            (func_idx_or_closure, None)
-        };
-
-        // Get the function bytecode
-        let fn_bytecode = match functions.get(fn_idx) {
-            Some(chunk) => chunk.clone(),
-            None => {
-                CALL_DEPTH.fetch_sub(1, std::sync::atomic::Ordering::SeqCst);
-                return Err(JsError {
-                    kind: ErrorKind::ReferenceError,
-                    message: format!("Invalid function index: {}", fn_idx),
-                    stack: vec![],
-                    source_position: None,
-                });
-            }
-        };
+            };
 
-        // Create new instance object
+        // Create new instance object inheriting from the constructor's `prototype`
+        let prototype = self.function_prototype(func_idx_or_closure);
         let instance = if let Some(ref heap) = self.heap {
-            let gc_object = heap.create_object();
+            let mut gc_object = heap.create_object();
+            if matches!(prototype, Value::NativeObject(_)) {
+                gc_object.set_prototype_value(prototype);
+            }
             let boxed: Box<dyn Any> = Box::new(gc_object);
             Value::NativeObject(Rc::new(RefCell::new(boxed)) as Rc<RefCell<dyn Any>>)
         } else {
@@ -3222,8 +5018,7 @@ impl Dispatcher {
             Value::HeapObject(0)
         };
 
-        // Create new execution context
-        let mut fn_ctx = ExecutionContext::new(fn_bytecode);
+        fn_ctx.new_target = Value::HeapObject(func_idx_or_closure);
 
         // Set arguments in registers starting from 0 (matching parser's parameter allocation)
         // Parameters are allocated registers 0, 1, 2... by the parser
@@ -3232,7 +5027,9 @@ impl Dispatcher {
         }
 
         // Save current globals state and set `this` as a global variable
-        // The parser emits LoadGlobal("this") for `this` expressions
+        // The parser emits LoadGlobal("this") for `this` expressions, which
+        // reads the globals of the realm the function was created in
+        let caller_realm = self.enter_realm(self.function_realm(func_idx_or_closure));
         let saved_this = self.globals.get("this").cloned();
         self.globals.insert("this".to_string(), instance.clone());
 
@@ -3243,7 +5040,13 @@ impl Dispatcher {
             self.current_upvalues = upvalues;
         }
 
+        let stack_height = self.stack.len();
+        let frame = self.call_site_frame();
+        self.call_stack.push((func_idx_or_closure, frame));
         let result = self.execute(&mut fn_ctx, functions);
+        self.call_stack.pop();
+        // Drop any operands the body left behind
+        self.stack.truncate(stack_height);
 
         // Restore previous `this` binding
         if let Some(prev_this) = saved_this {
@@ -3251,6 +5054,7 @@ impl Dispatcher {
         } else {
             self.globals.remove("this");
         }
+        self.enter_realm(caller_realm);
 
         self.current_upvalues = saved_upvalues;
         self.open_upvalues = saved_open_upvalues;
@@ -3265,6 +5069,23 @@ impl Dispatcher {
         }
     }
 
+    /// Get the `prototype` object of a user-defined function, creating
+    /// `{ constructor: f }` on first access
+    fn function_prototype(&mut self, id: usize) -> Value {
+        if let Some(prototype) = self.function_prototypes.get(&id) {
+            return prototype.clone();
+        }
+        let Some(ref heap) = self.heap else {
+            return Value::Undefined;
+        };
+        let mut gc_object = heap.create_object();
+        gc_object.set("constructor".to_string(), Value::HeapObject(id));
+        let boxed: Box<dyn Any> = Box::new(gc_object);
+        let prototype = Value::NativeObject(Rc::new(RefCell::new(boxed)) as Rc<RefCell<dyn Any>>);
+        self.function_prototypes.insert(id, prototype.clone());
+        prototype
+    }
+
     /// Get global variable
     pub fn get_global(&self, name: &str) -> Option<Value> {
         self.globals.get(name).cloned()
@@ -3275,6 +5096,70 @@ impl Dispatcher {
         self.globals.insert(name, value);
     }
 
+    /// Names of all global bindings, sorted
+    ///
+    /// Internal entries such as `Error.prototype` are not bindings and are skipped.
+    pub fn global_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .globals
+            .keys()
+            .filter(|name| !name.contains('.'))
+            .cloned()
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// Property names `LoadProperty` resolves on `value`
+    ///
+    /// For objects this is their own properties followed by inherited ones;
+    /// for host objects such as `Math` it is the members they expose.
+    pub fn property_names(&self, value: &Value) -> Vec<String> {
+        let names: Vec<&str> = match value {
+            Value::NativeObject(obj) => {
+                let borrowed = obj.borrow();
+                if let Some(gc_object) = borrowed
+                    .downcast_ref::<Box<dyn Any>>()
+                    .and_then(|boxed| boxed.downcast_ref::<GCObject>())
+                {
+                    let mut keys = gc_object.keys_with_prototypes();
+                    let builtin = if gc_object.is_array() {
+                        ARRAY_PROTOTYPE_METHODS
+                    } else {
+                        OBJECT_PROTOTYPE_METHODS
+                    };
+                    for name in builtin {
+                        if !keys.iter().any(|key| key == name) {
+                            keys.push(name.to_string());
+                        }
+                    }
+                    return keys;
+                } else if borrowed.is::<ConsoleObject>() {
+                    CONSOLE_METHODS.to_vec()
+                } else if borrowed.is::<MathObject>() {
+                    let constants = MATH_CONSTANTS.iter().map(|(name, _)| *name);
+                    MATH_METHODS.iter().copied().chain(constants).collect()
+                } else if borrowed.is::<JSONObject>() {
+                    JSON_METHODS.to_vec()
+                } else if borrowed.is::<PerformanceObject>() {
+                    PERFORMANCE_METHODS.to_vec()
+                } else if borrowed.is::<JsDate>() {
+                    DATE_PROTOTYPE_METHODS.to_vec()
+                } else if borrowed.is::<GlobalObject>() {
+                    return self.global_names();
+                } else if borrowed.is::<BoundFunction>() {
+                    FUNCTION_PROTOTYPE_METHODS.to_vec()
+                } else {
+                    vec![]
+                }
+            }
+            Value::NativeFunction(name) if name == "Promise" => PROMISE_METHODS.to_vec(),
+            Value::NativeFunction(_) | Value::HeapObject(_) => FUNCTION_PROTOTYPE_METHODS.to_vec(),
+            _ => vec![],
+        };
+        names.into_iter().map(String::from).collect()
+    }
+
     // Arithmetic operations
 
     fn add(&self, a: Value, b: Value) -> Result<Value, JsError> {
@@ -3292,7 +5177,10 @@ impl Dispatcher {
                 Ok(Value::String(format!("{}{}", a_str, s)))
             }
             // Numeric addition
-            (Value::Smi(x), Value::Smi(y)) => Ok(Value::Smi(x.wrapping_add(*y))),
+            (Value::Smi(x), Value::Smi(y)) => Ok(match x.checked_add(*y) {
+                Some(sum) => Value::Smi(sum),
+                None => Value::Double(*x as f64 + *y as f64),
+            }),
             (Value::Double(x), Value::Double(y)) => Ok(Value::Double(*x + *y)),
             (Value::Smi(x), Value::Double(y)) => Ok(Value::Double(*x as f64 + *y)),
             (Value::Double(x), Value::Smi(y)) => Ok(Value::Double(*x + *y as f64)),
@@ -3315,18 +5203,50 @@ impl Dispatcher {
             Value::String(s) => s.clone(),
             Value::HeapObject(id) => format!("[object Object {}]", id),
             Value::NativeObject(_) => "[object Object]".to_string(),
-            Value::NativeFunction(name) => format!("function {}() {{ [native code] }}", name),
+            Value::NativeFunction(name) => {
+                let name = Self::split_realm_tag(name).0;
+                format!("function {}() {{ [native code] }}", name)
+            }
             Value::BigInt(n) => n.to_string(),
         }
     }
 
+    /// ToUint32: wrap a number modulo 2^32, with NaN and infinities as 0
+    fn to_uint32(n: f64) -> u32 {
+        if n.is_finite() {
+            n.trunc().rem_euclid(4_294_967_296.0) as u32
+        } else {
+            0
+        }
+    }
+
+    /// ToInt32: ToUint32 reinterpreted as a signed 32-bit integer
+    fn to_int32(n: f64) -> i32 {
+        Self::to_uint32(n) as i32
+    }
+
+    /// Get the pattern and flags of an object created by a regex literal
+    fn regexp_source_and_flags(value: &Value) -> Option<(String, String)> {
+        let Value::NativeObject(obj) = value else {
+            return None;
+        };
+        let borrowed = obj.borrow();
+        let gc_object = borrowed
+            .downcast_ref::<Box<dyn Any>>()
+            .and_then(|gc_obj| gc_obj.downcast_ref::<GCObject>())?;
+        match (gc_object.get("source"), gc_object.get("flags")) {
+            (Value::String(source), Value::String(flags)) => Some((source, flags)),
+            _ => None,
+        }
+    }
+
     /// Convert a value to a property key (string)
     fn to_property_key(&self, value: &Value) -> String {
         match value {
             Value::Smi(n) => n.to_string(),
             Value::Double(n) => {
-                // Check if it's an integer
-                if n.fract() == 0.0 && *n >= 0.0 && *n <= (i32::MAX as f64) {
+                // Check if it's an integer (-0 is the key "0")
+                if n.fract() == 0.0 && *n > -1.0 && *n <= (i32::MAX as f64) {
                     (*n as i32).to_string()
                 } else {
                     n.to_string()
@@ -3336,13 +5256,133 @@ impl Dispatcher {
             Value::Boolean(b) => b.to_string(),
             Value::Undefined => "undefined".to_string(),
             Value::Null => "null".to_string(),
-            _ => "[object]".to_string(),
+            other => match Self::symbol_value(other) {
+                Some(symbol) => Self::symbol_key(&symbol),
+                None => self.to_string_value(other),
+            },
+        }
+    }
+
+    /// Convert a value to a primitive (ECMAScript ToPrimitive)
+    ///
+    /// Objects call their `Symbol.toPrimitive` method with the hint if they
+    /// have one, otherwise their own `valueOf` and `toString` in hint order.
+    /// Without either, arrays join their elements and other objects become
+    /// `"[object Object]"`. Values that aren't plain objects are returned as is.
+    fn to_primitive(
+        &mut self,
+        value: Value,
+        hint: PreferredType,
+        functions: &[BytecodeChunk],
+    ) -> Result<Value, JsError> {
+        let Value::NativeObject(obj) = &value else {
+            return Ok(value);
+        };
+        let order = match hint {
+            PreferredType::String => ["toString", "valueOf"],
+            PreferredType::Default | PreferredType::Number => ["valueOf", "toString"],
+        };
+        let (exotic, methods) = {
+            let borrowed = obj.borrow();
+            let Some(gc_object) = borrowed
+                .downcast_ref::<Box<dyn Any>>()
+                .and_then(|gc_obj| gc_obj.downcast_ref::<GCObject>())
+            else {
+                drop(borrowed);
+                return Ok(value);
+            };
+            let key = Self::symbol_key(&SymbolConstructor::to_primitive());
+            (gc_object.get(&key), order.map(|name| gc_object.get(name)))
+        };
+
+        let is_object = |value: &Value| {
+            matches!(value, Value::NativeObject(_)) && Self::symbol_value(value).is_none()
+        };
+        match exotic {
+            Value::Undefined | Value::Null => {}
+            method => {
+                let hint = Value::String(hint.as_str().to_string());
+                let result = self.call_value(method, Some(value.clone()), vec![hint], functions)?;
+                if is_object(&result) {
+                    return Err(Self::type_error("Cannot convert object to primitive value"));
+                }
+                return Ok(result);
+            }
+        }
+
+        for method in methods {
+            if Self::is_callable(&method) {
+                let result = self.call_value(method, Some(value.clone()), Vec::new(), functions)?;
+                if !is_object(&result) {
+                    return Ok(result);
+                }
+            }
+        }
+        self.coerce_property_key(value, functions).map(Value::String)
+    }
+
+    /// Property key a symbol is stored under
+    fn symbol_key(symbol: &SymbolValue) -> String {
+        format!("{}{}", SYMBOL_KEY_PREFIX, symbol.id())
+    }
+
+    /// Convert a computed member key to a property key, calling the key's own
+    /// `toString` for objects
+    ///
+    /// Arrays join their elements with commas; other objects without a
+    /// callable `toString` become `"[object Object]"`.
+    fn coerce_property_key(&mut self, key: Value, functions: &[BytecodeChunk]) -> Result<String, JsError> {
+        let Value::NativeObject(obj) = &key else {
+            return Ok(self.to_property_key(&key));
+        };
+        if Self::symbol_value(&key).is_some() {
+            return Ok(self.to_property_key(&key));
+        }
+
+        let to_string = {
+            let borrowed = obj.borrow();
+            let Some(gc_object) = borrowed
+                .downcast_ref::<Box<dyn Any>>()
+                .and_then(|gc_obj| gc_obj.downcast_ref::<GCObject>())
+            else {
+                return Ok("[object Object]".to_string());
+            };
+
+            if let Value::Smi(len) = gc_object.get("length") {
+                let parts: Vec<String> = (0..len.max(0))
+                    .map(|i| match gc_object.get(&i.to_string()) {
+                        Value::Undefined | Value::Null => String::new(),
+                        element => self.to_property_key(&element),
+                    })
+                    .collect();
+                return Ok(parts.join(","));
+            }
+            gc_object.get("toString")
+        };
+
+        match to_string {
+            Value::HeapObject(_) | Value::NativeFunction(_) => {
+                let primitive = self.call_value(to_string, Some(key.clone()), Vec::new(), functions)?;
+                match primitive {
+                    Value::NativeObject(_) => Err(JsError {
+                        kind: ErrorKind::TypeError,
+                        message: "Cannot convert object to primitive value".to_string(),
+                        stack: vec![],
+                        source_position: None,
+                    }),
+                    primitive => Ok(self.to_property_key(&primitive)),
+                }
+            }
+            _ => Ok("[object Object]".to_string()),
         }
     }
 
     fn sub(&self, a: Value, b: Value) -> Result<Value, JsError> {
         match (a, b) {
-            (Value::Smi(x), Value::Smi(y)) => Ok(Value::Smi(x.wrapping_sub(y))),
+            (Value::Smi(x), Value::Smi(y)) => Ok(match x.checked_sub(y) {
+                Some(difference) => Value::Smi(difference),
+                None => Value::Double(x as f64 - y as f64),
+            }),
             (Value::Double(x), Value::Double(y)) => Ok(Value::Double(x - y)),
             (Value::Smi(x), Value::Double(y)) => Ok(Value::Double(x as f64 - y)),
             (Value::Double(x), Value::Smi(y)) => Ok(Value::Double(x - y as f64)),
@@ -3357,7 +5397,12 @@ impl Dispatcher {
 
     fn mul(&self, a: Value, b: Value) -> Result<Value, JsError> {
         match (a, b) {
-            (Value::Smi(x), Value::Smi(y)) => Ok(Value::Smi(x.wrapping_mul(y))),
+            (Value::Smi(x), Value::Smi(y)) => Ok(match x.checked_mul(y) {
+                // A zero product with a negative operand is -0, which only a Double holds
+                Some(0) if x < 0 || y < 0 => Value::Double(-0.0),
+                Some(product) => Value::Smi(product),
+                None => Value::Double(x as f64 * y as f64),
+            }),
             (Value::Double(x), Value::Double(y)) => Ok(Value::Double(x * y)),
             (Value::Smi(x), Value::Double(y)) => Ok(Value::Double(x as f64 * y)),
             (Value::Double(x), Value::Smi(y)) => Ok(Value::Double(x * y as f64)),
@@ -3380,10 +5425,13 @@ impl Dispatcher {
     fn modulo(&self, a: Value, b: Value) -> Result<Value, JsError> {
         match (&a, &b) {
             (Value::Smi(x), Value::Smi(y)) => {
-                if *y != 0 {
-                    Ok(Value::Smi(x % y))
-                } else {
-                    Ok(Value::Double(f64::NAN))
+                match x.checked_rem(*y) {
+                    // The remainder takes the dividend's sign, so -4 % 2 is -0
+                    Some(0) if *x < 0 => Ok(Value::Double(-0.0)),
+                    Some(rem) => Ok(Value::Smi(rem)),
+                    None if *y == 0 => Ok(Value::Double(f64::NAN)),
+                    // i32::MIN % -1
+                    None => Ok(Value::Double(-0.0)),
                 }
             }
             _ => {
@@ -3397,12 +5445,41 @@ impl Dispatcher {
     fn exponentiate(&self, a: Value, b: Value) -> Result<Value, JsError> {
         let a_num = self.to_number(&a);
         let b_num = self.to_number(&b);
+        // powf treats 1 ** NaN and (-1) ** Infinity as 1; Number::exponentiate gives NaN
+        if b_num.is_nan() || (a_num.abs() == 1.0 && b_num.is_infinite()) {
+            return Ok(Value::Double(f64::NAN));
+        }
         Ok(Value::Double(a_num.powf(b_num)))
     }
 
+    /// Binary bitwise and shift operators, applied to ToInt32/ToUint32 of the operands
+    fn bitwise(&self, opcode: &Opcode, a: &Value, b: &Value) -> Value {
+        let lhs = Self::to_int32(self.to_number(a));
+        // Shift counts use only the low five bits of ToUint32
+        let count = Self::to_uint32(self.to_number(b)) & 31;
+        match opcode {
+            Opcode::BitAnd => Value::Smi(lhs & Self::to_int32(self.to_number(b))),
+            Opcode::BitOr => Value::Smi(lhs | Self::to_int32(self.to_number(b))),
+            Opcode::BitXor => Value::Smi(lhs ^ Self::to_int32(self.to_number(b))),
+            Opcode::ShiftLeft => Value::Smi(lhs.wrapping_shl(count)),
+            Opcode::ShiftRight => Value::Smi(lhs >> count),
+            _ => {
+                let result = Self::to_uint32(self.to_number(a)) >> count;
+                i32::try_from(result)
+                    .map(Value::Smi)
+                    .unwrap_or(Value::Double(result as f64))
+            }
+        }
+    }
+
     fn neg(&self, a: Value) -> Result<Value, JsError> {
         match a {
-            Value::Smi(x) => Ok(Value::Smi(-x)),
+            // -0 and -i32::MIN don't fit in a Smi
+            Value::Smi(0) => Ok(Value::Double(-0.0)),
+            Value::Smi(x) => Ok(x
+                .checked_neg()
+                .map(Value::Smi)
+                .unwrap_or(Value::Double(-(x as f64)))),
             Value::Double(x) => Ok(Value::Double(-x)),
             _ => Ok(Value::Double(f64::NAN)),
         }
@@ -3571,53 +5648,53 @@ impl Dispatcher {
         Value::Boolean(a_num >= b_num)
     }
 
+    /// `obj instanceof constructor`: whether the constructor's `prototype`
+    /// is on the prototype chain of `obj`
+    ///
+    /// Each realm has its own prototypes, so an array from another realm is
+    /// not an instance of this realm's `Array`.
     fn instanceof_check(&self, obj: Value, constructor: Value) -> Value {
-        // Basic instanceof implementation for Test262 compliance
-        // In JavaScript: obj instanceof Constructor
-        // Returns true if Constructor.prototype is in obj's prototype chain
-
-        match (&obj, &constructor) {
-            // Primitives are not instances of constructors
-            (Value::Undefined, _) | (Value::Null, _) => Value::Boolean(false),
-            (Value::Boolean(_), _) | (Value::Smi(_), _) | (Value::Double(_), _) => {
-                Value::Boolean(false)
-            }
-
-            // Check if constructor is actually a constructor
-            (_, Value::NativeFunction(name)) => {
-                // For native constructors, check object properties
-                // This is a simplified check - full implementation would check prototype chain
-                match name.as_str() {
-                    "Error" | "TypeError" | "ReferenceError" | "RangeError" |
-                    "SyntaxError" | "URIError" | "EvalError" => {
-                        // Check if obj is an error object
-                        // For now, return false - would need to check object's constructor property
-                        Value::Boolean(false)
-                    }
-                    "Array" => {
-                        // Check if obj is an array
-                        Value::Boolean(false)
-                    }
-                    "Object" => {
-                        // All objects are instances of Object
-                        Value::Boolean(matches!(obj, Value::HeapObject(_) | Value::NativeObject(_)))
-                    }
-                    "Function" => {
-                        // Check if obj is a function
-                        Value::Boolean(matches!(obj, Value::NativeFunction(_)))
-                    }
-                    _ => Value::Boolean(false),
-                }
-            }
+        let prototype = match &constructor {
+            Value::HeapObject(id) => self.function_prototypes.get(id).cloned(),
+            Value::NativeFunction(name) => match Self::split_realm_tag(name) {
+                // Object literals link no prototype, so every object counts
+                ("Object", _) => {
+                    let is_object = matches!(obj, Value::HeapObject(_) | Value::NativeObject(_));
+                    return Value::Boolean(is_object);
+                }
+                ("Function", _) => {
+                    let is_function =
+                        matches!(obj, Value::HeapObject(_) | Value::NativeFunction(_));
+                    return Value::Boolean(is_function);
+                }
+                (name, realm) => self.realm_global(realm, &format!("{}.prototype", name)),
+            },
+            _ => None,
+        };
+        let Some(Value::NativeObject(target)) = prototype else {
+            return Value::Boolean(false);
+        };
 
-            // HeapObject instanceof constructor would need prototype chain walking
-            (Value::HeapObject(_), _) | (Value::NativeObject(_), _) => {
-                // TODO: Implement prototype chain walking
-                // For now, return false
-                Value::Boolean(false)
+        let mut current = match &obj {
+            Value::NativeObject(object) => Self::linked_prototype(object),
+            _ => None,
+        };
+        while let Some(prototype) = current {
+            if Rc::ptr_eq(&prototype, &target) {
+                return Value::Boolean(true);
             }
+            current = Self::linked_prototype(&prototype);
+        }
+        Value::Boolean(false)
+    }
 
-            _ => Value::Boolean(false),
+    /// The shared prototype a GCObject inherits from, if one is linked
+    fn linked_prototype(object: &Rc<RefCell<dyn Any>>) -> Option<Rc<RefCell<dyn Any>>> {
+        let borrowed = object.try_borrow().ok()?;
+        let gc_object = borrowed.downcast_ref::<Box<dyn Any>>()?.downcast_ref::<GCObject>()?;
+        match gc_object.prototype_value()? {
+            Value::NativeObject(prototype) => Some(prototype.clone()),
+            _ => None,
         }
     }
 
@@ -3660,7 +5737,7 @@ impl Dispatcher {
                         if let Some(gc_obj) = borrowed.downcast_ref::<Box<dyn Any>>() {
                             if let Some(gc_object) = gc_obj.downcast_ref::<GCObject>() {
                                 // Check if it's an array
-                                if matches!(gc_object.get("length"), Value::Smi(_)) {
+                                if gc_object.is_array() {
                                     "Array"
                                 } else {
                                     "Object"
@@ -3811,22 +5888,36 @@ impl Dispatcher {
                 Ok(Value::String(s.trim_end().to_string()))
             }
             "String.prototype.split" => {
-                let separator = args.first().map(|v| self.to_string_value(v));
-                let limit = args.get(1).map(|v| self.to_number(v) as usize);
+                let limit = match args.get(1) {
+                    None | Some(Value::Undefined) => None,
+                    Some(v) => Some(Self::to_uint32(self.to_number(v))),
+                };
 
-                let parts: Vec<&str> = if let Some(sep) = separator {
-                    if let Some(lim) = limit {
-                        s.split(&sep).take(lim).collect()
-                    } else {
-                        s.split(&sep).collect()
+                let parts = match args.first() {
+                    None | Some(Value::Undefined) => {
+                        if limit == Some(0) { Vec::new() } else { vec![s] }
+                    }
+                    Some(separator) => {
+                        let result = match Self::regexp_source_and_flags(separator) {
+                            Some((source, flags)) => builtins::RegExpObject::new(&source, &flags)
+                                .and_then(|re| builtins::StringPrototype::split(&s, &re, limit)),
+                            None => {
+                                let separator = self.to_string_value(separator);
+                                builtins::StringPrototype::split(&s, separator.as_str(), limit)
+                            }
+                        };
+                        result.map_err(|e| JsError {
+                            kind: ErrorKind::SyntaxError,
+                            message: e.message,
+                            stack: vec![],
+                            source_position: None,
+                        })?
                     }
-                } else {
-                    vec![&s]
                 };
 
                 // Create array with parts
                 if let Some(ref heap) = self.heap {
-                    let mut gc_object = heap.create_object();
+                    let mut gc_object = heap.create_array();
                     for (i, part) in parts.iter().enumerate() {
                         gc_object.set(i.to_string(), Value::String(part.to_string()));
                     }
@@ -3884,6 +5975,12 @@ impl Dispatcher {
                     Ok(Value::String(format!("{}{}", s, &pad[..pad_len])))
                 }
             }
+            "String.prototype.isWellFormed" => {
+                Ok(Value::Boolean(builtins::StringPrototype::is_well_formed(&s)))
+            }
+            "String.prototype.toWellFormed" => {
+                Ok(Value::String(builtins::StringPrototype::to_well_formed(&s)))
+            }
             _ => Err(JsError {
                 kind: ErrorKind::TypeError,
                 message: format!("Unknown String.prototype method: {}", name),
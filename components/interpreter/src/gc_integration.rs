@@ -3,12 +3,34 @@
 //! Provides heap-allocated JavaScript objects that integrate with
 //! the memory_manager's garbage collector.
 
-use core_types::Value;
+use crate::inline_cache::ShapeId;
+use core_types::{ObjectView, Value, MAX_ARRAY_ITEMS};
+use indexmap::IndexMap;
 use memory_manager::{Heap, HiddenClass};
+use std::any::Any;
 use std::cell::RefCell;
+use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
 use std::rc::Rc;
 
+/// Prefix of the property keys symbol-keyed properties are stored under
+///
+/// Symbol keys are left out of [`GCObject::keys`], so they never show up
+/// as string keys.
+pub const SYMBOL_KEY_PREFIX: &str = "@@symbol:";
+
+/// Shape of an object with no own properties
+const EMPTY_SHAPE: ShapeId = 0;
+
+/// Shape reached by adding `key` to an object of shape `shape`
+fn next_shape(shape: ShapeId, key: &str) -> ShapeId {
+    let mut hasher = DefaultHasher::new();
+    shape.hash(&mut hasher);
+    key.hash(&mut hasher);
+    hasher.finish() as ShapeId
+}
+
 /// GC-managed JavaScript object
 ///
 /// This structure represents a JavaScript object that is allocated
@@ -17,12 +39,30 @@ use std::rc::Rc;
 pub struct GCObject {
     /// Reference to the shared heap
     heap: Rc<RefCell<Heap>>,
-    /// Property storage
-    properties: HashMap<String, Value>,
+    /// Property storage, in insertion order
+    properties: IndexMap<String, Value>,
     /// Prototype object (for prototype chain)
     prototype: Option<Box<GCObject>>,
+    /// Shared prototype object (a constructor's `prototype`), linked by `new`
+    prototype_value: Option<Value>,
     /// Hidden class for property layout optimization
     hidden_class: Option<Box<HiddenClass>>,
+    /// Whether this is an Array exotic object rather than a plain object
+    array: bool,
+    /// Layout of the own properties, see [`GCObject::shape`]
+    shape: ShapeId,
+    /// Getter and setter of each own accessor property; the property itself
+    /// holds `undefined` in `properties` so it keeps its place in key order
+    accessors: HashMap<String, Accessor>,
+}
+
+/// Getter and setter functions of an accessor property
+#[derive(Debug, Clone, Default)]
+pub struct Accessor {
+    /// Function called with the object as `this` when the property is read
+    pub get: Option<Value>,
+    /// Function called with the object as `this` and the value when it is written
+    pub set: Option<Value>,
 }
 
 impl std::fmt::Debug for GCObject {
@@ -44,9 +84,13 @@ impl GCObject {
     pub fn new(heap: Rc<RefCell<Heap>>) -> Self {
         Self {
             heap,
-            properties: HashMap::new(),
+            properties: IndexMap::new(),
             prototype: None,
+            prototype_value: None,
             hidden_class: Some(Box::new(HiddenClass::new())),
+            array: false,
+            shape: EMPTY_SHAPE,
+            accessors: HashMap::new(),
         }
     }
 
@@ -59,12 +103,36 @@ impl GCObject {
     pub fn with_prototype(heap: Rc<RefCell<Heap>>, prototype: GCObject) -> Self {
         Self {
             heap,
-            properties: HashMap::new(),
+            properties: IndexMap::new(),
             prototype: Some(Box::new(prototype)),
+            prototype_value: None,
             hidden_class: Some(Box::new(HiddenClass::new())),
+            array: false,
+            shape: EMPTY_SHAPE,
+            accessors: HashMap::new(),
         }
     }
 
+    /// Create a new empty array, with `length` 0
+    ///
+    /// # Arguments
+    ///
+    /// * `heap` - Shared reference to the GC heap
+    pub fn new_array(heap: Rc<RefCell<Heap>>) -> Self {
+        let mut array = Self::new(heap);
+        array.array = true;
+        array.set("length".to_string(), Value::Smi(0));
+        array
+    }
+
+    /// Check whether this object is an array
+    ///
+    /// Only objects created as arrays are; a plain object with a numeric
+    /// `length` is array-like but not an array.
+    pub fn is_array(&self) -> bool {
+        self.array
+    }
+
     /// Get a property value by name
     ///
     /// Traverses the prototype chain if the property is not found
@@ -93,6 +161,9 @@ impl GCObject {
         if let Some(ref proto) = self.prototype {
             return proto.get(key);
         }
+        if let Some(proto) = self.shared_prototype() {
+            return proto.get(key);
+        }
 
         Value::Undefined
     }
@@ -106,15 +177,51 @@ impl GCObject {
     /// * `key` - The property name
     /// * `value` - The value to set
     pub fn set(&mut self, key: String, value: Value) {
-        // Update hidden class for new property
+        // Update hidden class and shape for new property
         if !self.properties.contains_key(&key) {
             if let Some(ref class) = self.hidden_class {
                 self.hidden_class = Some(class.add_property(key.clone()));
             }
+            self.shape = next_shape(self.shape, &key);
+        }
+        if !self.accessors.is_empty() {
+            self.accessors.remove(&key);
         }
         self.properties.insert(key, value);
     }
 
+    /// Define the getter or setter of an own accessor property
+    ///
+    /// Defining one half keeps the other, so `get` and `set` for the same
+    /// key in an object literal make up a single property.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The property name
+    /// * `get` - The getter to install, if any
+    /// * `set` - The setter to install, if any
+    pub fn define_accessor(&mut self, key: String, get: Option<Value>, set: Option<Value>) {
+        let mut accessor = self.accessors.remove(&key).unwrap_or_default();
+        accessor.get = get.or(accessor.get);
+        accessor.set = set.or(accessor.set);
+        self.set(key.clone(), Value::Undefined);
+        self.accessors.insert(key, accessor);
+    }
+
+    /// Find the accessor property `key` resolves to
+    ///
+    /// Traverses the prototype chain like [`GCObject::get`]; a data property
+    /// found first shadows any accessor further up.
+    pub fn accessor(&self, key: &str) -> Option<Accessor> {
+        if self.properties.contains_key(key) {
+            return self.accessors.get(key).cloned();
+        }
+        if let Some(ref proto) = self.prototype {
+            return proto.accessor(key);
+        }
+        self.shared_prototype().and_then(|proto| proto.accessor(key))
+    }
+
     /// Check if the object has a property (including prototype chain)
     ///
     /// # Arguments
@@ -131,6 +238,7 @@ impl GCObject {
                 .as_ref()
                 .map(|p| p.has(key))
                 .unwrap_or(false)
+            || self.shared_prototype().map(|p| p.has(key)).unwrap_or(false)
     }
 
     /// Check if the object has an own property (not from prototype)
@@ -148,11 +256,44 @@ impl GCObject {
 
     /// Get all own property keys
     ///
+    /// Array index keys come first in ascending order, then the other keys
+    /// in the order they were added, as for `Object.keys`.
+    ///
     /// # Returns
     ///
     /// A vector of property names
     pub fn keys(&self) -> Vec<String> {
-        self.properties.keys().cloned().collect()
+        let (mut indices, names): (Vec<(u32, &String)>, Vec<(u32, &String)>) = self
+            .properties
+            .keys()
+            .filter(|key| !key.starts_with(SYMBOL_KEY_PREFIX))
+            .map(|key| (array_index(key).unwrap_or(u32::MAX), key))
+            .partition(|(index, _)| *index != u32::MAX);
+        indices.sort_unstable_by_key(|(index, _)| *index);
+        indices
+            .into_iter()
+            .chain(names)
+            .map(|(_, key)| key.clone())
+            .collect()
+    }
+
+    /// Get own and inherited property names, own properties first
+    ///
+    /// Follows both the owned prototype chain and a shared prototype linked
+    /// with `set_prototype_value`. Shadowed names appear once.
+    pub fn keys_with_prototypes(&self) -> Vec<String> {
+        let mut keys = self.keys();
+        let inherited = self
+            .prototype()
+            .map(GCObject::keys_with_prototypes)
+            .or_else(|| self.shared_prototype().map(|proto| proto.keys_with_prototypes()))
+            .unwrap_or_default();
+        for key in inherited {
+            if !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+        keys
     }
 
     /// Get a reference to the prototype object
@@ -169,6 +310,36 @@ impl GCObject {
         self.prototype = Some(Box::new(prototype));
     }
 
+    /// Link a shared prototype object
+    ///
+    /// Unlike `set_prototype`, the prototype is held by reference, so later
+    /// changes to it are visible through this object.
+    ///
+    /// # Arguments
+    ///
+    /// * `prototype` - A `Value::NativeObject` wrapping a GCObject
+    pub fn set_prototype_value(&mut self, prototype: Value) {
+        self.prototype_value = Some(prototype);
+    }
+
+    /// Get the shared prototype object, if one is linked
+    pub fn prototype_value(&self) -> Option<&Value> {
+        self.prototype_value.as_ref()
+    }
+
+    /// Borrow the GCObject behind the shared prototype
+    fn shared_prototype(&self) -> Option<std::cell::Ref<'_, GCObject>> {
+        match self.prototype_value {
+            Some(Value::NativeObject(ref obj)) => {
+                std::cell::Ref::filter_map(obj.try_borrow().ok()?, |any| {
+                    any.downcast_ref::<Box<dyn Any>>()?.downcast_ref::<GCObject>()
+                })
+                .ok()
+            }
+            _ => None,
+        }
+    }
+
     /// Remove a property from the object
     ///
     /// # Arguments
@@ -179,7 +350,34 @@ impl GCObject {
     ///
     /// `true` if the property was removed, `false` if it didn't exist
     pub fn delete(&mut self, key: &str) -> bool {
-        self.properties.remove(key).is_some()
+        if self.properties.shift_remove(key).is_none() {
+            return false;
+        }
+        self.accessors.remove(key);
+        self.shape = self.properties.keys().fold(EMPTY_SHAPE, |shape, key| next_shape(shape, key));
+        true
+    }
+
+    /// Identify the layout of the own properties
+    ///
+    /// Objects that gained the same keys in the same order share a shape, so
+    /// a key's offset is the same in all of them. Values do not affect it.
+    pub fn shape(&self) -> ShapeId {
+        self.shape
+    }
+
+    /// Offset of own property `key` among the own properties
+    pub fn property_offset(&self, key: &str) -> Option<u32> {
+        self.properties.get_index_of(key).map(|offset| offset as u32)
+    }
+
+    /// Own property at `offset`, provided it is named `key`
+    ///
+    /// The name check keeps a stale inline cache entry from reading the
+    /// wrong property should two layouts ever share a shape.
+    pub fn property_at(&self, offset: u32, key: &str) -> Option<Value> {
+        let (name, value) = self.properties.get_index(offset as usize)?;
+        (name == key).then(|| value.clone())
     }
 
     /// Get the number of own properties
@@ -198,6 +396,64 @@ impl GCObject {
     }
 }
 
+/// The array index a property key names, if any
+///
+/// Array indices are the canonical decimal strings of 0 through 2^32 - 2.
+fn array_index(key: &str) -> Option<u32> {
+    let index: u32 = key.parse().ok()?;
+    (index != u32::MAX && index.to_string() == key).then_some(index)
+}
+
+/// Object inspector for `Value::inspect`
+///
+/// Renders GC objects with a numeric `length` as arrays, reading no more
+/// than [`MAX_ARRAY_ITEMS`] elements, and everything else as plain objects
+/// with their own properties in [`GCObject::keys`] order.
+pub fn inspect_gc_object(payload: &dyn Any) -> Option<ObjectView> {
+    let object = payload.downcast_ref::<Box<dyn Any>>()?.downcast_ref::<GCObject>()?;
+    if let Value::Smi(len) = object.get("length") {
+        let length = len.max(0) as usize;
+        let elements = (0..length.min(MAX_ARRAY_ITEMS))
+            .map(|i| object.get(&i.to_string()))
+            .collect();
+        return Some(ObjectView::Array { elements, length });
+    }
+    let entries = object
+        .keys()
+        .into_iter()
+        .map(|key| {
+            let value = object.get(&key);
+            (key, value)
+        })
+        .collect();
+    Some(ObjectView::Object(entries))
+}
+
+/// Create a GCObject on `heap` from `view`: the inverse of [`inspect_gc_object`]
+///
+/// Registered as the thread's [`core_types::ObjectBuilder`] by the dispatcher.
+pub fn build_gc_object(heap: &VMHeap, view: ObjectView) -> Value {
+    let object = match view {
+        ObjectView::Object(entries) => {
+            let mut object = heap.create_object();
+            for (key, value) in entries {
+                object.set(key, value);
+            }
+            object
+        }
+        ObjectView::Array { elements, length } => {
+            let mut array = heap.create_array();
+            for (index, value) in elements.into_iter().enumerate() {
+                array.set(index.to_string(), value);
+            }
+            array.set("length".to_string(), Value::Smi(length as i32));
+            array
+        }
+    };
+    let boxed: Box<dyn Any> = Box::new(object);
+    Value::NativeObject(Rc::new(RefCell::new(boxed)) as Rc<RefCell<dyn Any>>)
+}
+
 /// Heap wrapper for the VM
 ///
 /// Provides a simplified interface for creating and managing
@@ -205,6 +461,8 @@ impl GCObject {
 pub struct VMHeap {
     /// Shared heap reference
     heap: Rc<RefCell<Heap>>,
+    /// `Array.prototype` of the running realm, linked to each new array
+    array_prototype: RefCell<Option<Value>>,
 }
 
 impl VMHeap {
@@ -214,6 +472,7 @@ impl VMHeap {
     pub fn new() -> Self {
         Self {
             heap: Rc::new(RefCell::new(Heap::new())),
+            array_prototype: RefCell::new(None),
         }
     }
 
@@ -229,6 +488,7 @@ impl VMHeap {
                 young_gen_size,
                 promotion_threshold,
             ))),
+            array_prototype: RefCell::new(None),
         }
     }
 
@@ -237,6 +497,26 @@ impl VMHeap {
         GCObject::new(Rc::clone(&self.heap))
     }
 
+    /// Create a new empty GC-managed array
+    ///
+    /// The array inherits from the `Array.prototype` set with
+    /// `set_array_prototype`, if any.
+    pub fn create_array(&self) -> GCObject {
+        let mut array = GCObject::new_array(Rc::clone(&self.heap));
+        if let Some(prototype) = self.array_prototype.borrow().clone() {
+            array.set_prototype_value(prototype);
+        }
+        array
+    }
+
+    /// Set the `Array.prototype` new arrays inherit from
+    ///
+    /// Realms share one heap, so the dispatcher updates this whenever it
+    /// enters another realm.
+    pub fn set_array_prototype(&self, prototype: Option<Value>) {
+        *self.array_prototype.borrow_mut() = prototype;
+    }
+
     /// Create a new GC-managed object with a prototype
     ///
     /// # Arguments
@@ -343,6 +623,24 @@ mod tests {
         assert_eq!(obj.get("z"), Value::Undefined);
     }
 
+    #[test]
+    fn test_gc_object_accessor_halves_merge() {
+        let heap = VMHeap::new();
+        let mut obj = heap.create_object();
+
+        obj.define_accessor("v".to_string(), Some(Value::HeapObject(1)), None);
+        obj.define_accessor("v".to_string(), None, Some(Value::HeapObject(2)));
+        let accessor = obj.accessor("v").unwrap();
+        assert_eq!(accessor.get, Some(Value::HeapObject(1)));
+        assert_eq!(accessor.set, Some(Value::HeapObject(2)));
+        assert_eq!(obj.keys(), vec!["v".to_string()]);
+
+        // A data store replaces the accessor
+        obj.set("v".to_string(), Value::Smi(3));
+        assert!(obj.accessor("v").is_none());
+        assert_eq!(obj.get("v"), Value::Smi(3));
+    }
+
     #[test]
     fn test_gc_object_has_property() {
         let heap = VMHeap::new();
@@ -372,6 +670,37 @@ mod tests {
         assert!(keys.contains(&"c".to_string()));
     }
 
+    #[test]
+    fn test_gc_object_keys_follow_property_order() {
+        let heap = VMHeap::new();
+        let mut obj = heap.create_object();
+        for key in ["zeta", "10", "alpha", "2", "mid"] {
+            obj.set(key.to_string(), Value::Smi(0));
+        }
+        obj.delete("alpha");
+        obj.set("alpha".to_string(), Value::Smi(1));
+
+        assert_eq!(obj.keys(), ["2", "10", "zeta", "mid", "alpha"]);
+    }
+
+    #[test]
+    fn test_inspect_reads_at_most_max_array_items() {
+        let heap = VMHeap::new();
+        let mut array = heap.create_array();
+        array.set("length".to_string(), Value::Smi(i32::MAX));
+        array.set("0".to_string(), Value::Smi(7));
+
+        let payload: Box<dyn Any> = Box::new(array);
+        match inspect_gc_object(&payload) {
+            Some(ObjectView::Array { elements, length }) => {
+                assert_eq!(elements.len(), MAX_ARRAY_ITEMS);
+                assert_eq!(elements[0], Value::Smi(7));
+                assert_eq!(length, i32::MAX as usize);
+            }
+            other => panic!("expected an array view, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_gc_object_delete() {
         let heap = VMHeap::new();
@@ -0,0 +1,82 @@
+//! Generator objects and their suspended frames
+
+use crate::context::ExecutionContext;
+use crate::dispatch::TryHandler;
+use crate::upvalue::UpvalueHandle;
+use core_types::Value;
+use std::collections::{HashMap, VecDeque};
+
+/// Where a generator is in its lifecycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeneratorState {
+    /// Created but not yet started
+    SuspendedStart,
+    /// Paused at a `yield`
+    SuspendedYield,
+    /// Currently running
+    Executing,
+    /// Returned or threw; every further `next` reports done
+    Completed,
+}
+
+/// How a suspended generator is resumed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResumeMode {
+    /// `next(value)`: the paused `yield` evaluates to the value
+    Next,
+    /// `return(value)`: the generator completes with the value
+    Return,
+    /// `throw(value)`: the value is thrown at the paused `yield`
+    Throw,
+}
+
+/// The state a generator keeps between resumptions
+#[derive(Debug, Clone)]
+pub(crate) struct GeneratorFrame {
+    /// Registers and instruction pointer of the generator body
+    pub(crate) ctx: ExecutionContext,
+    /// Operand stack values pushed by the body and still live at the yield
+    pub(crate) stack: Vec<Value>,
+    /// Try handlers opened by the body, with stack heights relative to the
+    /// start of `stack`
+    pub(crate) try_handlers: Vec<TryHandler>,
+    /// The closure's captured upvalues
+    pub(crate) upvalues: Vec<UpvalueHandle>,
+    /// Upvalues still open over the body's registers
+    pub(crate) open_upvalues: HashMap<usize, UpvalueHandle>,
+    /// `this` for the body, if it was called as a method
+    pub(crate) this_value: Option<Value>,
+    /// Paused inside `yield*`, so resumption is forwarded to the delegate
+    pub(crate) delegating: bool,
+    /// `return()` value held while a finally block that yielded runs
+    pub(crate) pending_return: Option<Value>,
+    /// Exception held while a finally block that yielded runs
+    pub(crate) pending_exception: Option<Value>,
+}
+
+/// A generator object returned by calling a generator function
+#[derive(Debug)]
+pub struct GeneratorObject {
+    /// Lifecycle state
+    pub state: GeneratorState,
+    /// The suspended body; `None` while executing or once completed
+    pub(crate) frame: Option<GeneratorFrame>,
+}
+
+impl GeneratorObject {
+    /// Create a generator suspended at the start of its body
+    pub(crate) fn new(frame: GeneratorFrame) -> Self {
+        Self {
+            state: GeneratorState::SuspendedStart,
+            frame: Some(frame),
+        }
+    }
+}
+
+/// Iterator over a snapshot of an iterable's values, used to delegate to
+/// arrays and strings with `yield*`
+#[derive(Debug, Clone)]
+pub(crate) struct ListIterator {
+    /// The values still to produce
+    pub(crate) values: VecDeque<Value>,
+}
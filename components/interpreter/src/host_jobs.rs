@@ -0,0 +1,260 @@
+//! Host-scheduled jobs: `queueMicrotask` callbacks and timers
+//!
+//! Microtasks go on the async runtime's job queue, shared with promise jobs,
+//! so the two interleave in the order they were queued. Timers go on the
+//! event loop's timer heap, which embedders share through
+//! [`HostJobs::event_loop`], so script and host timers fire in one deadline
+//! order. Running a microtask or timer only hands its callback back here;
+//! the dispatcher then calls it.
+
+use async_runtime::{EventLoop, MicroTask, Task, TimerId};
+use core_types::{JsError, StackFrame, Value};
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::rc::Rc;
+use std::time::Duration;
+
+/// A callback waiting to be invoked, with the arguments to pass it
+#[derive(Debug, Clone)]
+pub struct HostJob {
+    /// The function to call
+    pub callback: Value,
+    /// Arguments for the call
+    pub args: Vec<Value>,
+    /// Name of the host function that scheduled the job
+    pub name: &'static str,
+    /// Script location that scheduled the job, reported as its async parent
+    pub async_parent: StackFrame,
+}
+
+/// Pending microtasks and timers scheduled by scripts
+#[derive(Debug, Default)]
+pub struct HostJobs {
+    /// Runs the shared microtask queue one job at a time and holds the timers
+    /// of scripts and embedders alike
+    event_loop: EventLoop,
+    /// Callbacks whose microtask or timer has run, waiting for the dispatcher
+    ready: Rc<RefCell<VecDeque<HostJob>>>,
+    /// Event loop timers of armed script timers, keyed by script timer ID
+    timers: HashMap<u32, TimerId>,
+    /// Last timer ID handed out; IDs start at 1
+    next_timer_id: u32,
+}
+
+impl HostJobs {
+    /// Create empty job queues
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The event loop holding script timers, for embedders to schedule their own work on
+    pub fn event_loop(&mut self) -> &mut EventLoop {
+        &mut self.event_loop
+    }
+
+    /// Queue a callback to run once the current task has finished
+    pub fn enqueue_microtask(&mut self, job: HostJob) {
+        let ready = Rc::clone(&self.ready);
+        let (name, parent) = (job.name, job.async_parent.clone());
+        let task = MicroTask::new(move || {
+            ready.borrow_mut().push_back(job);
+            Ok(Value::Undefined)
+        });
+        self.event_loop
+            .enqueue_microtask(task.with_name(name).with_async_parent(parent));
+    }
+
+    /// Take the next queued microtask callback, if any
+    ///
+    /// Promise jobs and other microtasks queued ahead of it run first, in
+    /// queue order. An error thrown by one of those is returned in place of
+    /// a callback.
+    pub fn next_microtask(&mut self) -> Option<Result<HostJob, JsError>> {
+        loop {
+            if let Some(job) = self.ready.borrow_mut().pop_front() {
+                return Some(Ok(job));
+            }
+            match self.event_loop.run_next_microtask() {
+                Ok(true) => {}
+                Ok(false) => return None,
+                Err(err) => return Some(Err(err)),
+            }
+        }
+    }
+
+    /// Arm a timer that fires after `delay`, returning its ID
+    pub fn set_timer(&mut self, job: HostJob, delay: Duration) -> u32 {
+        let id = self.allocate_timer_id();
+        let ready = Rc::clone(&self.ready);
+        let (name, parent) = (job.name, job.async_parent.clone());
+        let task = Task::new(move || {
+            ready.borrow_mut().push_back(job);
+            Ok(Value::Undefined)
+        });
+        let timer = self
+            .event_loop
+            .set_timeout(delay, task.with_name(name).with_async_parent(parent));
+        self.timers.insert(id, timer);
+        id
+    }
+
+    /// Disarm a timer; unknown or already-fired IDs are ignored
+    pub fn clear_timer(&mut self, id: u32) {
+        if let Some(timer) = self.timers.remove(&id) {
+            self.event_loop.clear_timeout(timer);
+        }
+    }
+
+    /// Take the earliest script timer's callback, waiting until it is due
+    ///
+    /// Embedder timers due before it run first, in deadline order. An error
+    /// thrown by one of those is returned in place of a callback.
+    pub fn next_timer(&mut self) -> Option<Result<HostJob, JsError>> {
+        loop {
+            if let Some(job) = self.ready.borrow_mut().pop_front() {
+                return Some(Ok(job));
+            }
+            match self.event_loop.run_next_timer() {
+                Ok(Some(fired)) => self.timers.retain(|_, timer| *timer != fired),
+                Ok(None) => return None,
+                Err(err) => return Some(Err(err)),
+            }
+        }
+    }
+
+    /// Check whether any microtask or timer is pending
+    pub fn is_empty(&self) -> bool {
+        self.ready.borrow().is_empty()
+            && self.event_loop.is_microtask_queue_empty()
+            && self.timers.is_empty()
+    }
+
+    fn allocate_timer_id(&mut self) -> u32 {
+        // IDs stay within Smi range so scripts see them as integers
+        self.next_timer_id = self.next_timer_id % i32::MAX as u32 + 1;
+        self.next_timer_id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(tag: &str) -> HostJob {
+        HostJob {
+            callback: Value::NativeFunction(tag.to_string()),
+            args: vec![],
+            name: "test",
+            async_parent: StackFrame {
+                function_name: None,
+                source_url: None,
+                line: 0,
+                column: 0,
+            },
+        }
+    }
+
+    fn tag(job: HostJob) -> String {
+        match job.callback {
+            Value::NativeFunction(name) => name,
+            other => panic!("unexpected callback {:?}", other),
+        }
+    }
+
+    fn next_tag(jobs: &mut HostJobs) -> Option<String> {
+        jobs.next_microtask().map(|job| tag(job.unwrap()))
+    }
+
+    fn next_timer_tag(jobs: &mut HostJobs) -> Option<String> {
+        jobs.next_timer().map(|job| tag(job.unwrap()))
+    }
+
+    #[test]
+    fn test_microtasks_run_in_fifo_order() {
+        let mut jobs = HostJobs::new();
+        jobs.enqueue_microtask(job("a"));
+        jobs.enqueue_microtask(job("b"));
+
+        assert_eq!(next_tag(&mut jobs).as_deref(), Some("a"));
+        assert_eq!(next_tag(&mut jobs).as_deref(), Some("b"));
+        assert!(jobs.next_microtask().is_none());
+        assert!(jobs.is_empty());
+    }
+
+    #[test]
+    fn test_microtasks_interleave_with_promise_jobs() {
+        use async_runtime::{Function, Promise};
+
+        let reactions = Rc::new(RefCell::new(Vec::new()));
+        let promise = Rc::new(RefCell::new(Promise::new()));
+        promise.borrow_mut().resolve(Value::Smi(1));
+
+        let mut jobs = HostJobs::new();
+        jobs.enqueue_microtask(job("a"));
+        let seen = Rc::clone(&reactions);
+        let on_fulfilled = Function::new(move |args| {
+            seen.borrow_mut().push(args[0].clone());
+            Ok(Value::Undefined)
+        });
+        Promise::then_shared(&promise, Some(on_fulfilled), None);
+        jobs.enqueue_microtask(job("c"));
+
+        assert_eq!(next_tag(&mut jobs).as_deref(), Some("a"));
+        assert!(reactions.borrow().is_empty());
+        assert_eq!(next_tag(&mut jobs).as_deref(), Some("c"));
+        assert_eq!(*reactions.borrow(), vec![Value::Smi(1)]);
+    }
+
+    #[test]
+    fn test_timers_fire_in_due_order() {
+        let mut jobs = HostJobs::new();
+        jobs.set_timer(job("later"), Duration::from_millis(5));
+        jobs.set_timer(job("first"), Duration::ZERO);
+        jobs.set_timer(job("second"), Duration::ZERO);
+
+        assert_eq!(next_timer_tag(&mut jobs).unwrap(), "first");
+        assert_eq!(next_timer_tag(&mut jobs).unwrap(), "second");
+        assert_eq!(next_timer_tag(&mut jobs).unwrap(), "later");
+        assert!(jobs.next_timer().is_none());
+    }
+
+    #[test]
+    fn test_cleared_timer_does_not_fire() {
+        let mut jobs = HostJobs::new();
+        let id = jobs.set_timer(job("cancelled"), Duration::ZERO);
+        jobs.set_timer(job("kept"), Duration::ZERO);
+        jobs.clear_timer(id);
+
+        assert_eq!(next_timer_tag(&mut jobs).unwrap(), "kept");
+        assert!(jobs.is_empty());
+    }
+
+    #[test]
+    fn test_embedder_timers_share_the_timer_heap() {
+        let fired = Rc::new(RefCell::new(Vec::new()));
+        let mut jobs = HostJobs::new();
+        jobs.set_timer(job("script"), Duration::from_millis(10));
+        let log = Rc::clone(&fired);
+        let host = Task::new(move || {
+            log.borrow_mut().push("host");
+            Ok(Value::Undefined)
+        });
+        jobs.event_loop().set_timeout(Duration::ZERO, host);
+        let failing = Task::new(|| {
+            Err(JsError {
+                kind: core_types::ErrorKind::TypeError,
+                message: "host failed".to_string(),
+                stack: vec![],
+                source_position: None,
+            })
+        });
+        jobs.event_loop().set_timeout(Duration::from_millis(1), failing);
+
+        let err = jobs.next_timer().unwrap().unwrap_err();
+        assert_eq!(err.message, "host failed");
+        assert_eq!(*fired.borrow(), vec!["host"]);
+        assert_eq!(next_timer_tag(&mut jobs).as_deref(), Some("script"));
+        assert!(jobs.next_timer().is_none());
+        assert_eq!(jobs.event_loop().pending_timer_count(), 0);
+    }
+}
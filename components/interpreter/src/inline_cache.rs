@@ -3,6 +3,10 @@
 //! Provides mono/poly/megamorphic caching states for fast property lookups.
 
 use arrayvec::ArrayVec;
+use bytecode_system::{BytecodeChunk, Opcode};
+use core_types::{InternedString, Interner};
+use std::cell::RefCell;
+use std::rc::Rc;
 
 /// Shape identifier for objects (hidden class ID)
 pub type ShapeId = usize;
@@ -112,6 +116,79 @@ impl Default for InlineCache {
     }
 }
 
+/// Inline cache of one `LoadProperty` instruction
+#[derive(Debug, Clone, PartialEq)]
+pub struct PropertySlot {
+    name: InternedString,
+    cache: RefCell<InlineCache>,
+}
+
+impl PropertySlot {
+    /// The property name the instruction reads, interned when its chunk was loaded
+    pub fn name(&self) -> InternedString {
+        self.name
+    }
+
+    /// Current state of the instruction's cache
+    pub fn cache(&self) -> InlineCache {
+        self.cache.borrow().clone()
+    }
+
+    /// Look up the cached offset of the property for objects with `shape`
+    pub fn lookup(&self, shape: ShapeId) -> Option<u32> {
+        self.cache.borrow().lookup(shape)
+    }
+
+    /// Record the offset of the property for objects with `shape`
+    pub fn update(&self, shape: ShapeId, offset: u32) {
+        self.cache.borrow_mut().update(shape, offset);
+    }
+}
+
+/// Inline cache slots of a chunk, one per instruction
+///
+/// Built when a chunk is loaded, interning the name of each `LoadProperty`
+/// once. Clones share the slots, so every call of a function warms the same
+/// caches, and a property read finds its cache by instruction offset without
+/// hashing the name.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PropertySlots {
+    slots: Rc<[Option<PropertySlot>]>,
+}
+
+impl PropertySlots {
+    /// Create the slots of `chunk`, interning its property names in `interner`
+    pub fn new(chunk: &BytecodeChunk, interner: &mut Interner) -> Self {
+        let slots = chunk
+            .instructions
+            .iter()
+            .map(|inst| match &inst.opcode {
+                Opcode::LoadProperty(name) => Some(PropertySlot {
+                    name: interner.intern(name),
+                    cache: RefCell::new(InlineCache::new()),
+                }),
+                _ => None,
+            })
+            .collect();
+        Self { slots }
+    }
+
+    /// The slot of the instruction at `offset`, if it reads a property
+    pub fn get(&self, offset: usize) -> Option<&PropertySlot> {
+        self.slots.get(offset)?.as_ref()
+    }
+
+    /// Number of instructions covered
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Check whether no instructions are covered
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -27,22 +27,34 @@
 #![warn(missing_docs)]
 #![warn(clippy::all)]
 
+pub mod bound_function;
 pub mod call_frame;
+pub mod clock;
 pub mod context;
+pub mod debugger;
 pub mod dispatch;
 pub mod gc_integration;
+pub mod generator;
+pub mod host_jobs;
 pub mod inline_cache;
+pub mod private_fields;
 pub mod profile;
 pub mod promise_integration;
 pub mod upvalue;
 pub mod vm;
 
 // Re-export main types at crate root
+pub use bound_function::BoundFunction;
 pub use call_frame::CallFrame;
+pub use clock::{Clock, ManualClock, PerformanceObject, SeededRandom, SystemClock};
 pub use context::ExecutionContext;
-pub use gc_integration::{GCObject, VMHeap};
-pub use inline_cache::{InlineCache, ShapeId};
+pub use debugger::{DebuggerHook, FrameSnapshot};
+pub use gc_integration::{Accessor, GCObject, VMHeap};
+pub use generator::{GeneratorObject, GeneratorState, ResumeMode};
+pub use host_jobs::{HostJob, HostJobs};
+pub use inline_cache::{InlineCache, PropertySlot, PropertySlots, ShapeId};
+pub use private_fields::PrivateFieldTable;
 pub use profile::{BranchOutcome, ProfileData, TypeInfo};
 pub use promise_integration::{PromiseConstructor, PromiseObject};
 pub use upvalue::{Closure, Upvalue, UpvalueHandle};
-pub use vm::VM;
+pub use vm::{RealmId, VM};
@@ -0,0 +1,142 @@
+//! Storage for class private fields (`#name`)
+//!
+//! Private fields live in a side table keyed by object identity rather than
+//! on the object itself, so they never show up in property enumeration. Each
+//! entry holds only a weak reference to its object and goes away with it.
+
+use core_types::Value;
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::{Rc, Weak};
+
+/// Private fields of one object
+#[derive(Debug)]
+struct PrivateEntry {
+    owner: Weak<RefCell<dyn Any>>,
+    fields: HashMap<String, Value>,
+}
+
+impl PrivateEntry {
+    fn is_alive(&self) -> bool {
+        self.owner.strong_count() > 0
+    }
+}
+
+/// Side table mapping objects to their private fields, weakly keyed by identity
+#[derive(Debug, Default)]
+pub struct PrivateFieldTable {
+    entries: HashMap<usize, PrivateEntry>,
+}
+
+impl PrivateFieldTable {
+    /// Create an empty table
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn identity(object: &Rc<RefCell<dyn Any>>) -> usize {
+        Rc::as_ptr(object) as *const () as usize
+    }
+
+    /// Entry for `object`, ignoring one left behind by a collected object at the same address
+    fn entry(&self, object: &Rc<RefCell<dyn Any>>) -> Option<&PrivateEntry> {
+        self.entries
+            .get(&Self::identity(object))
+            .filter(|entry| entry.is_alive())
+    }
+
+    /// Read private field `key`, or `None` if `object` does not have it
+    pub fn get(&self, object: &Rc<RefCell<dyn Any>>, key: &str) -> Option<Value> {
+        self.entry(object)?.fields.get(key).cloned()
+    }
+
+    /// Check whether `object` has private field `key`
+    pub fn has(&self, object: &Rc<RefCell<dyn Any>>, key: &str) -> bool {
+        self.entry(object)
+            .is_some_and(|entry| entry.fields.contains_key(key))
+    }
+
+    /// Overwrite existing private field `key`; returns false if `object` does not have it
+    pub fn set(&mut self, object: &Rc<RefCell<dyn Any>>, key: &str, value: Value) -> bool {
+        if !self.has(object, key) {
+            return false;
+        }
+        if let Some(entry) = self.entries.get_mut(&Self::identity(object)) {
+            entry.fields.insert(key.to_string(), value);
+        }
+        true
+    }
+
+    /// Add private field `key` to `object`; returns false if it is already present
+    pub fn define(&mut self, object: &Rc<RefCell<dyn Any>>, key: &str, value: Value) -> bool {
+        if self.has(object, key) {
+            return false;
+        }
+        let identity = Self::identity(object);
+        if !self.entries.get(&identity).is_some_and(PrivateEntry::is_alive) {
+            // A new object: drop entries whose objects have been collected
+            self.entries.retain(|_, entry| entry.is_alive());
+            self.entries.insert(
+                identity,
+                PrivateEntry {
+                    owner: Rc::downgrade(object),
+                    fields: HashMap::new(),
+                },
+            );
+        }
+        if let Some(entry) = self.entries.get_mut(&identity) {
+            entry.fields.insert(key.to_string(), value);
+        }
+        true
+    }
+
+    /// Number of objects with private fields still tracked
+    pub fn len(&self) -> usize {
+        self.entries.values().filter(|entry| entry.is_alive()).count()
+    }
+
+    /// Check whether no live object has private fields
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn object() -> Rc<RefCell<dyn Any>> {
+        Rc::new(RefCell::new(0u8))
+    }
+
+    #[test]
+    fn test_fields_are_keyed_by_identity() {
+        let mut table = PrivateFieldTable::new();
+        let a = object();
+        let b = object();
+
+        assert!(table.define(&a, "#x@0", Value::Smi(1)));
+        assert!(!table.define(&a, "#x@0", Value::Smi(2)));
+        assert_eq!(table.get(&a, "#x@0"), Some(Value::Smi(1)));
+        assert_eq!(table.get(&b, "#x@0"), None);
+        assert!(!table.set(&b, "#x@0", Value::Smi(3)));
+        assert!(table.set(&a, "#x@0", Value::Smi(4)));
+        assert_eq!(table.get(&a, "#x@0"), Some(Value::Smi(4)));
+    }
+
+    #[test]
+    fn test_entries_are_dropped_with_their_object() {
+        let mut table = PrivateFieldTable::new();
+        let a = object();
+        table.define(&a, "#x@0", Value::Smi(1));
+        assert_eq!(table.len(), 1);
+
+        drop(a);
+        assert!(table.is_empty());
+
+        let b = object();
+        table.define(&b, "#y@0", Value::Smi(2));
+        assert_eq!(table.entries.len(), 1);
+    }
+}
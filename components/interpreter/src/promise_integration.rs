@@ -37,12 +37,20 @@ impl PromiseObject {
         Self { promise }
     }
 
+    /// Create a Promise rejected with a JavaScript value as its reason
+    pub fn reject_with_reason(reason: Value) -> Self {
+        let mut promise = Promise::new();
+        promise.reject_with_reason(reason);
+        Self { promise }
+    }
+
     /// Get the current state of the Promise
     pub fn state(&self) -> &PromiseState {
         &self.promise.state
     }
 
-    /// Get the resolved value (if fulfilled)
+    /// Get the resolved value (if fulfilled) or the rejection reason, when
+    /// the Promise was rejected with one
     pub fn value(&self) -> Option<&Value> {
         self.promise.result.as_ref()
     }
@@ -86,6 +94,12 @@ impl PromiseConstructor {
         Value::NativeObject(Rc::new(RefCell::new(promise_obj)) as Rc<RefCell<dyn Any>>)
     }
 
+    /// Create a new Promise rejected with the given reason
+    pub fn reject_with_reason(reason: Value) -> Value {
+        let promise_obj = PromiseObject::reject_with_reason(reason);
+        Value::NativeObject(Rc::new(RefCell::new(promise_obj)) as Rc<RefCell<dyn Any>>)
+    }
+
     /// Create a new pending Promise
     pub fn new_pending() -> Value {
         let promise_obj = PromiseObject::new();
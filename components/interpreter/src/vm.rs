@@ -2,16 +2,33 @@
 //!
 //! Main entry point for executing JavaScript bytecode.
 
-use bytecode_system::BytecodeChunk;
-use core_types::{JsError, Value};
+use async_runtime::EventLoop;
+use builtins::console::ConsoleObserver;
+use bytecode_system::{BytecodeChunk, InstructionCounters};
+use core_types::{ErrorKind, Interner, JsError, Value};
 use std::collections::HashMap;
+use std::rc::Rc;
 
 use crate::call_frame::CallFrame;
-use crate::context::ExecutionContext;
+use crate::clock::Clock;
+use crate::debugger::DebuggerHook;
 use crate::dispatch::Dispatcher;
 use crate::gc_integration::VMHeap;
+use crate::inline_cache::PropertySlot;
 use crate::profile::ProfileData;
 
+/// Identifies a realm: a global object with its own set of builtins
+///
+/// Every VM starts in [`RealmId::DEFAULT`]; more are made with
+/// [`VM::create_realm`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RealmId(usize);
+
+impl RealmId {
+    /// The realm a VM is created in
+    pub const DEFAULT: RealmId = RealmId(0);
+}
+
 /// Virtual Machine for executing JavaScript bytecode
 ///
 /// The VM manages the execution state including:
@@ -41,6 +58,10 @@ pub struct VM {
     jit_threshold: u64,
     /// Number of calls before optimizing JIT compilation should be considered
     opt_threshold: u64,
+    /// Whether executed chunks carry per-instruction counters
+    profiling: bool,
+    /// Top-level chunks executed while profiling, with their counters attached
+    profiled_chunks: Vec<BytecodeChunk>,
 }
 
 impl std::fmt::Debug for VM {
@@ -53,6 +74,7 @@ impl std::fmt::Debug for VM {
             .field("execution_counts", &self.execution_counts)
             .field("jit_threshold", &self.jit_threshold)
             .field("opt_threshold", &self.opt_threshold)
+            .field("profiling", &self.profiling)
             .finish()
     }
 }
@@ -76,6 +98,8 @@ impl VM {
             profile_data: HashMap::new(),
             jit_threshold: 100,   // Baseline JIT after 100 calls
             opt_threshold: 10000, // Optimizing JIT after 10,000 calls
+            profiling: false,
+            profiled_chunks: Vec::new(),
         }
     }
 
@@ -88,7 +112,10 @@ impl VM {
     /// # Returns
     ///
     /// The index (function ID) that can be used with CreateClosure
-    pub fn register_function(&mut self, chunk: BytecodeChunk) -> usize {
+    pub fn register_function(&mut self, mut chunk: BytecodeChunk) -> usize {
+        if self.profiling {
+            chunk.enable_counters();
+        }
         let idx = self.functions.len();
         self.functions.push(chunk);
         idx
@@ -127,7 +154,7 @@ impl VM {
         // This allows CreateClosure to find the function bytecode
         let base_idx = self.functions.len();
         for nested_fn in chunk.nested_functions() {
-            self.functions.push(nested_fn.clone());
+            self.register_function(nested_fn.clone());
         }
 
         // Adjust closure indices in the chunk if needed
@@ -147,10 +174,67 @@ impl VM {
             }
         }
 
-        let mut ctx = ExecutionContext::new(adjusted_chunk);
+        if self.profiling {
+            adjusted_chunk.counters = Some(self.top_level_counters(chunk));
+        }
+
+        let mut ctx = self.dispatcher.load_chunk(adjusted_chunk);
         self.dispatcher.execute(&mut ctx, &self.functions)
     }
 
+    /// Count how often each instruction runs in chunks executed from now on
+    ///
+    /// Already-registered functions start counting too. Execution without
+    /// profiling carries no counters and pays no counting cost.
+    pub fn enable_profiling(&mut self) {
+        self.profiling = true;
+        for function in &mut self.functions {
+            function.enable_counters();
+        }
+    }
+
+    /// Get `(offset, count)` for every instruction of `chunk`
+    ///
+    /// Counts from every executed or registered copy of the chunk are summed.
+    /// Instructions that never ran under profiling report a count of 0.
+    pub fn instruction_profile(&self, chunk: &BytecodeChunk) -> Vec<(usize, u64)> {
+        let mut profile: Vec<(usize, u64)> = (0..chunk.instruction_count())
+            .map(|offset| (offset, 0))
+            .collect();
+        let copies = self
+            .profiled_chunks
+            .iter()
+            .chain(&self.functions)
+            .filter(|c| *c == chunk);
+        for counters in copies.filter_map(|c| c.counters.as_ref()) {
+            for (offset, count) in profile.iter_mut() {
+                *count += counters.get(*offset);
+            }
+        }
+        profile
+    }
+
+    /// Get the counters for a top-level chunk, reusing them when it runs again
+    fn top_level_counters(&mut self, chunk: &BytecodeChunk) -> InstructionCounters {
+        if let Some(counters) = self
+            .profiled_chunks
+            .iter()
+            .find(|c| *c == chunk)
+            .and_then(|c| c.counters.clone())
+        {
+            return counters;
+        }
+
+        let mut profiled = chunk.clone();
+        profiled.enable_counters();
+        let counters = profiled
+            .counters
+            .clone()
+            .expect("counters were just enabled");
+        self.profiled_chunks.push(profiled);
+        counters
+    }
+
     /// Execute a registered function by index
     ///
     /// This method:
@@ -181,6 +265,37 @@ impl VM {
         }
     }
 
+    /// Run microtasks and timers scheduled by executed scripts until none remain
+    ///
+    /// Errors thrown by these jobs do not stop the drain; collect them with
+    /// [`VM::take_uncaught_errors`].
+    pub fn run_host_jobs(&mut self) {
+        self.dispatcher.run_host_jobs(&self.functions);
+    }
+
+    /// Take the errors thrown by host jobs since the last call
+    pub fn take_uncaught_errors(&mut self) -> Vec<JsError> {
+        self.dispatcher.take_uncaught_errors()
+    }
+
+    /// The event loop that script timers are scheduled on
+    ///
+    /// Timers an embedder sets here fire in deadline order with script
+    /// timers during [`VM::run_host_jobs`].
+    pub fn event_loop(&mut self) -> &mut EventLoop {
+        self.dispatcher.event_loop()
+    }
+
+    /// Replace the time source behind `performance.now()` and `Date.now()`
+    pub fn set_clock(&mut self, clock: impl Clock + 'static) {
+        self.dispatcher.set_clock(Box::new(clock));
+    }
+
+    /// Make `Math.random()` return the same sequence on every run with `seed`
+    pub fn set_random_seed(&mut self, seed: u64) {
+        self.dispatcher.set_random_seed(seed);
+    }
+
     /// Get a global variable by name
     ///
     /// # Arguments
@@ -205,6 +320,78 @@ impl VM {
         self.dispatcher.set_global(name, value);
     }
 
+    /// Create a realm with its own globals and builtins
+    ///
+    /// Realms share this VM's heap and function registry, so objects can be
+    /// passed between them, but each has distinct intrinsics such as
+    /// `Object.prototype` and constructors that are distinct values. Scripts
+    /// run in the entered realm, and functions run with the globals of the
+    /// realm they were created in, whichever realm calls them.
+    pub fn create_realm(&mut self) -> RealmId {
+        RealmId(self.dispatcher.create_realm())
+    }
+
+    /// The realm whose globals scripts currently see
+    pub fn current_realm(&self) -> RealmId {
+        RealmId(self.dispatcher.current_realm())
+    }
+
+    /// Make `realm` the one scripts run in, returning the previous realm
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InternalError` if `realm` was not created by this VM
+    pub fn enter_realm(&mut self, realm: RealmId) -> Result<RealmId, JsError> {
+        if realm.0 >= self.dispatcher.realm_count() {
+            return Err(JsError {
+                kind: ErrorKind::InternalError,
+                message: format!("Unknown realm {}", realm.0),
+                stack: vec![],
+                source_position: None,
+            });
+        }
+        Ok(RealmId(self.dispatcher.enter_realm(realm.0)))
+    }
+
+    /// Names of all global bindings, sorted
+    pub fn global_names(&self) -> Vec<String> {
+        self.dispatcher.global_names()
+    }
+
+    /// Property names that can be read from `value`
+    ///
+    /// Own properties come first, then inherited ones and built-in methods.
+    pub fn property_names(&self, value: &Value) -> Vec<String> {
+        self.dispatcher.property_names(value)
+    }
+
+    /// Set (or clear) the observer notified of each `console` call scripts make
+    ///
+    /// Every realm of this VM shares one console, so the observer sees calls
+    /// from all of them.
+    pub fn set_console_observer(&mut self, observer: Option<Rc<dyn ConsoleObserver>>) {
+        self.dispatcher.set_console_observer(observer);
+    }
+
+    /// Set (or clear) the hook notified each time a script reaches `debugger`
+    ///
+    /// The hook sees the paused frame's locals and captured variables and
+    /// the globals of the running realm; execution resumes when it returns.
+    pub fn set_debugger_hook(&mut self, hook: Option<Rc<dyn DebuggerHook>>) {
+        self.dispatcher.set_debugger_hook(hook);
+    }
+
+    /// Inline cache slot of the property read at `offset` in function `fn_idx`,
+    /// once the function has been called
+    pub fn property_slot(&self, fn_idx: usize, offset: usize) -> Option<&PropertySlot> {
+        self.dispatcher.property_slot(fn_idx, offset)
+    }
+
+    /// Property names this VM has interned; dropped along with the VM
+    pub fn interner(&self) -> &Interner {
+        self.dispatcher.interner()
+    }
+
     /// Get the current call stack depth
     pub fn call_stack_depth(&self) -> usize {
         self.call_stack.len()
@@ -711,4 +898,42 @@ mod tests {
         let result = vm.execute_function(999);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_vm_instruction_profile_counts_loop_body() {
+        use bytecode_system::{RegisterId, Value as BcValue};
+
+        // let i = 0; while (i < 10) { i = i + 1; } return i;
+        let mut chunk = BytecodeChunk::new();
+        let zero = chunk.add_constant(BcValue::Number(0.0));
+        let ten = chunk.add_constant(BcValue::Number(10.0));
+        let one = chunk.add_constant(BcValue::Number(1.0));
+        chunk.register_count = 1;
+        chunk.emit(Opcode::LoadConstant(zero)); // 0: prologue
+        chunk.emit(Opcode::StoreLocal(RegisterId(0))); // 1
+        chunk.emit(Opcode::LoadLocal(RegisterId(0))); // 2: loop test
+        chunk.emit(Opcode::LoadConstant(ten)); // 3
+        chunk.emit(Opcode::LessThan); // 4
+        chunk.emit(Opcode::JumpIfFalse(11)); // 5
+        chunk.emit(Opcode::LoadLocal(RegisterId(0))); // 6: loop body
+        chunk.emit(Opcode::LoadConstant(one)); // 7
+        chunk.emit(Opcode::Add); // 8
+        chunk.emit(Opcode::StoreLocal(RegisterId(0))); // 9
+        chunk.emit(Opcode::Jump(2)); // 10
+        chunk.emit(Opcode::LoadLocal(RegisterId(0))); // 11: epilogue
+        chunk.emit(Opcode::Return); // 12
+
+        let mut vm = VM::new();
+        assert!(vm.instruction_profile(&chunk).iter().all(|&(_, count)| count == 0));
+
+        vm.enable_profiling();
+        assert_eq!(vm.execute(&chunk).unwrap(), Value::Smi(10));
+
+        let profile = vm.instruction_profile(&chunk);
+        assert_eq!(profile.len(), chunk.instruction_count());
+        assert_eq!(profile[0], (0, 1));
+        assert_eq!(profile[2], (2, 11));
+        assert_eq!(profile[8], (8, 10));
+        assert!(profile[6..=10].iter().all(|&(_, count)| count > profile[0].1));
+    }
 }
@@ -2,11 +2,36 @@
 //!
 //! Tests the integration between async_runtime and interpreter.
 
+use async_runtime::{Promise, SettledResult};
 use bytecode_system::{BytecodeChunk, Opcode};
-use core_types::Value;
+use core_types::{ObjectView, Value};
 use interpreter::promise_integration::{is_promise, PromiseConstructor, PromiseObject};
 use interpreter::VM;
 
+#[test]
+fn test_all_settled_fulfills_with_an_array_of_result_objects() {
+    let _vm = VM::new();
+    let (rejected, _resolve, mut reject) = Promise::with_resolvers();
+    let fulfilled = Promise::promise_resolve(Value::Smi(7));
+    let combined = Promise::all_settled(vec![rejected, fulfilled]);
+    reject.call(vec![Value::String("nope".to_string())]).unwrap();
+    Promise::run_jobs();
+
+    let result = combined.borrow().result.clone().unwrap();
+    assert!(matches!(result.object_view(), Some(ObjectView::Array { length: 2, .. })));
+    assert_eq!(
+        result.inspect(2),
+        "[ { status: 'rejected', reason: 'nope' }, { status: 'fulfilled', value: 7 } ]"
+    );
+    assert_eq!(
+        Promise::settled_results(&result).unwrap(),
+        vec![
+            SettledResult::Rejected(Value::String("nope".to_string())),
+            SettledResult::Fulfilled(Value::Smi(7)),
+        ]
+    );
+}
+
 #[test]
 fn test_promise_resolve_creates_fulfilled_promise() {
     let value = PromiseConstructor::resolve(Value::Smi(42));
@@ -150,6 +175,26 @@ fn test_promise_reject_method() {
     }
 }
 
+#[test]
+fn test_await_rejected_promise_throws_original_reason() {
+    let mut vm = VM::new();
+    let mut chunk = BytecodeChunk::new();
+
+    // try { await Promise.reject(7) } catch (e) { return e }
+    let reason = chunk.add_constant(bytecode_system::Value::Number(7.0));
+    chunk.emit(Opcode::PushTry(7));
+    chunk.emit(Opcode::LoadGlobal("Promise".to_string()));
+    chunk.emit(Opcode::LoadProperty("reject".to_string()));
+    chunk.emit(Opcode::LoadConstant(reason));
+    chunk.emit(Opcode::Call(1));
+    chunk.emit(Opcode::Await);
+    chunk.emit(Opcode::Return);
+    chunk.emit(Opcode::Return); // 7: catch, with the reason on the stack
+
+    let result = vm.execute(&chunk).unwrap();
+    assert_eq!(result, Value::Smi(7));
+}
+
 #[test]
 fn test_await_rejected_promise_throws() {
     let mut vm = VM::new();
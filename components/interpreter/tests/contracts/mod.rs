@@ -68,6 +68,8 @@ fn test_execution_context_fields_contract() {
         registers: vec![Value::Undefined; 10],
         instruction_pointer: 0,
         bytecode: chunk,
+        new_target: Value::Undefined,
+        property_slots: Default::default(),
     };
 
     assert_eq!(ctx.registers.len(), 10);
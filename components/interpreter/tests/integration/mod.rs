@@ -153,6 +153,59 @@ fn test_inline_cache_evolution() {
     assert!(matches!(cache, InlineCache::Megamorphic));
 }
 
+#[test]
+fn test_property_reads_populate_the_vm_inline_cache() {
+    let mut vm = VM::new();
+
+    // function getY(o) { return o.y }
+    let mut get_y = BytecodeChunk::new();
+    get_y.emit(Opcode::LoadLocal(RegisterId(0)));
+    get_y.emit(Opcode::LoadProperty("y".to_string()));
+    get_y.emit(Opcode::Return);
+    let get_y = vm.register_function(get_y);
+
+    let mut chunk = BytecodeChunk::new();
+    let constants: Vec<usize> =
+        (1..=3).map(|n| chunk.add_constant(BcValue::Number(n as f64))).collect();
+
+    // a = { x: 1, y: 2 }; b = { y: 3 }; return getY(a) + getY(b) + getY(a)
+    for (global, props) in [("a", vec![("x", 0), ("y", 1)]), ("b", vec![("y", 2)])] {
+        chunk.emit(Opcode::CreateObject);
+        for (name, constant) in props {
+            chunk.emit(Opcode::Dup);
+            chunk.emit(Opcode::LoadConstant(constants[constant]));
+            chunk.emit(Opcode::StoreProperty(name.to_string()));
+            chunk.emit(Opcode::Pop);
+        }
+        chunk.emit(Opcode::StoreGlobal(global.to_string()));
+    }
+    for (i, global) in ["a", "b", "a"].into_iter().enumerate() {
+        chunk.emit(Opcode::CreateClosure(get_y, vec![]));
+        chunk.emit(Opcode::LoadGlobal(global.to_string()));
+        chunk.emit(Opcode::Call(1));
+        if i > 0 {
+            chunk.emit(Opcode::Add);
+        }
+    }
+    chunk.emit(Opcode::Return);
+
+    assert_eq!(vm.execute(&chunk).unwrap(), Value::Smi(7));
+
+    // Every call shares the read's slot, and `y` sits at a different
+    // offset in each of the two shapes
+    let slot = vm.property_slot(get_y, 1).expect("the read has a cache slot");
+    assert_eq!(vm.interner().lookup("y"), Some(slot.name()));
+    match slot.cache() {
+        InlineCache::Polymorphic { entries } => {
+            let mut offsets: Vec<u32> = entries.iter().map(|(_, offset)| *offset).collect();
+            offsets.sort();
+            assert_eq!(offsets, vec![0, 1]);
+        }
+        other => panic!("expected a polymorphic cache, got {:?}", other),
+    }
+    assert!(vm.interner().lookup("x").is_none());
+}
+
 #[test]
 fn test_vm_global_and_local_interaction() {
     let mut vm = VM::new();
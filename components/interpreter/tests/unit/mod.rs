@@ -3,7 +3,8 @@
 use bytecode_system::{BytecodeChunk, Opcode, RegisterId, Value as BcValue};
 use core_types::Value;
 use interpreter::{
-    BranchOutcome, CallFrame, ExecutionContext, InlineCache, ProfileData, TypeInfo, VM,
+    BranchOutcome, CallFrame, ExecutionContext, InlineCache, ProfileData, PropertySlots,
+    TypeInfo, VM,
 };
 
 // ============================================================================
@@ -409,6 +410,46 @@ fn test_vm_execute_store_local() {
     assert_eq!(result.unwrap(), Value::Smi(22));
 }
 
+#[test]
+fn test_vm_execute_add_assign_local() {
+    let mut vm = VM::new();
+    let mut chunk = BytecodeChunk::new();
+    chunk.register_count = 1;
+
+    let ten = chunk.add_constant(BcValue::Number(10.0));
+    let five = chunk.add_constant(BcValue::Number(5.0));
+    chunk.emit(Opcode::LoadConstant(ten));
+    chunk.emit(Opcode::StoreLocal(RegisterId(0)));
+    chunk.emit(Opcode::LoadConstant(five));
+    chunk.emit(Opcode::AddAssignLocal(RegisterId(0)));
+    chunk.emit(Opcode::Pop);
+    chunk.emit(Opcode::LoadLocal(RegisterId(0)));
+    chunk.emit(Opcode::Return);
+
+    let result = vm.execute(&chunk);
+    assert_eq!(result.unwrap(), Value::Smi(15));
+}
+
+#[test]
+fn test_vm_execute_mul_assign_local_overflow_promotes_to_double() {
+    let mut vm = VM::new();
+    let mut chunk = BytecodeChunk::new();
+    chunk.register_count = 1;
+
+    let start = chunk.add_constant(BcValue::Number(i32::MAX as f64));
+    let two = chunk.add_constant(BcValue::Number(2.0));
+    chunk.emit(Opcode::LoadConstant(start));
+    chunk.emit(Opcode::StoreLocal(RegisterId(0)));
+    chunk.emit(Opcode::LoadConstant(two));
+    chunk.emit(Opcode::MulAssignLocal(RegisterId(0)));
+    chunk.emit(Opcode::Pop);
+    chunk.emit(Opcode::LoadLocal(RegisterId(0)));
+    chunk.emit(Opcode::Return);
+
+    let result = vm.execute(&chunk);
+    assert_eq!(result.unwrap(), Value::Double(i32::MAX as f64 * 2.0));
+}
+
 // ============================================================================
 // ExecutionContext Tests
 // ============================================================================
@@ -420,6 +461,8 @@ fn test_execution_context_creation() {
         registers: vec![Value::Undefined; 5],
         instruction_pointer: 0,
         bytecode: chunk,
+        new_target: Value::Undefined,
+        property_slots: Default::default(),
     };
 
     assert_eq!(ctx.registers.len(), 5);
@@ -433,6 +476,8 @@ fn test_execution_context_registers() {
         registers: vec![Value::Undefined; 3],
         instruction_pointer: 0,
         bytecode: chunk,
+        new_target: Value::Undefined,
+        property_slots: Default::default(),
     };
 
     ctx.registers[0] = Value::Smi(100);
@@ -450,6 +495,8 @@ fn test_execution_context_instruction_pointer() {
         registers: vec![],
         instruction_pointer: 0,
         bytecode: chunk,
+        new_target: Value::Undefined,
+        property_slots: Default::default(),
     };
 
     ctx.instruction_pointer = 10;
@@ -471,6 +518,31 @@ fn test_inline_cache_uninitialized() {
     assert!(cache.lookup(100).is_none());
 }
 
+#[test]
+fn test_property_slots_cover_each_property_read() {
+    let mut interner = core_types::Interner::new();
+    let mut chunk = BytecodeChunk::new();
+    chunk.emit(Opcode::LoadLocal(RegisterId(0)));
+    chunk.emit(Opcode::LoadProperty("x".to_string()));
+    chunk.emit(Opcode::StoreProperty("y".to_string()));
+    let slots = PropertySlots::new(&chunk, &mut interner);
+
+    assert_eq!(slots.len(), 3);
+    assert!(slots.get(0).is_none());
+    assert!(slots.get(2).is_none());
+    assert!(interner.lookup("y").is_none());
+
+    let slot = slots.get(1).expect("LoadProperty has a slot");
+    assert_eq!(slot.name(), interner.intern("x"));
+    slot.update(3, 1);
+    assert_eq!(slot.lookup(3), Some(1));
+    assert!(slot.lookup(4).is_none());
+
+    // Clones share the same caches
+    let shared = slots.clone();
+    assert_eq!(shared.get(1).unwrap().cache(), InlineCache::Monomorphic { shape: 3, offset: 1 });
+}
+
 #[test]
 fn test_inline_cache_monomorphic_hit() {
     let cache = InlineCache::Monomorphic {
@@ -0,0 +1,518 @@
+//! Bounds-check elimination for monotonic loop indices
+//!
+//! A loop of the shape
+//!
+//! ```text
+//! for (let i = start; i < arr.length; i += step) { ... arr[i] ... }
+//! ```
+//!
+//! with a non-negative integer `start`, a positive integer `step`, and `arr[i]` read
+//! before `i` is bumped, can only index inside the array: the loop condition
+//! just proved `i < arr.length`, and `i` never decreases below `start`.
+//! Those element loads are rewritten to `GetIndexUnchecked`.
+//!
+//! The proof only holds while the length stays what the condition saw. When the
+//! loop body contains anything that could change it (a call, a store), each
+//! unchecked load is preceded by a `LengthGuard` that deoptimizes on a mismatch.
+//!
+//! This is the IR analysis only. No backend executes the rewritten loads yet:
+//! the Cranelift backend has no arrays in its value model and refuses element
+//! access, so such loops keep running in the interpreter. [`check_guard`]
+//! is the comparison a backend's guard has to make.
+//!
+//! [`check_guard`]: BoundsCheckEliminator::check_guard
+//!
+//! # Example
+//!
+//! ```ignore
+//! // Before:
+//! LoadReg(a), LoadReg(i), GetIndex
+//!
+//! // After (body has a call):
+//! LoadReg(a), LoadReg(i), LengthGuard(0), GetIndexUnchecked
+//! ```
+
+use crate::deopt::DeoptReason;
+use crate::ir::{IRFunction, IRInstruction, IROpcode};
+use bytecode_system::Value as BcValue;
+use std::collections::HashMap;
+
+/// Guard that the array length a loop condition checked is still current
+#[derive(Debug, Clone, PartialEq)]
+pub struct LengthGuard {
+    /// Unique identifier for this guard
+    pub guard_id: u32,
+    /// Register holding the array
+    pub array_reg: u32,
+    /// Register holding the loop index
+    pub index_reg: u32,
+    /// Bytecode offset of the guarded element load, where the interpreter resumes
+    pub deopt_offset: usize,
+    /// Number of times this guard has been checked
+    pub check_count: u64,
+    /// Number of times this guard has failed
+    pub failure_count: u64,
+}
+
+impl LengthGuard {
+    /// Create a new length guard
+    pub fn new(guard_id: u32, array_reg: u32, index_reg: u32, deopt_offset: usize) -> Self {
+        Self {
+            guard_id,
+            array_reg,
+            index_reg,
+            deopt_offset,
+            check_count: 0,
+            failure_count: 0,
+        }
+    }
+}
+
+/// Statistics about bounds-check elimination
+#[derive(Debug, Clone, Default)]
+pub struct BoundsCheckStats {
+    /// Number of loops examined
+    pub loops_analyzed: u64,
+    /// Number of loops whose index was proven in bounds
+    pub loops_optimized: u64,
+    /// Number of element loads whose bounds check was removed
+    pub bounds_checks_eliminated: u64,
+    /// Number of length guards inserted
+    pub length_guards_inserted: u64,
+    /// Number of guard failures that requested deoptimization
+    pub deopts_triggered: u64,
+}
+
+/// A loop whose index is provably within its array's bounds
+#[derive(Debug, Clone)]
+struct BoundedLoop {
+    /// Array register compared against by the loop condition
+    array_reg: u32,
+    /// Index register
+    index_reg: u32,
+    /// Element loads `arr[i]` (IR index of the `GetIndex`)
+    accesses: Vec<usize>,
+    /// Whether the body could change the array's length
+    needs_guard: bool,
+}
+
+/// Removes redundant bounds checks on array loads inside counted loops
+pub struct BoundsCheckEliminator {
+    /// Statistics about elimination
+    stats: BoundsCheckStats,
+    /// Length guards indexed by guard ID
+    guards: HashMap<u32, LengthGuard>,
+    /// Next guard ID to assign
+    next_guard_id: u32,
+}
+
+impl BoundsCheckEliminator {
+    /// Create a new bounds-check eliminator
+    pub fn new() -> Self {
+        Self {
+            stats: BoundsCheckStats::default(),
+            guards: HashMap::new(),
+            next_guard_id: 0,
+        }
+    }
+
+    /// Rewrite provably in-bounds element loads of an IR function
+    pub fn eliminate(&mut self, ir: &IRFunction) -> IRFunction {
+        let mut unchecked: HashMap<usize, Option<u32>> = HashMap::new();
+
+        for bounded in self.find_bounded_loops(ir) {
+            self.stats.loops_optimized += 1;
+            for access in bounded.accesses {
+                let guard = if bounded.needs_guard {
+                    let offset = ir.instructions[access].bytecode_offset;
+                    Some(self.create_guard(bounded.array_reg, bounded.index_reg, offset))
+                } else {
+                    None
+                };
+                unchecked.insert(access, guard);
+                self.stats.bounds_checks_eliminated += 1;
+            }
+        }
+
+        let mut optimized = IRFunction::new();
+        optimized.constants = ir.constants.clone();
+        optimized.register_count = ir.register_count;
+
+        for (idx, instruction) in ir.instructions.iter().enumerate() {
+            match unchecked.get(&idx) {
+                Some(guard) => {
+                    let offset = instruction.bytecode_offset;
+                    if let Some(guard_id) = guard {
+                        optimized
+                            .instructions
+                            .push(IRInstruction::new(IROpcode::LengthGuard(*guard_id), offset));
+                    }
+                    optimized
+                        .instructions
+                        .push(IRInstruction::new(IROpcode::GetIndexUnchecked, offset));
+                }
+                None => optimized.instructions.push(instruction.clone()),
+            }
+        }
+
+        optimized
+    }
+
+    /// Evaluate a length guard against the length its loop condition compared
+    /// with and the array's length at the guarded load
+    ///
+    /// Returns the deoptimization reason if the length has changed.
+    pub fn check_guard(
+        &mut self,
+        guard_id: u32,
+        checked_length: usize,
+        current_length: usize,
+    ) -> Option<DeoptReason> {
+        let guard = self.guards.get_mut(&guard_id)?;
+        guard.check_count += 1;
+
+        if checked_length == current_length {
+            None
+        } else {
+            guard.failure_count += 1;
+            self.stats.deopts_triggered += 1;
+            Some(DeoptReason::BoundsCheckFailure)
+        }
+    }
+
+    /// Get a guard by ID
+    pub fn get_guard(&self, guard_id: u32) -> Option<&LengthGuard> {
+        self.guards.get(&guard_id)
+    }
+
+    /// Get elimination statistics
+    pub fn stats(&self) -> &BoundsCheckStats {
+        &self.stats
+    }
+
+    /// Reset elimination statistics
+    pub fn reset_stats(&mut self) {
+        self.stats = BoundsCheckStats::default();
+    }
+
+    /// Find every loop whose element loads are provably in bounds
+    fn find_bounded_loops(&mut self, ir: &IRFunction) -> Vec<BoundedLoop> {
+        let ops: Vec<&IROpcode> = ir.instructions.iter().map(|inst| &inst.opcode).collect();
+
+        // A closure may capture the index register and change it behind our back
+        if ops
+            .iter()
+            .any(|op| matches!(op, IROpcode::CreateClosure(_) | IROpcode::CreateAsyncFunction(_)))
+        {
+            return Vec::new();
+        }
+
+        let mut loops = Vec::new();
+        for (back_edge, op) in ops.iter().enumerate() {
+            if let IROpcode::Jump(header) = op {
+                if *header <= back_edge {
+                    self.stats.loops_analyzed += 1;
+                    if let Some(bounded) = Self::analyze_loop(ir, &ops, *header, back_edge) {
+                        loops.push(bounded);
+                    }
+                }
+            }
+        }
+        loops
+    }
+
+    /// Check one loop (`header..=back_edge`) for the counted-loop shape
+    fn analyze_loop(
+        ir: &IRFunction,
+        ops: &[&IROpcode],
+        header: usize,
+        back_edge: usize,
+    ) -> Option<BoundedLoop> {
+        // Condition: i < arr.length, exiting past the back edge when false
+        let (index_reg, array_reg) = match ops.get(header..header + 5)? {
+            [IROpcode::LoadReg(i), IROpcode::LoadReg(a), IROpcode::LoadProperty(name), IROpcode::LessThan(_), IROpcode::JumpIfFalse(exit)]
+                if name == "length" && *exit > back_edge && i != a =>
+            {
+                (*i, *a)
+            }
+            _ => return None,
+        };
+        let body_start = header + 5;
+        let body = body_start..back_edge;
+
+        // The array register must be loop-invariant
+        if ops[body.clone()]
+            .iter()
+            .any(|op| op.stored_register() == Some(array_reg))
+        {
+            return None;
+        }
+
+        // The index may only be bumped by a positive integer constant, exactly once
+        let stores: Vec<usize> = body
+            .clone()
+            .filter(|&k| ops[k].stored_register() == Some(index_reg))
+            .collect();
+        let increment = match stores.as_slice() {
+            [k] if *k >= body_start + 3 => *k,
+            _ => return None,
+        };
+        match &ops[increment - 3..increment] {
+            [IROpcode::LoadReg(r), IROpcode::LoadConst(c), IROpcode::Add(_)]
+                if *r == index_reg
+                    && Self::constant(ir, *c)
+                        .is_some_and(|step| step > 0.0 && step.fract() == 0.0) => {}
+            _ => return None,
+        }
+
+        // And it must start at a non-negative integer
+        let init = (0..header)
+            .rev()
+            .find(|&k| ops[k].stored_register() == Some(index_reg))?;
+        match ops.get(init.checked_sub(1)?)? {
+            IROpcode::LoadConst(c)
+                if Self::constant(ir, *c).is_some_and(|v| v >= 0.0 && v.fract() == 0.0) => {}
+            _ => return None,
+        }
+
+        // arr[i] loads that run after the condition and before the increment
+        let accesses: Vec<usize> = (body_start + 2..increment - 3)
+            .filter(|&k| {
+                *ops[k] == IROpcode::GetIndex
+                    && *ops[k - 2] == IROpcode::LoadReg(array_reg)
+                    && *ops[k - 1] == IROpcode::LoadReg(index_reg)
+            })
+            .collect();
+        if accesses.is_empty() {
+            return None;
+        }
+
+        let needs_guard = ops[body].iter().any(|op| Self::may_change_length(op));
+
+        Some(BoundedLoop {
+            array_reg,
+            index_reg,
+            accesses,
+            needs_guard,
+        })
+    }
+
+    /// Whether an operation could change the length of an array it doesn't name
+    fn may_change_length(op: &IROpcode) -> bool {
+        matches!(
+            op,
+            IROpcode::Call(_)
+                | IROpcode::TailCall(_)
+                | IROpcode::CallMethod(_)
+                | IROpcode::CallNew(_)
+                | IROpcode::SetIndex
+                | IROpcode::StoreProperty(_)
+                | IROpcode::DeleteProperty(_)
+                | IROpcode::Await
+                | IROpcode::Yield
+                | IROpcode::GetIterator
+                | IROpcode::YieldDelegate
+                | IROpcode::IteratorNext
+                | IROpcode::Debugger
+        )
+    }
+
+    /// Numeric value of a constant pool entry
+    fn constant(ir: &IRFunction, idx: usize) -> Option<f64> {
+        match ir.constants.get(idx)? {
+            BcValue::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// Create a new length guard and return its ID
+    fn create_guard(&mut self, array_reg: u32, index_reg: u32, deopt_offset: usize) -> u32 {
+        let guard_id = self.next_guard_id;
+        self.next_guard_id += 1;
+
+        let guard = LengthGuard::new(guard_id, array_reg, index_reg, deopt_offset);
+        self.guards.insert(guard_id, guard);
+        self.stats.length_guards_inserted += 1;
+
+        guard_id
+    }
+}
+
+impl Default for BoundsCheckEliminator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiled_code::CompiledCode;
+    use crate::deopt::Deoptimizer;
+    use bytecode_system::{BytecodeChunk, Opcode, RegisterId};
+
+    /// `let sum = 0; for (let i = 0; i < arr.length; i++) { sum = sum + arr[i]; } return sum;`
+    ///
+    /// With `call_in_body`, the body also calls `arr.push(sum)` before loading `arr[i]`.
+    fn sum_loop(call_in_body: bool) -> BytecodeChunk {
+        let (arr, sum, i) = (RegisterId(0), RegisterId(1), RegisterId(2));
+        let mut chunk = BytecodeChunk::new();
+        let zero = chunk.add_constant(BcValue::Number(0.0));
+        let one = chunk.add_constant(BcValue::Number(1.0));
+        let push = chunk.add_constant(BcValue::String("push".to_string()));
+        chunk.register_count = 3;
+
+        chunk.emit(Opcode::LoadConstant(zero));
+        chunk.emit(Opcode::StoreLocal(sum));
+        chunk.emit(Opcode::LoadConstant(zero));
+        chunk.emit(Opcode::StoreLocal(i));
+        let header = chunk.instruction_count();
+        chunk.emit(Opcode::LoadLocal(i));
+        chunk.emit(Opcode::LoadLocal(arr));
+        chunk.emit(Opcode::LoadProperty("length".to_string()));
+        chunk.emit(Opcode::LessThan);
+        let exit_jump = chunk.instruction_count();
+        chunk.emit(Opcode::JumpIfFalse(0));
+        if call_in_body {
+            chunk.emit(Opcode::LoadLocal(arr));
+            chunk.emit(Opcode::LoadConstant(push));
+            chunk.emit(Opcode::LoadLocal(sum));
+            chunk.emit(Opcode::CallMethod(1));
+            chunk.emit(Opcode::Pop);
+        }
+        chunk.emit(Opcode::LoadLocal(sum));
+        chunk.emit(Opcode::LoadLocal(arr));
+        chunk.emit(Opcode::LoadLocal(i));
+        chunk.emit(Opcode::GetIndex);
+        chunk.emit(Opcode::Add);
+        chunk.emit(Opcode::StoreLocal(sum));
+        chunk.emit(Opcode::LoadLocal(i));
+        chunk.emit(Opcode::LoadConstant(one));
+        chunk.emit(Opcode::Add);
+        chunk.emit(Opcode::StoreLocal(i));
+        chunk.emit(Opcode::Jump(header));
+        let exit = chunk.instruction_count();
+        chunk.instructions[exit_jump].opcode = Opcode::JumpIfFalse(exit);
+        chunk.emit(Opcode::LoadLocal(sum));
+        chunk.emit(Opcode::Return);
+        chunk
+    }
+
+    fn count(ir: &IRFunction, opcode: &IROpcode) -> usize {
+        ir.instructions.iter().filter(|inst| &inst.opcode == opcode).count()
+    }
+
+    #[test]
+    fn test_sum_loop_bounds_check_eliminated() {
+        let ir = IRFunction::from_bytecode(&sum_loop(false));
+        let mut eliminator = BoundsCheckEliminator::new();
+        let optimized = eliminator.eliminate(&ir);
+
+        assert_eq!(eliminator.stats().loops_analyzed, 1);
+        assert_eq!(eliminator.stats().bounds_checks_eliminated, 1);
+        assert_eq!(eliminator.stats().length_guards_inserted, 0);
+        assert_eq!(count(&optimized, &IROpcode::GetIndex), 0);
+        assert_eq!(count(&optimized, &IROpcode::GetIndexUnchecked), 1);
+        assert_eq!(optimized.instruction_count(), ir.instruction_count());
+    }
+
+    #[test]
+    fn test_length_change_mid_loop_fails_guard() {
+        let chunk = sum_loop(true);
+        let ir = IRFunction::from_bytecode(&chunk);
+        let mut eliminator = BoundsCheckEliminator::new();
+        let optimized = eliminator.eliminate(&ir);
+
+        assert_eq!(eliminator.stats().bounds_checks_eliminated, 1);
+        assert_eq!(eliminator.stats().length_guards_inserted, 1);
+        let guard_at = optimized
+            .instructions
+            .iter()
+            .position(|inst| inst.opcode == IROpcode::LengthGuard(0))
+            .unwrap();
+        assert_eq!(optimized.instructions[guard_at + 1].opcode, IROpcode::GetIndexUnchecked);
+
+        // Drive the guard's check by hand, as no backend runs it yet: the
+        // condition reads the length, then the push grows the array on the
+        // third iteration
+        let mut arr = vec![1, 2, 3, 4];
+        let mut deoptimizer = Deoptimizer::new();
+        let compiled = CompiledCode::new(std::ptr::null(), 0, vec![]);
+        let mut resumed = None;
+        let mut i = 0;
+        while i < arr.len() {
+            let checked_length = arr.len();
+            if i == 2 {
+                arr.push(0);
+            }
+            if let Some(reason) = eliminator.check_guard(0, checked_length, arr.len()) {
+                let offset = eliminator.get_guard(0).unwrap().deopt_offset;
+                resumed = Some(deoptimizer.deoptimize_with_reason(&compiled, &chunk, reason, offset));
+                break;
+            }
+            i += 1;
+        }
+
+        let state = resumed.expect("length change should deoptimize");
+        assert_eq!(i, 2);
+        assert_eq!(chunk.instructions[state.instruction_pointer].opcode, Opcode::GetIndex);
+        assert_eq!(deoptimizer.history()[0].reason, DeoptReason::BoundsCheckFailure);
+        assert_eq!(eliminator.stats().deopts_triggered, 1);
+        assert_eq!(eliminator.get_guard(0).unwrap().check_count, 3);
+    }
+
+    #[test]
+    fn test_non_monotonic_index_keeps_bounds_check() {
+        let mut chunk = sum_loop(false);
+        // i = i + -1
+        let minus_one = chunk.add_constant(BcValue::Number(-1.0));
+        let bump = chunk
+            .instructions
+            .iter()
+            .position(|inst| inst.opcode == Opcode::LoadConstant(1))
+            .unwrap();
+        chunk.instructions[bump].opcode = Opcode::LoadConstant(minus_one);
+
+        let mut eliminator = BoundsCheckEliminator::new();
+        let optimized = eliminator.eliminate(&IRFunction::from_bytecode(&chunk));
+
+        assert_eq!(eliminator.stats().bounds_checks_eliminated, 0);
+        assert_eq!(count(&optimized, &IROpcode::GetIndex), 1);
+    }
+
+    #[test]
+    fn test_fractional_step_keeps_bounds_check() {
+        let mut chunk = sum_loop(false);
+        // i = i + 0.5 reads arr[0.5], which is a property, not an element
+        let half = chunk.add_constant(BcValue::Number(0.5));
+        let bump = chunk
+            .instructions
+            .iter()
+            .position(|inst| inst.opcode == Opcode::LoadConstant(1))
+            .unwrap();
+        chunk.instructions[bump].opcode = Opcode::LoadConstant(half);
+
+        let mut eliminator = BoundsCheckEliminator::new();
+        let optimized = eliminator.eliminate(&IRFunction::from_bytecode(&chunk));
+
+        assert_eq!(eliminator.stats().bounds_checks_eliminated, 0);
+        assert_eq!(count(&optimized, &IROpcode::GetIndex), 1);
+    }
+
+    #[test]
+    fn test_array_reassigned_in_loop_keeps_bounds_check() {
+        let mut chunk = sum_loop(false);
+        let back_edge = chunk.instruction_count() - 3;
+        // Overwrite `sum = ...` with `arr = ...`
+        let store_sum = (0..back_edge)
+            .rev()
+            .find(|&k| chunk.instructions[k].opcode == Opcode::StoreLocal(RegisterId(1)))
+            .unwrap();
+        chunk.instructions[store_sum].opcode = Opcode::StoreLocal(RegisterId(0));
+
+        let mut eliminator = BoundsCheckEliminator::new();
+        eliminator.eliminate(&IRFunction::from_bytecode(&chunk));
+
+        assert_eq!(eliminator.stats().bounds_checks_eliminated, 0);
+    }
+}
@@ -146,6 +146,15 @@ impl CodeGenerator {
             IROpcode::JumpIfFalse(_) => vec![0x0F, 0x84, 0x00, 0x00, 0x00, 0x00], // jz rel32
             IROpcode::TypeGuard(_) => vec![0x48, 0x85, 0xC0], // test rax, rax
             IROpcode::DeoptPoint(_) => vec![0xCC],      // int3
+            IROpcode::GetIndex => {
+                // cmp rax, rcx; jae rel32; mov rax, [rbx + rax*8]
+                vec![0x48, 0x39, 0xC8, 0x0F, 0x83, 0x00, 0x00, 0x00, 0x00, 0x48, 0x8B, 0x04, 0xC3]
+            }
+            IROpcode::GetIndexUnchecked => vec![0x48, 0x8B, 0x04, 0xC3], // mov rax, [rbx + rax*8]
+            IROpcode::LengthGuard(_) => {
+                // cmp rcx, rdx; jne rel32 (to the deopt exit)
+                vec![0x48, 0x39, 0xD1, 0x0F, 0x85, 0x00, 0x00, 0x00, 0x00]
+            }
             _ => vec![0x90],                            // nop
         }
     }
@@ -263,6 +272,22 @@ mod tests {
         assert!(!result.osr_entries.is_empty());
     }
 
+    #[test]
+    fn test_unchecked_index_drops_bounds_check() {
+        let gen = CodeGenerator::default();
+        let mut checked = IRFunction::new();
+        checked.emit(IROpcode::GetIndex, 0);
+        let mut unchecked = IRFunction::new();
+        unchecked.emit(IROpcode::GetIndexUnchecked, 0);
+        let mut guarded = IRFunction::new();
+        guarded.emit(IROpcode::LengthGuard(0), 0);
+        guarded.emit(IROpcode::GetIndexUnchecked, 0);
+
+        let unchecked_size = gen.generate(&unchecked).code_size;
+        assert!(unchecked_size < gen.generate(&checked).code_size);
+        assert!(gen.generate(&guarded).code_size > unchecked_size);
+    }
+
     #[test]
     fn test_dead_code_elimination() {
         let gen = CodeGenerator::new(CodegenConfig::optimizing());
@@ -2,11 +2,12 @@
 //!
 //! Provides real native code generation using Cranelift for JavaScript bytecode.
 
-use bytecode_system::{BytecodeChunk, Opcode, Value as BcValue};
+use crate::ir::{IRFunction, IROpcode};
+use bytecode_system::{BytecodeChunk, Value as BcValue};
 use cranelift_codegen::ir::{types, AbiParam, InstBuilder};
 use cranelift_codegen::settings::{self, Configurable};
 use cranelift_codegen::Context;
-use cranelift_frontend::{FunctionBuilder, FunctionBuilderContext};
+use cranelift_frontend::{FunctionBuilder, FunctionBuilderContext, Variable};
 use cranelift_jit::{JITBuilder, JITModule};
 use cranelift_module::{Linkage, Module};
 
@@ -60,8 +61,16 @@ impl CraneliftBackend {
     /// Returns a pointer to the compiled function that can be called.
     /// The function signature is `() -> f64` (returns a number).
     pub fn compile_function(&mut self, chunk: &BytecodeChunk) -> Result<CompiledFunction, String> {
-        // Build Cranelift IR from bytecode
-        self.build_ir(chunk)?;
+        self.compile_ir(&IRFunction::from_bytecode(chunk))
+    }
+
+    /// Compile an IR function to native code
+    ///
+    /// Takes IR rather than bytecode so that optimization passes run before
+    /// code generation are reflected in the emitted code.
+    pub fn compile_ir(&mut self, ir: &IRFunction) -> Result<CompiledFunction, String> {
+        // Build Cranelift IR from the JIT's IR
+        self.build_ir(ir)?;
 
         // Generate unique function name
         let func_name = format!("js_func_{}", self.func_counter);
@@ -96,7 +105,7 @@ impl CraneliftBackend {
         })
     }
 
-    fn build_ir(&mut self, chunk: &BytecodeChunk) -> Result<(), String> {
+    fn build_ir(&mut self, ir: &IRFunction) -> Result<(), String> {
         // Set up function signature: () -> f64
         let mut sig = self.module.make_signature();
         sig.returns.push(AbiParam::new(types::F64));
@@ -111,40 +120,60 @@ impl CraneliftBackend {
         builder.switch_to_block(entry_block);
         builder.seal_block(entry_block);
 
-        // Translate bytecode to Cranelift IR
+        // Registers become Cranelift variables, starting out undefined (NaN)
+        let registers: Vec<Variable> = (0..ir.register_count).map(Variable::from_u32).collect();
+        for &register in &registers {
+            builder.declare_var(register, types::F64);
+            let undefined = builder.ins().f64const(f64::NAN);
+            builder.def_var(register, undefined);
+        }
+        let register = |reg: u32| {
+            registers
+                .get(reg as usize)
+                .copied()
+                .ok_or_else(|| format!("Invalid register: {}", reg))
+        };
+
+        // Translate IR to Cranelift IR
         let mut value_stack: Vec<cranelift_codegen::ir::Value> = Vec::new();
         let mut has_return = false;
 
-        for instruction in &chunk.instructions {
+        for instruction in &ir.instructions {
             match &instruction.opcode {
-                Opcode::LoadConstant(idx) => {
-                    if let Some(constant) = chunk.constants.get(*idx) {
-                        if let BcValue::Number(n) = constant {
-                            let val = builder.ins().f64const(*n);
-                            value_stack.push(val);
-                        } else {
-                            // For non-number constants, push NaN as placeholder
-                            let val = builder.ins().f64const(f64::NAN);
-                            value_stack.push(val);
-                        }
-                    } else {
-                        return Err(format!("Invalid constant index: {}", idx));
-                    }
+                IROpcode::LoadConst(idx) => {
+                    let val = match ir.constants.get(*idx) {
+                        Some(BcValue::Number(n)) => builder.ins().f64const(*n),
+                        // For non-number constants, push NaN as placeholder
+                        Some(_) => builder.ins().f64const(f64::NAN),
+                        None => return Err(format!("Invalid constant index: {}", idx)),
+                    };
+                    value_stack.push(val);
                 }
-                Opcode::LoadUndefined | Opcode::LoadNull => {
+                IROpcode::LoadUndefined | IROpcode::LoadNull => {
                     // Represent undefined/null as NaN
                     let val = builder.ins().f64const(f64::NAN);
                     value_stack.push(val);
                 }
-                Opcode::LoadTrue => {
+                IROpcode::LoadTrue => {
                     let val = builder.ins().f64const(1.0);
                     value_stack.push(val);
                 }
-                Opcode::LoadFalse => {
+                IROpcode::LoadFalse => {
                     let val = builder.ins().f64const(0.0);
                     value_stack.push(val);
                 }
-                Opcode::Add => {
+                IROpcode::LoadReg(reg) => {
+                    let val = builder.use_var(register(*reg)?);
+                    value_stack.push(val);
+                }
+                IROpcode::StoreReg(reg) => {
+                    let val = value_stack.pop().ok_or("Stack underflow on StoreReg")?;
+                    builder.def_var(register(*reg)?, val);
+                }
+                IROpcode::Pop => {
+                    value_stack.pop().ok_or("Stack underflow on Pop")?;
+                }
+                IROpcode::Add(_) => {
                     if value_stack.len() >= 2 {
                         let b = value_stack.pop().unwrap();
                         let a = value_stack.pop().unwrap();
@@ -154,7 +183,7 @@ impl CraneliftBackend {
                         return Err("Stack underflow on Add".to_string());
                     }
                 }
-                Opcode::Sub => {
+                IROpcode::Sub(_) => {
                     if value_stack.len() >= 2 {
                         let b = value_stack.pop().unwrap();
                         let a = value_stack.pop().unwrap();
@@ -164,7 +193,7 @@ impl CraneliftBackend {
                         return Err("Stack underflow on Sub".to_string());
                     }
                 }
-                Opcode::Mul => {
+                IROpcode::Mul(_) => {
                     if value_stack.len() >= 2 {
                         let b = value_stack.pop().unwrap();
                         let a = value_stack.pop().unwrap();
@@ -174,7 +203,7 @@ impl CraneliftBackend {
                         return Err("Stack underflow on Mul".to_string());
                     }
                 }
-                Opcode::Div => {
+                IROpcode::Div(_) => {
                     if value_stack.len() >= 2 {
                         let b = value_stack.pop().unwrap();
                         let a = value_stack.pop().unwrap();
@@ -184,7 +213,7 @@ impl CraneliftBackend {
                         return Err("Stack underflow on Div".to_string());
                     }
                 }
-                Opcode::Mod => {
+                IROpcode::Mod(_) => {
                     // Modulo is more complex for floats, use fmod-like operation
                     // For now, we'll compute a - b * floor(a / b)
                     if value_stack.len() >= 2 {
@@ -199,12 +228,12 @@ impl CraneliftBackend {
                         return Err("Stack underflow on Mod".to_string());
                     }
                 }
-                Opcode::Exp => {
+                IROpcode::Exp(_) => {
                     // Exponentiation is not directly supported in Cranelift
                     // Fall back to interpreter for this opcode
                     return Err("Exponentiation not supported in JIT".to_string());
                 }
-                Opcode::Neg => {
+                IROpcode::Neg(_) => {
                     if let Some(a) = value_stack.pop() {
                         let result = builder.ins().fneg(a);
                         value_stack.push(result);
@@ -212,7 +241,18 @@ impl CraneliftBackend {
                         return Err("Stack underflow on Neg".to_string());
                     }
                 }
-                Opcode::Return => {
+                IROpcode::LoadProperty(_) => {
+                    // Objects are not numbers here, so their properties load as NaN
+                    value_stack.pop().ok_or("Stack underflow on LoadProperty")?;
+                    let val = builder.ins().f64const(f64::NAN);
+                    value_stack.push(val);
+                }
+                IROpcode::GetIndex | IROpcode::GetIndexUnchecked | IROpcode::LengthGuard(_) => {
+                    // Values here are plain numbers with no arrays to index or
+                    // lengths to guard, so element access stays in the interpreter
+                    return Err("Array element access not supported in JIT".to_string());
+                }
+                IROpcode::Return => {
                     let ret_val = value_stack.pop().unwrap_or_else(|| builder.ins().f64const(0.0));
                     builder.ins().return_(&[ret_val]);
                     has_return = true;
@@ -245,7 +285,7 @@ impl Default for CraneliftBackend {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use bytecode_system::{BytecodeChunk, Opcode, Value as BcValue};
+    use bytecode_system::Opcode;
 
     #[test]
     fn test_cranelift_backend_creation() {
@@ -460,6 +500,38 @@ mod tests {
         assert_eq!(result, 0.0);
     }
 
+    #[test]
+    fn test_compile_ir_with_registers() {
+        let mut backend = CraneliftBackend::new().unwrap();
+        let mut ir = IRFunction::new();
+        ir.constants.push(BcValue::Number(42.0));
+        ir.register_count = 2;
+        ir.emit(IROpcode::LoadConst(0), 0);
+        ir.emit(IROpcode::StoreReg(1), 1);
+        ir.emit(IROpcode::LoadReg(0), 2);
+        ir.emit(IROpcode::Pop, 3);
+        ir.emit(IROpcode::LoadReg(1), 4);
+        ir.emit(IROpcode::Return, 5);
+
+        let compiled = backend.compile_ir(&ir).unwrap();
+        let func_ptr: extern "C" fn() -> f64 = unsafe { std::mem::transmute(compiled.code_ptr) };
+        assert_eq!(func_ptr(), 42.0);
+    }
+
+    #[test]
+    fn test_element_access_falls_back() {
+        let mut backend = CraneliftBackend::new().unwrap();
+        for opcode in [IROpcode::GetIndex, IROpcode::GetIndexUnchecked, IROpcode::LengthGuard(0)] {
+            let mut ir = IRFunction::new();
+            ir.register_count = 2;
+            ir.emit(IROpcode::LoadReg(0), 0);
+            ir.emit(IROpcode::LoadReg(1), 1);
+            ir.emit(opcode, 2);
+            ir.emit(IROpcode::Return, 3);
+            assert!(backend.compile_ir(&ir).is_err());
+        }
+    }
+
     #[test]
     fn test_stack_underflow_add() {
         let mut backend = CraneliftBackend::new().unwrap();
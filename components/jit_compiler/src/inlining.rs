@@ -148,13 +148,17 @@ impl FunctionInfo {
         let has_exception_handlers = chunk.instructions.iter().any(|inst| {
             matches!(
                 inst.opcode,
-                bytecode_system::Opcode::PushTry(_) | bytecode_system::Opcode::Throw
+                bytecode_system::Opcode::PushTry(_)
+                    | bytecode_system::Opcode::PushFinally(_)
+                    | bytecode_system::Opcode::Throw
             )
         });
         let is_generator_or_async = chunk.instructions.iter().any(|inst| {
             matches!(
                 inst.opcode,
-                bytecode_system::Opcode::Await | bytecode_system::Opcode::CreateAsyncFunction(_,_)
+                bytecode_system::Opcode::Await
+                    | bytecode_system::Opcode::CreateAsyncFunction(_, _)
+                    | bytecode_system::Opcode::InitialYield
             )
         });
 
@@ -954,6 +958,12 @@ impl Inliner {
             // Register operations need remapping
             IROpcode::LoadReg(reg) => IROpcode::LoadReg(register_base + reg),
             IROpcode::StoreReg(reg) => IROpcode::StoreReg(register_base + reg),
+            IROpcode::AddAssignReg(reg) => IROpcode::AddAssignReg(register_base + reg),
+            IROpcode::SubAssignReg(reg) => IROpcode::SubAssignReg(register_base + reg),
+            IROpcode::MulAssignReg(reg) => IROpcode::MulAssignReg(register_base + reg),
+            IROpcode::DivAssignReg(reg) => IROpcode::DivAssignReg(register_base + reg),
+            IROpcode::ModAssignReg(reg) => IROpcode::ModAssignReg(register_base + reg),
+            IROpcode::ExpAssignReg(reg) => IROpcode::ExpAssignReg(register_base + reg),
             IROpcode::LoadUpvalue(idx) => IROpcode::LoadUpvalue(*idx),
             IROpcode::StoreUpvalue(idx) => IROpcode::StoreUpvalue(*idx),
 
@@ -961,6 +971,10 @@ impl Inliner {
             IROpcode::Jump(target) => IROpcode::Jump(target + offset_adjustment),
             IROpcode::JumpIfTrue(target) => IROpcode::JumpIfTrue(target + offset_adjustment),
             IROpcode::JumpIfFalse(target) => IROpcode::JumpIfFalse(target + offset_adjustment),
+            IROpcode::OptionalChainStart(reg) => IROpcode::OptionalChainStart(register_base + reg),
+            IROpcode::OptionalChainShortCircuit(reg, target) => {
+                IROpcode::OptionalChainShortCircuit(register_base + reg, target + offset_adjustment)
+            }
 
             // Return becomes a jump to the continuation (simplified: keep as return for now)
             // In full implementation, would track return target
@@ -5,6 +5,7 @@
 
 use bytecode_system::{BytecodeChunk, Opcode};
 use core_types::TypeInfo;
+use std::collections::{HashMap, HashSet};
 
 /// IR operation types
 #[derive(Debug, Clone, PartialEq)]
@@ -23,6 +24,18 @@ pub enum IROpcode {
     LoadReg(u32),
     /// Store to register
     StoreReg(u32),
+    /// Add the popped value to a register in place and push the result
+    AddAssignReg(u32),
+    /// Subtract the popped value from a register in place and push the result
+    SubAssignReg(u32),
+    /// Multiply a register by the popped value in place and push the result
+    MulAssignReg(u32),
+    /// Divide a register by the popped value in place and push the result
+    DivAssignReg(u32),
+    /// Take a register modulo the popped value in place and push the result
+    ModAssignReg(u32),
+    /// Raise a register to the popped value in place and push the result
+    ExpAssignReg(u32),
     /// Add two values (with optional type specialization)
     Add(Option<TypeInfo>),
     /// Subtract two values
@@ -35,6 +48,20 @@ pub enum IROpcode {
     Mod(Option<TypeInfo>),
     /// Exponentiation operation
     Exp(Option<TypeInfo>),
+    /// Bitwise AND
+    BitAnd,
+    /// Bitwise OR
+    BitOr,
+    /// Bitwise XOR
+    BitXor,
+    /// Bitwise NOT
+    BitNot,
+    /// Left shift
+    ShiftLeft,
+    /// Sign-propagating right shift
+    ShiftRight,
+    /// Zero-fill right shift
+    ShiftRightUnsigned,
     /// Negate value
     Neg(Option<TypeInfo>),
     /// Logical NOT
@@ -61,6 +88,10 @@ pub enum IROpcode {
     JumpIfTrue(usize),
     /// Jump if false
     JumpIfFalse(usize),
+    /// Save the stack height for an optional chain in a register
+    OptionalChainStart(u32),
+    /// End an optional chain early with undefined if the top value is nullish
+    OptionalChainShortCircuit(u32, usize),
     /// Return from function
     Return,
     /// Create object
@@ -83,6 +114,8 @@ pub enum IROpcode {
     CreateClosure(usize),
     /// Call function
     Call(u8),
+    /// Call function in tail position, reusing the current frame
+    TailCall(u8),
     /// Type guard (for speculation)
     TypeGuard(TypeInfo),
     /// Deoptimization point
@@ -110,23 +143,71 @@ pub enum IROpcode {
     /// Create async function
     CreateAsyncFunction(usize),
 
+    // Generator operations
+    /// Suspend a freshly called generator, returning its generator object
+    InitialYield,
+    /// Suspend the running generator, yielding top of stack
+    Yield,
+    /// Replace an iterable with an iterator over it
+    GetIterator,
+    /// Drive a delegated iterator (`yield*`)
+    YieldDelegate,
+    /// Step an iterator for `for-of`
+    IteratorNext,
+
+    // Debugging
+    /// Pause in the attached debugger (`debugger`)
+    Debugger,
+
     // Array and method operations
     /// Get array element by index (stack: [array, index] -> [value])
     GetIndex,
+    /// Get array element by index with the bounds check elided (stack: [array, index] -> [value])
+    GetIndexUnchecked,
+    /// Deoptimize unless an array still has the length its loop condition checked (guard ID)
+    LengthGuard(u32),
     /// Set array element by index (stack: [array, index, value] -> [])
     SetIndex,
+    /// Load class private field (stack: [object] -> [value])
+    LoadPrivate(String),
+    /// Store existing class private field (stack: [object, value] -> [value])
+    StorePrivate(String),
+    /// Add class private field to a new instance (stack: [object, value] -> [value])
+    DefinePrivate(String),
+    /// Push `new.target` of the running function (stack: [] -> [value])
+    LoadNewTarget,
+    /// Copy own enumerable properties onto an object (stack: [target, source] -> [target])
+    CopyDataProperties,
+    /// Define a getter on an object (stack: [object, key, function] -> [object])
+    DefineGetter,
+    /// Define a setter on an object (stack: [object, key, function] -> [object])
+    DefineSetter,
     /// Create array with given number of elements from stack
     CreateArray(usize),
+    /// Append a value to an array (stack: [array, value] -> [array])
+    ArrayPush,
+    /// Append an iterable's elements to an array (stack: [array, iterable] -> [array])
+    ArraySpread,
     /// Create RegExp object with pattern and flags (constant pool indices)
     CreateRegExp(usize, usize),
     /// Call method on object (stack: [object, method_name, args...] -> [result])
     CallMethod(u8),
     /// Call constructor with new (stack: [constructor, args...] -> [instance])
     CallNew(u8),
+    /// Call function with an argument array (stack: [function, args] -> [result])
+    CallSpread,
+    /// Call method with an argument array (stack: [object, method, args] -> [result])
+    CallMethodSpread,
+    /// Call constructor with an argument array (stack: [constructor, args] -> [instance])
+    CallNewSpread,
     /// typeof operator - push type string
     Typeof,
     /// void operator - evaluate expression, push undefined
     Void,
+    /// Unary plus - convert with ToNumber
+    ToNumber,
+    /// Convert with ToString
+    ToString,
     /// instanceof operator - check if object is instance of constructor
     Instanceof,
     /// in operator - check if property exists in object
@@ -139,6 +220,22 @@ pub enum IROpcode {
     Deoptimize,
 }
 
+impl IROpcode {
+    /// Register written by this instruction, if any
+    pub fn stored_register(&self) -> Option<u32> {
+        match self {
+            IROpcode::StoreReg(reg)
+            | IROpcode::AddAssignReg(reg)
+            | IROpcode::SubAssignReg(reg)
+            | IROpcode::MulAssignReg(reg)
+            | IROpcode::DivAssignReg(reg)
+            | IROpcode::ModAssignReg(reg)
+            | IROpcode::ExpAssignReg(reg) => Some(*reg),
+            _ => None,
+        }
+    }
+}
+
 /// Single IR instruction
 #[derive(Debug, Clone, PartialEq)]
 pub struct IRInstruction {
@@ -196,12 +293,25 @@ impl IRFunction {
                 Opcode::StoreGlobal(name) => IROpcode::StoreGlobal(name.clone()),
                 Opcode::LoadLocal(reg) => IROpcode::LoadReg(reg.0),
                 Opcode::StoreLocal(reg) => IROpcode::StoreReg(reg.0),
+                Opcode::AddAssignLocal(reg) => IROpcode::AddAssignReg(reg.0),
+                Opcode::SubAssignLocal(reg) => IROpcode::SubAssignReg(reg.0),
+                Opcode::MulAssignLocal(reg) => IROpcode::MulAssignReg(reg.0),
+                Opcode::DivAssignLocal(reg) => IROpcode::DivAssignReg(reg.0),
+                Opcode::ModAssignLocal(reg) => IROpcode::ModAssignReg(reg.0),
+                Opcode::ExpAssignLocal(reg) => IROpcode::ExpAssignReg(reg.0),
                 Opcode::Add => IROpcode::Add(None),
                 Opcode::Sub => IROpcode::Sub(None),
                 Opcode::Mul => IROpcode::Mul(None),
                 Opcode::Div => IROpcode::Div(None),
                 Opcode::Mod => IROpcode::Mod(None),
                 Opcode::Exp => IROpcode::Exp(None),
+                Opcode::BitAnd => IROpcode::BitAnd,
+                Opcode::BitOr => IROpcode::BitOr,
+                Opcode::BitXor => IROpcode::BitXor,
+                Opcode::BitNot => IROpcode::BitNot,
+                Opcode::ShiftLeft => IROpcode::ShiftLeft,
+                Opcode::ShiftRight => IROpcode::ShiftRight,
+                Opcode::ShiftRightUnsigned => IROpcode::ShiftRightUnsigned,
                 Opcode::Neg => IROpcode::Neg(None),
                 Opcode::Not => IROpcode::Not,
                 Opcode::Equal => IROpcode::Equal,
@@ -215,6 +325,10 @@ impl IRFunction {
                 Opcode::Jump(target) => IROpcode::Jump(*target),
                 Opcode::JumpIfTrue(target) => IROpcode::JumpIfTrue(*target),
                 Opcode::JumpIfFalse(target) => IROpcode::JumpIfFalse(*target),
+                Opcode::OptionalChainStart(reg) => IROpcode::OptionalChainStart(reg.0),
+                Opcode::OptionalChainShortCircuit(reg, target) => {
+                    IROpcode::OptionalChainShortCircuit(reg.0, *target)
+                }
                 Opcode::Return => IROpcode::Return,
                 Opcode::CreateObject => IROpcode::CreateObject,
                 Opcode::LoadProperty(name) => IROpcode::LoadProperty(name.clone()),
@@ -224,6 +338,7 @@ impl IRFunction {
                 Opcode::CloseUpvalue => IROpcode::CloseUpvalue,
                 Opcode::CreateClosure(idx, _) => IROpcode::CreateClosure(*idx),
                 Opcode::Call(argc) => IROpcode::Call(*argc),
+                Opcode::TailCall(argc) => IROpcode::TailCall(*argc),
                 // Exception handling
                 Opcode::Throw => IROpcode::Throw,
                 Opcode::PushTry(offset) => IROpcode::PushTry(*offset),
@@ -235,17 +350,38 @@ impl IRFunction {
                 // Async operations
                 Opcode::Await => IROpcode::Await,
                 Opcode::CreateAsyncFunction(idx, _) => IROpcode::CreateAsyncFunction(*idx),
+                // Generator operations
+                Opcode::InitialYield => IROpcode::InitialYield,
+                Opcode::Yield => IROpcode::Yield,
+                Opcode::GetIterator => IROpcode::GetIterator,
+                Opcode::YieldDelegate => IROpcode::YieldDelegate,
+                Opcode::IteratorNext => IROpcode::IteratorNext,
+                Opcode::Debugger => IROpcode::Debugger,
                 // Array and method operations
                 Opcode::GetIndex => IROpcode::GetIndex,
                 Opcode::SetIndex => IROpcode::SetIndex,
+                Opcode::LoadPrivate(name) => IROpcode::LoadPrivate(name.clone()),
+                Opcode::StorePrivate(name) => IROpcode::StorePrivate(name.clone()),
+                Opcode::DefinePrivate(name) => IROpcode::DefinePrivate(name.clone()),
+                Opcode::LoadNewTarget => IROpcode::LoadNewTarget,
+                Opcode::CopyDataProperties => IROpcode::CopyDataProperties,
+                Opcode::DefineGetter => IROpcode::DefineGetter,
+                Opcode::DefineSetter => IROpcode::DefineSetter,
                 Opcode::CreateArray(size) => IROpcode::CreateArray(*size),
+                Opcode::ArrayPush => IROpcode::ArrayPush,
+                Opcode::ArraySpread => IROpcode::ArraySpread,
                 Opcode::CreateRegExp(pattern_idx, flags_idx) => {
                     IROpcode::CreateRegExp(*pattern_idx, *flags_idx)
                 }
                 Opcode::CallMethod(argc) => IROpcode::CallMethod(*argc),
                 Opcode::CallNew(argc) => IROpcode::CallNew(*argc),
+                Opcode::CallSpread => IROpcode::CallSpread,
+                Opcode::CallMethodSpread => IROpcode::CallMethodSpread,
+                Opcode::CallNewSpread => IROpcode::CallNewSpread,
                 Opcode::Typeof => IROpcode::Typeof,
                 Opcode::Void => IROpcode::Void,
+                Opcode::ToNumber => IROpcode::ToNumber,
+                Opcode::ToString => IROpcode::ToString,
                 Opcode::Instanceof => IROpcode::Instanceof,
                 Opcode::In => IROpcode::In,
                 Opcode::DeleteProperty(ref s) => IROpcode::DeleteProperty(s.clone()),
@@ -268,6 +404,814 @@ impl IRFunction {
     pub fn instruction_count(&self) -> usize {
         self.instructions.len()
     }
+
+    /// Sparse conditional constant propagation
+    ///
+    /// Tracks which registers and stack slots hold a known constant along
+    /// executable paths only. Where paths merge, a value stays constant if
+    /// every incoming path agrees on it. Operations on constants are replaced
+    /// by a load of their result, branches on a known condition become
+    /// unconditional, and unreachable instructions are removed. The operand
+    /// loads left behind are cleaned up by `eliminate_dead`.
+    ///
+    /// Functions with exception handlers or `await` are left unchanged.
+    /// Returns the number of values and branches folded.
+    pub fn run_sccp(&mut self) -> usize {
+        let Some(states) = self.propagate_constants() else {
+            return 0;
+        };
+
+        let mut replacements = HashMap::new();
+        let mut folded = 0;
+        for (idx, state) in states.iter().enumerate() {
+            let Some(state) = state else {
+                // Never executed
+                replacements.insert(idx, Vec::new());
+                continue;
+            };
+            let opcode = self.instructions[idx].opcode.clone();
+            match opcode {
+                IROpcode::JumpIfTrue(target) | IROpcode::JumpIfFalse(target) => {
+                    let Some(Lattice::Const(condition)) = state.stack.last() else {
+                        continue;
+                    };
+                    let jumps = condition.is_truthy() == matches!(opcode, IROpcode::JumpIfTrue(_));
+                    let mut replacement = vec![IROpcode::Pop];
+                    if jumps {
+                        replacement.push(IROpcode::Jump(target));
+                    }
+                    replacements.insert(idx, replacement);
+                    folded += 1;
+                }
+                ref op if Self::is_foldable(op) => {
+                    let Some((after, _)) = self.transfer(&opcode, state) else {
+                        continue;
+                    };
+                    let Some(Lattice::Const(value)) = after.stack.last() else {
+                        continue;
+                    };
+                    let (pops, _) = Self::stack_effect(&opcode).unwrap_or((0, 1));
+                    let mut replacement = vec![IROpcode::Pop; pops];
+                    replacement.push(self.materialize(value));
+                    replacements.insert(idx, replacement);
+                    folded += 1;
+                }
+                _ => {}
+            }
+        }
+
+        self.rewrite(replacements);
+        folded
+    }
+
+    /// Remove dead stores and unused side-effect-free values
+    ///
+    /// A store to a register that no path reads again becomes a `Pop`; a pure
+    /// instruction whose result is immediately popped is removed along with
+    /// the `Pop`, which in turn can make its operands dead. Jumps to the next
+    /// instruction are dropped. Stores are kept in
+    /// functions that create closures or install exception handlers, since
+    /// those can read registers outside the visible control flow.
+    ///
+    /// Returns the number of instructions removed.
+    pub fn eliminate_dead(&mut self) -> usize {
+        let original_len = self.instructions.len();
+        loop {
+            let mut replacements = self.dead_stores();
+            let jump_targets = self.jump_targets();
+
+            for (idx, instruction) in self.instructions.iter().enumerate() {
+                if instruction.opcode == IROpcode::Jump(idx + 1) {
+                    replacements.insert(idx, Vec::new());
+                }
+            }
+
+            let mut idx = 0;
+            while idx + 1 < self.instructions.len() {
+                let producer = &self.instructions[idx].opcode;
+                let consumer = &self.instructions[idx + 1].opcode;
+                let pair_is_dead = *consumer == IROpcode::Pop
+                    && !jump_targets.contains(&(idx + 1))
+                    && !replacements.contains_key(&idx)
+                    && !replacements.contains_key(&(idx + 1));
+                if pair_is_dead && *producer == IROpcode::Dup {
+                    replacements.insert(idx, Vec::new());
+                    replacements.insert(idx + 1, Vec::new());
+                    idx += 2;
+                } else if pair_is_dead && Self::is_pure(producer) {
+                    let (pops, _) = Self::stack_effect(producer).unwrap_or((0, 1));
+                    replacements.insert(idx, vec![IROpcode::Pop; pops]);
+                    replacements.insert(idx + 1, Vec::new());
+                    idx += 2;
+                } else {
+                    idx += 1;
+                }
+            }
+
+            if replacements.is_empty() {
+                break;
+            }
+            self.rewrite(replacements);
+        }
+        original_len.saturating_sub(self.instructions.len())
+    }
+
+    /// Entry state of every instruction reachable with the known constants,
+    /// or `None` if the function can't be analyzed
+    fn propagate_constants(&self) -> Option<Vec<Option<AbstractState>>> {
+        let len = self.instructions.len();
+        let has_unmodeled_flow = self.instructions.iter().any(|inst| {
+            // A short circuit also drops a varying number of stack values
+            matches!(
+                inst.opcode,
+                IROpcode::PushTry(_)
+                    | IROpcode::PushFinally(_)
+                    | IROpcode::Await
+                    | IROpcode::OptionalChainShortCircuit(_, _)
+                    | IROpcode::InitialYield
+                    | IROpcode::Yield
+                    | IROpcode::YieldDelegate
+            )
+        });
+        if len == 0 || has_unmodeled_flow {
+            return None;
+        }
+
+        let mut states: Vec<Option<AbstractState>> = vec![None; len];
+        // Registers hold parameters or undefined on entry; neither is known here
+        states[0] = Some(AbstractState {
+            stack: Vec::new(),
+            registers: vec![Lattice::Varying; self.register_count as usize],
+        });
+        let mut worklist = vec![0];
+
+        while let Some(idx) = worklist.pop() {
+            let state = states[idx].clone()?;
+            let opcode = &self.instructions[idx].opcode;
+            let (after, condition) = self.transfer(opcode, &state)?;
+
+            let successors = match opcode {
+                IROpcode::Jump(target) => vec![*target],
+                IROpcode::JumpIfTrue(target) | IROpcode::JumpIfFalse(target) => {
+                    let jumps_if = matches!(opcode, IROpcode::JumpIfTrue(_));
+                    match condition {
+                        // Only the edge the known condition takes is executable
+                        Some(Lattice::Const(value)) if value.is_truthy() == jumps_if => vec![*target],
+                        Some(Lattice::Const(_)) => vec![idx + 1],
+                        _ => vec![*target, idx + 1],
+                    }
+                }
+                IROpcode::Return | IROpcode::Throw | IROpcode::TailCall(_) | IROpcode::Deoptimize => {
+                    Vec::new()
+                }
+                _ => vec![idx + 1],
+            };
+
+            for successor in successors {
+                if successor >= len {
+                    // Falling or jumping off the end returns undefined
+                    if successor == len {
+                        continue;
+                    }
+                    return None;
+                }
+                match &mut states[successor] {
+                    Some(existing) => {
+                        if existing.join(&after)? {
+                            worklist.push(successor);
+                        }
+                    }
+                    slot @ None => {
+                        *slot = Some(after.clone());
+                        worklist.push(successor);
+                    }
+                }
+            }
+        }
+
+        Some(states)
+    }
+
+    /// Abstract effect of `opcode` on `state`
+    ///
+    /// Returns the state after the instruction and, for conditional jumps,
+    /// the popped condition. `None` means the stack underflowed.
+    fn transfer(&self, opcode: &IROpcode, state: &AbstractState) -> Option<(AbstractState, Option<Lattice>)> {
+        let mut after = state.clone();
+        let (pops, pushes) = Self::stack_effect(opcode).unwrap_or((0, 0));
+        if after.stack.len() < pops {
+            return None;
+        }
+        let operands = after.stack.split_off(after.stack.len() - pops);
+
+        let constant_operands: Option<Vec<Constant>> = operands
+            .iter()
+            .map(|value| match value {
+                Lattice::Const(c) => Some(c.clone()),
+                Lattice::Varying => None,
+            })
+            .collect();
+
+        let result = match opcode {
+            IROpcode::LoadConst(idx) => self
+                .constants
+                .get(*idx)
+                .and_then(Constant::from_pool)
+                .map_or(Lattice::Varying, Lattice::Const),
+            IROpcode::LoadUndefined => Lattice::Const(Constant::Undefined),
+            IROpcode::LoadNull => Lattice::Const(Constant::Null),
+            IROpcode::LoadTrue => Lattice::Const(Constant::Boolean(true)),
+            IROpcode::LoadFalse => Lattice::Const(Constant::Boolean(false)),
+            IROpcode::LoadReg(reg) => after.register(*reg),
+            IROpcode::StoreReg(reg) => {
+                after.set_register(*reg, operands[0].clone());
+                Lattice::Varying
+            }
+            IROpcode::AddAssignReg(reg)
+            | IROpcode::SubAssignReg(reg)
+            | IROpcode::MulAssignReg(reg)
+            | IROpcode::DivAssignReg(reg)
+            | IROpcode::ModAssignReg(reg)
+            | IROpcode::ExpAssignReg(reg) => {
+                after.set_register(*reg, Lattice::Varying);
+                Lattice::Varying
+            }
+            IROpcode::Dup => {
+                after.stack.push(operands[0].clone());
+                operands[0].clone()
+            }
+            IROpcode::JumpIfTrue(_) | IROpcode::JumpIfFalse(_) => {
+                return Some((after, operands.into_iter().next()));
+            }
+            op => constant_operands
+                .and_then(|values| Constant::fold(op, &values))
+                .map_or(Lattice::Varying, Lattice::Const),
+        };
+
+        if pushes > 0 {
+            after.stack.push(result);
+        }
+        Some((after, None))
+    }
+
+    /// Whether an instruction computes a value `run_sccp` may replace with a constant
+    fn is_foldable(opcode: &IROpcode) -> bool {
+        matches!(
+            opcode,
+            IROpcode::LoadReg(_)
+                | IROpcode::Add(_)
+                | IROpcode::Sub(_)
+                | IROpcode::Mul(_)
+                | IROpcode::Div(_)
+                | IROpcode::Mod(_)
+                | IROpcode::Exp(_)
+                | IROpcode::BitAnd
+                | IROpcode::BitOr
+                | IROpcode::BitXor
+                | IROpcode::BitNot
+                | IROpcode::ShiftLeft
+                | IROpcode::ShiftRight
+                | IROpcode::ShiftRightUnsigned
+                | IROpcode::Neg(_)
+                | IROpcode::Not
+                | IROpcode::Equal
+                | IROpcode::StrictEqual
+                | IROpcode::NotEqual
+                | IROpcode::StrictNotEqual
+                | IROpcode::LessThan(_)
+                | IROpcode::LessThanEqual(_)
+                | IROpcode::GreaterThan(_)
+                | IROpcode::GreaterThanEqual(_)
+                | IROpcode::Typeof
+                | IROpcode::Void
+        )
+    }
+
+    /// Whether an instruction pushing one value can be dropped when the value is unused
+    ///
+    /// Generic arithmetic and loose comparisons may call `valueOf`, so only
+    /// their number-specialized forms count.
+    fn is_pure(opcode: &IROpcode) -> bool {
+        const NUMBER: Option<TypeInfo> = Some(TypeInfo::Number);
+        match opcode {
+            IROpcode::Add(ty)
+            | IROpcode::Sub(ty)
+            | IROpcode::Mul(ty)
+            | IROpcode::Div(ty)
+            | IROpcode::Mod(ty)
+            | IROpcode::Exp(ty)
+            | IROpcode::Neg(ty)
+            | IROpcode::LessThan(ty)
+            | IROpcode::LessThanEqual(ty)
+            | IROpcode::GreaterThan(ty)
+            | IROpcode::GreaterThanEqual(ty) => *ty == NUMBER,
+            IROpcode::LoadConst(_)
+            | IROpcode::LoadUndefined
+            | IROpcode::LoadNull
+            | IROpcode::LoadTrue
+            | IROpcode::LoadFalse
+            | IROpcode::LoadReg(_)
+            | IROpcode::LoadUpvalue(_)
+            | IROpcode::LoadNewTarget
+            | IROpcode::CreateObject
+            | IROpcode::CreateArray(_)
+            | IROpcode::CreateClosure(_)
+            | IROpcode::CreateAsyncFunction(_)
+            | IROpcode::CreateRegExp(_, _)
+            | IROpcode::Not
+            | IROpcode::StrictEqual
+            | IROpcode::StrictNotEqual
+            | IROpcode::Typeof
+            | IROpcode::Void => true,
+            _ => false,
+        }
+    }
+
+    /// Values popped and pushed by an instruction; `None` for ones that leave the function
+    fn stack_effect(opcode: &IROpcode) -> Option<(usize, usize)> {
+        Some(match opcode {
+            IROpcode::LoadConst(_)
+            | IROpcode::LoadUndefined
+            | IROpcode::LoadNull
+            | IROpcode::LoadTrue
+            | IROpcode::LoadFalse
+            | IROpcode::LoadReg(_)
+            | IROpcode::LoadGlobal(_)
+            | IROpcode::LoadUpvalue(_)
+            | IROpcode::LoadNewTarget
+            | IROpcode::CreateObject
+            | IROpcode::CreateClosure(_)
+            | IROpcode::CreateAsyncFunction(_)
+            | IROpcode::CreateRegExp(_, _)
+            | IROpcode::DeleteGlobal(_) => (0, 1),
+            IROpcode::StoreReg(_)
+            | IROpcode::StoreGlobal(_)
+            | IROpcode::StoreUpvalue(_)
+            | IROpcode::JumpIfTrue(_)
+            | IROpcode::JumpIfFalse(_)
+            | IROpcode::Pop => (1, 0),
+            IROpcode::Neg(_)
+            | IROpcode::Not
+            | IROpcode::BitNot
+            | IROpcode::LoadProperty(_)
+            | IROpcode::LoadPrivate(_)
+            | IROpcode::Typeof
+            | IROpcode::Void
+            | IROpcode::ToNumber
+            | IROpcode::ToString
+            | IROpcode::DeleteProperty(_)
+            | IROpcode::AddAssignReg(_)
+            | IROpcode::SubAssignReg(_)
+            | IROpcode::MulAssignReg(_)
+            | IROpcode::DivAssignReg(_)
+            | IROpcode::ModAssignReg(_)
+            | IROpcode::ExpAssignReg(_)
+            | IROpcode::Await
+            | IROpcode::Yield
+            | IROpcode::GetIterator => (1, 1),
+            IROpcode::Add(_)
+            | IROpcode::Sub(_)
+            | IROpcode::Mul(_)
+            | IROpcode::Div(_)
+            | IROpcode::Mod(_)
+            | IROpcode::Exp(_)
+            | IROpcode::BitAnd
+            | IROpcode::BitOr
+            | IROpcode::BitXor
+            | IROpcode::ShiftLeft
+            | IROpcode::ShiftRight
+            | IROpcode::ShiftRightUnsigned
+            | IROpcode::Equal
+            | IROpcode::StrictEqual
+            | IROpcode::NotEqual
+            | IROpcode::StrictNotEqual
+            | IROpcode::LessThan(_)
+            | IROpcode::LessThanEqual(_)
+            | IROpcode::GreaterThan(_)
+            | IROpcode::GreaterThanEqual(_)
+            | IROpcode::StoreProperty(_)
+            | IROpcode::GetIndex
+            | IROpcode::GetIndexUnchecked
+            | IROpcode::StorePrivate(_)
+            | IROpcode::DefinePrivate(_)
+            | IROpcode::CopyDataProperties
+            | IROpcode::ArrayPush
+            | IROpcode::ArraySpread
+            | IROpcode::CallSpread
+            | IROpcode::CallNewSpread
+            | IROpcode::Instanceof
+            | IROpcode::In
+            | IROpcode::YieldDelegate => (2, 1),
+            IROpcode::SetIndex
+            | IROpcode::CallMethodSpread
+            | IROpcode::DefineGetter
+            | IROpcode::DefineSetter => (3, 1),
+            IROpcode::Dup | IROpcode::IteratorNext => (1, 2),
+            IROpcode::CreateArray(count) => (*count, 1),
+            IROpcode::Call(argc) | IROpcode::CallNew(argc) => (*argc as usize + 1, 1),
+            IROpcode::CallMethod(argc) => (*argc as usize + 2, 1),
+            IROpcode::Jump(_)
+            | IROpcode::OptionalChainStart(_)
+            | IROpcode::OptionalChainShortCircuit(_, _)
+            | IROpcode::CloseUpvalue
+            | IROpcode::TypeGuard(_)
+            | IROpcode::DeoptPoint(_)
+            | IROpcode::LengthGuard(_)
+            | IROpcode::PushTry(_)
+            | IROpcode::PopTry
+            | IROpcode::PushFinally(_)
+            | IROpcode::PopFinally
+            | IROpcode::InitialYield
+            | IROpcode::Debugger => (0, 0),
+            IROpcode::Return
+            | IROpcode::Throw
+            | IROpcode::TailCall(_)
+            | IROpcode::Deoptimize => return None,
+        })
+    }
+
+    /// Stores to registers that no later instruction reads, each mapped to a `Pop`
+    fn dead_stores(&self) -> HashMap<usize, Vec<IROpcode>> {
+        let mut dead = HashMap::new();
+        let escapes = self.instructions.iter().any(|inst| {
+            matches!(
+                inst.opcode,
+                IROpcode::CreateClosure(_)
+                    | IROpcode::CreateAsyncFunction(_)
+                    | IROpcode::PushTry(_)
+                    | IROpcode::PushFinally(_)
+                    | IROpcode::Debugger
+            )
+        });
+        if escapes {
+            return dead;
+        }
+
+        // Backward liveness of registers to a fixpoint
+        let len = self.instructions.len();
+        let mut live_in: Vec<HashSet<u32>> = vec![HashSet::new(); len];
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for idx in (0..len).rev() {
+                let mut live = self.live_out(idx, &live_in);
+                match self.instructions[idx].opcode {
+                    IROpcode::LoadReg(reg) => {
+                        live.insert(reg);
+                    }
+                    IROpcode::StoreReg(reg) => {
+                        live.remove(&reg);
+                    }
+                    // Compound assignments read the register before writing it
+                    ref op => {
+                        if let Some(reg) = op.stored_register() {
+                            live.insert(reg);
+                        }
+                    }
+                }
+                if live != live_in[idx] {
+                    live_in[idx] = live;
+                    changed = true;
+                }
+            }
+        }
+
+        for idx in 0..len {
+            if let IROpcode::StoreReg(reg) = self.instructions[idx].opcode {
+                if !self.live_out(idx, &live_in).contains(&reg) {
+                    dead.insert(idx, vec![IROpcode::Pop]);
+                }
+            }
+        }
+        dead
+    }
+
+    /// Registers live after instruction `idx`, given each instruction's live-in set
+    fn live_out(&self, idx: usize, live_in: &[HashSet<u32>]) -> HashSet<u32> {
+        let successors = match self.instructions[idx].opcode {
+            IROpcode::Jump(target) => vec![target],
+            IROpcode::JumpIfTrue(target)
+            | IROpcode::JumpIfFalse(target)
+            | IROpcode::OptionalChainShortCircuit(_, target) => vec![target, idx + 1],
+            IROpcode::Return | IROpcode::Throw | IROpcode::TailCall(_) | IROpcode::Deoptimize => {
+                Vec::new()
+            }
+            _ => vec![idx + 1],
+        };
+        successors
+            .into_iter()
+            .filter_map(|successor| live_in.get(successor))
+            .flatten()
+            .copied()
+            .collect()
+    }
+
+    /// Instructions some jump or handler transfers control to
+    fn jump_targets(&self) -> HashSet<usize> {
+        self.instructions
+            .iter()
+            .filter_map(|inst| Self::jump_target(&inst.opcode))
+            .collect()
+    }
+
+    fn jump_target(opcode: &IROpcode) -> Option<usize> {
+        match opcode {
+            IROpcode::Jump(target)
+            | IROpcode::JumpIfTrue(target)
+            | IROpcode::JumpIfFalse(target)
+            | IROpcode::OptionalChainShortCircuit(_, target)
+            | IROpcode::PushTry(target)
+            | IROpcode::PushFinally(target) => Some(*target),
+            _ => None,
+        }
+    }
+
+    /// Instruction loading `value`, adding it to the constant pool if needed
+    fn materialize(&mut self, value: &Constant) -> IROpcode {
+        let pooled = match value {
+            Constant::Undefined => return IROpcode::LoadUndefined,
+            Constant::Null => return IROpcode::LoadNull,
+            Constant::Boolean(true) => return IROpcode::LoadTrue,
+            Constant::Boolean(false) => return IROpcode::LoadFalse,
+            Constant::Number(n) => bytecode_system::Value::Number(*n),
+            Constant::String(s) => bytecode_system::Value::String(s.clone()),
+        };
+        let existing = self
+            .constants
+            .iter()
+            .position(|c| Constant::from_pool(c).as_ref() == Some(value));
+        let idx = existing.unwrap_or_else(|| {
+            self.constants.push(pooled);
+            self.constants.len() - 1
+        });
+        IROpcode::LoadConst(idx)
+    }
+
+    /// Replace instructions by index, keeping jump targets on the same code
+    ///
+    /// An empty replacement deletes the instruction; jumps to it land on the
+    /// next surviving instruction. Targets inside replacements refer to
+    /// original indices too.
+    fn rewrite(&mut self, mut replacements: HashMap<usize, Vec<IROpcode>>) {
+        if replacements.is_empty() {
+            return;
+        }
+        let mut new_index = Vec::with_capacity(self.instructions.len() + 1);
+        let mut rewritten = Vec::with_capacity(self.instructions.len());
+        for (idx, instruction) in self.instructions.drain(..).enumerate() {
+            new_index.push(rewritten.len());
+            match replacements.remove(&idx) {
+                Some(opcodes) => rewritten.extend(
+                    opcodes
+                        .into_iter()
+                        .map(|opcode| IRInstruction::new(opcode, instruction.bytecode_offset)),
+                ),
+                None => rewritten.push(instruction),
+            }
+        }
+        new_index.push(rewritten.len());
+
+        for instruction in &mut rewritten {
+            if let IROpcode::Jump(target)
+            | IROpcode::JumpIfTrue(target)
+            | IROpcode::JumpIfFalse(target)
+            | IROpcode::PushTry(target)
+            | IROpcode::PushFinally(target) = &mut instruction.opcode
+            {
+                *target = new_index.get(*target).copied().unwrap_or(*target);
+            }
+        }
+        self.instructions = rewritten;
+    }
+}
+
+/// Primitive value known at compile time
+#[derive(Debug, Clone)]
+enum Constant {
+    Undefined,
+    Null,
+    Boolean(bool),
+    Number(f64),
+    String(String),
+}
+
+impl PartialEq for Constant {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Constant::Undefined, Constant::Undefined) | (Constant::Null, Constant::Null) => true,
+            (Constant::Boolean(a), Constant::Boolean(b)) => a == b,
+            // Bitwise, so NaN matches itself and 0 and -0 stay distinct
+            (Constant::Number(a), Constant::Number(b)) => a.to_bits() == b.to_bits(),
+            (Constant::String(a), Constant::String(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Constant {
+    fn from_pool(value: &bytecode_system::Value) -> Option<Self> {
+        match value {
+            bytecode_system::Value::Undefined => Some(Constant::Undefined),
+            bytecode_system::Value::Null => Some(Constant::Null),
+            bytecode_system::Value::Boolean(b) => Some(Constant::Boolean(*b)),
+            bytecode_system::Value::Number(n) => Some(Constant::Number(*n)),
+            bytecode_system::Value::String(s) => Some(Constant::String(s.clone())),
+            _ => None,
+        }
+    }
+
+    fn is_truthy(&self) -> bool {
+        match self {
+            Constant::Undefined | Constant::Null => false,
+            Constant::Boolean(b) => *b,
+            Constant::Number(n) => *n != 0.0 && !n.is_nan(),
+            Constant::String(s) => !s.is_empty(),
+        }
+    }
+
+    fn to_number(&self) -> Option<f64> {
+        match self {
+            Constant::Undefined => Some(f64::NAN),
+            Constant::Null => Some(0.0),
+            Constant::Boolean(b) => Some(if *b { 1.0 } else { 0.0 }),
+            Constant::Number(n) => Some(*n),
+            // Leave the string numeric grammar to the runtime
+            Constant::String(_) => None,
+        }
+    }
+
+    /// String form, for the values whose formatting is unambiguous
+    fn to_js_string(&self) -> Option<String> {
+        match self {
+            Constant::Undefined => Some("undefined".to_string()),
+            Constant::Null => Some("null".to_string()),
+            Constant::Boolean(b) => Some(b.to_string()),
+            Constant::Number(n) if n.fract() == 0.0 && n.abs() < 1e15 && *n != 0.0 => {
+                Some(format!("{}", *n as i64))
+            }
+            Constant::Number(n) if *n == 0.0 => Some("0".to_string()),
+            Constant::Number(_) => None,
+            Constant::String(s) => Some(s.clone()),
+        }
+    }
+
+    fn type_of(&self) -> &'static str {
+        match self {
+            Constant::Undefined => "undefined",
+            Constant::Null => "object",
+            Constant::Boolean(_) => "boolean",
+            Constant::Number(_) => "number",
+            Constant::String(_) => "string",
+        }
+    }
+
+    fn strict_equals(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Constant::Number(a), Constant::Number(b)) => a == b,
+            (a, b) => a == b,
+        }
+    }
+
+    /// Result of `op` applied to constant operands, if it can be computed here
+    fn fold(op: &IROpcode, operands: &[Constant]) -> Option<Constant> {
+        let number = |f: fn(f64, f64) -> f64| -> Option<Constant> {
+            Some(Constant::Number(f(operands[0].to_number()?, operands[1].to_number()?)))
+        };
+        let int32 = |f: fn(i32, i32) -> i32| -> Option<Constant> {
+            let (a, b) = (operands[0].to_number()?, operands[1].to_number()?);
+            Some(Constant::Number(f(to_int32(a), to_int32(b)) as f64))
+        };
+        let compare = |f: fn(f64, f64) -> bool| -> Option<Constant> {
+            Some(Constant::Boolean(f(operands[0].to_number()?, operands[1].to_number()?)))
+        };
+        match op {
+            IROpcode::Add(_) => match (&operands[0], &operands[1]) {
+                (Constant::String(_), _) | (_, Constant::String(_)) => Some(Constant::String(format!(
+                    "{}{}",
+                    operands[0].to_js_string()?,
+                    operands[1].to_js_string()?
+                ))),
+                _ => number(|a, b| a + b),
+            },
+            IROpcode::Sub(_) => number(|a, b| a - b),
+            IROpcode::Mul(_) => number(|a, b| a * b),
+            IROpcode::Div(_) => number(|a, b| a / b),
+            IROpcode::Mod(_) => number(|a, b| a % b),
+            IROpcode::Exp(_) => number(exponentiate),
+            IROpcode::BitAnd => int32(|a, b| a & b),
+            IROpcode::BitOr => int32(|a, b| a | b),
+            IROpcode::BitXor => int32(|a, b| a ^ b),
+            IROpcode::ShiftLeft => int32(|a, b| a.wrapping_shl(b as u32)),
+            IROpcode::ShiftRight => int32(|a, b| a.wrapping_shr(b as u32)),
+            IROpcode::ShiftRightUnsigned => Some(Constant::Number(
+                (to_int32(operands[0].to_number()?) as u32)
+                    .wrapping_shr(to_int32(operands[1].to_number()?) as u32) as f64,
+            )),
+            IROpcode::BitNot => Some(Constant::Number(!to_int32(operands[0].to_number()?) as f64)),
+            IROpcode::Neg(_) => Some(Constant::Number(-operands[0].to_number()?)),
+            IROpcode::Not => Some(Constant::Boolean(!operands[0].is_truthy())),
+            IROpcode::StrictEqual => Some(Constant::Boolean(operands[0].strict_equals(&operands[1]))),
+            IROpcode::StrictNotEqual => Some(Constant::Boolean(!operands[0].strict_equals(&operands[1]))),
+            IROpcode::Equal | IROpcode::NotEqual => {
+                let equal = match (&operands[0], &operands[1]) {
+                    (
+                        Constant::Undefined | Constant::Null,
+                        Constant::Undefined | Constant::Null,
+                    ) => true,
+                    (Constant::Undefined | Constant::Null, _) | (_, Constant::Undefined | Constant::Null) => false,
+                    (a, b) if std::mem::discriminant(a) == std::mem::discriminant(b) => a.strict_equals(b),
+                    // Mixed types go through ToNumber; leave them to the runtime
+                    _ => return None,
+                };
+                Some(Constant::Boolean(equal == (*op == IROpcode::Equal)))
+            }
+            IROpcode::LessThan(_) => compare(|a, b| a < b),
+            IROpcode::LessThanEqual(_) => compare(|a, b| a <= b),
+            IROpcode::GreaterThan(_) => compare(|a, b| a > b),
+            IROpcode::GreaterThanEqual(_) => compare(|a, b| a >= b),
+            IROpcode::Typeof => Some(Constant::String(operands[0].type_of().to_string())),
+            IROpcode::Void => Some(Constant::Undefined),
+            _ => None,
+        }
+    }
+}
+
+/// `a ** b`; unlike `powf`, `1 ** NaN` and `(-1) ** Infinity` are NaN
+fn exponentiate(a: f64, b: f64) -> f64 {
+    if b.is_nan() || (a.abs() == 1.0 && b.is_infinite()) {
+        f64::NAN
+    } else {
+        a.powf(b)
+    }
+}
+
+/// ToInt32: a number wrapped modulo 2^32 into the signed 32-bit range
+fn to_int32(n: f64) -> i32 {
+    if n.is_finite() {
+        n.trunc().rem_euclid(4_294_967_296.0) as u32 as i32
+    } else {
+        0
+    }
+}
+
+/// Constant propagation lattice: a single known constant, or any value
+#[derive(Debug, Clone, PartialEq)]
+enum Lattice {
+    Const(Constant),
+    Varying,
+}
+
+impl Lattice {
+    fn meet(&self, other: &Lattice) -> Lattice {
+        match (self, other) {
+            (Lattice::Const(a), Lattice::Const(b)) if a == b => self.clone(),
+            _ => Lattice::Varying,
+        }
+    }
+}
+
+/// Values of the operand stack and registers at one program point
+#[derive(Debug, Clone, PartialEq)]
+struct AbstractState {
+    stack: Vec<Lattice>,
+    registers: Vec<Lattice>,
+}
+
+impl AbstractState {
+    fn register(&self, reg: u32) -> Lattice {
+        self.registers
+            .get(reg as usize)
+            .cloned()
+            .unwrap_or(Lattice::Varying)
+    }
+
+    fn set_register(&mut self, reg: u32, value: Lattice) {
+        let idx = reg as usize;
+        if idx >= self.registers.len() {
+            self.registers.resize(idx + 1, Lattice::Varying);
+        }
+        self.registers[idx] = value;
+    }
+
+    /// Merge the state of another incoming edge, the stack IR's equivalent of
+    /// phi nodes; returns whether anything changed, or `None` if the stack
+    /// heights disagree
+    fn join(&mut self, other: &AbstractState) -> Option<bool> {
+        if self.stack.len() != other.stack.len() {
+            return None;
+        }
+        let before = self.clone();
+        for (mine, theirs) in self.stack.iter_mut().zip(&other.stack) {
+            *mine = mine.meet(theirs);
+        }
+        let len = self.registers.len().max(other.registers.len());
+        self.registers.resize(len, Lattice::Varying);
+        for (idx, mine) in self.registers.iter_mut().enumerate() {
+            let theirs = other.registers.get(idx).cloned().unwrap_or(Lattice::Varying);
+            *mine = mine.meet(&theirs);
+        }
+        Some(*self != before)
+    }
 }
 
 impl Default for IRFunction {
@@ -333,4 +1277,122 @@ mod tests {
 
         assert_ne!(add_generic, add_number);
     }
+
+    fn opcodes(ir: &IRFunction) -> Vec<IROpcode> {
+        ir.instructions.iter().map(|inst| inst.opcode.clone()).collect()
+    }
+
+    fn pool_value(ir: &IRFunction, opcode: &IROpcode) -> Option<bytecode_system::Value> {
+        match opcode {
+            IROpcode::LoadConst(idx) => ir.constants.get(*idx).cloned(),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_fold_exponentiation_follows_js() {
+        let fold = |a: f64, b: f64| match Constant::fold(
+            &IROpcode::Exp(None),
+            &[Constant::Number(a), Constant::Number(b)],
+        ) {
+            Some(Constant::Number(n)) => n,
+            other => panic!("expected a number, got {:?}", other),
+        };
+        assert!(fold(1.0, f64::NAN).is_nan());
+        assert!(fold(1.0, f64::INFINITY).is_nan());
+        assert!(fold(-1.0, f64::NEG_INFINITY).is_nan());
+        assert_eq!(fold(2.0, 10.0), 1024.0);
+        assert_eq!(fold(f64::NAN, 0.0), 1.0);
+    }
+
+    #[test]
+    fn test_sccp_folds_constant_through_merge() {
+        // r0 = arg ? 4 : 4; return r0 + 1;
+        let mut ir = IRFunction::new();
+        ir.constants = vec![
+            bytecode_system::Value::Number(4.0),
+            bytecode_system::Value::Number(1.0),
+        ];
+        ir.register_count = 2;
+        ir.emit(IROpcode::LoadReg(1), 0);
+        ir.emit(IROpcode::JumpIfFalse(5), 1);
+        ir.emit(IROpcode::LoadConst(0), 2);
+        ir.emit(IROpcode::StoreReg(0), 3);
+        ir.emit(IROpcode::Jump(7), 4);
+        ir.emit(IROpcode::LoadConst(0), 5);
+        ir.emit(IROpcode::StoreReg(0), 6);
+        ir.emit(IROpcode::LoadReg(0), 7);
+        ir.emit(IROpcode::LoadConst(1), 8);
+        ir.emit(IROpcode::Add(None), 9);
+        ir.emit(IROpcode::Return, 10);
+
+        assert!(ir.run_sccp() > 0);
+        ir.eliminate_dead();
+
+        let ops = opcodes(&ir);
+        assert!(!ops.iter().any(|op| matches!(op, IROpcode::Add(_))));
+        let ret = ops.iter().position(|op| *op == IROpcode::Return).unwrap();
+        assert_eq!(
+            pool_value(&ir, &ops[ret - 1]),
+            Some(bytecode_system::Value::Number(5.0))
+        );
+    }
+
+    #[test]
+    fn test_sccp_prunes_branch_on_known_condition() {
+        // r0 = 3; if (r0 < 2) { g(); } return r0;
+        let mut ir = IRFunction::new();
+        ir.constants = vec![
+            bytecode_system::Value::Number(3.0),
+            bytecode_system::Value::Number(2.0),
+        ];
+        ir.register_count = 1;
+        ir.emit(IROpcode::LoadConst(0), 0);
+        ir.emit(IROpcode::StoreReg(0), 1);
+        ir.emit(IROpcode::LoadReg(0), 2);
+        ir.emit(IROpcode::LoadConst(1), 3);
+        ir.emit(IROpcode::LessThan(None), 4);
+        ir.emit(IROpcode::JumpIfFalse(9), 5);
+        ir.emit(IROpcode::LoadGlobal("g".to_string()), 6);
+        ir.emit(IROpcode::Call(0), 7);
+        ir.emit(IROpcode::Pop, 8);
+        ir.emit(IROpcode::LoadReg(0), 9);
+        ir.emit(IROpcode::Return, 10);
+
+        ir.run_sccp();
+        ir.eliminate_dead();
+
+        assert_eq!(opcodes(&ir), vec![IROpcode::LoadConst(0), IROpcode::Return]);
+    }
+
+    #[test]
+    fn test_eliminate_dead_keeps_side_effects() {
+        // r0 === r1; g(); r2 = 7; return undefined;
+        let mut ir = IRFunction::new();
+        ir.constants = vec![bytecode_system::Value::Number(7.0)];
+        ir.register_count = 3;
+        ir.emit(IROpcode::LoadReg(0), 0);
+        ir.emit(IROpcode::LoadReg(1), 1);
+        ir.emit(IROpcode::StrictEqual, 2);
+        ir.emit(IROpcode::Pop, 3);
+        ir.emit(IROpcode::LoadGlobal("g".to_string()), 4);
+        ir.emit(IROpcode::Call(0), 5);
+        ir.emit(IROpcode::Pop, 6);
+        ir.emit(IROpcode::LoadConst(0), 7);
+        ir.emit(IROpcode::StoreReg(2), 8);
+        ir.emit(IROpcode::LoadUndefined, 9);
+        ir.emit(IROpcode::Return, 10);
+
+        assert_eq!(ir.eliminate_dead(), 6);
+        assert_eq!(
+            opcodes(&ir),
+            vec![
+                IROpcode::LoadGlobal("g".to_string()),
+                IROpcode::Call(0),
+                IROpcode::Pop,
+                IROpcode::LoadUndefined,
+                IROpcode::Return,
+            ]
+        );
+    }
 }
@@ -33,6 +33,7 @@
 #![warn(clippy::all)]
 
 pub mod baseline;
+pub mod bounds_check;
 pub mod codegen;
 pub mod compiled_code;
 pub mod cranelift_backend;
@@ -45,6 +46,7 @@ pub mod type_specialization;
 
 // Re-export main types at crate root
 pub use baseline::BaselineJIT;
+pub use bounds_check::{BoundsCheckEliminator, BoundsCheckStats, LengthGuard};
 pub use compiled_code::CompiledCode;
 pub use cranelift_backend::{CompiledFunction, CraneliftBackend};
 pub use deopt::{Deoptimizer, InterpreterState};
@@ -3,8 +3,10 @@
 //! Speculation-based compilation that uses profiling data to generate
 //! highly optimized code with type guards and deoptimization support.
 
+use crate::bounds_check::BoundsCheckEliminator;
 use crate::compiled_code::CompiledCode;
 use crate::cranelift_backend::CraneliftBackend;
+use crate::ir::IRFunction;
 use bytecode_system::BytecodeChunk;
 use core_types::{ErrorKind, JsError, ProfileData, TypeInfo};
 
@@ -21,6 +23,8 @@ pub struct OptimizingStats {
     pub type_guards_inserted: u64,
     /// Number of deopt points inserted
     pub deopt_points_inserted: u64,
+    /// Number of array bounds checks eliminated in counted loops
+    pub bounds_checks_eliminated: u64,
 }
 
 /// Optimizing JIT compiler
@@ -120,11 +124,18 @@ impl OptimizingJIT {
             self.stats.deopt_points_inserted += 1;
         }
 
+        // Drop bounds checks that counted loops already prove
+        let mut eliminator = BoundsCheckEliminator::new();
+        let ir = eliminator.eliminate(&IRFunction::from_bytecode(chunk));
+        let bce_stats = eliminator.stats();
+        self.stats.bounds_checks_eliminated += bce_stats.bounds_checks_eliminated;
+        self.stats.deopt_points_inserted += bce_stats.length_guards_inserted;
+
         // Get the backend and compile
         let backend = self.backend.as_mut().unwrap();
 
-        // Compile to native code using Cranelift
-        let compiled_func = backend.compile_function(chunk).map_err(|e| JsError {
+        // Compile the optimized IR to native code using Cranelift
+        let compiled_func = backend.compile_ir(&ir).map_err(|e| JsError {
             kind: ErrorKind::InternalError,
             message: format!("Optimizing JIT compilation failed: {}", e),
             stack: vec![],
@@ -408,4 +419,38 @@ mod tests {
 
         assert_eq!(result, Value::Smi(42));
     }
+
+    #[test]
+    fn test_compile_counts_eliminated_bounds_checks() {
+        use bytecode_system::RegisterId;
+
+        // for (let i = 0; i < arr.length; i++) { arr[i]; }
+        let mut jit = OptimizingJIT::new();
+        let mut chunk = BytecodeChunk::new();
+        let zero = chunk.add_constant(BcValue::Number(0.0));
+        let one = chunk.add_constant(BcValue::Number(1.0));
+        chunk.register_count = 2;
+        chunk.emit(Opcode::LoadConstant(zero));
+        chunk.emit(Opcode::StoreLocal(RegisterId(1)));
+        chunk.emit(Opcode::LoadLocal(RegisterId(1))); // 2: loop header
+        chunk.emit(Opcode::LoadLocal(RegisterId(0)));
+        chunk.emit(Opcode::LoadProperty("length".to_string()));
+        chunk.emit(Opcode::LessThan);
+        chunk.emit(Opcode::JumpIfFalse(17));
+        chunk.emit(Opcode::LoadLocal(RegisterId(0)));
+        chunk.emit(Opcode::LoadLocal(RegisterId(1)));
+        chunk.emit(Opcode::GetIndex);
+        chunk.emit(Opcode::Pop);
+        chunk.emit(Opcode::LoadLocal(RegisterId(1)));
+        chunk.emit(Opcode::LoadConstant(one));
+        chunk.emit(Opcode::Add);
+        chunk.emit(Opcode::StoreLocal(RegisterId(1)));
+        chunk.emit(Opcode::Jump(2));
+        chunk.emit(Opcode::LoadUndefined);
+        chunk.emit(Opcode::Return); // 17
+
+        // The backend cannot run the loop yet, but the pass has rewritten it
+        assert!(jit.compile(&chunk, &ProfileData::new()).is_err());
+        assert_eq!(jit.stats().bounds_checks_eliminated, 1);
+    }
 }
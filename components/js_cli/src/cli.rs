@@ -31,6 +31,10 @@ pub struct Cli {
     /// Print AST
     #[arg(long = "print-ast")]
     pub print_ast: bool,
+
+    /// Allow fetching modules over the network
+    #[arg(long = "allow-net")]
+    pub allow_net: bool,
 }
 
 impl Cli {
@@ -43,6 +47,7 @@ impl Cli {
             jit: true,
             print_bytecode: false,
             print_ast: false,
+            allow_net: false,
         }
     }
 
@@ -55,6 +60,7 @@ impl Cli {
             jit: true,
             print_bytecode: false,
             print_ast: false,
+            allow_net: false,
         }
     }
 
@@ -67,6 +73,7 @@ impl Cli {
             jit: true,
             print_bytecode: false,
             print_ast: false,
+            allow_net: false,
         }
     }
 
@@ -79,6 +86,7 @@ impl Cli {
             jit: true,
             print_bytecode: false,
             print_ast: false,
+            allow_net: false,
         }
     }
 }
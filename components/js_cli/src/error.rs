@@ -17,6 +17,20 @@ pub enum CliError {
 
     /// REPL error
     ReplError(String),
+
+    /// Bytecode cache is malformed or stale
+    CacheError(String),
+}
+
+impl CliError {
+    /// Process exit code for this error: 2 for syntax errors, 1 otherwise
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            CliError::ParseError(_) => 2,
+            CliError::JsError(e) if e.kind == core_types::ErrorKind::SyntaxError => 2,
+            _ => 1,
+        }
+    }
 }
 
 impl fmt::Display for CliError {
@@ -26,6 +40,7 @@ impl fmt::Display for CliError {
             CliError::IoError(e) => write!(f, "File error: {}", e),
             CliError::ParseError(s) => write!(f, "Parse error: {}", s),
             CliError::ReplError(s) => write!(f, "REPL error: {}", s),
+            CliError::CacheError(s) => write!(f, "Bytecode cache error: {}", s),
         }
     }
 }
@@ -7,9 +7,11 @@
 
 pub mod cli;
 pub mod error;
+pub mod loader;
 pub mod repl;
 pub mod runtime;
 
 pub use cli::Cli;
 pub use error::{CliError, CliResult};
+pub use loader::{FetchResponse, ModuleFetcher, ModuleResolver};
 pub use runtime::Runtime;
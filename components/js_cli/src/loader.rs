@@ -0,0 +1,513 @@
+//! Module loading: specifier resolution, fetching and linking
+//!
+//! The ModuleResolver turns import specifiers into absolute URLs, fetches
+//! each module once, and links the module graph. Local modules are read
+//! from disk; `http://` and `https://` modules are fetched over the network,
+//! which must be enabled explicitly (`--allow-net`).
+
+use crate::error::{CliError, CliResult};
+use async_runtime::{ImportEntry, Module, ModuleStatus};
+use core_types::{ErrorKind, JsError};
+use parser::ast::ImportSpecifier;
+use parser::{ASTNode, Parser, Statement};
+use std::collections::HashMap;
+use url::Url;
+
+/// Redirects followed for one fetch before giving up
+const MAX_REDIRECTS: usize = 10;
+
+/// Response to fetching a remote module
+#[derive(Debug, Clone, PartialEq)]
+pub enum FetchResponse {
+    /// The module's source text
+    Source(String),
+    /// The module moved; the location may be relative to the requested URL
+    Redirect(String),
+}
+
+/// Fetches remote module sources
+pub trait ModuleFetcher {
+    /// Fetch `url` without following redirects
+    fn fetch(&mut self, url: &str) -> CliResult<FetchResponse>;
+}
+
+/// ModuleFetcher performing HTTP(S) GET requests
+pub struct HttpFetcher {
+    agent: ureq::Agent,
+}
+
+impl HttpFetcher {
+    /// Create a fetcher; redirects are reported rather than followed
+    pub fn new() -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new().redirects(0).build(),
+        }
+    }
+}
+
+impl Default for HttpFetcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ModuleFetcher for HttpFetcher {
+    fn fetch(&mut self, url: &str) -> CliResult<FetchResponse> {
+        match self.agent.get(url).call() {
+            Ok(response) if (300..400).contains(&response.status()) => {
+                match response.header("location") {
+                    Some(location) => Ok(FetchResponse::Redirect(location.to_string())),
+                    None => Err(fetch_error(url, "redirect without a Location header")),
+                }
+            }
+            Ok(response) => response
+                .into_string()
+                .map(FetchResponse::Source)
+                .map_err(CliError::IoError),
+            Err(ureq::Error::Status(code, _)) => {
+                Err(fetch_error(url, &format!("HTTP status {}", code)))
+            }
+            Err(err) => Err(fetch_error(url, &err.to_string())),
+        }
+    }
+}
+
+/// Resolves, fetches and links modules, caching each by URL
+pub struct ModuleResolver {
+    /// Whether remote (`http:`/`https:`) modules may be fetched
+    allow_net: bool,
+    /// Fetcher for remote modules
+    fetcher: Box<dyn ModuleFetcher>,
+    /// Loaded modules by final URL
+    modules: HashMap<String, Module>,
+    /// Requested URL to final URL, for modules reached through redirects
+    redirects: HashMap<String, String>,
+    /// Final URLs each loaded module imports, in source order
+    dependencies: HashMap<String, Vec<String>>,
+}
+
+impl ModuleResolver {
+    /// Create a resolver fetching remote modules over HTTP(S)
+    ///
+    /// # Example
+    /// ```
+    /// use js_cli::loader::ModuleResolver;
+    ///
+    /// let resolver = ModuleResolver::new(false);
+    /// assert!(!resolver.is_net_allowed());
+    /// ```
+    pub fn new(allow_net: bool) -> Self {
+        Self::with_fetcher(allow_net, HttpFetcher::new())
+    }
+
+    /// Create a resolver fetching remote modules with `fetcher`
+    pub fn with_fetcher(allow_net: bool, fetcher: impl ModuleFetcher + 'static) -> Self {
+        Self {
+            allow_net,
+            fetcher: Box::new(fetcher),
+            modules: HashMap::new(),
+            redirects: HashMap::new(),
+            dependencies: HashMap::new(),
+        }
+    }
+
+    /// Check whether remote modules may be fetched
+    pub fn is_net_allowed(&self) -> bool {
+        self.allow_net
+    }
+
+    /// Allow or deny fetching remote modules
+    pub fn set_allow_net(&mut self, allow_net: bool) {
+        self.allow_net = allow_net;
+    }
+
+    /// Resolve `specifier` to an absolute URL
+    ///
+    /// `base` is the URL of the importing module, or a file path for
+    /// top-level loads. Bare specifiers such as `"lodash"` are rejected, and
+    /// so are `file:` URLs imported from remote modules.
+    pub fn resolve(&self, specifier: &str, base: &str) -> CliResult<String> {
+        let base = base_url(base)?;
+        let url = resolve_url(specifier, &base)?;
+        check_local_access(&base, &url)?;
+        Ok(url.into())
+    }
+
+    /// Load and link the module `specifier` imported from `base` and its dependencies
+    ///
+    /// Returns the module's final URL, which differs from the requested one
+    /// when the fetch was redirected.
+    pub fn load(&mut self, specifier: &str, base: &str) -> CliResult<String> {
+        let requested = self.resolve(specifier, base)?;
+        let url = self.load_url(&requested)?;
+        if let Some(module) = self.modules.get_mut(&url) {
+            module.link()?;
+        }
+        Ok(url)
+    }
+
+    /// Load and link the entry module at file `path` and its dependencies
+    pub fn load_entry(&mut self, path: &str) -> CliResult<String> {
+        let url = base_url(path)?;
+        self.load(url.as_str(), path)
+    }
+
+    /// Modules to evaluate before (and including) `url`, dependencies first
+    ///
+    /// Returns each module's URL and source; modules already evaluated are skipped.
+    pub fn evaluation_order(&self, url: &str) -> Vec<(String, String)> {
+        let mut visited = std::collections::HashSet::new();
+        let mut order = Vec::new();
+        self.visit_unevaluated(url, &mut visited, &mut order);
+        order
+    }
+
+    /// Record that the module at `url` has been evaluated
+    pub fn mark_evaluated(&mut self, url: &str) -> CliResult<()> {
+        if let Some(module) = self.modules.get_mut(url) {
+            module.evaluate()?;
+        }
+        Ok(())
+    }
+
+    /// Post-order walk of the module graph used by `evaluation_order`
+    fn visit_unevaluated(
+        &self,
+        url: &str,
+        visited: &mut std::collections::HashSet<String>,
+        order: &mut Vec<(String, String)>,
+    ) {
+        if !visited.insert(url.to_string()) {
+            return;
+        }
+        let Some(module) = self.modules.get(url) else {
+            return;
+        };
+        if module.status == ModuleStatus::Evaluated {
+            return;
+        }
+        for dependency in self.dependencies.get(url).into_iter().flatten() {
+            self.visit_unevaluated(dependency, visited, order);
+        }
+        order.push((url.to_string(), module.source.clone()));
+    }
+
+    /// Module loaded from `url`, following cached redirects
+    pub fn module(&self, url: &str) -> Option<&Module> {
+        let url = self.redirects.get(url).map(String::as_str).unwrap_or(url);
+        self.modules.get(url)
+    }
+
+    /// Fetch the module at `url` and its dependencies unless already loaded
+    fn load_url(&mut self, requested: &str) -> CliResult<String> {
+        if let Some(url) = self.redirects.get(requested) {
+            return Ok(url.clone());
+        }
+        if self.modules.contains_key(requested) {
+            return Ok(requested.to_string());
+        }
+
+        let (url, source) = self.fetch(requested)?;
+        if url != requested {
+            self.redirects.insert(requested.to_string(), url.clone());
+            if self.modules.contains_key(&url) {
+                return Ok(url);
+            }
+        }
+
+        let mut module = Module::new(source);
+        let mut parser = Parser::new(&module.source);
+        parser.set_module_mode(true);
+        let dependencies = match parser.parse() {
+            Ok(ast) => collect_imports(&ast, &mut module),
+            Err(_) => {
+                module.set_has_syntax_error(true);
+                Vec::new()
+            }
+        };
+        // Cache before loading dependencies so import cycles terminate
+        self.modules.insert(url.clone(), module);
+
+        // Relative imports resolve against the final URL, after redirects
+        let mut dependency_urls = Vec::with_capacity(dependencies.len());
+        for dependency in dependencies {
+            let resolved = self.resolve(&dependency, &url)?;
+            let dependency_url = self.load_url(&resolved)?;
+            if let Some(module) = self.modules.get_mut(&dependency_url) {
+                module.link()?;
+            }
+            dependency_urls.push(dependency_url);
+        }
+        self.dependencies.insert(url.clone(), dependency_urls);
+        Ok(url)
+    }
+
+    /// Fetch `url`, following redirects; returns the final URL and the source
+    fn fetch(&mut self, url: &str) -> CliResult<(String, String)> {
+        let mut current = Url::parse(url).map_err(|e| type_error(format!("Invalid module URL \"{}\": {}", url, e)))?;
+        let requested = current.clone();
+        for _ in 0..=MAX_REDIRECTS {
+            // A remote server must not redirect to a local file
+            check_local_access(&requested, &current)?;
+            match current.scheme() {
+                "file" => {
+                    let path = current
+                        .to_file_path()
+                        .map_err(|_| type_error(format!("Invalid file URL \"{}\"", current)))?;
+                    let source = std::fs::read_to_string(path)?;
+                    return Ok((current.into(), source));
+                }
+                "http" | "https" => {
+                    if !self.allow_net {
+                        return Err(type_error(format!(
+                            "Requires net access to \"{}\", run again with the --allow-net flag",
+                            current
+                        )));
+                    }
+                    match self.fetcher.fetch(current.as_str())? {
+                        FetchResponse::Source(source) => return Ok((current.into(), source)),
+                        FetchResponse::Redirect(location) => {
+                            current = current
+                                .join(&location)
+                                .map_err(|e| fetch_error(current.as_str(), &e.to_string()))?;
+                        }
+                    }
+                }
+                scheme => {
+                    return Err(type_error(format!("Unsupported module scheme \"{}:\" in \"{}\"", scheme, current)));
+                }
+            }
+        }
+        Err(fetch_error(url, "too many redirects"))
+    }
+}
+
+/// Check whether `source` uses import or export declarations
+pub fn is_module_source(source: &str) -> bool {
+    let mut parser = Parser::new(source);
+    parser.set_module_mode(true);
+    let Ok(ASTNode::Program(statements)) = parser.parse() else {
+        return false;
+    };
+    statements.iter().any(|statement| {
+        matches!(
+            statement,
+            Statement::ImportDeclaration { .. }
+                | Statement::ExportNamedDeclaration { .. }
+                | Statement::ExportDefaultDeclaration { .. }
+                | Statement::ExportAllDeclaration { .. }
+        )
+    })
+}
+
+/// Record `ast`'s imports on `module`; returns the specifiers it depends on
+fn collect_imports(ast: &ASTNode, module: &mut Module) -> Vec<String> {
+    let ASTNode::Program(statements) = ast else {
+        return Vec::new();
+    };
+
+    let mut dependencies = Vec::new();
+    for statement in statements {
+        match statement {
+            Statement::ImportDeclaration { specifiers, source, .. } => {
+                for specifier in specifiers {
+                    let (import_name, local_name) = match specifier {
+                        ImportSpecifier::Default(local) => ("default", local),
+                        ImportSpecifier::Namespace(local) => ("*", local),
+                        ImportSpecifier::Named { local, imported } => (imported.as_str(), local),
+                    };
+                    module.add_import(ImportEntry {
+                        module_specifier: source.clone(),
+                        import_name: import_name.to_string(),
+                        local_name: local_name.clone(),
+                    });
+                }
+                dependencies.push(source.clone());
+            }
+            Statement::ExportNamedDeclaration { source: Some(source), .. }
+            | Statement::ExportAllDeclaration { source, .. } => dependencies.push(source.clone()),
+            _ => {}
+        }
+    }
+    dependencies
+}
+
+/// Parse `base` as a URL, treating anything else as a file path
+fn base_url(base: &str) -> CliResult<Url> {
+    if let Ok(url) = Url::parse(base) {
+        return Ok(url);
+    }
+    let path = std::path::absolute(base)?;
+    Url::from_file_path(&path).map_err(|_| type_error(format!("Invalid module base \"{}\"", base)))
+}
+
+/// Resolve `specifier` against `base` without any access checks
+fn resolve_url(specifier: &str, base: &Url) -> CliResult<Url> {
+    if let Ok(url) = Url::parse(specifier) {
+        return Ok(url);
+    }
+    if !["./", "../", "/"].iter().any(|prefix| specifier.starts_with(prefix)) {
+        return Err(type_error(format!(
+            "Failed to resolve module specifier \"{}\". \
+             Relative references must start with either \"/\", \"./\", or \"../\"",
+            specifier
+        )));
+    }
+    base.join(specifier)
+        .map_err(|e| type_error(format!("Invalid module specifier \"{}\": {}", specifier, e)))
+}
+
+/// Refuse a `file:` target requested by a remote (`http(s):`) module or URL
+fn check_local_access(from: &Url, target: &Url) -> CliResult<()> {
+    if target.scheme() == "file" && matches!(from.scheme(), "http" | "https") {
+        return Err(type_error(format!(
+            "Remote module \"{}\" cannot load local file \"{}\"",
+            from, target
+        )));
+    }
+    Ok(())
+}
+
+fn type_error(message: String) -> CliError {
+    CliError::JsError(JsError {
+        kind: ErrorKind::TypeError,
+        message,
+        stack: vec![],
+        source_position: None,
+    })
+}
+
+fn fetch_error(url: &str, reason: &str) -> CliError {
+    type_error(format!("Failed to fetch module \"{}\": {}", url, reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    /// Serves canned responses and records each requested URL
+    struct MockFetcher {
+        responses: HashMap<String, FetchResponse>,
+        requests: Rc<RefCell<Vec<String>>>,
+    }
+
+    impl ModuleFetcher for MockFetcher {
+        fn fetch(&mut self, url: &str) -> CliResult<FetchResponse> {
+            self.requests.borrow_mut().push(url.to_string());
+            self.responses
+                .get(url)
+                .cloned()
+                .ok_or_else(|| fetch_error(url, "HTTP status 404"))
+        }
+    }
+
+    fn mock_resolver(allow_net: bool) -> (ModuleResolver, Rc<RefCell<Vec<String>>>) {
+        let requests = Rc::new(RefCell::new(Vec::new()));
+        let responses = HashMap::from([
+            (
+                "https://example.com/app.js".to_string(),
+                FetchResponse::Redirect("https://cdn.example.com/v2/app.js".to_string()),
+            ),
+            (
+                "https://cdn.example.com/v2/app.js".to_string(),
+                FetchResponse::Source("import { helper } from './util.js'; export const x = 1;".to_string()),
+            ),
+            (
+                "https://cdn.example.com/v2/util.js".to_string(),
+                FetchResponse::Source("export function helper() {}".to_string()),
+            ),
+            (
+                "https://evil.example.com/import.js".to_string(),
+                FetchResponse::Source("import key from 'file:///etc/passwd';".to_string()),
+            ),
+            (
+                "https://evil.example.com/redirect.js".to_string(),
+                FetchResponse::Redirect("file:///etc/passwd".to_string()),
+            ),
+        ]);
+        let fetcher = MockFetcher {
+            responses,
+            requests: Rc::clone(&requests),
+        };
+        (ModuleResolver::with_fetcher(allow_net, fetcher), requests)
+    }
+
+    #[test]
+    fn test_https_import_follows_redirect_and_links() {
+        let (mut resolver, requests) = mock_resolver(true);
+
+        let url = resolver.load("https://example.com/app.js", "/tmp/main.js").unwrap();
+        assert_eq!(url, "https://cdn.example.com/v2/app.js");
+
+        // The relative import resolved against the redirected URL
+        let app = resolver.module("https://example.com/app.js").unwrap();
+        assert_eq!(app.status, ModuleStatus::Linked);
+        assert_eq!(app.imports[0].import_name, "helper");
+        let util = resolver.module("https://cdn.example.com/v2/util.js").unwrap();
+        assert_eq!(util.status, ModuleStatus::Linked);
+        assert_eq!(util.source, "export function helper() {}");
+
+        // Cached by URL: loading again fetches nothing
+        resolver.load("https://example.com/app.js", "/tmp/main.js").unwrap();
+        assert_eq!(
+            *requests.borrow(),
+            vec![
+                "https://example.com/app.js",
+                "https://cdn.example.com/v2/app.js",
+                "https://cdn.example.com/v2/util.js",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_remote_import_requires_allow_net() {
+        let (mut resolver, requests) = mock_resolver(false);
+
+        match resolver.load("https://example.com/app.js", "/tmp/main.js") {
+            Err(CliError::JsError(err)) => assert!(err.message.contains("--allow-net")),
+            other => panic!("expected net access error, got {:?}", other),
+        }
+        assert!(requests.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_remote_module_cannot_import_local_file() {
+        let (mut resolver, requests) = mock_resolver(true);
+
+        match resolver.load("https://evil.example.com/import.js", "/tmp/main.js") {
+            Err(CliError::JsError(err)) => {
+                assert!(err.message.contains("cannot load local file"), "{}", err.message)
+            }
+            other => panic!("expected local file access error, got {:?}", other),
+        }
+        assert_eq!(*requests.borrow(), vec!["https://evil.example.com/import.js"]);
+        assert!(resolver.resolve("file:///etc/passwd", "https://evil.example.com/a.js").is_err());
+        // Local modules may still import local files
+        assert!(resolver.resolve("file:///tmp/a.js", "/tmp/main.js").is_ok());
+    }
+
+    #[test]
+    fn test_redirect_to_local_file_is_refused() {
+        let (mut resolver, _) = mock_resolver(true);
+
+        match resolver.load("https://evil.example.com/redirect.js", "/tmp/main.js") {
+            Err(CliError::JsError(err)) => {
+                assert!(err.message.contains("cannot load local file"), "{}", err.message)
+            }
+            other => panic!("expected local file access error, got {:?}", other),
+        }
+        assert!(resolver.module("https://evil.example.com/redirect.js").is_none());
+    }
+
+    #[test]
+    fn test_resolve_rejects_bare_specifiers() {
+        let resolver = ModuleResolver::new(false);
+        assert_eq!(
+            resolver.resolve("../lib/a.js", "https://example.com/app/main.js").unwrap(),
+            "https://example.com/lib/a.js"
+        );
+        assert!(resolver.resolve("lodash", "https://example.com/main.js").is_err());
+    }
+}
@@ -11,7 +11,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let mut runtime = Runtime::new(cli.jit)
         .with_print_bytecode(cli.print_bytecode)
-        .with_print_ast(cli.print_ast);
+        .with_print_ast(cli.print_ast)
+        .with_allow_net(cli.allow_net);
 
     // Execute based on CLI arguments
     if let Some(file) = cli.file {
@@ -21,23 +22,13 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 if !matches!(result, core_types::Value::Undefined) {
                     println!("{:?}", result);
                 }
+                exit_on_uncaught(&mut runtime);
             }
             Err(CliError::IoError(e)) => {
                 eprintln!("Error: Could not read file '{}': {}", file, e);
                 std::process::exit(1);
             }
-            Err(CliError::ParseError(e)) => {
-                eprintln!("Syntax Error: {}", e);
-                std::process::exit(1);
-            }
-            Err(CliError::JsError(e)) => {
-                eprintln!("JavaScript Error: {:?}", e);
-                std::process::exit(1);
-            }
-            Err(e) => {
-                eprintln!("Error: {}", e);
-                std::process::exit(1);
-            }
+            Err(e) => report_and_exit(e),
         }
     } else if let Some(code) = cli.eval {
         match runtime.execute_string(&code) {
@@ -46,19 +37,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 if !matches!(result, core_types::Value::Undefined) {
                     println!("{:?}", result);
                 }
+                exit_on_uncaught(&mut runtime);
             }
-            Err(CliError::ParseError(e)) => {
-                eprintln!("Syntax Error: {}", e);
-                std::process::exit(1);
-            }
-            Err(CliError::JsError(e)) => {
-                eprintln!("JavaScript Error: {:?}", e);
-                std::process::exit(1);
-            }
-            Err(e) => {
-                eprintln!("Error: {}", e);
-                std::process::exit(1);
-            }
+            Err(e) => report_and_exit(e),
         }
     } else if cli.repl {
         runtime.repl()?;
@@ -76,3 +57,25 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+/// Print an execution error and exit with its kind-specific code
+fn report_and_exit(err: CliError) -> ! {
+    match &err {
+        CliError::ParseError(e) => eprintln!("Syntax Error: {}", e),
+        CliError::JsError(e) => eprintln!("JavaScript Error: {:?}", e),
+        e => eprintln!("Error: {}", e),
+    }
+    std::process::exit(err.exit_code())
+}
+
+/// Print errors thrown by `queueMicrotask` or timer callbacks, exiting with
+/// code 1 if there were any
+fn exit_on_uncaught(runtime: &mut Runtime) {
+    let errors = runtime.take_uncaught_errors();
+    for err in &errors {
+        eprintln!("Uncaught {:?}", err);
+    }
+    if !errors.is_empty() {
+        std::process::exit(1);
+    }
+}
@@ -59,6 +59,9 @@ pub fn run_repl(runtime: &mut Runtime) -> CliResult<()> {
                     match runtime.execute_string(&line_buffer) {
                         Ok(value) => {
                             println!("{}", format_value(&value));
+                            for err in runtime.take_uncaught_errors() {
+                                eprintln!("Uncaught {:?}", err);
+                            }
                         }
                         Err(CliError::ParseError(e)) => {
                             // Check if it's an incomplete input error
@@ -200,7 +203,7 @@ fn format_value(value: &core_types::Value) -> String {
         }
         core_types::Value::HeapObject(_) => "[Object]".to_string(),
         core_types::Value::String(s) => format!("'{}'", s),
-        core_types::Value::NativeObject(_) => "[native object]".to_string(),
+        core_types::Value::NativeObject(_) => value.inspect(2),
         core_types::Value::NativeFunction(name) => format!("[Function: {}]", name),
         core_types::Value::BigInt(n) => format!("{}n", n),
     }
@@ -8,8 +8,13 @@
 //! - Builtins for standard library
 
 use crate::error::{CliError, CliResult};
+use crate::loader::{self, ModuleResolver};
 use async_runtime::EventLoop;
 use core_types::Value;
+use interpreter::{Clock, ManualClock, RealmId};
+
+/// Wall-clock time `Date.now()` starts at in deterministic mode (2020-01-01T00:00:00Z)
+pub const DETERMINISTIC_EPOCH_MS: f64 = 1_577_836_800_000.0;
 
 /// Main runtime that orchestrates all JavaScript execution components
 pub struct Runtime {
@@ -19,10 +24,16 @@ pub struct Runtime {
     print_bytecode: bool,
     /// Whether to print AST before execution
     print_ast: bool,
-    /// Event loop for async operations
-    event_loop: EventLoop,
     /// Persistent VM instance for maintaining state
     vm: interpreter::VM,
+    /// Hook consulted before an uncaught exception is reported
+    uncaught_handler: Option<Box<dyn FnMut(&core_types::JsError) -> bool>>,
+    /// Errors thrown by host jobs that the handler did not take, oldest first
+    uncaught_errors: Vec<core_types::JsError>,
+    /// Resolver and cache for imported modules
+    module_resolver: ModuleResolver,
+    /// Clock that [`Runtime::advance_clock`] steps, in deterministic mode
+    manual_clock: Option<ManualClock>,
 }
 
 impl Runtime {
@@ -42,8 +53,11 @@ impl Runtime {
             enable_jit,
             print_bytecode: false,
             print_ast: false,
-            event_loop: EventLoop::new(),
             vm: interpreter::VM::new(),
+            uncaught_handler: None,
+            uncaught_errors: Vec::new(),
+            module_resolver: ModuleResolver::new(false),
+            manual_clock: None,
         }
     }
 
@@ -59,6 +73,114 @@ impl Runtime {
         self
     }
 
+    /// Allow modules to be fetched over `http:` and `https:`
+    pub fn with_allow_net(mut self, enabled: bool) -> Self {
+        self.module_resolver.set_allow_net(enabled);
+        self
+    }
+
+    /// Replace the module resolver, e.g. to use a custom fetcher
+    pub fn with_module_resolver(mut self, resolver: ModuleResolver) -> Self {
+        self.module_resolver = resolver;
+        self
+    }
+
+    /// Use `clock` for `performance.now()` and `Date.now()`
+    ///
+    /// # Example
+    /// ```
+    /// use js_cli::Runtime;
+    /// use interpreter::SystemClock;
+    ///
+    /// let runtime = Runtime::new(false).with_clock(SystemClock::new());
+    /// ```
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.vm.set_clock(clock);
+        self
+    }
+
+    /// Make scripts produce identical output across runs
+    ///
+    /// `Math.random()` becomes a generator seeded with `seed`, and
+    /// `performance.now()` and `Date.now()` read a fake clock starting at
+    /// [`DETERMINISTIC_EPOCH_MS`] that only moves through
+    /// [`Runtime::advance_clock`].
+    ///
+    /// # Example
+    /// ```
+    /// use js_cli::Runtime;
+    ///
+    /// let mut runtime = Runtime::new(false).with_deterministic(42);
+    /// let first = runtime.execute_string("Math.random()").unwrap();
+    /// let mut again = Runtime::new(false).with_deterministic(42);
+    /// assert_eq!(again.execute_string("Math.random()").unwrap(), first);
+    /// ```
+    pub fn with_deterministic(mut self, seed: u64) -> Self {
+        let clock = ManualClock::new(DETERMINISTIC_EPOCH_MS);
+        self.vm.set_clock(clock.clone());
+        self.vm.set_random_seed(seed);
+        self.manual_clock = Some(clock);
+        self
+    }
+
+    /// Step the deterministic clock forward by `ms` milliseconds
+    ///
+    /// Has no effect unless the runtime was built with
+    /// [`Runtime::with_deterministic`].
+    pub fn advance_clock(&mut self, ms: f64) {
+        if let Some(clock) = &self.manual_clock {
+            clock.advance(ms);
+        }
+    }
+
+    /// Install a handler for exceptions that escape the script or a host job
+    ///
+    /// The handler sees every uncaught error before default handling. Returning
+    /// `true` marks the error as handled: it is not printed or propagated, and
+    /// execution of the remaining host jobs and event loop continues.
+    ///
+    /// # Example
+    /// ```
+    /// use js_cli::Runtime;
+    ///
+    /// let mut runtime = Runtime::new(false);
+    /// runtime.set_uncaught_handler(|err| {
+    ///     eprintln!("caught: {}", err.message);
+    ///     true
+    /// });
+    /// assert!(runtime.execute_string("undefined();").is_ok());
+    /// ```
+    pub fn set_uncaught_handler(&mut self, handler: impl FnMut(&core_types::JsError) -> bool + 'static) {
+        self.uncaught_handler = Some(Box::new(handler));
+    }
+
+    /// Take the errors thrown by `queueMicrotask` and timer callbacks since
+    /// the last call
+    ///
+    /// Such a job runs after the script has returned, so its error cannot
+    /// fail the `execute_*` call; errors the uncaught handler took are not
+    /// recorded.
+    ///
+    /// # Example
+    /// ```
+    /// use js_cli::Runtime;
+    ///
+    /// let mut runtime = Runtime::new(false);
+    /// runtime.execute_string("queueMicrotask(function () { null.x(); });").unwrap();
+    /// assert_eq!(runtime.take_uncaught_errors().len(), 1);
+    /// ```
+    pub fn take_uncaught_errors(&mut self) -> Vec<core_types::JsError> {
+        std::mem::take(&mut self.uncaught_errors)
+    }
+
+    /// Offer `err` to the uncaught handler; returns true if it was handled
+    fn handle_uncaught(&mut self, err: &core_types::JsError) -> bool {
+        match self.uncaught_handler.as_mut() {
+            Some(handler) => handler(err),
+            None => false,
+        }
+    }
+
     /// Execute a JavaScript file
     ///
     /// # Arguments
@@ -80,11 +202,29 @@ impl Runtime {
     pub fn execute_file(&mut self, path: &str) -> CliResult<Value> {
         // Read file content
         let source = std::fs::read_to_string(path)?;
+        if loader::is_module_source(&source) {
+            return self.execute_module(path);
+        }
 
         // Execute the source code
         self.execute_string(&source)
     }
 
+    /// Load the module at `path` with its imports and evaluate the graph
+    ///
+    /// Dependencies run first, each once. Remote imports are fetched only
+    /// when net access was allowed with [`Runtime::with_allow_net`].
+    fn execute_module(&mut self, path: &str) -> CliResult<Value> {
+        let url = self.module_resolver.load_entry(path)?;
+        let mut result = Value::Undefined;
+        for (module_url, source) in self.module_resolver.evaluation_order(&url) {
+            let bytecode = self.compile_source(&source, true)?;
+            result = self.run_chunk(&bytecode)?;
+            self.module_resolver.mark_evaluated(&module_url)?;
+        }
+        Ok(result)
+    }
+
     /// Execute a JavaScript source string
     ///
     /// # Arguments
@@ -105,8 +245,123 @@ impl Runtime {
     /// let result = runtime.execute_string("let x = 42;").unwrap();
     /// ```
     pub fn execute_string(&mut self, source: &str) -> CliResult<Value> {
+        let bytecode = self.compile(source)?;
+        self.run_chunk(&bytecode)
+    }
+
+    /// Create a realm with its own global object and builtins
+    ///
+    /// Realms share the heap, so values returned from one realm can be
+    /// handed to another. See [`interpreter::VM::create_realm`].
+    pub fn create_realm(&mut self) -> RealmId {
+        self.vm.create_realm()
+    }
+
+    /// Execute JavaScript source code in the given realm
+    ///
+    /// The previously entered realm is restored afterwards, even if the
+    /// script throws.
+    ///
+    /// # Errors
+    /// Returns `CliError` if the realm is unknown, or parsing or execution fails
+    ///
+    /// # Example
+    /// ```
+    /// use js_cli::Runtime;
+    ///
+    /// let mut runtime = Runtime::new(false);
+    /// let realm = runtime.create_realm();
+    /// runtime.execute_in_realm(realm, "globalThis.x = 1;").unwrap();
+    /// assert!(runtime.vm().get_global("x").is_none());
+    /// ```
+    pub fn execute_in_realm(&mut self, realm: RealmId, source: &str) -> CliResult<Value> {
+        let bytecode = self.compile(source)?;
+        let previous = self.vm.enter_realm(realm).map_err(CliError::JsError)?;
+        let result = self.run_chunk(&bytecode);
+        self.vm.enter_realm(previous).map_err(CliError::JsError)?;
+        result
+    }
+
+    /// Precompile a JavaScript file to a bytecode cache file
+    ///
+    /// The cache records the source path and a hash of its contents so that
+    /// `execute_cached` can detect when the source has changed since compilation.
+    ///
+    /// # Arguments
+    /// * `src_path` - Path to the JavaScript source file
+    /// * `out_path` - Path of the `.cbc` cache file to write
+    ///
+    /// # Errors
+    /// Returns `CliError` if the source cannot be read or compiled, or the cache cannot be written
+    ///
+    /// # Example
+    /// ```no_run
+    /// use js_cli::Runtime;
+    ///
+    /// let mut runtime = Runtime::new(false);
+    /// runtime.compile_to_file("app.js", "app.cbc").unwrap();
+    /// let result = runtime.execute_cached("app.cbc").unwrap();
+    /// ```
+    pub fn compile_to_file(&mut self, src_path: &str, out_path: &str) -> CliResult<()> {
+        let source = std::fs::read_to_string(src_path)?;
+        let bytecode = self.compile(&source)?;
+
+        // Record an absolute path so the cache can be validated from any working directory
+        let src_path = std::fs::canonicalize(src_path)?;
+        let src_path = src_path.to_string_lossy();
+
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(CACHE_MAGIC);
+        bytes.extend_from_slice(&source_hash(&source).to_le_bytes());
+        bytes.extend_from_slice(&(src_path.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(src_path.as_bytes());
+        bytes.extend_from_slice(&bytecode.to_bytes());
+
+        std::fs::write(out_path, bytes)?;
+        Ok(())
+    }
+
+    /// Execute a bytecode cache file produced by `compile_to_file`
+    ///
+    /// Parsing and bytecode generation are skipped. The cache is rejected if the
+    /// recorded source file no longer hashes to the value stored in its header.
+    ///
+    /// # Arguments
+    /// * `cbc_path` - Path to the `.cbc` cache file
+    ///
+    /// # Errors
+    /// Returns `CliError::CacheError` if the cache is malformed or stale
+    pub fn execute_cached(&mut self, cbc_path: &str) -> CliResult<Value> {
+        let bytes = std::fs::read(cbc_path)?;
+        let (src_path, hash, chunk_bytes) = parse_cache_header(&bytes)?;
+
+        let source = std::fs::read_to_string(&src_path)?;
+        if source_hash(&source) != hash {
+            return Err(CliError::CacheError(format!(
+                "Stale bytecode cache: '{}' has changed since it was compiled",
+                src_path
+            )));
+        }
+
+        let bytecode = bytecode_system::BytecodeChunk::from_bytes(chunk_bytes)
+            .map_err(CliError::CacheError)?;
+        self.run_chunk(&bytecode)
+    }
+
+    /// Parse source and generate bytecode for it
+    fn compile(&self, source: &str) -> CliResult<bytecode_system::BytecodeChunk> {
+        self.compile_source(source, false)
+    }
+
+    /// Parse source as a script or module and generate bytecode for it
+    fn compile_source(
+        &self,
+        source: &str,
+        module: bool,
+    ) -> CliResult<bytecode_system::BytecodeChunk> {
         // Parse the source code
         let mut parser = parser::Parser::new(source);
+        parser.set_module_mode(module);
         let ast = parser
             .parse()
             .map_err(|e| CliError::ParseError(format!("Parse error: {:?}", e)))?;
@@ -118,15 +373,17 @@ impl Runtime {
 
         // Generate bytecode
         let mut generator = parser::BytecodeGenerator::new();
-        let bytecode = generator
+        generator
             .generate(&ast)
-            .map_err(|e| CliError::ParseError(format!("Bytecode generation error: {:?}", e)))?;
+            .map_err(|e| CliError::ParseError(format!("Bytecode generation error: {:?}", e)))
+    }
 
+    /// Execute compiled bytecode on the persistent VM and drain the event loop
+    fn run_chunk(&mut self, bytecode: &bytecode_system::BytecodeChunk) -> CliResult<Value> {
         // Register nested functions with the VM before executing
         // This ensures that CreateClosure opcodes can find their function bytecode
-        let nested_functions = generator.take_nested_functions();
-        for func_bytecode in nested_functions {
-            self.vm.register_function(func_bytecode);
+        for func_bytecode in bytecode.nested_functions() {
+            self.vm.register_function(func_bytecode.clone());
         }
 
         // Optionally print bytecode
@@ -135,10 +392,24 @@ impl Runtime {
         }
 
         // Execute using persistent VM
-        let result = self.vm.execute(&bytecode).map_err(CliError::JsError)?;
+        let result = match self.vm.execute(bytecode) {
+            Ok(value) => value,
+            Err(err) if self.handle_uncaught(&err) => Value::Undefined,
+            Err(err) => return Err(CliError::JsError(err)),
+        };
+
+        // Run queueMicrotask callbacks and timers, host timers on the same
+        // heap included; a throwing job is recorded without stopping the ones
+        // after it
+        self.vm.run_host_jobs();
+        for err in self.vm.take_uncaught_errors() {
+            if !self.handle_uncaught(&err) {
+                self.uncaught_errors.push(err);
+            }
+        }
 
         // Run event loop to process pending promises and microtasks
-        self.event_loop.run_until_done().map_err(CliError::JsError)?;
+        self.vm.event_loop().run_until_done().map_err(CliError::JsError)?;
 
         Ok(result)
     }
@@ -147,13 +418,22 @@ impl Runtime {
     ///
     /// # Arguments
     /// * `task` - A closure to execute as a microtask
+    #[track_caller]
     pub fn queue_microtask(&mut self, task: impl FnOnce() -> Result<Value, core_types::JsError> + Send + 'static) {
-        self.event_loop.enqueue_microtask(async_runtime::MicroTask::new(task));
+        self.vm.event_loop().enqueue_microtask(async_runtime::MicroTask::new(task));
+    }
+
+    /// Get access to the module resolver and its cache
+    pub fn module_resolver(&mut self) -> &mut ModuleResolver {
+        &mut self.module_resolver
     }
 
     /// Get access to the event loop for advanced async operations
+    ///
+    /// Script timers live on this loop's timer heap, so a timer set here
+    /// fires in deadline order with `setTimeout` callbacks.
     pub fn event_loop(&mut self) -> &mut EventLoop {
-        &mut self.event_loop
+        self.vm.event_loop()
     }
 
     /// Get access to the VM for direct manipulation
@@ -161,6 +441,48 @@ impl Runtime {
         &mut self.vm
     }
 
+    /// Completion candidates for REPL input
+    ///
+    /// A bare prefix completes against global names. A dotted prefix such as
+    /// `Math.s` completes against the properties of the object it names, own
+    /// and inherited, returning the property names (`sqrt` for `Math.sq`).
+    /// The object path is only looked up, never called.
+    ///
+    /// # Example
+    /// ```
+    /// use js_cli::Runtime;
+    ///
+    /// let mut runtime = Runtime::new(false);
+    /// assert_eq!(runtime.complete("Math.sq"), vec!["sqrt".to_string()]);
+    /// ```
+    pub fn complete(&mut self, prefix: &str) -> Vec<String> {
+        let (path, partial) = match prefix.rfind('.') {
+            Some(dot) => (Some(&prefix[..dot]), &prefix[dot + 1..]),
+            None => (None, prefix),
+        };
+
+        let names = match path {
+            None => self.vm.global_names(),
+            Some(path) => {
+                let is_identifier = |segment: &str| {
+                    segment.chars().next().is_some_and(|c| !c.is_ascii_digit())
+                        && segment.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+                };
+                if !path.split('.').all(is_identifier) {
+                    return Vec::new();
+                }
+                // Run the lookup directly so a missing name is not reported as uncaught
+                let object = match self.compile(path) {
+                    Ok(chunk) => self.vm.execute(&chunk).unwrap_or(Value::Undefined),
+                    Err(_) => return Vec::new(),
+                };
+                self.vm.property_names(&object)
+            }
+        };
+
+        names.into_iter().filter(|name| name.starts_with(partial)).collect()
+    }
+
     /// Start the REPL (Read-Eval-Print Loop)
     ///
     /// # Returns
@@ -244,6 +566,31 @@ impl Runtime {
     }
 }
 
+/// Magic bytes identifying a Corten bytecode cache file
+const CACHE_MAGIC: &[u8; 4] = b"CBC\0";
+
+/// Hash source text for cache invalidation (64-bit FNV-1a, stable across builds)
+fn source_hash(source: &str) -> u64 {
+    source.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Split a cache file into its source path, source hash, and chunk bytes
+fn parse_cache_header(bytes: &[u8]) -> CliResult<(String, u64, &[u8])> {
+    let malformed = || CliError::CacheError("Malformed bytecode cache header".to_string());
+
+    if bytes.len() < 16 || &bytes[0..4] != CACHE_MAGIC {
+        return Err(CliError::CacheError("Not a bytecode cache file".to_string()));
+    }
+    let hash = u64::from_le_bytes(bytes[4..12].try_into().map_err(|_| malformed())?);
+    let path_len = u32::from_le_bytes(bytes[12..16].try_into().map_err(|_| malformed())?) as usize;
+    let path_bytes = bytes.get(16..16 + path_len).ok_or_else(malformed)?;
+    let src_path = String::from_utf8(path_bytes.to_vec()).map_err(|_| malformed())?;
+
+    Ok((src_path, hash, &bytes[16 + path_len..]))
+}
+
 /// Statistics about the runtime's JIT compilation and execution
 #[derive(Debug, Clone)]
 pub struct RuntimeStats {
@@ -458,5 +805,67 @@ mod tests {
         );
         assert_eq!(result.unwrap(), Value::Smi(120));
     }
-}
 
+    #[test]
+    fn test_complete_global_prefix() {
+        let mut runtime = Runtime::new(false);
+        runtime.execute_string("Mapping = 1;").unwrap();
+
+        let candidates = runtime.complete("Ma");
+        assert!(candidates.contains(&"Math".to_string()));
+        assert!(candidates.contains(&"Mapping".to_string()));
+        assert!(candidates.iter().all(|name| name.starts_with("Ma")));
+        assert!(!runtime.complete("").is_empty());
+    }
+
+    #[test]
+    fn test_complete_host_object_members() {
+        let mut runtime = Runtime::new(false);
+        let candidates = runtime.complete("Math.s");
+        assert!(candidates.contains(&"sqrt".to_string()));
+        assert!(candidates.contains(&"sin".to_string()));
+        assert!(candidates.iter().all(|name| name.starts_with('s')));
+
+        assert!(runtime.complete("missing.x").is_empty());
+        assert!(runtime.complete("f().x").is_empty());
+    }
+
+    #[test]
+    fn test_completions_resolve_to_members() {
+        let mut runtime = Runtime::new(false);
+        runtime.execute_string("list = [1]; when = new Date(0);").unwrap();
+
+        // Every offered member must be something property access finds
+        for object in ["list", "when", "Math", "JSON", "console", "performance", "Promise"] {
+            let candidates = runtime.complete(&format!("{}.", object));
+            assert!(!candidates.is_empty(), "no completions for {}", object);
+            // Index keys such as `0` cannot follow a dot
+            let is_index = |name: &&String| name.starts_with(|c: char| c.is_ascii_digit());
+            for name in candidates.iter().filter(|name| !is_index(name)) {
+                let check = format!("typeof {}.{} !== 'undefined'", object, name);
+                let result = runtime.execute_string(&check).unwrap();
+                assert_eq!(result, Value::Boolean(true), "{}.{} is undefined", object, name);
+            }
+        }
+    }
+
+    #[test]
+    fn test_complete_includes_prototype_chain() {
+        let mut runtime = Runtime::new(false);
+        runtime
+            .execute_string(
+                r#"
+                function Point() { this.x = 1; }
+                Point.prototype.norm = 2;
+                point = new Point();
+                "#,
+            )
+            .unwrap();
+
+        let candidates = runtime.complete("point.");
+        // Own properties first, then the prototype's
+        assert_eq!(&candidates[..3], &["x", "constructor", "norm"]);
+        assert!(candidates.contains(&"hasOwnProperty".to_string()));
+        assert_eq!(runtime.complete("point.n"), vec!["norm".to_string()]);
+    }
+}
@@ -410,8 +410,6 @@ fn test_execute_break_statement() {
 }
 
 // Test continue statement
-// Note: Continue in for loops is tricky - jump target needs to be at the update expression
-// This test uses while loop where continue works correctly
 #[test]
 fn test_execute_continue_statement() {
     let mut runtime = Runtime::new(false);
@@ -422,6 +420,121 @@ fn test_execute_continue_statement() {
     assert_eq!(result, Value::Smi(8));
 }
 
+#[test]
+fn test_execute_continue_in_for_loop_runs_update() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime
+        .execute_string(
+            "let sum = 0; for (let i = 0; i < 5; i = i + 1) { \
+             if (i == 2) { continue; } sum = sum + i; }; sum",
+        )
+        .unwrap();
+
+    assert_eq!(result, Value::Smi(8));
+}
+
+#[test]
+fn test_execute_labeled_break_exits_both_loops() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime
+        .execute_string(
+            "let count = 0; outer: for (let i = 0; i < 5; i = i + 1) { \
+             for (let j = 0; j < 5; j = j + 1) { \
+             if (i == 2 && j == 1) { break outer; } count = count + 1; } }; count",
+        )
+        .unwrap();
+
+    // Two full inner loops, then one iteration of the third
+    assert_eq!(result, Value::Smi(11));
+}
+
+#[test]
+fn test_execute_labeled_continue_resumes_outer_loop() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime
+        .execute_string(
+            "let count = 0; let outerRuns = 0; \
+             outer: for (let i = 0; i < 4; i = i + 1) { outerRuns = outerRuns + 1; \
+             for (let j = 0; j < 4; j = j + 1) { \
+             if (j == 1) { continue outer; } count = count + 1; } \
+             count = count + 100; }; \
+             count + outerRuns * 1000",
+        )
+        .unwrap();
+
+    // Each outer iteration runs the inner body once and never reaches `count + 100`
+    assert_eq!(result, Value::Smi(4004));
+}
+
+#[test]
+fn test_execute_labeled_break_out_of_switch_in_loop() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime
+        .execute_string(
+            "let n = 0; loop: while (true) { \
+             switch (n) { case 3: break loop; default: n = n + 1; } }; n",
+        )
+        .unwrap();
+
+    assert_eq!(result, Value::Smi(3));
+}
+
+#[test]
+fn test_optional_member_chain_short_circuits_on_null() {
+    let mut runtime = Runtime::new(false);
+    // Without short-circuiting, `.c` would throw on undefined
+    let result = runtime.execute_string("let a = null; a?.b.c").unwrap();
+    assert_eq!(result, Value::Undefined);
+
+    let result = runtime.execute_string("let o = { b: { c: 5 } }; o?.b.c").unwrap();
+    assert_eq!(result, Value::Smi(5));
+}
+
+#[test]
+fn test_optional_call_skips_call_and_arguments() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime
+        .execute_string(
+            "let calls = 0; function count() { calls = calls + 1; return 1; } \
+             let o = {}; let n = null; \
+             let r1 = o.b?.(count()); let r2 = n?.b(count()); let r3 = n?.b.c(count()); \
+             [r1, r2, r3, calls].join(',')",
+        )
+        .unwrap();
+    assert_eq!(result, Value::String(",,,0".to_string()));
+}
+
+#[test]
+fn test_optional_call_invokes_present_method_with_this() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime
+        .execute_string("let o = { x: 7, get() { return this.x; } }; o?.get() + o.get?.()")
+        .unwrap();
+    assert_eq!(result, Value::Smi(14));
+}
+
+#[test]
+fn test_optional_chain_in_argument_is_independent() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime
+        .execute_string(
+            "function id(v) { return v; } let n = null; \
+             let r = id?.(n?.x); [r === undefined, (n?.x) === undefined].join(',')",
+        )
+        .unwrap();
+    assert_eq!(result, Value::String("true,true".to_string()));
+}
+
+#[test]
+fn test_execute_labeled_block_break() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime
+        .execute_string("let x = 1; block: { x = 2; if (x == 2) { break block; } x = 3; }; x")
+        .unwrap();
+
+    assert_eq!(result, Value::Smi(2));
+}
+
 // Test unary negation
 #[test]
 fn test_execute_unary_negation() {
@@ -639,3 +752,1331 @@ fn test_limitation_objects() {
     assert!(matches!(result, Value::NativeObject(_)));
     // TODO: Property access and manipulation need implementation
 }
+
+#[test]
+fn test_number_is_nan_does_not_coerce() {
+    let mut runtime = Runtime::new(false);
+
+    // Number.isNaN only accepts actual NaN; the global isNaN coerces first
+    let result = runtime.execute_string("Number.isNaN(\"NaN\")").unwrap();
+    assert_eq!(result, Value::Boolean(false));
+    let result = runtime.execute_string("isNaN(\"NaN\")").unwrap();
+    assert_eq!(result, Value::Boolean(true));
+}
+
+#[test]
+fn test_number_static_predicates() {
+    let mut runtime = Runtime::new(false);
+
+    let result = runtime.execute_string("Number.isSafeInteger(2 ** 53)").unwrap();
+    assert_eq!(result, Value::Boolean(false));
+    let result = runtime.execute_string("Number.isSafeInteger(2 ** 53 - 1)").unwrap();
+    assert_eq!(result, Value::Boolean(true));
+    let result = runtime.execute_string("Number.isInteger(5.5)").unwrap();
+    assert_eq!(result, Value::Boolean(false));
+    let result = runtime.execute_string("Number.isFinite(\"12\")").unwrap();
+    assert_eq!(result, Value::Boolean(false));
+    let result = runtime
+        .execute_string("Number.MAX_SAFE_INTEGER === -Number.MIN_SAFE_INTEGER")
+        .unwrap();
+    assert_eq!(result, Value::Boolean(true));
+    let result = runtime.execute_string("1 + Number.EPSILON > 1").unwrap();
+    assert_eq!(result, Value::Boolean(true));
+}
+
+#[test]
+fn test_object_spread_later_keys_override() {
+    let mut runtime = Runtime::new(false);
+
+    let result = runtime.execute_string("({...{a:1}, a:2}).a").unwrap();
+    assert_eq!(result, Value::Smi(2));
+    // A spread after an explicit key overrides it
+    let result = runtime.execute_string("({a:2, ...{a:1}}).a").unwrap();
+    assert_eq!(result, Value::Smi(1));
+    let result = runtime
+        .execute_string("({...null, ...undefined, b:3}).b")
+        .unwrap();
+    assert_eq!(result, Value::Smi(3));
+}
+
+#[test]
+fn test_object_literal_key_forms() {
+    let mut runtime = Runtime::new(false);
+
+    let result = runtime
+        .execute_string("let k = 'x'; ({[k + 'y']: 5}).xy")
+        .unwrap();
+    assert_eq!(result, Value::Smi(5));
+    let result = runtime.execute_string("({'s t': 3})['s t']").unwrap();
+    assert_eq!(result, Value::Smi(3));
+    let result = runtime.execute_string("({1: 4})[1]").unwrap();
+    assert_eq!(result, Value::Smi(4));
+}
+
+#[test]
+fn test_uri_global_functions() {
+    let mut runtime = Runtime::new(false);
+
+    let result = runtime.execute_string("encodeURIComponent(' /?')").unwrap();
+    assert_eq!(result, Value::String("%20%2F%3F".to_string()));
+    let result = runtime
+        .execute_string("decodeURIComponent(encodeURIComponent('a b/é?'))")
+        .unwrap();
+    assert_eq!(result, Value::String("a b/é?".to_string()));
+    let result = runtime.execute_string("encodeURI('a b/?')").unwrap();
+    assert_eq!(result, Value::String("a%20b/?".to_string()));
+
+    let err = runtime
+        .execute_string("decodeURIComponent('%')")
+        .unwrap_err();
+    assert!(err.to_string().contains("URIError"), "got {}", err);
+}
+
+#[test]
+fn test_global_this() {
+    let mut runtime = Runtime::new(false);
+
+    let result = runtime.execute_string("typeof globalThis").unwrap();
+    assert_eq!(result, Value::String("object".to_string()));
+    let result = runtime.execute_string("globalThis.isNaN('x')").unwrap();
+    assert_eq!(result, Value::Boolean(true));
+    let result = runtime
+        .execute_string("globalThis.answer = 42; answer")
+        .unwrap();
+    assert_eq!(result, Value::Smi(42));
+}
+
+#[test]
+fn test_tail_recursion_runs_in_constant_stack() {
+    let mut runtime = Runtime::new(false);
+
+    // 50000 frames is far past the interpreter's call depth limit
+    let result = runtime
+        .execute_string(
+            "'use strict';
+             function sum(n, acc) { if (n === 0) { return acc; } return sum(n - 1, acc + n); }
+             sum(50000, 0)",
+        )
+        .unwrap();
+    assert_eq!(result, Value::Smi(1_250_025_000));
+}
+
+#[test]
+fn test_tail_call_to_closure_and_native() {
+    let mut runtime = Runtime::new(false);
+
+    let result = runtime
+        .execute_string(
+            "'use strict';
+             function adder(k) { return function(x) { return x + k; }; }
+             const add5 = adder(5);
+             function run(n) { if (n === 0) { return add5(1); } return run(n - 1); }
+             function clamp(x) { return Math.max(x, 2); }
+             run(20000) + clamp(1)",
+        )
+        .unwrap();
+    assert_eq!(result, Value::Double(8.0));
+}
+
+#[test]
+fn test_json_parse_proto_key_does_not_pollute() {
+    let mut runtime = Runtime::new(false);
+
+    let result = runtime
+        .execute_string(
+            "var parsed = JSON.parse('{\"__proto__\": {\"polluted\": true}}');
+             parsed.hasOwnProperty('__proto__')",
+        )
+        .unwrap();
+    assert_eq!(result, Value::Boolean(true));
+    let result = runtime.execute_string("({}).polluted").unwrap();
+    assert_eq!(result, Value::Undefined);
+}
+
+#[test]
+fn test_queue_microtask_runs_after_sync_code_and_before_timers() {
+    let mut runtime = Runtime::new(false);
+
+    runtime
+        .execute_string(
+            "log = [];
+             setTimeout(function () { log.push('timer'); }, 0);
+             queueMicrotask(function () { log.push('microtask'); });
+             log.push('sync');",
+        )
+        .unwrap();
+    let result = runtime.execute_string("log.join(',')").unwrap();
+    assert_eq!(result, Value::String("sync,microtask,timer".to_string()));
+}
+
+#[test]
+fn test_script_and_host_timers_share_the_event_loop() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use std::time::Duration;
+
+    let mut runtime = Runtime::new(false);
+    let host_fired = Rc::new(Cell::new(false));
+    let fired = Rc::clone(&host_fired);
+    let host = async_runtime::Task::new(move || {
+        fired.set(true);
+        Ok(Value::Undefined)
+    });
+    runtime.event_loop().set_timeout(Duration::from_millis(5), host);
+
+    runtime
+        .execute_string(
+            "log = [];
+             setTimeout(function () { log.push('late'); }, 10);
+             setTimeout(function () { log.push('early'); }, 0);",
+        )
+        .unwrap();
+    assert!(host_fired.get());
+    assert_eq!(runtime.event_loop().pending_timer_count(), 0);
+    let result = runtime.execute_string("log.join(',')").unwrap();
+    assert_eq!(result, Value::String("early,late".to_string()));
+}
+
+#[test]
+fn test_throwing_microtask_does_not_abort_drain() {
+    let mut runtime = Runtime::new(false);
+
+    runtime
+        .execute_string(
+            "log = [];
+             queueMicrotask(function () { throw new Error('boom'); });
+             queueMicrotask(function () { log.push('after'); });",
+        )
+        .unwrap();
+    let result = runtime.execute_string("log.join(',')").unwrap();
+    assert_eq!(result, Value::String("after".to_string()));
+}
+
+#[test]
+fn test_throwing_host_jobs_are_recorded_as_uncaught() {
+    let mut runtime = Runtime::new(false);
+
+    runtime
+        .execute_string(
+            "queueMicrotask(function () { throw new TypeError('boom'); });
+             setTimeout(function () { throw new RangeError('late'); }, 0);",
+        )
+        .unwrap();
+    let jobs: Vec<_> = runtime
+        .take_uncaught_errors()
+        .into_iter()
+        .map(|err| err.stack.last().and_then(|frame| frame.function_name.clone()))
+        .collect();
+    let expected = ["async queueMicrotask", "async setTimeout"].map(|name| Some(name.to_string()));
+    assert_eq!(jobs, expected);
+    assert!(runtime.take_uncaught_errors().is_empty());
+
+    // Errors the handler takes are not recorded
+    runtime.set_uncaught_handler(|_| true);
+    runtime.execute_string("queueMicrotask(function () { null.x(); });").unwrap();
+    assert!(runtime.take_uncaught_errors().is_empty());
+}
+
+#[test]
+fn test_object_is_distinguishes_signed_zero() {
+    let mut runtime = Runtime::new(false);
+
+    let result = runtime
+        .execute_string("[Object.is(0, -0), Object.is(NaN, NaN), 0 === -0, NaN === NaN].join(',')")
+        .unwrap();
+    assert_eq!(result, Value::String("false,true,true,false".to_string()));
+}
+
+/// Clock whose time only moves when the test says so
+struct FakeClock {
+    now_ms: std::rc::Rc<std::cell::Cell<f64>>,
+}
+
+impl interpreter::Clock for FakeClock {
+    fn monotonic_ms(&self) -> f64 {
+        self.now_ms.get()
+    }
+
+    fn wall_clock_ms(&self) -> f64 {
+        1_700_000_000_000.0 + self.now_ms.get()
+    }
+}
+
+#[test]
+fn test_performance_now_follows_injected_clock() {
+    let now_ms = std::rc::Rc::new(std::cell::Cell::new(10.25));
+    let mut runtime = Runtime::new(false).with_clock(FakeClock {
+        now_ms: now_ms.clone(),
+    });
+
+    let start = runtime.execute_string("performance.now()").unwrap();
+    assert_eq!(start, Value::Double(10.25));
+
+    now_ms.set(12.75);
+    let later = runtime.execute_string("performance.now()").unwrap();
+    assert_eq!(later, Value::Double(12.75));
+
+    // A clock that steps backwards must not make performance.now() decrease
+    now_ms.set(5.0);
+    let after_step_back = runtime.execute_string("performance.now()").unwrap();
+    assert_eq!(after_step_back, Value::Double(12.75));
+
+    let wall = runtime.execute_string("Date.now()").unwrap();
+    assert_eq!(wall, Value::Double(1_700_000_000_005.0));
+}
+
+#[test]
+fn test_date_constructor_reads_clock() {
+    let mut runtime = Runtime::new(false).with_clock(FakeClock {
+        now_ms: std::rc::Rc::new(std::cell::Cell::new(0.0)),
+    });
+
+    let epoch = runtime
+        .execute_string("var d = new Date(0); d.getTime() + '|' + d.toISOString()")
+        .unwrap();
+    assert_eq!(epoch, Value::String("0|1970-01-01T00:00:00.000Z".to_string()));
+
+    let now = runtime.execute_string("new Date().getTime()").unwrap();
+    assert_eq!(now, Value::Double(1_700_000_000_000.0));
+
+    let parsed = runtime
+        .execute_string("new Date('2020-02-03T04:05:06.007Z').getUTCMonth()")
+        .unwrap();
+    assert_eq!(parsed, Value::Double(1.0));
+
+    let called = runtime.execute_string("typeof Date()").unwrap();
+    assert_eq!(called, Value::String("string".to_string()));
+
+    let invalid = runtime.execute_string("new Date(NaN).toISOString()");
+    assert!(invalid.is_err());
+}
+
+#[test]
+fn test_date_constructor_normalises_components() {
+    let mut runtime = Runtime::new(false);
+    let mut local = |args: &str| {
+        let script = format!(
+            "var d = new Date({}); \
+             [d.getFullYear(), d.getMonth(), d.getDate(), d.getHours()].join('-')",
+            args
+        );
+        runtime.execute_string(&script).unwrap()
+    };
+
+    let expected = |s: &str| Value::String(s.to_string());
+    assert_eq!(local("2020, 0, 0"), expected("2019-11-31-0"));
+    assert_eq!(local("2020, -1, 1"), expected("2019-11-1-0"));
+    assert_eq!(local("2020, 13, 1"), expected("2021-1-1-0"));
+    assert_eq!(local("2020, 0, 1, 25"), expected("2020-0-2-1"));
+    assert_eq!(local("2020, 0, 1, 0, -1"), expected("2019-11-31-23"));
+
+    let clipped = runtime
+        .execute_string("isNaN(new Date(8.64e15 + 1).getTime()) && new Date(8.64e15).getTime()")
+        .unwrap();
+    assert_eq!(clipped, Value::Double(8.64e15));
+}
+
+#[test]
+fn test_deterministic_runtime_repeats_random_sequence() {
+    let script = "var out = []; for (var i = 0; i < 5; i = i + 1) { out.push(Math.random()); }; \
+                  out.join(',')";
+    let first = Runtime::new(false).with_deterministic(1234).execute_string(script).unwrap();
+    let second = Runtime::new(false).with_deterministic(1234).execute_string(script).unwrap();
+    let other_seed = Runtime::new(false).with_deterministic(99).execute_string(script).unwrap();
+
+    assert_eq!(first, second);
+    assert_ne!(first, other_seed);
+}
+
+#[test]
+fn test_deterministic_runtime_has_fixed_advanceable_date() {
+    let mut runtime = Runtime::new(false).with_deterministic(0);
+    let now = runtime.execute_string("Date.now()").unwrap();
+    assert_eq!(now, Value::Double(js_cli::runtime::DETERMINISTIC_EPOCH_MS));
+
+    runtime.advance_clock(1500.0);
+    let later = runtime.execute_string("Date.now() - 1577836800000").unwrap();
+    assert_eq!(later, Value::Smi(1500));
+}
+
+#[test]
+fn test_bind_partially_applies_arguments() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime
+        .execute_string(
+            r#"
+            function add(a, b) { return a + b; }
+            var addOne = add.bind(null, 1);
+            addOne(41);
+        "#,
+        )
+        .unwrap();
+    assert_eq!(result, Value::Smi(42));
+
+    let kind = runtime
+        .execute_string("function f() {} typeof f.bind() + '|' + typeof f.bind().bind(null)")
+        .unwrap();
+    assert_eq!(kind, Value::String("function|function".to_string()));
+}
+
+#[test]
+fn test_bound_function_ignores_later_this_and_constructs_target() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime
+        .execute_string(
+            r#"
+            function name() { return this.name; }
+            var obj = { name: "obj", getName: name.bind({ name: "bound" }) };
+            function Point(x, y) { this.x = x; this.y = y; }
+            var AtOne = Point.bind({ name: "ignored" }, 1);
+            var p = new AtOne(2);
+            obj.getName() + "," + p.x + "," + p.y;
+        "#,
+        )
+        .unwrap();
+    assert_eq!(result, Value::String("bound,1,2".to_string()));
+}
+
+#[test]
+fn test_apply_spreads_array_into_variadic_function() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime.execute_string("Math.max.apply(null, [3, 9, 4])").unwrap();
+    assert_eq!(result, Value::Smi(9));
+}
+
+#[test]
+fn test_call_sets_this() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime
+        .execute_string(
+            r#"
+            function greet(greeting) { return greeting + " " + this.name; }
+            greet.call({ name: "Ada" }, "Hello");
+        "#,
+        )
+        .unwrap();
+    assert_eq!(result, Value::String("Hello Ada".to_string()));
+}
+
+#[test]
+fn test_optional_catch_binding_runs_catch_body() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime
+        .execute_string(
+            r#"
+            result = "not caught";
+            try { throw new Error("boom"); } catch { result = "caught"; }
+            result;
+        "#,
+        )
+        .unwrap();
+    assert_eq!(result, Value::String("caught".to_string()));
+}
+
+#[test]
+fn test_destructuring_catch_binding_extracts_field() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime
+        .execute_string(
+            r#"
+            result = "";
+            try { throw { message: "boom", code: 7 }; } catch ({ message, code: c }) { result = message + c; }
+            result;
+        "#,
+        )
+        .unwrap();
+    assert_eq!(result, Value::String("boom7".to_string()));
+}
+
+#[test]
+fn test_error_cause_is_kept_from_options() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime
+        .execute_string(
+            r#"
+            result = "";
+            try { throw new Error("outer", { cause: "inner" }); } catch ({ message, cause }) { result = message + "<-" + cause; }
+            result;
+        "#,
+        )
+        .unwrap();
+    assert_eq!(result, Value::String("outer<-inner".to_string()));
+}
+
+#[test]
+fn test_computed_member_access_with_string_and_number_keys() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime
+        .execute_string(
+            r#"
+            var obj = { a: "x" };
+            var arr = [10, 20, 30];
+            obj["a"] + arr[1] + arr[1.0] + arr["2"];
+        "#,
+        )
+        .unwrap();
+    assert_eq!(result, Value::String("x202030".to_string()));
+}
+
+#[test]
+fn test_computed_member_access_coerces_object_key_via_to_string() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime
+        .execute_string(
+            r#"
+            var key = { toString: function() { return "name"; } };
+            var dict = {};
+            dict[key] = "stored";
+            dict[{}] = "plain";
+            dict.name + "," + dict["[object Object]"] + "," + dict[key];
+        "#,
+        )
+        .unwrap();
+    assert_eq!(result, Value::String("stored,plain,stored".to_string()));
+}
+
+#[test]
+fn test_string_split_with_regex_and_limit() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime
+        .execute_string(r#""a1b2c".split(/(\d)/).join("|") + ";" + "a,b,c".split(",", 2).join("|")"#)
+        .unwrap();
+    assert_eq!(result, Value::String("a|1|b|2|c;a|b".to_string()));
+}
+
+#[test]
+fn test_reduce_right_and_reduce_without_initial_value() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime
+        .execute_string(
+            r#"
+            var joined = ["a", "b", "c"].reduceRight(function(acc, s) { return acc + s; });
+            var sum = [1, 2, 3].reduce(function(acc, n) { return acc + n; });
+            joined + sum;
+        "#,
+        )
+        .unwrap();
+    assert_eq!(result, Value::String("cba6".to_string()));
+
+    let empty = runtime.execute_string("[].reduce(function(acc, n) { return acc + n; })");
+    assert!(empty.is_err());
+}
+
+#[test]
+fn test_uncaught_handler_suppresses_default_handling() {
+    use core_types::ErrorKind;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    let recorder = Rc::clone(&seen);
+    let mut runtime = Runtime::new(false);
+    runtime.set_uncaught_handler(move |err| {
+        recorder.borrow_mut().push(err.kind.clone());
+        true
+    });
+
+    let result = runtime.execute_string("var f = undefined; f();");
+    assert!(result.is_ok());
+    assert_eq!(*seen.borrow(), vec![ErrorKind::TypeError]);
+
+    // The runtime stays usable after a handled error
+    let result = runtime.execute_string("1 + 1").unwrap();
+    assert_eq!(result, Value::Smi(2));
+}
+
+#[test]
+fn test_host_job_errors_report_script_call_site() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    let recorder = Rc::clone(&seen);
+    let mut runtime = Runtime::new(false);
+    runtime.set_uncaught_handler(move |err| {
+        recorder.borrow_mut().push(err.stack.last().cloned());
+        true
+    });
+
+    let source = "var x = 1;\nqueueMicrotask(function() { throw new Error('late'); });";
+    runtime.execute_string(source).unwrap();
+
+    let seen = seen.borrow();
+    assert_eq!(seen.len(), 1);
+    let frame = seen[0].as_ref().expect("async parent frame");
+    assert_eq!(frame.function_name.as_deref(), Some("async queueMicrotask"));
+    assert_eq!(frame.line, 2);
+}
+
+#[test]
+fn test_cli_error_exit_codes() {
+    use core_types::{ErrorKind, JsError};
+    use js_cli::CliError;
+
+    let syntax = CliError::ParseError("Unexpected token".to_string());
+    assert_eq!(syntax.exit_code(), 2);
+
+    let thrown = CliError::JsError(JsError {
+        kind: ErrorKind::TypeError,
+        message: "not a function".to_string(),
+        stack: vec![],
+        source_position: None,
+    });
+    assert_eq!(thrown.exit_code(), 1);
+}
+
+#[test]
+fn test_array_spread_expands_in_place() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime
+        .execute_string(
+            r#"
+            var a = [...[1, 2], 3, ...[4]];
+            a.join(",") + "|" + a.length + "|" + [..."hi"].join("-");
+        "#,
+        )
+        .unwrap();
+    assert_eq!(result, Value::String("1,2,3,4|4|h-i".to_string()));
+}
+
+#[test]
+fn test_spreading_array_like_throws() {
+    use core_types::ErrorKind;
+
+    let mut runtime = Runtime::new(false);
+    let err = runtime.execute_string("[...{ length: 2 }]").unwrap_err();
+    match err {
+        js_cli::CliError::JsError(err) => assert_eq!(err.kind, ErrorKind::TypeError),
+        other => panic!("expected a TypeError, got {:?}", other),
+    }
+
+    let copied = runtime.execute_string("[...Array.of(1, 2), ...'ab'.split('')].join()").unwrap();
+    assert_eq!(copied, Value::String("1,2,a,b".to_string()));
+}
+
+#[test]
+fn test_index_of_uses_strict_equality() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime
+        .execute_string(
+            r#"
+            var a = [NaN, 1, "1", 1];
+            a.indexOf(NaN) + "|" + a.lastIndexOf(NaN) + "|" + a.includes(NaN) + "|" +
+                a.indexOf(1) + "|" + a.lastIndexOf(1) + "|" + a.lastIndexOf("1", 1);
+        "#,
+        )
+        .unwrap();
+    assert_eq!(result, Value::String("-1|-1|true|1|3|-1".to_string()));
+}
+
+#[test]
+fn test_spread_call_arguments() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime
+        .execute_string(
+            r#"
+            function sum(a, b, c) { return a + b + c; }
+            var o = { base: 100, add: function(a, b) { return this.base + a + b; } };
+            function Pair(a, b) { this.total = a + b; }
+            var pair = new Pair(...[6, 7]);
+            sum(...[1, 2], 3) + o.add(...[4, 5]) + pair.total;
+        "#,
+        )
+        .unwrap();
+    assert_eq!(result, Value::Smi(6 + 109 + 13));
+}
+
+#[test]
+fn test_spread_in_new_and_super_calls() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime
+        .execute_string(
+            r#"
+            class Point { constructor(x, y) { this.x = x; this.y = y; } }
+            var p = new Point(...[1, 2]);
+            class Point3 extends Point {
+                constructor(args, z) { super(...args); this.z = z; }
+            }
+            var q = new Point3([4, 5], 6);
+            class Shifted extends Point {
+                constructor(coords) { var self = super(10, ...coords); this.same = self === this; }
+            }
+            var s = new Shifted([20]);
+            [p.x, p.y, q.x, q.y, q.z, s.x, s.y, s.same].join(",");
+        "#,
+        )
+        .unwrap();
+    assert_eq!(result, Value::String("1,2,4,5,6,10,20,true".to_string()));
+}
+
+#[test]
+fn test_spread_of_non_iterable_throws_type_error() {
+    let mut runtime = Runtime::new(false);
+    match runtime.execute_string("[...{}];") {
+        Err(js_cli::CliError::JsError(err)) => {
+            assert_eq!(err.kind, core_types::ErrorKind::TypeError)
+        }
+        other => panic!("expected TypeError, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_private_fields_read_and_write() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime
+        .execute_string(
+            r#"
+            class Counter {
+                #count = 10;
+                constructor(step) {
+                    this.#count = this.#count + step;
+                    this.value = this.#count;
+                }
+            }
+            var c = new Counter(5);
+            c.value;
+        "#,
+        )
+        .unwrap();
+    assert_eq!(result, Value::Smi(15));
+}
+
+#[test]
+fn test_private_fields_in_class_expression() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime
+        .execute_string(
+            r#"
+            var Counter = class {
+                #count = 1;
+                constructor(step) { this.value = this.#count + step; }
+            };
+            new Counter(2).value;
+        "#,
+        )
+        .unwrap();
+    assert_eq!(result, Value::Smi(3));
+}
+
+#[test]
+fn test_private_fields_are_not_enumerable_keys() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime
+        .execute_string(
+            r##"
+            class Point { #secret = 1; x = 2; constructor() { this.y = 3; } }
+            var point = new Point();
+            var keys = Object.keys(point);
+            keys.length + "|" + keys.indexOf("#secret") + "|" + point.x;
+        "##,
+        )
+        .unwrap();
+    assert_eq!(result, Value::String("2|-1|2".to_string()));
+}
+
+#[test]
+fn test_derived_class_fields_are_set_after_super_returns() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime
+        .execute_string(
+            r#"
+            class A { constructor() { this.x = 1; this.seen = this.y; } }
+            class B extends A { x = 2; y = 3; constructor() { super(); } }
+            class C extends A { x = 4; constructor() { var init = () => super(); init(); } }
+            var b = new B();
+            b.x + "|" + b.seen + "|" + new C().x;
+        "#,
+        )
+        .unwrap();
+    assert_eq!(result, Value::String("2|undefined|4".to_string()));
+}
+
+#[test]
+fn test_private_methods_are_installed_on_instances() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime
+        .execute_string(
+            r#"
+            class A {
+                #scale = 2;
+                #times(x) { return this.#scale * x; }
+                early = this.#times(3);
+                constructor() { this.late = this.#times(5); }
+            }
+            var a = new A();
+            a.early + "|" + a.late;
+        "#,
+        )
+        .unwrap();
+    assert_eq!(result, Value::String("6|10".to_string()));
+
+    match runtime.execute_string("class B { get #v() { return 1; } }") {
+        Err(js_cli::CliError::ParseError(message)) => {
+            assert!(message.contains("SyntaxError") && message.contains("not supported"))
+        }
+        other => panic!("expected SyntaxError, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_object_literal_getter_and_setter() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime
+        .execute_string(
+            r#"
+            var o = {
+                k: 1,
+                get v() { return this.k + 1; },
+                set v(x) { this.k = x * 2; },
+                get ["w" + 1]() { return 7; }
+            };
+            var before = o.v;
+            o.v = 5;
+            before + "|" + o.v + "|" + o.k + "|" + o["w1"] + "|" + Object.keys(o).join();
+        "#,
+        )
+        .unwrap();
+    assert_eq!(result, Value::String("2|11|10|7|k,v,w1".to_string()));
+
+    // Assigning to a getter-only property leaves it alone
+    let result = runtime
+        .execute_string("var p = { get g() { return 1; } }; p.g = 3; p.g;")
+        .unwrap();
+    assert_eq!(result, Value::Smi(1));
+}
+
+#[test]
+fn test_private_field_on_foreign_object_throws_type_error() {
+    let mut runtime = Runtime::new(false);
+    match runtime.execute_string("class A { #x = 1; constructor(o) { this.y = o.#x; } } new A({});") {
+        Err(js_cli::CliError::JsError(err)) => {
+            assert_eq!(err.kind, core_types::ErrorKind::TypeError)
+        }
+        other => panic!("expected TypeError, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_new_links_instance_to_constructor_prototype() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime
+        .execute_string(
+            r#"
+            function Foo(x) { this.x = x; }
+            Foo.prototype.double = function() { return this.x * 2; };
+            var foo = new Foo(21);
+            (foo.constructor === Foo) + "|" + foo.double();
+        "#,
+        )
+        .unwrap();
+    assert_eq!(result, Value::String("true|42".to_string()));
+}
+
+#[test]
+fn test_constructor_returning_object_overrides_this() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime
+        .execute_string(
+            r#"
+            function Override() { this.a = 1; return { b: 2 }; }
+            function Primitive() { this.a = 1; return 5; }
+            var o = new Override();
+            var p = new Primitive();
+            o.a + "|" + o.b + "|" + p.a;
+        "#,
+        )
+        .unwrap();
+    assert_eq!(result, Value::String("undefined|2|1".to_string()));
+}
+
+#[test]
+fn test_new_target_is_constructor_only_under_new() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime
+        .execute_string(
+            r#"
+            function Probe() { return new.target; }
+            function Check() { this.isNew = new.target === Check; }
+            var c = new Check();
+            (Probe() === undefined) + "|" + c.isNew;
+        "#,
+        )
+        .unwrap();
+    assert_eq!(result, Value::String("true|true".to_string()));
+}
+
+#[test]
+fn test_compound_assignment_applies_operator() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime
+        .execute_string(
+            r#"
+            var total = 100;
+            function run() {
+                let x = 10;
+                x += 5;
+                x -= 3;
+                x *= 2;
+                x %= 7;
+                total -= x;
+                let big = 2147483647;
+                big *= 2;
+                return x + "|" + total + "|" + big;
+            }
+            run();
+        "#,
+        )
+        .unwrap();
+    assert_eq!(result, Value::String("3|97|4294967294".to_string()));
+}
+
+#[test]
+fn test_yield_star_delegates_to_array() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime
+        .execute_string(
+            r#"
+            function* g() { yield* [1, 2]; yield 3; }
+            var it = g();
+            var seen = "";
+            var step = it.next();
+            while (!step.done) {
+                seen = seen + step.value + ",";
+                step = it.next();
+            }
+            seen;
+        "#,
+        )
+        .unwrap();
+    assert_eq!(result, Value::String("1,2,3,".to_string()));
+}
+
+#[test]
+fn test_yield_star_forwards_return_and_result() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime
+        .execute_string(
+            r#"
+            function* inner() { yield 1; yield 2; }
+            var i = inner();
+            function* g() { yield* i; }
+            var it = g();
+            it.next();
+            var r = it.return(5);
+
+            function* doubler() { var a = yield 1; return a * 2; }
+            function* outer() { var v = yield* doubler(); yield v; }
+            var o = outer();
+            o.next();
+            r.value + "|" + r.done + "|" + i.next().done + "|" + o.next(21).value;
+        "#,
+        )
+        .unwrap();
+    assert_eq!(result, Value::String("5|true|true|42".to_string()));
+}
+
+#[test]
+fn test_for_of_and_spread_drive_generators() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime
+        .execute_string(
+            r#"
+            function* letters() { yield "a"; yield "b"; yield "c"; }
+            var out = "";
+            for (var n of [1, 2, 3]) { out = out + n; }
+            for (const letter of letters()) {
+                if (letter === "c") break;
+                out = out + letter;
+            }
+            for (let ch of "xyz") {
+                if (ch === "y") continue;
+                out = out + ch;
+            }
+            var pair;
+            for (pair of [[1, 2], [3, 4]]) {
+                for (const [p, q] of [pair]) { out = out + (p + q); }
+            }
+            function* nums() { yield 4; yield 9; }
+            out + "|" + [...letters()].join("-") + "|" + Math.max(...nums());
+        "#,
+        )
+        .unwrap();
+    assert_eq!(result, Value::String("123abxz37|a-b-c|9".to_string()));
+}
+
+#[test]
+fn test_generator_return_runs_pending_finally_blocks() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime
+        .execute_string(
+            r#"
+            var state = { log: "" };
+            function* g() {
+                try {
+                    try { yield 1; yield 2; } finally { state.log = state.log + "inner,"; }
+                } finally {
+                    state.log = state.log + "outer,";
+                }
+            }
+            var it = g();
+            it.next();
+            var r = it.return(9);
+            var after = it.next();
+
+            function* h() {
+                try { yield 1; } finally { yield "cleanup"; state.log = state.log + "h,"; }
+            }
+            var k = h();
+            k.next();
+            var c = k.return(7);
+            var d = k.next();
+
+            function* thrower() {
+                try {
+                    try { yield 1; } finally { state.log = state.log + "t,"; }
+                } catch (e) {
+                    yield "caught " + e;
+                }
+            }
+            var t = thrower();
+            t.next();
+            var e = t.throw(5);
+
+            state.log + "|" + r.value + r.done + after.done + "|" + c.value + c.done + "|"
+                + d.value + d.done + "|" + e.value;
+        "#,
+        )
+        .unwrap();
+    assert_eq!(
+        result,
+        Value::String("inner,outer,h,t,|9truetrue|cleanupfalse|7true|caught 5".to_string())
+    );
+}
+
+#[test]
+fn test_error_capture_stack_trace_and_limit() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime
+        .execute_string(
+            r#"
+            function MyError(message) {
+                this.name = "MyError";
+                this.message = message;
+                Error.captureStackTrace(this, MyError);
+            }
+            function helper() { return new MyError("bad"); }
+            function outer() { return helper(); }
+            var trimmed = outer().stack.split("\n");
+
+            var plain = {};
+            function capture() { Error.captureStackTrace(plain); }
+            capture();
+            var full = plain.stack.split("\n");
+
+            var initial = Error.stackTraceLimit;
+            Error.stackTraceLimit = 1;
+            var limited = helper().stack.split("\n").length;
+            Error.stackTraceLimit = 0;
+            var bare = new TypeError("t").stack;
+
+            trimmed[0] + "|" + trimmed.length + "|" + (trimmed[1].indexOf(":7:") > 0)
+                + "|" + full.length + "|" + initial + "|" + limited + "|" + bare;
+        "#,
+        )
+        .unwrap();
+    // MyError's own frame is omitted: the trace starts in its caller, helper
+    assert_eq!(
+        result,
+        Value::String("MyError: bad|4|true|3|10|2|TypeError: t".to_string())
+    );
+}
+
+#[test]
+fn test_contextual_keywords_used_as_identifiers_divide() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime
+        .execute_string("var of = 8; var yield = 4; (of / 2 / 1) + ',' + (yield / 2 / 1)")
+        .unwrap();
+    assert_eq!(result, Value::String("4,2".to_string()));
+}
+
+#[test]
+fn test_finally_runs_before_an_exception_propagates() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime
+        .execute_string(
+            r#"
+            var state = { log: "" };
+            try {
+                try { throw 1; } finally { state.log = state.log + "f,"; }
+            } catch (e) {
+                state.log = state.log + "caught " + e;
+            }
+            state.log;
+        "#,
+        )
+        .unwrap();
+    assert_eq!(result, Value::String("f,caught 1".to_string()));
+}
+
+#[test]
+fn test_nullish_assignment_short_circuits() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime
+        .execute_string(
+            r#"
+            var calls = [];
+            function expensive() { calls.push("x"); return 9; }
+            var a = 1;
+            a ??= expensive();
+            var b = null;
+            b ??= expensive();
+            var zero = 0;
+            zero &&= expensive();
+            var o = { p: 0 };
+            o.p ??= expensive();
+            o.q ||= 9;
+            a + "|" + b + "|" + zero + "|" + o.p + "|" + o.q + "|" + calls.length;
+        "#,
+        )
+        .unwrap();
+    assert_eq!(result, Value::String("1|9|0|0|9|1".to_string()));
+}
+
+#[test]
+fn test_conditional_evaluates_only_selected_branch() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime
+        .execute_string(
+            r#"
+            var seen = [];
+            function mark(name) { seen.push(name); return name; }
+            var first = true ? mark("yes") : mark("no");
+            var second = 0 ? mark("then") : mark("else");
+            first + "|" + second + "|" + seen.join(",");
+        "#,
+        )
+        .unwrap();
+    assert_eq!(result, Value::String("yes|else|yes,else".to_string()));
+}
+
+#[test]
+fn test_realm_globals_are_isolated() {
+    let mut runtime = Runtime::new(false);
+    let first = runtime.create_realm();
+    let second = runtime.create_realm();
+
+    runtime.execute_in_realm(first, "globalThis.secret = 42;").unwrap();
+    let seen = runtime
+        .execute_in_realm(second, "typeof secret")
+        .unwrap();
+    assert_eq!(seen, Value::String("undefined".to_string()));
+    let kept = runtime.execute_in_realm(first, "secret").unwrap();
+    assert_eq!(kept, Value::Smi(42));
+    assert!(runtime.vm().get_global("secret").is_none());
+}
+
+#[test]
+fn test_realms_have_distinct_intrinsics() {
+    let mut runtime = Runtime::new(false);
+    let first = runtime.create_realm();
+    let second = runtime.create_realm();
+
+    let proto = runtime.execute_in_realm(first, "Object.prototype").unwrap();
+    let array = runtime.execute_in_realm(first, "[1, 2, 3]").unwrap();
+    let array_ctor = runtime.execute_in_realm(first, "Array").unwrap();
+    let make_array = runtime
+        .execute_in_realm(first, "function makeArray() { return [Array, []]; } makeArray")
+        .unwrap();
+
+    // Hand values from the first realm to the second
+    let previous = runtime.vm().enter_realm(second).unwrap();
+    runtime.vm().set_global("otherProto".to_string(), proto);
+    runtime.vm().set_global("otherArray".to_string(), array);
+    runtime.vm().set_global("OtherArray".to_string(), array_ctor);
+    runtime.vm().set_global("makeArray".to_string(), make_array);
+    runtime.vm().enter_realm(previous).unwrap();
+
+    let result = runtime
+        .execute_in_realm(
+            second,
+            r#"
+            function F() {}
+            var made = makeArray();
+            [
+                otherProto !== Object.prototype,
+                Array.isArray(otherArray),
+                otherArray.length,
+                otherArray instanceof Array,
+                otherArray instanceof OtherArray,
+                [] instanceof Array,
+                OtherArray !== Array,
+                made[0] === OtherArray,
+                made[1] instanceof OtherArray,
+                new F() instanceof F,
+            ].join("|");
+        "#,
+        )
+        .unwrap();
+    assert_eq!(
+        result,
+        Value::String("true|true|3|false|true|true|true|true|true|true".to_string())
+    );
+}
+
+#[test]
+fn test_exponentiation_operator() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime.execute_string("2 ** 10").unwrap();
+    assert_eq!(result, Value::Double(1024.0));
+    // Right-associative: 2 ** (3 ** 2)
+    let result = runtime.execute_string("2 ** 3 ** 2").unwrap();
+    assert_eq!(result, Value::Double(512.0));
+    let result = runtime.execute_string("(-2) ** 2").unwrap();
+    assert_eq!(result, Value::Double(4.0));
+    assert!(runtime.execute_string("-2 ** 2").is_err());
+}
+
+#[test]
+fn test_bitwise_operators_use_to_int32() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime.execute_string("-1 >>> 0").unwrap();
+    assert_eq!(result, Value::Double(4294967295.0));
+    let result = runtime.execute_string("2.9 | 0").unwrap();
+    assert_eq!(result, Value::Smi(2));
+    let result = runtime.execute_string("~5").unwrap();
+    assert_eq!(result, Value::Smi(-6));
+    let result = runtime.execute_string("4294967297 | 0").unwrap();
+    assert_eq!(result, Value::Smi(1));
+    let result = runtime.execute_string("1 << 31").unwrap();
+    assert_eq!(result, Value::Smi(i32::MIN));
+    let result = runtime.execute_string("-16 >> 2").unwrap();
+    assert_eq!(result, Value::Smi(-4));
+    let result = runtime.execute_string("(5 & 3) + (5 ^ 3) * 10").unwrap();
+    assert_eq!(result, Value::Smi(61));
+}
+
+#[test]
+fn test_bitwise_compound_assignment() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime
+        .execute_string(
+            r#"
+            var h = 5;
+            h |= 2;
+            h <<= 4;
+            var o = { bits: 12 };
+            o.bits >>>= 2;
+            o.bits ^= 1;
+            h + "|" + o.bits;
+        "#,
+        )
+        .unwrap();
+    assert_eq!(result, Value::String("112|2".to_string()));
+}
+
+#[test]
+fn test_objects_convert_through_to_primitive() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime
+        .execute_string(
+            r#"
+            var hints = [];
+            var o = {
+                [Symbol.toPrimitive](hint) {
+                    hints.push(hint);
+                    return hint === "string" ? "str" : 41;
+                }
+            };
+            var v = { valueOf() { return 2; } };
+            (o + 1) + "|" + (+o) + "|" + `${o}!` + "|" + (v * 3) + "|" +
+                typeof Symbol.toPrimitive + "|" + hints.join(",");
+        "#,
+        )
+        .unwrap();
+    assert_eq!(
+        result,
+        Value::String("42|41|str!|6|symbol|default,number,string".to_string())
+    );
+}
+
+#[test]
+fn test_object_keys_follow_insertion_order() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime
+        .execute_string("var o = { b: 1, a: 2, 1: 3 }; o.c = 4; Object.keys(o).join();")
+        .unwrap();
+    assert_eq!(result, Value::String("1,b,a,c".to_string()));
+}
+
+#[test]
+fn test_array_methods_respect_holes() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime
+        .execute_string(
+            r#"
+            var visited = [];
+            var sparse = [1, , 3];
+            sparse.forEach(function (v, i) { visited.push(i); });
+            var doubled = sparse.map(function (v) { return v * 2; });
+            var mapped = [];
+            doubled.forEach(function (v, i) { mapped.push(i + ":" + v); });
+            visited.join() + "|" + doubled.length + "|" + mapped.join() + "|" +
+                [1, [2, [3, [4]]], , 5].flat().length + "|" +
+                [1, [2, [3, [4]]]].flat(Infinity).join();
+        "#,
+        )
+        .unwrap();
+    assert_eq!(result, Value::String("0,2|3|0:2,2:6|4|1,2,3,4".to_string()));
+}
+
+#[test]
+fn test_array_likes_are_not_arrays() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime
+        .execute_string(
+            r#"
+            var like = { length: 1, map: "own" };
+            Array.isArray(like) + "|" + Array.isArray([]) + "|" + like.map + "|" +
+                Object.prototype.toString.call(like);
+        "#,
+        )
+        .unwrap();
+    assert_eq!(result, Value::String("false|true|own|[object Object]".to_string()));
+}
+
+#[test]
+fn test_console_assert_and_dir_are_callable() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime
+        .execute_string(
+            r#"
+            var o = { nested: { deeper: {} } };
+            o.self = o;
+            typeof console.assert + "|" + typeof console.dir + "|" +
+                console.assert(true, "hidden") + "|" + console.dir(o, { depth: 0 });
+        "#,
+        )
+        .unwrap();
+    assert_eq!(result, Value::String("function|function|undefined|undefined".to_string()));
+}
+
+#[test]
+fn test_super_calls_in_class_expressions_and_arrows() {
+    let mut runtime = Runtime::new(false);
+    let result = runtime
+        .execute_string(
+            r#"
+            class A { constructor(x) { this.x = x; } }
+            var C = class extends A { constructor(x) { super(x); this.y = 2; } };
+            class D extends A { constructor(x) { var f = () => super(x * 10); f(); } }
+            var E = class extends A {
+                constructor(x) { const g = () => { super(x + 1); }; g(); }
+            };
+            var c = new C(5);
+            [c.x, c.y, new D(3).x, new E(1).x].join(",");
+        "#,
+        )
+        .unwrap();
+    assert_eq!(result, Value::String("5,2,30,2".to_string()));
+}
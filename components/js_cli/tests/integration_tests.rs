@@ -44,6 +44,7 @@ fn integration_debug_output_workflow() {
         jit: true,
         print_bytecode: true,
         print_ast: true,
+        allow_net: false,
     };
 
     let mut runtime = Runtime::new(cli.jit)
@@ -108,6 +109,7 @@ fn integration_cli_to_runtime_config() {
         jit: false,
         print_bytecode: true,
         print_ast: true,
+        allow_net: false,
     };
 
     let runtime = Runtime::new(cli.jit)
@@ -154,6 +156,7 @@ fn integration_jit_setting_preserved() {
         jit: true,
         print_bytecode: false,
         print_ast: false,
+        allow_net: false,
     };
 
     let cli_jit_off = Cli {
@@ -163,6 +166,7 @@ fn integration_jit_setting_preserved() {
         jit: false,
         print_bytecode: false,
         print_ast: false,
+        allow_net: false,
     };
 
     let runtime_on = Runtime::new(cli_jit_on.jit);
@@ -259,3 +263,82 @@ fn integration_runtime_reuse() {
         assert!(result.is_ok(), "Failed on iteration {}", i);
     }
 }
+
+/// Test compiling to a bytecode cache and executing from it
+#[test]
+fn integration_bytecode_cache_roundtrip() {
+    let dir = TempDir::new().unwrap();
+    let src_path = dir.path().join("cached.js");
+    let cbc_path = dir.path().join("cached.cbc");
+
+    let js_code = r#"
+        function square(x) {
+            return x * x;
+        }
+        let total = 0;
+        for (let i = 1; i <= 4; i = i + 1) {
+            total = total + square(i);
+        }
+        total
+    "#;
+    fs::write(&src_path, js_code).unwrap();
+
+    let direct = Runtime::new(false).execute_string(js_code).unwrap();
+    assert_eq!(direct, core_types::Value::Smi(30));
+
+    let mut runtime = Runtime::new(false);
+    runtime
+        .compile_to_file(src_path.to_str().unwrap(), cbc_path.to_str().unwrap())
+        .unwrap();
+    assert!(cbc_path.exists());
+
+    let cached = Runtime::new(false)
+        .execute_cached(cbc_path.to_str().unwrap())
+        .unwrap();
+    assert_eq!(cached, direct);
+}
+
+/// Test that a cache is rejected once its source has changed
+#[test]
+fn integration_bytecode_cache_stale_source() {
+    let dir = TempDir::new().unwrap();
+    let src_path = dir.path().join("stale.js");
+    let cbc_path = dir.path().join("stale.cbc");
+
+    fs::write(&src_path, "1 + 1").unwrap();
+    Runtime::new(false)
+        .compile_to_file(src_path.to_str().unwrap(), cbc_path.to_str().unwrap())
+        .unwrap();
+
+    fs::write(&src_path, "2 + 2").unwrap();
+    let result = Runtime::new(false).execute_cached(cbc_path.to_str().unwrap());
+    assert!(matches!(result, Err(js_cli::CliError::CacheError(_))));
+}
+
+/// Test that a file's imports are loaded and evaluated before it
+#[test]
+fn integration_module_imports_run_first() {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("util.js"), "export function helper() { return 41; }").unwrap();
+    let main_path = dir.path().join("main.js");
+    fs::write(&main_path, "import { helper } from './util.js';\nhelper() + 1;").unwrap();
+
+    let mut runtime = Runtime::new(false);
+    let result = runtime.execute_file(main_path.to_str().unwrap()).unwrap();
+    assert_eq!(result, core_types::Value::Smi(42));
+}
+
+/// Test that remote imports fail unless --allow-net was given
+#[test]
+fn integration_remote_import_requires_allow_net() {
+    let dir = TempDir::new().unwrap();
+    let main_path = dir.path().join("main.js");
+    fs::write(&main_path, "import { x } from 'https://example.com/x.js';\nx;").unwrap();
+
+    let cli = Cli::with_file(main_path.to_str().unwrap().to_string());
+    let mut runtime = Runtime::new(cli.jit).with_allow_net(cli.allow_net);
+    match runtime.execute_file(cli.file.as_ref().unwrap()) {
+        Err(js_cli::CliError::JsError(err)) => assert!(err.message.contains("--allow-net")),
+        other => panic!("expected net access error, got {:?}", other),
+    }
+}
@@ -7,7 +7,7 @@
 //! - Update all references to new locations
 //! - Swap spaces after collection
 
-use std::collections::{HashMap, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::ptr;
 
 /// Mark colors for tri-color marking.
@@ -392,16 +392,170 @@ pub struct OldGeneration {
     gray_list: VecDeque<*mut GcObject>,
     /// Total memory used by objects in this generation
     total_size: usize,
+    /// Contiguous space for objects allocated directly in the old generation
+    /// (word-backed so every object is 8-byte aligned)
+    space: Vec<u64>,
+    /// Objects in the space that compaction must not move
+    pinned: HashSet<*mut GcObject>,
 }
 
 impl OldGeneration {
     /// Creates a new empty old generation.
     pub fn new() -> Self {
+        Self::with_space(0)
+    }
+
+    /// Creates a new empty old generation with a contiguous allocation space.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Size of the old-generation space in bytes (rounded up to 8)
+    pub fn with_space(capacity: usize) -> Self {
         OldGeneration {
             objects: Vec::new(),
             gray_list: VecDeque::new(),
             total_size: 0,
+            space: vec![0u64; align_to_word(capacity) / 8],
+            pinned: HashSet::new(),
+        }
+    }
+
+    /// Allocates `size` bytes in the old-generation space using first-fit.
+    ///
+    /// The returned memory is not tracked until it is initialized as a
+    /// GcObject and passed to [`OldGeneration::add_object`].
+    ///
+    /// # Returns
+    ///
+    /// Pointer to the allocated memory, or None if no free block is large enough.
+    pub fn allocate(&mut self, size: usize) -> Option<*mut u8> {
+        let size = align_to_word(size);
+        let base = self.space_base() as usize;
+        let mut cursor = 0;
+
+        for (offset, obj_size) in self.space_layout() {
+            if offset - cursor >= size {
+                break;
+            }
+            cursor = offset + obj_size;
         }
+
+        if self.space_capacity() - cursor >= size {
+            Some((base + cursor) as *mut u8)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the size of the old-generation space in bytes.
+    pub fn space_capacity(&self) -> usize {
+        self.space.len() * 8
+    }
+
+    /// Returns the offset just past the highest live object in the space.
+    pub fn space_frontier(&self) -> usize {
+        self.space_layout()
+            .last()
+            .map_or(0, |&(offset, size)| offset + size)
+    }
+
+    /// Returns the largest contiguous free block in the space in bytes.
+    pub fn largest_free_block(&self) -> usize {
+        let mut cursor = 0;
+        let mut largest = 0;
+        for (offset, size) in self.space_layout() {
+            largest = largest.max(offset - cursor);
+            cursor = offset + size;
+        }
+        largest.max(self.space_capacity() - cursor)
+    }
+
+    /// Checks if a pointer lies within the old-generation space.
+    pub fn is_in_space(&self, ptr: *const u8) -> bool {
+        let base = self.space_base() as usize;
+        let addr = ptr as usize;
+        addr >= base && addr < base + self.space_capacity()
+    }
+
+    /// Excludes an object from being moved by compaction.
+    pub fn pin(&mut self, obj: *mut GcObject) {
+        if !obj.is_null() {
+            self.pinned.insert(obj);
+        }
+    }
+
+    /// Allows a previously pinned object to be moved again.
+    pub fn unpin(&mut self, obj: *mut GcObject) {
+        self.pinned.remove(&obj);
+    }
+
+    /// Returns whether an object is pinned.
+    pub fn is_pinned(&self, obj: *mut GcObject) -> bool {
+        self.pinned.contains(&obj)
+    }
+
+    /// Slides live objects in the space towards its start.
+    ///
+    /// Objects are visited in address order and moved down to close any
+    /// gaps left by swept objects. Pinned objects stay where they are, so
+    /// gaps below them can only be filled up to their start.
+    ///
+    /// # Returns
+    ///
+    /// Pairs of (old_location, new_location) for every moved object, so
+    /// callers can update references held outside the generation.
+    pub fn compact(&mut self) -> Vec<(*mut GcObject, *mut GcObject)> {
+        let base = self.space_base() as usize;
+        let mut relocations = Vec::new();
+        let mut cursor = 0;
+
+        for (offset, size) in self.space_layout() {
+            let obj = (base + offset) as *mut GcObject;
+            if self.pinned.contains(&obj) || offset == cursor {
+                cursor = offset + size;
+                continue;
+            }
+
+            let new_location = (base + cursor) as *mut GcObject;
+            // SAFETY: Both ranges lie within the space; ptr::copy handles the
+            // overlap when an object slides over part of its old location.
+            unsafe {
+                ptr::copy(obj as *const u8, new_location as *mut u8, size);
+                (*new_location).header.forwarding = ptr::null_mut();
+            }
+            relocations.push((obj, new_location));
+            cursor += size;
+        }
+
+        if !relocations.is_empty() {
+            let moved: HashMap<_, _> = relocations.iter().copied().collect();
+            for obj in &mut self.objects {
+                if let Some(&new_location) = moved.get(obj) {
+                    *obj = new_location;
+                }
+            }
+        }
+
+        relocations
+    }
+
+    /// Returns the space base address.
+    fn space_base(&self) -> *const u8 {
+        self.space.as_ptr() as *const u8
+    }
+
+    /// Returns (offset, size) of every object in the space, sorted by offset.
+    fn space_layout(&self) -> Vec<(usize, usize)> {
+        let base = self.space_base() as usize;
+        let mut layout: Vec<(usize, usize)> = self
+            .objects
+            .iter()
+            .filter(|&&obj| self.is_in_space(obj as *const u8))
+            // SAFETY: Objects in our list are guaranteed valid by add_object
+            .map(|&obj| (obj as usize - base, align_to_word(unsafe { (*obj).total_size() })))
+            .collect();
+        layout.sort_unstable();
+        layout
     }
 
     /// Adds a promoted object from the young generation.
@@ -567,7 +721,7 @@ impl OldGeneration {
         let mut freed = 0;
         let mut retained = Vec::new();
 
-        for obj_ptr in self.objects.drain(..) {
+        for obj_ptr in std::mem::take(&mut self.objects) {
             if obj_ptr.is_null() {
                 continue;
             }
@@ -583,6 +737,12 @@ impl OldGeneration {
                 freed += size;
                 self.total_size -= size;
 
+                // Objects in the space are reclaimed by dropping them from the list
+                if self.is_in_space(obj_ptr as *const u8) {
+                    self.pinned.remove(&obj_ptr);
+                    continue;
+                }
+
                 // SAFETY: We're freeing the object, which was allocated with Box
                 // The caller is responsible for ensuring proper allocation/deallocation
                 unsafe {
@@ -638,6 +798,11 @@ impl OldGeneration {
     }
 }
 
+/// Rounds a byte count up to the old-generation space's 8-byte alignment.
+fn align_to_word(size: usize) -> usize {
+    (size + 7) & !7
+}
+
 impl Default for OldGeneration {
     fn default() -> Self {
         Self::new()
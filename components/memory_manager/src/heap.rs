@@ -6,7 +6,11 @@
 
 use crate::gc::{GcObject, GcObjectHeader, OldGeneration, YoungGeneration};
 use crate::write_barrier::{CardTable, RememberedSet};
+use core_types::Value;
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::ptr;
+use std::rc::{Rc, Weak};
 
 /// Statistics tracking for garbage collection operations.
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
@@ -59,6 +63,10 @@ pub struct Heap {
     gc_stats: GcStats,
     /// Root objects that should not be collected
     roots: Vec<*mut GcObject>,
+    /// Roots registered by embedders, shared with their [`RootHandle`]s
+    handle_roots: Rc<RefCell<HandleRoots>>,
+    /// Check the remembered set against the old generation before each young GC
+    verify_barriers: bool,
 }
 
 impl Heap {
@@ -75,18 +83,34 @@ impl Heap {
     ///
     /// * `young_gen_size` - Size of each semi-space in the young generation (bytes)
     /// * `promotion_threshold` - Age at which objects are promoted to old gen
+    ///
+    /// The old-generation space used by [`Heap::allocate_old`] is sized to
+    /// match one semi-space.
     pub fn with_config(young_gen_size: usize, promotion_threshold: u8) -> Self {
         Heap {
             young_gen: YoungGeneration::new(young_gen_size),
-            old_gen: OldGeneration::new(),
+            old_gen: OldGeneration::with_space(young_gen_size),
             remembered_set: RememberedSet::new(),
             card_table: None, // Can be enabled later if needed
             promotion_threshold,
             gc_stats: GcStats::default(),
             roots: Vec::new(),
+            handle_roots: Rc::new(RefCell::new(HandleRoots::default())),
+            verify_barriers: false,
         }
     }
 
+    /// Enables write barrier verification, a debugging aid for VM developers.
+    ///
+    /// Before each young collection the whole old generation is scanned for
+    /// old-to-young pointers, and the collection panics if any holder is
+    /// missing from the remembered set. See [`Heap::find_missing_barriers`]
+    /// for how pointers are found.
+    pub fn with_barrier_verification(mut self) -> Self {
+        self.verify_barriers = true;
+        self
+    }
+
     /// Allocates memory for an object, triggering GC if needed.
     ///
     /// This method tries to allocate in the young generation first.
@@ -165,9 +189,21 @@ impl Heap {
     /// 3. Updates the remembered set after collection
     /// 4. Tracks GC statistics
     pub fn collect_garbage(&mut self) {
+        if self.verify_barriers {
+            if let Some(&(holder, target)) = self.find_missing_barriers().first() {
+                panic!(
+                    "write barrier missed: old object {:p} references young object {:p} \
+                     but is not in the remembered set",
+                    holder, target
+                );
+            }
+        }
+
         // Combine application roots with remembered set (old-to-young references)
-        let mut all_roots = self.roots.clone();
+        let mut all_roots = self.all_roots();
         all_roots.extend(self.remembered_set.as_roots());
+        // Only young objects are evacuated; old-generation roots stay put
+        all_roots.retain(|&root| !self.old_gen.is_in_space(root as *const u8));
 
         // Perform young generation collection
         let relocations = self.young_gen.collect(&all_roots);
@@ -211,13 +247,90 @@ impl Heap {
         self.collect_garbage();
 
         // Then collect old generation
-        let old_roots: Vec<*mut GcObject> = self.roots.clone();
+        let old_roots = self.all_roots();
         let freed = self.old_gen.collect(&old_roots);
 
         self.gc_stats.total_freed += freed;
         self.gc_stats.old_gc_count += 1;
     }
 
+    /// Allocates memory for an object directly in the old generation.
+    ///
+    /// Used for large or long-lived objects that should skip the young
+    /// generation. Unlike [`Heap::allocate`], this never triggers a GC;
+    /// when the old-generation space is too fragmented to fit the object,
+    /// running [`Heap::compact`] may free up a large enough block.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - Number of bytes to allocate (will include GcObjectHeader)
+    ///
+    /// # Returns
+    ///
+    /// Pointer to allocated memory, or null if no free block is large enough.
+    pub fn allocate_old(&mut self, size: usize) -> *mut u8 {
+        // Keep the recorded size in step with the space's 8-byte layout
+        let total_size = (size + std::mem::size_of::<GcObjectHeader>() + 7) & !7;
+
+        match self.old_gen.allocate(total_size) {
+            Some(ptr) => {
+                // SAFETY: ptr points to free memory of at least total_size bytes
+                // inside the old-generation space
+                unsafe {
+                    let obj = ptr as *mut GcObject;
+                    (*obj).header = GcObjectHeader::new(total_size as u32);
+                    self.old_gen.add_object(obj);
+                }
+
+                self.gc_stats.total_allocated += total_size;
+                ptr
+            }
+            None => ptr::null_mut(),
+        }
+    }
+
+    /// Compacts the old-generation space to reduce fragmentation.
+    ///
+    /// Live objects are slid towards the start of the space, closing gaps
+    /// left by collected objects. Roots and remembered-set entries are
+    /// updated to the new locations. Pinned objects are never moved.
+    ///
+    /// # Returns
+    ///
+    /// Number of bytes reclaimed at the end of the space.
+    pub fn compact(&mut self) -> usize {
+        let frontier_before = self.old_gen.space_frontier();
+        let relocations = self.old_gen.compact();
+
+        self.update_roots(&relocations);
+
+        // Swap entries in two passes so a new location can't be mistaken
+        // for an old one that is still waiting to be moved
+        let remembered: Vec<_> = relocations
+            .iter()
+            .filter(|&&(old_loc, _)| self.remembered_set.contains(old_loc))
+            .copied()
+            .collect();
+        for &(old_loc, _) in &remembered {
+            self.remembered_set.remove(old_loc);
+        }
+        for &(_, new_loc) in &remembered {
+            self.remembered_set.add(new_loc);
+        }
+
+        frontier_before - self.old_gen.space_frontier()
+    }
+
+    /// Pins an old-generation object so compaction will not move it.
+    pub fn pin(&mut self, obj: *mut GcObject) {
+        self.old_gen.pin(obj);
+    }
+
+    /// Unpins an object, allowing compaction to move it again.
+    pub fn unpin(&mut self, obj: *mut GcObject) {
+        self.old_gen.unpin(obj);
+    }
+
     /// Promotes objects from young to old generation based on age.
     ///
     /// Objects that have survived enough collections (reached promotion_threshold)
@@ -275,6 +388,18 @@ impl Heap {
                 }
             }
         }
+        for root in self.handle_roots.borrow_mut().entries.values_mut() {
+            if let Some(&(_, new_loc)) = relocations.iter().find(|&&(old, _)| old == *root) {
+                *root = new_loc;
+            }
+        }
+    }
+
+    /// Returns application roots followed by roots held through handles.
+    fn all_roots(&self) -> Vec<*mut GcObject> {
+        let mut roots = self.roots.clone();
+        roots.extend(self.handle_roots.borrow().entries.values().copied());
+        roots
     }
 
     /// Adds a root object to the heap.
@@ -288,6 +413,42 @@ impl Heap {
         }
     }
 
+    /// Registers a value as a root on behalf of an embedder.
+    ///
+    /// The value stays alive, along with everything reachable from it, until
+    /// the returned [`RootHandle`] is dropped. Values that are not heap
+    /// objects produce a handle that roots nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core_types::Value;
+    /// use memory_manager::Heap;
+    ///
+    /// let mut heap = Heap::with_config(1024, 3);
+    /// let ptr = heap.allocate(32);
+    ///
+    /// let handle = heap.add_value_root(&Value::HeapObject(ptr as usize));
+    /// assert_eq!(heap.root_count(), 1);
+    ///
+    /// drop(handle);
+    /// assert_eq!(heap.root_count(), 0);
+    /// ```
+    pub fn add_value_root(&mut self, value: &Value) -> RootHandle {
+        let mut handle_roots = self.handle_roots.borrow_mut();
+        let id = handle_roots.next_id;
+        handle_roots.next_id += 1;
+        if let Value::HeapObject(addr) = value {
+            if *addr != 0 {
+                handle_roots.entries.insert(id, *addr as *mut GcObject);
+            }
+        }
+        RootHandle {
+            id,
+            roots: Rc::downgrade(&self.handle_roots),
+        }
+    }
+
     /// Removes a root object from the heap.
     pub fn remove_root(&mut self, root: *mut GcObject) {
         self.roots.retain(|&r| r != root);
@@ -298,9 +459,9 @@ impl Heap {
         self.roots.clear();
     }
 
-    /// Returns the number of roots.
+    /// Returns the number of roots, including those held through handles.
     pub fn root_count(&self) -> usize {
-        self.roots.len()
+        self.roots.len() + self.handle_roots.borrow().entries.len()
     }
 
     /// Returns the current size of the young generation (used space).
@@ -472,6 +633,47 @@ impl Heap {
         );
     }
 
+    /// Finds old-to-young references whose holder is not in the remembered set.
+    ///
+    /// Objects carry no type information, so the payload of every object in
+    /// the old-generation space is scanned conservatively: any word that
+    /// points into the young generation counts as a reference. Promoted
+    /// objects hold only their header and have nothing to scan.
+    ///
+    /// # Returns
+    ///
+    /// `(holder, target)` pairs, one per unremembered reference.
+    pub fn find_missing_barriers(&self) -> Vec<(*mut GcObject, *mut GcObject)> {
+        let header_size = std::mem::size_of::<GcObjectHeader>();
+        let word_size = std::mem::size_of::<usize>();
+        let mut missing = Vec::new();
+
+        for &holder in self.old_gen.objects() {
+            if holder.is_null()
+                || !self.old_gen.is_in_space(holder as *const u8)
+                || self.remembered_set.contains(holder)
+            {
+                continue;
+            }
+
+            // SAFETY: objects in the old-generation space own `total_size`
+            // bytes starting at their header
+            unsafe {
+                let size = (*holder).total_size();
+                let mut offset = header_size;
+                while offset + word_size <= size {
+                    let word = ((holder as *const u8).add(offset) as *const usize).read_unaligned();
+                    if self.is_in_young_gen(word as *const u8) {
+                        missing.push((holder, word as *mut GcObject));
+                    }
+                    offset += word_size;
+                }
+            }
+        }
+
+        missing
+    }
+
     /// Returns a reference to the young generation.
     pub fn young_gen(&self) -> &YoungGeneration {
         &self.young_gen
@@ -483,6 +685,42 @@ impl Heap {
     }
 }
 
+/// Roots registered through [`Heap::add_value_root`], keyed by handle id.
+#[derive(Default)]
+struct HandleRoots {
+    next_id: u64,
+    entries: HashMap<u64, *mut GcObject>,
+}
+
+/// Keeps a value registered with [`Heap::add_value_root`] alive.
+///
+/// Dropping the handle unregisters the root, after which the value may be
+/// collected. The handle follows the object when the collector moves it.
+pub struct RootHandle {
+    id: u64,
+    roots: Weak<RefCell<HandleRoots>>,
+}
+
+impl RootHandle {
+    /// Returns the rooted value at its current location.
+    ///
+    /// Returns `Value::Undefined` for handles that root nothing or whose
+    /// heap has been dropped.
+    pub fn value(&self) -> Value {
+        self.roots
+            .upgrade()
+            .and_then(|roots| roots.borrow().entries.get(&self.id).copied())
+            .map_or(Value::Undefined, |ptr| Value::HeapObject(ptr as usize))
+    }
+}
+
+impl Drop for RootHandle {
+    fn drop(&mut self) {
+        if let Some(roots) = self.roots.upgrade() {
+            roots.borrow_mut().entries.remove(&self.id);
+        }
+    }
+}
 
 impl Default for Heap {
     fn default() -> Self {
@@ -970,6 +1208,42 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_heap_value_root_survives_until_handle_dropped() {
+        let mut heap = Heap::with_config(1024, 3);
+
+        let ptr = heap.allocate(32) as *mut GcObject;
+        unsafe {
+            *(*ptr).data_ptr() = 42;
+        }
+        let handle = heap.add_value_root(&Value::HeapObject(ptr as usize));
+
+        heap.full_gc();
+
+        // The object was only reachable through the handle, which follows it
+        let Value::HeapObject(addr) = handle.value() else {
+            panic!("rooted value should still be a heap object");
+        };
+        let moved = addr as *mut GcObject;
+        assert!(heap.is_in_young_gen(moved as *const u8));
+        assert_eq!(unsafe { *(*moved).data_ptr() }, 42);
+        assert!(heap.young_generation_size() > 0);
+
+        drop(handle);
+        assert_eq!(heap.root_count(), 0);
+        heap.full_gc();
+        assert_eq!(heap.young_generation_size(), 0);
+    }
+
+    #[test]
+    fn test_heap_value_root_ignores_non_heap_values() {
+        let mut heap = Heap::with_config(1024, 3);
+
+        let handle = heap.add_value_root(&Value::Smi(7));
+        assert_eq!(heap.root_count(), 0);
+        assert_eq!(handle.value(), Value::Undefined);
+    }
+
     #[test]
     fn test_heap_roots_updated_after_gc() {
         let mut heap = Heap::with_config(1024, 3);
@@ -1048,6 +1322,145 @@ mod tests {
         assert_eq!(heap.young_gen().space_size(), 1024);
     }
 
+    /// Fills the old-generation space with rooted objects of `size` bytes
+    fn fill_old_space(heap: &mut Heap, size: usize) -> Vec<*mut GcObject> {
+        let mut objects = Vec::new();
+        loop {
+            let ptr = heap.allocate_old(size);
+            if ptr.is_null() {
+                return objects;
+            }
+            heap.add_root(ptr as *mut GcObject);
+            objects.push(ptr as *mut GcObject);
+        }
+    }
+
+    #[test]
+    fn test_heap_allocate_old() {
+        let mut heap = Heap::with_config(1024, 3);
+
+        let ptr = heap.allocate_old(40) as *mut GcObject;
+        assert!(!ptr.is_null());
+        assert!(heap.is_in_old_gen(ptr));
+        assert!(!heap.is_in_young_gen(ptr as *const u8));
+        assert_eq!(heap.young_generation_size(), 0);
+    }
+
+    #[test]
+    fn test_heap_compact_defragments_old_space() {
+        let mut heap = Heap::with_config(1024, 3);
+        let header = std::mem::size_of::<GcObjectHeader>();
+        let objects = fill_old_space(&mut heap, 32);
+        assert!(objects.len() >= 4);
+
+        // Tag each survivor's data so we can check it after moving
+        for (i, &obj) in objects.iter().enumerate() {
+            unsafe {
+                *(*obj).data_ptr() = i as u8;
+            }
+        }
+
+        // Free every other object: half the space is free, but in small gaps
+        for &obj in objects.iter().step_by(2) {
+            heap.remove_root(obj);
+        }
+        heap.full_gc();
+
+        let large = 3 * (32 + header);
+        assert!(heap.allocate_old(large).is_null());
+
+        let reclaimed = heap.compact();
+        assert_eq!(reclaimed, (objects.len() / 2) * (32 + header));
+
+        // Roots now point at the moved objects, with their data intact
+        let mut tags: Vec<u8> = heap
+            .roots
+            .iter()
+            .map(|&root| unsafe {
+                assert!(heap.is_in_old_gen(root));
+                assert_eq!((*root).total_size(), 32 + header);
+                *(*root).data_ptr()
+            })
+            .collect();
+        tags.sort_unstable();
+        let expected: Vec<u8> = (1..objects.len()).step_by(2).map(|i| i as u8).collect();
+        assert_eq!(tags, expected);
+
+        assert!(!heap.allocate_old(large).is_null());
+    }
+
+    #[test]
+    fn test_heap_compact_updates_remembered_set() {
+        let mut heap = Heap::with_config(1024, 3);
+        let objects = fill_old_space(&mut heap, 32);
+
+        heap.remove_root(objects[0]);
+        heap.full_gc();
+
+        let survivor = objects[1];
+        heap.remembered_set_mut().add(survivor);
+        heap.compact();
+
+        assert!(!heap.remembered_set().contains(survivor));
+        assert!(heap.remembered_set().contains(objects[0]));
+    }
+
+    #[test]
+    fn test_heap_compact_skips_pinned_objects() {
+        let mut heap = Heap::with_config(1024, 3);
+        let objects = fill_old_space(&mut heap, 32);
+
+        heap.remove_root(objects[0]);
+        heap.remove_root(objects[2]);
+        heap.full_gc();
+        heap.pin(objects[1]);
+
+        let object_size = unsafe { (*objects[1]).total_size() };
+        assert_eq!(heap.compact(), object_size);
+
+        // The pinned object stays put, so only the gap above it is closed
+        assert!(heap.roots.contains(&objects[1]));
+        assert!(!heap.roots.contains(&objects[0]));
+        assert!(heap.old_gen().is_pinned(objects[1]));
+    }
+
+    /// Allocates an old object with one reference slot and returns it with the slot
+    fn old_object_with_slot(heap: &mut Heap) -> (*mut GcObject, *mut *mut GcObject) {
+        let holder = heap.allocate_old(std::mem::size_of::<usize>()) as *mut GcObject;
+        assert!(!holder.is_null());
+        let slot = unsafe {
+            (holder as *mut u8).add(std::mem::size_of::<GcObjectHeader>()) as *mut *mut GcObject
+        };
+        unsafe { slot.write_unaligned(ptr::null_mut()) };
+        (holder, slot)
+    }
+
+    #[test]
+    fn test_barrier_verification_accepts_recorded_writes() {
+        let mut heap = Heap::with_config(1024, 3).with_barrier_verification();
+        let (holder, slot) = old_object_with_slot(&mut heap);
+        let young = heap.allocate(16) as *mut GcObject;
+
+        unsafe { heap.write_barrier(holder, slot, young) };
+
+        assert!(heap.find_missing_barriers().is_empty());
+        heap.collect_garbage();
+    }
+
+    #[test]
+    #[should_panic(expected = "write barrier missed")]
+    fn test_barrier_verification_catches_missing_barrier() {
+        let mut heap = Heap::with_config(1024, 3).with_barrier_verification();
+        let (holder, slot) = old_object_with_slot(&mut heap);
+        let young = heap.allocate(16) as *mut GcObject;
+
+        // Store the young pointer without going through the write barrier
+        unsafe { slot.write_unaligned(young) };
+
+        assert_eq!(heap.find_missing_barriers(), vec![(holder, young)]);
+        heap.collect_garbage();
+    }
+
     #[test]
     fn test_heap_old_gen_accessor() {
         let heap = Heap::with_config(1024, 3);
@@ -17,7 +17,7 @@ pub mod write_barrier;
 
 // Re-export main types
 pub use gc::*;
-pub use heap::{GcStats, Heap};
+pub use heap::{GcStats, Heap, RootHandle};
 pub use hidden_class::HiddenClass;
 pub use object::JSObject;
 pub use write_barrier::{write_barrier, write_barrier_gc, CardTable, Object, RememberedSet};
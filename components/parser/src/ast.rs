@@ -1,6 +1,6 @@
 //! Abstract Syntax Tree node definitions
 
-use core_types::SourcePosition;
+use core_types::SourceSpan;
 
 /// AST node representing JavaScript program elements
 #[derive(Debug, Clone, PartialEq)]
@@ -22,8 +22,8 @@ pub enum Statement {
         kind: VariableKind,
         /// List of declarators
         declarations: Vec<VariableDeclarator>,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// Function declaration
@@ -38,8 +38,8 @@ pub enum Statement {
         is_async: bool,
         /// Is generator function
         is_generator: bool,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// Class declaration
@@ -50,24 +50,24 @@ pub enum Statement {
         super_class: Option<Box<Expression>>,
         /// Class body
         body: Vec<ClassElement>,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// Expression statement
     ExpressionStatement {
         /// The expression
         expression: Expression,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// Return statement
     ReturnStatement {
         /// Return value
         argument: Option<Expression>,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// If statement
@@ -78,8 +78,8 @@ pub enum Statement {
         consequent: Box<Statement>,
         /// Alternate block
         alternate: Option<Box<Statement>>,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// While loop
@@ -88,8 +88,8 @@ pub enum Statement {
         test: Expression,
         /// Loop body
         body: Box<Statement>,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// For loop
@@ -102,8 +102,8 @@ pub enum Statement {
         update: Option<Expression>,
         /// Loop body
         body: Box<Statement>,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// For...in loop
@@ -114,8 +114,8 @@ pub enum Statement {
         right: Expression,
         /// Loop body
         body: Box<Statement>,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// For...of loop
@@ -128,46 +128,46 @@ pub enum Statement {
         body: Box<Statement>,
         /// Is await for-of
         r#await: bool,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// Block statement
     BlockStatement {
         /// Block body
         body: Vec<Statement>,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// Empty statement
     EmptyStatement {
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// Break statement
     BreakStatement {
         /// Optional label
         label: Option<String>,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// Continue statement
     ContinueStatement {
         /// Optional label
         label: Option<String>,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// Throw statement
     ThrowStatement {
         /// Exception to throw
         argument: Expression,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// Try statement
@@ -178,8 +178,8 @@ pub enum Statement {
         handler: Option<CatchClause>,
         /// Finally block
         finalizer: Option<Vec<Statement>>,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// Do-while loop
@@ -188,8 +188,8 @@ pub enum Statement {
         body: Box<Statement>,
         /// Loop condition
         test: Expression,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// Switch statement
@@ -198,8 +198,8 @@ pub enum Statement {
         discriminant: Expression,
         /// Case clauses
         cases: Vec<SwitchCase>,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// With statement
@@ -208,14 +208,14 @@ pub enum Statement {
         object: Expression,
         /// Body statement
         body: Box<Statement>,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// Debugger statement
     DebuggerStatement {
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// Labeled statement
@@ -224,16 +224,16 @@ pub enum Statement {
         label: String,
         /// Body statement
         body: Box<Statement>,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// Export default declaration
     ExportDefaultDeclaration {
         /// The declaration being exported (class, function, or expression)
         declaration: Box<ExportDefaultDecl>,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// Export named declaration
@@ -244,8 +244,8 @@ pub enum Statement {
         specifiers: Vec<ExportSpecifier>,
         /// Source module (for re-exports)
         source: Option<String>,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// Export all declaration (export * from 'module')
@@ -254,8 +254,8 @@ pub enum Statement {
         source: String,
         /// Exported name (for `export * as name from 'module'`)
         exported: Option<String>,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// Import declaration
@@ -264,8 +264,8 @@ pub enum Statement {
         specifiers: Vec<ImportSpecifier>,
         /// Source module
         source: String,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 }
 
@@ -329,16 +329,16 @@ pub enum Expression {
     Identifier {
         /// Variable name
         name: String,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// Literal value
     Literal {
         /// Literal value
         value: Literal,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// Parenthesized expression - tracks that an expression was wrapped in parentheses
@@ -347,8 +347,8 @@ pub enum Expression {
     ParenthesizedExpression {
         /// The inner expression
         expression: Box<Expression>,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// Binary operation
@@ -359,8 +359,8 @@ pub enum Expression {
         operator: BinaryOperator,
         /// Right operand
         right: Box<Expression>,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// Unary operation
@@ -371,8 +371,8 @@ pub enum Expression {
         argument: Box<Expression>,
         /// Is prefix operator
         prefix: bool,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// Update expression (++, --)
@@ -383,8 +383,8 @@ pub enum Expression {
         argument: Box<Expression>,
         /// Is prefix operator
         prefix: bool,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// Logical expression (&&, ||, ??)
@@ -395,8 +395,8 @@ pub enum Expression {
         operator: LogicalOperator,
         /// Right operand
         right: Box<Expression>,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// Assignment expression
@@ -407,8 +407,8 @@ pub enum Expression {
         operator: AssignmentOperator,
         /// Right-hand side
         right: Box<Expression>,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// Conditional expression (ternary)
@@ -419,8 +419,8 @@ pub enum Expression {
         consequent: Box<Expression>,
         /// Alternate
         alternate: Box<Expression>,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// Function call
@@ -431,8 +431,8 @@ pub enum Expression {
         arguments: Vec<Expression>,
         /// Optional call (e.g., foo?.())
         optional: bool,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// Member access (obj.prop or obj[prop])
@@ -445,8 +445,8 @@ pub enum Expression {
         computed: bool,
         /// Optional access (e.g., obj?.prop)
         optional: bool,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// New expression
@@ -455,8 +455,8 @@ pub enum Expression {
         callee: Box<Expression>,
         /// Arguments
         arguments: Vec<Expression>,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// Meta property (new.target, import.meta)
@@ -465,24 +465,24 @@ pub enum Expression {
         meta: String,
         /// Property (e.g., "target" or "meta")
         property: String,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// Array literal
     ArrayExpression {
         /// Elements
         elements: Vec<Option<ArrayElement>>,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// Object literal
     ObjectExpression {
         /// Properties
         properties: Vec<ObjectProperty>,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// Arrow function
@@ -493,8 +493,8 @@ pub enum Expression {
         body: ArrowFunctionBody,
         /// Is async
         is_async: bool,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// Function expression
@@ -509,36 +509,36 @@ pub enum Expression {
         is_async: bool,
         /// Is generator
         is_generator: bool,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// This expression
     ThisExpression {
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// Super expression
     SuperExpression {
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// Private identifier (used in private field presence check: #field in obj)
     PrivateIdentifier {
         /// Private field name (without the # prefix)
         name: String,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// Await expression
     AwaitExpression {
         /// Argument
         argument: Box<Expression>,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// Yield expression
@@ -547,8 +547,8 @@ pub enum Expression {
         argument: Option<Box<Expression>>,
         /// Is delegated (yield*)
         delegate: bool,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// Class expression
@@ -559,8 +559,8 @@ pub enum Expression {
         super_class: Option<Box<Expression>>,
         /// Class body
         body: Vec<ClassElement>,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// Template literal
@@ -569,32 +569,32 @@ pub enum Expression {
         quasis: Vec<TemplateElement>,
         /// Expressions (interpolated parts)
         expressions: Vec<Expression>,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// Spread element
     SpreadElement {
         /// Argument to spread
         argument: Box<Expression>,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// Sequence expression (comma-separated)
     SequenceExpression {
         /// Expressions
         expressions: Vec<Expression>,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// Dynamic import expression: import(specifier)
     ImportExpression {
         /// Module specifier
         source: Box<Expression>,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 
     /// Tagged template expression: tag`template`
@@ -603,8 +603,8 @@ pub enum Expression {
         tag: Box<Expression>,
         /// Template quasi (containing quasis and expressions)
         quasi: Box<Expression>,
-        /// Source location
-        position: Option<SourcePosition>,
+        /// Source span covered by the node
+        position: Option<SourceSpan>,
     },
 }
 
@@ -939,6 +939,17 @@ pub enum ObjectProperty {
         /// Is computed
         computed: bool,
     },
+    /// Getter or setter (`get key() {}`, `set key(v) {}`)
+    Accessor {
+        /// Key
+        key: PropertyKey,
+        /// `MethodKind::Get` or `MethodKind::Set`
+        kind: MethodKind,
+        /// The accessor function
+        value: Expression,
+        /// Is computed
+        computed: bool,
+    },
     /// Spread property
     SpreadElement(Expression),
 }
@@ -2,11 +2,15 @@
 
 use crate::ast::*;
 use bytecode_system::{
-    BytecodeChunk, Opcode, RegisterId, UpvalueDescriptor, Value as BytecodeValue,
+    BytecodeChunk, Opcode, RegisterId, SourcePosition, UpvalueDescriptor, Value as BytecodeValue,
 };
-use core_types::{ErrorKind, JsError};
+use core_types::{ErrorKind, JsError, SourceSpan};
 use num_bigint::BigInt;
 use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Hidden binding holding a derived class's superclass; not a valid identifier
+const SUPER_BINDING: &str = "%super";
 
 /// Result of resolving a variable name
 #[derive(Debug, Clone)]
@@ -19,13 +23,47 @@ enum VarResolution {
     Global,
 }
 
+/// Which jumps a [`JumpTarget`] accepts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum JumpTargetKind {
+    /// A loop: the target of `continue` and of unlabeled `break`
+    Loop,
+    /// A `switch`: the target of unlabeled `break`
+    Switch,
+    /// Any other labeled statement: only a labeled `break` leaves it
+    Block,
+}
+
+/// A statement that `break` or `continue` can jump out of
+#[derive(Debug)]
+struct JumpTarget {
+    kind: JumpTargetKind,
+    /// Labels naming the statement
+    labels: Vec<String>,
+    /// Operand stack values held by enclosing statements at the target
+    stack_depth: usize,
+    /// `break` jumps, patched to the end of the statement
+    breaks: Vec<usize>,
+    /// `continue` jumps, patched to the start of the loop's next iteration
+    continues: Vec<usize>,
+}
+
 /// Bytecode generator that converts AST to bytecode
 pub struct BytecodeGenerator {
     chunk: BytecodeChunk,
     locals: HashMap<String, RegisterId>,
     next_register: u32,
-    loop_starts: Vec<usize>,
-    loop_exits: Vec<Vec<usize>>,
+    /// Statements `break`/`continue` can currently leave, innermost last
+    jump_targets: Vec<JumpTarget>,
+    /// Labels waiting for the loop or switch they name
+    pending_labels: Vec<String>,
+    /// Operand stack values held by enclosing statements (a switch's discriminant)
+    stack_depth: usize,
+    /// Optional chain being compiled: the register holding its start height
+    /// and its short-circuit jumps
+    optional_chain: Option<(RegisterId, Vec<usize>)>,
+    /// Set while compiling a chain link's object or callee, which continues the chain
+    continue_chain: bool,
     last_was_expression: bool,
 
     // For closure support
@@ -37,6 +75,25 @@ pub struct BytecodeGenerator {
     // For nested function registration
     /// Nested function bytecode chunks collected during compilation
     nested_functions: Vec<BytecodeChunk>,
+
+    // For proper tail calls
+    /// Whether the code being generated is strict mode code
+    strict: bool,
+    /// Whether the current function is async or a generator (never tail-calls)
+    suspendable: bool,
+    /// Whether the current function is a (non-async) generator
+    in_generator: bool,
+    /// Number of enclosing try blocks; calls inside them are not in tail position
+    try_depth: usize,
+
+    // For class private names
+    /// Private names visible here (`#x`) mapped to their class-unique runtime keys
+    private_names: HashMap<String, String>,
+    /// Classes compiled so far; only the outermost generator's count is used
+    class_count: usize,
+    /// Body of the derived class whose constructor this is; its fields are
+    /// initialized when `super(...)` returns
+    derived_class_body: Option<Rc<[ClassElement]>>,
 }
 
 impl BytecodeGenerator {
@@ -46,30 +103,721 @@ impl BytecodeGenerator {
             chunk: BytecodeChunk::new(),
             locals: HashMap::new(),
             next_register: 0,
-            loop_starts: Vec::new(),
-            loop_exits: Vec::new(),
+            jump_targets: Vec::new(),
+            pending_labels: Vec::new(),
+            stack_depth: 0,
+            optional_chain: None,
+            continue_chain: false,
             last_was_expression: false,
             enclosing: None,
             upvalues: Vec::new(),
             nested_functions: Vec::new(),
+            strict: false,
+            suspendable: false,
+            in_generator: false,
+            try_depth: 0,
+            private_names: HashMap::new(),
+            class_count: 0,
+            derived_class_body: None,
         }
     }
 
     /// Create a new bytecode generator with an enclosing scope
     fn with_enclosing(enclosing: Box<BytecodeGenerator>) -> Self {
+        // Strictness and private names are inherited by nested functions
+        let strict = enclosing.strict;
+        let private_names = enclosing.private_names.clone();
         Self {
             chunk: BytecodeChunk::new(),
             locals: HashMap::new(),
             next_register: 0,
-            loop_starts: Vec::new(),
-            loop_exits: Vec::new(),
+            jump_targets: Vec::new(),
+            pending_labels: Vec::new(),
+            stack_depth: 0,
+            optional_chain: None,
+            continue_chain: false,
             last_was_expression: false,
             enclosing: Some(enclosing),
             upvalues: Vec::new(),
             nested_functions: Vec::new(),
+            strict,
+            suspendable: false,
+            in_generator: false,
+            try_depth: 0,
+            private_names,
+            class_count: 0,
+            derived_class_body: None,
+        }
+    }
+
+    /// Apply a function body's directive prologue and kind before generating it
+    fn enter_function_body(&mut self, body: &[Statement], is_async: bool, is_generator: bool) {
+        self.strict |= has_use_strict_directive(body);
+        self.suspendable = is_async || is_generator;
+        self.in_generator = is_generator && !is_async;
+        if self.in_generator {
+            // Calling a generator function only creates the generator object
+            self.chunk.emit(Opcode::InitialYield);
+        }
+    }
+
+    /// Emit `return <expr>`
+    ///
+    /// A plain call in tail position of a strict, non-suspendable function is
+    /// emitted as a TailCall so the VM can reuse the current frame.
+    fn emit_return(&mut self, expr: &Expression) -> Result<(), JsError> {
+        if let Expression::CallExpression {
+            callee, arguments, ..
+        } = expr
+        {
+            let tail_position = self.strict
+                && !self.suspendable
+                && self.try_depth == 0
+                && self.enclosing.is_some();
+            // `super(...)` is not a plain call; it always goes through emit_super_call
+            let plain_callee = !matches!(
+                callee.as_ref(),
+                Expression::MemberExpression { .. } | Expression::SuperExpression { .. }
+            );
+            if tail_position
+                && plain_callee
+                && !Self::has_spread(arguments)
+                && !Self::is_optional_chain(expr)
+            {
+                self.visit_expression(callee)?;
+                for arg in arguments {
+                    self.visit_expression(arg)?;
+                }
+                self.chunk.emit(Opcode::TailCall(arguments.len() as u8));
+                return Ok(());
+            }
+        }
+
+        self.visit_expression(expr)?;
+        self.chunk.emit(Opcode::Return);
+        Ok(())
+    }
+
+    /// Emit a class's constructor function, leaving the closure on the stack
+    ///
+    /// Instance fields are initialized at the start of a base class's
+    /// constructor, before its body runs, and right after each `super(...)`
+    /// of a derived one, so that the superclass constructor cannot overwrite
+    /// them. Private names declared by the class are in scope for the
+    /// constructor and everything nested in it.
+    fn emit_class_constructor(
+        &mut self,
+        body: &[ClassElement],
+        derived: bool,
+    ) -> Result<(), JsError> {
+        let (params, ctor_body): (&[Pattern], &[Statement]) = body
+            .iter()
+            .find_map(|element| match element {
+                ClassElement::MethodDefinition {
+                    kind: MethodKind::Constructor,
+                    value: Expression::FunctionExpression { params, body, .. },
+                    ..
+                } => Some((params.as_slice(), body.as_slice())),
+                _ => None,
+            })
+            .unwrap_or((&[], &[]));
+
+        // Give this class's private names keys no other class shares
+        let class_id = self.next_class_id();
+        let outer_private_names = self.private_names.clone();
+        for element in body {
+            if let ClassElement::PropertyDefinition {
+                key: PropertyKey::Identifier(field),
+                is_private: true,
+                ..
+            }
+            | ClassElement::MethodDefinition {
+                key: PropertyKey::Identifier(field),
+                is_private: true,
+                ..
+            } = element
+            {
+                if let ClassElement::MethodDefinition { kind, is_static, .. } = element {
+                    if *kind != MethodKind::Method || *is_static {
+                        return Err(JsError {
+                            kind: ErrorKind::SyntaxError,
+                            message: format!(
+                                "Private accessors and static private methods are not \
+                                 supported: '#{}'",
+                                field
+                            ),
+                            stack: vec![],
+                            source_position: None,
+                        });
+                    }
+                }
+                let source_name = format!("#{}", field);
+                let key = format!("{}@{}", source_name, class_id);
+                self.private_names.insert(source_name, key);
+            }
+        }
+
+        // Create function bytecode with enclosing scope for closure support
+        let current_gen = std::mem::replace(self, BytecodeGenerator::new());
+        let mut func_gen = BytecodeGenerator::with_enclosing(Box::new(current_gen));
+        // Class code is always strict
+        func_gen.strict = true;
+
+        // Set up parameters as locals
+        for param in params {
+            if let Pattern::Identifier(param_name) = param {
+                let reg = func_gen.allocate_register();
+                func_gen.locals.insert(param_name.clone(), reg);
+            }
+        }
+
+        if derived {
+            func_gen.derived_class_body = Some(body.into());
+        } else {
+            func_gen.emit_instance_initializers(body)?;
+        }
+
+        // Generate constructor body
+        for stmt in ctor_body {
+            func_gen.visit_statement(stmt)?;
+        }
+
+        // Ensure return (constructor returns 'this' implicitly, but we handle that in VM)
+        if !matches!(
+            func_gen.chunk.instructions.last().map(|i| &i.opcode),
+            Some(Opcode::Return)
+        ) {
+            func_gen.chunk.emit(Opcode::LoadUndefined);
+            func_gen.chunk.emit(Opcode::Return);
+        }
+
+        func_gen.chunk.register_count = func_gen.next_register;
+        func_gen.record_local_names();
+
+        // Get the upvalues captured by this function
+        let upvalues = func_gen.get_upvalues();
+
+        // Get the compiled function bytecode
+        let mut func_bytecode = func_gen.chunk.clone();
+
+        // Collect any nested functions from the inner function
+        let inner_nested = func_gen.take_nested_functions();
+
+        // Restore the outer generator from the enclosing scope
+        *self = *func_gen.enclosing.take().unwrap();
+        self.private_names = outer_private_names;
+
+        // Add the compiled function to our nested functions list
+        let func_idx = self.nested_functions.len();
+
+        // Adjust indices in the function's bytecode for nested functions
+        let inner_base_idx = func_idx + 1;
+        Self::adjust_closure_indices(&mut func_bytecode, inner_base_idx);
+
+        self.nested_functions.push(func_bytecode);
+
+        // Also include any nested functions from the inner function
+        let mut adjusted_inner_nested = inner_nested;
+        for nested_chunk in &mut adjusted_inner_nested {
+            Self::adjust_closure_indices(nested_chunk, inner_base_idx);
+        }
+        self.nested_functions.extend(adjusted_inner_nested);
+
+        // Create closure with upvalue descriptors
+        self.chunk.emit(Opcode::CreateClosure(func_idx, upvalues));
+        Ok(())
+    }
+
+    /// Initialize a new instance from the class's private methods and fields
+    ///
+    /// Private methods are installed on every instance, ahead of the fields
+    /// whose initializers may call them.
+    fn emit_instance_initializers(&mut self, body: &[ClassElement]) -> Result<(), JsError> {
+        for element in body {
+            if let ClassElement::MethodDefinition {
+                key: PropertyKey::Identifier(name),
+                kind: MethodKind::Method,
+                value,
+                is_static: false,
+                is_private: true,
+                ..
+            } = element
+            {
+                self.chunk.emit(Opcode::LoadGlobal("this".to_string()));
+                self.visit_expression(value)?;
+                let key = self.private_key(&format!("#{}", name))?;
+                self.chunk.emit(Opcode::DefinePrivate(key));
+                self.chunk.emit(Opcode::Pop);
+            }
+        }
+
+        for element in body {
+            let ClassElement::PropertyDefinition {
+                key,
+                value,
+                is_static: false,
+                is_private,
+                ..
+            } = element
+            else {
+                continue;
+            };
+
+            self.chunk.emit(Opcode::LoadGlobal("this".to_string()));
+            match key {
+                PropertyKey::Identifier(field) if *is_private => {
+                    self.emit_field_value(value)?;
+                    let key = self.private_key(&format!("#{}", field))?;
+                    self.chunk.emit(Opcode::DefinePrivate(key));
+                }
+                PropertyKey::Identifier(field) | PropertyKey::String(field) => {
+                    self.emit_field_value(value)?;
+                    self.chunk.emit(Opcode::StoreProperty(field.clone()));
+                }
+                PropertyKey::Number(n) => {
+                    let idx = self.chunk.add_constant(BytecodeValue::Number(*n));
+                    self.chunk.emit(Opcode::LoadConstant(idx));
+                    self.emit_field_value(value)?;
+                    self.chunk.emit(Opcode::SetIndex);
+                }
+                PropertyKey::Computed(key_expr) => {
+                    self.visit_expression(key_expr)?;
+                    self.emit_field_value(value)?;
+                    self.chunk.emit(Opcode::SetIndex);
+                }
+            }
+            // Discard the stored value
+            self.chunk.emit(Opcode::Pop);
+        }
+        Ok(())
+    }
+
+    fn emit_field_value(&mut self, value: &Option<Expression>) -> Result<(), JsError> {
+        match value {
+            Some(expr) => self.visit_expression(expr),
+            None => {
+                self.chunk.emit(Opcode::LoadUndefined);
+                Ok(())
+            }
+        }
+    }
+
+    /// Allocate a class ID, unique across the whole compilation unit
+    fn next_class_id(&mut self) -> usize {
+        match self.enclosing.as_mut() {
+            Some(enclosing) => enclosing.next_class_id(),
+            None => {
+                let id = self.class_count;
+                self.class_count += 1;
+                id
+            }
+        }
+    }
+
+    /// Runtime key for private name `#name` from the innermost class declaring it
+    fn private_key(&self, name: &str) -> Result<String, JsError> {
+        self.private_names.get(name).cloned().ok_or_else(|| JsError {
+            kind: ErrorKind::SyntaxError,
+            message: format!("Private field '{}' must be declared in an enclosing class", name),
+            stack: vec![],
+            source_position: None,
+        })
+    }
+
+    /// Load property `name` of the object on the stack; `#name` reads a private field
+    fn emit_load_named(&mut self, name: &str) -> Result<(), JsError> {
+        if name.starts_with('#') {
+            let key = self.private_key(name)?;
+            self.chunk.emit(Opcode::LoadPrivate(key));
+        } else {
+            self.chunk.emit(Opcode::LoadProperty(name.to_string()));
+        }
+        Ok(())
+    }
+
+    /// Store to property `name` (stack: [obj, value]); `#name` writes a private field
+    fn emit_store_named(&mut self, name: &str) -> Result<(), JsError> {
+        if name.starts_with('#') {
+            let key = self.private_key(name)?;
+            self.chunk.emit(Opcode::StorePrivate(key));
+        } else {
+            self.chunk.emit(Opcode::StoreProperty(name.to_string()));
+        }
+        Ok(())
+    }
+
+    /// Arithmetic opcode applied by a compound assignment operator
+    ///
+    /// Returns `None` for `=` and for operators without a bytecode
+    /// equivalent, which are stored as plain assignments.
+    fn compound_assignment_opcode(operator: &AssignmentOperator) -> Option<Opcode> {
+        Some(match operator {
+            AssignmentOperator::AddAssign => Opcode::Add,
+            AssignmentOperator::SubAssign => Opcode::Sub,
+            AssignmentOperator::MulAssign => Opcode::Mul,
+            AssignmentOperator::DivAssign => Opcode::Div,
+            AssignmentOperator::ModAssign => Opcode::Mod,
+            AssignmentOperator::ExpAssign => Opcode::Exp,
+            AssignmentOperator::BitAndAssign => Opcode::BitAnd,
+            AssignmentOperator::BitOrAssign => Opcode::BitOr,
+            AssignmentOperator::BitXorAssign => Opcode::BitXor,
+            AssignmentOperator::LeftShiftAssign => Opcode::ShiftLeft,
+            AssignmentOperator::RightShiftAssign => Opcode::ShiftRight,
+            AssignmentOperator::UnsignedRightShiftAssign => Opcode::ShiftRightUnsigned,
+            _ => return None,
+        })
+    }
+
+    /// Whether `expr` is a member/call chain with an optional link (`?.`)
+    ///
+    /// Parentheses end a chain, so `(a?.b).c` is not one.
+    fn is_optional_chain(expr: &Expression) -> bool {
+        match expr {
+            Expression::MemberExpression {
+                object, optional, ..
+            } => *optional || Self::is_optional_chain(object),
+            Expression::CallExpression {
+                callee, optional, ..
+            } => *optional || Self::is_optional_chain(callee),
+            _ => false,
+        }
+    }
+
+    /// Compile a member/call chain containing `?.`
+    ///
+    /// The stack height at the start of the chain is saved in a fresh
+    /// register. A nullish optional link drops the values the chain has
+    /// pushed so far and makes the whole chain evaluate to undefined.
+    fn emit_optional_chain(&mut self, expr: &Expression) -> Result<(), JsError> {
+        let reg = self.allocate_register();
+        self.chunk.emit(Opcode::OptionalChainStart(reg));
+        let outer = self.optional_chain.replace((reg, Vec::new()));
+        self.continue_chain = true;
+        let result = self.visit_expression(expr);
+        let chain = std::mem::replace(&mut self.optional_chain, outer);
+        result?;
+        let end = self.chunk.instruction_count();
+        for jump in chain.map(|(_, jumps)| jumps).unwrap_or_default() {
+            self.patch_jump(jump, end);
+        }
+        Ok(())
+    }
+
+    /// Logical operator applied by `&&=`, `||=` and `??=`
+    fn logical_assignment_operator(operator: &AssignmentOperator) -> Option<LogicalOperator> {
+        match operator {
+            AssignmentOperator::LogicalAndAssign => Some(LogicalOperator::And),
+            AssignmentOperator::LogicalOrAssign => Some(LogicalOperator::Or),
+            AssignmentOperator::NullishCoalesceAssign => Some(LogicalOperator::NullishCoalesce),
+            _ => None,
+        }
+    }
+
+    /// Emit the test of a short-circuiting operator (stack: [left])
+    ///
+    /// Returns the jump to patch to the end of the expression; it is taken
+    /// with `left` still on the stack when `operator` short-circuits, and
+    /// falls through with `left` on the stack otherwise.
+    fn emit_short_circuit(&mut self, operator: &LogicalOperator) -> usize {
+        self.chunk.emit(Opcode::Dup);
+        match operator {
+            LogicalOperator::And => {
+                let skip = self.chunk.instruction_count();
+                self.chunk.emit(Opcode::JumpIfFalse(0));
+                skip
+            }
+            LogicalOperator::Or => {
+                let skip = self.chunk.instruction_count();
+                self.chunk.emit(Opcode::JumpIfTrue(0));
+                skip
+            }
+            LogicalOperator::NullishCoalesce => {
+                // `left == null` holds for exactly null and undefined
+                self.chunk.emit(Opcode::LoadNull);
+                self.chunk.emit(Opcode::Equal);
+                let skip = self.chunk.instruction_count();
+                self.chunk.emit(Opcode::JumpIfFalse(0));
+                skip
+            }
+        }
+    }
+
+    /// Emit `left &&= right`, `left ||= right` or `left ??= right`
+    ///
+    /// The target is read once; `right` is only evaluated, and the target
+    /// only written, when `operator` would not short-circuit. The object and
+    /// key of a member target are evaluated once and kept in scratch registers.
+    fn emit_logical_assignment(
+        &mut self,
+        left: &AssignmentTarget,
+        operator: &LogicalOperator,
+        right: &Expression,
+    ) -> Result<(), JsError> {
+        match left {
+            AssignmentTarget::Identifier(name) => {
+                let resolution = self.resolve_variable(name);
+                match &resolution {
+                    VarResolution::Local(reg) => self.chunk.emit(Opcode::LoadLocal(*reg)),
+                    VarResolution::Upvalue(idx) => self.chunk.emit(Opcode::LoadUpvalue(*idx)),
+                    VarResolution::Global => self.chunk.emit(Opcode::LoadGlobal(name.clone())),
+                };
+                let skip = self.emit_short_circuit(operator);
+                self.chunk.emit(Opcode::Pop);
+                self.visit_expression(right)?;
+                self.chunk.emit(Opcode::Dup);
+                match resolution {
+                    VarResolution::Local(reg) => self.chunk.emit(Opcode::StoreLocal(reg)),
+                    VarResolution::Upvalue(idx) => self.chunk.emit(Opcode::StoreUpvalue(idx)),
+                    VarResolution::Global => self.chunk.emit(Opcode::StoreGlobal(name.clone())),
+                };
+                let end = self.chunk.instruction_count();
+                self.patch_jump(skip, end);
+            }
+            AssignmentTarget::Member(member_expr) => {
+                let Expression::MemberExpression {
+                    object,
+                    property,
+                    computed,
+                    ..
+                } = member_expr.as_ref()
+                else {
+                    return Ok(());
+                };
+                let object_reg = self.allocate_register();
+                self.visit_expression(object)?;
+                self.chunk.emit(Opcode::StoreLocal(object_reg));
+
+                let key_reg = if *computed {
+                    let reg = self.allocate_register();
+                    self.visit_expression(property)?;
+                    self.chunk.emit(Opcode::StoreLocal(reg));
+                    Some(reg)
+                } else {
+                    None
+                };
+                let name = match property.as_ref() {
+                    Expression::Identifier { name, .. } => Some(name.as_str()),
+                    _ => None,
+                };
+
+                // Read the current value
+                self.chunk.emit(Opcode::LoadLocal(object_reg));
+                if let Some(reg) = key_reg {
+                    self.chunk.emit(Opcode::LoadLocal(reg));
+                    self.chunk.emit(Opcode::GetIndex);
+                } else if let Some(name) = name {
+                    self.emit_load_named(name)?;
+                }
+                let skip = self.emit_short_circuit(operator);
+                self.chunk.emit(Opcode::Pop);
+
+                // Store `right`; SetIndex and StoreProperty push the value
+                self.chunk.emit(Opcode::LoadLocal(object_reg));
+                if let Some(reg) = key_reg {
+                    self.chunk.emit(Opcode::LoadLocal(reg));
+                    self.visit_expression(right)?;
+                    self.chunk.emit(Opcode::SetIndex);
+                } else {
+                    self.visit_expression(right)?;
+                    if let Some(name) = name {
+                        self.emit_store_named(name)?;
+                    }
+                }
+                let end = self.chunk.instruction_count();
+                self.patch_jump(skip, end);
+            }
+            AssignmentTarget::Pattern(_) => {
+                // Logical assignment to a pattern is an early error
+                self.visit_expression(right)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Emit the nullish check of an optional link in the chain being compiled
+    fn emit_optional_short_circuit(&mut self) {
+        if let Some((reg, jumps)) = &mut self.optional_chain {
+            jumps.push(self.chunk.instruction_count());
+            self.chunk.emit(Opcode::OptionalChainShortCircuit(*reg, 0)); // Placeholder
         }
     }
 
+    /// `obj.prop op= value` and `obj[key] op= value`
+    ///
+    /// The object and key are evaluated once into scratch registers, then
+    /// read back for both the load of the current value and the store.
+    fn emit_compound_member_assignment(
+        &mut self,
+        object: &Expression,
+        property: &Expression,
+        computed: bool,
+        op: Opcode,
+        right: &Expression,
+    ) -> Result<(), JsError> {
+        let object_reg = self.allocate_register();
+        self.visit_expression(object)?;
+        self.chunk.emit(Opcode::StoreLocal(object_reg));
+
+        if computed {
+            let key_reg = self.allocate_register();
+            self.visit_expression(property)?;
+            self.chunk.emit(Opcode::StoreLocal(key_reg));
+
+            // SetIndex expects [obj, key, value]
+            self.chunk.emit(Opcode::LoadLocal(object_reg));
+            self.chunk.emit(Opcode::LoadLocal(key_reg));
+            self.chunk.emit(Opcode::LoadLocal(object_reg));
+            self.chunk.emit(Opcode::LoadLocal(key_reg));
+            self.chunk.emit(Opcode::GetIndex);
+            self.visit_expression(right)?;
+            self.chunk.emit(op);
+            self.chunk.emit(Opcode::SetIndex);
+        } else if let Expression::Identifier { name, .. } = property {
+            // StoreProperty expects [obj, value]
+            self.chunk.emit(Opcode::LoadLocal(object_reg));
+            self.chunk.emit(Opcode::LoadLocal(object_reg));
+            self.emit_load_named(name)?;
+            self.visit_expression(right)?;
+            self.chunk.emit(op);
+            self.emit_store_named(name)?;
+        }
+        Ok(())
+    }
+
+    /// Whether any call argument is a spread element
+    fn has_spread(arguments: &[Expression]) -> bool {
+        arguments
+            .iter()
+            .any(|arg| matches!(arg, Expression::SpreadElement { .. }))
+    }
+
+    /// Emit a class's constructor closure, leaving it on the stack
+    ///
+    /// The superclass lives in a hidden local the constructor, and any arrow
+    /// inside it, captures for `super(...)` calls.
+    fn emit_class(
+        &mut self,
+        super_class: Option<&Expression>,
+        body: &[ClassElement],
+    ) -> Result<(), JsError> {
+        let outer_super = self.locals.get(SUPER_BINDING).copied();
+        if let Some(super_expr) = super_class {
+            self.visit_expression(super_expr)?;
+            let reg = self.allocate_register();
+            self.chunk.emit(Opcode::StoreLocal(reg));
+            self.locals.insert(SUPER_BINDING.to_string(), reg);
+        }
+
+        self.emit_class_constructor(body, super_class.is_some())?;
+
+        match outer_super {
+            Some(reg) => self.locals.insert(SUPER_BINDING.to_string(), reg),
+            None => self.locals.remove(SUPER_BINDING),
+        };
+        Ok(())
+    }
+
+    /// Emit `super(...arguments)` in a derived constructor
+    ///
+    /// The superclass constructor runs with the current `this` as receiver,
+    /// so it initializes the instance being constructed; the call evaluates
+    /// to `this`. Spread arguments are passed positionally.
+    fn emit_super_call(&mut self, arguments: &[Expression]) -> Result<(), JsError> {
+        self.chunk.emit(Opcode::LoadGlobal("this".to_string()));
+        match self.resolve_variable(SUPER_BINDING) {
+            VarResolution::Local(reg) => self.chunk.emit(Opcode::LoadLocal(reg)),
+            VarResolution::Upvalue(idx) => self.chunk.emit(Opcode::LoadUpvalue(idx)),
+            VarResolution::Global => {
+                return Err(JsError {
+                    kind: ErrorKind::SyntaxError,
+                    message: "'super' keyword unexpected here".to_string(),
+                    stack: vec![],
+                    source_position: None,
+                });
+            }
+        }
+
+        if Self::has_spread(arguments) {
+            self.emit_argument_array(arguments)?;
+            self.chunk.emit(Opcode::CallMethodSpread);
+        } else {
+            for arg in arguments {
+                self.visit_expression(arg)?;
+            }
+            self.chunk.emit(Opcode::CallMethod(arguments.len() as u8));
+        }
+
+        self.chunk.emit(Opcode::Pop);
+        if let Some(body) = self.enclosing_derived_class_body() {
+            self.emit_instance_initializers(&body)?;
+        }
+        self.chunk.emit(Opcode::LoadGlobal("this".to_string()));
+        Ok(())
+    }
+
+    /// Body of the derived class whose constructor encloses this code, if any
+    fn enclosing_derived_class_body(&self) -> Option<Rc<[ClassElement]>> {
+        match &self.derived_class_body {
+            Some(body) => Some(Rc::clone(body)),
+            None => self.enclosing.as_ref()?.enclosing_derived_class_body(),
+        }
+    }
+
+    /// Collect call arguments into a single array, expanding spread elements
+    fn emit_argument_array(&mut self, arguments: &[Expression]) -> Result<(), JsError> {
+        let elements: Vec<Option<(&Expression, bool)>> = arguments
+            .iter()
+            .map(|arg| match arg {
+                Expression::SpreadElement { argument, .. } => Some((argument.as_ref(), true)),
+                other => Some((other, false)),
+            })
+            .collect();
+        self.emit_array(&elements)
+    }
+
+    /// Emit an array built from `(expression, is_spread)` elements, `None` being a hole
+    ///
+    /// Elements before the first spread are created in one CreateArray; each
+    /// later element is appended in order so spread sources land at the right
+    /// position.
+    fn emit_array(&mut self, elements: &[Option<(&Expression, bool)>]) -> Result<(), JsError> {
+        // Elements before the first spread or hole are created in one go
+        let prefix_len = elements
+            .iter()
+            .position(|elem| matches!(elem, Some((_, true)) | None))
+            .unwrap_or(elements.len());
+
+        for (e, _) in elements[..prefix_len].iter().flatten() {
+            self.visit_expression(e)?;
+        }
+        self.chunk.emit(Opcode::CreateArray(prefix_len));
+
+        for elem in &elements[prefix_len..] {
+            match elem {
+                Some((e, true)) => {
+                    self.visit_expression(e)?;
+                    self.chunk.emit(Opcode::ArraySpread);
+                }
+                Some((e, false)) => {
+                    self.visit_expression(e)?;
+                    self.chunk.emit(Opcode::ArrayPush);
+                }
+                None => {
+                    // A hole only advances the length: arr.length = arr.length + 1
+                    self.chunk.emit(Opcode::Dup);
+                    self.chunk.emit(Opcode::Dup);
+                    self.chunk.emit(Opcode::LoadProperty("length".to_string()));
+                    let one = self.chunk.add_constant(BytecodeValue::Number(1.0));
+                    self.chunk.emit(Opcode::LoadConstant(one));
+                    self.chunk.emit(Opcode::Add);
+                    self.chunk.emit(Opcode::StoreProperty("length".to_string()));
+                    self.chunk.emit(Opcode::Pop);
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Resolve a variable name to its location (local, upvalue, or global)
     fn resolve_variable(&mut self, name: &str) -> VarResolution {
         // Check local scope first
@@ -82,18 +830,20 @@ impl BytecodeGenerator {
             match enclosing.resolve_variable(name) {
                 VarResolution::Local(reg) => {
                     // Capture from parent - it's a local in parent scope
-                    let upvalue_idx = self.add_upvalue(UpvalueDescriptor {
+                    let upvalue = UpvalueDescriptor {
                         is_local: true,
                         index: reg.0,
-                    });
+                    };
+                    let upvalue_idx = self.add_upvalue(upvalue, name);
                     return VarResolution::Upvalue(upvalue_idx);
                 }
                 VarResolution::Upvalue(idx) => {
                     // Capture from grandparent+ - it's already an upvalue in parent
-                    let upvalue_idx = self.add_upvalue(UpvalueDescriptor {
+                    let upvalue = UpvalueDescriptor {
                         is_local: false,
                         index: idx,
-                    });
+                    };
+                    let upvalue_idx = self.add_upvalue(upvalue, name);
                     return VarResolution::Upvalue(upvalue_idx);
                 }
                 VarResolution::Global => {}
@@ -103,8 +853,8 @@ impl BytecodeGenerator {
         VarResolution::Global
     }
 
-    /// Add an upvalue descriptor and return its index
-    fn add_upvalue(&mut self, descriptor: UpvalueDescriptor) -> u32 {
+    /// Add an upvalue descriptor captured under `name` and return its index
+    fn add_upvalue(&mut self, descriptor: UpvalueDescriptor, name: &str) -> u32 {
         // Check if already captured
         for (i, uv) in self.upvalues.iter().enumerate() {
             if uv == &descriptor {
@@ -114,9 +864,22 @@ impl BytecodeGenerator {
         // Add new upvalue
         let idx = self.upvalues.len() as u32;
         self.upvalues.push(descriptor);
+        self.chunk.upvalue_names.push(name.to_string());
         idx
     }
 
+    /// Record the source names of the locals in the chunk, for debuggers
+    fn record_local_names(&mut self) {
+        let mut names: Vec<(RegisterId, String)> = self
+            .locals
+            .iter()
+            .filter(|(name, _)| name.as_str() != SUPER_BINDING)
+            .map(|(name, &reg)| (reg, name.clone()))
+            .collect();
+        names.sort_by_key(|(reg, _)| reg.0);
+        self.chunk.local_names = names;
+    }
+
     /// Get the captured upvalues for this function
     pub fn get_upvalues(&self) -> Vec<UpvalueDescriptor> {
         self.upvalues.clone()
@@ -175,6 +938,7 @@ impl BytecodeGenerator {
         }
 
         self.chunk.register_count = self.next_register;
+        self.record_local_names();
 
         // Transfer nested functions to the chunk
         // This allows the VM to access them when executing closures
@@ -187,6 +951,7 @@ impl BytecodeGenerator {
     fn visit_node(&mut self, node: &ASTNode) -> Result<(), JsError> {
         match node {
             ASTNode::Program(statements) => {
+                self.strict |= has_use_strict_directive(statements);
                 for stmt in statements {
                     self.visit_statement(stmt)?;
                 }
@@ -225,12 +990,18 @@ impl BytecodeGenerator {
             }
 
             Statement::FunctionDeclaration {
-                name, params, body, ..
+                name,
+                params,
+                body,
+                is_async,
+                is_generator,
+                ..
             } => {
                 // Create function bytecode with enclosing scope for closure support
                 // We need to temporarily take ownership of self to pass it as enclosing
                 let current_gen = std::mem::replace(self, BytecodeGenerator::new());
                 let mut func_gen = BytecodeGenerator::with_enclosing(Box::new(current_gen));
+                func_gen.enter_function_body(body, *is_async, *is_generator);
 
                 // Set up parameters as locals
                 for param in params {
@@ -257,6 +1028,7 @@ impl BytecodeGenerator {
                 }
 
                 func_gen.chunk.register_count = func_gen.next_register;
+                func_gen.record_local_names();
 
                 // Get the upvalues captured by this function
                 let upvalues = func_gen.get_upvalues();
@@ -305,127 +1077,11 @@ impl BytecodeGenerator {
                     self.locals.insert(name.clone(), reg);
                 }
             }
-
-            Statement::ClassDeclaration { name, body, .. } => {
-                // A class declaration creates a constructor function bound to the class name
-                // Find the constructor method in the class body
-                let constructor_method = body.iter().find_map(|element| {
-                    if let ClassElement::MethodDefinition {
-                        kind: MethodKind::Constructor,
-                        value,
-                        ..
-                    } = element
-                    {
-                        Some(value)
-                    } else {
-                        None
-                    }
-                });
-
-                if let Some(constructor_expr) = constructor_method {
-                    // Extract parameters and body from the constructor function expression
-                    if let Expression::FunctionExpression {
-                        params,
-                        body: func_body,
-                        ..
-                    } = constructor_expr
-                    {
-                        // Create function bytecode with enclosing scope for closure support
-                        let current_gen = std::mem::replace(self, BytecodeGenerator::new());
-                        let mut func_gen =
-                            BytecodeGenerator::with_enclosing(Box::new(current_gen));
-
-                        // Set up parameters as locals
-                        for param in params {
-                            if let Pattern::Identifier(param_name) = param {
-                                let reg = func_gen.allocate_register();
-                                func_gen.locals.insert(param_name.clone(), reg);
-                            }
-                        }
-
-                        // Generate constructor body
-                        for stmt in func_body {
-                            func_gen.visit_statement(stmt)?;
-                        }
-
-                        // Ensure return (constructor returns 'this' implicitly, but we handle that in VM)
-                        if func_gen.chunk.instructions.is_empty()
-                            || !matches!(
-                                func_gen.chunk.instructions.last().map(|i| &i.opcode),
-                                Some(Opcode::Return)
-                            )
-                        {
-                            func_gen.chunk.emit(Opcode::LoadUndefined);
-                            func_gen.chunk.emit(Opcode::Return);
-                        }
-
-                        func_gen.chunk.register_count = func_gen.next_register;
-
-                        // Get the upvalues captured by this function
-                        let upvalues = func_gen.get_upvalues();
-
-                        // Get the compiled function bytecode
-                        let mut func_bytecode = func_gen.chunk.clone();
-
-                        // Collect any nested functions from the inner function
-                        let inner_nested = func_gen.take_nested_functions();
-
-                        // Restore the outer generator from the enclosing scope
-                        *self = *func_gen.enclosing.take().unwrap();
-
-                        // Add the compiled function to our nested functions list
-                        let func_idx = self.nested_functions.len();
-
-                        // Adjust indices in the function's bytecode for nested functions
-                        let inner_base_idx = func_idx + 1;
-                        Self::adjust_closure_indices(&mut func_bytecode, inner_base_idx);
-
-                        self.nested_functions.push(func_bytecode);
-
-                        // Also include any nested functions from the inner function
-                        let mut adjusted_inner_nested = inner_nested;
-                        for nested_chunk in &mut adjusted_inner_nested {
-                            Self::adjust_closure_indices(nested_chunk, inner_base_idx);
-                        }
-                        self.nested_functions.extend(adjusted_inner_nested);
-
-                        // Create closure with upvalue descriptors
-                        self.chunk.emit(Opcode::CreateClosure(func_idx, upvalues));
-
-                        // Store the constructor function with the class name as a global
-                        self.chunk.emit(Opcode::StoreGlobal(name.clone()));
-                    } else {
-                        // Constructor is not a function expression (shouldn't happen with valid parsing)
-                        // Fallback: create empty constructor
-                        let current_gen = std::mem::replace(self, BytecodeGenerator::new());
-                        let mut func_gen =
-                            BytecodeGenerator::with_enclosing(Box::new(current_gen));
-                        func_gen.chunk.emit(Opcode::LoadUndefined);
-                        func_gen.chunk.emit(Opcode::Return);
-                        func_gen.chunk.register_count = func_gen.next_register;
-                        let upvalues = func_gen.get_upvalues();
-                        let func_bytecode = func_gen.chunk.clone();
-                        *self = *func_gen.enclosing.take().unwrap();
-                        let func_idx = self.nested_functions.len();
-                        self.nested_functions.push(func_bytecode);
-                        self.chunk.emit(Opcode::CreateClosure(func_idx, upvalues));
-                        self.chunk.emit(Opcode::StoreGlobal(name.clone()));
-                    }
-                } else {
-                    // No explicit constructor - create a default empty constructor
-                    let current_gen = std::mem::replace(self, BytecodeGenerator::new());
-                    let mut func_gen = BytecodeGenerator::with_enclosing(Box::new(current_gen));
-                    func_gen.chunk.emit(Opcode::LoadUndefined);
-                    func_gen.chunk.emit(Opcode::Return);
-                    func_gen.chunk.register_count = func_gen.next_register;
-                    let upvalues = func_gen.get_upvalues();
-                    let func_bytecode = func_gen.chunk.clone();
-                    *self = *func_gen.enclosing.take().unwrap();
-                    let func_idx = self.nested_functions.len();
-                    self.nested_functions.push(func_bytecode);
-                    self.chunk.emit(Opcode::CreateClosure(func_idx, upvalues));
-                    self.chunk.emit(Opcode::StoreGlobal(name.clone()));
-                }
+
+            Statement::ClassDeclaration { name, super_class, body, .. } => {
+                // A class declaration creates a constructor function bound to the class name
+                self.emit_class(super_class.as_deref(), body)?;
+                self.chunk.emit(Opcode::StoreGlobal(name.clone()));
             }
 
             Statement::ExpressionStatement { expression, .. } => {
@@ -435,11 +1091,11 @@ impl BytecodeGenerator {
 
             Statement::ReturnStatement { argument, .. } => {
                 if let Some(expr) = argument {
-                    self.visit_expression(expr)?;
+                    self.emit_return(expr)?;
                 } else {
                     self.chunk.emit(Opcode::LoadUndefined);
+                    self.chunk.emit(Opcode::Return);
                 }
-                self.chunk.emit(Opcode::Return);
             }
 
             Statement::IfStatement {
@@ -480,9 +1136,9 @@ impl BytecodeGenerator {
             }
 
             Statement::WhileStatement { test, body, .. } => {
+                let labels = std::mem::take(&mut self.pending_labels);
                 let loop_start = self.chunk.instruction_count();
-                self.loop_starts.push(loop_start);
-                self.loop_exits.push(Vec::new());
+                self.push_jump_target(JumpTargetKind::Loop, labels);
 
                 self.visit_expression(test)?;
 
@@ -497,13 +1153,7 @@ impl BytecodeGenerator {
                 // Patch exit jump
                 let end_addr = self.chunk.instruction_count();
                 self.patch_jump(exit_jump, end_addr);
-
-                // Patch any break jumps
-                let exits = self.loop_exits.pop().unwrap();
-                for exit in exits {
-                    self.patch_jump(exit, end_addr);
-                }
-                self.loop_starts.pop();
+                self.pop_jump_target(loop_start);
             }
 
             Statement::ForStatement {
@@ -513,6 +1163,7 @@ impl BytecodeGenerator {
                 body,
                 ..
             } => {
+                let labels = std::mem::take(&mut self.pending_labels);
                 // Init
                 if let Some(init) = init {
                     match init {
@@ -537,8 +1188,7 @@ impl BytecodeGenerator {
                 }
 
                 let loop_start = self.chunk.instruction_count();
-                self.loop_starts.push(loop_start);
-                self.loop_exits.push(Vec::new());
+                self.push_jump_target(JumpTargetKind::Loop, labels);
 
                 // Test
                 let exit_jump = if let Some(test) = test {
@@ -553,7 +1203,8 @@ impl BytecodeGenerator {
                 // Body
                 self.visit_statement(body)?;
 
-                // Update
+                // Update, where `continue` resumes
+                let continue_addr = self.chunk.instruction_count();
                 if let Some(update) = update {
                     self.visit_expression(update)?;
                 }
@@ -566,15 +1217,11 @@ impl BytecodeGenerator {
                 if let Some(j) = exit_jump {
                     self.patch_jump(j, end_addr);
                 }
-
-                let exits = self.loop_exits.pop().unwrap();
-                for exit in exits {
-                    self.patch_jump(exit, end_addr);
-                }
-                self.loop_starts.pop();
+                self.pop_jump_target(continue_addr);
             }
 
             Statement::ForInStatement { left, right, body, .. } => {
+                self.pending_labels.clear();
                 // For-in loop: iterate over enumerable properties
                 // This is a simplified implementation that doesn't actually iterate
                 // A proper implementation would need runtime support for property enumeration
@@ -612,39 +1259,44 @@ impl BytecodeGenerator {
             }
 
             Statement::ForOfStatement { left, right, body, r#await: _, .. } => {
-                // For-of loop: iterate over iterable
-                // This is a simplified implementation that doesn't actually iterate
-                // A proper implementation would need runtime support for the iterator protocol
+                let labels = std::mem::take(&mut self.pending_labels);
 
-                // For now, just evaluate the right side and skip
+                // The iterator lives in a register for the whole loop
                 self.visit_expression(right)?;
-                self.chunk.emit(Opcode::Pop); // discard iterable
+                self.chunk.emit(Opcode::GetIterator);
+                let iterator = self.allocate_register();
+                self.chunk.emit(Opcode::StoreLocal(iterator));
 
-                // Initialize the loop variable with undefined
+                let loop_start = self.chunk.instruction_count();
+                self.push_jump_target(JumpTargetKind::Loop, labels);
+
+                // Step the iterator: [value, done]
+                self.chunk.emit(Opcode::LoadLocal(iterator));
+                self.chunk.emit(Opcode::IteratorNext);
+                let exit_jump = self.chunk.instruction_count();
+                self.chunk.emit(Opcode::JumpIfTrue(0)); // Placeholder
+
+                // Assign the value to the loop variable
                 match left {
-                    ForInOfLeft::VariableDeclaration { kind: _, id } => {
-                        if let Pattern::Identifier(name) = id {
-                            let reg = self.allocate_register();
-                            self.chunk.emit(Opcode::LoadUndefined);
-                            self.chunk.emit(Opcode::StoreLocal(reg));
-                            self.locals.insert(name.clone(), reg);
-                        }
-                    }
+                    ForInOfLeft::VariableDeclaration { id, .. } => self.bind_pattern(id)?,
                     ForInOfLeft::Pattern(Pattern::Identifier(name)) => {
                         if let Some(&reg) = self.locals.get(name) {
-                            self.chunk.emit(Opcode::LoadUndefined);
                             self.chunk.emit(Opcode::StoreLocal(reg));
                         } else {
-                            self.chunk.emit(Opcode::LoadUndefined);
                             self.chunk.emit(Opcode::StoreGlobal(name.clone()));
                         }
                     }
-                    _ => {}
+                    _ => return Err(Self::unsupported_pattern("for-of assignment target")),
                 }
 
-                // Note: Proper for-of would iterate over values
-                // For now, just skip the body (don't execute)
-                let _ = body;
+                self.visit_statement(body)?;
+                self.chunk.emit(Opcode::Jump(loop_start));
+
+                // The final step leaves its undefined value behind
+                let exit = self.chunk.instruction_count();
+                self.patch_jump(exit_jump, exit);
+                self.chunk.emit(Opcode::Pop);
+                self.pop_jump_target(loop_start);
             }
 
             Statement::BlockStatement { body, .. } => {
@@ -653,18 +1305,12 @@ impl BytecodeGenerator {
                 }
             }
 
-            Statement::BreakStatement { .. } => {
-                let jump = self.chunk.instruction_count();
-                self.chunk.emit(Opcode::Jump(0)); // Placeholder
-                if let Some(exits) = self.loop_exits.last_mut() {
-                    exits.push(jump);
-                }
+            Statement::BreakStatement { label, .. } => {
+                self.emit_loop_jump(label.as_deref(), false)?;
             }
 
-            Statement::ContinueStatement { .. } => {
-                if let Some(&start) = self.loop_starts.last() {
-                    self.chunk.emit(Opcode::Jump(start));
-                }
+            Statement::ContinueStatement { label, .. } => {
+                self.emit_loop_jump(label.as_deref(), true)?;
             }
 
             Statement::ThrowStatement { argument, .. } => {
@@ -678,35 +1324,43 @@ impl BytecodeGenerator {
                 finalizer,
                 ..
             } => {
-                // Emit PushTry with placeholder for catch offset
-                let push_try_idx = self.chunk.instruction_count();
-                self.chunk.emit(Opcode::PushTry(0)); // Will patch later
+                // The finally handler encloses both the try and the catch block,
+                // so an exception from either runs it before propagating
+                let push_finally_idx = finalizer.as_ref().map(|_| {
+                    let idx = self.chunk.instruction_count();
+                    self.chunk.emit(Opcode::PushFinally(0)); // Will patch later
+                    idx
+                });
+                self.try_depth += usize::from(finalizer.is_some());
 
-                // Execute try block
-                for stmt in block {
-                    self.visit_statement(stmt)?;
-                }
+                if let Some(catch_clause) = handler {
+                    // Emit PushTry with placeholder for catch offset
+                    let push_try_idx = self.chunk.instruction_count();
+                    self.chunk.emit(Opcode::PushTry(0)); // Will patch later
 
-                // Pop the try handler (no exception occurred)
-                self.chunk.emit(Opcode::PopTry);
+                    // Execute try block
+                    self.try_depth += 1;
+                    for stmt in block {
+                        self.visit_statement(stmt)?;
+                    }
+                    self.try_depth -= 1;
 
-                // Jump over catch block (normal execution path)
-                let jump_over_catch_idx = self.chunk.instruction_count();
-                self.chunk.emit(Opcode::Jump(0)); // Will patch later
+                    // Pop the try handler (no exception occurred)
+                    self.chunk.emit(Opcode::PopTry);
 
-                // Patch PushTry to point to catch block start
-                let catch_start = self.chunk.instruction_count();
-                self.patch_jump(push_try_idx, catch_start);
+                    // Jump over catch block (normal execution path)
+                    let jump_over_catch_idx = self.chunk.instruction_count();
+                    self.chunk.emit(Opcode::Jump(0)); // Will patch later
+
+                    // Patch PushTry to point to catch block start
+                    let catch_start = self.chunk.instruction_count();
+                    self.patch_jump(push_try_idx, catch_start);
 
-                // Handle catch block
-                if let Some(catch_clause) = handler {
                     // Bind exception value to parameter (exception is on stack)
-                    if let Some(Pattern::Identifier(param_name)) = &catch_clause.param {
-                        let reg = self.allocate_register();
-                        self.locals.insert(param_name.clone(), reg);
-                        self.chunk.emit(Opcode::StoreLocal(reg));
+                    if let Some(param) = &catch_clause.param {
+                        self.bind_pattern(param)?;
                     } else {
-                        // No parameter or non-identifier pattern - discard exception
+                        // Optional catch binding - discard exception without a register
                         self.chunk.emit(Opcode::Pop);
                     }
 
@@ -714,36 +1368,50 @@ impl BytecodeGenerator {
                     for stmt in &catch_clause.body {
                         self.visit_statement(stmt)?;
                     }
+
+                    // Patch jump to skip over catch block
+                    let after_catch = self.chunk.instruction_count();
+                    self.patch_jump(jump_over_catch_idx, after_catch);
                 } else {
-                    // No catch block - just pop the exception value
-                    self.chunk.emit(Opcode::Pop);
+                    for stmt in block {
+                        self.visit_statement(stmt)?;
+                    }
                 }
-
-                // Patch jump to skip over catch block
-                let after_catch = self.chunk.instruction_count();
-                self.patch_jump(jump_over_catch_idx, after_catch);
+                self.try_depth -= usize::from(finalizer.is_some());
 
                 // Handle finally block (if present)
-                if let Some(finally_block) = finalizer {
+                if let (Some(finally_block), Some(push_finally_idx)) =
+                    (finalizer, push_finally_idx)
+                {
+                    // Leaving normally: drop the handler and run the block inline
+                    self.chunk.emit(Opcode::PopTry);
+                    let finally_start = self.chunk.instruction_count();
+                    self.patch_jump(push_finally_idx, finally_start);
                     for stmt in finally_block {
                         self.visit_statement(stmt)?;
                     }
+                    // Resume whatever completion was interrupted to get here
+                    self.chunk.emit(Opcode::PopFinally);
                 }
             }
 
             Statement::EmptyStatement { .. } => {}
 
             Statement::DoWhileStatement { body, test, .. } => {
+                let labels = std::mem::take(&mut self.pending_labels);
                 let loop_start = self.chunk.instruction_count();
+                self.push_jump_target(JumpTargetKind::Loop, labels);
 
                 // Execute body
                 self.visit_statement(body)?;
 
-                // Evaluate condition
+                // Evaluate condition, where `continue` resumes
+                let continue_addr = self.chunk.instruction_count();
                 self.visit_expression(test)?;
 
                 // Jump back to start if condition is true
                 self.chunk.emit(Opcode::JumpIfTrue(loop_start));
+                self.pop_jump_target(continue_addr);
             }
 
             Statement::SwitchStatement {
@@ -752,8 +1420,11 @@ impl BytecodeGenerator {
                 ..
             } => {
                 use crate::ast::SwitchCase;
+                let labels = std::mem::take(&mut self.pending_labels);
                 // Evaluate discriminant
                 self.visit_expression(discriminant)?;
+                self.stack_depth += 1;
+                self.push_jump_target(JumpTargetKind::Switch, labels);
 
                 // For now, emit a simple sequential check for each case
                 let mut jump_patches = Vec::new();
@@ -789,8 +1460,10 @@ impl BytecodeGenerator {
                     }
                 }
 
-                // End of switch
+                // End of switch, where `break` lands
                 let switch_end = self.chunk.instruction_count();
+                self.pop_jump_target(switch_end);
+                self.stack_depth -= 1;
 
                 // Patch all the jumps
                 for (jump_idx, case_idx) in jump_patches {
@@ -820,14 +1493,30 @@ impl BytecodeGenerator {
                 self.visit_statement(body)?;
             }
 
-            Statement::DebuggerStatement { .. } => {
-                // Debugger statement is a no-op in bytecode
+            Statement::DebuggerStatement { position } => {
+                self.emit_at(Opcode::Debugger, position);
             }
 
-            Statement::LabeledStatement { body, .. } => {
-                // For now, just execute the body
-                // Proper labeled break/continue support would need more work
-                self.visit_statement(body)?;
+            Statement::LabeledStatement { label, body, .. } => {
+                self.pending_labels.push(label.clone());
+                match body.as_ref() {
+                    // These take the pending labels themselves, so that
+                    // `continue label` resumes the labeled loop
+                    Statement::WhileStatement { .. }
+                    | Statement::DoWhileStatement { .. }
+                    | Statement::ForStatement { .. }
+                    | Statement::ForInStatement { .. }
+                    | Statement::ForOfStatement { .. }
+                    | Statement::SwitchStatement { .. }
+                    | Statement::LabeledStatement { .. } => self.visit_statement(body)?,
+                    _ => {
+                        let labels = std::mem::take(&mut self.pending_labels);
+                        self.push_jump_target(JumpTargetKind::Block, labels);
+                        self.visit_statement(body)?;
+                        let end = self.chunk.instruction_count();
+                        self.pop_jump_target(end);
+                    }
+                }
             }
 
             // Module declarations - not supported in bytecode generation yet
@@ -851,6 +1540,12 @@ impl BytecodeGenerator {
     }
 
     fn visit_expression(&mut self, expr: &Expression) -> Result<(), JsError> {
+        // Only a chain link's object or callee continues its optional chain;
+        // a chain anywhere else (an argument, a computed key) is compiled on its own
+        let in_chain = std::mem::take(&mut self.continue_chain);
+        if !in_chain && Self::is_optional_chain(expr) {
+            return self.emit_optional_chain(expr);
+        }
         match expr {
             Expression::Identifier { name, .. } => {
                 match self.resolve_variable(name) {
@@ -952,6 +1647,12 @@ impl BytecodeGenerator {
                     BinaryOperator::Div => Opcode::Div,
                     BinaryOperator::Mod => Opcode::Mod,
                     BinaryOperator::Exp => Opcode::Exp,
+                    BinaryOperator::BitwiseAnd => Opcode::BitAnd,
+                    BinaryOperator::BitwiseOr => Opcode::BitOr,
+                    BinaryOperator::BitwiseXor => Opcode::BitXor,
+                    BinaryOperator::LeftShift => Opcode::ShiftLeft,
+                    BinaryOperator::RightShift => Opcode::ShiftRight,
+                    BinaryOperator::UnsignedRightShift => Opcode::ShiftRightUnsigned,
                     BinaryOperator::Eq => Opcode::Equal,
                     BinaryOperator::NotEq => Opcode::NotEqual,
                     BinaryOperator::StrictEq => Opcode::StrictEqual,
@@ -962,14 +1663,6 @@ impl BytecodeGenerator {
                     BinaryOperator::GtEq => Opcode::GreaterThanEqual,
                     BinaryOperator::Instanceof => Opcode::Instanceof,
                     BinaryOperator::In => Opcode::In,
-                    _ => {
-                        return Err(JsError {
-                            kind: ErrorKind::InternalError,
-                            message: format!("Unsupported binary operator: {:?}", operator),
-                            stack: vec![],
-                            source_position: None,
-                        })
-                    }
                 };
                 self.chunk.emit(op);
             }
@@ -1002,11 +1695,10 @@ impl BytecodeGenerator {
                         self.chunk.emit(Opcode::LoadTrue);
                     }
                     UnaryOperator::Plus => {
-                        // Unary plus - coerce to number (ToNumber)
-                        // For now, it's a no-op since we emit the value already
+                        self.chunk.emit(Opcode::ToNumber);
                     }
                     UnaryOperator::BitwiseNot => {
-                        // Bitwise NOT - TODO: implement when we have bitwise ops
+                        self.chunk.emit(Opcode::BitNot);
                     }
                 }
             }
@@ -1085,62 +1777,54 @@ impl BytecodeGenerator {
                 right,
                 ..
             } => {
+                // `left` stays on the stack as the result when it short-circuits
                 self.visit_expression(left)?;
-
-                match operator {
-                    LogicalOperator::And => {
-                        // For &&: return left if falsy, else return right
-                        // Stack: [left] -> Dup -> [left, left] -> JumpIfFalse -> [left]
-                        // If falsy: jump to end with left on stack
-                        // If truthy: Pop left, evaluate right
-                        self.chunk.emit(Opcode::Dup);
-                        let skip = self.chunk.instruction_count();
-                        self.chunk.emit(Opcode::JumpIfFalse(0));
-                        self.chunk.emit(Opcode::Pop); // Discard left, we'll use right
-                        self.visit_expression(right)?;
-                        let end = self.chunk.instruction_count();
-                        self.patch_jump(skip, end);
-                    }
-                    LogicalOperator::Or => {
-                        // For ||: return left if truthy, else return right
-                        // Stack: [left] -> Dup -> [left, left] -> JumpIfTrue -> [left]
-                        // If truthy: jump to end with left on stack
-                        // If falsy: Pop left, evaluate right
-                        self.chunk.emit(Opcode::Dup);
-                        let skip = self.chunk.instruction_count();
-                        self.chunk.emit(Opcode::JumpIfTrue(0));
-                        self.chunk.emit(Opcode::Pop); // Discard left, we'll use right
-                        self.visit_expression(right)?;
-                        let end = self.chunk.instruction_count();
-                        self.patch_jump(skip, end);
-                    }
-                    LogicalOperator::NullishCoalesce => {
-                        // For ??: return left if not null/undefined, else return right
-                        // Simplified - treat as OR for now (TODO: proper nullish check)
-                        self.chunk.emit(Opcode::Dup);
-                        let skip = self.chunk.instruction_count();
-                        self.chunk.emit(Opcode::JumpIfTrue(0));
-                        self.chunk.emit(Opcode::Pop); // Discard left, we'll use right
-                        self.visit_expression(right)?;
-                        let end = self.chunk.instruction_count();
-                        self.patch_jump(skip, end);
-                    }
-                }
+                let skip = self.emit_short_circuit(operator);
+                self.chunk.emit(Opcode::Pop); // Discard left, we'll use right
+                self.visit_expression(right)?;
+                let end = self.chunk.instruction_count();
+                self.patch_jump(skip, end);
             }
 
             Expression::AssignmentExpression {
                 left,
-                operator: _,
+                operator,
                 right,
                 ..
             } => {
+                if let Some(logical) = Self::logical_assignment_operator(operator) {
+                    return self.emit_logical_assignment(left, &logical, right);
+                }
+
                 // Assignment expressions return the assigned value
                 // So we need to duplicate the value before storing
                 match left {
                     AssignmentTarget::Identifier(name) => {
-                        self.visit_expression(right)?;
+                        let resolution = self.resolve_variable(name);
+                        let compound = Self::compound_assignment_opcode(operator);
+
+                        // `local op= value` updates the register in one instruction
+                        if let VarResolution::Local(reg) = resolution {
+                            if let Some(fused) = compound.as_ref().and_then(|op| op.assign_local(reg)) {
+                                self.visit_expression(right)?;
+                                self.chunk.emit(fused);
+                                return Ok(());
+                            }
+                        }
+
+                        if let Some(op) = compound {
+                            match &resolution {
+                                VarResolution::Local(reg) => self.chunk.emit(Opcode::LoadLocal(*reg)),
+                                VarResolution::Upvalue(idx) => self.chunk.emit(Opcode::LoadUpvalue(*idx)),
+                                VarResolution::Global => self.chunk.emit(Opcode::LoadGlobal(name.clone())),
+                            };
+                            self.visit_expression(right)?;
+                            self.chunk.emit(op);
+                        } else {
+                            self.visit_expression(right)?;
+                        }
                         self.chunk.emit(Opcode::Dup); // Keep value on stack for expression result
-                        match self.resolve_variable(name) {
+                        match resolution {
                             VarResolution::Local(reg) => {
                                 self.chunk.emit(Opcode::StoreLocal(reg));
                             }
@@ -1160,7 +1844,11 @@ impl BytecodeGenerator {
                             ..
                         } = member_expr.as_ref()
                         {
-                            if *computed {
+                            if let Some(op) = Self::compound_assignment_opcode(operator) {
+                                self.emit_compound_member_assignment(
+                                    object, property, *computed, op, right,
+                                )?;
+                            } else if *computed {
                                 // Computed assignment: obj[key] = value
                                 // SetIndex expects stack: [obj, key, value], pushes value as result
                                 self.visit_expression(object)?;
@@ -1173,7 +1861,7 @@ impl BytecodeGenerator {
                                 self.visit_expression(object)?;
                                 self.visit_expression(right)?;
                                 if let Expression::Identifier { name, .. } = property.as_ref() {
-                                    self.chunk.emit(Opcode::StoreProperty(name.clone()));
+                                    self.emit_store_named(name)?;
                                 }
                             }
                         }
@@ -1209,19 +1897,31 @@ impl BytecodeGenerator {
             }
 
             Expression::CallExpression {
-                callee, arguments, ..
+                callee,
+                arguments,
+                optional,
+                position,
             } => {
+                if let Expression::SuperExpression { .. } = callee.as_ref() {
+                    return self.emit_super_call(arguments);
+                }
+
                 // Check if this is a method call (callee is MemberExpression)
                 if let Expression::MemberExpression {
                     object,
                     property,
                     computed,
+                    optional: optional_member,
                     ..
                 } = callee.as_ref()
                 {
                     // Method call: obj.method(args) - need to bind 'this' to obj
                     // Stack should be: [obj, method, arg1, arg2, ...]
+                    self.continue_chain = in_chain;
                     self.visit_expression(object)?;
+                    if *optional_member {
+                        self.emit_optional_short_circuit();
+                    }
 
                     // Duplicate object so we have it for both property access and 'this'
                     self.chunk.emit(Opcode::Dup);
@@ -1231,7 +1931,17 @@ impl BytecodeGenerator {
                         self.visit_expression(property)?;
                         self.chunk.emit(Opcode::GetIndex);
                     } else if let Expression::Identifier { name, .. } = property.as_ref() {
-                        self.chunk.emit(Opcode::LoadProperty(name.clone()));
+                        self.emit_load_named(name)?;
+                    }
+                    if *optional {
+                        self.emit_optional_short_circuit();
+                    }
+
+                    if Self::has_spread(arguments) {
+                        // CallMethodSpread expects stack: [obj (this), method, [args]]
+                        self.emit_argument_array(arguments)?;
+                        self.chunk.emit(Opcode::CallMethodSpread);
+                        return Ok(());
                     }
 
                     // Push arguments
@@ -1241,11 +1951,21 @@ impl BytecodeGenerator {
 
                     // CallMethod expects stack: [obj (this), method, arg1, arg2, ...]
                     // argc includes the arguments only (not 'this' or method)
-                    self.chunk.emit(Opcode::CallMethod(arguments.len() as u8));
+                    self.emit_at(Opcode::CallMethod(arguments.len() as u8), position);
                 } else {
                     // Regular function call
                     // Push callee first (it goes underneath the arguments on stack)
+                    self.continue_chain = in_chain;
                     self.visit_expression(callee)?;
+                    if *optional {
+                        self.emit_optional_short_circuit();
+                    }
+
+                    if Self::has_spread(arguments) {
+                        self.emit_argument_array(arguments)?;
+                        self.chunk.emit(Opcode::CallSpread);
+                        return Ok(());
+                    }
 
                     // Push arguments (they go on top of callee)
                     for arg in arguments {
@@ -1253,7 +1973,7 @@ impl BytecodeGenerator {
                     }
 
                     // Call - dispatcher expects stack: [callee, arg1, arg2, ...]
-                    self.chunk.emit(Opcode::Call(arguments.len() as u8));
+                    self.emit_at(Opcode::Call(arguments.len() as u8), position);
                 }
             }
 
@@ -1261,39 +1981,50 @@ impl BytecodeGenerator {
                 object,
                 property,
                 computed,
+                optional,
                 ..
             } => {
+                self.continue_chain = in_chain;
                 self.visit_expression(object)?;
+                if *optional {
+                    self.emit_optional_short_circuit();
+                }
 
                 if *computed {
                     // Computed access: obj[expr] - use GetIndex
                     self.visit_expression(property)?;
                     self.chunk.emit(Opcode::GetIndex);
                 } else {
-                    // Static access: obj.prop - use LoadProperty
+                    // Static access: obj.prop - use LoadProperty (LoadPrivate for obj.#prop)
                     if let Expression::Identifier { name, .. } = property.as_ref() {
-                        self.chunk.emit(Opcode::LoadProperty(name.clone()));
+                        self.emit_load_named(name)?;
                     }
                 }
             }
 
             Expression::NewExpression {
-                callee, arguments, ..
+                callee,
+                arguments,
+                position,
             } => {
                 // Push constructor first (it goes underneath the arguments on stack)
                 self.visit_expression(callee)?;
+                if Self::has_spread(arguments) {
+                    self.emit_argument_array(arguments)?;
+                    self.chunk.emit(Opcode::CallNewSpread);
+                    return Ok(());
+                }
                 for arg in arguments {
                     self.visit_expression(arg)?;
                 }
                 // Use CallNew to properly create new instance
-                self.chunk.emit(Opcode::CallNew(arguments.len() as u8));
+                self.emit_at(Opcode::CallNew(arguments.len() as u8), position);
             }
 
             Expression::MetaProperty { meta, property, .. } => {
                 // Handle new.target and import.meta
                 if meta == "new" && property == "target" {
-                    // new.target - push undefined for now (proper implementation needs function context)
-                    self.chunk.emit(Opcode::LoadUndefined);
+                    self.chunk.emit(Opcode::LoadNewTarget);
                 } else if meta == "import" && property == "meta" {
                     // import.meta - push an empty object for now
                     self.chunk.emit(Opcode::CreateObject);
@@ -1308,54 +2039,98 @@ impl BytecodeGenerator {
             }
 
             Expression::ArrayExpression { elements, .. } => {
-                // Push all elements onto the stack first
-                let mut element_count = 0;
-                for elem in elements {
-                    if let Some(el) = elem {
-                        match el {
-                            ArrayElement::Expression(e) => {
-                                self.visit_expression(e)?;
-                                element_count += 1;
-                            }
-                            ArrayElement::Spread(e) => {
-                                // For spread, we push the array to be spread
-                                // The VM should handle expanding it
-                                self.visit_expression(e)?;
-                                element_count += 1;
-                            }
-                        }
-                    } else {
-                        // Hole in array - push undefined
-                        self.chunk.emit(Opcode::LoadUndefined);
-                        element_count += 1;
-                    }
-                }
-                // Create the array with the specified number of elements
-                self.chunk.emit(Opcode::CreateArray(element_count));
+                // Each element is (expression, is_spread); a hole is None
+                let elements: Vec<Option<(&Expression, bool)>> = elements
+                    .iter()
+                    .map(|elem| {
+                        elem.as_ref().map(|el| match el {
+                            ArrayElement::Expression(e) => (e, false),
+                            ArrayElement::Spread(e) => (e, true),
+                        })
+                    })
+                    .collect();
+                self.emit_array(&elements)?;
             }
 
             Expression::ObjectExpression { properties, .. } => {
                 self.chunk.emit(Opcode::CreateObject);
 
                 for prop in properties {
-                    if let ObjectProperty::Property { key, value, .. } = prop {
-                        if let PropertyKey::Identifier(name) = key {
-                            // Duplicate the object so StoreProperty doesn't consume it
+                    match prop {
+                        ObjectProperty::Property { key, value, .. } => {
+                            // Duplicate the object so the store doesn't consume it
                             self.chunk.emit(Opcode::Dup);
-                            self.visit_expression(value)?;
-                            self.chunk.emit(Opcode::StoreProperty(name.clone()));
-                            // Pop the value pushed by StoreProperty - we don't need it here
+                            match key {
+                                PropertyKey::Identifier(name) | PropertyKey::String(name) => {
+                                    self.visit_expression(value)?;
+                                    self.chunk.emit(Opcode::StoreProperty(name.clone()));
+                                }
+                                PropertyKey::Number(n) => {
+                                    // Numeric keys go through the VM's property-key conversion
+                                    let idx = self.chunk.add_constant(BytecodeValue::Number(*n));
+                                    self.chunk.emit(Opcode::LoadConstant(idx));
+                                    self.visit_expression(value)?;
+                                    self.chunk.emit(Opcode::SetIndex);
+                                }
+                                PropertyKey::Computed(key_expr) => {
+                                    self.visit_expression(key_expr)?;
+                                    self.visit_expression(value)?;
+                                    self.chunk.emit(Opcode::SetIndex);
+                                }
+                            }
+                            // Pop the value pushed by the store - we don't need it here
                             self.chunk.emit(Opcode::Pop);
                         }
+                        ObjectProperty::Accessor { key, kind, value, .. } => {
+                            // Defining an accessor leaves the object on the stack
+                            match key {
+                                PropertyKey::Identifier(name) | PropertyKey::String(name) => {
+                                    let value = BytecodeValue::String(name.clone());
+                                    let idx = self.chunk.add_constant(value);
+                                    self.chunk.emit(Opcode::LoadConstant(idx));
+                                }
+                                PropertyKey::Number(n) => {
+                                    let idx = self.chunk.add_constant(BytecodeValue::Number(*n));
+                                    self.chunk.emit(Opcode::LoadConstant(idx));
+                                }
+                                PropertyKey::Computed(key_expr) => {
+                                    self.visit_expression(key_expr)?;
+                                }
+                            }
+                            self.visit_expression(value)?;
+                            self.chunk.emit(match kind {
+                                MethodKind::Set => Opcode::DefineSetter,
+                                _ => Opcode::DefineGetter,
+                            });
+                        }
+                        ObjectProperty::SpreadElement(source) => {
+                            // Copy own enumerable properties at this position so
+                            // later keys override spread ones and vice versa
+                            self.visit_expression(source)?;
+                            self.chunk.emit(Opcode::CopyDataProperties);
+                        }
                     }
                 }
                 // Object remains on stack after all properties are set
             }
 
-            Expression::ArrowFunctionExpression { params, body, .. } => {
+            Expression::ArrowFunctionExpression {
+                params,
+                body,
+                is_async,
+                ..
+            } => {
                 // Create function bytecode with enclosing scope for closure support
                 let current_gen = std::mem::replace(self, BytecodeGenerator::new());
                 let mut func_gen = BytecodeGenerator::with_enclosing(Box::new(current_gen));
+                match body {
+                    ArrowFunctionBody::Block(stmts) => {
+                        func_gen.enter_function_body(stmts, *is_async, false)
+                    }
+                    ArrowFunctionBody::Expression(_) => {
+                        func_gen.enter_function_body(&[], *is_async, false)
+                    }
+                }
 
                 for param in params {
                     if let Pattern::Identifier(name) = param {
@@ -1366,8 +2141,7 @@ impl BytecodeGenerator {
 
                 match body {
                     ArrowFunctionBody::Expression(expr) => {
-                        func_gen.visit_expression(expr)?;
-                        func_gen.chunk.emit(Opcode::Return);
+                        func_gen.emit_return(expr)?;
                     }
                     ArrowFunctionBody::Block(stmts) => {
                         for stmt in stmts {
@@ -1386,6 +2160,7 @@ impl BytecodeGenerator {
                 }
 
                 func_gen.chunk.register_count = func_gen.next_register;
+                func_gen.record_local_names();
 
                 // Get the upvalues captured by this function
                 let upvalues = func_gen.get_upvalues();
@@ -1420,17 +2195,19 @@ impl BytecodeGenerator {
             }
 
             Expression::FunctionExpression {
-                name, params, body, ..
+                name,
+                params,
+                body,
+                is_async,
+                is_generator,
+                ..
             } => {
                 // Create function bytecode with enclosing scope for closure support
                 let current_gen = std::mem::replace(self, BytecodeGenerator::new());
                 let mut func_gen = BytecodeGenerator::with_enclosing(Box::new(current_gen));
+                func_gen.enter_function_body(body, *is_async, *is_generator);
 
-                if let Some(n) = name {
-                    let reg = func_gen.allocate_register();
-                    func_gen.locals.insert(n.clone(), reg);
-                }
-
+                // Arguments arrive in the first registers, so parameters come first
                 for param in params {
                     if let Pattern::Identifier(param_name) = param {
                         let reg = func_gen.allocate_register();
@@ -1438,6 +2215,13 @@ impl BytecodeGenerator {
                     }
                 }
 
+                if let Some(n) = name {
+                    if !func_gen.locals.contains_key(n) {
+                        let reg = func_gen.allocate_register();
+                        func_gen.locals.insert(n.clone(), reg);
+                    }
+                }
+
                 for stmt in body {
                     func_gen.visit_statement(stmt)?;
                 }
@@ -1453,6 +2237,7 @@ impl BytecodeGenerator {
                 }
 
                 func_gen.chunk.register_count = func_gen.next_register;
+                func_gen.record_local_names();
 
                 // Get the upvalues captured by this function
                 let upvalues = func_gen.get_upvalues();
@@ -1507,26 +2292,42 @@ impl BytecodeGenerator {
                 self.chunk.emit(Opcode::Await);
             }
 
-            Expression::YieldExpression { argument, .. } => {
+            Expression::YieldExpression {
+                argument, delegate, ..
+            } => {
                 if let Some(arg) = argument {
                     self.visit_expression(arg)?;
                 } else {
                     self.chunk.emit(Opcode::LoadUndefined);
                 }
+                if self.in_generator {
+                    if *delegate {
+                        // Iterator stays on the stack while YieldDelegate drives it;
+                        // the first value sent in is undefined
+                        self.chunk.emit(Opcode::GetIterator);
+                        self.chunk.emit(Opcode::LoadUndefined);
+                        self.chunk.emit(Opcode::YieldDelegate);
+                    } else {
+                        self.chunk.emit(Opcode::Yield);
+                    }
+                }
             }
 
-            Expression::TemplateLiteral { quasis, .. } => {
-                // Simplified - just load the first quasi
-                if let Some(quasi) = quasis.first() {
-                    let idx = self
-                        .chunk
-                        .add_constant(BytecodeValue::String(quasi.cooked.clone()));
-                    self.chunk.emit(Opcode::LoadConstant(idx));
-                } else {
-                    let idx = self
-                        .chunk
-                        .add_constant(BytecodeValue::String(String::new()));
-                    self.chunk.emit(Opcode::LoadConstant(idx));
+            Expression::TemplateLiteral { quasis, expressions, .. } => {
+                // Concatenate the quasis with each substitution converted by ToString
+                let first = quasis.first().map(|quasi| quasi.cooked.clone()).unwrap_or_default();
+                let idx = self.chunk.add_constant(BytecodeValue::String(first));
+                self.chunk.emit(Opcode::LoadConstant(idx));
+                for (expr, quasi) in expressions.iter().zip(quasis.iter().skip(1)) {
+                    self.visit_expression(expr)?;
+                    self.chunk.emit(Opcode::ToString);
+                    self.chunk.emit(Opcode::Add);
+                    if !quasi.cooked.is_empty() {
+                        let cooked = BytecodeValue::String(quasi.cooked.clone());
+                        let idx = self.chunk.add_constant(cooked);
+                        self.chunk.emit(Opcode::LoadConstant(idx));
+                        self.chunk.emit(Opcode::Add);
+                    }
                 }
             }
 
@@ -1545,16 +2346,8 @@ impl BytecodeGenerator {
                 super_class,
                 ..
             } => {
-                // Visit superclass if present
-                if let Some(super_expr) = super_class {
-                    self.visit_expression(super_expr)?;
-                }
-                // Visit method bodies in the class
-                for element in body {
-                    if let crate::ast::ClassElement::MethodDefinition { value, .. } = element {
-                        self.visit_expression(value)?;
-                    }
-                }
+                // The constructor closure is the value of the expression
+                self.emit_class(super_class.as_deref(), body)?;
             }
 
             Expression::ParenthesizedExpression { expression, .. } => {
@@ -1577,18 +2370,180 @@ impl BytecodeGenerator {
         Ok(())
     }
 
+    /// Bind the value on top of the stack to the names in `pattern`
+    ///
+    /// Each name gets a new local register; the value is popped.
+    fn bind_pattern(&mut self, pattern: &Pattern) -> Result<(), JsError> {
+        match pattern {
+            Pattern::Identifier(name) => {
+                let reg = self.allocate_register();
+                self.locals.insert(name.clone(), reg);
+                self.chunk.emit(Opcode::StoreLocal(reg));
+            }
+            Pattern::ObjectPattern(properties) => {
+                let source = self.allocate_register();
+                self.chunk.emit(Opcode::StoreLocal(source));
+                for property in properties {
+                    if let Pattern::RestElement(_) = property.value {
+                        return Err(Self::unsupported_pattern("object rest"));
+                    }
+                    self.chunk.emit(Opcode::LoadLocal(source));
+                    match &property.key {
+                        PatternKey::Literal(key) => {
+                            self.chunk.emit(Opcode::LoadProperty(key.clone()));
+                        }
+                        PatternKey::Computed(key) => {
+                            self.visit_expression(key)?;
+                            self.chunk.emit(Opcode::GetIndex);
+                        }
+                    }
+                    self.bind_pattern(&property.value)?;
+                }
+            }
+            Pattern::ArrayPattern(elements) => {
+                let source = self.allocate_register();
+                self.chunk.emit(Opcode::StoreLocal(source));
+                for (index, element) in elements.iter().enumerate() {
+                    let Some(element) = element else { continue };
+                    if let Pattern::RestElement(_) = element {
+                        return Err(Self::unsupported_pattern("array rest"));
+                    }
+                    self.chunk.emit(Opcode::LoadLocal(source));
+                    let idx = self.chunk.add_constant(BytecodeValue::Number(index as f64));
+                    self.chunk.emit(Opcode::LoadConstant(idx));
+                    self.chunk.emit(Opcode::GetIndex);
+                    self.bind_pattern(element)?;
+                }
+            }
+            Pattern::AssignmentPattern { left, right } => {
+                // Use the default only when the value is undefined
+                let value = self.allocate_register();
+                self.chunk.emit(Opcode::StoreLocal(value));
+                self.chunk.emit(Opcode::LoadLocal(value));
+                self.chunk.emit(Opcode::LoadUndefined);
+                self.chunk.emit(Opcode::StrictEqual);
+                let skip_default = self.chunk.instruction_count();
+                self.chunk.emit(Opcode::JumpIfFalse(0));
+                self.visit_expression(right)?;
+                self.chunk.emit(Opcode::StoreLocal(value));
+                let after_default = self.chunk.instruction_count();
+                self.patch_jump(skip_default, after_default);
+                self.chunk.emit(Opcode::LoadLocal(value));
+                self.bind_pattern(left)?;
+            }
+            Pattern::RestElement(_) => return Err(Self::unsupported_pattern("rest element")),
+            Pattern::MemberExpression(_) => {
+                return Err(Self::unsupported_pattern("member expression"))
+            }
+        }
+        Ok(())
+    }
+
+    fn unsupported_pattern(kind: &str) -> JsError {
+        JsError {
+            kind: ErrorKind::SyntaxError,
+            message: format!("Unsupported {} in binding pattern", kind),
+            stack: vec![],
+            source_position: None,
+        }
+    }
+
     fn allocate_register(&mut self) -> RegisterId {
         let reg = RegisterId(self.next_register);
         self.next_register += 1;
         reg
     }
 
+    /// Enter a statement that `break`/`continue` can jump out of
+    fn push_jump_target(&mut self, kind: JumpTargetKind, labels: Vec<String>) {
+        self.jump_targets.push(JumpTarget {
+            kind,
+            labels,
+            stack_depth: self.stack_depth,
+            breaks: Vec::new(),
+            continues: Vec::new(),
+        });
+    }
+
+    /// Leave the innermost jump target, patching its `break` jumps to the
+    /// current address and its `continue` jumps to `continue_addr`
+    fn pop_jump_target(&mut self, continue_addr: usize) {
+        let target = self.jump_targets.pop().expect("unbalanced jump targets");
+        let end_addr = self.chunk.instruction_count();
+        for jump in target.breaks {
+            self.patch_jump(jump, end_addr);
+        }
+        for jump in target.continues {
+            self.patch_jump(jump, continue_addr);
+        }
+    }
+
+    /// Emit the jump for `break label` or `continue label`
+    ///
+    /// Without a label, `break` leaves the innermost loop or switch and
+    /// `continue` the innermost loop. Values that statements between here and
+    /// the target hold on the operand stack are popped before jumping.
+    fn emit_loop_jump(&mut self, label: Option<&str>, is_continue: bool) -> Result<(), JsError> {
+        let keyword = if is_continue { "continue" } else { "break" };
+        let index = self.jump_targets.iter().rposition(|target| match label {
+            Some(label) => target.labels.iter().any(|l| l == label),
+            None if is_continue => target.kind == JumpTargetKind::Loop,
+            None => target.kind != JumpTargetKind::Block,
+        });
+        let index = match index {
+            Some(index)
+                if !is_continue || self.jump_targets[index].kind == JumpTargetKind::Loop =>
+            {
+                index
+            }
+            _ => {
+                let message = match label {
+                    Some(label) => {
+                        format!("Illegal {} statement: no loop labeled '{}'", keyword, label)
+                    }
+                    None => format!("Illegal {} statement", keyword),
+                };
+                return Err(JsError {
+                    kind: ErrorKind::SyntaxError,
+                    message,
+                    stack: vec![],
+                    source_position: None,
+                });
+            }
+        };
+        for _ in self.jump_targets[index].stack_depth..self.stack_depth {
+            self.chunk.emit(Opcode::Pop);
+        }
+        let jump = self.chunk.instruction_count();
+        self.chunk.emit(Opcode::Jump(0)); // Placeholder
+        let target = &mut self.jump_targets[index];
+        if is_continue {
+            target.continues.push(jump);
+        } else {
+            target.breaks.push(jump);
+        }
+        Ok(())
+    }
+
+    /// Emit `opcode`, tagged with the start of `span` when there is one
+    fn emit_at(&mut self, opcode: Opcode, span: &Option<SourceSpan>) {
+        match span {
+            Some(span) => {
+                let start = &span.start;
+                let position = SourcePosition::new(start.line, start.column, start.offset as u32);
+                self.chunk.emit_with_position(opcode, position);
+            }
+            None => self.chunk.emit(opcode),
+        }
+    }
+
     fn patch_jump(&mut self, jump_idx: usize, target: usize) {
         if let Some(inst) = self.chunk.instructions.get_mut(jump_idx) {
             match &mut inst.opcode {
                 Opcode::Jump(ref mut addr)
                 | Opcode::JumpIfTrue(ref mut addr)
                 | Opcode::JumpIfFalse(ref mut addr)
+                | Opcode::OptionalChainShortCircuit(_, ref mut addr)
                 | Opcode::PushTry(ref mut addr)
                 | Opcode::PushFinally(ref mut addr) => {
                     *addr = target;
@@ -1605,6 +2560,23 @@ impl Default for BytecodeGenerator {
     }
 }
 
+/// Check whether a body's directive prologue contains "use strict"
+fn has_use_strict_directive(body: &[Statement]) -> bool {
+    body.iter()
+        .map_while(|stmt| match stmt {
+            Statement::ExpressionStatement {
+                expression:
+                    Expression::Literal {
+                        value: Literal::String(directive),
+                        ..
+                    },
+                ..
+            } => Some(directive),
+            _ => None,
+        })
+        .any(|directive| directive == "use strict")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1943,17 +2915,18 @@ mod tests {
 
         let chunk = gen.generate(&ast).unwrap();
 
-        // Should have LoadUndefined for the hole
-        let has_load_undefined = chunk.instructions.iter().any(|i| {
-            matches!(i.opcode, Opcode::LoadUndefined)
+        // Elements before the hole are created together
+        let has_create_array = chunk.instructions.iter().any(|i| {
+            matches!(i.opcode, Opcode::CreateArray(1))
         });
-        assert!(has_load_undefined, "Expected LoadUndefined for array hole");
+        assert!(has_create_array, "Expected CreateArray(1) before the hole");
 
-        // Should have CreateArray(3)
-        let has_create_array = chunk.instructions.iter().any(|i| {
-            matches!(i.opcode, Opcode::CreateArray(3))
+        // The hole only bumps the length, leaving no element behind
+        let bumps_length = chunk.instructions.iter().any(|i| {
+            matches!(&i.opcode, Opcode::StoreProperty(name) if name == "length")
         });
-        assert!(has_create_array, "Expected CreateArray(3) for array with hole");
+        assert!(bumps_length, "Expected a length store for the array hole");
+        assert!(chunk.instructions.iter().any(|i| matches!(i.opcode, Opcode::ArrayPush)));
     }
 
     #[test]
@@ -3063,6 +4036,162 @@ mod tests {
             "Should LoadGlobal('Foo') to get constructor"
         );
     }
+
+    /// Generate `source` and report whether its first function uses TailCall / Call
+    fn first_function_calls(source: &str) -> (bool, bool) {
+        use crate::Parser;
+
+        let ast = Parser::new(source).parse().expect("Failed to parse");
+        let chunk = BytecodeGenerator::new()
+            .generate(&ast)
+            .expect("Failed to generate bytecode");
+        let func = &chunk.nested_functions[0];
+        let has = |pred: fn(&Opcode) -> bool| func.instructions.iter().any(|i| pred(&i.opcode));
+        (
+            has(|op| matches!(op, Opcode::TailCall(1))),
+            has(|op| matches!(op, Opcode::Call(1))),
+        )
+    }
+
+    #[test]
+    fn test_strict_return_call_emits_tail_call() {
+        let (tail, call) = first_function_calls("'use strict'; function f(n) { return g(n); }");
+        assert!(tail && !call);
+
+        let (tail, _) = first_function_calls("function f(n) { 'use strict'; return g(n); }");
+        assert!(tail);
+    }
+
+    #[test]
+    fn test_tail_call_requires_tail_position() {
+        // Sloppy mode
+        let (tail, call) = first_function_calls("function f(n) { return g(n); }");
+        assert!(!tail && call);
+
+        // Inside a try block
+        let (tail, _) =
+            first_function_calls("'use strict'; function f(n) { try { return g(n); } catch (e) {} }");
+        assert!(!tail);
+
+        // Result is used, not returned directly
+        let (tail, _) = first_function_calls("'use strict'; function f(n) { return 1 + g(n); }");
+        assert!(!tail);
+
+        // Async functions resume after the call
+        let (tail, _) = first_function_calls("'use strict'; async function f(n) { return g(n); }");
+        assert!(!tail);
+    }
+
+    fn generate_source(source: &str) -> BytecodeChunk {
+        use crate::Parser;
+
+        let ast = Parser::new(source).parse().expect("Failed to parse");
+        BytecodeGenerator::new()
+            .generate(&ast)
+            .expect("Failed to generate bytecode")
+    }
+
+    #[test]
+    fn test_optional_catch_binding_allocates_no_register() {
+        let chunk = generate_source("try { throw e; } catch { x = 1; }");
+
+        assert_eq!(chunk.register_count, 0);
+        let catch_start = match chunk.instructions[0].opcode {
+            Opcode::PushTry(offset) => offset,
+            ref other => panic!("expected PushTry, got {:?}", other),
+        };
+        assert_eq!(chunk.instructions[catch_start].opcode, Opcode::Pop);
+    }
+
+    #[test]
+    fn test_destructuring_catch_binding_loads_fields() {
+        let chunk = generate_source("try { throw e; } catch ({ message }) { x = message; }");
+
+        let has_load_message = chunk
+            .instructions
+            .iter()
+            .any(|i| matches!(i.opcode, Opcode::LoadProperty(ref s) if s == "message"));
+        assert!(has_load_message);
+        // One register for the thrown object, one for `message`
+        assert_eq!(chunk.register_count, 2);
+    }
+
+    #[test]
+    fn test_array_spread_appends_after_prefix() {
+        let chunk = generate_source("[...a, 3, ...b];");
+
+        let ops: Vec<&Opcode> = chunk.instructions.iter().map(|i| &i.opcode).collect();
+        assert!(matches!(
+            ops.as_slice(),
+            [
+                Opcode::CreateArray(0),
+                Opcode::LoadGlobal(_),
+                Opcode::ArraySpread,
+                Opcode::LoadConstant(_),
+                Opcode::ArrayPush,
+                Opcode::LoadGlobal(_),
+                Opcode::ArraySpread,
+                ..
+            ]
+        ));
+    }
+
+    #[test]
+    fn test_spread_call_collects_arguments() {
+        let chunk = generate_source("f(1, ...args); o.m(...args); new C(...args);");
+
+        let has = |pred: fn(&Opcode) -> bool| chunk.instructions.iter().any(|i| pred(&i.opcode));
+        assert!(has(|op| matches!(op, Opcode::CreateArray(1))));
+        assert!(has(|op| matches!(op, Opcode::CallSpread)));
+        assert!(has(|op| matches!(op, Opcode::CallMethodSpread)));
+        assert!(has(|op| matches!(op, Opcode::CallNewSpread)));
+        assert!(!has(|op| matches!(op, Opcode::Call(_) | Opcode::CallMethod(_) | Opcode::CallNew(_))));
+    }
+
+    #[test]
+    fn test_super_call_spreads_into_superclass_constructor() {
+        let chunk = generate_source(
+            "class A { constructor(a, b) {} } class B extends A { constructor(xs) { super(...xs); } }",
+        );
+
+        let derived = &chunk.nested_functions()[1];
+        let has = |pred: fn(&Opcode) -> bool| derived.instructions.iter().any(|i| pred(&i.opcode));
+        assert!(has(|op| matches!(op, Opcode::LoadUpvalue(_))));
+        assert!(has(|op| matches!(op, Opcode::CallMethodSpread)));
+        assert!(!has(|op| matches!(op, Opcode::LoadGlobal(name) if name == "super")));
+    }
+
+    #[test]
+    fn test_private_fields_use_private_opcodes() {
+        let chunk = generate_source(
+            "class A { #x = 1; constructor() { this.#x = this.#x + 1; this.y = 2; } }",
+        );
+
+        let ctor = &chunk.nested_functions()[0];
+        let has = |pred: fn(&Opcode) -> bool| ctor.instructions.iter().any(|i| pred(&i.opcode));
+        assert!(has(|op| matches!(op, Opcode::DefinePrivate(k) if k == "#x@0")));
+        assert!(has(|op| matches!(op, Opcode::LoadPrivate(k) if k == "#x@0")));
+        assert!(has(|op| matches!(op, Opcode::StorePrivate(k) if k == "#x@0")));
+        assert!(has(|op| matches!(op, Opcode::StoreProperty(k) if k == "y")));
+    }
+
+    #[test]
+    fn test_compound_assignment_to_local_is_fused() {
+        let chunk = generate_source("function f(x) { x += 5; x **= 2; total -= x; return x; }");
+
+        let ops: Vec<&Opcode> = chunk.nested_functions()[0]
+            .instructions
+            .iter()
+            .map(|i| &i.opcode)
+            .collect();
+        assert!(ops.contains(&&Opcode::AddAssignLocal(RegisterId(0))));
+        assert!(ops.contains(&&Opcode::ExpAssignLocal(RegisterId(0))));
+        // Globals still decompose into load, operate, store
+        let sub = ops.iter().position(|op| **op == Opcode::Sub).expect("Sub");
+        assert!(matches!(ops[sub - 2], Opcode::LoadGlobal(name) if name == "total"));
+        assert!(matches!(ops[sub + 2], Opcode::StoreGlobal(name) if name == "total"));
+    }
+
 }
 
 
@@ -1,6 +1,7 @@
 //! JavaScript Lexer - tokenizes source code into tokens
 
-use core_types::{ErrorKind, JsError, SourcePosition};
+use core_types::{ErrorKind, JsError, SourcePosition, SourceSpan};
+use std::collections::HashMap;
 
 /// JavaScript keyword types
 #[derive(Debug, Clone, PartialEq)]
@@ -253,6 +254,152 @@ pub enum Token {
     EOF,
 }
 
+/// Kind of the last significant token, as far as a following `/` or `{` cares
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+enum LastToken {
+    /// Start of a statement: input start, `;`, a block's braces, `else`, `do`
+    #[default]
+    Start,
+    /// An operator or keyword that expects an operand
+    Operand,
+    /// The end of an operand: identifier, literal, `]`, postfix `++`/`--`
+    Value,
+    /// `)`, which ends an operand or the head of `if`, `for` or a function
+    CloseParen,
+    /// `=>`, whose `{` opens a function body block
+    Arrow,
+    /// `.` or `?.`, after which any word is a property name
+    Dot,
+}
+
+/// What an open bracket started
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Bracket {
+    Paren,
+    Square,
+    /// A block statement, or the body of a declaration or arrow function
+    Block,
+    /// An object literal
+    Object,
+    /// The body of a function or class expression
+    ExpressionBody,
+    /// A template literal substitution (`${`)
+    Template,
+}
+
+/// Token context deciding whether a `/` starts a regex literal
+#[derive(Debug, Clone, Default)]
+struct SlashContext {
+    last: LastToken,
+    /// Open brackets, innermost last
+    brackets: Vec<Bracket>,
+    /// Bracket depths at which a function or class expression's body opens
+    pending_bodies: Vec<usize>,
+}
+
+impl SlashContext {
+    /// Whether a `/` here starts a regex literal rather than a division
+    ///
+    /// Where the tokens leave it open (after `of`, `yield`, `await` or `in`,
+    /// or a `}` that could close either a block or an object), the `/` lexes
+    /// as division and the parser rescans it as a regex where an operand is
+    /// expected.
+    fn regex_allowed(&self) -> bool {
+        matches!(self.last, LastToken::Start | LastToken::Operand | LastToken::Arrow)
+    }
+
+    /// Advance past `token`
+    fn note(&mut self, token: &Token) {
+        let word = matches!(token, Token::Identifier(..) | Token::Keyword(_));
+        if self.last == LastToken::Dot && word {
+            self.last = LastToken::Value;
+            return;
+        }
+        self.last = match token {
+            Token::Keyword(keyword) => match keyword {
+                Keyword::Return | Keyword::Typeof | Keyword::Instanceof | Keyword::New
+                | Keyword::Delete | Keyword::Void | Keyword::Throw | Keyword::Case => {
+                    LastToken::Operand
+                }
+                Keyword::Do | Keyword::Else | Keyword::Try | Keyword::Finally => LastToken::Start,
+                Keyword::Function | Keyword::Class => {
+                    if matches!(self.last, LastToken::Operand | LastToken::Arrow) {
+                        self.pending_bodies.push(self.brackets.len());
+                    }
+                    LastToken::Value
+                }
+                _ => LastToken::Value,
+            },
+            Token::TemplateHead(..) | Token::TemplateMiddle(..) => {
+                self.brackets.push(Bracket::Template);
+                LastToken::Operand
+            }
+            Token::Punctuator(punctuator) => match punctuator {
+                Punctuator::LParen => {
+                    self.brackets.push(Bracket::Paren);
+                    LastToken::Operand
+                }
+                Punctuator::LBracket => {
+                    self.brackets.push(Bracket::Square);
+                    LastToken::Operand
+                }
+                Punctuator::LBrace => {
+                    let bracket = if self.pending_bodies.last() == Some(&self.brackets.len()) {
+                        self.pending_bodies.pop();
+                        Bracket::ExpressionBody
+                    } else if self.last == LastToken::Operand {
+                        Bracket::Object
+                    } else {
+                        Bracket::Block
+                    };
+                    self.brackets.push(bracket);
+                    if bracket == Bracket::Block {
+                        LastToken::Start
+                    } else {
+                        LastToken::Operand
+                    }
+                }
+                Punctuator::RParen => {
+                    self.close();
+                    LastToken::CloseParen
+                }
+                Punctuator::RBracket => {
+                    self.close();
+                    LastToken::Value
+                }
+                Punctuator::RBrace => match self.close() {
+                    Some(Bracket::Object | Bracket::ExpressionBody | Bracket::Template) => {
+                        LastToken::Value
+                    }
+                    _ => LastToken::Start,
+                },
+                Punctuator::Semicolon => LastToken::Start,
+                Punctuator::PlusPlus | Punctuator::MinusMinus => LastToken::Value,
+                Punctuator::Dot | Punctuator::OptionalChain => LastToken::Dot,
+                Punctuator::Arrow => LastToken::Arrow,
+                _ => LastToken::Operand,
+            },
+            _ => LastToken::Value,
+        };
+    }
+
+    /// Pop the innermost bracket, dropping bodies expected inside it
+    fn close(&mut self) -> Option<Bracket> {
+        let bracket = self.brackets.pop();
+        let depth = self.brackets.len();
+        self.pending_bodies.retain(|&pending| pending <= depth);
+        bracket
+    }
+}
+
+/// Token positions saved alongside a buffered token during lookahead
+#[derive(Debug, Clone)]
+pub struct TokenSpans {
+    token_start: SourcePosition,
+    token_end: SourcePosition,
+    last_token_span: SourceSpan,
+}
+
 /// Lexer for JavaScript source code
 pub struct Lexer<'a> {
     source: &'a str,
@@ -266,12 +413,28 @@ pub struct Lexer<'a> {
     pub line_terminator_before_token: bool,
     /// Previous line number (used to detect line changes)
     pub previous_line: u32,
+    /// Start of the most recently scanned token (the peeked one, if any)
+    pub token_start: SourcePosition,
+    /// End of the most recently scanned token
+    token_end: SourcePosition,
+    /// Span of the last token returned by `next_token`
+    pub last_token_span: SourceSpan,
+    /// Regex context after the last scanned token
+    slash_context: SlashContext,
+    /// Regex context after each token scanned so far, by end offset, so that
+    /// rewinding `position` also rewinds the context
+    slash_contexts: HashMap<usize, SlashContext>,
 }
 
 impl<'a> Lexer<'a> {
     /// Create a new lexer for the given source code
     pub fn new(source: &'a str) -> Self {
         let chars: Vec<char> = source.chars().collect();
+        let origin = SourcePosition {
+            line: 1,
+            column: 1,
+            offset: 0,
+        };
         let mut lexer = Self {
             source,
             chars,
@@ -281,10 +444,16 @@ impl<'a> Lexer<'a> {
             current_token: None,
             line_terminator_before_token: false,
             previous_line: 1,
+            token_start: origin.clone(),
+            token_end: origin.clone(),
+            last_token_span: SourceSpan::new(origin.clone(), origin),
+            slash_context: SlashContext::default(),
+            slash_contexts: HashMap::new(),
         };
 
         // Handle hashbang comment at the start of the file
         lexer.skip_hashbang();
+        lexer.slash_contexts.insert(lexer.position, SlashContext::default());
         lexer
     }
 
@@ -320,12 +489,30 @@ impl<'a> Lexer<'a> {
         matches!(c, '\n' | '\r' | '\u{2028}' | '\u{2029}')
     }
 
+    /// Save the token positions, to restore with `current_token` after lookahead
+    pub fn token_spans(&self) -> TokenSpans {
+        TokenSpans {
+            token_start: self.token_start.clone(),
+            token_end: self.token_end.clone(),
+            last_token_span: self.last_token_span.clone(),
+        }
+    }
+
+    /// Restore token positions saved by `token_spans`
+    pub fn restore_token_spans(&mut self, spans: TokenSpans) {
+        self.token_start = spans.token_start;
+        self.token_end = spans.token_end;
+        self.last_token_span = spans.last_token_span;
+    }
+
     /// Get the next token from the source
     pub fn next_token(&mut self) -> Result<Token, JsError> {
-        if let Some(token) = self.current_token.take() {
-            return Ok(token);
-        }
-        self.scan_token()
+        let token = match self.current_token.take() {
+            Some(token) => token,
+            None => self.scan_token()?,
+        };
+        self.last_token_span = SourceSpan::new(self.token_start.clone(), self.token_end.clone());
+        Ok(token)
     }
 
     /// Peek at the next token without consuming it
@@ -368,6 +555,23 @@ impl<'a> Lexer<'a> {
     }
 
     fn scan_token(&mut self) -> Result<Token, JsError> {
+        if let Some(context) = self.slash_contexts.get(&self.position) {
+            self.slash_context = context.clone();
+        }
+        let token = self.scan_token_at_start()?;
+        self.token_end = self.current_position();
+        self.note_token(&token);
+        Ok(token)
+    }
+
+    /// Advance the regex context past `token`, which ends at `position`
+    fn note_token(&mut self, token: &Token) {
+        self.slash_context.note(token);
+        self.slash_contexts.insert(self.position, self.slash_context.clone());
+    }
+
+    /// Scan a token, recording where it starts in `token_start`
+    fn scan_token_at_start(&mut self) -> Result<Token, JsError> {
         // Record the line before skipping whitespace
         let line_before = self.line;
 
@@ -377,6 +581,7 @@ impl<'a> Lexer<'a> {
         self.line_terminator_before_token = self.line > line_before;
         self.previous_line = self.line;
 
+        self.token_start = self.current_position();
         if self.is_at_end() {
             return Ok(Token::EOF);
         }
@@ -481,7 +686,12 @@ impl<'a> Lexer<'a> {
             }
 
             '/' => {
-                if self.match_char('=') {
+                if self.slash_context.regex_allowed() {
+                    // Rescan from the opening '/'
+                    self.position -= 1;
+                    self.column -= 1;
+                    self.scan_regexp_literal()
+                } else if self.match_char('=') {
                     Ok(Token::Punctuator(Punctuator::SlashEq))
                 } else {
                     Ok(Token::Punctuator(Punctuator::Slash))
@@ -983,6 +1193,13 @@ impl<'a> Lexer<'a> {
     /// Called by the parser after it has consumed the closing `}` token.
     /// The lexer position should now be right after the `}`.
     pub fn scan_template_middle(&mut self) -> Result<Token, JsError> {
+        let token = self.scan_template_continuation()?;
+        self.note_token(&token);
+        Ok(token)
+    }
+
+    /// Scan a template literal from right after a substitution's `}`
+    fn scan_template_continuation(&mut self) -> Result<Token, JsError> {
         // Clear any buffered token since we're switching to template mode
         self.current_token = None;
 
@@ -1031,6 +1248,13 @@ impl<'a> Lexer<'a> {
     /// This should be called by the parser when it sees a '/' in a context where
     /// a regex literal is expected (not division).
     pub fn scan_regexp(&mut self) -> Result<Token, JsError> {
+        let token = self.scan_regexp_literal()?;
+        self.note_token(&token);
+        Ok(token)
+    }
+
+    /// Scan a regular expression literal starting at the opening '/'
+    fn scan_regexp_literal(&mut self) -> Result<Token, JsError> {
         let start_pos = self.current_position();
 
         // We expect to be positioned at the opening '/'
@@ -1837,41 +2061,35 @@ _ => {
                             is_legacy_octal = true; // All digits 0-7
                         }
                     }
-                    // Handle decimal point and exponent if present
-                    if !is_float && !self.is_at_end() && self.peek() == '.' {
+                    // Handle decimal point and exponent if present. A legacy octal
+                    // literal (07) has neither, so `07.5` is `07` followed by `.5`.
+                    // A non-octal decimal (08.5) may, and stays flagged for strict mode.
+                    if !is_legacy_octal && !self.is_at_end() && self.peek() == '.' {
                         // Look ahead to see what follows the dot
                         if let Some(next_after_dot) = self.peek_next() {
                             if next_after_dot.is_ascii_digit() {
                                 // Decimal with fractional part: 0.5
                                 is_float = true;
-                                is_legacy_octal = false; // No longer legacy octal
-                                is_non_octal_decimal = false;
                                 num_str.push(self.advance()); // consume '.'
                                 while !self.is_at_end() && self.peek().is_ascii_digit() {
                                     num_str.push(self.advance());
                                 }
-                            } else if !is_id_start(next_after_dot) {
+                            } else if !is_id_start(next_after_dot) || self.dot_starts_exponent() {
                                 // Trailing decimal: 0. followed by non-identifier (like ; or whitespace)
-                                // This is valid: "0." equals 0.0
+                                // or by an exponent (0.e1). This is valid: "0." equals 0.0
                                 is_float = true;
-                                is_legacy_octal = false;
-                                is_non_octal_decimal = false;
                                 num_str.push(self.advance()); // consume '.'
                             }
                             // If next is an identifier start (like 'toString'), leave the dot for member access
                         } else {
                             // End of file after dot: 0.EOF is valid
                             is_float = true;
-                            is_legacy_octal = false;
-                            is_non_octal_decimal = false;
                             num_str.push(self.advance()); // consume '.'
                         }
                     }
                     // Handle exponent
-                    if !self.is_at_end() && (self.peek() == 'e' || self.peek() == 'E') {
+                    if !is_legacy_octal && !self.is_at_end() && (self.peek() == 'e' || self.peek() == 'E') {
                         is_float = true;
-                        is_legacy_octal = false;
-                        is_non_octal_decimal = false;
                         num_str.push(self.advance());
                         if !self.is_at_end() && (self.peek() == '+' || self.peek() == '-') {
                             num_str.push(self.advance());
@@ -1890,12 +2108,7 @@ _ => {
         // Check for BigInt suffix 'n'
         if !self.is_at_end() && self.peek() == 'n' {
             if is_float {
-                return Err(JsError {
-                    kind: ErrorKind::SyntaxError,
-                    message: "BigInt literals cannot have decimal points".to_string(),
-                    stack: vec![],
-                    source_position: Some(start_pos.clone()),
-                });
+                return Err(Self::fractional_bigint_error(&num_str, start_pos));
             }
             // Numbers starting with 0 followed by more digits cannot have BigInt suffix
             // This includes legacy octal (00, 07) and non-octal decimal (08, 09)
@@ -1949,6 +2162,13 @@ _ => {
                     source_position: Some(start_pos.clone()),
                 })?
             }
+            // Legacy octal: 010 is 8
+            None if is_legacy_octal => u64::from_str_radix(&num_str, 8).map(|n| n as f64).map_err(|_| JsError {
+                kind: ErrorKind::SyntaxError,
+                message: format!("Invalid number: {}", num_str),
+                stack: vec![],
+                source_position: Some(start_pos.clone()),
+            })?,
             None => {
                 // Parse decimal
                 num_str.parse::<f64>().map_err(|_| JsError {
@@ -2046,9 +2266,9 @@ _ => {
                         stack: vec![],
                         source_position: Some(start_pos.clone()),
                     });
-                } else if !is_id_start(next) {
+                } else if !is_id_start(next) || self.dot_starts_exponent() {
                     // Trailing decimal: 1. followed by non-identifier (like ; or whitespace)
-                    // This is valid: "1." equals 1.0
+                    // or by an exponent (1.e3). This is valid: "1." equals 1.0
                     *is_float = true;
                     num_str.push(self.advance()); // consume '.'
                 }
@@ -2233,6 +2453,11 @@ _ => {
             });
         }
 
+        // .5n is not a BigInt
+        if !self.is_at_end() && self.peek() == 'n' {
+            return Err(Self::fractional_bigint_error(&num_str, start_pos));
+        }
+
         // Parse as float
         let value = num_str.parse::<f64>().map_err(|_| JsError {
             kind: ErrorKind::SyntaxError,
@@ -2640,6 +2865,32 @@ _ => {
         }
     }
 
+    /// Whether the '.' at the current position is followed by an exponent
+    /// part, as in `1.e3` or `1.E-3`
+    fn dot_starts_exponent(&self) -> bool {
+        let at = |offset: usize| self.chars.get(self.position + offset).copied();
+        match (at(1), at(2), at(3)) {
+            (Some('e' | 'E'), Some(d), _) if d.is_ascii_digit() => true,
+            (Some('e' | 'E'), Some('+' | '-'), Some(d)) => d.is_ascii_digit(),
+            _ => false,
+        }
+    }
+
+    /// Error for a BigInt suffix on a literal with a fraction or exponent
+    fn fractional_bigint_error(num_str: &str, start_pos: SourcePosition) -> JsError {
+        let message = if num_str.contains(['e', 'E']) {
+            "BigInt literals cannot have exponents"
+        } else {
+            "BigInt literals cannot have decimal points"
+        };
+        JsError {
+            kind: ErrorKind::SyntaxError,
+            message: message.to_string(),
+            stack: vec![],
+            source_position: Some(start_pos),
+        }
+    }
+
     fn advance(&mut self) -> char {
         let ch = self.chars[self.position];
         self.position += 1;
@@ -2657,7 +2908,8 @@ _ => {
         }
     }
 
-    fn current_position(&self) -> SourcePosition {
+    /// Position of the next unscanned character
+    pub fn current_position(&self) -> SourcePosition {
         SourcePosition {
             line: self.line,
             column: self.column,
@@ -3351,4 +3603,115 @@ mod tests {
         let token = lexer.next_token().unwrap();
         assert!(matches!(token, Token::Number(_)));
     }
+
+    #[test]
+    fn test_lexer_bigint_rejects_fraction_and_exponent() {
+        for (source, expected) in [
+            ("1.5n", "decimal points"),
+            (".5n", "decimal points"),
+            ("1e3n", "exponents"),
+            ("1E3n", "exponents"),
+            ("1.e3n", "exponents"),
+            (".5e1n", "exponents"),
+            ("08n", "starting with 0"),
+        ] {
+            let err = Lexer::new(source).next_token().expect_err(source);
+            assert!(err.message.contains(expected), "{}: {}", source, err.message);
+        }
+    }
+
+    #[test]
+    fn test_lexer_decimal_point_forms() {
+        let number = |source: &str| match Lexer::new(source).next_token() {
+            Ok(Token::Number(n)) => n,
+            other => panic!("{}: {:?}", source, other),
+        };
+        assert_eq!(number(".5"), 0.5);
+        assert_eq!(number("5."), 5.0);
+        assert_eq!(number("1e10"), 1e10);
+        assert_eq!(number("5.e1"), 50.0);
+        assert_eq!(number("1.E-1"), 0.1);
+        assert_eq!(number(".5e1"), 5.0);
+
+        // `5.toString` keeps the dot for member access
+        let mut lexer = Lexer::new("5.toString");
+        assert!(matches!(lexer.next_token().unwrap(), Token::Number(n) if n == 5.0));
+        assert!(matches!(lexer.next_token().unwrap(), Token::Punctuator(Punctuator::Dot)));
+    }
+
+    #[test]
+    fn test_lexer_leading_zero_literals() {
+        assert!(matches!(Lexer::new("010").next_token().unwrap(), Token::LegacyOctalLiteral(n) if n == 8.0));
+        assert!(matches!(Lexer::new("08").next_token().unwrap(), Token::NonOctalDecimalLiteral(n) if n == 8.0));
+        assert!(matches!(Lexer::new("09.5").next_token().unwrap(), Token::NonOctalDecimalLiteral(n) if n == 9.5));
+
+        // A legacy octal literal has no fraction, so `07.5` is `07` then `.5`
+        let mut lexer = Lexer::new("07.5");
+        assert!(matches!(lexer.next_token().unwrap(), Token::LegacyOctalLiteral(n) if n == 7.0));
+        assert!(matches!(lexer.next_token().unwrap(), Token::Number(n) if n == 0.5));
+    }
+
+    fn lex_all(source: &str) -> Vec<Token> {
+        let mut lexer = Lexer::new(source);
+        let mut tokens = Vec::new();
+        loop {
+            match lexer.next_token().unwrap() {
+                Token::EOF => return tokens,
+                token => tokens.push(token),
+            }
+        }
+    }
+
+    fn count_regexps(tokens: &[Token]) -> usize {
+        tokens.iter().filter(|t| matches!(t, Token::RegExp(..))).count()
+    }
+
+    #[test]
+    fn test_lexer_slash_after_operands_is_division() {
+        let tokens = lex_all("a = b / c / d");
+        let slashes = tokens
+            .iter()
+            .filter(|t| matches!(t, Token::Punctuator(Punctuator::Slash)))
+            .count();
+        assert_eq!(slashes, 2);
+        assert_eq!(count_regexps(&tokens), 0);
+
+        assert_eq!(count_regexps(&lex_all("f(x) / 2 / g[0] / i++ / 1")), 0);
+        assert_eq!(count_regexps(&lex_all("a.return / b / c")), 0);
+        assert_eq!(count_regexps(&lex_all("x /= 2")), 0);
+        assert_eq!(count_regexps(&lex_all("var of = 8; of / 2 / 1")), 0);
+        assert_eq!(count_regexps(&lex_all("var yield = 4; yield / 2 / 1")), 0);
+        assert_eq!(count_regexps(&lex_all("await / 2 / 1")), 0);
+    }
+
+    #[test]
+    fn test_lexer_slash_after_keyword_is_regex() {
+        let tokens = lex_all("return /x/g");
+        assert!(matches!(&tokens[1], Token::RegExp(p, f) if p == "x" && f == "g"));
+
+        assert_eq!(count_regexps(&lex_all("typeof /x/")), 1);
+        assert_eq!(count_regexps(&lex_all("a = /=/")), 1);
+        assert_eq!(count_regexps(&lex_all("f(/a/, /* c */ /b/)")), 2);
+    }
+
+    #[test]
+    fn test_lexer_slash_after_block_is_regex() {
+        let tokens = lex_all("{}/x/");
+        assert_eq!(tokens.len(), 3);
+        assert!(matches!(&tokens[2], Token::RegExp(p, f) if p == "x" && f.is_empty()));
+
+        assert_eq!(count_regexps(&lex_all("if (a) {} /x/.test(s)")), 1);
+        assert_eq!(count_regexps(&lex_all("function f() {} /x/.test(s)")), 1);
+        assert_eq!(count_regexps(&lex_all("x => {}\n/x/.test(s)")), 1);
+    }
+
+    #[test]
+    fn test_lexer_slash_after_expression_brace_is_division() {
+        assert_eq!(count_regexps(&lex_all("var x = {} / 1")), 0);
+        assert_eq!(count_regexps(&lex_all("var f = function(){} / 1")), 0);
+        assert_eq!(count_regexps(&lex_all("var f = function(a = () => {}) { if (a) {} } / 1")), 0);
+        assert_eq!(count_regexps(&lex_all("var c = class { m() {} } / 1")), 0);
+        assert_eq!(count_regexps(&lex_all("f({ a: {} } / 2, [{}] / 3)")), 0);
+        assert_eq!(count_regexps(&lex_all("5. / 2 / 1")), 0);
+    }
 }
@@ -3,7 +3,7 @@
 use crate::ast::*;
 use crate::error::*;
 use crate::lexer::{Keyword, Lexer, Punctuator, Token};
-use core_types::JsError;
+use core_types::{JsError, SourceSpan};
 
 /// Lazy AST representation for deferred parsing
 #[derive(Debug, Clone)]
@@ -328,6 +328,7 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_statement(&mut self) -> Result<Statement, JsError> {
+        let node_start = self.node_start();
         let token = self.lexer.peek_token()?.clone();
 
         // Handle 'let' specially - in non-strict mode it can be an identifier
@@ -343,6 +344,7 @@ impl<'a> Parser<'a> {
                 let saved_col = self.lexer.column;
                 let saved_line_term = self.lexer.line_terminator_before_token;
                 let saved_tok = self.lexer.current_token.clone();
+                let saved_tok_spans = self.lexer.token_spans();
 
                 self.lexer.next_token()?; // consume 'let'
                 let next = self.lexer.peek_token()?.clone();
@@ -353,6 +355,7 @@ impl<'a> Parser<'a> {
                 self.lexer.column = saved_col;
                 self.lexer.line_terminator_before_token = saved_line_term;
                 self.lexer.current_token = saved_tok;
+                self.lexer.restore_token_spans(saved_tok_spans);
 
                 if matches!(next, Token::Punctuator(Punctuator::LBracket)) {
                     return Err(syntax_error(
@@ -397,6 +400,7 @@ impl<'a> Parser<'a> {
                 let saved_col = self.lexer.column;
                 let saved_line_term = self.lexer.line_terminator_before_token;
                 let saved_token = self.lexer.current_token.clone();
+                let saved_token_spans = self.lexer.token_spans();
 
                 self.lexer.next_token()?; // consume 'import'
                 let next = self.lexer.peek_token()?.clone();
@@ -407,6 +411,7 @@ impl<'a> Parser<'a> {
                 self.lexer.column = saved_col;
                 self.lexer.line_terminator_before_token = saved_line_term;
                 self.lexer.current_token = saved_token;
+                self.lexer.restore_token_spans(saved_token_spans);
 
                 match next {
                     Token::Punctuator(Punctuator::LParen) | Token::Punctuator(Punctuator::Dot) => {
@@ -422,7 +427,7 @@ impl<'a> Parser<'a> {
             Token::Punctuator(Punctuator::LBrace) => self.parse_block_statement(),
             Token::Punctuator(Punctuator::Semicolon) => {
                 self.lexer.next_token()?;
-                Ok(Statement::EmptyStatement { position: None })
+                Ok(Statement::EmptyStatement { position: self.span_from(&node_start) })
             }
             // Handle / and /= at statement start as regex literal expression
             Token::Punctuator(Punctuator::Slash) | Token::Punctuator(Punctuator::SlashEq) => {
@@ -433,6 +438,7 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_variable_declaration(&mut self) -> Result<Statement, JsError> {
+        let node_start = self.node_start();
         let kind = match self.lexer.next_token()? {
             Token::Keyword(Keyword::Let) => VariableKind::Let,
             Token::Keyword(Keyword::Const) => VariableKind::Const,
@@ -494,7 +500,7 @@ impl<'a> Parser<'a> {
         Ok(Statement::VariableDeclaration {
             kind,
             declarations,
-            position: None,
+            position: self.span_from(&node_start),
         })
     }
 
@@ -509,6 +515,7 @@ impl<'a> Parser<'a> {
         let saved_previous_line = self.lexer.previous_line;
         let saved_line_term = self.lexer.line_terminator_before_token;
         let saved_token = self.lexer.current_token.clone();
+        let saved_token_spans = self.lexer.token_spans();
 
         self.lexer.next_token()?; // consume "let"
         let next = self.lexer.peek_token()?.clone();
@@ -554,6 +561,7 @@ impl<'a> Parser<'a> {
         self.lexer.previous_line = saved_previous_line;
         self.lexer.line_terminator_before_token = saved_line_term;
         self.lexer.current_token = saved_token;
+        self.lexer.restore_token_spans(saved_token_spans);
 
         Ok(is_declaration)
     }
@@ -705,6 +713,7 @@ impl<'a> Parser<'a> {
                     (self.parse_pattern()?, false)
                 } else {
                     // Shorthand only valid for identifiers
+                    self.validate_identifier(&key)?;
                     (Pattern::Identifier(key.clone()), true)
                 };
 
@@ -798,6 +807,7 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_function_declaration(&mut self) -> Result<Statement, JsError> {
+        let node_start = self.node_start();
         self.expect_keyword(Keyword::Function)?;
 
         let is_generator = self.check_punctuator(Punctuator::Star)?;
@@ -856,13 +866,14 @@ impl<'a> Parser<'a> {
             body,
             is_async: false,
             is_generator,
-            position: None,
+            position: self.span_from(&node_start),
         })
     }
 
     /// Parse statement starting with 'async' keyword
     /// Handles: async function declaration, async arrow expression statement, or async as identifier
     fn parse_async_statement(&mut self) -> Result<Statement, JsError> {
+        let node_start = self.node_start();
         self.expect_keyword(Keyword::Async)?;
 
         // Peek next token to update line_terminator_before_token
@@ -880,6 +891,7 @@ impl<'a> Parser<'a> {
                 let start_line = self.lexer.line;
                 let start_col = self.lexer.column;
                 let start_token = self.lexer.current_token.clone();
+                let start_token_spans = self.lexer.token_spans();
 
                 // Clear token cache so next_token actually scans
                 self.lexer.current_token = None;
@@ -908,6 +920,7 @@ impl<'a> Parser<'a> {
                 self.lexer.line = start_line;
                 self.lexer.column = start_col;
                 self.lexer.current_token = start_token;
+                self.lexer.restore_token_spans(start_token_spans);
                 self.lexer.line_terminator_before_token = true;
 
                 if is_arrow {
@@ -924,9 +937,9 @@ impl<'a> Parser<'a> {
             return Ok(Statement::ExpressionStatement {
                 expression: Expression::Identifier {
                     name: "async".to_string(),
-                    position: None,
+                    position: self.token_span(),
                 },
-                position: None,
+                position: self.span_from(&node_start),
             });
         }
 
@@ -941,7 +954,7 @@ impl<'a> Parser<'a> {
         self.consume_semicolon()?;
         Ok(Statement::ExpressionStatement {
             expression: expr,
-            position: None,
+            position: self.span_from(&node_start),
         })
     }
 
@@ -953,6 +966,7 @@ impl<'a> Parser<'a> {
     /// Parse async expression after 'async' keyword has been consumed (for expression statements)
     /// Handles async arrow functions and async as identifier
     fn parse_async_expression_after_async(&mut self) -> Result<Expression, JsError> {
+        let node_start = self.node_start();
         if self.check_punctuator(Punctuator::LParen)? {
             // Async arrow function with parens: async (params) => body
             // Set async context for parameter parsing - 'await' should be reserved
@@ -960,9 +974,10 @@ impl<'a> Parser<'a> {
             let prev_async = self.in_async;
             self.in_async = true;
             let params = self.parse_parameters()?;
-            // Validate arrow parameters (duplicates and yield expressions)
+            // Validate arrow parameters (duplicates, yield and await expressions)
             self.validate_arrow_parameters(&params)?;
             self.validate_arrow_params_no_yield(&params)?;
+            self.validate_params_no_await(&params)?;
             // Check for line terminator before =>
             if self.check_punctuator(Punctuator::Arrow)? {
                 if self.lexer.line_terminator_before_token {
@@ -983,7 +998,7 @@ impl<'a> Parser<'a> {
                 params,
                 body,
                 is_async: true,
-                position: None,
+                position: self.span_from(&node_start),
             })
         } else if let Token::Identifier(name, _) = self.lexer.peek_token()?.clone() {
             // Could be async arrow function without parens: async x => body
@@ -1005,7 +1020,7 @@ impl<'a> Parser<'a> {
                     params: vec![Pattern::Identifier(name)],
                     body,
                     is_async: true,
-                    position: None,
+                    position: self.span_from(&node_start),
                 })
             } else {
                 // No arrow - this is 'async' as identifier followed by something else
@@ -1018,13 +1033,14 @@ impl<'a> Parser<'a> {
             // Return async as standalone identifier
             Ok(Expression::Identifier {
                 name: "async".to_string(),
-                position: None,
+                position: self.token_span(),
             })
         }
     }
 
     /// Parse async function declaration after 'async' keyword has been consumed
     fn parse_async_function_declaration_after_async(&mut self) -> Result<Statement, JsError> {
+        let node_start = self.node_start();
         if self.check_keyword(Keyword::Function)? {
             self.lexer.next_token()?;
 
@@ -1084,7 +1100,7 @@ impl<'a> Parser<'a> {
                 body,
                 is_async: true,
                 is_generator,
-                position: None,
+                position: self.span_from(&node_start),
             })
         } else {
             Err(syntax_error("Expected function after async", None))
@@ -1092,6 +1108,7 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_class_declaration(&mut self) -> Result<Statement, JsError> {
+        let node_start = self.node_start();
         self.expect_keyword(Keyword::Class)?;
 
         // All parts of a class are strict mode code, including the class name
@@ -1129,11 +1146,12 @@ impl<'a> Parser<'a> {
             name,
             super_class,
             body,
-            position: None,
+            position: self.span_from(&node_start),
         })
     }
 
     fn parse_class_body(&mut self) -> Result<Vec<ClassElement>, JsError> {
+        let node_start = self.node_start();
         self.expect_punctuator(Punctuator::LBrace)?;
         let mut elements = Vec::new();
         let mut has_constructor = false;
@@ -1172,6 +1190,7 @@ impl<'a> Parser<'a> {
                 let saved_column = self.lexer.column;
                 let saved_line_term = self.lexer.line_terminator_before_token;
                 let saved_token = self.lexer.current_token.clone();
+                let saved_token_spans = self.lexer.token_spans();
 
                 self.lexer.next_token()?;
                 let next = self.lexer.peek_token()?;
@@ -1261,6 +1280,7 @@ impl<'a> Parser<'a> {
                     self.lexer.column = saved_column;
                     self.lexer.line_terminator_before_token = saved_line_term;
                     self.lexer.current_token = saved_token;
+                    self.lexer.restore_token_spans(saved_token_spans);
                     false
                 } else {
                     // It's the static keyword
@@ -1343,6 +1363,7 @@ impl<'a> Parser<'a> {
                 let saved_previous_line = self.lexer.previous_line;
                 let saved_line_term = self.lexer.line_terminator_before_token;
                 let saved_token = self.lexer.current_token.clone();
+                let saved_token_spans = self.lexer.token_spans();
 
                 self.lexer.next_token()?;
                 // Check if followed by a valid property name
@@ -1380,6 +1401,7 @@ impl<'a> Parser<'a> {
                     self.lexer.previous_line = saved_previous_line;
                     self.lexer.line_terminator_before_token = saved_line_term;
                     self.lexer.current_token = saved_token;
+                    self.lexer.restore_token_spans(saved_token_spans);
                     (self.parse_property_name()?, false)
                 }
             } else if !is_generator && self.check_identifier("set")? {
@@ -1389,6 +1411,7 @@ impl<'a> Parser<'a> {
                 let saved_previous_line = self.lexer.previous_line;
                 let saved_line_term = self.lexer.line_terminator_before_token;
                 let saved_token = self.lexer.current_token.clone();
+                let saved_token_spans = self.lexer.token_spans();
 
                 self.lexer.next_token()?;
                 // Check if followed by a valid property name
@@ -1426,6 +1449,7 @@ impl<'a> Parser<'a> {
                     self.lexer.previous_line = saved_previous_line;
                     self.lexer.line_terminator_before_token = saved_line_term;
                     self.lexer.current_token = saved_token;
+                    self.lexer.restore_token_spans(saved_token_spans);
                     (self.parse_property_name()?, false)
                 }
             } else {
@@ -1637,7 +1661,7 @@ impl<'a> Parser<'a> {
                         body,
                         is_async,
                         is_generator,
-                        position: None,
+                        position: self.span_from(&node_start),
                     },
                     is_static,
                     is_private,
@@ -1761,6 +1785,7 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_class_expression(&mut self) -> Result<Expression, JsError> {
+        let node_start = self.node_start();
         self.expect_keyword(Keyword::Class)?;
 
         // All parts of a class are strict mode code, including the class name
@@ -1805,7 +1830,7 @@ impl<'a> Parser<'a> {
             name,
             super_class,
             body,
-            position: None,
+            position: self.span_from(&node_start),
         })
     }
 
@@ -1903,6 +1928,7 @@ impl<'a> Parser<'a> {
         let initial_column = self.lexer.column;
         let initial_line_term = self.lexer.line_terminator_before_token;
         let initial_token = self.lexer.current_token.clone();
+        let initial_token_spans = self.lexer.token_spans();
 
         while !self.check_punctuator(Punctuator::RBrace)? {
             let token = self.lexer.peek_token()?.clone();
@@ -1960,6 +1986,7 @@ impl<'a> Parser<'a> {
         self.lexer.column = initial_column;
         self.lexer.line_terminator_before_token = initial_line_term;
         self.lexer.current_token = initial_token;
+        self.lexer.restore_token_spans(initial_token_spans);
 
         Ok(())
     }
@@ -2044,6 +2071,7 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_return_statement(&mut self) -> Result<Statement, JsError> {
+        let node_start = self.node_start();
         // Return is only valid inside a function body, not in static blocks
         // Static blocks are NOT functions even when nested inside functions
         if self.function_depth == 0 || self.in_static_block {
@@ -2071,11 +2099,12 @@ impl<'a> Parser<'a> {
 
         Ok(Statement::ReturnStatement {
             argument,
-            position: None,
+            position: self.span_from(&node_start),
         })
     }
 
     fn parse_if_statement(&mut self) -> Result<Statement, JsError> {
+        let node_start = self.node_start();
         self.expect_keyword(Keyword::If)?;
         self.expect_punctuator(Punctuator::LParen)?;
         let test = self.parse_expression()?;
@@ -2099,11 +2128,12 @@ impl<'a> Parser<'a> {
             test,
             consequent,
             alternate,
-            position: None,
+            position: self.span_from(&node_start),
         })
     }
 
     fn parse_while_statement(&mut self) -> Result<Statement, JsError> {
+        let node_start = self.node_start();
         self.expect_keyword(Keyword::While)?;
         self.expect_punctuator(Punctuator::LParen)?;
         let test = self.parse_expression()?;
@@ -2119,11 +2149,12 @@ impl<'a> Parser<'a> {
         Ok(Statement::WhileStatement {
             test,
             body,
-            position: None,
+            position: self.span_from(&node_start),
         })
     }
 
     fn parse_do_while_statement(&mut self) -> Result<Statement, JsError> {
+        let node_start = self.node_start();
         self.expect_keyword(Keyword::Do)?;
         self.loop_depth += 1;
         let body = Box::new(self.parse_substatement()?);
@@ -2139,11 +2170,12 @@ impl<'a> Parser<'a> {
         Ok(Statement::DoWhileStatement {
             body,
             test,
-            position: None,
+            position: self.span_from(&node_start),
         })
     }
 
     fn parse_switch_statement(&mut self) -> Result<Statement, JsError> {
+        let node_start = self.node_start();
         use crate::ast::SwitchCase;
         self.expect_keyword(Keyword::Switch)?;
         self.expect_punctuator(Punctuator::LParen)?;
@@ -2204,7 +2236,7 @@ impl<'a> Parser<'a> {
         Ok(Statement::SwitchStatement {
             discriminant,
             cases,
-            position: None,
+            position: self.span_from(&node_start),
         })
     }
 
@@ -2272,6 +2304,7 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_with_statement(&mut self) -> Result<Statement, JsError> {
+        let node_start = self.node_start();
         self.expect_keyword(Keyword::With)?;
         // 'with' statements are not allowed in strict mode
         if self.strict_mode {
@@ -2289,17 +2322,19 @@ impl<'a> Parser<'a> {
         Ok(Statement::WithStatement {
             object,
             body,
-            position: None,
+            position: self.span_from(&node_start),
         })
     }
 
     fn parse_debugger_statement(&mut self) -> Result<Statement, JsError> {
+        let node_start = self.node_start();
         self.expect_keyword(Keyword::Debugger)?;
         self.consume_semicolon()?;
-        Ok(Statement::DebuggerStatement { position: None })
+        Ok(Statement::DebuggerStatement { position: self.span_from(&node_start) })
     }
 
     fn parse_export_declaration(&mut self) -> Result<Statement, JsError> {
+        let node_start = self.node_start();
         // Export is only allowed in module mode
         if !self.is_module {
             return Err(syntax_error(
@@ -2338,7 +2373,7 @@ impl<'a> Parser<'a> {
                 Ok(Statement::ExportAllDeclaration {
                     source,
                     exported,
-                    position: None,
+                    position: self.span_from(&node_start),
                 })
             }
             Token::Punctuator(Punctuator::LBrace) => {
@@ -2357,7 +2392,7 @@ impl<'a> Parser<'a> {
                     declaration: Some(Box::new(declaration)),
                     specifiers: vec![],
                     source: None,
-                    position: None,
+                    position: self.span_from(&node_start),
                 })
             }
             _ => Err(syntax_error("Unexpected token after export", self.last_position.clone())),
@@ -2365,6 +2400,7 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_export_default(&mut self) -> Result<Statement, JsError> {
+        let node_start = self.node_start();
         let token = self.lexer.peek_token()?.clone();
 
         let declaration = match token {
@@ -2482,11 +2518,12 @@ impl<'a> Parser<'a> {
 
         Ok(Statement::ExportDefaultDeclaration {
             declaration: Box::new(declaration),
-            position: None,
+            position: self.span_from(&node_start),
         })
     }
 
     fn parse_export_named_specifiers(&mut self) -> Result<Statement, JsError> {
+        let node_start = self.node_start();
         self.expect_punctuator(Punctuator::LBrace)?;
 
         let mut specifiers = Vec::new();
@@ -2523,11 +2560,12 @@ impl<'a> Parser<'a> {
             declaration: None,
             specifiers,
             source,
-            position: None,
+            position: self.span_from(&node_start),
         })
     }
 
     fn parse_import_declaration(&mut self) -> Result<Statement, JsError> {
+        let node_start = self.node_start();
         // Import declaration is only allowed in module mode
         if !self.is_module {
             return Err(syntax_error(
@@ -2547,7 +2585,7 @@ impl<'a> Parser<'a> {
             return Ok(Statement::ImportDeclaration {
                 specifiers: vec![],
                 source,
-                position: None,
+                position: self.span_from(&node_start),
             });
         }
 
@@ -2599,7 +2637,7 @@ impl<'a> Parser<'a> {
         Ok(Statement::ImportDeclaration {
             specifiers,
             source,
-            position: None,
+            position: self.span_from(&node_start),
         })
     }
 
@@ -2612,6 +2650,7 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_for_statement(&mut self) -> Result<Statement, JsError> {
+        let node_start = self.node_start();
         self.expect_keyword(Keyword::For)?;
 
         // Check for for-await-of: for await (... of ...)
@@ -2654,6 +2693,7 @@ impl<'a> Parser<'a> {
             let saved_previous_line = self.lexer.previous_line;
             let saved_line_term = self.lexer.line_terminator_before_token;
             let saved_token = self.lexer.current_token.clone();
+            let saved_token_spans = self.lexer.token_spans();
 
             self.lexer.next_token()?; // consume "let"
             let next = self.lexer.peek_token()?;
@@ -2672,6 +2712,7 @@ impl<'a> Parser<'a> {
             self.lexer.previous_line = saved_previous_line;
             self.lexer.line_terminator_before_token = saved_line_term;
             self.lexer.current_token = saved_token;
+            self.lexer.restore_token_spans(saved_token_spans);
 
             is_keyword
         } else if self.check_keyword(Keyword::Let)? && self.strict_mode {
@@ -2724,7 +2765,7 @@ impl<'a> Parser<'a> {
                     left: ForInOfLeft::VariableDeclaration { kind, id },
                     right,
                     body,
-                    position: None,
+                    position: self.span_from(&node_start),
                 });
             }
 
@@ -2752,7 +2793,7 @@ impl<'a> Parser<'a> {
                     right,
                     body,
                     r#await: is_await,
-                    position: None,
+                    position: self.span_from(&node_start),
                 });
             }
 
@@ -2846,7 +2887,7 @@ impl<'a> Parser<'a> {
                 left,
                 right,
                 body,
-                position: None,
+                position: self.span_from(&node_start),
             });
         }
 
@@ -2898,7 +2939,7 @@ impl<'a> Parser<'a> {
                 right,
                 body,
                 r#await: is_await,
-                position: None,
+                position: self.span_from(&node_start),
             });
         }
 
@@ -2918,6 +2959,7 @@ impl<'a> Parser<'a> {
 
     /// Parse the rest of a regular for loop after init is determined
     fn parse_regular_for(&mut self, init: Option<ForInit>) -> Result<Statement, JsError> {
+        let node_start = self.node_start();
         self.expect_punctuator(Punctuator::Semicolon)?;
 
         let test = if self.check_punctuator(Punctuator::Semicolon)? {
@@ -2975,11 +3017,12 @@ impl<'a> Parser<'a> {
             test,
             update,
             body,
-            position: None,
+            position: self.span_from(&node_start),
         })
     }
 
     fn parse_break_statement(&mut self) -> Result<Statement, JsError> {
+        let node_start = self.node_start();
         self.expect_keyword(Keyword::Break)?;
 
         // Peek to update line_terminator_before_token
@@ -3020,11 +3063,12 @@ impl<'a> Parser<'a> {
         self.consume_semicolon()?;
         Ok(Statement::BreakStatement {
             label,
-            position: None,
+            position: self.span_from(&node_start),
         })
     }
 
     fn parse_continue_statement(&mut self) -> Result<Statement, JsError> {
+        let node_start = self.node_start();
         self.expect_keyword(Keyword::Continue)?;
 
         // Peek to update line_terminator_before_token
@@ -3073,11 +3117,12 @@ impl<'a> Parser<'a> {
         self.consume_semicolon()?;
         Ok(Statement::ContinueStatement {
             label,
-            position: None,
+            position: self.span_from(&node_start),
         })
     }
 
     fn parse_throw_statement(&mut self) -> Result<Statement, JsError> {
+        let node_start = self.node_start();
         self.expect_keyword(Keyword::Throw)?;
 
         // Peek the next token to ensure line terminator state is updated
@@ -3098,11 +3143,12 @@ impl<'a> Parser<'a> {
         self.consume_semicolon()?;
         Ok(Statement::ThrowStatement {
             argument,
-            position: None,
+            position: self.span_from(&node_start),
         })
     }
 
     fn parse_try_statement(&mut self) -> Result<Statement, JsError> {
+        let node_start = self.node_start();
         self.expect_keyword(Keyword::Try)?;
         let block = self.parse_block_body()?;
 
@@ -3157,7 +3203,7 @@ impl<'a> Parser<'a> {
             block,
             handler,
             finalizer,
-            position: None,
+            position: self.span_from(&node_start),
         })
     }
 
@@ -3185,10 +3231,11 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_block_statement(&mut self) -> Result<Statement, JsError> {
+        let node_start = self.node_start();
         let body = self.parse_block_body()?;
         Ok(Statement::BlockStatement {
             body,
-            position: None,
+            position: self.span_from(&node_start),
         })
     }
 
@@ -3407,6 +3454,7 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_expression_statement(&mut self) -> Result<Statement, JsError> {
+        let node_start = self.node_start();
         // Check for labeled statement: identifier followed by colon
         // In non-strict mode, 'yield' can be used as a label (when not in generator)
         // In non-module, non-async contexts, 'await' can be used as a label
@@ -3431,6 +3479,7 @@ impl<'a> Parser<'a> {
             let saved_previous_line = self.lexer.previous_line;
             let saved_line_term = self.lexer.line_terminator_before_token;
             let saved_token = self.lexer.current_token.clone();
+            let saved_token_spans = self.lexer.token_spans();
 
             self.lexer.next_token()?; // consume identifier/yield
 
@@ -3482,6 +3531,7 @@ impl<'a> Parser<'a> {
                     let saved_col = self.lexer.column;
                     let saved_line_term = self.lexer.line_terminator_before_token;
                     let saved_tok = self.lexer.current_token.clone();
+                    let saved_tok_spans = self.lexer.token_spans();
 
                     self.lexer.next_token()?; // consume async
                     if !self.lexer.line_terminator_before_token && self.check_keyword(Keyword::Function)? {
@@ -3497,6 +3547,7 @@ impl<'a> Parser<'a> {
                     self.lexer.column = saved_col;
                     self.lexer.line_terminator_before_token = saved_line_term;
                     self.lexer.current_token = saved_tok;
+                    self.lexer.restore_token_spans(saved_tok_spans);
                 }
 
                 // Also check for nested labels that might wrap an iteration
@@ -3557,7 +3608,7 @@ impl<'a> Parser<'a> {
                 return Ok(Statement::LabeledStatement {
                     label: name,
                     body,
-                    position: None,
+                    position: self.span_from(&node_start),
                 });
             }
 
@@ -3568,6 +3619,7 @@ impl<'a> Parser<'a> {
             self.lexer.previous_line = saved_previous_line;
             self.lexer.line_terminator_before_token = saved_line_term;
             self.lexer.current_token = saved_token;
+            self.lexer.restore_token_spans(saved_token_spans);
         }
 
         let expression = self.parse_expression()?;
@@ -3575,11 +3627,12 @@ impl<'a> Parser<'a> {
 
         Ok(Statement::ExpressionStatement {
             expression,
-            position: None,
+            position: self.span_from(&node_start),
         })
     }
 
     fn parse_expression(&mut self) -> Result<Expression, JsError> {
+        let node_start = self.node_start();
         // Expression can be comma-separated (SequenceExpression)
         let mut expr = self.parse_assignment_expression()?;
 
@@ -3591,7 +3644,7 @@ impl<'a> Parser<'a> {
             }
             expr = Expression::SequenceExpression {
                 expressions,
-                position: None,
+                position: self.span_from(&node_start),
             };
         }
 
@@ -3599,6 +3652,7 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_assignment_expression(&mut self) -> Result<Expression, JsError> {
+        let node_start = self.node_start();
         // YieldExpression is at AssignmentExpression level, NOT at UnaryExpression level
         // This ensures `void yield` fails (yield cannot be identifier in generator)
         if self.in_generator && self.check_keyword(Keyword::Yield)? {
@@ -3620,7 +3674,7 @@ impl<'a> Parser<'a> {
                     params: vec![Pattern::Identifier(name.clone())],
                     body,
                     is_async: false,
-                    position: None,
+                    position: self.span_from(&node_start),
                 });
             }
         }
@@ -3633,7 +3687,7 @@ impl<'a> Parser<'a> {
                 left,
                 operator: op,
                 right,
-                position: None,
+                position: self.span_from(&node_start),
             });
         }
 
@@ -3643,6 +3697,7 @@ impl<'a> Parser<'a> {
     /// Parse a YieldExpression
     /// This is at AssignmentExpression level, NOT UnaryExpression level
     fn parse_yield_expression(&mut self) -> Result<Expression, JsError> {
+        let node_start = self.node_start();
         self.expect_keyword(Keyword::Yield)?;
 
         // Check for yield* (delegate)
@@ -3680,7 +3735,7 @@ impl<'a> Parser<'a> {
         Ok(Expression::YieldExpression {
             argument,
             delegate,
-            position: None,
+            position: self.span_from(&node_start),
         })
     }
 
@@ -3689,6 +3744,7 @@ impl<'a> Parser<'a> {
     /// but need to continue parsing `= 1` and any subsequent parts.
     /// Also handles binary operators and conditional expressions.
     fn finish_expression_from_lhs(&mut self, lhs: Expression) -> Result<Expression, JsError> {
+        let node_start = self.node_start();
         // We're in for loop init, so set the flag to disallow 'in' as relational operator
         let prev_in_for_init = self.in_for_init;
         self.in_for_init = true;
@@ -3721,7 +3777,7 @@ impl<'a> Parser<'a> {
                 test: Box::new(expr),
                 consequent,
                 alternate,
-                position: None,
+                position: self.span_from(&node_start),
             }
         } else {
             expr
@@ -3736,7 +3792,7 @@ impl<'a> Parser<'a> {
                 left,
                 operator: op,
                 right,
-                position: None,
+                position: self.span_from(&node_start),
             }
         } else {
             expr
@@ -3758,7 +3814,7 @@ impl<'a> Parser<'a> {
             }
             Ok(Expression::SequenceExpression {
                 expressions,
-                position: None,
+                position: self.span_from(&node_start),
             })
         } else {
             Ok(expr)
@@ -3768,6 +3824,7 @@ impl<'a> Parser<'a> {
     // Helper functions to continue parsing binary operators from an existing LHS
 
     fn continue_exponentiation_from_lhs(&mut self, lhs: Expression) -> Result<Expression, JsError> {
+        let node_start = self.node_start();
         if self.check_punctuator(Punctuator::StarStar)? {
             self.lexer.next_token()?;
             let right = self.parse_exponentiation_expression()?;
@@ -3775,7 +3832,7 @@ impl<'a> Parser<'a> {
                 left: Box::new(lhs),
                 operator: BinaryOperator::Exp,
                 right: Box::new(right),
-                position: None,
+                position: self.span_from(&node_start),
             })
         } else {
             Ok(lhs)
@@ -3783,6 +3840,7 @@ impl<'a> Parser<'a> {
     }
 
     fn continue_multiplicative_from_lhs(&mut self, mut left: Expression) -> Result<Expression, JsError> {
+        let node_start = self.node_start();
         loop {
             let op = match self.lexer.peek_token()? {
                 Token::Punctuator(Punctuator::Star) => BinaryOperator::Mul,
@@ -3796,13 +3854,14 @@ impl<'a> Parser<'a> {
                 left: Box::new(left),
                 operator: op,
                 right: Box::new(right),
-                position: None,
+                position: self.span_from(&node_start),
             };
         }
         Ok(left)
     }
 
     fn continue_additive_from_lhs(&mut self, mut left: Expression) -> Result<Expression, JsError> {
+        let node_start = self.node_start();
         loop {
             let op = match self.lexer.peek_token()? {
                 Token::Punctuator(Punctuator::Plus) => BinaryOperator::Add,
@@ -3815,13 +3874,14 @@ impl<'a> Parser<'a> {
                 left: Box::new(left),
                 operator: op,
                 right: Box::new(right),
-                position: None,
+                position: self.span_from(&node_start),
             };
         }
         Ok(left)
     }
 
     fn continue_shift_from_lhs(&mut self, mut left: Expression) -> Result<Expression, JsError> {
+        let node_start = self.node_start();
         loop {
             let op = match self.lexer.peek_token()? {
                 Token::Punctuator(Punctuator::LtLt) => BinaryOperator::LeftShift,
@@ -3835,13 +3895,14 @@ impl<'a> Parser<'a> {
                 left: Box::new(left),
                 operator: op,
                 right: Box::new(right),
-                position: None,
+                position: self.span_from(&node_start),
             };
         }
         Ok(left)
     }
 
     fn continue_relational_from_lhs(&mut self, mut left: Expression) -> Result<Expression, JsError> {
+        let node_start = self.node_start();
         loop {
             let op = match self.lexer.peek_token()? {
                 Token::Punctuator(Punctuator::Lt) => BinaryOperator::Lt,
@@ -3859,13 +3920,14 @@ impl<'a> Parser<'a> {
                 left: Box::new(left),
                 operator: op,
                 right: Box::new(right),
-                position: None,
+                position: self.span_from(&node_start),
             };
         }
         Ok(left)
     }
 
     fn continue_equality_from_lhs(&mut self, mut left: Expression) -> Result<Expression, JsError> {
+        let node_start = self.node_start();
         loop {
             let op = match self.lexer.peek_token()? {
                 Token::Punctuator(Punctuator::EqEq) => BinaryOperator::Eq,
@@ -3880,13 +3942,14 @@ impl<'a> Parser<'a> {
                 left: Box::new(left),
                 operator: op,
                 right: Box::new(right),
-                position: None,
+                position: self.span_from(&node_start),
             };
         }
         Ok(left)
     }
 
     fn continue_bitwise_and_from_lhs(&mut self, mut left: Expression) -> Result<Expression, JsError> {
+        let node_start = self.node_start();
         while self.check_punctuator(Punctuator::And)? {
             self.lexer.next_token()?;
             let right = self.parse_equality_expression()?;
@@ -3894,13 +3957,14 @@ impl<'a> Parser<'a> {
                 left: Box::new(left),
                 operator: BinaryOperator::BitwiseAnd,
                 right: Box::new(right),
-                position: None,
+                position: self.span_from(&node_start),
             };
         }
         Ok(left)
     }
 
     fn continue_bitwise_xor_from_lhs(&mut self, mut left: Expression) -> Result<Expression, JsError> {
+        let node_start = self.node_start();
         while self.check_punctuator(Punctuator::Xor)? {
             self.lexer.next_token()?;
             let right = self.parse_bitwise_and_expression()?;
@@ -3908,13 +3972,14 @@ impl<'a> Parser<'a> {
                 left: Box::new(left),
                 operator: BinaryOperator::BitwiseXor,
                 right: Box::new(right),
-                position: None,
+                position: self.span_from(&node_start),
             };
         }
         Ok(left)
     }
 
     fn continue_bitwise_or_from_lhs(&mut self, mut left: Expression) -> Result<Expression, JsError> {
+        let node_start = self.node_start();
         while self.check_punctuator(Punctuator::Or)? {
             self.lexer.next_token()?;
             let right = self.parse_bitwise_xor_expression()?;
@@ -3922,13 +3987,14 @@ impl<'a> Parser<'a> {
                 left: Box::new(left),
                 operator: BinaryOperator::BitwiseOr,
                 right: Box::new(right),
-                position: None,
+                position: self.span_from(&node_start),
             };
         }
         Ok(left)
     }
 
     fn continue_logical_and_from_lhs(&mut self, mut left: Expression) -> Result<Expression, JsError> {
+        let node_start = self.node_start();
         while self.check_punctuator(Punctuator::AndAnd)? {
             self.lexer.next_token()?;
             let right = self.parse_bitwise_or_expression()?;
@@ -3936,13 +4002,14 @@ impl<'a> Parser<'a> {
                 left: Box::new(left),
                 operator: LogicalOperator::And,
                 right: Box::new(right),
-                position: None,
+                position: self.span_from(&node_start),
             };
         }
         Ok(left)
     }
 
     fn continue_logical_or_from_lhs(&mut self, mut left: Expression) -> Result<Expression, JsError> {
+        let node_start = self.node_start();
         while self.check_punctuator(Punctuator::OrOr)? {
             self.lexer.next_token()?;
             let right = self.parse_logical_and_expression()?;
@@ -3950,13 +4017,14 @@ impl<'a> Parser<'a> {
                 left: Box::new(left),
                 operator: LogicalOperator::Or,
                 right: Box::new(right),
-                position: None,
+                position: self.span_from(&node_start),
             };
         }
         Ok(left)
     }
 
     fn continue_nullish_from_lhs(&mut self, mut left: Expression) -> Result<Expression, JsError> {
+        let node_start = self.node_start();
         while self.check_punctuator(Punctuator::NullishCoalesce)? {
             self.lexer.next_token()?;
             let right = self.parse_logical_or_expression()?;
@@ -3964,7 +4032,7 @@ impl<'a> Parser<'a> {
                 left: Box::new(left),
                 operator: LogicalOperator::NullishCoalesce,
                 right: Box::new(right),
-                position: None,
+                position: self.span_from(&node_start),
             };
         }
         Ok(left)
@@ -3995,10 +4063,6 @@ impl<'a> Parser<'a> {
                 }
                 Ok(AssignmentTarget::Member(Box::new(expr)))
             }
-            Expression::CallExpression { .. } => {
-                // Call expressions are never valid assignment targets
-                Err(syntax_error("Call expression cannot be assigned", None))
-            }
             // Handle array destructuring: [a, b] = value
             Expression::ArrayExpression { .. } => {
                 let pattern = self.expression_to_pattern(expr)?;
@@ -4037,28 +4101,26 @@ impl<'a> Parser<'a> {
                     // Parenthesized object/array literals cannot be destructuring targets
                     // ({x}) = y and ([x]) = y are invalid
                     Expression::ObjectExpression { .. } | Expression::ArrayExpression { .. } => {
-                        Err(syntax_error("Invalid left-hand side in assignment", None))
+                        Err(self.invalid_assignment_target())
                     }
                     // Nested parentheses: recurse
                     Expression::ParenthesizedExpression { .. } => {
                         self.expression_to_assignment_target(*expression)
                     }
-                    // Sequence expressions in parentheses can be valid if last is assignable
-                    Expression::SequenceExpression { expressions, .. } => {
-                        if let Some(last) = expressions.into_iter().last() {
-                            self.expression_to_assignment_target(last)
-                        } else {
-                            Err(syntax_error("Invalid assignment target", None))
-                        }
-                    }
-                    // All other parenthesized expressions are invalid
-                    _ => Err(syntax_error("Invalid left-hand side in assignment", None)),
+                    // All other parenthesized expressions are invalid, including
+                    // sequences: (a, b) = 1
+                    _ => Err(self.invalid_assignment_target()),
                 }
             }
-            _ => Err(syntax_error("Invalid assignment target", None)),
+            // Literals, calls, operators and every other non-reference are invalid
+            _ => Err(self.invalid_assignment_target()),
         }
     }
 
+    fn invalid_assignment_target(&self) -> JsError {
+        syntax_error("Invalid left-hand side in assignment", self.last_position.clone())
+    }
+
     fn check_assignment_operator(&mut self) -> Result<Option<AssignmentOperator>, JsError> {
         let op = match self.lexer.peek_token()? {
             Token::Punctuator(Punctuator::Assign) => Some(AssignmentOperator::Assign),
@@ -4083,6 +4145,7 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_conditional_expression(&mut self) -> Result<Expression, JsError> {
+        let node_start = self.node_start();
         let test = self.parse_nullish_coalescing_expression()?;
 
         if self.check_punctuator(Punctuator::Question)? {
@@ -4099,7 +4162,7 @@ impl<'a> Parser<'a> {
                 test: Box::new(test),
                 consequent,
                 alternate,
-                position: None,
+                position: self.span_from(&node_start),
             });
         }
 
@@ -4107,6 +4170,7 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_nullish_coalescing_expression(&mut self) -> Result<Expression, JsError> {
+        let node_start = self.node_start();
         let mut left = self.parse_logical_or_expression()?;
 
         while self.check_punctuator(Punctuator::NullishCoalesce)? {
@@ -4130,7 +4194,7 @@ impl<'a> Parser<'a> {
                 left: Box::new(left),
                 operator: LogicalOperator::NullishCoalesce,
                 right: Box::new(right),
-                position: None,
+                position: self.span_from(&node_start),
             };
         }
 
@@ -4148,6 +4212,7 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_logical_or_expression(&mut self) -> Result<Expression, JsError> {
+        let node_start = self.node_start();
         let mut left = self.parse_logical_and_expression()?;
 
         while self.check_punctuator(Punctuator::OrOr)? {
@@ -4157,7 +4222,7 @@ impl<'a> Parser<'a> {
                 left: Box::new(left),
                 operator: LogicalOperator::Or,
                 right: Box::new(right),
-                position: None,
+                position: self.span_from(&node_start),
             };
         }
 
@@ -4165,6 +4230,7 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_logical_and_expression(&mut self) -> Result<Expression, JsError> {
+        let node_start = self.node_start();
         let mut left = self.parse_bitwise_or_expression()?;
 
         while self.check_punctuator(Punctuator::AndAnd)? {
@@ -4174,7 +4240,7 @@ impl<'a> Parser<'a> {
                 left: Box::new(left),
                 operator: LogicalOperator::And,
                 right: Box::new(right),
-                position: None,
+                position: self.span_from(&node_start),
             };
         }
 
@@ -4182,6 +4248,7 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_bitwise_or_expression(&mut self) -> Result<Expression, JsError> {
+        let node_start = self.node_start();
         let mut left = self.parse_bitwise_xor_expression()?;
 
         while self.check_punctuator(Punctuator::Or)? {
@@ -4191,7 +4258,7 @@ impl<'a> Parser<'a> {
                 left: Box::new(left),
                 operator: BinaryOperator::BitwiseOr,
                 right: Box::new(right),
-                position: None,
+                position: self.span_from(&node_start),
             };
         }
 
@@ -4199,6 +4266,7 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_bitwise_xor_expression(&mut self) -> Result<Expression, JsError> {
+        let node_start = self.node_start();
         let mut left = self.parse_bitwise_and_expression()?;
 
         while self.check_punctuator(Punctuator::Xor)? {
@@ -4208,7 +4276,7 @@ impl<'a> Parser<'a> {
                 left: Box::new(left),
                 operator: BinaryOperator::BitwiseXor,
                 right: Box::new(right),
-                position: None,
+                position: self.span_from(&node_start),
             };
         }
 
@@ -4216,6 +4284,7 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_bitwise_and_expression(&mut self) -> Result<Expression, JsError> {
+        let node_start = self.node_start();
         let mut left = self.parse_equality_expression()?;
 
         while self.check_punctuator(Punctuator::And)? {
@@ -4225,7 +4294,7 @@ impl<'a> Parser<'a> {
                 left: Box::new(left),
                 operator: BinaryOperator::BitwiseAnd,
                 right: Box::new(right),
-                position: None,
+                position: self.span_from(&node_start),
             };
         }
 
@@ -4233,6 +4302,7 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_equality_expression(&mut self) -> Result<Expression, JsError> {
+        let node_start = self.node_start();
         let mut left = self.parse_relational_expression()?;
 
         loop {
@@ -4249,7 +4319,7 @@ impl<'a> Parser<'a> {
                 left: Box::new(left),
                 operator: op,
                 right: Box::new(right),
-                position: None,
+                position: self.span_from(&node_start),
             };
         }
 
@@ -4257,6 +4327,7 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_relational_expression(&mut self) -> Result<Expression, JsError> {
+        let node_start = self.node_start();
         // Handle private field presence check: #field in expr
         // This is a special case where the left side is just a PrivateIdentifier
         let mut left = if self.check_private_identifier()? && !self.in_for_init {
@@ -4281,17 +4352,17 @@ impl<'a> Parser<'a> {
                 return Ok(Expression::BinaryExpression {
                     left: Box::new(Expression::PrivateIdentifier {
                         name: priv_name,
-                        position: None,
+                        position: self.span_from(&node_start),
                     }),
                     operator: BinaryOperator::In,
                     right: Box::new(right),
-                    position: None,
+                    position: self.span_from(&node_start),
                 });
             } else {
                 // Not followed by 'in', create private identifier expression
                 Expression::PrivateIdentifier {
                     name: priv_name,
-                    position: None,
+                    position: self.span_from(&node_start),
                 }
             }
         } else {
@@ -4315,7 +4386,7 @@ impl<'a> Parser<'a> {
                 left: Box::new(left),
                 operator: op,
                 right: Box::new(right),
-                position: None,
+                position: self.span_from(&node_start),
             };
         }
 
@@ -4323,6 +4394,7 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_shift_expression(&mut self) -> Result<Expression, JsError> {
+        let node_start = self.node_start();
         let mut left = self.parse_additive_expression()?;
 
         loop {
@@ -4338,7 +4410,7 @@ impl<'a> Parser<'a> {
                 left: Box::new(left),
                 operator: op,
                 right: Box::new(right),
-                position: None,
+                position: self.span_from(&node_start),
             };
         }
 
@@ -4346,6 +4418,7 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_additive_expression(&mut self) -> Result<Expression, JsError> {
+        let node_start = self.node_start();
         let mut left = self.parse_multiplicative_expression()?;
 
         loop {
@@ -4360,7 +4433,7 @@ impl<'a> Parser<'a> {
                 left: Box::new(left),
                 operator: op,
                 right: Box::new(right),
-                position: None,
+                position: self.span_from(&node_start),
             };
         }
 
@@ -4368,6 +4441,7 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_multiplicative_expression(&mut self) -> Result<Expression, JsError> {
+        let node_start = self.node_start();
         let mut left = self.parse_exponentiation_expression()?;
 
         loop {
@@ -4396,7 +4470,7 @@ impl<'a> Parser<'a> {
                 left: Box::new(left),
                 operator: op,
                 right: Box::new(right),
-                position: None,
+                position: self.span_from(&node_start),
             };
         }
 
@@ -4405,6 +4479,7 @@ impl<'a> Parser<'a> {
 
     /// Parse exponentiation expression (**) which is right-associative
     fn parse_exponentiation_expression(&mut self) -> Result<Expression, JsError> {
+        let node_start = self.node_start();
         let left = self.parse_unary_expression()?;
 
         // Exponentiation is right-associative: a ** b ** c = a ** (b ** c)
@@ -4425,7 +4500,7 @@ impl<'a> Parser<'a> {
                 left: Box::new(left),
                 operator: BinaryOperator::Exp,
                 right: Box::new(right),
-                position: None,
+                position: self.span_from(&node_start),
             });
         }
 
@@ -4443,6 +4518,7 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_unary_expression(&mut self) -> Result<Expression, JsError> {
+        let node_start = self.node_start();
         let op = match self.lexer.peek_token()? {
             Token::Punctuator(Punctuator::Not) => Some(UnaryOperator::Not),
             Token::Punctuator(Punctuator::Minus) => Some(UnaryOperator::Minus),
@@ -4479,7 +4555,7 @@ impl<'a> Parser<'a> {
                 operator,
                 argument,
                 prefix: true,
-                position: None,
+                position: self.span_from(&node_start),
             });
         }
 
@@ -4490,7 +4566,7 @@ impl<'a> Parser<'a> {
             let argument = Box::new(self.parse_unary_expression()?);
             return Ok(Expression::AwaitExpression {
                 argument,
-                position: None,
+                position: self.span_from(&node_start),
             });
         }
 
@@ -4501,6 +4577,7 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_update_expression(&mut self) -> Result<Expression, JsError> {
+        let node_start = self.node_start();
         // Prefix ++/--
         if self.check_punctuator(Punctuator::PlusPlus)? {
             self.lexer.next_token()?;
@@ -4511,7 +4588,7 @@ impl<'a> Parser<'a> {
                 operator: UpdateOperator::Increment,
                 argument,
                 prefix: true,
-                position: None,
+                position: self.span_from(&node_start),
             });
         }
 
@@ -4524,7 +4601,7 @@ impl<'a> Parser<'a> {
                 operator: UpdateOperator::Decrement,
                 argument,
                 prefix: true,
-                position: None,
+                position: self.span_from(&node_start),
             });
         }
 
@@ -4540,7 +4617,7 @@ impl<'a> Parser<'a> {
                 operator: UpdateOperator::Increment,
                 argument: Box::new(expr),
                 prefix: false,
-                position: None,
+                position: self.span_from(&node_start),
             });
         }
 
@@ -4552,7 +4629,7 @@ impl<'a> Parser<'a> {
                 operator: UpdateOperator::Decrement,
                 argument: Box::new(expr),
                 prefix: false,
-                position: None,
+                position: self.span_from(&node_start),
             });
         }
 
@@ -4640,6 +4717,7 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_left_hand_side_expression(&mut self) -> Result<Expression, JsError> {
+        let node_start = self.node_start();
         let mut expr = if self.check_keyword(Keyword::New)? {
             self.parse_new_expression()?
         } else {
@@ -4666,14 +4744,14 @@ impl<'a> Parser<'a> {
                     self.validate_private_name_reference(&name)?;
                     Expression::Identifier {
                         name: format!("#{}", name),
-                        position: None,
+                        position: self.token_span(),
                     }
                 } else {
                     // IdentifierName only (not strings or numbers) after dot
                     let name = self.expect_identifier_name()?;
                     Expression::Identifier {
                         name,
-                        position: None,
+                        position: self.span_from(&node_start),
                     }
                 };
                 expr = Expression::MemberExpression {
@@ -4681,7 +4759,7 @@ impl<'a> Parser<'a> {
                     property: Box::new(property),
                     computed: false,
                     optional: false,
-                    position: None,
+                    position: self.span_from(&node_start),
                 };
             } else if self.check_punctuator(Punctuator::OptionalChain)? {
                 self.lexer.next_token()?;
@@ -4695,7 +4773,7 @@ impl<'a> Parser<'a> {
                         callee: Box::new(expr),
                         arguments,
                         optional: true,
-                        position: None,
+                        position: self.span_from(&node_start),
                     };
                 } else if self.check_punctuator(Punctuator::LBracket)? {
                     // Optional computed property: obj?.[expr]
@@ -4710,7 +4788,7 @@ impl<'a> Parser<'a> {
                         property,
                         computed: true,
                         optional: true,
-                        position: None,
+                        position: self.span_from(&node_start),
                     };
                 } else if self.check_private_identifier()? {
                     // Optional private field: obj?.#prop
@@ -4719,28 +4797,28 @@ impl<'a> Parser<'a> {
                     self.validate_private_name_reference(&name)?;
                     let property = Expression::Identifier {
                         name: format!("#{}", name),
-                        position: None,
+                        position: self.token_span(),
                     };
                     expr = Expression::MemberExpression {
                         object: Box::new(expr),
                         property: Box::new(property),
                         computed: false,
                         optional: true,
-                        position: None,
+                        position: self.span_from(&node_start),
                     };
                 } else {
                     // Optional member access: obj?.prop
                     let name = self.expect_property_name()?;
                     let property = Expression::Identifier {
                         name,
-                        position: None,
+                        position: self.span_from(&node_start),
                     };
                     expr = Expression::MemberExpression {
                         object: Box::new(expr),
                         property: Box::new(property),
                         computed: false,
                         optional: true,
-                        position: None,
+                        position: self.span_from(&node_start),
                     };
                 }
             } else if self.check_punctuator(Punctuator::LBracket)? {
@@ -4756,7 +4834,7 @@ impl<'a> Parser<'a> {
                     property,
                     computed: true,
                     optional: false,
-                    position: None,
+                    position: self.span_from(&node_start),
                 };
             } else if self.check_punctuator(Punctuator::LParen)? {
                 // super() calls are only allowed in constructors
@@ -4778,7 +4856,7 @@ impl<'a> Parser<'a> {
                     callee: Box::new(expr),
                     arguments,
                     optional: false,
-                    position: None,
+                    position: self.span_from(&node_start),
                 };
             } else if matches!(self.lexer.peek_token()?, Token::TemplateLiteral(_, _)) {
                 // Tagged template literal: tag`template`
@@ -4798,12 +4876,12 @@ impl<'a> Parser<'a> {
                             tail: true,
                         }],
                         expressions: vec![],
-                        position: None,
+                        position: self.span_from(&node_start),
                     };
                     expr = Expression::TaggedTemplateExpression {
                         tag: Box::new(expr),
                         quasi: Box::new(quasi),
-                        position: None,
+                        position: self.span_from(&node_start),
                     };
                 }
             } else if matches!(self.lexer.peek_token()?, Token::TemplateHead(_, _)) {
@@ -4821,7 +4899,7 @@ impl<'a> Parser<'a> {
                     expr = Expression::TaggedTemplateExpression {
                         tag: Box::new(expr),
                         quasi: Box::new(quasi),
-                        position: None,
+                        position: self.span_from(&node_start),
                     };
                 }
             } else {
@@ -4833,6 +4911,7 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_new_expression(&mut self) -> Result<Expression, JsError> {
+        let node_start = self.node_start();
         self.expect_keyword(Keyword::New)?;
 
         // Check for new.target meta property
@@ -4861,7 +4940,7 @@ impl<'a> Parser<'a> {
                         return Ok(Expression::MetaProperty {
                             meta: "new".to_string(),
                             property: "target".to_string(),
-                            position: None,
+                            position: self.span_from(&node_start),
                         });
                     } else {
                         return Err(syntax_error("Expected 'target' after 'new.'", None));
@@ -4891,11 +4970,12 @@ impl<'a> Parser<'a> {
         Ok(Expression::NewExpression {
             callee,
             arguments,
-            position: None,
+            position: self.span_from(&node_start),
         })
     }
 
     fn parse_member_expression_without_call(&mut self) -> Result<Expression, JsError> {
+        let node_start = self.node_start();
         let mut expr = if self.check_keyword(Keyword::New)? {
             self.parse_new_expression()?
         } else {
@@ -4913,14 +4993,14 @@ impl<'a> Parser<'a> {
                     self.validate_private_name_reference(&name)?;
                     Expression::Identifier {
                         name: format!("#{}", name),
-                        position: None,
+                        position: self.token_span(),
                     }
                 } else {
                     // IdentifierName only (not strings or numbers) after dot
                     let name = self.expect_identifier_name()?;
                     Expression::Identifier {
                         name,
-                        position: None,
+                        position: self.span_from(&node_start),
                     }
                 };
                 expr = Expression::MemberExpression {
@@ -4928,7 +5008,7 @@ impl<'a> Parser<'a> {
                     property: Box::new(property),
                     computed: false,
                     optional: false,
-                    position: None,
+                    position: self.span_from(&node_start),
                 };
             } else if self.check_punctuator(Punctuator::LBracket)? {
                 // Computed property access: obj[expr] - 'in' is always allowed
@@ -4943,7 +5023,7 @@ impl<'a> Parser<'a> {
                     property,
                     computed: true,
                     optional: false,
-                    position: None,
+                    position: self.span_from(&node_start),
                 };
             } else {
                 // Do NOT parse LParen here - that's for the NewExpression's arguments
@@ -4955,6 +5035,7 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_arguments(&mut self) -> Result<Vec<Expression>, JsError> {
+        let node_start = self.node_start();
         self.expect_punctuator(Punctuator::LParen)?;
         let mut args = Vec::new();
 
@@ -4964,7 +5045,7 @@ impl<'a> Parser<'a> {
                 let expr = self.parse_assignment_expression()?;
                 args.push(Expression::SpreadElement {
                     argument: Box::new(expr),
-                    position: None,
+                    position: self.span_from(&node_start),
                 });
             } else {
                 args.push(self.parse_assignment_expression()?);
@@ -4981,6 +5062,7 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_primary_expression(&mut self) -> Result<Expression, JsError> {
+        let node_start = self.node_start();
         let token = self.lexer.peek_token()?.clone();
 
         match token {
@@ -4991,14 +5073,14 @@ impl<'a> Parser<'a> {
                 self.validate_identifier(&name)?;
                 Ok(Expression::Identifier {
                     name,
-                    position: None,
+                    position: self.span_from(&node_start),
                 })
             }
             Token::Number(n) => {
                 self.lexer.next_token()?;
                 Ok(Expression::Literal {
                     value: Literal::Number(n),
-                    position: None,
+                    position: self.token_span(),
                 })
             }
             Token::LegacyOctalLiteral(n) => {
@@ -5011,7 +5093,7 @@ impl<'a> Parser<'a> {
                 self.lexer.next_token()?;
                 Ok(Expression::Literal {
                     value: Literal::Number(n),
-                    position: None,
+                    position: self.token_span(),
                 })
             }
             Token::NonOctalDecimalLiteral(n) => {
@@ -5024,14 +5106,14 @@ impl<'a> Parser<'a> {
                 self.lexer.next_token()?;
                 Ok(Expression::Literal {
                     value: Literal::Number(n),
-                    position: None,
+                    position: self.token_span(),
                 })
             }
             Token::String(s) => {
                 self.lexer.next_token()?;
                 Ok(Expression::Literal {
                     value: Literal::String(s),
-                    position: None,
+                    position: self.token_span(),
                 })
             }
             Token::LegacyEscapeString(s) => {
@@ -5044,7 +5126,7 @@ impl<'a> Parser<'a> {
                 self.lexer.next_token()?;
                 Ok(Expression::Literal {
                     value: Literal::String(s),
-                    position: None,
+                    position: self.token_span(),
                 })
             }
             Token::TemplateLiteral(s, has_invalid_escape) => {
@@ -5063,7 +5145,7 @@ impl<'a> Parser<'a> {
                         tail: true,
                     }],
                     expressions: vec![],
-                    position: None,
+                    position: self.span_from(&node_start),
                 })
             }
             Token::TemplateHead(s, has_invalid_escape) => {
@@ -5081,28 +5163,28 @@ impl<'a> Parser<'a> {
                 self.lexer.next_token()?;
                 Ok(Expression::Literal {
                     value: Literal::Boolean(true),
-                    position: None,
+                    position: self.token_span(),
                 })
             }
             Token::Keyword(Keyword::False) => {
                 self.lexer.next_token()?;
                 Ok(Expression::Literal {
                     value: Literal::Boolean(false),
-                    position: None,
+                    position: self.token_span(),
                 })
             }
             Token::Keyword(Keyword::Null) => {
                 self.lexer.next_token()?;
                 Ok(Expression::Literal {
                     value: Literal::Null,
-                    position: None,
+                    position: self.token_span(),
                 })
             }
             // Note: 'undefined' is NOT a keyword - it's handled as an identifier
             // and resolved at runtime from the global scope
             Token::Keyword(Keyword::This) => {
                 self.lexer.next_token()?;
-                Ok(Expression::ThisExpression { position: None })
+                Ok(Expression::ThisExpression { position: self.span_from(&node_start) })
             }
             Token::Keyword(Keyword::Super) => {
                 self.lexer.next_token()?;
@@ -5113,7 +5195,7 @@ impl<'a> Parser<'a> {
                         self.last_position.clone(),
                     ));
                 }
-                Ok(Expression::SuperExpression { position: None })
+                Ok(Expression::SuperExpression { position: self.span_from(&node_start) })
             }
             Token::Keyword(Keyword::Function) => self.parse_function_expression(),
             Token::Keyword(Keyword::Async) => self.parse_async_function_expression(),
@@ -5137,7 +5219,7 @@ impl<'a> Parser<'a> {
                 }
                 Ok(Expression::Identifier {
                     name: "yield".to_string(),
-                    position: None,
+                    position: self.token_span(),
                 })
             }
             Token::Keyword(Keyword::Let) => {
@@ -5151,7 +5233,7 @@ impl<'a> Parser<'a> {
                 }
                 Ok(Expression::Identifier {
                     name: "let".to_string(),
-                    position: None,
+                    position: self.token_span(),
                 })
             }
             Token::Keyword(Keyword::Static) => {
@@ -5165,7 +5247,7 @@ impl<'a> Parser<'a> {
                 }
                 Ok(Expression::Identifier {
                     name: "static".to_string(),
-                    position: None,
+                    position: self.token_span(),
                 })
             }
             // 'await' is an identifier outside async functions and static blocks
@@ -5173,14 +5255,14 @@ impl<'a> Parser<'a> {
                 self.lexer.next_token()?;
                 Ok(Expression::Identifier {
                     name: "await".to_string(),
-                    position: None,
+                    position: self.token_span(),
                 })
             }
             Token::BigIntLiteral(s) => {
                 self.lexer.next_token()?;
                 Ok(Expression::Literal {
                     value: Literal::BigInt(s),
-                    position: None,
+                    position: self.token_span(),
                 })
             }
             Token::Punctuator(Punctuator::LParen) => self.parse_parenthesized_or_arrow(),
@@ -5221,7 +5303,7 @@ impl<'a> Parser<'a> {
                                 // Return ImportExpression with phase marker (for now, same as regular import)
                                 return Ok(Expression::ImportExpression {
                                     source: Box::new(source),
-                                    position: None,
+                                    position: self.span_from(&node_start),
                                 });
                             } else {
                                 // Unknown import.X - syntax error
@@ -5252,7 +5334,15 @@ impl<'a> Parser<'a> {
                 self.expect_punctuator(Punctuator::RParen)?;
                 Ok(Expression::ImportExpression {
                     source: Box::new(source),
-                    position: None,
+                    position: self.span_from(&node_start),
+                })
+            }
+            // Regular expression literal already recognized by the lexer
+            Token::RegExp(pattern, flags) => {
+                self.lexer.next_token()?;
+                Ok(Expression::Literal {
+                    value: Literal::RegExp(pattern, flags),
+                    position: self.token_span(),
                 })
             }
             // Regular expression literal
@@ -5282,7 +5372,7 @@ impl<'a> Parser<'a> {
                 if let Token::RegExp(pattern, flags) = regex_token {
                     Ok(Expression::Literal {
                         value: Literal::RegExp(pattern, flags),
-                        position: None,
+                        position: self.token_span(),
                     })
                 } else {
                     Err(syntax_error("Expected regular expression literal", None))
@@ -5298,6 +5388,7 @@ impl<'a> Parser<'a> {
         &mut self,
         head: String,
     ) -> Result<Expression, JsError> {
+        let node_start = self.node_start();
         let mut quasis = Vec::new();
         let mut expressions = Vec::new();
 
@@ -5363,7 +5454,7 @@ impl<'a> Parser<'a> {
         Ok(Expression::TemplateLiteral {
             quasis,
             expressions,
-            position: None,
+            position: self.span_from(&node_start),
         })
     }
 
@@ -5373,6 +5464,7 @@ impl<'a> Parser<'a> {
         &mut self,
         head: String,
     ) -> Result<Expression, JsError> {
+        let node_start = self.node_start();
         let mut quasis = Vec::new();
         let mut expressions = Vec::new();
 
@@ -5424,11 +5516,12 @@ impl<'a> Parser<'a> {
         Ok(Expression::TemplateLiteral {
             quasis,
             expressions,
-            position: None,
+            position: self.span_from(&node_start),
         })
     }
 
     fn parse_function_expression(&mut self) -> Result<Expression, JsError> {
+        let node_start = self.node_start();
         self.expect_keyword(Keyword::Function)?;
 
         // Check for generator: function *name() or function *()
@@ -5507,11 +5600,12 @@ impl<'a> Parser<'a> {
             body,
             is_async: false,
             is_generator,
-            position: None,
+            position: self.span_from(&node_start),
         })
     }
 
     fn parse_async_function_expression(&mut self) -> Result<Expression, JsError> {
+        let node_start = self.node_start();
         self.expect_keyword(Keyword::Async)?;
 
         // Peek next token to update line_terminator_before_token
@@ -5530,6 +5624,7 @@ impl<'a> Parser<'a> {
                 let start_line = self.lexer.line;
                 let start_col = self.lexer.column;
                 let start_token = self.lexer.current_token.clone();
+                let start_token_spans = self.lexer.token_spans();
 
                 // Clear token cache so next_token actually scans
                 self.lexer.current_token = None;
@@ -5558,6 +5653,7 @@ impl<'a> Parser<'a> {
                 self.lexer.line = start_line;
                 self.lexer.column = start_col;
                 self.lexer.current_token = start_token;
+                self.lexer.restore_token_spans(start_token_spans);
                 self.lexer.line_terminator_before_token = true;
 
                 if is_arrow {
@@ -5572,7 +5668,7 @@ impl<'a> Parser<'a> {
             // Not an async arrow function, treat `async` as an identifier
             return Ok(Expression::Identifier {
                 name: "async".to_string(),
-                position: None,
+                position: self.token_span(),
             });
         }
 
@@ -5647,7 +5743,7 @@ impl<'a> Parser<'a> {
                 body,
                 is_async: true,
                 is_generator,
-                position: None,
+                position: self.span_from(&node_start),
             })
         } else if self.check_punctuator(Punctuator::LParen)? {
             // Async arrow function with parens: async (params) => body
@@ -5656,9 +5752,10 @@ impl<'a> Parser<'a> {
             let prev_async = self.in_async;
             self.in_async = true;
             let params = self.parse_parameters()?;
-            // Validate arrow parameters (duplicates and yield expressions)
+            // Validate arrow parameters (duplicates, yield and await expressions)
             self.validate_arrow_parameters(&params)?;
             self.validate_arrow_params_no_yield(&params)?;
+            self.validate_params_no_await(&params)?;
             // Check for line terminator before =>
             if self.check_punctuator(Punctuator::Arrow)? {
                 if self.lexer.line_terminator_before_token {
@@ -5680,7 +5777,7 @@ impl<'a> Parser<'a> {
                 params,
                 body,
                 is_async: true,
-                position: None,
+                position: self.span_from(&node_start),
             })
         } else if let Token::Identifier(name, _) = self.lexer.peek_token()?.clone() {
             // Async arrow function without parens: async x => body
@@ -5694,6 +5791,7 @@ impl<'a> Parser<'a> {
             let saved_previous_line = self.lexer.previous_line;
             let saved_line_term = self.lexer.line_terminator_before_token;
             let saved_token = self.lexer.current_token.clone();
+            let saved_token_spans = self.lexer.token_spans();
 
             self.lexer.next_token()?; // consume identifier
             let has_arrow = self.check_punctuator(Punctuator::Arrow)?;
@@ -5710,7 +5808,7 @@ impl<'a> Parser<'a> {
                     params: vec![Pattern::Identifier(name)],
                     body,
                     is_async: true,
-                    position: None,
+                    position: self.span_from(&node_start),
                 })
             } else {
                 // No arrow - restore lexer state and treat 'async' as an identifier
@@ -5720,10 +5818,11 @@ impl<'a> Parser<'a> {
                 self.lexer.previous_line = saved_previous_line;
                 self.lexer.line_terminator_before_token = saved_line_term;
                 self.lexer.current_token = saved_token;
+                self.lexer.restore_token_spans(saved_token_spans);
 
                 Ok(Expression::Identifier {
                     name: "async".to_string(),
-                    position: None,
+                    position: self.token_span(),
                 })
             }
         } else {
@@ -5731,12 +5830,13 @@ impl<'a> Parser<'a> {
             // This means 'async' is a standalone identifier
             Ok(Expression::Identifier {
                 name: "async".to_string(),
-                position: None,
+                position: self.token_span(),
             })
         }
     }
 
     fn parse_parenthesized_or_arrow(&mut self) -> Result<Expression, JsError> {
+        let node_start = self.node_start();
         self.lexer.next_token()?; // consume (
 
         // Check for empty params ()
@@ -5756,7 +5856,7 @@ impl<'a> Parser<'a> {
                     params: vec![],
                     body,
                     is_async: false,
-                    position: None,
+                    position: self.span_from(&node_start),
                 });
             }
             return Err(syntax_error("Unexpected )", None));
@@ -5781,7 +5881,7 @@ impl<'a> Parser<'a> {
                     params: vec![Pattern::RestElement(Box::new(rest_pattern))],
                     body,
                     is_async: false,
-                    position: None,
+                    position: self.span_from(&node_start),
                 });
             }
             return Err(syntax_error("Rest parameter must be in arrow function", None));
@@ -5818,7 +5918,7 @@ impl<'a> Parser<'a> {
                     params,
                     body,
                     is_async: false,
-                    position: None,
+                    position: self.span_from(&node_start),
                 });
             }
             // Single expression in parentheses - wrap in ParenthesizedExpression
@@ -5827,7 +5927,7 @@ impl<'a> Parser<'a> {
             self.validate_no_cover_initialized_name(&first)?;
             return Ok(Expression::ParenthesizedExpression {
                 expression: Box::new(first),
-                position: None,
+                position: self.span_from(&node_start),
             });
         }
 
@@ -5887,7 +5987,7 @@ impl<'a> Parser<'a> {
                     params,
                     body,
                     is_async: false,
-                    position: None,
+                    position: self.span_from(&node_start),
                 });
             }
 
@@ -5899,9 +5999,9 @@ impl<'a> Parser<'a> {
             return Ok(Expression::ParenthesizedExpression {
                 expression: Box::new(Expression::SequenceExpression {
                     expressions: exprs,
-                    position: None,
+                    position: self.span_from(&node_start),
                 }),
-                position: None,
+                position: self.span_from(&node_start),
             });
         }
 
@@ -5909,7 +6009,7 @@ impl<'a> Parser<'a> {
         // Single expression after comma check - wrap in ParenthesizedExpression
         Ok(Expression::ParenthesizedExpression {
             expression: Box::new(first),
-            position: None,
+            position: self.span_from(&node_start),
         })
     }
 
@@ -6010,6 +6110,12 @@ impl<'a> Parser<'a> {
                                 shorthand,
                             });
                         }
+                        ObjectProperty::Accessor { .. } => {
+                            return Err(syntax_error(
+                                "Invalid destructuring assignment target",
+                                self.last_position.clone(),
+                            ));
+                        }
                         ObjectProperty::SpreadElement(expr) => {
                             let pattern = self.expression_to_pattern(expr)?;
                             patterns.push(ObjectPatternProperty {
@@ -6095,6 +6201,7 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_array_literal(&mut self) -> Result<Expression, JsError> {
+        let node_start = self.node_start();
         self.expect_punctuator(Punctuator::LBracket)?;
         let mut elements = Vec::new();
         let mut last_was_spread = false;
@@ -6138,11 +6245,12 @@ impl<'a> Parser<'a> {
 
         Ok(Expression::ArrayExpression {
             elements,
-            position: None,
+            position: self.span_from(&node_start),
         })
     }
 
     fn parse_object_literal(&mut self) -> Result<Expression, JsError> {
+        let node_start = self.node_start();
         self.expect_punctuator(Punctuator::LBrace)?;
         let mut properties = Vec::new();
 
@@ -6203,7 +6311,7 @@ impl<'a> Parser<'a> {
                     body,
                     is_async: false,
                     is_generator: true,
-                    position: None,
+                    position: self.span_from(&node_start),
                 };
 
                 properties.push(ObjectProperty::Property {
@@ -6244,7 +6352,7 @@ impl<'a> Parser<'a> {
                         body,
                         is_async: false,
                         is_generator: false,
-                        position: None,
+                        position: self.span_from(&node_start),
                     };
 
                     properties.push(ObjectProperty::Property {
@@ -6290,7 +6398,7 @@ impl<'a> Parser<'a> {
                             key: PropertyKey::Identifier("get".to_string()),
                             value: Expression::Identifier {
                                 name: "get".to_string(),
-                                position: None,
+                                position: self.token_span(),
                             },
                             shorthand: true,
                             computed: false,
@@ -6320,7 +6428,7 @@ impl<'a> Parser<'a> {
                         body,
                         is_async: false,
                         is_generator: false,
-                        position: None,
+                        position: self.span_from(&node_start),
                     };
 
                     properties.push(ObjectProperty::Property {
@@ -6366,13 +6474,13 @@ impl<'a> Parser<'a> {
                         body,
                         is_async: false,
                         is_generator: false,
-                        position: None,
+                        position: self.span_from(&node_start),
                     };
 
-                    properties.push(ObjectProperty::Property {
+                    properties.push(ObjectProperty::Accessor {
                         key,
+                        kind: MethodKind::Get,
                         value: func,
-                        shorthand: false,
                         computed,
                     });
                 }
@@ -6401,7 +6509,7 @@ impl<'a> Parser<'a> {
                             key: PropertyKey::Identifier("set".to_string()),
                             value: Expression::Identifier {
                                 name: "set".to_string(),
-                                position: None,
+                                position: self.token_span(),
                             },
                             shorthand: true,
                             computed: false,
@@ -6431,7 +6539,7 @@ impl<'a> Parser<'a> {
                         body,
                         is_async: false,
                         is_generator: false,
-                        position: None,
+                        position: self.span_from(&node_start),
                     };
 
                     properties.push(ObjectProperty::Property {
@@ -6491,13 +6599,13 @@ impl<'a> Parser<'a> {
                         body,
                         is_async: false,
                         is_generator: false,
-                        position: None,
+                        position: self.span_from(&node_start),
                     };
 
-                    properties.push(ObjectProperty::Property {
+                    properties.push(ObjectProperty::Accessor {
                         key,
+                        kind: MethodKind::Set,
                         value: func,
-                        shorthand: false,
                         computed,
                     });
                 }
@@ -6527,7 +6635,7 @@ impl<'a> Parser<'a> {
                             key: PropertyKey::Identifier("async".to_string()),
                             value: Expression::Identifier {
                                 name: "async".to_string(),
-                                position: None,
+                                position: self.token_span(),
                             },
                             shorthand: true,
                             computed: false,
@@ -6584,7 +6692,7 @@ impl<'a> Parser<'a> {
                         body,
                         is_async: true,
                         is_generator,
-                        position: None,
+                        position: self.span_from(&node_start),
                     };
 
                     properties.push(ObjectProperty::Property {
@@ -6627,7 +6735,7 @@ impl<'a> Parser<'a> {
                     body,
                     is_async: false,
                     is_generator: true,
-                    position: None,
+                    position: self.span_from(&node_start),
                 };
 
                 properties.push(ObjectProperty::Property {
@@ -6673,7 +6781,7 @@ impl<'a> Parser<'a> {
                         body,
                         is_async: false,
                         is_generator: false,
-                        position: None,
+                        position: self.span_from(&node_start),
                     };
 
                     properties.push(ObjectProperty::Property {
@@ -6714,7 +6822,7 @@ impl<'a> Parser<'a> {
                             left: AssignmentTarget::Identifier(key),
                             operator: AssignmentOperator::Assign,
                             right: Box::new(default_value),
-                            position: None,
+                            position: self.span_from(&node_start),
                         },
                         shorthand: true,  // Mark as CoverInitializedName
                         computed: false,
@@ -6753,7 +6861,7 @@ impl<'a> Parser<'a> {
                         key: PropertyKey::Identifier(key.clone()),
                         value: Expression::Identifier {
                             name: key,
-                            position: None,
+                            position: self.token_span(),
                         },
                         shorthand: true,
                         computed: false,
@@ -6771,7 +6879,7 @@ impl<'a> Parser<'a> {
 
         Ok(Expression::ObjectExpression {
             properties,
-            position: None,
+            position: self.span_from(&node_start),
         })
     }
 
@@ -6939,8 +7047,24 @@ impl<'a> Parser<'a> {
                 Ok(s)
             }
             Token::Number(n) => Ok(n.to_string()),
-            Token::LegacyOctalLiteral(n) => Ok(n.to_string()),
-            Token::NonOctalDecimalLiteral(n) => Ok(n.to_string()),
+            Token::LegacyOctalLiteral(n) => {
+                if self.strict_mode {
+                    return Err(syntax_error(
+                        "Octal literals are not allowed in strict mode",
+                        self.last_position.clone(),
+                    ));
+                }
+                Ok(n.to_string())
+            }
+            Token::NonOctalDecimalLiteral(n) => {
+                if self.strict_mode {
+                    return Err(syntax_error(
+                        "Decimals with leading zeros are not allowed in strict mode",
+                        self.last_position.clone(),
+                    ));
+                }
+                Ok(n.to_string())
+            }
             Token::BigIntLiteral(s) => Ok(s), // BigInt property names converted to string
             _ => Err(unexpected_token(
                 "property name",
@@ -6965,6 +7089,25 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// Where the node about to be parsed starts: the peeked token, if any
+    fn node_start(&self) -> core_types::SourcePosition {
+        if self.lexer.current_token.is_some() {
+            self.lexer.token_start.clone()
+        } else {
+            self.lexer.current_position()
+        }
+    }
+
+    /// Span from `start` to the end of the last consumed token
+    fn span_from(&self, start: &core_types::SourcePosition) -> Option<SourceSpan> {
+        Some(SourceSpan::new(start.clone(), self.lexer.last_token_span.end.clone()))
+    }
+
+    /// Span of the last consumed token
+    fn token_span(&self) -> Option<SourceSpan> {
+        Some(self.lexer.last_token_span.clone())
+    }
+
     fn update_position(&mut self) -> Result<(), JsError> {
         // Get position from lexer's current state
         // This is a simplified approach - in a full implementation, the lexer would expose position
@@ -6992,8 +7135,24 @@ impl<'a> Parser<'a> {
                 Ok(PropertyKey::String(s))
             }
             Token::Number(n) => Ok(PropertyKey::Number(n)),
-            Token::LegacyOctalLiteral(n) => Ok(PropertyKey::Number(n)),
-            Token::NonOctalDecimalLiteral(n) => Ok(PropertyKey::Number(n)),
+            Token::LegacyOctalLiteral(n) => {
+                if self.strict_mode {
+                    return Err(syntax_error(
+                        "Octal literals are not allowed in strict mode",
+                        self.last_position.clone(),
+                    ));
+                }
+                Ok(PropertyKey::Number(n))
+            }
+            Token::NonOctalDecimalLiteral(n) => {
+                if self.strict_mode {
+                    return Err(syntax_error(
+                        "Decimals with leading zeros are not allowed in strict mode",
+                        self.last_position.clone(),
+                    ));
+                }
+                Ok(PropertyKey::Number(n))
+            }
             Token::BigIntLiteral(s) => {
                 // BigInt property names are converted to their string representation
                 Ok(PropertyKey::String(s))
@@ -7366,7 +7525,8 @@ impl<'a> Parser<'a> {
             Expression::ObjectExpression { properties, .. } => {
                 for prop in properties {
                     match prop {
-                        ObjectProperty::Property { value, .. } => {
+                        ObjectProperty::Property { value, .. }
+                        | ObjectProperty::Accessor { value, .. } => {
                             self.validate_for_in_of_left_element(value)?;
                         }
                         ObjectProperty::SpreadElement(expr) => {
@@ -7594,6 +7754,12 @@ impl<'a> Parser<'a> {
         body: &[Statement],
     ) -> Result<(), JsError> {
         if let Some(n) = name {
+            if Self::body_contains_use_strict(body) && self.is_strict_reserved_word(n) {
+                return Err(syntax_error(
+                    format!("'{}' is a reserved word in strict mode", n),
+                    self.last_position.clone(),
+                ));
+            }
             if Self::body_contains_use_strict(body) && (n == "eval" || n == "arguments") {
                 return Err(syntax_error(
                     &format!(
@@ -7632,7 +7798,8 @@ impl<'a> Parser<'a> {
         Ok(())
     }
 
-    /// Check for 'eval'/'arguments' as parameter names when body becomes strict
+    /// Check for 'eval'/'arguments' and strict reserved words as parameter names
+    /// when body becomes strict
     fn validate_no_eval_arguments_params_in_strict_body(
         &self,
         params: &[Pattern],
@@ -7642,6 +7809,17 @@ impl<'a> Parser<'a> {
             return Ok(());
         }
 
+        let mut names = Vec::new();
+        for param in params {
+            Self::collect_bound_names(param, &mut names);
+        }
+        if let Some(name) = names.iter().find(|n| self.is_strict_reserved_word(n)) {
+            return Err(syntax_error(
+                format!("'{}' is a reserved word in strict mode", name),
+                self.last_position.clone(),
+            ));
+        }
+
         Self::check_params_for_eval_arguments(params, &self.last_position)
     }
 
@@ -8031,7 +8209,8 @@ impl<'a> Parser<'a> {
             Expression::ObjectExpression { properties, .. } => {
                 properties.iter().any(|prop| {
                     match prop {
-                        crate::ast::ObjectProperty::Property { value, key, .. } => {
+                        crate::ast::ObjectProperty::Property { value, key, .. }
+                        | crate::ast::ObjectProperty::Accessor { value, key, .. } => {
                             Self::expression_contains_yield(value)
                                 || match key {
                                     crate::ast::PropertyKey::Computed(expr) => Self::expression_contains_yield(expr),
@@ -8129,7 +8308,8 @@ impl<'a> Parser<'a> {
             Expression::ObjectExpression { properties, .. } => {
                 properties.iter().any(|prop| {
                     match prop {
-                        crate::ast::ObjectProperty::Property { value, key, .. } => {
+                        crate::ast::ObjectProperty::Property { value, key, .. }
+                        | crate::ast::ObjectProperty::Accessor { value, key, .. } => {
                             Self::expression_contains_await(value)
                                 || match key {
                                     crate::ast::PropertyKey::Computed(expr) => Self::expression_contains_await(expr),
@@ -8151,9 +8331,10 @@ impl<'a> Parser<'a> {
     fn pattern_contains_await(pattern: &Pattern) -> bool {
         match pattern {
             Pattern::Identifier(_) => false,
-            Pattern::ObjectPattern(properties) => {
-                properties.iter().any(|prop| Self::pattern_contains_await(&prop.value))
-            }
+            Pattern::ObjectPattern(properties) => properties.iter().any(|prop| {
+                matches!(&prop.key, PatternKey::Computed(key) if Self::expression_contains_await(key))
+                    || Self::pattern_contains_await(&prop.value)
+            }),
             Pattern::ArrayPattern(elements) => {
                 elements.iter().any(|elem| {
                     if let Some(pat) = elem {
@@ -8254,7 +8435,8 @@ impl<'a> Parser<'a> {
             Expression::ObjectExpression { properties, .. } => {
                 properties.iter().any(|prop| {
                     match prop {
-                        crate::ast::ObjectProperty::Property { value, key, .. } => {
+                        crate::ast::ObjectProperty::Property { value, key, .. }
+                        | crate::ast::ObjectProperty::Accessor { value, key, .. } => {
                             Self::expression_contains_arguments(value)
                                 || match key {
                                     crate::ast::PropertyKey::Computed(expr) => Self::expression_contains_arguments(expr),
@@ -8469,6 +8651,7 @@ impl<'a> Parser<'a> {
     /// Parse a statement in a context where lexical declarations are not allowed
     /// (e.g., after if, while, for without braces)
     fn parse_substatement(&mut self) -> Result<Statement, JsError> {
+        let node_start = self.node_start();
         let token = self.lexer.peek_token()?.clone();
 
         // Lexical declarations (let, const) are not allowed in statement positions
@@ -8521,7 +8704,7 @@ impl<'a> Parser<'a> {
             // Parse the rest of the expression (if any) and create an ExpressionStatement
             let let_expr = Expression::Identifier {
                 name: "let".to_string(),
-                position: None,
+                position: self.token_span(),
             };
 
             // Check if there's more to the expression (e.g., let.foo or let())
@@ -8553,7 +8736,7 @@ impl<'a> Parser<'a> {
 
             return Ok(Statement::ExpressionStatement {
                 expression: expr,
-                position: None,
+                position: self.span_from(&node_start),
             });
         }
 
@@ -8959,6 +9142,51 @@ result = {[a]:b, ...rest} = vals;
         assert!(result.is_ok(), "Rest array pattern with default error: {:?}", result.err());
     }
 
+    #[test]
+    fn test_async_arrow_complex_parameters() {
+        let code = "var f = async ({ a, b = 1 }, [c, [d] = [2]], e = 3, ...rest) => a + b;";
+        let mut parser = Parser::new(code);
+        let result = parser.parse();
+        assert!(result.is_ok(), "async arrow params error: {:?}", result.err());
+
+        let statement = "async ({ a: { b } = {} }, ...[c, d = 4]) => b + c + d;";
+        let ast = Parser::new(statement).parse().unwrap();
+        let ASTNode::Program(body) = ast else { panic!("expected program") };
+        match &body[0] {
+            Statement::ExpressionStatement {
+                expression: Expression::ArrowFunctionExpression { params, is_async, .. },
+                ..
+            } => {
+                assert!(*is_async);
+                assert!(matches!(params[0], Pattern::ObjectPattern(_)));
+                assert!(
+                    matches!(&params[1], Pattern::RestElement(inner) if matches!(**inner, Pattern::ArrayPattern(_)))
+                );
+            }
+            other => panic!("expected async arrow, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_async_arrow_await_in_parameter_default_is_error() {
+        for code in [
+            "var f = async (a = await 1) => a;",
+            "async (a = await 1) => a;",
+            "var f = async ({ a = await x }) => a;",
+            "var f = async ([a = await x], ...rest) => a;",
+            "var f = async (...[a = await x]) => a;",
+            "var f = async ({ [await x]: a }) => a;",
+        ] {
+            let err = Parser::new(code).parse().expect_err(code);
+            assert_eq!(err.kind, core_types::ErrorKind::SyntaxError, "{}", code);
+            assert_eq!(err.message, "Formal parameters cannot contain await expressions", "{}", code);
+        }
+
+        // await inside the body is fine
+        let result = Parser::new("var f = async (a = 1) => await a;").parse();
+        assert!(result.is_ok(), "{:?}", result.err());
+    }
+
     #[test]
     fn test_async_identifier_in_for_of() {
         // async.x is a member expression where async is an identifier
@@ -8986,4 +9214,116 @@ result = {[a]:b, ...rest} = vals;
         let result3 = parser3.parse();
         assert!(result3.is_ok(), "full test262 case error: {:?}", result3.err());
     }
+
+    #[test]
+    fn test_invalid_assignment_targets_are_early_errors() {
+        for code in ["1 = 2", "(a + b) = c", "f() = 1", "(a, b) = c", "'s' += 1", "a + b = c"] {
+            let err = Parser::new(code).parse().expect_err(code);
+            assert_eq!(err.kind, core_types::ErrorKind::SyntaxError, "{}", code);
+            assert_eq!(err.message, "Invalid left-hand side in assignment", "{}", code);
+        }
+    }
+
+    #[test]
+    fn test_reference_and_pattern_assignment_targets_are_valid() {
+        for code in ["a.b = 1", "a[0] = 1", "(a) = 1", "[a] = x", "({ a } = x)", "a.b += 1"] {
+            let result = Parser::new(code).parse();
+            assert!(result.is_ok(), "{}: {:?}", code, result.err());
+        }
+    }
+
+    #[test]
+    fn test_strict_mode_early_errors() {
+        let sources = [
+            "var package = 1;",
+            "var implements, interface, private, protected, public, static;",
+            "var yield = 1;",
+            "var let = 1;",
+            "function f(package) {}",
+            "var { package } = o;",
+            "package = 1;",
+            "var x = 0777;",
+            "var x = 08;",
+            "var x = 09;",
+            "var x = 08.5;",
+            "var x = 09e1;",
+            "var o = { 0777: 1 };",
+            "with (obj) { x; }",
+        ];
+        for code in sources {
+            let sloppy = Parser::new(code).parse();
+            assert!(sloppy.is_ok(), "sloppy {}: {:?}", code, sloppy.err());
+
+            let strict = format!("'use strict'; {}", code);
+            let err = Parser::new(&strict).parse().expect_err(&strict);
+            assert_eq!(err.kind, core_types::ErrorKind::SyntaxError, "{}", strict);
+
+            let nested = format!("function g() {{ 'use strict'; {} }}", code);
+            let err = Parser::new(&nested).parse().expect_err(&nested);
+            assert_eq!(err.kind, core_types::ErrorKind::SyntaxError, "{}", nested);
+        }
+
+        // A "use strict" directive applies retroactively to the function's own name and params
+        for (code, word) in [
+            ("function package() { 'use strict'; }", "package"),
+            ("function f(static) { 'use strict'; }", "static"),
+        ] {
+            let err = Parser::new(code).parse().expect_err(code);
+            assert_eq!(err.message, format!("'{}' is a reserved word in strict mode", word));
+        }
+    }
+
+    #[test]
+    fn test_regex_and_division_contexts_parse() {
+        for code in [
+            "function f(s) { return /x/.test(s) }",
+            "a = b / c / d",
+            "{} /x/g.test('x')",
+            "if (a) /x/.test(s)",
+            "x = (a) / 2 / (b)",
+            "var of = 8; of / 2 / 1",
+            "var yield = 4; yield / 2 / 1",
+            "function* g() { yield /x/g; yield /=/; }",
+            "async function f() { await /x/; }",
+            "for (var m of /x/g.exec('x')) {}",
+            "'source' in /x/",
+            "var x = {} / 1",
+            "var f = function(){} / 1",
+            "var g = function*(a = {}) { yield {} } / 2 / 1",
+            "var n = 5. / 2 / 1",
+            "var t = `${ {} / 3 }${ /x/.source }` / 4",
+        ] {
+            Parser::new(code).parse().unwrap_or_else(|e| panic!("{}: {:?}", code, e));
+        }
+    }
+
+    #[test]
+    fn test_nodes_carry_source_spans() {
+        let ast = Parser::new("let x = 1;\nfoo(a + b);").parse().unwrap();
+        let ASTNode::Program(statements) = ast else {
+            panic!("expected a program");
+        };
+        let Statement::ExpressionStatement { expression, .. } = &statements[1] else {
+            panic!("expected an expression statement");
+        };
+        let Expression::CallExpression {
+            arguments,
+            position,
+            ..
+        } = expression
+        else {
+            panic!("expected a call");
+        };
+
+        let span = |node: &Option<SourceSpan>| {
+            let span = node.clone().expect("span");
+            ((span.start.line, span.start.column), (span.end.line, span.end.column))
+        };
+        // `foo(a + b)` spans columns 1-10 of line 2, ending before the `;`
+        assert_eq!(span(position), ((2, 1), (2, 11)));
+        let Expression::BinaryExpression { position, .. } = &arguments[0] else {
+            panic!("expected a binary expression");
+        };
+        assert_eq!(span(position), ((2, 5), (2, 10)));
+    }
 }
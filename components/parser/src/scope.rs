@@ -532,7 +532,8 @@ impl ScopeAnalyzer {
             Expression::ObjectExpression { properties, .. } => {
                 for prop in properties {
                     match prop {
-                        ObjectProperty::Property { value, .. } => {
+                        ObjectProperty::Property { value, .. }
+                        | ObjectProperty::Accessor { value, .. } => {
                             self.visit_expression(value)?;
                         }
                         ObjectProperty::SpreadElement(expr) => {
@@ -3,7 +3,11 @@
 //! These tests verify the parser component implements its contract correctly.
 
 use core_types::JsError;
-use parser::{ASTNode, BytecodeGenerator, Lexer, Parser, ScopeAnalyzer, ScopeInfo, Token};
+use parser::ast::{MethodKind, ObjectProperty, PropertyKey};
+use parser::{
+    ASTNode, BytecodeGenerator, Expression, Lexer, Parser, ScopeAnalyzer, ScopeInfo, Statement,
+    Token,
+};
 
 // =============================================================================
 // Lexer Contract Tests
@@ -536,3 +540,106 @@ fn test_shift_operators() {
     println!("Shift operators: {:?}", result);
     assert!(result.is_ok(), "Shift operators failed: {:?}", result);
 }
+
+// =============================================================================
+// Object Literal Tests
+// =============================================================================
+
+/// Parse `({ ... })` and return the object literal's properties
+fn parse_object_literal(source: &str) -> Vec<ObjectProperty> {
+    let ast = Parser::new(source).parse().expect("object literal should parse");
+    match ast {
+        ASTNode::Program(mut stmts) => match stmts.remove(0) {
+            Statement::ExpressionStatement {
+                expression: Expression::ParenthesizedExpression { expression, .. },
+                ..
+            } => match *expression {
+                Expression::ObjectExpression { properties, .. } => properties,
+                other => panic!("Expected object expression, got {:?}", other),
+            },
+            other => panic!("Expected object expression, got {:?}", other),
+        },
+        other => panic!("Expected program, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_object_literal_shorthand_property() {
+    let props = parse_object_literal("({x});");
+    match &props[0] {
+        ObjectProperty::Property {
+            key: PropertyKey::Identifier(name),
+            value: Expression::Identifier { name: value_name, .. },
+            shorthand,
+            computed,
+        } => {
+            assert_eq!(name, "x");
+            assert_eq!(value_name, "x");
+            assert!(*shorthand);
+            assert!(!*computed);
+        }
+        other => panic!("Expected shorthand property, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_object_literal_computed_key() {
+    let props = parse_object_literal("({[k]: v});");
+    match &props[0] {
+        ObjectProperty::Property {
+            key: PropertyKey::Computed(Expression::Identifier { name, .. }),
+            computed,
+            ..
+        } => {
+            assert_eq!(name, "k");
+            assert!(*computed);
+        }
+        other => panic!("Expected computed property, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_object_literal_method_shorthand() {
+    let props = parse_object_literal("({m() { return 1; }});");
+    match &props[0] {
+        ObjectProperty::Property {
+            key: PropertyKey::Identifier(name),
+            value: Expression::FunctionExpression { .. },
+            ..
+        } => assert_eq!(name, "m"),
+        other => panic!("Expected method property, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_object_literal_getter_and_setter() {
+    let props = parse_object_literal("({get v() { return 1; }, set v(x) {}});");
+    assert_eq!(props.len(), 2);
+    for (prop, expected) in props.iter().zip([MethodKind::Get, MethodKind::Set]) {
+        match prop {
+            ObjectProperty::Accessor {
+                key: PropertyKey::Identifier(name),
+                kind,
+                value: Expression::FunctionExpression { .. },
+                ..
+            } => {
+                assert_eq!(name, "v");
+                assert_eq!(*kind, expected);
+            }
+            other => panic!("Expected accessor property, got {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn test_object_literal_spread() {
+    let props = parse_object_literal("({...src, a: 1});");
+    assert_eq!(props.len(), 2);
+    match &props[0] {
+        ObjectProperty::SpreadElement(Expression::Identifier { name, .. }) => {
+            assert_eq!(name, "src")
+        }
+        other => panic!("Expected spread element, got {:?}", other),
+    }
+    assert!(matches!(props[1], ObjectProperty::Property { .. }));
+}
@@ -3,6 +3,7 @@
 //! Provides security controls for script execution, including
 //! CSP directives, eval() restrictions, and trusted types.
 
+use serde_json::json;
 use std::collections::{HashMap, HashSet};
 
 /// Content Security Policy configuration
@@ -70,15 +71,69 @@ impl ContentSecurityPolicy {
     }
 
     /// Check if inline scripts are allowed
+    ///
+    /// Inline scripts carrying a matching nonce or hash are allowed regardless;
+    /// check those with `validate_nonce` and `validate_hash`.
     pub fn allows_inline_script(&self) -> bool {
-        self.allows_source("script-src", "'unsafe-inline'")
+        self.allows_unsafe_inline()
+    }
+
+    /// Check if inline event handlers (`onclick="..."`) are allowed
+    ///
+    /// Handlers cannot carry a nonce and are never hash-allowlisted, so only
+    /// an effective `'unsafe-inline'` allows them.
+    pub fn allows_inline_event_handler(&self) -> bool {
+        self.allows_unsafe_inline()
+    }
+
+    /// Check if navigating to a `javascript:` URL may run its script
+    ///
+    /// Like event handlers, these are only allowed by `'unsafe-inline'`.
+    pub fn allows_javascript_url(&self) -> bool {
+        self.allows_unsafe_inline()
+    }
+
+    /// Whether `'unsafe-inline'` is in effect for `script-src`
+    ///
+    /// A nonce, hash or `'strict-dynamic'` in the same directive makes
+    /// browsers ignore `'unsafe-inline'`.
+    fn allows_unsafe_inline(&self) -> bool {
+        let cancelled = self
+            .directives
+            .get("script-src")
+            .or_else(|| self.directives.get("default-src"))
+            .is_some_and(|sources| {
+                sources.iter().any(|s| {
+                    s == "'strict-dynamic'"
+                        || ["'nonce-", "'sha256-", "'sha384-", "'sha512-"]
+                            .iter()
+                            .any(|prefix| s.starts_with(prefix))
+                })
+            });
+        !cancelled && self.allows_source("script-src", "'unsafe-inline'")
     }
 
     /// Check if script source is allowed
+    ///
+    /// Under `'strict-dynamic'` host and scheme sources are ignored, so bare
+    /// URLs are blocked; only nonce- and hash-validated scripts load.
     pub fn allows_script_source(&self, source: &str) -> bool {
+        if self.propagates_trust() && !source.starts_with('\'') {
+            return false;
+        }
         self.allows_source("script-src", source)
     }
 
+    /// Whether scripts inserted by trusted scripts inherit their trust
+    ///
+    /// True when the effective `script-src` contains `'strict-dynamic'`.
+    pub fn propagates_trust(&self) -> bool {
+        self.directives
+            .get("script-src")
+            .or_else(|| self.directives.get("default-src"))
+            .is_some_and(|sources| sources.iter().any(|s| s == "'strict-dynamic'"))
+    }
+
     /// Check if style source is allowed
     pub fn allows_style_source(&self, source: &str) -> bool {
         self.allows_source("style-src", source)
@@ -153,14 +208,18 @@ impl ContentSecurityPolicy {
             // Nonce and hash patterns match exactly
             return source == pattern;
         }
-        if pattern.ends_with('*') {
-            let prefix = &pattern[..pattern.len() - 1];
-            return source.starts_with(prefix);
+        if let Some(scheme) = pattern.strip_suffix(':') {
+            // Scheme source such as `https:`
+            return source
+                .split_once(':')
+                .is_some_and(|(s, _)| s.eq_ignore_ascii_case(scheme));
         }
-        if pattern.starts_with("*.") {
-            // Wildcard subdomain
-            let domain = &pattern[2..];
-            return source.ends_with(domain) || source == domain;
+        if let (Some(expr), Some(url)) = (SourceUrl::parse(pattern), SourceUrl::parse(source)) {
+            return expr.matches(&url);
+        }
+        if let Some(prefix) = pattern.strip_suffix('*') {
+            // Legacy prefix wildcard such as `https://cdn.*`
+            return source.starts_with(prefix);
         }
         pattern == source
     }
@@ -221,6 +280,31 @@ impl ContentSecurityPolicy {
         )
     }
 
+    /// Get the reporting endpoints from `report-uri` and `report-to`
+    ///
+    /// `report-uri` URLs come first, followed by `report-to` group names.
+    pub fn report_endpoints(&self) -> Vec<String> {
+        ["report-uri", "report-to"]
+            .iter()
+            .filter_map(|name| self.directives.get(*name))
+            .flatten()
+            .cloned()
+            .collect()
+    }
+
+    /// Build the JSON violation report body POSTed to the report endpoints
+    pub fn build_report(&self, violation: &CspViolation) -> serde_json::Value {
+        json!({
+            "csp-report": {
+                "document-uri": violation.document_uri,
+                "violated-directive": violation.violated_directive,
+                "effective-directive": violation.directive,
+                "blocked-uri": violation.blocked_uri,
+                "original-policy": self.to_header(),
+            }
+        })
+    }
+
     /// Create a strict CSP policy
     pub fn strict() -> Self {
         let mut csp = Self::new();
@@ -270,6 +354,23 @@ impl ContentSecurityPolicy {
         self.directives.len()
     }
 
+    /// Get the tokens allowed by the `sandbox` directive
+    ///
+    /// Returns `None` without a `sandbox` directive. An empty set means the
+    /// document is fully sandboxed; tokens are lowercased.
+    pub fn sandbox_flags(&self) -> Option<HashSet<String>> {
+        self.directives
+            .get("sandbox")
+            .map(|tokens| tokens.iter().map(|token| token.to_ascii_lowercase()).collect())
+    }
+
+    /// Check if `require-trusted-types-for 'script'` is set
+    pub fn requires_trusted_types(&self) -> bool {
+        self.directives
+            .get("require-trusted-types-for")
+            .is_some_and(|sinks| sinks.iter().any(|sink| sink.eq_ignore_ascii_case("'script'")))
+    }
+
     /// Validate a nonce
     pub fn validate_nonce(&self, directive: &str, nonce: &str) -> bool {
         let nonce_value = format!("'nonce-{}'", nonce);
@@ -283,6 +384,125 @@ impl ContentSecurityPolicy {
     }
 }
 
+/// Scheme, host, port and path of a host-source expression or a URL
+///
+/// Scheme and host are lowercased since CSP compares them
+/// case-insensitively; the path is kept as written.
+#[derive(Debug, Clone, PartialEq)]
+struct SourceUrl {
+    scheme: Option<String>,
+    host: String,
+    port: Option<String>,
+    path: String,
+}
+
+impl SourceUrl {
+    /// Parse `scheme://host:port/path`, where everything but the host is optional
+    fn parse(text: &str) -> Option<Self> {
+        let (scheme, rest) = match text.split_once("://") {
+            Some((scheme, rest)) => (Some(scheme.to_ascii_lowercase()), rest),
+            None => (None, text),
+        };
+        // Queries and fragments never take part in matching
+        let rest = rest.split(['?', '#']).next().unwrap_or("");
+        let (authority, path) = match rest.find('/') {
+            Some(index) => rest.split_at(index),
+            None => (rest, ""),
+        };
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => (host, Some(port.to_string())),
+            None => (authority, None),
+        };
+        // `*` is only allowed alone or as a leading `*.` label
+        let labels = if host == "*" { "" } else { host.strip_prefix("*.").unwrap_or(host) };
+        let valid_host = !host.is_empty()
+            && labels
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-'));
+        let valid_port = port
+            .as_deref()
+            .is_none_or(|p| p == "*" || (!p.is_empty() && p.chars().all(|c| c.is_ascii_digit())));
+        if !valid_host || !valid_port || (scheme.is_none() && !host.contains('.') && host != "*") {
+            return None;
+        }
+        Some(Self {
+            scheme,
+            host: host.to_ascii_lowercase(),
+            port,
+            path: path.to_string(),
+        })
+    }
+
+    fn default_port(scheme: &str) -> Option<&'static str> {
+        match scheme {
+            "http" | "ws" => Some("80"),
+            "https" | "wss" => Some("443"),
+            _ => None,
+        }
+    }
+
+    /// The explicit port, or the scheme's default
+    fn effective_port(&self, scheme: &str) -> Option<String> {
+        self.port
+            .clone()
+            .or_else(|| Self::default_port(scheme).map(String::from))
+    }
+
+    /// Match `url` against this host-source expression
+    fn matches(&self, url: &SourceUrl) -> bool {
+        let url_scheme = url.scheme.as_deref().unwrap_or("https");
+        let scheme_matches = match self.scheme.as_deref() {
+            // A scheme-less expression matches the protected resource's
+            // scheme; HTTP(S) is assumed here
+            None => matches!(url_scheme, "http" | "https"),
+            // Secure upgrades of the listed scheme are allowed
+            Some("http") => matches!(url_scheme, "http" | "https"),
+            Some("ws") => matches!(url_scheme, "ws" | "wss"),
+            Some(scheme) => scheme == url_scheme,
+        };
+        if !scheme_matches {
+            return false;
+        }
+
+        let host_matches = if self.host == "*" {
+            true
+        } else if let Some(domain) = self.host.strip_prefix("*.") {
+            url.host.ends_with(&format!(".{}", domain))
+        } else {
+            self.host == url.host
+        };
+        if !host_matches {
+            return false;
+        }
+
+        let port_matches = match self.port.as_deref() {
+            Some("*") => true,
+            _ => {
+                let expr_scheme = self.scheme.as_deref().unwrap_or(url_scheme);
+                let expected = self.effective_port(expr_scheme);
+                let actual = url.effective_port(url_scheme);
+                // An upgrade from http to https also upgrades the default port
+                let upgraded = self.port.is_none()
+                    && expr_scheme == "http"
+                    && url_scheme == "https"
+                    && actual.as_deref() == Some("443");
+                expected == actual || upgraded
+            }
+        };
+        if !port_matches {
+            return false;
+        }
+
+        if self.path.is_empty() {
+            true
+        } else if self.path.ends_with('/') {
+            url.path.starts_with(&self.path)
+        } else {
+            url.path == self.path
+        }
+    }
+}
+
 impl Default for ContentSecurityPolicy {
     fn default() -> Self {
         Self::new()
@@ -3,10 +3,20 @@
 //! Provides debugging capabilities through the Chrome DevTools Protocol,
 //! enabling remote debugging, profiling, and inspection of JavaScript code.
 
+use std::cell::RefCell;
 use std::collections::HashMap;
+use std::rc::Rc;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+use builtins::console::ConsoleObserver;
+use builtins::value::JsValue;
+use core_types::{ErrorKind, JsError, ObjectView, Value};
+use interpreter::{DebuggerHook, FrameSnapshot, VM};
 use serde::{Serialize, Deserialize};
 use serde_json::{Value as JsonValue, json};
 
+use crate::service_worker::{FetchRequest, FetchResponse};
+use crate::source_maps::SourceMap;
+
 /// Debug protocol message
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProtocolMessage {
@@ -53,11 +63,16 @@ pub struct Location {
 }
 
 /// Variable scope
+///
+/// The scope object's `value` holds the bindings as a JSON object keyed by variable name.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Scope {
     #[serde(rename = "type")]
     pub scope_type: String,  // "global", "local", "closure"
     pub object: RemoteObject,
+    /// Names of `const` bindings in this scope, which the debugger must not overwrite
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub const_bindings: Vec<String>,
 }
 
 /// Remote object representation
@@ -70,15 +85,77 @@ pub struct RemoteObject {
     pub object_id: Option<String>,
 }
 
+/// Live variable state of one call frame, captured by the embedder when
+/// execution pauses
+///
+/// The VM keeps locals in registers and captured variables in upvalues;
+/// the embedder pairs each with its source name.
+#[derive(Debug, Clone)]
+pub struct PausedFrame {
+    pub function_name: String,
+    pub location: Location,
+    /// Register-backed locals of the frame
+    pub locals: Vec<(String, JsValue)>,
+    /// Variables the frame's closure captured as upvalues
+    pub closure: Vec<(String, JsValue)>,
+    /// Names of `const` bindings among `locals` and `closure`
+    pub const_bindings: Vec<String>,
+}
+
 /// DevTools Protocol Server
 pub struct DevToolsServer {
     breakpoints: HashMap<String, Breakpoint>,
     paused: bool,
     call_stack: Vec<CallFrame>,
     scripts: HashMap<String, String>,  // script_id -> source
+    script_urls: HashMap<String, String>,  // script_id -> url
+    source_maps: HashMap<String, SourceMap>,  // script_id -> source map
+    url_breakpoints: Vec<UrlBreakpoint>,
     next_script_id: u64,
     next_breakpoint_id: u64,
     next_object_id: u64,
+    network_enabled: bool,
+    next_request_id: u64,
+    events: Vec<ProtocolMessage>,
+    created: Instant,
+    /// Last failure evaluating each breakpoint's condition, keyed by breakpoint ID
+    condition_errors: RefCell<HashMap<String, String>>,
+    /// Variables of each scope in the paused call stack, keyed by scope object ID
+    scope_variables: HashMap<String, Vec<ScopeVariable>>,
+}
+
+/// One binding of a paused frame's scope, as `Runtime.getProperties` reports it
+#[derive(Debug, Clone, Serialize)]
+struct ScopeVariable {
+    name: String,
+    value: RemoteObject,
+    writable: bool,
+    configurable: bool,
+    enumerable: bool,
+}
+
+/// URL filter of a `Debugger.setBreakpointByUrl` request
+enum UrlMatcher {
+    Exact(String),
+    Regex(regex::Regex),
+}
+
+impl UrlMatcher {
+    fn matches(&self, url: &str) -> bool {
+        match self {
+            UrlMatcher::Exact(expected) => expected == url,
+            UrlMatcher::Regex(re) => re.is_match(url),
+        }
+    }
+}
+
+/// Breakpoint set by URL, kept so scripts loaded later can resolve it
+struct UrlBreakpoint {
+    id: String,
+    url_matcher: UrlMatcher,
+    line_number: u32,
+    column_number: Option<u32>,
+    condition: Option<String>,
 }
 
 impl DevToolsServer {
@@ -89,9 +166,18 @@ impl DevToolsServer {
             paused: false,
             call_stack: Vec::new(),
             scripts: HashMap::new(),
+            script_urls: HashMap::new(),
+            source_maps: HashMap::new(),
+            url_breakpoints: Vec::new(),
             next_script_id: 1,
             next_breakpoint_id: 1,
             next_object_id: 1,
+            network_enabled: false,
+            next_request_id: 1,
+            events: Vec::new(),
+            created: Instant::now(),
+            condition_errors: RefCell::new(HashMap::new()),
+            scope_variables: HashMap::new(),
         }
     }
 
@@ -102,14 +188,19 @@ impl DevToolsServer {
         match method {
             "Debugger.enable" => self.debugger_enable(message),
             "Debugger.setBreakpoint" => self.debugger_set_breakpoint(message),
+            "Debugger.setBreakpointByUrl" => self.debugger_set_breakpoint_by_url(message),
             "Debugger.removeBreakpoint" => self.debugger_remove_breakpoint(message),
             "Debugger.resume" => self.debugger_resume(message),
             "Debugger.stepOver" => self.debugger_step_over(message),
             "Debugger.stepInto" => self.debugger_step_into(message),
             "Debugger.stepOut" => self.debugger_step_out(message),
             "Debugger.pause" => self.debugger_pause(message),
+            "Debugger.setVariableValue" => self.debugger_set_variable_value(message),
+            "Debugger.evaluateOnCallFrame" => self.debugger_evaluate_on_call_frame(message),
             "Runtime.evaluate" => self.runtime_evaluate(message),
             "Runtime.getProperties" => self.runtime_get_properties(message),
+            "Network.enable" => self.network_set_enabled(message, true),
+            "Network.disable" => self.network_set_enabled(message, false),
             _ => self.method_not_found(message),
         }
     }
@@ -154,10 +245,182 @@ impl DevToolsServer {
         }
     }
 
+    fn debugger_set_breakpoint_by_url(&mut self, msg: &ProtocolMessage) -> ProtocolMessage {
+        let params = match &msg.params {
+            Some(params) => params,
+            None => return self.error_response(msg, "Missing params"),
+        };
+        let url_matcher = if let Some(pattern) = params["urlRegex"].as_str() {
+            match regex::Regex::new(pattern) {
+                Ok(re) => UrlMatcher::Regex(re),
+                Err(e) => return self.error_response(msg, &format!("Invalid urlRegex: {}", e)),
+            }
+        } else if let Some(url) = params["url"].as_str() {
+            UrlMatcher::Exact(url.to_string())
+        } else {
+            return self.error_response(msg, "Either url or urlRegex must be specified");
+        };
+        let line_number = params["lineNumber"].as_u64().unwrap_or(0) as u32;
+        let column_number = params["columnNumber"].as_u64().map(|n| n as u32);
+        let condition = params.get("condition").and_then(|c| c.as_str()).map(String::from);
+
+        let bp_id = format!("bp_{}", self.next_breakpoint_id);
+        self.next_breakpoint_id += 1;
+
+        let locations = self.resolve_url_location(&url_matcher, line_number, column_number);
+        for location in &locations {
+            self.insert_resolved_breakpoint(&bp_id, location, &condition);
+        }
+        self.url_breakpoints.push(UrlBreakpoint {
+            id: bp_id.clone(),
+            url_matcher,
+            line_number,
+            column_number,
+            condition,
+        });
+
+        ProtocolMessage {
+            id: msg.id,
+            method: None,
+            params: None,
+            result: Some(json!({
+                "breakpointId": bp_id,
+                "locations": locations
+                    .iter()
+                    .map(|l| json!({
+                        "scriptId": l.script_id,
+                        "lineNumber": l.line_number,
+                        "columnNumber": l.column_number
+                    }))
+                    .collect::<Vec<_>>()
+            })),
+            error: None,
+        }
+    }
+
+    /// Find the generated locations for a breakpoint on `url` at an original position
+    ///
+    /// A script whose own URL matches is used as-is; otherwise a matching source
+    /// in the script's source map is translated to its generated position.
+    fn resolve_url_location(
+        &self,
+        url_matcher: &UrlMatcher,
+        line_number: u32,
+        column_number: Option<u32>,
+    ) -> Vec<Location> {
+        let mut script_ids: Vec<&String> = self.scripts.keys().collect();
+        script_ids.sort();
+
+        script_ids
+            .into_iter()
+            .filter_map(|script_id| {
+                self.resolve_in_script(script_id, url_matcher, line_number, column_number)
+            })
+            .collect()
+    }
+
+    /// Generated location in `script_id` for a breakpoint on `url`, if the
+    /// script or one of its source-mapped sources matches
+    fn resolve_in_script(
+        &self,
+        script_id: &str,
+        url_matcher: &UrlMatcher,
+        line_number: u32,
+        column_number: Option<u32>,
+    ) -> Option<Location> {
+        if self.script_urls.get(script_id).is_some_and(|url| url_matcher.matches(url)) {
+            return Some(Location {
+                script_id: script_id.to_string(),
+                line_number,
+                column_number: column_number.unwrap_or(0),
+            });
+        }
+
+        let source_map = self.source_maps.get(script_id)?;
+        // Without a column, take the first mapping on the original line
+        let column = column_number.unwrap_or(u32::MAX);
+        source_map
+            .sources
+            .iter()
+            .filter(|source| url_matcher.matches(source))
+            .find_map(|source| source_map.generated_position_for(source, line_number, column))
+            .map(|generated| Location {
+                script_id: script_id.to_string(),
+                line_number: generated.line,
+                column_number: generated.column,
+            })
+    }
+
+    /// Store a concrete breakpoint at `location` for protocol breakpoint `bp_id`
+    fn insert_resolved_breakpoint(
+        &mut self,
+        bp_id: &str,
+        location: &Location,
+        condition: &Option<String>,
+    ) {
+        // Every resolved location shares the protocol breakpoint ID
+        let resolved = self.breakpoints.values().filter(|bp| bp.id == bp_id).count();
+        let key = if resolved == 0 { bp_id.to_string() } else { format!("{}.{}", bp_id, resolved) };
+        self.breakpoints.insert(
+            key,
+            Breakpoint {
+                id: bp_id.to_string(),
+                script_id: location.script_id.clone(),
+                line_number: location.line_number,
+                column_number: Some(location.column_number),
+                condition: condition.clone(),
+                enabled: true,
+            },
+        );
+    }
+
+    /// Resolve pending URL breakpoints against a newly loaded or source-mapped
+    /// script, emitting `Debugger.breakpointResolved` for each new location
+    fn resolve_url_breakpoints(&mut self, script_id: &str) -> Vec<Location> {
+        let mut resolved = Vec::new();
+        for url_bp in &self.url_breakpoints {
+            let already_resolved = self
+                .breakpoints
+                .values()
+                .any(|bp| bp.id == url_bp.id && bp.script_id == script_id);
+            if already_resolved {
+                continue;
+            }
+            if let Some(location) = self.resolve_in_script(
+                script_id,
+                &url_bp.url_matcher,
+                url_bp.line_number,
+                url_bp.column_number,
+            ) {
+                resolved.push((url_bp.id.clone(), url_bp.condition.clone(), location));
+            }
+        }
+
+        resolved
+            .into_iter()
+            .map(|(bp_id, condition, location)| {
+                self.insert_resolved_breakpoint(&bp_id, &location, &condition);
+                self.emit(
+                    "Debugger.breakpointResolved",
+                    json!({
+                        "breakpointId": bp_id,
+                        "location": {
+                            "scriptId": location.script_id,
+                            "lineNumber": location.line_number,
+                            "columnNumber": location.column_number
+                        }
+                    }),
+                );
+                location
+            })
+            .collect()
+    }
+
     fn debugger_remove_breakpoint(&mut self, msg: &ProtocolMessage) -> ProtocolMessage {
         if let Some(params) = &msg.params {
             if let Some(bp_id) = params["breakpointId"].as_str() {
-                self.breakpoints.remove(bp_id);
+                self.breakpoints.retain(|_, bp| bp.id != bp_id);
+                self.url_breakpoints.retain(|bp| bp.id != bp_id);
             }
         }
 
@@ -173,6 +436,7 @@ impl DevToolsServer {
     fn debugger_resume(&mut self, msg: &ProtocolMessage) -> ProtocolMessage {
         self.paused = false;
         self.call_stack.clear();
+        self.scope_variables.clear();
 
         ProtocolMessage {
             id: msg.id,
@@ -228,6 +492,97 @@ impl DevToolsServer {
         }
     }
 
+    fn debugger_set_variable_value(&mut self, msg: &ProtocolMessage) -> ProtocolMessage {
+        if !self.paused {
+            return self.error_response(msg, "Can only perform operation while paused");
+        }
+
+        let params = msg.params.as_ref().cloned().unwrap_or(JsonValue::Null);
+        let frame_id = params["callFrameId"].as_str().unwrap_or("");
+        let variable_name = params["variableName"].as_str().unwrap_or("");
+        let Some(new_value) = params["newValue"].get("value").cloned() else {
+            return self.error_response(msg, "Invalid newValue: expected a value");
+        };
+        let Some(scope_number) = params["scopeNumber"].as_u64().map(|n| n as usize) else {
+            return self.error_response(msg, "Invalid scopeNumber");
+        };
+
+        let Some(frame) = self.call_stack.iter_mut().find(|f| f.call_frame_id == frame_id) else {
+            return self.error_response(msg, "Could not find call frame with given id");
+        };
+        let Some(scope) = frame.scope_chain.get_mut(scope_number) else {
+            return self.error_response(msg, "Could not find scope with given number");
+        };
+
+        if scope.const_bindings.iter().any(|name| name == variable_name) {
+            return self.error_response(msg, "TypeError: Assignment to constant variable.");
+        }
+
+        match scope.object.value.as_mut().and_then(|v| v.get_mut(variable_name)) {
+            Some(slot) => *slot = new_value,
+            None => return self.error_response(msg, "Could not find variable with given name"),
+        }
+
+        ProtocolMessage {
+            id: msg.id,
+            method: None,
+            params: None,
+            result: Some(json!({})),
+            error: None,
+        }
+    }
+
+    fn debugger_evaluate_on_call_frame(&mut self, msg: &ProtocolMessage) -> ProtocolMessage {
+        if !self.paused {
+            return self.error_response(msg, "Can only perform operation while paused");
+        }
+
+        let params = msg.params.as_ref().cloned().unwrap_or(JsonValue::Null);
+        let frame_id = params["callFrameId"].as_str().unwrap_or("");
+        let expression = params["expression"].as_str().unwrap_or("");
+
+        let Some(frame) = self.call_stack.iter().find(|f| f.call_frame_id == frame_id) else {
+            return self.error_response(msg, "Could not find call frame with given id");
+        };
+
+        let result = match evaluate_on_frame(frame, expression) {
+            Ok(value) => json!({ "result": value }),
+            Err(err) => {
+                let class_name = format!("{:?}", err.kind);
+                let description = format!("{}: {}", class_name, err.message);
+                let exception = RemoteObject {
+                    object_type: "object".to_string(),
+                    value: None,
+                    description: Some(description.clone()),
+                    object_id: Some(self.next_object_id()),
+                };
+                json!({
+                    "result": exception,
+                    "exceptionDetails": {
+                        "exceptionId": 1,
+                        "text": "Uncaught",
+                        "lineNumber": 0,
+                        "columnNumber": 0,
+                        "exception": {
+                            "type": "object",
+                            "subtype": "error",
+                            "className": class_name,
+                            "description": description,
+                        },
+                    },
+                })
+            }
+        };
+
+        ProtocolMessage {
+            id: msg.id,
+            method: None,
+            params: None,
+            result: Some(result),
+            error: None,
+        }
+    }
+
     fn runtime_evaluate(&mut self, msg: &ProtocolMessage) -> ProtocolMessage {
         let expression = msg.params.as_ref()
             .and_then(|p| p["expression"].as_str())
@@ -260,15 +615,61 @@ impl DevToolsServer {
     }
 
     fn runtime_get_properties(&mut self, msg: &ProtocolMessage) -> ProtocolMessage {
+        let object_id = msg.params.as_ref()
+            .and_then(|p| p["objectId"].as_str())
+            .unwrap_or("");
+        let properties = self.scope_variables.get(object_id).cloned().unwrap_or_default();
+
         ProtocolMessage {
             id: msg.id,
             method: None,
             params: None,
-            result: Some(json!({ "result": [] })),
+            result: Some(json!({ "result": properties })),
             error: None,
         }
     }
 
+    fn network_set_enabled(&mut self, msg: &ProtocolMessage, enabled: bool) -> ProtocolMessage {
+        self.network_enabled = enabled;
+
+        ProtocolMessage {
+            id: msg.id,
+            method: None,
+            params: None,
+            result: Some(json!({})),
+            error: None,
+        }
+    }
+
+    /// Queue a notification for the front-end
+    fn emit(&mut self, method: &str, params: JsonValue) {
+        self.events.push(ProtocolMessage {
+            id: None,
+            method: Some(method.to_string()),
+            params: Some(params),
+            result: None,
+            error: None,
+        });
+    }
+
+    /// Seconds since the server was created, used as the CDP monotonic timestamp
+    fn timestamp(&self) -> f64 {
+        self.created.elapsed().as_secs_f64()
+    }
+
+    fn error_response(&self, msg: &ProtocolMessage, message: &str) -> ProtocolMessage {
+        ProtocolMessage {
+            id: msg.id,
+            method: None,
+            params: None,
+            result: None,
+            error: Some(ProtocolError {
+                code: -32000,
+                message: message.to_string(),
+            }),
+        }
+    }
+
     fn method_not_found(&self, msg: &ProtocolMessage) -> ProtocolMessage {
         ProtocolMessage {
             id: msg.id,
@@ -307,6 +708,35 @@ impl DevToolsServer {
         id
     }
 
+    /// Add script loaded from `url` and return its ID
+    ///
+    /// Breakpoints already set on a matching URL are resolved in the script;
+    /// [`DevToolsServer::add_script_with_url_resolving`] also returns where.
+    pub fn add_script_with_url(&mut self, source: String, url: &str) -> String {
+        self.add_script_with_url_resolving(source, url).0
+    }
+
+    /// Add script loaded from `url`, returning its ID and the locations where
+    /// breakpoints set by URL were resolved in it
+    ///
+    /// Each location is also reported with a `Debugger.breakpointResolved` event.
+    pub fn add_script_with_url_resolving(
+        &mut self,
+        source: String,
+        url: &str,
+    ) -> (String, Vec<Location>) {
+        let id = self.add_script(source);
+        self.script_urls.insert(id.clone(), url.to_string());
+        let locations = self.resolve_url_breakpoints(&id);
+        (id, locations)
+    }
+
+    /// Attach a source map to a script so URL breakpoints on its original sources resolve
+    pub fn register_source_map(&mut self, script_id: &str, source_map: SourceMap) {
+        self.source_maps.insert(script_id.to_string(), source_map);
+        self.resolve_url_breakpoints(script_id);
+    }
+
     /// Get script source by ID
     pub fn get_script(&self, script_id: &str) -> Option<&String> {
         self.scripts.get(script_id)
@@ -319,6 +749,137 @@ impl DevToolsServer {
         })
     }
 
+    /// Check if we should pause at location, evaluating breakpoint conditions
+    /// against `scope`
+    ///
+    /// `scope` holds the paused frame's bindings as VM values. A breakpoint
+    /// with a condition only pauses when the condition, run on a VM with
+    /// those bindings as globals, is truthy. Like Chrome, a condition that
+    /// fails to parse or throws pauses anyway; the error is kept for
+    /// [`DevToolsServer::condition_error`].
+    pub fn should_pause_at_with_scope(
+        &self,
+        script_id: &str,
+        line: u32,
+        scope: &HashMap<String, Value>,
+    ) -> bool {
+        let mut pause = false;
+        for bp in self.breakpoints.values() {
+            if !(bp.enabled && bp.script_id == script_id && bp.line_number == line) {
+                continue;
+            }
+            let Some(condition) = bp.condition.as_deref().filter(|c| !c.trim().is_empty()) else {
+                pause = true;
+                continue;
+            };
+            let bindings = scope.iter().map(|(name, value)| (name.clone(), value.clone()));
+            match evaluate_in_scope(bindings, condition) {
+                Ok(value) => {
+                    self.condition_errors.borrow_mut().remove(&bp.id);
+                    pause |= value.is_truthy();
+                }
+                Err(err) => {
+                    let message = format!("{:?}: {}", err.kind, err.message);
+                    self.condition_errors.borrow_mut().insert(bp.id.clone(), message);
+                    pause = true;
+                }
+            }
+        }
+        pause
+    }
+
+    /// Error from the last evaluation of a breakpoint's condition, if it failed
+    pub fn condition_error(&self, breakpoint_id: &str) -> Option<String> {
+        self.condition_errors.borrow().get(breakpoint_id).cloned()
+    }
+
+    /// Pause execution with `frames` as the call stack, innermost first
+    ///
+    /// Each frame gets a local scope, a closure scope if it captured any
+    /// variables, and the global scope holding `globals`. A scope object's
+    /// `value` carries the bindings as JSON for `Debugger.evaluateOnCallFrame`
+    /// and `Debugger.setVariableValue`; its `objectId` lists them as remote
+    /// objects through `Runtime.getProperties`. Emits `Debugger.paused`, whose
+    /// scopes also include those `variables`.
+    pub fn pause(&mut self, reason: &str, frames: Vec<PausedFrame>, globals: &[(String, JsValue)]) {
+        self.call_stack.clear();
+        self.scope_variables.clear();
+        for (index, frame) in frames.into_iter().enumerate() {
+            let consts = &frame.const_bindings;
+            let mut scope_chain = vec![self.build_scope("local", &frame.locals, consts)];
+            if !frame.closure.is_empty() {
+                scope_chain.push(self.build_scope("closure", &frame.closure, consts));
+            }
+            scope_chain.push(self.build_scope("global", globals, &[]));
+            self.call_stack.push(CallFrame {
+                call_frame_id: format!("frame_{}", index),
+                function_name: frame.function_name,
+                location: frame.location,
+                scope_chain,
+            });
+        }
+        self.paused = true;
+
+        let call_frames: Vec<JsonValue> = self.call_stack.iter().map(|frame| {
+            let mut value = json!(frame);
+            for scope in value["scope_chain"].as_array_mut().into_iter().flatten() {
+                let variables = scope["object"]["object_id"].as_str()
+                    .and_then(|id| self.scope_variables.get(id))
+                    .cloned()
+                    .unwrap_or_default();
+                scope["variables"] = json!(variables);
+            }
+            value
+        }).collect();
+        self.emit("Debugger.paused", json!({
+            "callFrames": call_frames,
+            "reason": reason,
+        }));
+    }
+
+    /// Build a scope object over `bindings`, recording its variables for `Runtime.getProperties`
+    fn build_scope(
+        &mut self,
+        scope_type: &str,
+        bindings: &[(String, JsValue)],
+        const_bindings: &[String],
+    ) -> Scope {
+        let mut values = serde_json::Map::new();
+        let mut variables = Vec::new();
+        for (name, value) in bindings {
+            let remote = self.js_value_to_remote_object(value);
+            if let Some(json_value) = &remote.value {
+                values.insert(name.clone(), json_value.clone());
+            }
+            variables.push(ScopeVariable {
+                name: name.clone(),
+                value: remote,
+                writable: !const_bindings.contains(name),
+                configurable: false,
+                enumerable: true,
+            });
+        }
+
+        let object_id = self.next_object_id();
+        self.scope_variables.insert(object_id.clone(), variables);
+        let mut description = scope_type.to_string();
+        description[..1].make_ascii_uppercase();
+        Scope {
+            scope_type: scope_type.to_string(),
+            object: RemoteObject {
+                object_type: "object".to_string(),
+                value: Some(JsonValue::Object(values)),
+                description: Some(description),
+                object_id: Some(object_id),
+            },
+            const_bindings: bindings
+                .iter()
+                .filter(|(name, _)| const_bindings.contains(name))
+                .map(|(name, _)| name.clone())
+                .collect(),
+        }
+    }
+
     /// Push a call frame onto the stack
     pub fn push_call_frame(&mut self, frame: CallFrame) {
         self.call_stack.push(frame);
@@ -334,6 +895,124 @@ impl DevToolsServer {
         self.paused = paused;
     }
 
+    /// Check if the Network domain is enabled
+    pub fn is_network_enabled(&self) -> bool {
+        self.network_enabled
+    }
+
+    /// Report that a fetch request is about to be sent, returning its request ID
+    ///
+    /// Emits `Network.requestWillBeSent` when the Network domain is enabled.
+    /// Pass the returned ID to [`DevToolsServer::notify_response`] and
+    /// [`DevToolsServer::notify_loading_finished`].
+    pub fn notify_request(&mut self, request: &FetchRequest) -> String {
+        let request_id = format!("req_{}", self.next_request_id);
+        self.next_request_id += 1;
+
+        if self.network_enabled {
+            let params = json!({
+                "requestId": request_id,
+                "documentURL": request.url,
+                "request": {
+                    "url": request.url,
+                    "method": request.method.to_string(),
+                    "headers": request.headers,
+                },
+                "timestamp": self.timestamp(),
+            });
+            self.emit("Network.requestWillBeSent", params);
+        }
+        request_id
+    }
+
+    /// Report the response headers for a request
+    ///
+    /// Emits `Network.responseReceived` when the Network domain is enabled.
+    pub fn notify_response(&mut self, request_id: &str, response: &FetchResponse) {
+        if self.network_enabled {
+            let params = json!({
+                "requestId": request_id,
+                "timestamp": self.timestamp(),
+                "type": "Fetch",
+                "response": {
+                    "url": response.url,
+                    "status": response.status,
+                    "statusText": response.status_text,
+                    "headers": response.headers,
+                },
+            });
+            self.emit("Network.responseReceived", params);
+        }
+    }
+
+    /// Report that a request's body has been fully received
+    ///
+    /// Emits `Network.loadingFinished` when the Network domain is enabled.
+    pub fn notify_loading_finished(&mut self, request_id: &str, encoded_data_length: usize) {
+        if self.network_enabled {
+            let params = json!({
+                "requestId": request_id,
+                "timestamp": self.timestamp(),
+                "encodedDataLength": encoded_data_length,
+            });
+            self.emit("Network.loadingFinished", params);
+        }
+    }
+
+    /// Report a console API call made by script
+    ///
+    /// Emits `Runtime.consoleAPICalled` with each argument as a `RemoteObject`.
+    pub fn notify_console_api_called(&mut self, call_type: &str, args: &[JsValue]) {
+        let args: Vec<RemoteObject> =
+            args.iter().map(|arg| self.js_value_to_remote_object(arg)).collect();
+        // Runtime.Timestamp is milliseconds since the epoch
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs_f64() * 1000.0)
+            .unwrap_or(0.0);
+        let params = json!({
+            "type": call_type,
+            "args": args,
+            "executionContextId": 1,
+            "timestamp": timestamp,
+        });
+        self.emit("Runtime.consoleAPICalled", params);
+    }
+
+    fn js_value_to_remote_object(&mut self, value: &JsValue) -> RemoteObject {
+        let object_type = value.type_of();
+        let (json_value, description) = match value {
+            JsValue::Undefined => (None, None),
+            JsValue::Null => (Some(JsonValue::Null), Some("null".to_string())),
+            JsValue::Boolean(b) => (Some(json!(b)), Some(b.to_string())),
+            JsValue::Number(n) => (number_to_json(*n), Some(format_number(*n))),
+            JsValue::String(s) => (Some(json!(s)), Some(s.clone())),
+            JsValue::BigInt(n) => (None, Some(format!("{}n", n.to_string()))),
+            JsValue::Symbol(_) | JsValue::Error(_) => (None, Some(value.to_js_string())),
+            JsValue::Array(items) => {
+                (None, Some(format!("Array({})", items.borrow().elements.len())))
+            }
+            JsValue::Function(_) | JsValue::Constructor(_) => (None, Some("function".to_string())),
+            _ => (None, Some("Object".to_string())),
+        };
+        // Only non-null objects and functions can be inspected further
+        let object_id = match (object_type, &json_value) {
+            ("object", None) | ("function", _) => Some(self.next_object_id()),
+            _ => None,
+        };
+        RemoteObject {
+            object_type: object_type.to_string(),
+            value: json_value,
+            description,
+            object_id,
+        }
+    }
+
+    /// Take the notifications queued since the last call
+    pub fn take_events(&mut self) -> Vec<ProtocolMessage> {
+        std::mem::take(&mut self.events)
+    }
+
     /// Get the next object ID for remote objects
     pub fn next_object_id(&mut self) -> String {
         let id = format!("obj_{}", self.next_object_id);
@@ -351,6 +1030,239 @@ impl Default for DevToolsServer {
 /// Type alias for convenience
 pub type DebugProtocol = DevToolsServer;
 
+/// Forwards a script's console calls to a [`DevToolsServer`]
+///
+/// Install it on a `ConsoleObject` with `set_observer` so that every call is
+/// reported as a `Runtime.consoleAPICalled` notification.
+pub struct DevToolsConsoleObserver {
+    server: Rc<RefCell<DevToolsServer>>,
+}
+
+impl DevToolsConsoleObserver {
+    /// Create an observer reporting to `server`
+    pub fn new(server: Rc<RefCell<DevToolsServer>>) -> Self {
+        Self { server }
+    }
+}
+
+impl ConsoleObserver for DevToolsConsoleObserver {
+    fn console_api_called(&self, call_type: &str, args: &[JsValue]) {
+        self.server.borrow_mut().notify_console_api_called(call_type, args);
+    }
+}
+
+/// Pauses a [`DevToolsServer`] on each `debugger` statement a VM reaches
+///
+/// Install it with `VM::set_debugger_hook`; the paused frame's locals and
+/// captured variables become the scope chain of `Debugger.paused`.
+pub struct DevToolsDebuggerHook {
+    server: Rc<RefCell<DevToolsServer>>,
+    script_id: String,
+}
+
+impl DevToolsDebuggerHook {
+    /// Create a hook pausing `server` in the script `script_id`
+    pub fn new(server: Rc<RefCell<DevToolsServer>>, script_id: impl Into<String>) -> Self {
+        Self { server, script_id: script_id.into() }
+    }
+}
+
+impl DebuggerHook for DevToolsDebuggerHook {
+    fn paused(&self, frame: &FrameSnapshot, globals: &[(String, JsValue)]) {
+        // The VM counts from 1, the protocol from 0
+        let frame = PausedFrame {
+            function_name: String::new(),
+            location: Location {
+                script_id: self.script_id.clone(),
+                line_number: frame.line.saturating_sub(1),
+                column_number: frame.column.saturating_sub(1),
+            },
+            locals: frame.locals.clone(),
+            closure: frame.closure.clone(),
+            const_bindings: Vec::new(),
+        };
+        self.server.borrow_mut().pause("debugger", vec![frame], globals);
+    }
+}
+
+// Call frame evaluation
+//
+// While paused, the front-end only has the JSON snapshot of each frame's
+// scopes, so `Debugger.evaluateOnCallFrame` runs the expression on a fresh VM
+// whose globals are those bindings.
+
+/// Longest expression `Debugger.evaluateOnCallFrame` accepts
+///
+/// The parser and VM recurse once per level of nesting and an expression can
+/// nest once per character, so this bounds the stack an evaluation needs.
+const MAX_EXPRESSION_LENGTH: usize = 2048;
+
+/// Stack of the thread an expression is evaluated on, enough for the deepest
+/// nesting [`MAX_EXPRESSION_LENGTH`] allows in a debug build
+const EVALUATION_STACK_SIZE: usize = 512 * 1024 * 1024;
+
+/// Deepest object nesting copied into the JSON `value` of a result; deeper
+/// objects, and cycles, are left out
+const MAX_RESULT_DEPTH: usize = 16;
+
+/// Evaluate `expression` with the bindings of `frame`'s scopes as globals,
+/// inner scopes shadowing outer ones
+fn evaluate_on_frame(frame: &CallFrame, expression: &str) -> Result<RemoteObject, JsError> {
+    if expression.len() > MAX_EXPRESSION_LENGTH {
+        let message = format!("Expression is longer than {} bytes", MAX_EXPRESSION_LENGTH);
+        return Err(evaluation_error(ErrorKind::RangeError, message));
+    }
+    // Outermost scope first, so inner bindings overwrite outer ones
+    let bindings: Vec<(String, JsonValue)> = frame
+        .scope_chain
+        .iter()
+        .rev()
+        .filter_map(|scope| scope.object.value.as_ref()?.as_object())
+        .flat_map(|values| values.iter().map(|(name, value)| (name.clone(), value.clone())))
+        .collect();
+    let expression = expression.to_string();
+    let thread = std::thread::Builder::new()
+        .stack_size(EVALUATION_STACK_SIZE)
+        .spawn(move || {
+            let scope = bindings.into_iter().map(|(name, value)| (name, json_to_value(&value)));
+            evaluate_in_scope(scope, &expression).map(|value| value_to_remote_object(&value))
+        })
+        .map_err(|err| evaluation_error(ErrorKind::InternalError, err.to_string()))?;
+    thread.join().unwrap_or_else(|_| {
+        Err(evaluation_error(ErrorKind::InternalError, "Evaluation panicked".to_string()))
+    })
+}
+
+/// Run `source` on a fresh VM whose globals are `bindings`, set in order
+///
+/// `bindings` is only consumed once the VM exists, so it may build objects
+/// with `ObjectView::build`, which needs a VM on the current thread.
+fn evaluate_in_scope(
+    bindings: impl IntoIterator<Item = (String, Value)>,
+    source: &str,
+) -> Result<Value, JsError> {
+    let program = parser::Parser::new(source).parse()?;
+    let chunk = parser::BytecodeGenerator::new().generate(&program)?;
+    let mut vm = VM::new();
+    for (name, value) in bindings {
+        vm.set_global(name, value);
+    }
+    vm.execute(&chunk)
+}
+
+fn evaluation_error(kind: ErrorKind, message: String) -> JsError {
+    JsError { kind, message, stack: Vec::new(), source_position: None }
+}
+
+/// A JSON binding as a VM value, building objects and arrays with the VM
+/// registered on the current thread
+fn json_to_value(json: &JsonValue) -> Value {
+    match json {
+        JsonValue::Null => Value::Null,
+        JsonValue::Bool(b) => Value::Boolean(*b),
+        JsonValue::Number(n) => match n.as_i64().and_then(|n| i32::try_from(n).ok()) {
+            Some(n) => Value::Smi(n),
+            None => Value::Double(n.as_f64().unwrap_or(f64::NAN)),
+        },
+        JsonValue::String(s) => Value::String(s.clone()),
+        JsonValue::Array(items) => ObjectView::Array {
+            elements: items.iter().map(json_to_value).collect(),
+            length: items.len(),
+        }
+        .build()
+        .unwrap_or(Value::Undefined),
+        JsonValue::Object(map) => {
+            let entries = map.iter().map(|(key, value)| (key.clone(), json_to_value(value)));
+            ObjectView::Object(entries.collect()).build().unwrap_or(Value::Undefined)
+        }
+    }
+}
+
+/// Describe an evaluation result as a remote object
+fn value_to_remote_object(value: &Value) -> RemoteObject {
+    let (object_type, description) = match value {
+        Value::Undefined => (value.type_of(), None),
+        Value::Null => (value.type_of(), Some("null".to_string())),
+        Value::Boolean(b) => (value.type_of(), Some(b.to_string())),
+        Value::Smi(n) => (value.type_of(), Some(n.to_string())),
+        Value::Double(n) => (value.type_of(), Some(format_number(*n))),
+        Value::String(s) => (value.type_of(), Some(format!("\"{}\"", s))),
+        Value::BigInt(n) => (value.type_of(), Some(format!("{}n", n))),
+        // Closures are heap objects in the VM
+        Value::HeapObject(_) | Value::NativeFunction(_) => {
+            ("function".to_string(), Some("function".to_string()))
+        }
+        Value::NativeObject(_) => {
+            let description = match value.object_view() {
+                Some(ObjectView::Array { length, .. }) => format!("Array({})", length),
+                _ => "Object".to_string(),
+            };
+            (value.type_of(), Some(description))
+        }
+    };
+    RemoteObject {
+        object_type,
+        value: value_to_json(value, MAX_RESULT_DEPTH),
+        description,
+        object_id: None,
+    }
+}
+
+/// The JSON form of `value`, following objects `depth` levels deep; like
+/// `JSON.stringify`, values without one are dropped from objects and become
+/// null in arrays
+fn value_to_json(value: &Value, depth: usize) -> Option<JsonValue> {
+    match value {
+        Value::Null => Some(JsonValue::Null),
+        Value::Boolean(b) => Some(json!(b)),
+        Value::Smi(n) => Some(json!(n)),
+        Value::Double(n) => number_to_json(*n),
+        Value::String(s) => Some(json!(s)),
+        Value::NativeObject(_) if depth > 0 => match value.object_view()? {
+            ObjectView::Array { elements, .. } => Some(JsonValue::Array(
+                elements
+                    .iter()
+                    .map(|element| value_to_json(element, depth - 1).unwrap_or(JsonValue::Null))
+                    .collect(),
+            )),
+            ObjectView::Object(entries) => Some(JsonValue::Object(
+                entries
+                    .iter()
+                    .filter_map(|(key, value)| {
+                        Some((key.clone(), value_to_json(value, depth - 1)?))
+                    })
+                    .collect(),
+            )),
+        },
+        _ => None,
+    }
+}
+
+/// Format a number the way JavaScript's `String(n)` does for common values
+fn format_number(n: f64) -> String {
+    if n.is_nan() {
+        "NaN".to_string()
+    } else if n.is_infinite() {
+        if n > 0.0 { "Infinity" } else { "-Infinity" }.to_string()
+    } else if n.fract() == 0.0 && n.abs() < 1e21 {
+        format!("{}", n as i64)
+    } else {
+        n.to_string()
+    }
+}
+
+/// Integral numbers serialize without a fractional part; NaN and Infinity
+/// have no JSON form and are only carried in the description
+fn number_to_json(n: f64) -> Option<JsonValue> {
+    if !n.is_finite() {
+        None
+    } else if n.fract() == 0.0 && n.abs() < 9_007_199_254_740_992.0 {
+        Some(json!(n as i64))
+    } else {
+        Some(json!(n))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
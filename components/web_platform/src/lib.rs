@@ -13,16 +13,22 @@ pub mod structured_clone;
 pub mod service_worker;
 
 // Re-export main types
-pub use workers::{Worker, SharedArrayBuffer, Atomics};
-pub use wasm::{WebAssembly, WasmModule, WasmInstance};
-pub use devtools::{DevToolsServer, DebugProtocol};
+pub use workers::{Worker, SharedArrayBuffer, Atomics, WaitResult};
+pub use wasm::{WebAssembly, WasmModule, WasmInstance, FuncType, WasmValidationError, WasmMemory, WasmError};
+pub use devtools::{
+    DevToolsConsoleObserver, DevToolsDebuggerHook, DevToolsServer, DebugProtocol, PausedFrame,
+};
 pub use source_maps::SourceMap;
 pub use csp::ContentSecurityPolicy;
-pub use same_origin::{Origin, OpaqueOrigin, SameOriginPolicy, OriginError};
-pub use structured_clone::{StructuredClone, StructuredValue, CloneError, CloneOptions};
+pub use same_origin::{AnyOrigin, Origin, OpaqueOrigin, SameOriginPolicy, OriginError};
+pub use structured_clone::{
+    StructuredClone, StructuredValue, CloneError, CloneOptions, ArrayBuffer, MessagePort,
+    Transferable,
+};
 pub use service_worker::{
     ServiceWorker, ServiceWorkerState, ServiceWorkerRegistration, ServiceWorkerContainer,
     ServiceWorkerError, RegistrationOptions, UpdateViaCache,
     FetchRequest, FetchResponse, FetchEvent, RequestMethod, RequestMode, RequestDestination,
-    ResponseType, Cache, CacheStorage, FetchEventHandler,
+    ResponseType, Cache, CacheQueryOptions, CacheStorage, FetchEventHandler, Client, ClientType,
+    ClientQueryOptions, Clients, NavigationPreloadManager, NavigationPreloadState,
 };
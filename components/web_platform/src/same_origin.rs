@@ -3,10 +3,16 @@
 //! Implements origin comparison per HTML specification section 7.5
 //! https://html.spec.whatwg.org/multipage/origin.html
 
+use std::collections::HashSet;
 use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::OnceLock;
 
 /// Represents an origin tuple (scheme, host, port)
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+///
+/// Equality and hashing cover the tuple only; a relaxed `domain` is
+/// compared by [`Origin::is_same_origin_domain`].
+#[derive(Debug, Clone)]
 pub struct Origin {
     /// URL scheme (e.g., "https", "http", "file")
     pub scheme: String,
@@ -14,6 +20,8 @@ pub struct Origin {
     pub host: String,
     /// Port number (None means default port for scheme)
     pub port: Option<u16>,
+    /// Domain set through `document.domain`, if the origin has been relaxed
+    pub domain: Option<String>,
 }
 
 impl Origin {
@@ -23,6 +31,7 @@ impl Origin {
             scheme: scheme.into().to_lowercase(),
             host: host.into().to_lowercase(),
             port,
+            domain: None,
         }
     }
 
@@ -104,16 +113,66 @@ impl Origin {
 
     /// Check if this origin is same-origin-domain with another
     /// (includes document.domain relaxation)
-    pub fn is_same_origin_domain(&self, other: &Origin, domain: Option<&str>) -> bool {
-        // If both have the same domain set, compare domains
-        if let Some(d) = domain {
-            let d = d.to_lowercase();
-            return self.scheme == other.scheme
-                && self.host.ends_with(&d)
-                && other.host.ends_with(&d);
+    ///
+    /// Origins that both relaxed their domain match when the schemes and
+    /// domains are equal, regardless of host and port. An origin that has
+    /// not relaxed its domain only matches another that has not, and then
+    /// only when they are same-origin.
+    pub fn is_same_origin_domain(&self, other: &Origin) -> bool {
+        match (&self.domain, &other.domain) {
+            (Some(domain), Some(other_domain)) => {
+                self.scheme == other.scheme && domain == other_domain
+            }
+            (None, None) => self.is_same_origin(other),
+            _ => false,
         }
-        // Otherwise, fall back to same-origin
-        self.is_same_origin(other)
+    }
+
+    /// Relax this origin to `domain`, as setting `document.domain` does
+    ///
+    /// `domain` must be the host itself or a parent domain of it, and may not
+    /// be a public suffix such as `com`. IP address hosts cannot be relaxed.
+    pub fn with_relaxed_domain(&self, domain: &str) -> Result<Origin, OriginError> {
+        let domain = domain.trim().trim_end_matches('.').to_lowercase();
+        let invalid = |reason: &str| {
+            Err(OriginError::InvalidDomain(format!("{}: {}", domain, reason)))
+        };
+
+        if domain.is_empty() {
+            return invalid("empty domain");
+        }
+        if is_ip_address(&self.host) {
+            return invalid("cannot relax an IP address host");
+        }
+        if domain != self.host {
+            if !self.host.ends_with(&format!(".{}", domain)) {
+                return invalid(&format!("not a suffix of {}", self.host));
+            }
+            if is_public_suffix(&domain) {
+                return invalid("is a public suffix");
+            }
+        }
+
+        Ok(Origin {
+            domain: Some(domain),
+            ..self.clone()
+        })
+    }
+
+    /// The registrable domain of this origin's host: its public suffix plus
+    /// one more label (e.g. `example.co.uk` for `shop.example.co.uk`)
+    ///
+    /// IP address hosts and hosts that are themselves public suffixes have
+    /// no registrable domain.
+    pub fn registrable_domain(&self) -> Option<String> {
+        let host = self.host.trim_end_matches('.');
+        if host.is_empty() || is_ip_address(host) {
+            return None;
+        }
+        let suffix = public_suffix(host);
+        let owner = host.strip_suffix(suffix)?.strip_suffix('.')?;
+        let label = owner.rsplit('.').next()?;
+        Some(format!("{}.{}", label, suffix))
     }
 
     /// Serialize origin to string
@@ -132,6 +191,22 @@ impl Origin {
     }
 }
 
+impl PartialEq for Origin {
+    fn eq(&self, other: &Self) -> bool {
+        self.scheme == other.scheme && self.host == other.host && self.port == other.port
+    }
+}
+
+impl Eq for Origin {}
+
+impl Hash for Origin {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.scheme.hash(state);
+        self.host.hash(state);
+        self.port.hash(state);
+    }
+}
+
 impl fmt::Display for Origin {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.serialize())
@@ -150,6 +225,79 @@ fn default_port(scheme: &str) -> u16 {
     }
 }
 
+/// Whether `host` is an IPv4 or bracketed IPv6 address
+fn is_ip_address(host: &str) -> bool {
+    host.starts_with('[') || host.parse::<std::net::Ipv4Addr>().is_ok()
+}
+
+/// The Public Suffix List (https://publicsuffix.org/list/), ICANN and
+/// private sections, bundled with the runtime
+const PUBLIC_SUFFIX_LIST: &str = include_str!("../data/public_suffix_list.dat");
+
+/// Rules of the bundled Public Suffix List, by kind
+struct SuffixRules {
+    /// Plain rules, such as `co.uk`
+    exact: HashSet<&'static str>,
+    /// Wildcard rules without their `*.`, so `*.ck` is stored as `ck`
+    wildcard: HashSet<&'static str>,
+    /// Exception rules without their `!`, so `!www.ck` is stored as `www.ck`
+    exception: HashSet<&'static str>,
+}
+
+/// The bundled rules, parsed on first use
+fn suffix_rules() -> &'static SuffixRules {
+    static RULES: OnceLock<SuffixRules> = OnceLock::new();
+    RULES.get_or_init(|| {
+        let mut rules = SuffixRules {
+            exact: HashSet::new(),
+            wildcard: HashSet::new(),
+            exception: HashSet::new(),
+        };
+        // A rule is the first word of each line that is not a comment
+        let lines = PUBLIC_SUFFIX_LIST.lines().filter(|line| !line.starts_with("//"));
+        for rule in lines.filter_map(|line| line.split_whitespace().next()) {
+            if let Some(domain) = rule.strip_prefix('!') {
+                rules.exception.insert(domain);
+            } else if let Some(domain) = rule.strip_prefix("*.") {
+                rules.wildcard.insert(domain);
+            } else {
+                rules.exact.insert(rule);
+            }
+        }
+        rules
+    })
+}
+
+/// The public suffix of `host` under the Public Suffix List algorithm
+///
+/// The longest matching rule wins, an exception rule names the suffix one
+/// label shorter than itself, and a host no rule matches has its last label
+/// as its suffix (the implicit `*` rule).
+fn public_suffix(host: &str) -> &str {
+    let rules = suffix_rules();
+    let mut candidate = host;
+    loop {
+        let parent = candidate.split_once('.').map(|(_, rest)| rest);
+        if rules.exception.contains(candidate) {
+            return parent.unwrap_or(candidate);
+        }
+        if rules.exact.contains(candidate)
+            || parent.is_some_and(|parent| rules.wildcard.contains(parent))
+        {
+            return candidate;
+        }
+        match parent {
+            Some(rest) => candidate = rest,
+            None => return candidate,
+        }
+    }
+}
+
+/// Whether `domain` is a public suffix, under which anyone may register names
+fn is_public_suffix(domain: &str) -> bool {
+    public_suffix(domain) == domain
+}
+
 /// Represents an opaque origin (unique, cannot be same-origin with anything)
 #[derive(Debug, Clone)]
 pub struct OpaqueOrigin {
@@ -179,6 +327,27 @@ impl Default for OpaqueOrigin {
     }
 }
 
+/// Either a tuple origin or an opaque origin
+#[derive(Debug, Clone)]
+pub enum AnyOrigin {
+    /// A (scheme, host, port) origin
+    Tuple(Origin),
+    /// A unique opaque origin
+    Opaque(OpaqueOrigin),
+}
+
+impl From<Origin> for AnyOrigin {
+    fn from(origin: Origin) -> Self {
+        AnyOrigin::Tuple(origin)
+    }
+}
+
+impl From<OpaqueOrigin> for AnyOrigin {
+    fn from(origin: OpaqueOrigin) -> Self {
+        AnyOrigin::Opaque(origin)
+    }
+}
+
 /// Errors that can occur during origin operations
 #[derive(Debug, Clone, PartialEq)]
 pub enum OriginError {
@@ -186,6 +355,8 @@ pub enum OriginError {
     InvalidUrl(String),
     /// The origin is opaque (null)
     OpaqueOrigin,
+    /// The domain cannot be used to relax the origin
+    InvalidDomain(String),
     /// Cross-origin access denied
     CrossOriginDenied {
         source: String,
@@ -198,6 +369,7 @@ impl fmt::Display for OriginError {
         match self {
             OriginError::InvalidUrl(msg) => write!(f, "Invalid URL: {}", msg),
             OriginError::OpaqueOrigin => write!(f, "Opaque origin"),
+            OriginError::InvalidDomain(msg) => write!(f, "Invalid domain {}", msg),
             OriginError::CrossOriginDenied { source, target } => {
                 write!(
                     f,
@@ -254,6 +426,32 @@ impl SameOriginPolicy {
         }
     }
 
+    /// Check whether two origins are schemelessly same-site
+    ///
+    /// Hosts with a registrable domain match when those domains are equal;
+    /// other hosts, such as IP addresses, must be identical. Opaque origins
+    /// are never same-site with anything.
+    pub fn schemelessly_same_site(a: &AnyOrigin, b: &AnyOrigin) -> bool {
+        let (AnyOrigin::Tuple(a), AnyOrigin::Tuple(b)) = (a, b) else {
+            return false;
+        };
+        match a.registrable_domain() {
+            Some(site) => b.registrable_domain().as_deref() == Some(site.as_str()),
+            None => a.host == b.host,
+        }
+    }
+
+    /// Check whether two origins are same-site: schemelessly same-site and
+    /// with the same scheme
+    pub fn same_site(a: &AnyOrigin, b: &AnyOrigin) -> bool {
+        match (a, b) {
+            (AnyOrigin::Tuple(x), AnyOrigin::Tuple(y)) => {
+                x.scheme == y.scheme && Self::schemelessly_same_site(a, b)
+            }
+            _ => false,
+        }
+    }
+
     /// Check if a URL is same-origin
     pub fn is_same_origin_url(&self, url: &str) -> bool {
         Origin::parse(url)
@@ -354,6 +552,128 @@ mod tests {
         assert_eq!(origin.serialize(), "https://example.com:8443");
     }
 
+    #[test]
+    fn test_relaxed_domain_same_origin_domain() {
+        let a = Origin::parse("https://a.example.com").unwrap();
+        let b = Origin::parse("https://b.example.com:8443").unwrap();
+        assert!(!a.is_same_origin_domain(&b));
+
+        let relaxed_a = a.with_relaxed_domain("example.com").unwrap();
+        assert_eq!(relaxed_a.domain.as_deref(), Some("example.com"));
+        // Only one side relaxed: still cross-origin
+        assert!(!relaxed_a.is_same_origin_domain(&b));
+
+        let relaxed_b = b.with_relaxed_domain("EXAMPLE.com").unwrap();
+        assert!(relaxed_a.is_same_origin_domain(&relaxed_b));
+        // Relaxation does not affect strict same-origin checks
+        assert!(!relaxed_a.is_same_origin(&relaxed_b));
+
+        // Nor equality or hashing: the relaxed origin is still the same tuple
+        use std::collections::HashSet;
+        assert_eq!(relaxed_a, a);
+        assert!(HashSet::from([a.clone()]).contains(&relaxed_a));
+
+        let http = Origin::parse("http://c.example.com").unwrap();
+        let relaxed_http = http.with_relaxed_domain("example.com").unwrap();
+        assert!(!relaxed_a.is_same_origin_domain(&relaxed_http));
+    }
+
+    #[test]
+    fn test_relaxed_domain_rejects_invalid_domains() {
+        let origin = Origin::parse("https://a.example.com").unwrap();
+        assert!(matches!(
+            origin.with_relaxed_domain("other.com"),
+            Err(OriginError::InvalidDomain(_))
+        ));
+        assert!(origin.with_relaxed_domain("ample.com").is_err());
+        assert!(origin.with_relaxed_domain("com").is_err());
+        assert!(origin.with_relaxed_domain("a.example.com").is_ok());
+
+        let uk = Origin::parse("https://shop.example.co.uk").unwrap();
+        assert!(uk.with_relaxed_domain("co.uk").is_err());
+        assert!(uk.with_relaxed_domain("example.co.uk").is_ok());
+
+        let il = Origin::parse("https://shop.example.co.il").unwrap();
+        assert!(il.with_relaxed_domain("co.il").is_err());
+        assert!(il.with_relaxed_domain("example.co.il").is_ok());
+
+        let ip = Origin::parse("http://192.168.0.1").unwrap();
+        assert!(ip.with_relaxed_domain("168.0.1").is_err());
+    }
+
+    #[test]
+    fn test_registrable_domain() {
+        let origin = |url: &str| Origin::parse(url).unwrap();
+        assert_eq!(
+            origin("https://a.b.example.com").registrable_domain().as_deref(),
+            Some("example.com")
+        );
+        assert_eq!(
+            origin("https://shop.example.co.uk").registrable_domain().as_deref(),
+            Some("example.co.uk")
+        );
+        assert_eq!(
+            origin("https://user.github.io").registrable_domain().as_deref(),
+            Some("user.github.io")
+        );
+        assert_eq!(origin("https://co.uk").registrable_domain(), None);
+        assert_eq!(
+            origin("https://www.shop.example.co.il").registrable_domain().as_deref(),
+            Some("example.co.il")
+        );
+        // `*.ck` makes every second-level name a suffix, except `!www.ck`
+        assert_eq!(
+            origin("https://a.b.ck").registrable_domain().as_deref(),
+            Some("a.b.ck")
+        );
+        assert_eq!(
+            origin("https://www.ck").registrable_domain().as_deref(),
+            Some("www.ck")
+        );
+        assert_eq!(origin("http://localhost").registrable_domain(), None);
+        assert_eq!(origin("http://10.0.0.1").registrable_domain(), None);
+    }
+
+    #[test]
+    fn test_same_site_subdomains_and_schemes() {
+        let site = |url: &str| AnyOrigin::from(Origin::parse(url).unwrap());
+
+        let a = site("https://a.example.com");
+        let b = site("https://b.example.com:8443");
+        assert!(SameOriginPolicy::same_site(&a, &b));
+        assert!(SameOriginPolicy::schemelessly_same_site(&a, &b));
+
+        let https = site("https://example.com");
+        let http = site("http://example.com");
+        assert!(!SameOriginPolicy::same_site(&https, &http));
+        assert!(SameOriginPolicy::schemelessly_same_site(&https, &http));
+
+        let other = site("https://example.org");
+        assert!(!SameOriginPolicy::schemelessly_same_site(&https, &other));
+
+        let alice = site("https://alice.github.io");
+        let bob = site("https://bob.github.io");
+        assert!(!SameOriginPolicy::same_site(&alice, &bob));
+
+        let a = site("https://a.co.il");
+        let b = site("https://b.co.il");
+        assert!(!SameOriginPolicy::same_site(&a, &b));
+
+        let ip = site("http://10.0.0.1:80");
+        assert!(SameOriginPolicy::same_site(&ip, &site("http://10.0.0.1:8080")));
+        assert!(!SameOriginPolicy::same_site(&ip, &site("http://10.0.0.2")));
+    }
+
+    #[test]
+    fn test_opaque_origins_are_never_same_site() {
+        let opaque = AnyOrigin::from(Origin::opaque());
+        let tuple = AnyOrigin::from(Origin::parse("https://example.com").unwrap());
+        assert!(!SameOriginPolicy::same_site(&opaque, &opaque));
+        assert!(!SameOriginPolicy::schemelessly_same_site(&opaque, &opaque));
+        assert!(!SameOriginPolicy::same_site(&opaque, &tuple));
+        assert!(!SameOriginPolicy::schemelessly_same_site(&tuple, &opaque));
+    }
+
     #[test]
     fn test_ipv6_origin() {
         let origin = Origin::parse("https://[::1]:8080").unwrap();
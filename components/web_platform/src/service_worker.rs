@@ -7,7 +7,7 @@
 //! and fetch interception for web applications.
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex, RwLock};
 
@@ -45,6 +45,22 @@ impl ServiceWorkerState {
     pub fn is_terminal(&self) -> bool {
         matches!(self, ServiceWorkerState::Redundant)
     }
+
+    /// Check if a worker may move from this state to `next`
+    ///
+    /// Workers advance one step at a time through the lifecycle, and can
+    /// become redundant from any state.
+    pub fn can_transition_to(&self, next: ServiceWorkerState) -> bool {
+        use ServiceWorkerState::*;
+        matches!(
+            (self, next),
+            (_, Redundant)
+                | (Parsed, Installing)
+                | (Installing, Installed)
+                | (Installed, Activating)
+                | (Activating, Activated)
+        )
+    }
 }
 
 impl std::fmt::Display for ServiceWorkerState {
@@ -82,6 +98,13 @@ pub enum ServiceWorkerError {
         expected: String,
         actual: ServiceWorkerState,
     },
+    /// Operation needs the registration to have an active worker
+    NoActiveWorker(String),
+    /// Lifecycle transition that skips or reverses a state
+    InvalidTransition {
+        from: ServiceWorkerState,
+        to: ServiceWorkerState,
+    },
     /// Network error during script fetch
     NetworkError(String),
     /// Script evaluation error
@@ -125,6 +148,16 @@ impl std::fmt::Display for ServiceWorkerError {
                     expected, actual
                 )
             }
+            ServiceWorkerError::NoActiveWorker(scope) => {
+                write!(f, "No active service worker for scope: {}", scope)
+            }
+            ServiceWorkerError::InvalidTransition { from, to } => {
+                write!(
+                    f,
+                    "Invalid service worker state transition from {} to {}",
+                    from, to
+                )
+            }
             ServiceWorkerError::NetworkError(msg) => {
                 write!(f, "Network error: {}", msg)
             }
@@ -159,6 +192,8 @@ pub struct ServiceWorker {
     state_listeners: Mutex<Vec<Box<dyn Fn(ServiceWorkerState) + Send + Sync>>>,
     /// Message queue for postMessage
     message_queue: Mutex<Vec<StructuredValue>>,
+    /// Outstanding waitUntil promises of fetch events dispatched to this worker
+    extended_events: Mutex<Vec<Arc<Mutex<HashSet<u64>>>>>,
 }
 
 impl ServiceWorker {
@@ -171,6 +206,7 @@ impl ServiceWorker {
             state: RwLock::new(ServiceWorkerState::Parsed),
             state_listeners: Mutex::new(Vec::new()),
             message_queue: Mutex::new(Vec::new()),
+            extended_events: Mutex::new(Vec::new()),
         }
     }
 
@@ -189,15 +225,28 @@ impl ServiceWorker {
         *self.state.read().unwrap()
     }
 
-    /// Transition to a new state
-    pub(crate) fn set_state(&self, new_state: ServiceWorkerState) {
+    /// Transition to a new state, enforcing the lifecycle order
+    pub fn transition_to(&self, new_state: ServiceWorkerState) -> Result<(), ServiceWorkerError> {
         let mut state = self.state.write().unwrap();
         let old_state = *state;
-        if old_state != new_state {
-            *state = new_state;
-            drop(state);
-            self.notify_state_change(new_state);
+        if old_state == new_state && new_state.is_terminal() {
+            return Ok(());
         }
+        if !old_state.can_transition_to(new_state) {
+            return Err(ServiceWorkerError::InvalidTransition {
+                from: old_state,
+                to: new_state,
+            });
+        }
+        *state = new_state;
+        drop(state);
+        self.notify_state_change(new_state);
+        Ok(())
+    }
+
+    /// Make the worker redundant, which is legal from any state
+    pub(crate) fn make_redundant(&self) {
+        let _ = self.transition_to(ServiceWorkerState::Redundant);
     }
 
     /// Add a state change listener
@@ -235,6 +284,24 @@ impl ServiceWorker {
         let mut queue = self.message_queue.lock().unwrap();
         std::mem::take(&mut *queue)
     }
+
+    /// Keep this worker alive until the fetch event's waitUntil promises settle
+    pub fn track_fetch_event(&self, event: &FetchEvent) {
+        let mut events = self.extended_events.lock().unwrap();
+        events.push(Arc::clone(&event.pending_promises));
+    }
+
+    /// Check whether no fetch event is still extended by waitUntil
+    ///
+    /// Only an idle worker is eligible for termination.
+    pub fn is_idle(&self) -> bool {
+        let mut events = self.extended_events.lock().unwrap();
+        // Forget events that have been dropped with nothing left to wait for
+        events.retain(|pending| {
+            Arc::strong_count(pending) > 1 || !pending.lock().unwrap().is_empty()
+        });
+        events.iter().all(|pending| pending.lock().unwrap().is_empty())
+    }
 }
 
 impl std::fmt::Debug for ServiceWorker {
@@ -298,6 +365,8 @@ pub struct ServiceWorkerRegistration {
     update_pending: Mutex<bool>,
     /// Timestamp of last update check
     last_update_check: Mutex<Option<std::time::Instant>>,
+    /// Navigation preload configuration
+    navigation_preload: Mutex<NavigationPreloadState>,
 }
 
 impl ServiceWorkerRegistration {
@@ -313,6 +382,7 @@ impl ServiceWorkerRegistration {
             update_via_cache,
             update_pending: Mutex::new(false),
             last_update_check: Mutex::new(None),
+            navigation_preload: Mutex::new(NavigationPreloadState::default()),
         }
     }
 
@@ -346,22 +416,31 @@ impl ServiceWorkerRegistration {
         self.update_via_cache
     }
 
-    /// Check if navigation preload is enabled (stub)
+    /// Check if navigation preload is enabled
     pub fn navigation_preload_enabled(&self) -> bool {
-        false
+        self.navigation_preload.lock().unwrap().enabled
+    }
+
+    /// Get the navigation preload manager for this registration
+    pub fn navigation_preload(&self) -> NavigationPreloadManager<'_> {
+        NavigationPreloadManager { registration: self }
     }
 
     /// Start the installation process
-    pub(crate) fn start_install(&self, worker: Arc<ServiceWorker>) {
-        worker.set_state(ServiceWorkerState::Installing);
+    pub(crate) fn start_install(
+        &self,
+        worker: Arc<ServiceWorker>,
+    ) -> Result<(), ServiceWorkerError> {
+        worker.transition_to(ServiceWorkerState::Installing)?;
         *self.installing.write().unwrap() = Some(worker);
+        Ok(())
     }
 
     /// Complete installation successfully
     pub(crate) fn complete_install(&self) -> Result<(), ServiceWorkerError> {
         let installing = self.installing.read().unwrap().clone();
         if let Some(worker) = installing {
-            worker.set_state(ServiceWorkerState::Installed);
+            worker.transition_to(ServiceWorkerState::Installed)?;
 
             // Move installing to waiting
             *self.installing.write().unwrap() = None;
@@ -379,7 +458,7 @@ impl ServiceWorkerRegistration {
     pub(crate) fn fail_install(&self) {
         let installing = self.installing.write().unwrap().take();
         if let Some(worker) = installing {
-            worker.set_state(ServiceWorkerState::Redundant);
+            worker.make_redundant();
         }
     }
 
@@ -387,8 +466,7 @@ impl ServiceWorkerRegistration {
     pub(crate) fn start_activate(&self) -> Result<(), ServiceWorkerError> {
         let waiting = self.waiting.read().unwrap().clone();
         if let Some(worker) = waiting {
-            worker.set_state(ServiceWorkerState::Activating);
-            Ok(())
+            worker.transition_to(ServiceWorkerState::Activating)
         } else {
             Err(ServiceWorkerError::InvalidState {
                 expected: "waiting worker present".to_string(),
@@ -410,10 +488,10 @@ impl ServiceWorkerRegistration {
 
             // Make the old active worker redundant
             if let Some(old_active) = self.active.read().unwrap().as_ref() {
-                old_active.set_state(ServiceWorkerState::Redundant);
+                old_active.make_redundant();
             }
 
-            worker.set_state(ServiceWorkerState::Activated);
+            worker.transition_to(ServiceWorkerState::Activated)?;
 
             // Move waiting to active
             *self.waiting.write().unwrap() = None;
@@ -431,7 +509,7 @@ impl ServiceWorkerRegistration {
     pub(crate) fn fail_activate(&self) {
         let waiting = self.waiting.write().unwrap().take();
         if let Some(worker) = waiting {
-            worker.set_state(ServiceWorkerState::Redundant);
+            worker.make_redundant();
         }
     }
 
@@ -451,13 +529,13 @@ impl ServiceWorkerRegistration {
     pub fn unregister(&self) -> bool {
         // Make all workers redundant
         if let Some(w) = self.installing.write().unwrap().take() {
-            w.set_state(ServiceWorkerState::Redundant);
+            w.make_redundant();
         }
         if let Some(w) = self.waiting.write().unwrap().take() {
-            w.set_state(ServiceWorkerState::Redundant);
+            w.make_redundant();
         }
         if let Some(w) = self.active.write().unwrap().take() {
-            w.set_state(ServiceWorkerState::Redundant);
+            w.make_redundant();
         }
         true
     }
@@ -475,6 +553,71 @@ impl std::fmt::Debug for ServiceWorkerRegistration {
     }
 }
 
+// ============================================================================
+// Navigation Preload
+// ============================================================================
+
+/// Navigation preload configuration of a registration
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NavigationPreloadState {
+    /// Whether navigation requests are started while the worker boots
+    pub enabled: bool,
+    /// Value of the `Service-Worker-Navigation-Preload` request header
+    pub header_value: String,
+}
+
+impl Default for NavigationPreloadState {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            header_value: "true".to_string(),
+        }
+    }
+}
+
+/// Controls navigation preload for a registration
+///
+/// Changes require an active worker, since only an active worker can
+/// handle the preloaded response.
+pub struct NavigationPreloadManager<'a> {
+    registration: &'a ServiceWorkerRegistration,
+}
+
+impl NavigationPreloadManager<'_> {
+    /// Enable navigation preload
+    pub fn enable(&self) -> Result<(), ServiceWorkerError> {
+        self.update(|state| state.enabled = true)
+    }
+
+    /// Disable navigation preload
+    pub fn disable(&self) -> Result<(), ServiceWorkerError> {
+        self.update(|state| state.enabled = false)
+    }
+
+    /// Set the value sent in the `Service-Worker-Navigation-Preload` header
+    pub fn set_header_value(&self, value: &str) -> Result<(), ServiceWorkerError> {
+        self.update(|state| state.header_value = value.to_string())
+    }
+
+    /// Get the current configuration
+    pub fn get_state(&self) -> NavigationPreloadState {
+        self.registration.navigation_preload.lock().unwrap().clone()
+    }
+
+    /// Apply `change` if the registration has an active worker
+    fn update(
+        &self,
+        change: impl FnOnce(&mut NavigationPreloadState),
+    ) -> Result<(), ServiceWorkerError> {
+        if self.registration.active().is_none() {
+            let scope = self.registration.scope().to_string();
+            return Err(ServiceWorkerError::NoActiveWorker(scope));
+        }
+        change(&mut self.registration.navigation_preload.lock().unwrap());
+        Ok(())
+    }
+}
+
 // ============================================================================
 // Service Worker Container (navigator.serviceWorker)
 // ============================================================================
@@ -610,7 +753,7 @@ impl ServiceWorkerContainer {
         let worker = Arc::new(ServiceWorker::new(script_url.to_string()));
 
         // Start installation
-        registration.start_install(Arc::clone(&worker));
+        registration.start_install(Arc::clone(&worker))?;
 
         // Store registration
         registrations.insert(scope, Arc::clone(&registration));
@@ -917,6 +1060,8 @@ pub struct FetchEvent {
     responded: Mutex<bool>,
     /// The response (if respondWith was called)
     response: Mutex<Option<FetchResponse>>,
+    /// IDs of waitUntil promises that have not settled yet
+    pending_promises: Arc<Mutex<HashSet<u64>>>,
 }
 
 impl FetchEvent {
@@ -929,6 +1074,7 @@ impl FetchEvent {
             result_id: None,
             responded: Mutex::new(false),
             response: Mutex::new(None),
+            pending_promises: Arc::new(Mutex::new(HashSet::new())),
         }
     }
 
@@ -955,18 +1101,49 @@ impl FetchEvent {
     pub fn get_response(&self) -> Option<FetchResponse> {
         self.response.lock().unwrap().clone()
     }
+
+    /// Extend the event's lifetime until the given promise settles
+    pub fn wait_until(&self, promise_id: u64) {
+        self.pending_promises.lock().unwrap().insert(promise_id);
+    }
+
+    /// Record that a promise passed to `wait_until` has settled
+    pub fn settle(&self, promise_id: u64) {
+        self.pending_promises.lock().unwrap().remove(&promise_id);
+    }
+
+    /// Check whether every waitUntil promise has settled
+    pub fn is_settled(&self) -> bool {
+        self.pending_promises.lock().unwrap().is_empty()
+    }
+
+    /// Number of waitUntil promises still outstanding
+    pub fn pending_count(&self) -> usize {
+        self.pending_promises.lock().unwrap().len()
+    }
 }
 
 // ============================================================================
 // Cache API
 // ============================================================================
 
+/// Options controlling how `Cache::match_all` compares requests
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CacheQueryOptions {
+    /// Ignore the query string of both URLs
+    pub ignore_search: bool,
+    /// Match requests of any method, not just GET
+    pub ignore_method: bool,
+    /// Ignore the `Vary` header of cached responses
+    pub ignore_vary: bool,
+}
+
 /// A single cache in the Cache Storage
 pub struct Cache {
     /// Cache name
     name: String,
-    /// Cached entries (URL -> Response)
-    entries: RwLock<HashMap<String, FetchResponse>>,
+    /// Cached entries (URL -> Request, Response)
+    entries: RwLock<HashMap<String, (FetchRequest, FetchResponse)>>,
 }
 
 impl Cache {
@@ -990,20 +1167,31 @@ impl Cache {
 
     /// Match a URL against the cache
     pub fn match_url(&self, url: &str) -> Option<FetchResponse> {
-        self.entries.read().unwrap().get(url).cloned()
+        self.entries
+            .read()
+            .unwrap()
+            .get(url)
+            .map(|(_, response)| response.clone())
     }
 
     /// Match all entries for a request
-    pub fn match_all(&self, request: Option<&FetchRequest>) -> Vec<FetchResponse> {
+    ///
+    /// With no request, every cached response is returned.
+    pub fn match_all(
+        &self,
+        request: Option<&FetchRequest>,
+        options: CacheQueryOptions,
+    ) -> Vec<FetchResponse> {
         let entries = self.entries.read().unwrap();
-        if let Some(req) = request {
-            entries
-                .iter()
-                .filter(|(url, _)| url.starts_with(&req.url))
-                .map(|(_, resp)| resp.clone())
-                .collect()
-        } else {
-            entries.values().cloned().collect()
+        match request {
+            Some(request) => entries
+                .values()
+                .filter(|(cached, response)| {
+                    request_matches_cached_item(request, cached, response, &options)
+                })
+                .map(|(_, response)| response.clone())
+                .collect(),
+            None => entries.values().map(|(_, response)| response.clone()).collect(),
         }
     }
 
@@ -1021,7 +1209,7 @@ impl Cache {
         self.entries
             .write()
             .unwrap()
-            .insert(request.url.clone(), response);
+            .insert(request.url.clone(), (request.clone(), response));
         Ok(())
     }
 
@@ -1029,11 +1217,12 @@ impl Cache {
     pub fn add(&self, url: &str) -> Result<(), ServiceWorkerError> {
         // In a real implementation, this would fetch the URL
         // For now, we create a placeholder entry
+        let request = FetchRequest::new(url, RequestMethod::Get);
         let response = FetchResponse::new(200, Vec::new());
         self.entries
             .write()
             .unwrap()
-            .insert(url.to_string(), response);
+            .insert(url.to_string(), (request, response));
         Ok(())
     }
 
@@ -1061,6 +1250,49 @@ impl Cache {
     }
 }
 
+/// Whether `request` matches a cached request/response pair, per the
+/// "request matches cached item" algorithm of the Service Workers spec
+fn request_matches_cached_item(
+    request: &FetchRequest,
+    cached: &FetchRequest,
+    response: &FetchResponse,
+    options: &CacheQueryOptions,
+) -> bool {
+    if !options.ignore_method && request.method != RequestMethod::Get {
+        return false;
+    }
+
+    let comparable = |url: &str| {
+        let url = url.split('#').next().unwrap_or(url);
+        if options.ignore_search {
+            url.split('?').next().unwrap_or(url).to_string()
+        } else {
+            url.to_string()
+        }
+    };
+    if comparable(&request.url) != comparable(&cached.url) {
+        return false;
+    }
+
+    if options.ignore_vary {
+        return true;
+    }
+    let Some(vary) = header_value(&response.headers, "vary") else {
+        return true;
+    };
+    vary.split(',').map(str::trim).filter(|name| !name.is_empty()).all(|name| {
+        name != "*" && header_value(&request.headers, name) == header_value(&cached.headers, name)
+    })
+}
+
+/// Look up a header by case-insensitive name
+fn header_value<'a>(headers: &'a HashMap<String, String>, name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.as_str())
+}
+
 impl std::fmt::Debug for Cache {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Cache")
@@ -1172,6 +1404,7 @@ impl FetchEventHandler {
             if controller.state().can_intercept_fetch() {
                 // Create fetch event
                 let event = FetchEvent::new(request.clone());
+                controller.track_fetch_event(&event);
 
                 // In a real implementation, this would dispatch to the worker
                 // and wait for respondWith to be called
@@ -1204,6 +1437,205 @@ impl FetchEventHandler {
     }
 }
 
+// ============================================================================
+// Clients
+// ============================================================================
+
+/// Types of clients a service worker can see
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ClientType {
+    /// Document (window) client
+    Window,
+    /// Dedicated worker client
+    Worker,
+    /// Shared worker client
+    SharedWorker,
+    /// Matches any client type
+    All,
+}
+
+impl ClientType {
+    /// Check if a client of type `other` matches this filter
+    pub fn matches(&self, other: ClientType) -> bool {
+        *self == ClientType::All || *self == other
+    }
+}
+
+/// Represents an environment (window or worker) visible to a service worker
+pub struct Client {
+    /// Unique client ID
+    id: u64,
+    /// Client URL
+    url: String,
+    /// Client type
+    client_type: ClientType,
+    /// Controlling service worker (if any)
+    controller: RwLock<Option<Arc<ServiceWorker>>>,
+}
+
+impl Client {
+    /// Create a new, uncontrolled client
+    pub fn new(url: String, client_type: ClientType) -> Self {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+        Self {
+            id: NEXT_ID.fetch_add(1, Ordering::SeqCst),
+            url,
+            client_type,
+            controller: RwLock::new(None),
+        }
+    }
+
+    /// Get the client ID
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Get the client URL
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Get the client type
+    pub fn client_type(&self) -> ClientType {
+        self.client_type
+    }
+
+    /// Get the controlling service worker
+    pub fn controller(&self) -> Option<Arc<ServiceWorker>> {
+        self.controller.read().unwrap().clone()
+    }
+
+    /// Check if the client is controlled by the given worker
+    fn is_controlled_by(&self, worker: &Arc<ServiceWorker>) -> bool {
+        self.controller
+            .read()
+            .unwrap()
+            .as_ref()
+            .map_or(false, |c| Arc::ptr_eq(c, worker))
+    }
+}
+
+impl std::fmt::Debug for Client {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Client")
+            .field("id", &self.id)
+            .field("url", &self.url)
+            .field("client_type", &self.client_type)
+            .field("controlled", &self.controller().is_some())
+            .finish()
+    }
+}
+
+/// Options for `Clients::match_all`
+#[derive(Debug, Clone)]
+pub struct ClientQueryOptions {
+    /// Include same-origin clients not controlled by this worker
+    pub include_uncontrolled: bool,
+    /// Client type filter
+    pub client_type: ClientType,
+}
+
+impl Default for ClientQueryOptions {
+    fn default() -> Self {
+        Self {
+            include_uncontrolled: false,
+            client_type: ClientType::Window,
+        }
+    }
+}
+
+/// The `clients` interface of a service worker global scope
+pub struct Clients {
+    /// The worker this interface belongs to
+    worker: Arc<ServiceWorker>,
+    /// The worker's registration
+    registration: Arc<ServiceWorkerRegistration>,
+    /// All known clients
+    clients: RwLock<Vec<Arc<Client>>>,
+}
+
+impl Clients {
+    /// Create the clients interface for a worker
+    pub fn new(worker: Arc<ServiceWorker>, registration: Arc<ServiceWorkerRegistration>) -> Self {
+        Self {
+            worker,
+            registration,
+            clients: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Register a new, uncontrolled client
+    pub fn add_client(&self, url: impl Into<String>, client_type: ClientType) -> Arc<Client> {
+        let client = Arc::new(Client::new(url.into(), client_type));
+        self.clients.write().unwrap().push(Arc::clone(&client));
+        client
+    }
+
+    /// Get a client by ID
+    pub fn get(&self, id: u64) -> Option<Arc<Client>> {
+        self.clients
+            .read()
+            .unwrap()
+            .iter()
+            .find(|c| c.id() == id)
+            .cloned()
+    }
+
+    /// Take control of all in-scope clients
+    ///
+    /// Only the registration's active worker (or the waiting worker while it
+    /// is activating) may claim clients.
+    pub fn claim(&self) -> Result<(), ServiceWorkerError> {
+        let is_active = self
+            .registration
+            .active()
+            .map_or(false, |w| Arc::ptr_eq(&w, &self.worker));
+        let is_activating = self.worker.state() == ServiceWorkerState::Activating
+            && self
+                .registration
+                .waiting()
+                .map_or(false, |w| Arc::ptr_eq(&w, &self.worker));
+        if !is_active && !is_activating {
+            return Err(ServiceWorkerError::InvalidState {
+                expected: "active worker".to_string(),
+                actual: self.worker.state(),
+            });
+        }
+
+        let scope = self.registration.scope();
+        for client in self.clients.read().unwrap().iter() {
+            if client.url().starts_with(scope) {
+                *client.controller.write().unwrap() = Some(Arc::clone(&self.worker));
+            }
+        }
+        Ok(())
+    }
+
+    /// Get the clients matching the given options
+    pub fn match_all(&self, options: &ClientQueryOptions) -> Vec<Arc<Client>> {
+        let origin = Origin::parse(self.registration.scope()).ok();
+        self.clients
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|c| options.client_type.matches(c.client_type()))
+            .filter(|c| {
+                if c.is_controlled_by(&self.worker) {
+                    return true;
+                }
+                options.include_uncontrolled
+                    && match (&origin, Origin::parse(c.url())) {
+                        (Some(scope_origin), Ok(client_origin)) => {
+                            scope_origin.is_same_origin(&client_origin)
+                        }
+                        _ => false,
+                    }
+            })
+            .cloned()
+            .collect()
+    }
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -1266,23 +1698,52 @@ mod tests {
         fn test_state_transitions() {
             let worker = ServiceWorker::new("https://example.com/sw.js".to_string());
 
-            worker.set_state(ServiceWorkerState::Installing);
+            worker.transition_to(ServiceWorkerState::Installing).unwrap();
             assert_eq!(worker.state(), ServiceWorkerState::Installing);
 
-            worker.set_state(ServiceWorkerState::Installed);
+            worker.transition_to(ServiceWorkerState::Installed).unwrap();
             assert_eq!(worker.state(), ServiceWorkerState::Installed);
 
-            worker.set_state(ServiceWorkerState::Activating);
+            worker.transition_to(ServiceWorkerState::Activating).unwrap();
             assert_eq!(worker.state(), ServiceWorkerState::Activating);
 
-            worker.set_state(ServiceWorkerState::Activated);
+            worker.transition_to(ServiceWorkerState::Activated).unwrap();
             assert_eq!(worker.state(), ServiceWorkerState::Activated);
+
+            worker.transition_to(ServiceWorkerState::Redundant).unwrap();
+            assert_eq!(worker.state(), ServiceWorkerState::Redundant);
+        }
+
+        #[test]
+        fn test_state_transition_skip_rejected() {
+            let worker = ServiceWorker::new("https://example.com/sw.js".to_string());
+            worker.transition_to(ServiceWorkerState::Installing).unwrap();
+
+            let err = worker
+                .transition_to(ServiceWorkerState::Activated)
+                .unwrap_err();
+            assert!(matches!(
+                err,
+                ServiceWorkerError::InvalidTransition {
+                    from: ServiceWorkerState::Installing,
+                    to: ServiceWorkerState::Activated,
+                }
+            ));
+            assert!(err.to_string().contains("from installing to activated"));
+            assert_eq!(worker.state(), ServiceWorkerState::Installing);
         }
 
         #[test]
         fn test_post_message() {
             let worker = ServiceWorker::new("https://example.com/sw.js".to_string());
-            worker.set_state(ServiceWorkerState::Activated);
+            for state in [
+                ServiceWorkerState::Installing,
+                ServiceWorkerState::Installed,
+                ServiceWorkerState::Activating,
+                ServiceWorkerState::Activated,
+            ] {
+                worker.transition_to(state).unwrap();
+            }
 
             let msg = StructuredValue::String("hello".to_string());
             assert!(worker.post_message(msg).is_ok());
@@ -1294,7 +1755,7 @@ mod tests {
         #[test]
         fn test_post_message_redundant() {
             let worker = ServiceWorker::new("https://example.com/sw.js".to_string());
-            worker.set_state(ServiceWorkerState::Redundant);
+            worker.transition_to(ServiceWorkerState::Redundant).unwrap();
 
             let msg = StructuredValue::String("hello".to_string());
             assert!(worker.post_message(msg).is_err());
@@ -1334,7 +1795,7 @@ mod tests {
             let worker = Arc::new(ServiceWorker::new("https://example.com/sw.js".to_string()));
 
             // Start install
-            reg.start_install(Arc::clone(&worker));
+            reg.start_install(Arc::clone(&worker)).unwrap();
             assert!(reg.installing().is_some());
             assert_eq!(worker.state(), ServiceWorkerState::Installing);
 
@@ -1355,7 +1816,7 @@ mod tests {
             let worker = Arc::new(ServiceWorker::new("https://example.com/sw.js".to_string()));
 
             // Go through install
-            reg.start_install(Arc::clone(&worker));
+            reg.start_install(Arc::clone(&worker)).unwrap();
             reg.complete_install().unwrap();
 
             // Start activate
@@ -1377,7 +1838,7 @@ mod tests {
             );
 
             let worker = Arc::new(ServiceWorker::new("https://example.com/sw.js".to_string()));
-            reg.start_install(Arc::clone(&worker));
+            reg.start_install(Arc::clone(&worker)).unwrap();
             reg.complete_install().unwrap();
             reg.start_activate().unwrap();
             reg.complete_activate().unwrap();
@@ -1395,12 +1856,68 @@ mod tests {
             );
 
             let worker = Arc::new(ServiceWorker::new("https://example.com/sw.js".to_string()));
-            reg.start_install(Arc::clone(&worker));
+            reg.start_install(Arc::clone(&worker)).unwrap();
 
             reg.fail_install();
             assert!(reg.installing().is_none());
             assert_eq!(worker.state(), ServiceWorkerState::Redundant);
         }
+
+        fn activated_registration() -> ServiceWorkerRegistration {
+            let reg = ServiceWorkerRegistration::new(
+                "https://example.com/".to_string(),
+                UpdateViaCache::Imports,
+            );
+            let worker = Arc::new(ServiceWorker::new("https://example.com/sw.js".to_string()));
+            reg.start_install(worker).unwrap();
+            reg.complete_install().unwrap();
+            reg.start_activate().unwrap();
+            reg.complete_activate().unwrap();
+            reg
+        }
+
+        #[test]
+        fn test_navigation_preload_requires_active_worker() {
+            let reg = ServiceWorkerRegistration::new(
+                "https://example.com/".to_string(),
+                UpdateViaCache::Imports,
+            );
+            let error = reg.navigation_preload().enable().unwrap_err();
+            assert_eq!(
+                error,
+                ServiceWorkerError::NoActiveWorker("https://example.com/".to_string())
+            );
+            assert_eq!(
+                error.to_string(),
+                "No active service worker for scope: https://example.com/"
+            );
+            assert!(!reg.navigation_preload_enabled());
+        }
+
+        #[test]
+        fn test_navigation_preload_enable_disable() {
+            let reg = activated_registration();
+            let preload = reg.navigation_preload();
+            assert_eq!(preload.get_state(), NavigationPreloadState::default());
+
+            preload.enable().unwrap();
+            assert!(preload.get_state().enabled);
+            assert!(reg.navigation_preload_enabled());
+
+            preload.disable().unwrap();
+            assert!(!preload.get_state().enabled);
+        }
+
+        #[test]
+        fn test_navigation_preload_header_value() {
+            let reg = activated_registration();
+            let preload = reg.navigation_preload();
+            assert_eq!(preload.get_state().header_value, "true");
+
+            preload.set_header_value("v2").unwrap();
+            assert_eq!(preload.get_state().header_value, "v2");
+            assert_eq!(reg.navigation_preload().get_state().header_value, "v2");
+        }
     }
 
     // Container Tests
@@ -1555,6 +2072,70 @@ mod tests {
             let result = cache.put(&request, response);
             assert!(matches!(result, Err(ServiceWorkerError::CacheError(_))));
         }
+
+        #[test]
+        fn test_cache_match_all_ignore_search() {
+            let cache = Cache::new("test-cache".to_string());
+            let page1 = FetchRequest::new("https://example.com/list?page=1", RequestMethod::Get);
+            let page2 = FetchRequest::new("https://example.com/list?page=2", RequestMethod::Get);
+            cache.put(&page1, FetchResponse::new(200, b"1".to_vec())).unwrap();
+            cache.put(&page2, FetchResponse::new(200, b"2".to_vec())).unwrap();
+
+            let exact = cache.match_all(Some(&page1), CacheQueryOptions::default());
+            assert_eq!(exact.len(), 1);
+            assert_eq!(exact[0].body, b"1".to_vec());
+
+            let options = CacheQueryOptions {
+                ignore_search: true,
+                ..Default::default()
+            };
+            let mut bodies: Vec<Vec<u8>> = cache
+                .match_all(Some(&page1), options)
+                .into_iter()
+                .map(|response| response.body)
+                .collect();
+            bodies.sort();
+            assert_eq!(bodies, vec![b"1".to_vec(), b"2".to_vec()]);
+
+            let bare = FetchRequest::new("https://example.com/list", RequestMethod::Get);
+            assert_eq!(cache.match_all(Some(&bare), options).len(), 2);
+            assert_eq!(cache.match_all(None, CacheQueryOptions::default()).len(), 2);
+        }
+
+        #[test]
+        fn test_cache_match_all_method_and_vary() {
+            let cache = Cache::new("test-cache".to_string());
+            let mut request = FetchRequest::new("https://example.com/data", RequestMethod::Get);
+            request
+                .headers
+                .insert("Accept-Language".to_string(), "en".to_string());
+            let mut response = FetchResponse::new(200, vec![]);
+            response
+                .headers
+                .insert("Vary".to_string(), "accept-language".to_string());
+            cache.put(&request, response).unwrap();
+
+            let post = FetchRequest::new("https://example.com/data", RequestMethod::Post);
+            assert!(cache.match_all(Some(&post), CacheQueryOptions::default()).is_empty());
+            let ignore_method = CacheQueryOptions {
+                ignore_method: true,
+                ignore_vary: true,
+                ..Default::default()
+            };
+            assert_eq!(cache.match_all(Some(&post), ignore_method).len(), 1);
+
+            let mut french = FetchRequest::new("https://example.com/data", RequestMethod::Get);
+            french
+                .headers
+                .insert("accept-language".to_string(), "fr".to_string());
+            assert!(cache.match_all(Some(&french), CacheQueryOptions::default()).is_empty());
+            let ignore_vary = CacheQueryOptions {
+                ignore_vary: true,
+                ..Default::default()
+            };
+            assert_eq!(cache.match_all(Some(&french), ignore_vary).len(), 1);
+            assert_eq!(cache.match_all(Some(&request), CacheQueryOptions::default()).len(), 1);
+        }
     }
 
     // Cache Storage Tests
@@ -1658,6 +2239,28 @@ mod tests {
 
             assert!(result.is_err());
         }
+
+        #[test]
+        fn test_wait_until_keeps_worker_alive() {
+            let worker = ServiceWorker::new("/sw.js".to_string());
+            let event = FetchEvent::new(FetchRequest::navigate("https://example.com/"));
+            worker.track_fetch_event(&event);
+            assert!(event.is_settled());
+            assert!(worker.is_idle());
+
+            event.wait_until(1);
+            event.wait_until(2);
+            assert_eq!(event.pending_count(), 2);
+
+            event.settle(1);
+            assert_eq!(event.pending_count(), 1);
+            assert!(!event.is_settled());
+            assert!(!worker.is_idle());
+
+            event.settle(2);
+            assert!(event.is_settled());
+            assert!(worker.is_idle());
+        }
     }
 
     // Response Tests
@@ -1764,4 +2367,97 @@ mod tests {
                 .is_none());
         }
     }
+
+    // Clients Tests
+    mod clients_tests {
+        use super::*;
+
+        fn activated() -> (Arc<ServiceWorker>, Arc<ServiceWorkerRegistration>) {
+            let reg = Arc::new(ServiceWorkerRegistration::new(
+                "https://example.com/app/".to_string(),
+                UpdateViaCache::Imports,
+            ));
+            let worker = Arc::new(ServiceWorker::new(
+                "https://example.com/app/sw.js".to_string(),
+            ));
+            reg.start_install(Arc::clone(&worker)).unwrap();
+            reg.complete_install().unwrap();
+            reg.start_activate().unwrap();
+            reg.complete_activate().unwrap();
+            (worker, reg)
+        }
+
+        #[test]
+        fn test_claim_controls_existing_clients() {
+            let (worker, reg) = activated();
+            let clients = Clients::new(Arc::clone(&worker), reg);
+            let page = clients.add_client("https://example.com/app/index.html", ClientType::Window);
+            let outside = clients.add_client("https://example.com/other.html", ClientType::Window);
+            assert!(page.controller().is_none());
+
+            clients.claim().unwrap();
+            assert!(Arc::ptr_eq(&page.controller().unwrap(), &worker));
+            assert!(outside.controller().is_none());
+        }
+
+        #[test]
+        fn test_claim_requires_active_worker() {
+            let reg = Arc::new(ServiceWorkerRegistration::new(
+                "https://example.com/".to_string(),
+                UpdateViaCache::Imports,
+            ));
+            let worker = Arc::new(ServiceWorker::new("https://example.com/sw.js".to_string()));
+            reg.start_install(Arc::clone(&worker)).unwrap();
+            let clients = Clients::new(worker, reg);
+            assert!(matches!(
+                clients.claim(),
+                Err(ServiceWorkerError::InvalidState { .. })
+            ));
+        }
+
+        #[test]
+        fn test_match_all_include_uncontrolled() {
+            let (worker, reg) = activated();
+            let clients = Clients::new(worker, reg);
+            let page = clients.add_client("https://example.com/app/index.html", ClientType::Window);
+            clients.add_client("https://example.com/other.html", ClientType::Window);
+            clients.add_client("https://evil.com/", ClientType::Window);
+
+            let options = ClientQueryOptions::default();
+            assert!(clients.match_all(&options).is_empty());
+
+            clients.claim().unwrap();
+            let controlled = clients.match_all(&options);
+            assert_eq!(controlled.len(), 1);
+            assert_eq!(controlled[0].id(), page.id());
+
+            let all = clients.match_all(&ClientQueryOptions {
+                include_uncontrolled: true,
+                ..Default::default()
+            });
+            assert_eq!(all.len(), 2);
+        }
+
+        #[test]
+        fn test_match_all_filters_by_type() {
+            let (worker, reg) = activated();
+            let clients = Clients::new(worker, reg);
+            clients.add_client("https://example.com/app/index.html", ClientType::Window);
+            clients.add_client("https://example.com/app/worker.js", ClientType::Worker);
+            clients.claim().unwrap();
+
+            let workers = clients.match_all(&ClientQueryOptions {
+                include_uncontrolled: false,
+                client_type: ClientType::Worker,
+            });
+            assert_eq!(workers.len(), 1);
+            assert_eq!(workers[0].client_type(), ClientType::Worker);
+
+            let any = clients.match_all(&ClientQueryOptions {
+                include_uncontrolled: false,
+                client_type: ClientType::All,
+            });
+            assert_eq!(any.len(), 2);
+        }
+    }
 }
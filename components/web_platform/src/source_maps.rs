@@ -65,13 +65,90 @@ impl SourceMap {
     }
 
     /// Parse source map from JSON
+    ///
+    /// Index maps (a top-level `sections` array) are flattened into a single
+    /// composite map whose generated positions include each section's offset.
     pub fn from_json(json: &str) -> Result<Self, String> {
-        let mut map: SourceMap =
+        let value: serde_json::Value =
             serde_json::from_str(json).map_err(|e| format!("JSON parse error: {}", e))?;
+        if value.get("sections").is_some() {
+            return Self::from_index_map(&value);
+        }
+
+        let mut map: SourceMap =
+            serde_json::from_value(value).map_err(|e| format!("JSON parse error: {}", e))?;
         map.decode_mappings()?;
         Ok(map)
     }
 
+    /// Build a composite source map from an index map
+    fn from_index_map(value: &serde_json::Value) -> Result<Self, String> {
+        if value.get("version").and_then(|v| v.as_u64()) != Some(3) {
+            return Err("Index map version must be 3".to_string());
+        }
+        if value.get("mappings").is_some() {
+            return Err("Index map cannot have both sections and mappings".to_string());
+        }
+        let sections = value
+            .get("sections")
+            .and_then(|s| s.as_array())
+            .ok_or_else(|| "Index map sections must be an array".to_string())?;
+
+        let mut map = SourceMap::new();
+        map.file = value.get("file").and_then(|f| f.as_str()).map(String::from);
+
+        let mut last_offset = None;
+        for section in sections {
+            let offset = section
+                .get("offset")
+                .ok_or_else(|| "Index map section is missing offset".to_string())?;
+            let line = offset.get("line").and_then(|l| l.as_u64());
+            let column = offset.get("column").and_then(|c| c.as_u64());
+            let (line, column) = match (line, column) {
+                (Some(line), Some(column)) => (line as u32, column as u32),
+                _ => return Err("Index map section offset is invalid".to_string()),
+            };
+            if last_offset.is_some_and(|last| (line, column) < last) {
+                return Err("Index map sections must be in order".to_string());
+            }
+            last_offset = Some((line, column));
+
+            let nested = section
+                .get("map")
+                .ok_or_else(|| "Index map section is missing map".to_string())?;
+            let nested = Self::from_json(&nested.to_string())?;
+            map.append_section(nested, line, column);
+        }
+
+        map.regenerate_mappings();
+        Ok(map)
+    }
+
+    /// Merge a nested section map into this map at the given offset
+    fn append_section(&mut self, section: SourceMap, line: u32, column: u32) {
+        let source_base = self.sources.len() as u32;
+        let name_base = self.names.len() as u32;
+
+        let root = section.source_root.as_deref();
+        self.sources.extend(
+            section
+                .sources
+                .iter()
+                .map(|s| Self::join_source_root(root, s)),
+        );
+        self.names.extend(section.names);
+
+        for mut mapping in section.decoded_mappings {
+            if mapping.generated_line == 0 {
+                mapping.generated_column += column;
+            }
+            mapping.generated_line += line;
+            mapping.source_index = mapping.source_index.map(|i| i + source_base);
+            mapping.name_index = mapping.name_index.map(|i| i + name_base);
+            self.decoded_mappings.push(mapping);
+        }
+    }
+
     /// Convert source map to JSON
     pub fn to_json(&self) -> Result<String, String> {
         serde_json::to_string(self).map_err(|e| e.to_string())
@@ -210,6 +287,29 @@ impl SourceMap {
         result
     }
 
+    /// Resolve the source at `index` against `sourceRoot`
+    ///
+    /// Absolute paths and URLs are returned unchanged.
+    pub fn resolved_source(&self, index: usize) -> Option<String> {
+        let source = self.sources.get(index)?;
+        Some(Self::join_source_root(self.source_root.as_deref(), source))
+    }
+
+    fn join_source_root(root: Option<&str>, source: &str) -> String {
+        let root = match root {
+            Some(root) if !root.is_empty() => root,
+            _ => return source.to_string(),
+        };
+        if source.starts_with('/') || source.contains("://") {
+            return source.to_string();
+        }
+        if root.ends_with('/') {
+            format!("{}{}", root, source)
+        } else {
+            format!("{}/{}", root, source)
+        }
+    }
+
     /// Map generated position to original
     pub fn original_position_for(&self, line: u32, column: u32) -> Option<(String, u32, u32)> {
         // Find the mapping for this generated position
@@ -219,8 +319,7 @@ impl SourceMap {
             .filter(|m| m.generated_line == line && m.generated_column <= column)
             .last()?;
 
-        let source_index = mapping.source_index? as usize;
-        let source = self.sources.get(source_index)?.clone();
+        let source = self.resolved_source(mapping.source_index? as usize)?;
         let orig_line = mapping.original_line?;
         let orig_col = mapping.original_column?;
 
@@ -239,8 +338,7 @@ impl SourceMap {
             .filter(|m| m.generated_line == line && m.generated_column <= column)
             .last()?;
 
-        let source_index = mapping.source_index? as usize;
-        let source = self.sources.get(source_index)?.clone();
+        let source = self.resolved_source(mapping.source_index? as usize)?;
         let orig_line = mapping.original_line?;
         let orig_col = mapping.original_column?;
 
@@ -263,7 +361,9 @@ impl SourceMap {
         line: u32,
         column: u32,
     ) -> Option<GeneratedPosition> {
-        let source_index = self.sources.iter().position(|s| s == source)? as u32;
+        let source_index = (0..self.sources.len()).position(|i| {
+            self.sources[i] == source || self.resolved_source(i).as_deref() == Some(source)
+        })? as u32;
 
         let mapping = self.decoded_mappings.iter().find(|m| {
             m.source_index == Some(source_index)
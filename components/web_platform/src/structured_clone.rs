@@ -6,15 +6,23 @@
 //!
 //! Reference: https://html.spec.whatwg.org/multipage/structured-data.html
 
-use serde::{Deserialize, Serialize};
+use builtins::collections::{MapObject, SetObject};
+use builtins::value::JsValue;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::{HashMap, HashSet};
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 
 /// Errors that can occur during structured cloning
 #[derive(Debug, Clone, PartialEq)]
 pub enum CloneError {
     /// The value contains a non-cloneable type
-    DataCloneError(String),
+    DataCloneError {
+        /// What could not be cloned
+        message: String,
+        /// Property keys and array indices leading from the root to the value
+        path: Vec<String>,
+    },
     /// Circular reference detected but not supported in this context
     CircularReference,
     /// Maximum depth exceeded
@@ -28,7 +36,12 @@ pub enum CloneError {
 impl std::fmt::Display for CloneError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            CloneError::DataCloneError(msg) => write!(f, "DataCloneError: {}", msg),
+            CloneError::DataCloneError { message, path } if path.is_empty() => {
+                write!(f, "DataCloneError: {}", message)
+            }
+            CloneError::DataCloneError { message, .. } => {
+                write!(f, "DataCloneError: {} at {}", message, self.path_string())
+            }
             CloneError::CircularReference => write!(f, "Circular reference detected"),
             CloneError::MaxDepthExceeded => write!(f, "Maximum recursion depth exceeded"),
             CloneError::SerializationError(msg) => write!(f, "Serialization error: {}", msg),
@@ -39,6 +52,228 @@ impl std::fmt::Display for CloneError {
 
 impl std::error::Error for CloneError {}
 
+impl CloneError {
+    /// A DataCloneError at the root of the value
+    fn data_clone(message: impl Into<String>) -> Self {
+        CloneError::DataCloneError {
+            message: message.into(),
+            path: Vec::new(),
+        }
+    }
+
+    /// Keys traversed to reach the value that failed to clone; empty for other errors
+    pub fn path(&self) -> &[String] {
+        match self {
+            CloneError::DataCloneError { path, .. } => path,
+            _ => &[],
+        }
+    }
+
+    /// The path formatted as an accessor expression, like `root.data[3].callback`
+    pub fn path_string(&self) -> String {
+        let mut out = "root".to_string();
+        for segment in self.path() {
+            if segment.parse::<usize>().is_ok() {
+                out.push_str(&format!("[{}]", segment));
+            } else {
+                out.push('.');
+                out.push_str(segment);
+            }
+        }
+        out
+    }
+
+    /// Record that the failure happened under `segment`, as the error unwinds
+    fn under(mut self, segment: impl FnOnce() -> String, ctx: &StructuredCloneContext) -> Self {
+        if ctx.options.track_path {
+            if let CloneError::DataCloneError { path, .. } = &mut self {
+                path.insert(0, segment());
+            }
+        }
+        self
+    }
+}
+
+/// Allocate a process-unique identity for a transferable object
+fn next_transferable_id() -> u64 {
+    static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+    NEXT_ID.fetch_add(1, Ordering::SeqCst)
+}
+
+/// An ArrayBuffer with identity, which can be transferred (detached) as well as cloned
+///
+/// Handles are cheap to clone and share the same backing allocation. Once the buffer
+/// is transferred, every handle to the original reports a byte length of zero.
+#[derive(Debug, Clone)]
+pub struct ArrayBuffer {
+    id: u64,
+    data: Arc<Mutex<Option<Vec<u8>>>>,
+}
+
+impl ArrayBuffer {
+    /// Create a zero-filled buffer of the given length
+    pub fn new(byte_length: usize) -> Self {
+        Self::from_bytes(vec![0; byte_length])
+    }
+
+    /// Create a buffer that takes ownership of existing bytes
+    pub fn from_bytes(bytes: Vec<u8>) -> Self {
+        Self {
+            id: next_transferable_id(),
+            data: Arc::new(Mutex::new(Some(bytes))),
+        }
+    }
+
+    /// Identity of this buffer (shared by all handles to it)
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Length in bytes, or 0 once detached
+    pub fn byte_length(&self) -> usize {
+        self.data.lock().unwrap().as_ref().map_or(0, Vec::len)
+    }
+
+    /// Whether the buffer has been detached by a transfer
+    pub fn is_detached(&self) -> bool {
+        self.data.lock().unwrap().is_none()
+    }
+
+    /// Copy out the buffer contents
+    pub fn to_vec(&self) -> Result<Vec<u8>, CloneError> {
+        self.data
+            .lock()
+            .unwrap()
+            .clone()
+            .ok_or_else(|| CloneError::data_clone("ArrayBuffer is detached"))
+    }
+
+    /// Take the allocation out of the buffer, leaving it detached
+    fn detach(&self) -> Option<Vec<u8>> {
+        self.data.lock().unwrap().take()
+    }
+
+    /// Install an allocation into a buffer that received a transfer
+    fn attach(&self, bytes: Vec<u8>) {
+        *self.data.lock().unwrap() = Some(bytes);
+    }
+
+    /// Create a handle with no allocation yet; filled in once a transfer commits
+    fn pending() -> Self {
+        Self {
+            id: next_transferable_id(),
+            data: Arc::new(Mutex::new(None)),
+        }
+    }
+}
+
+impl PartialEq for ArrayBuffer {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id || *self.data.lock().unwrap() == *other.data.lock().unwrap()
+    }
+}
+
+impl Serialize for ArrayBuffer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.data.lock().unwrap().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ArrayBuffer {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = Option::<Vec<u8>>::deserialize(deserializer)?;
+        Ok(Self {
+            id: next_transferable_id(),
+            data: Arc::new(Mutex::new(data)),
+        })
+    }
+}
+
+/// A MessagePort endpoint; ports can only be transferred, never cloned
+#[derive(Debug, Clone)]
+pub struct MessagePort {
+    id: u64,
+    detached: Arc<AtomicBool>,
+}
+
+impl MessagePort {
+    /// Create a new port
+    pub fn new() -> Self {
+        Self {
+            id: next_transferable_id(),
+            detached: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Identity of this port (shared by all handles to it)
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Whether the port has been transferred away from this side
+    pub fn is_detached(&self) -> bool {
+        self.detached.load(Ordering::SeqCst)
+    }
+
+    fn detach(&self) {
+        self.detached.store(true, Ordering::SeqCst);
+    }
+}
+
+impl Default for MessagePort {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PartialEq for MessagePort {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Serialize for MessagePort {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.is_detached().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for MessagePort {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let detached = bool::deserialize(deserializer)?;
+        Ok(Self {
+            id: next_transferable_id(),
+            detached: Arc::new(AtomicBool::new(detached)),
+        })
+    }
+}
+
+/// An object that may appear in a transfer list
+#[derive(Debug, Clone, PartialEq)]
+pub enum Transferable {
+    /// ArrayBuffer whose allocation moves to the clone
+    ArrayBuffer(ArrayBuffer),
+    /// MessagePort that is re-homed in the clone
+    MessagePort(MessagePort),
+}
+
+impl Transferable {
+    /// Identity of the underlying object
+    pub fn id(&self) -> u64 {
+        match self {
+            Transferable::ArrayBuffer(buffer) => buffer.id(),
+            Transferable::MessagePort(port) => port.id(),
+        }
+    }
+
+    fn is_detached(&self) -> bool {
+        match self {
+            Transferable::ArrayBuffer(buffer) => buffer.is_detached(),
+            Transferable::MessagePort(port) => port.is_detached(),
+        }
+    }
+}
+
 /// Represents a JavaScript value that can be cloned
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum StructuredValue {
@@ -60,6 +295,10 @@ pub enum StructuredValue {
     RegExp { pattern: String, flags: String },
     /// ArrayBuffer (raw bytes)
     ArrayBuffer(Vec<u8>),
+    /// ArrayBuffer with identity, eligible for transfer
+    ArrayBufferHandle(ArrayBuffer),
+    /// MessagePort (transfer only)
+    MessagePort(MessagePort),
     /// SharedArrayBuffer reference ID (requires special handling)
     SharedArrayBuffer(u64),
     /// TypedArray (type, buffer data)
@@ -146,6 +385,8 @@ pub struct CloneOptions {
     pub max_depth: usize,
     /// List of transferable object IDs to transfer (not clone)
     pub transfer: Vec<u64>,
+    /// Record the path to an uncloneable value in the error; disable for speed on huge graphs
+    pub track_path: bool,
 }
 
 impl Default for CloneOptions {
@@ -153,6 +394,7 @@ impl Default for CloneOptions {
         Self {
             max_depth: 1000,
             transfer: Vec::new(),
+            track_path: true,
         }
     }
 }
@@ -169,6 +411,10 @@ pub struct StructuredCloneContext {
     options: CloneOptions,
     /// Set of transferred object IDs
     transferred: HashSet<u64>,
+    /// Transferred buffers: original ID -> (original, receiving handle)
+    transferred_buffers: HashMap<u64, (ArrayBuffer, ArrayBuffer)>,
+    /// Transferred ports: original ID -> (original, receiving handle)
+    transferred_ports: HashMap<u64, (MessagePort, MessagePort)>,
 }
 
 impl StructuredCloneContext {
@@ -181,6 +427,8 @@ impl StructuredCloneContext {
             depth: 0,
             options,
             transferred,
+            transferred_buffers: HashMap::new(),
+            transferred_ports: HashMap::new(),
         }
     }
 
@@ -233,6 +481,97 @@ impl StructuredClone {
         Self::clone_internal(value, &mut ctx)
     }
 
+    /// Clone a value, transferring ownership of the listed objects into the clone
+    ///
+    /// Each transferable must be reachable from `value` and not already detached.
+    /// Transferred ArrayBuffers move their allocation (no copy) into the clone and are
+    /// left detached in the source; transferred MessagePorts are detached likewise.
+    /// Nothing is detached if cloning fails.
+    pub fn clone_with_transfer(
+        value: &StructuredValue,
+        transfer: &[Transferable],
+    ) -> Result<StructuredValue, CloneError> {
+        let mut reachable = HashSet::new();
+        Self::collect_transferables(value, &mut reachable);
+
+        let mut seen = HashSet::new();
+        for item in transfer {
+            if !seen.insert(item.id()) {
+                return Err(CloneError::data_clone("Transfer list contains a duplicate object"));
+            }
+            if item.is_detached() {
+                return Err(CloneError::data_clone("Transfer list contains a detached object"));
+            }
+            if !reachable.contains(&item.id()) {
+                return Err(CloneError::data_clone(
+                    "Transferable is not reachable from the value being cloned",
+                ));
+            }
+        }
+
+        let mut ctx = StructuredCloneContext::new(CloneOptions::default());
+        for item in transfer {
+            match item {
+                Transferable::ArrayBuffer(buffer) => {
+                    ctx.transferred_buffers
+                        .insert(buffer.id(), (buffer.clone(), ArrayBuffer::pending()));
+                }
+                Transferable::MessagePort(port) => {
+                    ctx.transferred_ports
+                        .insert(port.id(), (port.clone(), MessagePort::new()));
+                }
+            }
+        }
+
+        let cloned = Self::clone_internal(value, &mut ctx)?;
+
+        // Commit: move allocations to the receiving side and neuter the originals
+        for (original, received) in ctx.transferred_buffers.values() {
+            if let Some(bytes) = original.detach() {
+                received.attach(bytes);
+            }
+        }
+        for (original, _) in ctx.transferred_ports.values() {
+            original.detach();
+        }
+
+        Ok(cloned)
+    }
+
+    /// Collect the identities of all transferable objects reachable from a value
+    fn collect_transferables(value: &StructuredValue, found: &mut HashSet<u64>) {
+        match value {
+            StructuredValue::ArrayBufferHandle(buffer) => {
+                found.insert(buffer.id());
+            }
+            StructuredValue::MessagePort(port) => {
+                found.insert(port.id());
+            }
+            StructuredValue::Map(entries) => {
+                for (k, v) in entries {
+                    Self::collect_transferables(k, found);
+                    Self::collect_transferables(v, found);
+                }
+            }
+            StructuredValue::Set(values) => {
+                for v in values {
+                    Self::collect_transferables(v, found);
+                }
+            }
+            StructuredValue::Object(props) => {
+                for (_, v) in props {
+                    Self::collect_transferables(v, found);
+                }
+            }
+            StructuredValue::Array(elements) => {
+                for v in elements.iter().flatten() {
+                    Self::collect_transferables(v, found);
+                }
+            }
+            _ => {}
+        }
+    }
+
     fn clone_internal(
         value: &StructuredValue,
         ctx: &mut StructuredCloneContext,
@@ -258,14 +597,28 @@ impl StructuredClone {
                 Ok(StructuredValue::ArrayBuffer(data.clone()))
             }
 
+            // ArrayBuffer handle - move if transferred, otherwise copy into a new buffer
+            StructuredValue::ArrayBufferHandle(buffer) => {
+                match ctx.transferred_buffers.get(&buffer.id()) {
+                    Some((_, received)) => Ok(StructuredValue::ArrayBufferHandle(received.clone())),
+                    None => Ok(StructuredValue::ArrayBufferHandle(ArrayBuffer::from_bytes(
+                        buffer.to_vec()?,
+                    ))),
+                }
+            }
+
+            // MessagePort - only valid when transferred
+            StructuredValue::MessagePort(port) => match ctx.transferred_ports.get(&port.id()) {
+                Some((_, received)) => Ok(StructuredValue::MessagePort(received.clone())),
+                None => Err(CloneError::data_clone("MessagePort must be in transfer list")),
+            },
+
             // SharedArrayBuffer - not clonable (must be transferred)
             StructuredValue::SharedArrayBuffer(id) => {
                 if ctx.is_transferred(*id) {
                     Ok(StructuredValue::SharedArrayBuffer(*id))
                 } else {
-                    Err(CloneError::DataCloneError(
-                        "SharedArrayBuffer must be in transfer list".to_string(),
-                    ))
+                    Err(CloneError::data_clone("SharedArrayBuffer must be in transfer list"))
                 }
             }
 
@@ -312,7 +665,11 @@ impl StructuredClone {
             StructuredValue::Object(props) => {
                 let cloned: Result<Vec<_>, _> = props
                     .iter()
-                    .map(|(k, v)| Ok((k.clone(), Self::clone_internal(v, ctx)?)))
+                    .map(|(k, v)| {
+                        let cloned = Self::clone_internal(v, ctx)
+                            .map_err(|e| e.under(|| k.clone(), ctx))?;
+                        Ok((k.clone(), cloned))
+                    })
                     .collect();
                 Ok(StructuredValue::Object(cloned?))
             }
@@ -321,9 +678,13 @@ impl StructuredClone {
             StructuredValue::Array(elements) => {
                 let cloned: Result<Vec<_>, _> = elements
                     .iter()
-                    .map(|elem| {
+                    .enumerate()
+                    .map(|(i, elem)| {
                         elem.as_ref()
-                            .map(|v| Self::clone_internal(v, ctx))
+                            .map(|v| {
+                                Self::clone_internal(v, ctx)
+                                    .map_err(|e| e.under(|| i.to_string(), ctx))
+                            })
                             .transpose()
                     })
                     .collect();
@@ -374,6 +735,167 @@ impl StructuredClone {
         result
     }
 
+    /// Serialize a runtime value into its structured form
+    ///
+    /// Objects, arrays, Maps and Sets are recorded in the memo table as they are
+    /// first reached; later occurrences, including cycles, become
+    /// `StructuredValue::Reference` to the memo id. Map and Set keep insertion order.
+    pub fn clone_value(value: &JsValue) -> Result<StructuredValue, CloneError> {
+        let mut ctx = StructuredCloneContext::new(CloneOptions::default());
+        Self::serialize_value(value, &mut ctx)
+    }
+
+    fn serialize_value(
+        value: &JsValue,
+        ctx: &mut StructuredCloneContext,
+    ) -> Result<StructuredValue, CloneError> {
+        if matches!(
+            value,
+            JsValue::Object(_) | JsValue::Array(_) | JsValue::Map(_) | JsValue::Set(_)
+        ) {
+            let identity = value.object_identity().unwrap_or_default() as u64;
+            if let Some(ref_id) = ctx.register(identity) {
+                return Ok(StructuredValue::Reference(ref_id));
+            }
+        }
+
+        ctx.enter()?;
+        let result = match value {
+            JsValue::Undefined => Ok(StructuredValue::Undefined),
+            JsValue::Null => Ok(StructuredValue::Null),
+            JsValue::Boolean(b) => Ok(StructuredValue::Boolean(*b)),
+            JsValue::Number(n) => Ok(StructuredValue::Number(*n)),
+            JsValue::String(s) => Ok(StructuredValue::String(s.clone())),
+            JsValue::Object(data) => {
+                let data = data.borrow();
+                let mut keys: Vec<&String> = data.properties.keys().collect();
+                keys.sort();
+                let props: Result<Vec<_>, _> = keys
+                    .into_iter()
+                    .map(|k| {
+                        let value = Self::serialize_value(&data.properties[k], ctx)
+                            .map_err(|e| e.under(|| k.clone(), ctx))?;
+                        Ok((k.clone(), value))
+                    })
+                    .collect();
+                Ok(StructuredValue::Object(props?))
+            }
+            JsValue::Array(data) => {
+                let data = data.borrow();
+                let elements: Result<Vec<_>, _> = data
+                    .elements
+                    .iter()
+                    .enumerate()
+                    .map(|(i, v)| {
+                        (!data.is_hole(i))
+                            .then(|| {
+                                Self::serialize_value(v, ctx)
+                                    .map_err(|e| e.under(|| i.to_string(), ctx))
+                            })
+                            .transpose()
+                    })
+                    .collect();
+                Ok(StructuredValue::Array(elements?))
+            }
+            JsValue::Map(data) => {
+                let data = data.borrow();
+                let entries: Result<Vec<_>, _> = data
+                    .entries
+                    .iter()
+                    .map(|(k, v)| Ok((Self::serialize_value(k, ctx)?, Self::serialize_value(v, ctx)?)))
+                    .collect();
+                Ok(StructuredValue::Map(entries?))
+            }
+            JsValue::Set(data) => {
+                let data = data.borrow();
+                let values: Result<Vec<_>, _> = data
+                    .values
+                    .iter()
+                    .map(|v| Self::serialize_value(v, ctx))
+                    .collect();
+                Ok(StructuredValue::Set(values?))
+            }
+            other => Err(CloneError::data_clone(format!(
+                "{} could not be cloned",
+                other.type_of()
+            ))),
+        };
+        ctx.exit();
+        result
+    }
+
+    /// Rebuild a runtime value from its structured form
+    ///
+    /// The inverse of [`StructuredClone::clone_value`]: memo ids are assigned in the
+    /// same order, so each `Reference` resolves to the object it was recorded for.
+    pub fn to_value(value: &StructuredValue) -> Result<JsValue, CloneError> {
+        let mut memo = Vec::new();
+        Self::deserialize_value(value, &mut memo)
+    }
+
+    fn deserialize_value(
+        value: &StructuredValue,
+        memo: &mut Vec<JsValue>,
+    ) -> Result<JsValue, CloneError> {
+        match value {
+            StructuredValue::Undefined => Ok(JsValue::Undefined),
+            StructuredValue::Null => Ok(JsValue::Null),
+            StructuredValue::Boolean(b) => Ok(JsValue::Boolean(*b)),
+            StructuredValue::Number(n) => Ok(JsValue::Number(*n)),
+            StructuredValue::String(s) => Ok(JsValue::string(s.clone())),
+            StructuredValue::Object(props) => {
+                let object = JsValue::object();
+                memo.push(object.clone());
+                for (k, v) in props {
+                    let v = Self::deserialize_value(v, memo)?;
+                    object.set(k, v);
+                }
+                Ok(object)
+            }
+            StructuredValue::Array(elements) => {
+                let array = JsValue::array();
+                memo.push(array.clone());
+                for (i, elem) in elements.iter().enumerate() {
+                    let element = match elem {
+                        Some(v) => Self::deserialize_value(v, memo)?,
+                        None => JsValue::Undefined,
+                    };
+                    if let JsValue::Array(data) = &array {
+                        let mut data = data.borrow_mut();
+                        data.elements.push(element);
+                        if elem.is_none() {
+                            data.holes.insert(i);
+                        }
+                    }
+                }
+                Ok(array)
+            }
+            StructuredValue::Map(entries) => {
+                let map = MapObject::new();
+                memo.push(map.clone());
+                for (k, v) in entries {
+                    let k = Self::deserialize_value(k, memo)?;
+                    let v = Self::deserialize_value(v, memo)?;
+                    MapObject::set(&map, k, v);
+                }
+                Ok(map)
+            }
+            StructuredValue::Set(values) => {
+                let set = SetObject::new();
+                memo.push(set.clone());
+                for v in values {
+                    let v = Self::deserialize_value(v, memo)?;
+                    SetObject::add(&set, v);
+                }
+                Ok(set)
+            }
+            StructuredValue::Reference(id) => memo.get(*id as usize).cloned().ok_or_else(|| {
+                CloneError::DeserializationError(format!("Unknown reference {}", id))
+            }),
+            _ => Err(CloneError::data_clone("Value has no runtime representation")),
+        }
+    }
+
     /// Serialize a value to bytes
     pub fn serialize(value: &StructuredValue) -> Result<Vec<u8>, CloneError> {
         bincode::serialize(value)
@@ -391,6 +913,8 @@ impl StructuredClone {
         match value {
             // Functions, Symbols, and certain objects are not cloneable
             StructuredValue::SharedArrayBuffer(_) => false, // Must be transferred
+            StructuredValue::MessagePort(_) => false, // Must be transferred
+            StructuredValue::ArrayBufferHandle(buffer) => !buffer.is_detached(),
             _ => true,
         }
     }
@@ -504,6 +1028,98 @@ mod tests {
         assert!(StructuredClone::clone_with_options(&sab, options).is_ok());
     }
 
+    #[test]
+    fn test_clone_array_buffer_handle_copies() {
+        let buffer = ArrayBuffer::from_bytes(vec![1, 2, 3]);
+        let value = StructuredValue::ArrayBufferHandle(buffer.clone());
+
+        let cloned = StructuredClone::clone(&value).unwrap();
+        assert_eq!(cloned, value);
+        assert_eq!(buffer.byte_length(), 3);
+    }
+
+    #[test]
+    fn test_message_port_requires_transfer() {
+        let value = StructuredValue::MessagePort(MessagePort::new());
+        assert!(StructuredClone::clone(&value).is_err());
+        assert!(!StructuredClone::is_cloneable(&value));
+    }
+
+    #[test]
+    fn test_transfer_detaches_message_port() {
+        let port = MessagePort::new();
+        let value = StructuredValue::MessagePort(port.clone());
+
+        let cloned =
+            StructuredClone::clone_with_transfer(&value, &[Transferable::MessagePort(port.clone())])
+                .unwrap();
+        assert!(port.is_detached());
+        match cloned {
+            StructuredValue::MessagePort(received) => assert!(!received.is_detached()),
+            other => panic!("expected MessagePort, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_transfer_array_buffer() {
+        let buffer = ArrayBuffer::from_bytes(vec![10, 20, 30, 40]);
+        let value = StructuredValue::Object(vec![
+            ("id".to_string(), StructuredValue::Number(1.0)),
+            ("payload".to_string(), StructuredValue::ArrayBufferHandle(buffer.clone())),
+        ]);
+
+        let cloned =
+            StructuredClone::clone_with_transfer(&value, &[Transferable::ArrayBuffer(buffer.clone())])
+                .unwrap();
+
+        // Original is neutered
+        assert!(buffer.is_detached());
+        assert_eq!(buffer.byte_length(), 0);
+        assert!(buffer.to_vec().is_err());
+
+        // Clone owns the bytes
+        match &cloned {
+            StructuredValue::Object(props) => match &props[1].1 {
+                StructuredValue::ArrayBufferHandle(received) => {
+                    assert_ne!(received.id(), buffer.id());
+                    assert_eq!(received.to_vec().unwrap(), vec![10, 20, 30, 40]);
+                }
+                other => panic!("expected ArrayBufferHandle, got {:?}", other),
+            },
+            other => panic!("expected Object, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_transfer_unreachable_is_error() {
+        let reachable = ArrayBuffer::new(8);
+        let stray = ArrayBuffer::new(8);
+        let value = StructuredValue::ArrayBufferHandle(reachable.clone());
+
+        let result = StructuredClone::clone_with_transfer(
+            &value,
+            &[
+                Transferable::ArrayBuffer(reachable.clone()),
+                Transferable::ArrayBuffer(stray.clone()),
+            ],
+        );
+        assert!(matches!(result, Err(CloneError::DataCloneError { .. })));
+        assert!(!reachable.is_detached());
+        assert!(!stray.is_detached());
+    }
+
+    #[test]
+    fn test_transfer_duplicate_is_error() {
+        let buffer = ArrayBuffer::new(4);
+        let value = StructuredValue::ArrayBufferHandle(buffer.clone());
+        let transfer = [
+            Transferable::ArrayBuffer(buffer.clone()),
+            Transferable::ArrayBuffer(buffer.clone()),
+        ];
+        assert!(StructuredClone::clone_with_transfer(&value, &transfer).is_err());
+        assert!(!buffer.is_detached());
+    }
+
     #[test]
     fn test_max_depth() {
         let mut value = StructuredValue::Object(vec![]);
@@ -544,4 +1160,77 @@ mod tests {
         let cloned = StructuredClone::clone(&err).unwrap();
         assert_eq!(cloned, err);
     }
+
+    #[test]
+    fn test_clone_value_map_containing_itself() {
+        let map = MapObject::new();
+        MapObject::set(&map, JsValue::string("self"), map.clone());
+        MapObject::set(&map, JsValue::string("n"), JsValue::number(1.0));
+
+        let structured = StructuredClone::clone_value(&map).unwrap();
+        assert_eq!(
+            structured,
+            StructuredValue::Map(vec![
+                (StructuredValue::String("self".to_string()), StructuredValue::Reference(0)),
+                (StructuredValue::String("n".to_string()), StructuredValue::Number(1.0)),
+            ])
+        );
+
+        let restored = StructuredClone::to_value(&structured).unwrap();
+        let inner = MapObject::get(&restored, &JsValue::string("self")).unwrap();
+        assert_eq!(inner.object_identity(), restored.object_identity());
+        assert_ne!(restored.object_identity(), map.object_identity());
+    }
+
+    #[test]
+    fn test_clone_value_set_preserves_order() {
+        let set = SetObject::new();
+        for n in [3.0, 1.0, 2.0] {
+            SetObject::add(&set, JsValue::number(n));
+        }
+
+        let structured = StructuredClone::clone_value(&set).unwrap();
+        let restored = StructuredClone::to_value(&structured).unwrap();
+        let JsValue::Set(data) = restored else {
+            panic!("expected a Set");
+        };
+        let values: Vec<f64> = data.borrow().values.iter().filter_map(JsValue::as_number).collect();
+        assert_eq!(values, vec![3.0, 1.0, 2.0]);
+    }
+
+    #[test]
+    fn test_data_clone_error_reports_path() {
+        // {a: {b: [0, 1, function(){}]}}
+        let array = JsValue::array_from(vec![
+            JsValue::number(0.0),
+            JsValue::number(1.0),
+            JsValue::function(|_, _| Ok(JsValue::Undefined)),
+        ]);
+        let inner = JsValue::object();
+        inner.set("b", array);
+        let outer = JsValue::object();
+        outer.set("a", inner);
+
+        let err = StructuredClone::clone_value(&outer).unwrap_err();
+        assert_eq!(err.path(), ["a", "b", "2"]);
+        assert_eq!(err.path_string(), "root.a.b[2]");
+        assert_eq!(err.to_string(), "DataCloneError: function could not be cloned at root.a.b[2]");
+    }
+
+    #[test]
+    fn test_data_clone_error_path_tracking_can_be_disabled() {
+        let value = StructuredValue::Object(vec![(
+            "port".to_string(),
+            StructuredValue::MessagePort(MessagePort::new()),
+        )]);
+        let err = StructuredClone::clone(&value).unwrap_err();
+        assert_eq!(err.path(), ["port"]);
+
+        let options = CloneOptions {
+            track_path: false,
+            ..Default::default()
+        };
+        let err = StructuredClone::clone_with_options(&value, options).unwrap_err();
+        assert!(err.path().is_empty());
+    }
 }
@@ -1,4 +1,7 @@
+use builtins::value::JsValue;
+use std::cell::{Ref, RefCell, RefMut};
 use std::collections::HashMap;
+use std::rc::Rc;
 
 /// WebAssembly module (compiled WASM)
 #[derive(Debug)]
@@ -6,12 +9,22 @@ pub struct WasmModule {
     bytes: Vec<u8>,
     exports: Vec<ExportDescriptor>,
     imports: Vec<ImportDescriptor>,
+    sections: ModuleSections,
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct ExportDescriptor {
     pub name: String,
     pub kind: ExportKind,
+    /// Signature of an exported function; `None` for other kinds
+    pub func_type: Option<FuncType>,
+}
+
+/// Parameter and result types of a function
+#[derive(Clone, Debug, PartialEq)]
+pub struct FuncType {
+    pub params: Vec<WasmType>,
+    pub results: Vec<WasmType>,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -27,6 +40,52 @@ pub struct ImportDescriptor {
     pub module: String,
     pub name: String,
     pub kind: ExportKind,
+    /// Signature the host must supply for a function import; `None` for other kinds
+    pub func_type: Option<FuncType>,
+}
+
+/// Why a module failed [`WebAssembly::validate_detailed`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct WasmValidationError {
+    /// Byte offset in the module where the problem was found
+    pub offset: usize,
+    /// Section being checked, e.g. `"type"` or `"code"`; `"header"` for the preamble
+    pub section: &'static str,
+    pub message: String,
+}
+
+impl WasmValidationError {
+    fn new(offset: usize, section: &'static str, message: impl Into<String>) -> Self {
+        Self { offset, section, message: message.into() }
+    }
+}
+
+impl std::fmt::Display for WasmValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (in {} section at byte {})", self.message, self.section, self.offset)
+    }
+}
+
+impl std::error::Error for WasmValidationError {}
+
+/// Name of the section with binary id `id`
+fn section_name(id: u8) -> &'static str {
+    match id {
+        0 => "custom",
+        1 => "type",
+        2 => "import",
+        3 => "function",
+        4 => "table",
+        5 => "memory",
+        6 => "global",
+        7 => "export",
+        8 => "start",
+        9 => "element",
+        10 => "code",
+        11 => "data",
+        12 => "data count",
+        _ => "unknown",
+    }
 }
 
 impl WasmModule {
@@ -46,30 +105,23 @@ impl WasmModule {
             return Err(format!("Unsupported WASM version: {}", version));
         }
 
-        // Parse exports and imports (simplified)
-        let exports = Self::parse_exports(bytes);
-        let imports = Self::parse_imports(bytes);
+        let sections = ModuleSections::decode(&bytes[8..])?;
 
         Ok(Self {
             bytes: bytes.to_vec(),
-            exports,
-            imports,
+            exports: sections.exports.clone(),
+            imports: sections.imports.clone(),
+            sections,
         })
     }
 
-    fn parse_exports(_bytes: &[u8]) -> Vec<ExportDescriptor> {
-        // Simplified: return empty for now
-        vec![]
-    }
-
-    fn parse_imports(_bytes: &[u8]) -> Vec<ImportDescriptor> {
-        vec![]
-    }
-
+    /// What the module exports, with signatures for functions
     pub fn exports(&self) -> &[ExportDescriptor] {
         &self.exports
     }
 
+    /// What the module expects the host to supply, with signatures for
+    /// functions, so imports can be checked before instantiation
     pub fn imports(&self) -> &[ImportDescriptor] {
         &self.imports
     }
@@ -79,11 +131,604 @@ impl WasmModule {
     }
 }
 
+/// Constant initializer expression (global initializers and segment offsets)
+#[derive(Clone, Debug, PartialEq)]
+enum ConstExpr {
+    Value(WasmValue),
+    GlobalGet(u32),
+}
+
+#[derive(Clone, Debug)]
+struct GlobalDef {
+    mutable: bool,
+    init: ConstExpr,
+}
+
+/// Active element segment: function indices copied into a table at instantiation
+#[derive(Clone, Debug)]
+struct ElementSegment {
+    table: u32,
+    offset: ConstExpr,
+    functions: Vec<u32>,
+}
+
+/// Active data segment: bytes copied into a memory at instantiation
+#[derive(Clone, Debug)]
+struct DataSegment {
+    memory: u32,
+    offset: ConstExpr,
+    bytes: Vec<u8>,
+}
+
+#[derive(Clone, Debug)]
+struct FunctionBody {
+    locals: Vec<WasmType>,
+    code: Vec<u8>,
+    /// Byte offset of `code` within the module
+    offset: usize,
+}
+
+/// Decoded sections of a module binary
+#[derive(Clone, Debug, Default)]
+struct ModuleSections {
+    types: Vec<(Vec<WasmType>, Vec<WasmType>)>,
+    imports: Vec<ImportDescriptor>,
+    imported_functions: u32,
+    imported_function_types: Vec<u32>,
+    imported_globals: Vec<String>,
+    /// Types of all globals, imported ones first
+    global_types: Vec<WasmType>,
+    functions: Vec<u32>,
+    /// Element type and initial size of each table
+    tables: Vec<(WasmType, u32)>,
+    memory: Option<(u32, Option<u32>)>,
+    globals: Vec<GlobalDef>,
+    exports: Vec<ExportDescriptor>,
+    /// Index of each export in its kind's index space, parallel to `exports`
+    export_indices: Vec<u32>,
+    start: Option<u32>,
+    elements: Vec<ElementSegment>,
+    code: Vec<FunctionBody>,
+    data: Vec<DataSegment>,
+}
+
+impl ModuleSections {
+    /// Decode the sections following the module header
+    fn decode(bytes: &[u8]) -> Result<Self, String> {
+        Self::decode_located(bytes).map_err(|e| e.message)
+    }
+
+    /// Decode the sections following the 8-byte module header, reporting
+    /// where a malformed section failed
+    fn decode_located(bytes: &[u8]) -> Result<Self, WasmValidationError> {
+        let mut sections = ModuleSections::default();
+        let mut reader = Reader::at(bytes, 8);
+        while !reader.is_empty() {
+            let id = reader.byte().map_err(|m| WasmValidationError::new(reader.offset(), "header", m))?;
+            let section = section_name(id);
+            let size = reader
+                .u32()
+                .map_err(|m| WasmValidationError::new(reader.offset(), section, m))? as usize;
+            let start = reader.offset();
+            let payload = reader
+                .bytes(size)
+                .map_err(|m| WasmValidationError::new(start, section, m))?;
+            let mut payload = Reader::at(payload, start);
+            sections
+                .decode_section(id, &mut payload)
+                .map_err(|m| WasmValidationError::new(payload.offset(), section, m))?;
+        }
+        Ok(sections)
+    }
+
+    fn decode_section(&mut self, id: u8, payload: &mut Reader) -> Result<(), String> {
+        match id {
+            1 => self.decode_types(payload)?,
+            2 => self.decode_imports(payload)?,
+            3 => {
+                for _ in 0..payload.u32()? {
+                    self.functions.push(payload.u32()?);
+                }
+            }
+            4 => {
+                for _ in 0..payload.u32()? {
+                    let element = payload.ref_type()?;
+                    let (min, _) = payload.limits()?;
+                    if min > WASM_MAX_TABLE_SIZE {
+                        return Err(format!("Invalid WASM: table size {} exceeds limit", min));
+                    }
+                    self.tables.push((element, min));
+                }
+            }
+            5 => {
+                if payload.u32()? > 0 {
+                    self.memory = Some(payload.limits()?);
+                }
+            }
+            6 => {
+                for _ in 0..payload.u32()? {
+                    self.global_types.push(payload.val_type()?);
+                    let mutable = payload.byte()? == 1;
+                    let init = payload.const_expr()?;
+                    self.globals.push(GlobalDef { mutable, init });
+                }
+            }
+            7 => {
+                for _ in 0..payload.u32()? {
+                    let name = payload.name()?;
+                    let kind = payload.extern_kind()?;
+                    let index = payload.u32()?;
+                    let func_type = match kind {
+                        ExportKind::Function => Some(self.func_type_of(index)?),
+                        _ => None,
+                    };
+                    self.exports.push(ExportDescriptor { name, kind, func_type });
+                    self.export_indices.push(index);
+                }
+            }
+            8 => self.start = Some(payload.u32()?),
+            9 => self.decode_elements(payload)?,
+            10 => self.decode_code(payload)?,
+            11 => self.decode_data(payload)?,
+            // Custom, data count and unknown sections carry nothing instantiation needs
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn decode_types(&mut self, reader: &mut Reader) -> Result<(), String> {
+        for _ in 0..reader.u32()? {
+            if reader.byte()? != 0x60 {
+                return Err("Invalid WASM: malformed function type".to_string());
+            }
+            let params = (0..reader.u32()?).map(|_| reader.val_type()).collect::<Result<_, _>>()?;
+            let results = (0..reader.u32()?).map(|_| reader.val_type()).collect::<Result<_, _>>()?;
+            self.types.push((params, results));
+        }
+        Ok(())
+    }
+
+    fn decode_imports(&mut self, reader: &mut Reader) -> Result<(), String> {
+        for _ in 0..reader.u32()? {
+            let module = reader.name()?;
+            let name = reader.name()?;
+            let kind = reader.extern_kind()?;
+            let mut func_type = None;
+            match kind {
+                ExportKind::Function => {
+                    let ty = reader.u32()?;
+                    func_type = Some(self.func_type(ty)?);
+                    self.imported_function_types.push(ty);
+                    self.imported_functions += 1;
+                }
+                ExportKind::Table => {
+                    reader.ref_type()?;
+                    reader.limits()?;
+                }
+                ExportKind::Memory => {
+                    reader.limits()?;
+                }
+                ExportKind::Global => {
+                    self.global_types.push(reader.val_type()?);
+                    reader.byte()?;
+                    self.imported_globals.push(name.clone());
+                }
+            }
+            self.imports.push(ImportDescriptor { module, name, kind, func_type });
+        }
+        Ok(())
+    }
+
+    /// Signature at `ty` in the type section
+    fn func_type(&self, ty: u32) -> Result<FuncType, String> {
+        let (params, results) = self
+            .types
+            .get(ty as usize)
+            .ok_or_else(|| format!("Invalid WASM: unknown type {}", ty))?;
+        Ok(FuncType { params: params.clone(), results: results.clone() })
+    }
+
+    /// Signature of function `index`, counting imported functions first
+    fn func_type_of(&self, index: u32) -> Result<FuncType, String> {
+        let ty = match index.checked_sub(self.imported_functions) {
+            None => self.imported_function_types[index as usize],
+            Some(defined) => *self
+                .functions
+                .get(defined as usize)
+                .ok_or_else(|| format!("Invalid WASM: unknown function {}", index))?,
+        };
+        self.func_type(ty)
+    }
+
+    /// Type-check the body of defined function `defined`
+    ///
+    /// Covers the straight-line instructions [`WasmInstance`] can execute;
+    /// any other instruction, such as `block`, is reported as unsupported.
+    fn check_function(&self, defined: usize) -> Result<(), WasmValidationError> {
+        let index = self.imported_functions as usize + defined;
+        let body = &self.code[defined];
+        let signature = self
+            .func_type(self.functions[defined])
+            .map_err(|m| WasmValidationError::new(body.offset, "function", m))?;
+        let mut locals = signature.params.clone();
+        locals.extend(body.locals.iter().cloned());
+
+        let mut code = Reader::at(&body.code, body.offset);
+        let mut stack = Vec::new();
+        loop {
+            let at = code.offset();
+            match self.check_instruction(index, &mut code, &locals, &signature.results, &mut stack) {
+                Ok(true) => {}
+                Ok(false) => return Ok(()),
+                Err(message) => return Err(WasmValidationError::new(at, "code", message)),
+            }
+        }
+    }
+
+    /// Apply one instruction to the type stack; `Ok(false)` once the rest of
+    /// the body needs no checking
+    fn check_instruction(
+        &self,
+        index: usize,
+        code: &mut Reader,
+        locals: &[WasmType],
+        results: &[WasmType],
+        stack: &mut Vec<WasmType>,
+    ) -> Result<bool, String> {
+        let expect = |stack: &mut Vec<WasmType>, expected: &WasmType| match stack.pop() {
+            Some(actual) if actual == *expected => Ok(()),
+            actual => Err(format!(
+                "type mismatch at function {}: expected {}, got {}",
+                index,
+                expected,
+                actual.map_or("nothing".to_string(), |ty| ty.to_string())
+            )),
+        };
+        let local = |code: &mut Reader| -> Result<WasmType, String> {
+            let local = code.u32()?;
+            locals.get(local as usize).cloned().ok_or_else(|| format!("Unknown local: {}", local))
+        };
+        let global = |code: &mut Reader| -> Result<WasmType, String> {
+            let global = code.u32()?;
+            self.global_types.get(global as usize).cloned().ok_or_else(|| format!("Unknown global: {}", global))
+        };
+        let table = |code: &mut Reader| -> Result<WasmType, String> {
+            let table = code.u32()?;
+            self.tables.get(table as usize).map(|(ty, _)| ty.clone()).ok_or_else(|| format!("Unknown table: {}", table))
+        };
+
+        match code.byte()? {
+            // Anything after `unreachable` is dead code, where any stack is valid
+            0x00 => return Ok(false),
+            0x01 => {}
+            op @ (0x0B | 0x0F) => {
+                for ty in results.iter().rev() {
+                    expect(stack, ty)?;
+                }
+                // `return` may leave extra values below the results; the final `end` may not
+                if op == 0x0B && !stack.is_empty() {
+                    return Err(format!(
+                        "type mismatch at function {}: {} extra value(s) left on the stack",
+                        index,
+                        stack.len()
+                    ));
+                }
+                return Ok(false);
+            }
+            0x10 => {
+                let callee = self.func_type_of(code.u32()?)?;
+                for ty in callee.params.iter().rev() {
+                    expect(stack, ty)?;
+                }
+                stack.extend(callee.results);
+            }
+            0x1A => {
+                if stack.pop().is_none() {
+                    return Err(format!("type mismatch at function {}: nothing to drop", index));
+                }
+            }
+            0x20 => stack.push(local(code)?),
+            op @ (0x21 | 0x22) => {
+                let ty = local(code)?;
+                expect(stack, &ty)?;
+                if op == 0x22 {
+                    stack.push(ty);
+                }
+            }
+            0x23 => stack.push(global(code)?),
+            0x24 => {
+                let ty = global(code)?;
+                expect(stack, &ty)?;
+            }
+            0x25 => {
+                let ty = table(code)?;
+                expect(stack, &WasmType::I32)?;
+                stack.push(ty);
+            }
+            0x26 => {
+                let ty = table(code)?;
+                expect(stack, &ty)?;
+                expect(stack, &WasmType::I32)?;
+            }
+            op @ (0x28 | 0x2D | 0x36 | 0x3A) => {
+                code.u32()?; // alignment hint
+                code.u32()?; // offset
+                if op >= 0x36 {
+                    expect(stack, &WasmType::I32)?;
+                }
+                expect(stack, &WasmType::I32)?;
+                if op < 0x36 {
+                    stack.push(WasmType::I32);
+                }
+            }
+            0x41 => {
+                code.i32()?;
+                stack.push(WasmType::I32);
+            }
+            0x42 => {
+                code.i64()?;
+                stack.push(WasmType::I64);
+            }
+            0x43 => {
+                code.bytes(4)?;
+                stack.push(WasmType::F32);
+            }
+            0x44 => {
+                code.bytes(8)?;
+                stack.push(WasmType::F64);
+            }
+            0x6A..=0x6C => {
+                expect(stack, &WasmType::I32)?;
+                expect(stack, &WasmType::I32)?;
+                stack.push(WasmType::I32);
+            }
+            0xD0 => stack.push(code.ref_type()?),
+            0xD1 => match stack.pop() {
+                Some(WasmType::ExternRef | WasmType::FuncRef) => stack.push(WasmType::I32),
+                actual => {
+                    return Err(format!(
+                        "type mismatch at function {}: expected a reference, got {}",
+                        index,
+                        actual.map_or("nothing".to_string(), |ty| ty.to_string())
+                    ))
+                }
+            },
+            0xD2 => {
+                self.func_type_of(code.u32()?)?;
+                stack.push(WasmType::FuncRef);
+            }
+            other => return Err(format!("Unsupported instruction: 0x{:02x}", other)),
+        }
+        Ok(true)
+    }
+
+    fn decode_elements(&mut self, reader: &mut Reader) -> Result<(), String> {
+        for _ in 0..reader.u32()? {
+            let flags = reader.u32()?;
+            let (table, offset) = match flags {
+                0 => (0, Some(reader.const_expr()?)),
+                2 => (reader.u32()?, Some(reader.const_expr()?)),
+                // Passive and declarative segments are not applied at instantiation
+                1 | 3 => (0, None),
+                _ => return Err(format!("Unsupported element segment kind: {}", flags)),
+            };
+            if flags != 0 {
+                reader.byte()?; // element kind
+            }
+            let functions = (0..reader.u32()?).map(|_| reader.u32()).collect::<Result<_, _>>()?;
+            if let Some(offset) = offset {
+                self.elements.push(ElementSegment { table, offset, functions });
+            }
+        }
+        Ok(())
+    }
+
+    fn decode_code(&mut self, reader: &mut Reader) -> Result<(), String> {
+        for _ in 0..reader.u32()? {
+            let size = reader.u32()? as usize;
+            let start = reader.offset();
+            let mut body = Reader::at(reader.bytes(size)?, start);
+            let mut locals = Vec::new();
+            for _ in 0..body.u32()? {
+                let count = body.u32()?;
+                let ty = body.val_type()?;
+                if locals.len() as u64 + count as u64 > WASM_MAX_FUNCTION_LOCALS as u64 {
+                    return Err("Invalid WASM: too many locals".to_string());
+                }
+                locals.extend(std::iter::repeat_n(ty, count as usize));
+            }
+            let offset = body.offset();
+            let code = body.rest().to_vec();
+            self.code.push(FunctionBody { locals, code, offset });
+        }
+        Ok(())
+    }
+
+    fn decode_data(&mut self, reader: &mut Reader) -> Result<(), String> {
+        for _ in 0..reader.u32()? {
+            let (memory, offset) = match reader.u32()? {
+                0 => (0, Some(reader.const_expr()?)),
+                1 => (0, None),
+                2 => (reader.u32()?, Some(reader.const_expr()?)),
+                other => return Err(format!("Unsupported data segment kind: {}", other)),
+            };
+            let len = reader.u32()? as usize;
+            let bytes = reader.bytes(len)?.to_vec();
+            // Passive segments are only copied by memory.init
+            if let Some(offset) = offset {
+                self.data.push(DataSegment { memory, offset, bytes });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Cursor over module bytes with LEB128 decoding
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    /// Module offset of `bytes[0]`, for error locations
+    base: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self::at(bytes, 0)
+    }
+
+    /// Reader over `bytes` that start at `base` in the module
+    fn at(bytes: &'a [u8], base: usize) -> Self {
+        Self { bytes, pos: 0, base }
+    }
+
+    /// Current position within the module
+    fn offset(&self) -> usize {
+        self.base + self.pos
+    }
+
+    fn is_empty(&self) -> bool {
+        self.pos >= self.bytes.len()
+    }
+
+    fn byte(&mut self) -> Result<u8, String> {
+        let byte = *self
+            .bytes
+            .get(self.pos)
+            .ok_or_else(|| "Invalid WASM: unexpected end of section".to_string())?;
+        self.pos += 1;
+        Ok(byte)
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], String> {
+        let end = self.pos.checked_add(len).filter(|&end| end <= self.bytes.len());
+        let end = end.ok_or_else(|| "Invalid WASM: unexpected end of section".to_string())?;
+        let slice = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn rest(&mut self) -> &'a [u8] {
+        let slice = &self.bytes[self.pos..];
+        self.pos = self.bytes.len();
+        slice
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        let mut result: u64 = 0;
+        let mut shift = 0;
+        loop {
+            let byte = self.byte()?;
+            result |= ((byte & 0x7F) as u64) << shift;
+            if byte & 0x80 == 0 {
+                break;
+            }
+            shift += 7;
+            if shift >= 35 {
+                return Err("Invalid WASM: integer too long".to_string());
+            }
+        }
+        u32::try_from(result).map_err(|_| "Invalid WASM: integer too large".to_string())
+    }
+
+    fn signed(&mut self, bits: u32) -> Result<i64, String> {
+        let mut result: i64 = 0;
+        let mut shift = 0;
+        loop {
+            let byte = self.byte()?;
+            result |= ((byte & 0x7F) as i64) << shift;
+            shift += 7;
+            if byte & 0x80 == 0 {
+                if shift < 64 && byte & 0x40 != 0 {
+                    result |= -1i64 << shift;
+                }
+                return Ok(result);
+            }
+            // Another byte would shift past the value's width (and 64 bits)
+            if shift >= bits {
+                return Err("Invalid WASM: integer too long".to_string());
+            }
+        }
+    }
+
+    fn i32(&mut self) -> Result<i32, String> {
+        Ok(self.signed(32)? as i32)
+    }
+
+    fn i64(&mut self) -> Result<i64, String> {
+        self.signed(64)
+    }
+
+    fn name(&mut self) -> Result<String, String> {
+        let len = self.u32()? as usize;
+        String::from_utf8(self.bytes(len)?.to_vec())
+            .map_err(|_| "Invalid WASM: name is not UTF-8".to_string())
+    }
+
+    fn val_type(&mut self) -> Result<WasmType, String> {
+        match self.byte()? {
+            0x7F => Ok(WasmType::I32),
+            0x7E => Ok(WasmType::I64),
+            0x7D => Ok(WasmType::F32),
+            0x7C => Ok(WasmType::F64),
+            0x70 => Ok(WasmType::FuncRef),
+            0x6F => Ok(WasmType::ExternRef),
+            other => Err(format!("Unsupported value type: 0x{:02x}", other)),
+        }
+    }
+
+    fn ref_type(&mut self) -> Result<WasmType, String> {
+        match self.byte()? {
+            0x70 => Ok(WasmType::FuncRef),
+            0x6F => Ok(WasmType::ExternRef),
+            other => Err(format!("Invalid WASM: unknown reference type 0x{:02x}", other)),
+        }
+    }
+
+    fn extern_kind(&mut self) -> Result<ExportKind, String> {
+        match self.byte()? {
+            0 => Ok(ExportKind::Function),
+            1 => Ok(ExportKind::Table),
+            2 => Ok(ExportKind::Memory),
+            3 => Ok(ExportKind::Global),
+            other => Err(format!("Invalid WASM: unknown external kind {}", other)),
+        }
+    }
+
+    fn limits(&mut self) -> Result<(u32, Option<u32>), String> {
+        match self.byte()? {
+            0 => Ok((self.u32()?, None)),
+            1 => Ok((self.u32()?, Some(self.u32()?))),
+            other => Err(format!("Invalid WASM: unknown limits flag {}", other)),
+        }
+    }
+
+    fn const_expr(&mut self) -> Result<ConstExpr, String> {
+        let expr = match self.byte()? {
+            0x41 => ConstExpr::Value(WasmValue::I32(self.i32()?)),
+            0x42 => ConstExpr::Value(WasmValue::I64(self.i64()?)),
+            0x43 => ConstExpr::Value(WasmValue::F32(f32::from_le_bytes(self.bytes(4)?.try_into().unwrap()))),
+            0x44 => ConstExpr::Value(WasmValue::F64(f64::from_le_bytes(self.bytes(8)?.try_into().unwrap()))),
+            0x23 => ConstExpr::GlobalGet(self.u32()?),
+            0xD0 => ConstExpr::Value(WasmValue::null(&self.ref_type()?)),
+            0xD2 => ConstExpr::Value(WasmValue::FuncRef(Some(self.u32()?))),
+            other => return Err(format!("Unsupported constant expression opcode: 0x{:02x}", other)),
+        };
+        if self.byte()? != 0x0B {
+            return Err("Invalid WASM: constant expression must end after one instruction".to_string());
+        }
+        Ok(expr)
+    }
+}
+
 /// WebAssembly instance (instantiated module)
 pub struct WasmInstance {
     module: WasmModule,
     memory: Option<WasmMemory>,
+    globals: Vec<WasmGlobal>,
+    tables: Vec<Vec<WasmValue>>,
     exports: HashMap<String, WasmExport>,
+    /// Wasm calls currently on the stack
+    call_depth: usize,
 }
 
 pub enum WasmExport {
@@ -131,6 +776,23 @@ pub enum WasmType {
     I64,
     F32,
     F64,
+    /// Nullable reference to a function in the module's index space
+    FuncRef,
+    /// Nullable reference to an opaque host (JavaScript) value
+    ExternRef,
+}
+
+impl std::fmt::Display for WasmType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            WasmType::I32 => "i32",
+            WasmType::I64 => "i64",
+            WasmType::F32 => "f32",
+            WasmType::F64 => "f64",
+            WasmType::FuncRef => "funcref",
+            WasmType::ExternRef => "externref",
+        })
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -139,52 +801,153 @@ pub enum WasmValue {
     I64(i64),
     F32(f32),
     F64(f64),
+    /// Function index, or `None` for a null funcref
+    FuncRef(Option<u32>),
+    /// JavaScript value passed through wasm untouched; a null externref is
+    /// `JsValue::Null`
+    ExternRef(JsValue),
+}
+
+impl WasmValue {
+    /// Zero value of `ty`, which locals and table slots start with
+    pub fn default_for(ty: &WasmType) -> Self {
+        match ty {
+            WasmType::I32 => WasmValue::I32(0),
+            WasmType::I64 => WasmValue::I64(0),
+            WasmType::F32 => WasmValue::F32(0.0),
+            WasmType::F64 => WasmValue::F64(0.0),
+            WasmType::FuncRef | WasmType::ExternRef => Self::null(ty),
+        }
+    }
+
+    /// Null reference of reference type `ty`
+    fn null(ty: &WasmType) -> Self {
+        match ty {
+            WasmType::ExternRef => WasmValue::ExternRef(JsValue::Null),
+            _ => WasmValue::FuncRef(None),
+        }
+    }
+
+    /// Whether this is a null funcref or externref
+    pub fn is_null(&self) -> bool {
+        matches!(self, WasmValue::FuncRef(None) | WasmValue::ExternRef(JsValue::Null))
+    }
+}
+
+/// Page size of linear memory in bytes
+const WASM_PAGE_SIZE: usize = 65536;
+
+/// Largest memory a 32-bit address space can index, in pages
+const WASM_MAX_PAGES: u32 = 65536;
+
+/// Largest initial table size a module may declare, in elements
+const WASM_MAX_TABLE_SIZE: u32 = 10_000_000;
+
+/// Most locals a function body may declare
+const WASM_MAX_FUNCTION_LOCALS: u32 = 50_000;
+
+/// Deepest nesting of wasm calls before they trap instead of overflowing
+/// the host stack; each level takes several KB of it in debug builds
+const WASM_MAX_CALL_DEPTH: usize = 100;
+
+/// Errors from host access to linear memory
+#[derive(Clone, Debug, PartialEq)]
+pub enum WasmError {
+    /// An access of `len` bytes at `offset` runs past the memory's `size` bytes
+    OutOfBounds { offset: u32, len: u32, size: usize },
+    /// Growing by `pages` would pass the memory's maximum of `max_pages`
+    GrowFailed { pages: u32, max_pages: u32 },
 }
 
+impl std::fmt::Display for WasmError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WasmError::OutOfBounds { offset, len, size } => write!(
+                f,
+                "out of bounds memory access: {} bytes at {} in a {}-byte memory",
+                len, offset, size
+            ),
+            WasmError::GrowFailed { pages, max_pages } => {
+                write!(f, "cannot grow memory by {} pages past its maximum of {}", pages, max_pages)
+            }
+        }
+    }
+}
+
+impl std::error::Error for WasmError {}
+
 /// WebAssembly linear memory
+///
+/// Handles are cheap to clone and share the same bytes, so a memory obtained
+/// from [`WasmInstance::get_memory`] sees the instance's reads and writes.
+#[derive(Clone)]
 pub struct WasmMemory {
-    data: Vec<u8>,
+    data: Rc<RefCell<Vec<u8>>>,
     max_pages: Option<u32>,
 }
 
 impl WasmMemory {
     pub fn new(initial_pages: u32, max_pages: Option<u32>) -> Self {
-        let size = (initial_pages as usize) * 65536; // 64KB per page
+        let size = (initial_pages as usize) * WASM_PAGE_SIZE;
         Self {
-            data: vec![0; size],
+            data: Rc::new(RefCell::new(vec![0; size])),
             max_pages,
         }
     }
 
-    pub fn grow(&mut self, pages: u32) -> i32 {
-        let current_pages = (self.data.len() / 65536) as u32;
-        let new_pages = current_pages + pages;
+    /// Grow by `pages` zeroed pages, returning the previous page count
+    pub fn grow(&self, pages: u32) -> Result<u32, WasmError> {
+        let current_pages = self.page_count();
+        let max_pages = self.max_pages.unwrap_or(WASM_MAX_PAGES).min(WASM_MAX_PAGES);
+        let new_pages = current_pages
+            .checked_add(pages)
+            .filter(|&new_pages| new_pages <= max_pages)
+            .ok_or(WasmError::GrowFailed { pages, max_pages })?;
 
-        if let Some(max) = self.max_pages {
-            if new_pages > max {
-                return -1; // Growth failed
-            }
-        }
+        self.data.borrow_mut().resize(new_pages as usize * WASM_PAGE_SIZE, 0);
+        Ok(current_pages)
+    }
+
+    /// Copy `len` bytes starting at `offset` out of the memory
+    pub fn read(&self, offset: u32, len: u32) -> Result<Vec<u8>, WasmError> {
+        let data = self.data.borrow();
+        let range = Self::range(offset, len, data.len())?;
+        Ok(data[range].to_vec())
+    }
+
+    /// Copy `bytes` into the memory starting at `offset`
+    pub fn write(&self, offset: u32, bytes: &[u8]) -> Result<(), WasmError> {
+        let mut data = self.data.borrow_mut();
+        let len = u32::try_from(bytes.len()).unwrap_or(u32::MAX);
+        let range = Self::range(offset, len, data.len())?;
+        data[range].copy_from_slice(bytes);
+        Ok(())
+    }
 
-        let new_size = (new_pages as usize) * 65536;
-        self.data.resize(new_size, 0);
-        current_pages as i32
+    /// Byte range of an access, if it lies within a memory of `size` bytes
+    fn range(offset: u32, len: u32, size: usize) -> Result<std::ops::Range<usize>, WasmError> {
+        let start = offset as usize;
+        start
+            .checked_add(len as usize)
+            .filter(|&end| end <= size)
+            .map(|end| start..end)
+            .ok_or(WasmError::OutOfBounds { offset, len, size })
     }
 
-    pub fn buffer(&self) -> &[u8] {
-        &self.data
+    pub fn buffer(&self) -> Ref<'_, [u8]> {
+        Ref::map(self.data.borrow(), Vec::as_slice)
     }
 
-    pub fn buffer_mut(&mut self) -> &mut [u8] {
-        &mut self.data
+    pub fn buffer_mut(&mut self) -> RefMut<'_, [u8]> {
+        RefMut::map(self.data.borrow_mut(), Vec::as_mut_slice)
     }
 
     pub fn page_count(&self) -> u32 {
-        (self.data.len() / 65536) as u32
+        (self.byte_length() / WASM_PAGE_SIZE) as u32
     }
 
     pub fn byte_length(&self) -> usize {
-        self.data.len()
+        self.data.borrow().len()
     }
 
     pub fn max_pages(&self) -> Option<u32> {
@@ -192,15 +955,6 @@ impl WasmMemory {
     }
 }
 
-impl Clone for WasmMemory {
-    fn clone(&self) -> Self {
-        Self {
-            data: self.data.clone(),
-            max_pages: self.max_pages,
-        }
-    }
-}
-
 pub struct WasmGlobal {
     value: WasmValue,
     mutable: bool,
@@ -229,24 +983,303 @@ impl WasmGlobal {
 }
 
 impl WasmInstance {
+    /// Instantiate `module`: create its memory, tables and globals, copy active
+    /// element and data segments into them, then run the start function
+    ///
+    /// A segment that does not fit its table or memory fails instantiation.
     pub fn new(
         module: WasmModule,
-        _imports: HashMap<String, WasmExport>,
+        imports: HashMap<String, WasmExport>,
     ) -> Result<Self, String> {
-        // Create default memory
-        let memory = Some(WasmMemory::new(1, None));
+        // Modules without a memory section get a default one-page memory
+        let memory = match module.sections.memory {
+            Some((initial, max)) => WasmMemory::new(initial, max),
+            None => WasmMemory::new(1, None),
+        };
 
-        Ok(Self {
+        let mut instance = Self {
             module,
-            memory,
+            memory: Some(memory),
+            globals: Vec::new(),
+            tables: Vec::new(),
             exports: HashMap::new(),
-        })
+            call_depth: 0,
+        };
+        let sections = instance.module.sections.clone();
+
+        for name in &sections.imported_globals {
+            match imports.get(name) {
+                Some(WasmExport::Global(global)) => instance
+                    .globals
+                    .push(WasmGlobal::new(global.value().clone(), global.is_mutable())),
+                _ => return Err(format!("Missing global import: {}", name)),
+            }
+        }
+        for global in &sections.globals {
+            let value = instance.eval_const(&global.init)?;
+            instance.globals.push(WasmGlobal::new(value, global.mutable));
+        }
+        instance.tables = sections
+            .tables
+            .iter()
+            .map(|(ty, size)| vec![WasmValue::default_for(ty); *size as usize])
+            .collect();
+
+        for segment in &sections.elements {
+            let offset = instance.eval_offset(&segment.offset)?;
+            let table = instance
+                .tables
+                .get_mut(segment.table as usize)
+                .ok_or_else(|| format!("Unknown table: {}", segment.table))?;
+            let end = offset + segment.functions.len();
+            if end > table.len() {
+                return Err("out of bounds table access: element segment does not fit".to_string());
+            }
+            for (slot, &func) in table[offset..end].iter_mut().zip(&segment.functions) {
+                *slot = WasmValue::FuncRef(Some(func));
+            }
+        }
+
+        for segment in &sections.data {
+            let offset = instance.eval_offset(&segment.offset)?;
+            if segment.memory != 0 {
+                return Err(format!("Unknown memory: {}", segment.memory));
+            }
+            let mut buffer = instance.memory.as_mut().unwrap().buffer_mut();
+            let end = offset + segment.bytes.len();
+            if end > buffer.len() {
+                return Err("out of bounds memory access: data segment does not fit".to_string());
+            }
+            buffer[offset..end].copy_from_slice(&segment.bytes);
+        }
+
+        if let Some(start) = sections.start {
+            instance.invoke(start, Vec::new())?;
+        }
+
+        Ok(instance)
+    }
+
+    fn eval_const(&self, expr: &ConstExpr) -> Result<WasmValue, String> {
+        match expr {
+            ConstExpr::Value(value) => Ok(value.clone()),
+            ConstExpr::GlobalGet(index) => self
+                .globals
+                .get(*index as usize)
+                .map(|g| g.value().clone())
+                .ok_or_else(|| format!("Unknown global: {}", index)),
+        }
+    }
+
+    fn eval_offset(&self, expr: &ConstExpr) -> Result<usize, String> {
+        match self.eval_const(expr)? {
+            WasmValue::I32(offset) => Ok(offset as u32 as usize),
+            other => Err(format!("Segment offset must be i32, got {:?}", other)),
+        }
+    }
+
+    /// Run the function at `index` with `args`, trapping once calls nest
+    /// deeper than [`WASM_MAX_CALL_DEPTH`]
+    fn invoke(&mut self, index: u32, args: Vec<WasmValue>) -> Result<Vec<WasmValue>, String> {
+        if self.call_depth >= WASM_MAX_CALL_DEPTH {
+            return Err("call stack exhausted".to_string());
+        }
+        self.call_depth += 1;
+        let result = self.run_function(index, args);
+        self.call_depth -= 1;
+        result
+    }
+
+    /// Execute the body of the function at `index` with `args`
+    ///
+    /// Only straight-line code is supported: constants, locals, globals, i32
+    /// arithmetic, loads/stores, direct calls, and reference and table
+    /// instructions.
+    fn run_function(&mut self, index: u32, args: Vec<WasmValue>) -> Result<Vec<WasmValue>, String> {
+        if index < self.module.sections.imported_functions {
+            return Err(format!("Calling imported function {} is not supported", index));
+        }
+        let defined = (index - self.module.sections.imported_functions) as usize;
+        let type_index = *self
+            .module
+            .sections
+            .functions
+            .get(defined)
+            .ok_or_else(|| format!("Unknown function: {}", index))?;
+        let body = self
+            .module
+            .sections
+            .code
+            .get(defined)
+            .cloned()
+            .ok_or_else(|| format!("Missing body for function {}", index))?;
+        let (_, results) = self
+            .module
+            .sections
+            .types
+            .get(type_index as usize)
+            .cloned()
+            .ok_or_else(|| format!("Unknown type: {}", type_index))?;
+
+        let mut locals = args;
+        locals.extend(body.locals.iter().map(WasmValue::default_for));
+        let mut stack: Vec<WasmValue> = Vec::new();
+        let mut code = Reader::new(&body.code);
+
+        loop {
+            match code.byte()? {
+                0x00 => return Err("unreachable executed".to_string()),
+                0x01 => {}
+                0x0B | 0x0F => break,
+                0x10 => {
+                    let callee = code.u32()?;
+                    let callee_type = self.function_type(callee)?;
+                    let split = stack
+                        .len()
+                        .checked_sub(callee_type.0.len())
+                        .ok_or_else(|| "stack underflow".to_string())?;
+                    let call_args = stack.split_off(split);
+                    let returned = self.invoke(callee, call_args)?;
+                    stack.extend(returned);
+                }
+                0x1A => {
+                    pop(&mut stack)?;
+                }
+                0x20 => {
+                    let local = code.u32()? as usize;
+                    stack.push(locals.get(local).cloned().ok_or("Unknown local")?);
+                }
+                op @ (0x21 | 0x22) => {
+                    let local = code.u32()? as usize;
+                    let value = pop(&mut stack)?;
+                    // local.tee keeps the value on the stack
+                    if op == 0x22 {
+                        stack.push(value.clone());
+                    }
+                    *locals.get_mut(local).ok_or("Unknown local")? = value;
+                }
+                0x23 => {
+                    let global = code.u32()? as usize;
+                    stack.push(self.globals.get(global).ok_or("Unknown global")?.value().clone());
+                }
+                0x24 => {
+                    let global = code.u32()? as usize;
+                    let value = pop(&mut stack)?;
+                    self.globals.get_mut(global).ok_or("Unknown global")?.set_value(value)?;
+                }
+                op @ (0x25 | 0x26) => {
+                    let table = code.u32()? as usize;
+                    let value = if op == 0x26 { Some(pop(&mut stack)?) } else { None };
+                    let slot = pop_i32(&mut stack)? as u32 as usize;
+                    let slot = self
+                        .tables
+                        .get_mut(table)
+                        .ok_or("Unknown table")?
+                        .get_mut(slot)
+                        .ok_or_else(|| "out of bounds table access".to_string())?;
+                    match value {
+                        Some(value) => *slot = value,
+                        None => stack.push(slot.clone()),
+                    }
+                }
+                op @ (0x28 | 0x2D | 0x36 | 0x3A) => {
+                    code.u32()?; // alignment hint
+                    let offset = code.u32()? as usize;
+                    let width = if matches!(op, 0x28 | 0x36) { 4 } else { 1 };
+                    let value = if op >= 0x36 { Some(pop(&mut stack)?) } else { None };
+                    let address = pop_i32(&mut stack)? as u32 as usize + offset;
+                    let mut buffer = self.memory.as_mut().ok_or("No memory")?.buffer_mut();
+                    let bytes = buffer
+                        .get_mut(address..address + width)
+                        .ok_or_else(|| "out of bounds memory access".to_string())?;
+                    match value {
+                        Some(WasmValue::I32(v)) => bytes.copy_from_slice(&v.to_le_bytes()[..width]),
+                        Some(other) => return Err(format!("Expected i32 to store, got {:?}", other)),
+                        None if width == 4 => {
+                            stack.push(WasmValue::I32(i32::from_le_bytes(bytes[..4].try_into().unwrap())))
+                        }
+                        None => stack.push(WasmValue::I32(bytes[0] as i32)),
+                    }
+                }
+                0x41 => stack.push(WasmValue::I32(code.i32()?)),
+                0x42 => stack.push(WasmValue::I64(code.i64()?)),
+                0x43 => stack.push(WasmValue::F32(f32::from_le_bytes(code.bytes(4)?.try_into().unwrap()))),
+                0x44 => stack.push(WasmValue::F64(f64::from_le_bytes(code.bytes(8)?.try_into().unwrap()))),
+                op @ (0x6A..=0x6C) => {
+                    let rhs = pop_i32(&mut stack)?;
+                    let lhs = pop_i32(&mut stack)?;
+                    stack.push(WasmValue::I32(match op {
+                        0x6A => lhs.wrapping_add(rhs),
+                        0x6B => lhs.wrapping_sub(rhs),
+                        _ => lhs.wrapping_mul(rhs),
+                    }));
+                }
+                0xD0 => stack.push(WasmValue::null(&code.ref_type()?)),
+                0xD1 => {
+                    let is_null = pop(&mut stack)?.is_null();
+                    stack.push(WasmValue::I32(is_null as i32));
+                }
+                0xD2 => stack.push(WasmValue::FuncRef(Some(code.u32()?))),
+                other => return Err(format!("Unsupported instruction: 0x{:02x}", other)),
+            }
+        }
+
+        let split = stack.len().saturating_sub(results.len());
+        Ok(stack.split_off(split))
+    }
+
+    fn function_type(&self, index: u32) -> Result<(Vec<WasmType>, Vec<WasmType>), String> {
+        let sections = &self.module.sections;
+        let defined = index
+            .checked_sub(sections.imported_functions)
+            .ok_or_else(|| format!("Calling imported function {} is not supported", index))?;
+        sections
+            .functions
+            .get(defined as usize)
+            .and_then(|&ty| sections.types.get(ty as usize))
+            .cloned()
+            .ok_or_else(|| format!("Unknown function: {}", index))
     }
 
     pub fn get_export(&self, name: &str) -> Option<&WasmExport> {
         self.exports.get(name)
     }
 
+    /// Handle to the memory exported as `name`, sharing the instance's bytes
+    pub fn get_memory(&self, name: &str) -> Option<WasmMemory> {
+        if let Some(WasmExport::Memory(memory)) = self.exports.get(name) {
+            return Some(memory.clone());
+        }
+        self.module
+            .sections
+            .exports
+            .iter()
+            .any(|export| export.name == name && export.kind == ExportKind::Memory)
+            .then(|| self.memory.clone())
+            .flatten()
+    }
+
+    /// Call the function exported as `name` with `args`
+    pub fn call_export(&mut self, name: &str, args: Vec<WasmValue>) -> Result<Vec<WasmValue>, String> {
+        let sections = &self.module.sections;
+        let index = sections
+            .exports
+            .iter()
+            .position(|export| export.name == name && export.kind == ExportKind::Function)
+            .map(|position| sections.export_indices[position])
+            .ok_or_else(|| format!("No exported function: {}", name))?;
+        let (params, _) = self.function_type(index)?;
+        if args.len() != params.len() {
+            return Err(format!(
+                "Function {} expects {} arguments, got {}",
+                name,
+                params.len(),
+                args.len()
+            ));
+        }
+        self.invoke(index, args)
+    }
+
     pub fn memory(&self) -> Option<&WasmMemory> {
         self.memory.as_ref()
     }
@@ -259,11 +1292,32 @@ impl WasmInstance {
         &self.module
     }
 
+    /// Global at `index` in the module's global index space
+    pub fn global(&self, index: u32) -> Option<&WasmGlobal> {
+        self.globals.get(index as usize)
+    }
+
+    /// References stored in table `index`; empty slots hold null
+    pub fn table(&self, index: u32) -> Option<&[WasmValue]> {
+        self.tables.get(index as usize).map(Vec::as_slice)
+    }
+
     pub fn add_export(&mut self, name: String, export: WasmExport) {
         self.exports.insert(name, export);
     }
 }
 
+fn pop(stack: &mut Vec<WasmValue>) -> Result<WasmValue, String> {
+    stack.pop().ok_or_else(|| "stack underflow".to_string())
+}
+
+fn pop_i32(stack: &mut Vec<WasmValue>) -> Result<i32, String> {
+    match pop(stack)? {
+        WasmValue::I32(v) => Ok(v),
+        other => Err(format!("Expected i32, got {:?}", other)),
+    }
+}
+
 /// Main WebAssembly API object
 pub struct WebAssembly;
 
@@ -280,7 +1334,45 @@ impl WebAssembly {
     }
 
     pub fn validate(bytes: &[u8]) -> bool {
-        bytes.len() >= 8 && &bytes[0..4] == b"\x00asm"
+        Self::validate_detailed(bytes).is_ok()
+    }
+
+    /// Validate a module, explaining the first problem found
+    ///
+    /// Checks the header, decodes every section, and type-checks function
+    /// bodies, e.g. "type mismatch at function 3: expected i32, got f64".
+    pub fn validate_detailed(bytes: &[u8]) -> Result<(), WasmValidationError> {
+        if bytes.len() < 8 {
+            return Err(WasmValidationError::new(bytes.len(), "header", "Invalid WASM: too short"));
+        }
+        if &bytes[0..4] != b"\x00asm" {
+            return Err(WasmValidationError::new(0, "header", "Invalid WASM: missing magic number"));
+        }
+        let version = u32::from_le_bytes(bytes[4..8].try_into().unwrap());
+        if version != 1 {
+            return Err(WasmValidationError::new(
+                4,
+                "header",
+                format!("Unsupported WASM version: {}", version),
+            ));
+        }
+
+        let sections = ModuleSections::decode_located(&bytes[8..])?;
+        if sections.functions.len() != sections.code.len() {
+            return Err(WasmValidationError::new(
+                bytes.len(),
+                "code",
+                format!(
+                    "Invalid WASM: {} functions declared but {} bodies found",
+                    sections.functions.len(),
+                    sections.code.len()
+                ),
+            ));
+        }
+        for defined in 0..sections.code.len() {
+            sections.check_function(defined)?;
+        }
+        Ok(())
     }
 }
 
@@ -413,36 +1505,38 @@ mod tests {
 
     #[test]
     fn test_wasm_memory_grow_success() {
-        let mut memory = WasmMemory::new(1, Some(5));
+        let memory = WasmMemory::new(1, Some(5));
         let old_pages = memory.grow(2);
-        assert_eq!(old_pages, 1);
+        assert_eq!(old_pages, Ok(1));
         assert_eq!(memory.page_count(), 3);
         assert_eq!(memory.byte_length(), 3 * 65536);
     }
 
     #[test]
     fn test_wasm_memory_grow_failure_exceeds_max() {
-        let mut memory = WasmMemory::new(1, Some(3));
+        let memory = WasmMemory::new(1, Some(3));
         let result = memory.grow(5);
-        assert_eq!(result, -1);
+        assert_eq!(result, Err(WasmError::GrowFailed { pages: 5, max_pages: 3 }));
         assert_eq!(memory.page_count(), 1); // Unchanged
     }
 
     #[test]
     fn test_wasm_memory_grow_no_max() {
-        let mut memory = WasmMemory::new(1, None);
+        let memory = WasmMemory::new(1, None);
         let old_pages = memory.grow(10);
-        assert_eq!(old_pages, 1);
+        assert_eq!(old_pages, Ok(1));
         assert_eq!(memory.page_count(), 11);
     }
 
     #[test]
     fn test_wasm_memory_buffer_read_write() {
         let mut memory = WasmMemory::new(1, None);
-        let buffer = memory.buffer_mut();
-        buffer[0] = 0xFF;
-        buffer[100] = 0xAB;
-        buffer[65535] = 0xCD;
+        {
+            let mut buffer = memory.buffer_mut();
+            buffer[0] = 0xFF;
+            buffer[100] = 0xAB;
+            buffer[65535] = 0xCD;
+        }
 
         let read_buffer = memory.buffer();
         assert_eq!(read_buffer[0], 0xFF);
@@ -535,6 +1629,7 @@ mod tests {
         let desc = ExportDescriptor {
             name: "memory".to_string(),
             kind: ExportKind::Memory,
+            func_type: None,
         };
         assert_eq!(desc.name, "memory");
         assert_eq!(desc.kind, ExportKind::Memory);
@@ -546,6 +1641,7 @@ mod tests {
             module: "env".to_string(),
             name: "print".to_string(),
             kind: ExportKind::Function,
+            func_type: None,
         };
         assert_eq!(desc.module, "env");
         assert_eq!(desc.name, "print");
@@ -605,4 +1701,386 @@ mod tests {
         assert_eq!(WasmType::F64, WasmType::F64);
         assert_ne!(WasmType::I32, WasmType::I64);
     }
+
+    fn section(id: u8, payload: &[u8]) -> Vec<u8> {
+        let mut bytes = vec![id, payload.len() as u8];
+        bytes.extend_from_slice(payload);
+        bytes
+    }
+
+    /// Module with a one-page memory, a two-slot table, a mutable i32 global,
+    /// a data segment writing [7, 8, 9] at `data_offset`, an element segment
+    /// placing function 0 in table slot 1, and a start function that sets the
+    /// global to `memory[16] + 100`
+    fn segments_and_start_wasm(data_offset: u8) -> Vec<u8> {
+        let mut bytes = minimal_wasm();
+        bytes.extend(section(1, &[0x01, 0x60, 0x00, 0x00])); // type: () -> ()
+        bytes.extend(section(3, &[0x01, 0x00])); // function 0: type 0
+        bytes.extend(section(4, &[0x01, 0x70, 0x00, 0x02])); // table: funcref, min 2
+        bytes.extend(section(5, &[0x01, 0x00, 0x01])); // memory: min 1 page
+        bytes.extend(section(6, &[0x01, 0x7F, 0x01, 0x41, 0x00, 0x0B])); // global: mut i32 = 0
+        bytes.extend(section(8, &[0x00])); // start: function 0
+        bytes.extend(section(9, &[0x01, 0x00, 0x41, 0x01, 0x0B, 0x01, 0x00])); // elem: table[1] = 0
+        bytes.extend(section(
+            10,
+            &[
+                0x01, 0x0D, 0x00, // one body of 13 bytes, no locals
+                0x41, 0x10, // i32.const 16
+                0x2D, 0x00, 0x00, // i32.load8_u
+                0x41, 0xE4, 0x00, // i32.const 100
+                0x6A, // i32.add
+                0x24, 0x00, // global.set 0
+                0x0B, // end
+            ],
+        ));
+        bytes.extend(section(11, &[0x01, 0x00, 0x41, data_offset, 0x0B, 0x03, 7, 8, 9]));
+        bytes
+    }
+
+    #[test]
+    fn test_instantiate_applies_segments_before_start() {
+        let module = WebAssembly::compile(&segments_and_start_wasm(0x10)).unwrap();
+        let instance = WebAssembly::instantiate(module, HashMap::new()).unwrap();
+
+        let memory = instance.memory().unwrap();
+        assert_eq!(&memory.buffer()[15..20], &[0, 7, 8, 9, 0]);
+        assert_eq!(
+            instance.table(0).unwrap(),
+            &[WasmValue::FuncRef(None), WasmValue::FuncRef(Some(0))]
+        );
+        // The start function saw the data segment's first byte
+        assert_eq!(instance.global(0).unwrap().value(), &WasmValue::I32(107));
+    }
+
+    #[test]
+    fn test_instantiate_traps_on_unbounded_recursion() {
+        let mut bytes = minimal_wasm();
+        bytes.extend(section(1, &[0x01, 0x60, 0x00, 0x00])); // type: () -> ()
+        bytes.extend(section(3, &[0x01, 0x00])); // function 0: type 0
+        bytes.extend(section(8, &[0x00])); // start: function 0
+        bytes.extend(section(10, &[0x01, 0x04, 0x00, 0x10, 0x00, 0x0B])); // call 0; end
+        assert_eq!(WebAssembly::validate_detailed(&bytes), Ok(()));
+
+        let module = WebAssembly::compile(&bytes).unwrap();
+        match WebAssembly::instantiate(module, HashMap::new()) {
+            Err(e) => assert_eq!(e, "call stack exhausted"),
+            Ok(_) => panic!("expected the recursive start function to trap"),
+        }
+    }
+
+    #[test]
+    fn test_instantiate_traps_on_out_of_bounds_data_segment() {
+        let mut bytes = segments_and_start_wasm(0x7F); // i32.const -1: offset 0xFFFFFFFF
+        let module = WebAssembly::compile(&bytes).unwrap();
+        match WebAssembly::instantiate(module, HashMap::new()) {
+            Err(e) => assert!(e.contains("out of bounds memory access"), "{}", e),
+            Ok(_) => panic!("expected the data segment to trap"),
+        }
+
+        // Truncated sections are rejected at compile time
+        bytes.pop();
+        assert!(WebAssembly::compile(&bytes).is_err());
+    }
+
+    /// Module exporting a one-page "memory" and `reverse(ptr)`, which reverses
+    /// the four bytes at `ptr` in place
+    fn reverse_wasm() -> Vec<u8> {
+        let mut body = vec![0x01, 0x04, 0x7F]; // four i32 locals
+        for i in 0..4 {
+            body.extend([0x20, 0x00, 0x2D, 0x00, i, 0x21, i + 1]); // local[i+1] = load8_u(ptr + i)
+        }
+        for i in 0..4 {
+            body.extend([0x20, 0x00, 0x20, 4 - i, 0x3A, 0x00, i]); // store8(ptr + i, local[4-i])
+        }
+        body.push(0x0B);
+        let mut code = vec![0x01, body.len() as u8];
+        code.extend(body);
+
+        let mut bytes = minimal_wasm();
+        bytes.extend(section(1, &[0x01, 0x60, 0x01, 0x7F, 0x00])); // type: (i32) -> ()
+        bytes.extend(section(3, &[0x01, 0x00])); // function 0: type 0
+        bytes.extend(section(5, &[0x01, 0x00, 0x01])); // memory: min 1 page
+        bytes.extend(section(
+            7,
+            &[
+                0x02, // two exports
+                0x06, b'm', b'e', b'm', b'o', b'r', b'y', 0x02, 0x00, // memory 0
+                0x07, b'r', b'e', b'v', b'e', b'r', b's', b'e', 0x00, 0x00, // function 0
+            ],
+        ));
+        bytes.extend(section(10, &code));
+        bytes
+    }
+
+    #[test]
+    fn test_exported_memory_round_trips_through_call() {
+        let module = WebAssembly::compile(&reverse_wasm()).unwrap();
+        let mut instance = WebAssembly::instantiate(module, HashMap::new()).unwrap();
+        let memory = instance.get_memory("memory").unwrap();
+        assert!(instance.get_memory("reverse").is_none());
+
+        memory.write(0, b"wasm").unwrap();
+        instance.call_export("reverse", vec![WasmValue::I32(0)]).unwrap();
+        assert_eq!(memory.read(0, 4).unwrap(), b"msaw");
+
+        assert_eq!(memory.grow(1), Ok(1));
+        assert_eq!(instance.memory().unwrap().page_count(), 2);
+    }
+
+    /// Module importing an externref global `env.ref`, with an externref
+    /// table and functions `get`, `echo`, `stash`, `load` and `is_null` that
+    /// move references between the global, arguments and the table
+    fn externref_wasm() -> Vec<u8> {
+        let mut bytes = minimal_wasm();
+        bytes.extend(section(
+            1,
+            &[
+                0x03, // three types
+                0x60, 0x00, 0x01, 0x6F, // () -> externref
+                0x60, 0x01, 0x6F, 0x01, 0x6F, // (externref) -> externref
+                0x60, 0x01, 0x6F, 0x01, 0x7F, // (externref) -> i32
+            ],
+        ));
+        bytes.extend(section(
+            2,
+            &[0x01, 0x03, b'e', b'n', b'v', 0x03, b'r', b'e', b'f', 0x03, 0x6F, 0x00], // env.ref: externref
+        ));
+        bytes.extend(section(3, &[0x05, 0x00, 0x01, 0x01, 0x00, 0x02]));
+        bytes.extend(section(4, &[0x01, 0x6F, 0x00, 0x01])); // table: externref, min 1
+        let mut exports = vec![0x05];
+        for (index, name) in ["get", "echo", "stash", "load", "is_null"].iter().enumerate() {
+            exports.push(name.len() as u8);
+            exports.extend(name.bytes());
+            exports.extend([0x00, index as u8]);
+        }
+        bytes.extend(section(7, &exports));
+        bytes.extend(section(
+            10,
+            &[
+                0x05, // five bodies
+                0x04, 0x00, 0x23, 0x00, 0x0B, // get: global.get 0
+                0x04, 0x00, 0x20, 0x00, 0x0B, // echo: local.get 0
+                // stash: table[0] = arg, returning the slot's previous value
+                0x0C, 0x00, 0x41, 0x00, 0x25, 0x00, 0x41, 0x00, 0x20, 0x00, 0x26, 0x00, 0x0B,
+                0x06, 0x00, 0x41, 0x00, 0x25, 0x00, 0x0B, // load: table.get 0 [0]
+                0x05, 0x00, 0x20, 0x00, 0xD1, 0x0B, // is_null: ref.is_null (local 0)
+            ],
+        ));
+        bytes
+    }
+
+    fn externref_instance(imported: JsValue) -> WasmInstance {
+        let bytes = externref_wasm();
+        assert!(WebAssembly::validate_detailed(&bytes).is_ok());
+        let module = WebAssembly::compile(&bytes).unwrap();
+        let mut imports = HashMap::new();
+        imports.insert(
+            "ref".to_string(),
+            WasmExport::Global(WasmGlobal::new(WasmValue::ExternRef(imported), false)),
+        );
+        WebAssembly::instantiate(module, imports).unwrap()
+    }
+
+    #[test]
+    fn test_externref_preserves_js_object_identity() {
+        let host = JsValue::object();
+        let mut instance = externref_instance(host.clone());
+
+        // Object references compare by identity
+        assert_eq!(instance.call_export("get", vec![]).unwrap(), vec![WasmValue::ExternRef(host.clone())]);
+
+        let other = JsValue::object();
+        let echoed = instance.call_export("echo", vec![WasmValue::ExternRef(other.clone())]).unwrap();
+        assert_eq!(echoed, vec![WasmValue::ExternRef(other.clone())]);
+        assert_ne!(echoed, vec![WasmValue::ExternRef(host)]);
+
+        // Stored in the table and read back as the same reference
+        let previous = instance.call_export("stash", vec![WasmValue::ExternRef(other.clone())]).unwrap();
+        assert_eq!(previous, vec![WasmValue::ExternRef(JsValue::Null)]);
+        assert_eq!(instance.table(0).unwrap(), &[WasmValue::ExternRef(other.clone())]);
+        assert_eq!(instance.call_export("load", vec![]).unwrap(), vec![WasmValue::ExternRef(other)]);
+    }
+
+    #[test]
+    fn test_null_externref_round_trips_as_js_null() {
+        let mut instance = externref_instance(JsValue::Null);
+        let null = WasmValue::ExternRef(JsValue::Null);
+
+        assert_eq!(instance.call_export("get", vec![]).unwrap(), vec![null.clone()]);
+        assert_eq!(instance.call_export("echo", vec![null.clone()]).unwrap(), vec![null.clone()]);
+        assert_eq!(instance.call_export("is_null", vec![null]).unwrap(), vec![WasmValue::I32(1)]);
+        assert_eq!(
+            instance.call_export("is_null", vec![WasmValue::ExternRef(JsValue::object())]).unwrap(),
+            vec![WasmValue::I32(0)]
+        );
+        assert_eq!(instance.call_export("load", vec![]).unwrap(), vec![WasmValue::ExternRef(JsValue::Null)]);
+    }
+
+    #[test]
+    fn test_memory_read_write_bounds_checked() {
+        let memory = WasmMemory::new(1, None);
+        assert!(memory.write(65532, &[1, 2, 3, 4]).is_ok());
+        assert_eq!(
+            memory.write(65533, &[1, 2, 3, 4]),
+            Err(WasmError::OutOfBounds { offset: 65533, len: 4, size: 65536 })
+        );
+        assert_eq!(memory.read(65532, 4).unwrap(), vec![1, 2, 3, 4]);
+        assert!(memory.read(65536, 1).is_err());
+        assert!(memory.read(u32::MAX, u32::MAX).is_err());
+    }
+
+    #[test]
+    fn test_module_import_and_export_signatures() {
+        let mut bytes = minimal_wasm();
+        bytes.extend(section(
+            1,
+            &[
+                0x02, // two types
+                0x60, 0x01, 0x7F, 0x00, // (i32) -> ()
+                0x60, 0x02, 0x7F, 0x7F, 0x01, 0x7F, // (i32, i32) -> i32
+            ],
+        ));
+        bytes.extend(section(
+            2,
+            &[0x01, 0x03, b'e', b'n', b'v', 0x03, b'l', b'o', b'g', 0x00, 0x00], // env.log: type 0
+        ));
+        bytes.extend(section(3, &[0x01, 0x01])); // function 1: type 1
+        bytes.extend(section(
+            7,
+            &[
+                0x02, // two exports
+                0x03, b'a', b'd', b'd', 0x00, 0x01, // "add" = function 1
+                0x03, b'l', b'o', b'g', 0x00, 0x00, // "log" re-exports the import
+            ],
+        ));
+        bytes.extend(section(10, &[0x01, 0x07, 0x00, 0x20, 0x00, 0x20, 0x01, 0x6A, 0x0B]));
+        let module = WasmModule::compile(&bytes).unwrap();
+
+        let log_type = FuncType { params: vec![WasmType::I32], results: vec![] };
+        let add_type = FuncType {
+            params: vec![WasmType::I32, WasmType::I32],
+            results: vec![WasmType::I32],
+        };
+        assert_eq!(
+            module.imports(),
+            &[ImportDescriptor {
+                module: "env".to_string(),
+                name: "log".to_string(),
+                kind: ExportKind::Function,
+                func_type: Some(log_type.clone()),
+            }]
+        );
+        assert_eq!(
+            module.exports(),
+            &[
+                ExportDescriptor {
+                    name: "add".to_string(),
+                    kind: ExportKind::Function,
+                    func_type: Some(add_type),
+                },
+                ExportDescriptor {
+                    name: "log".to_string(),
+                    kind: ExportKind::Function,
+                    func_type: Some(log_type),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_export_of_unknown_function_is_rejected() {
+        let mut bytes = minimal_wasm();
+        bytes.extend(section(7, &[0x01, 0x01, b'f', 0x00, 0x00]));
+        assert!(WasmModule::compile(&bytes).unwrap_err().contains("unknown function"));
+    }
+
+    #[test]
+    fn test_overlong_signed_integer_is_rejected() {
+        // Eleven LEB128 bytes: the last would shift by 70 bits
+        let mut overlong = vec![0x80; 10];
+        overlong.push(0x00);
+        assert!(Reader::new(&overlong).i64().is_err());
+        let mut reader = Reader::new(&[0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x7F]);
+        assert_eq!(reader.i64(), Ok(-1));
+
+        let mut reader = Reader::new(&[0xFF, 0xFF, 0xFF, 0xFF, 0x7F]);
+        assert_eq!(reader.i32(), Ok(-1));
+        let mut reader = Reader::new(&[0x80, 0x80, 0x80, 0x80, 0x80, 0x00]);
+        assert!(reader.i32().is_err());
+    }
+
+    #[test]
+    fn test_oversized_table_and_locals_are_rejected() {
+        let mut bytes = minimal_wasm();
+        // table: funcref, min 0xFFFFFFFF
+        bytes.extend(section(4, &[0x01, 0x70, 0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0x0F]));
+        assert!(WebAssembly::compile(&bytes).is_err());
+
+        let mut bytes = minimal_wasm();
+        bytes.extend(section(1, &[0x01, 0x60, 0x00, 0x00]));
+        bytes.extend(section(3, &[0x01, 0x00]));
+        // one body declaring 0xFFFFFFFF i32 locals
+        bytes.extend(section(10, &[0x01, 0x08, 0x01, 0xFF, 0xFF, 0xFF, 0xFF, 0x0F, 0x7F, 0x0B]));
+        assert!(WebAssembly::compile(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_validate_detailed_bad_magic() {
+        let err = WebAssembly::validate_detailed(&[0xFF, 0xFF, 0xFF, 0xFF, 1, 0, 0, 0]).unwrap_err();
+        assert_eq!(err.offset, 0);
+        assert_eq!(err.section, "header");
+        assert!(err.message.contains("magic number"));
+        assert!(!WebAssembly::validate(&[0xFF, 0xFF, 0xFF, 0xFF, 1, 0, 0, 0]));
+    }
+
+    #[test]
+    fn test_validate_detailed_truncated_section() {
+        let mut bytes = minimal_wasm();
+        bytes.extend([0x01, 0x05, 0x01, 0x60]); // type section claims 5 bytes, has 2
+        let err = WebAssembly::validate_detailed(&bytes).unwrap_err();
+        assert_eq!(err.section, "type");
+        assert_eq!(err.offset, 10);
+        assert!(err.message.contains("unexpected end of section"));
+    }
+
+    #[test]
+    fn test_validate_detailed_type_mismatch() {
+        let mut bytes = minimal_wasm();
+        bytes.extend(section(1, &[0x01, 0x60, 0x00, 0x01, 0x7F])); // type: () -> i32
+        bytes.extend(section(3, &[0x01, 0x00]));
+        // f64.const 1.5; end
+        let mut body = vec![0x0B, 0x00, 0x44];
+        body.extend(1.5f64.to_le_bytes());
+        body.push(0x0B);
+        let mut code = vec![0x01];
+        code.extend(body);
+        bytes.extend(section(10, &code));
+
+        let err = WebAssembly::validate_detailed(&bytes).unwrap_err();
+        assert_eq!(err.section, "code");
+        assert_eq!(err.message, "type mismatch at function 0: expected i32, got f64");
+        assert_eq!(err.offset, bytes.len() - 1); // the `end` instruction
+        assert!(WasmModule::compile(&bytes).is_ok());
+    }
+
+    #[test]
+    fn test_validate_detailed_unsupported_instruction() {
+        let mut bytes = minimal_wasm();
+        bytes.extend(section(1, &[0x01, 0x60, 0x00, 0x00])); // type: () -> ()
+        bytes.extend(section(3, &[0x01, 0x00]));
+        // block; end; end
+        bytes.extend(section(10, &[0x01, 0x05, 0x00, 0x02, 0x40, 0x0B, 0x0B]));
+
+        let err = WebAssembly::validate_detailed(&bytes).unwrap_err();
+        assert_eq!(err.section, "code");
+        assert_eq!(err.message, "Unsupported instruction: 0x02");
+        assert_eq!(err.offset, bytes.len() - 4); // the `block` instruction
+        assert!(!WebAssembly::validate(&bytes));
+    }
+
+    #[test]
+    fn test_validate_detailed_accepts_well_typed_module() {
+        assert_eq!(WebAssembly::validate_detailed(&segments_and_start_wasm(0x10)), Ok(()));
+        assert_eq!(WebAssembly::validate_detailed(&minimal_wasm()), Ok(()));
+    }
 }
@@ -1,11 +1,15 @@
-use std::sync::{Arc, Mutex, mpsc};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Condvar, Mutex, mpsc};
 use std::thread;
+use std::time::{Duration, Instant};
 use serde::{Serialize, Deserialize};
+use crate::structured_clone::{ArrayBuffer, StructuredClone, StructuredValue, Transferable};
 
 /// Message between main thread and worker
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum WorkerMessage {
     Data(String),  // JSON-serialized data
+    Structured(StructuredValue),  // Output of the structured clone algorithm
     Terminate,
 }
 
@@ -51,6 +55,9 @@ impl Worker {
                     // Echo back for now (real impl would execute JS)
                     let _ = tx.send(WorkerMessage::Data(data));
                 }
+                Ok(WorkerMessage::Structured(value)) => {
+                    let _ = tx.send(WorkerMessage::Structured(value));
+                }
                 Ok(WorkerMessage::Terminate) | Err(_) => break,
             }
         }
@@ -61,6 +68,19 @@ impl Worker {
             .map_err(|e| e.to_string())
     }
 
+    /// Posts a structured value, transferring the listed buffers to the worker.
+    ///
+    /// The message goes through the structured clone algorithm. Each buffer
+    /// in `transfer` must be reachable from `value`; its allocation moves to
+    /// the worker without a copy and the sender's buffer is left detached.
+    pub fn post_message_with_transfer(&self, value: StructuredValue, transfer: Vec<ArrayBuffer>) -> Result<(), String> {
+        let transfer: Vec<Transferable> = transfer.into_iter().map(Transferable::ArrayBuffer).collect();
+        let cloned = StructuredClone::clone_with_transfer(&value, &transfer)
+            .map_err(|e| e.to_string())?;
+        self.sender.send(WorkerMessage::Structured(cloned))
+            .map_err(|e| e.to_string())
+    }
+
     pub fn receive_message(&self) -> Option<String> {
         let rx = self.receiver.lock().ok()?;
         match rx.try_recv() {
@@ -69,6 +89,17 @@ impl Worker {
         }
     }
 
+    /// Receives the next message as a structured value; string messages
+    /// arrive as `StructuredValue::String`.
+    pub fn receive_structured_message(&self) -> Option<StructuredValue> {
+        let rx = self.receiver.lock().ok()?;
+        match rx.try_recv() {
+            Ok(WorkerMessage::Structured(value)) => Some(value),
+            Ok(WorkerMessage::Data(data)) => Some(StructuredValue::String(data)),
+            _ => None,
+        }
+    }
+
     pub fn terminate(&mut self) {
         let _ = self.sender.send(WorkerMessage::Terminate);
         if let Some(handle) = self.thread_handle.take() {
@@ -85,15 +116,31 @@ impl Drop for Worker {
     }
 }
 
+/// Agents blocked in `Atomics.wait` on one index, in arrival order
+struct WaiterList {
+    condvar: Arc<Condvar>,
+    waiting: VecDeque<u64>,
+    woken: HashSet<u64>,
+}
+
+/// Futex-like wait queues for a shared buffer, keyed by byte index
+#[derive(Default)]
+struct WaitQueues {
+    next_id: u64,
+    lists: HashMap<usize, WaiterList>,
+}
+
 /// SharedArrayBuffer for shared memory between workers
 pub struct SharedArrayBuffer {
     data: Arc<Mutex<Vec<u8>>>,
+    waiters: Arc<Mutex<WaitQueues>>,
 }
 
 impl SharedArrayBuffer {
     pub fn new(byte_length: usize) -> Self {
         Self {
             data: Arc::new(Mutex::new(vec![0; byte_length])),
+            waiters: Arc::new(Mutex::new(WaitQueues::default())),
         }
     }
 
@@ -109,10 +156,24 @@ impl SharedArrayBuffer {
 
 impl Clone for SharedArrayBuffer {
     fn clone(&self) -> Self {
-        Self { data: Arc::clone(&self.data) }
+        Self {
+            data: Arc::clone(&self.data),
+            waiters: Arc::clone(&self.waiters),
+        }
     }
 }
 
+/// Outcome of `Atomics.wait`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WaitResult {
+    /// Woken by `Atomics.notify`
+    Ok,
+    /// The value at the index did not match, so the agent never slept
+    NotEqual,
+    /// The timeout elapsed before a notify arrived
+    TimedOut,
+}
+
 /// Atomics operations for SharedArrayBuffer
 pub struct Atomics;
 
@@ -157,4 +218,81 @@ impl Atomics {
             0
         }
     }
+
+    /// Blocks until `notify` wakes this agent, provided the value at `index`
+    /// equals `value`. `None` waits without a timeout.
+    ///
+    /// Out-of-bounds indices return `NotEqual` without blocking.
+    pub fn wait(buffer: &SharedArrayBuffer, index: usize, value: i32, timeout: Option<Duration>) -> WaitResult {
+        // The wait queues stay locked from the value check until the agent
+        // sleeps, so a store followed by a notify can't slip in between
+        let mut queues = buffer.waiters.lock().unwrap();
+        let in_bounds = index.checked_add(4).is_some_and(|end| end <= buffer.byte_length());
+        if !in_bounds || Self::load(buffer, index) != value {
+            return WaitResult::NotEqual;
+        }
+
+        let id = queues.next_id;
+        queues.next_id += 1;
+        let condvar = {
+            let list = queues.lists.entry(index).or_insert_with(|| WaiterList {
+                condvar: Arc::new(Condvar::new()),
+                waiting: VecDeque::new(),
+                woken: HashSet::new(),
+            });
+            list.waiting.push_back(id);
+            Arc::clone(&list.condvar)
+        };
+
+        // A timeout too long to represent as a deadline waits forever
+        let deadline = timeout.and_then(|t| Instant::now().checked_add(t));
+        let result = loop {
+            let list = queues.lists.get_mut(&index).unwrap();
+            if list.woken.remove(&id) {
+                break WaitResult::Ok;
+            }
+            match deadline {
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        list.waiting.retain(|&w| w != id);
+                        break WaitResult::TimedOut;
+                    }
+                    queues = condvar.wait_timeout(queues, deadline - now).unwrap().0;
+                }
+                None => queues = condvar.wait(queues).unwrap(),
+            }
+        };
+
+        if queues.lists.get(&index).is_some_and(|l| l.waiting.is_empty() && l.woken.is_empty()) {
+            queues.lists.remove(&index);
+        }
+        result
+    }
+
+    /// Wakes up to `count` agents waiting on `index`, oldest first.
+    ///
+    /// Returns the number of agents woken; out-of-bounds indices wake none.
+    pub fn notify(buffer: &SharedArrayBuffer, index: usize, count: u32) -> u32 {
+        let mut queues = buffer.waiters.lock().unwrap();
+        if index.checked_add(4).is_none_or(|end| end > buffer.byte_length()) {
+            return 0;
+        }
+        let Some(list) = queues.lists.get_mut(&index) else {
+            return 0;
+        };
+
+        let mut woken = 0;
+        while woken < count {
+            let Some(id) = list.waiting.pop_front() else {
+                break;
+            };
+            list.woken.insert(id);
+            woken += 1;
+        }
+        if woken > 0 {
+            list.condvar.notify_all();
+        }
+        woken
+    }
 }
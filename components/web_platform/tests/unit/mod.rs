@@ -1,4 +1,4 @@
-use web_platform::{Worker, SharedArrayBuffer, Atomics, DevToolsServer, DebugProtocol, SourceMap, ContentSecurityPolicy};
+use web_platform::{Worker, SharedArrayBuffer, Atomics, WaitResult, DevToolsServer, DebugProtocol, SourceMap, ContentSecurityPolicy};
 use web_platform::devtools::{ProtocolMessage, CallFrame, Location, Scope, RemoteObject};
 use web_platform::source_maps::{SourceMapping, OriginalPosition, GeneratedPosition};
 use web_platform::csp::CspViolation;
@@ -42,6 +42,38 @@ mod worker_tests {
         assert_eq!(response, Some("test message".to_string()));
     }
 
+    #[test]
+    fn test_worker_post_message_with_transfer_detaches_buffer() {
+        use web_platform::{ArrayBuffer, StructuredValue};
+
+        let worker = Worker::new("test.js").expect("Should create worker");
+        let buffer = ArrayBuffer::from_bytes(vec![1, 2, 3, 4]);
+        let message = StructuredValue::Object(vec![
+            ("kind".to_string(), StructuredValue::String("frame".to_string())),
+            ("data".to_string(), StructuredValue::ArrayBufferHandle(buffer.clone())),
+        ]);
+
+        worker
+            .post_message_with_transfer(message, vec![buffer.clone()])
+            .expect("Should post message");
+        assert_eq!(buffer.byte_length(), 0);
+        assert!(buffer.to_vec().is_err());
+
+        thread::sleep(Duration::from_millis(50));
+
+        let Some(StructuredValue::Object(props)) = worker.receive_structured_message() else {
+            panic!("expected the echoed object");
+        };
+        let StructuredValue::ArrayBufferHandle(received) = &props[1].1 else {
+            panic!("expected a transferred buffer");
+        };
+        assert_eq!(received.to_vec().unwrap(), vec![1, 2, 3, 4]);
+
+        // A detached buffer can't be transferred again
+        let retry = StructuredValue::ArrayBufferHandle(buffer.clone());
+        assert!(worker.post_message_with_transfer(retry, vec![buffer]).is_err());
+    }
+
     #[test]
     fn test_worker_multiple_messages() {
         let worker = Worker::new("test.js").expect("Should create worker");
@@ -325,6 +357,68 @@ mod atomics_tests {
         // 10 threads * 100 increments = 1000
         assert_eq!(Atomics::load(&buffer, 0), 1000);
     }
+
+    #[test]
+    fn test_atomics_wait_woken_by_notify_after_store() {
+        let buffer = SharedArrayBuffer::new(16);
+        let shared = buffer.clone();
+        let waiter = std::thread::spawn(move || {
+            Atomics::wait(&shared, 0, 0, Some(std::time::Duration::from_secs(10)))
+        });
+
+        Atomics::store(&buffer, 0, 1);
+        // The waiter may not have gone to sleep yet; keep notifying until it has
+        let mut woken = 0;
+        while woken == 0 && !waiter.is_finished() {
+            woken = Atomics::notify(&buffer, 0, 1);
+            std::thread::yield_now();
+        }
+
+        let result = waiter.join().unwrap();
+        if woken == 1 {
+            assert_eq!(result, WaitResult::Ok);
+        } else {
+            // The store landed before the waiter checked the value
+            assert_eq!(result, WaitResult::NotEqual);
+        }
+    }
+
+    #[test]
+    fn test_atomics_wait_not_equal_and_timeout() {
+        let buffer = SharedArrayBuffer::new(16);
+        Atomics::store(&buffer, 4, 7);
+        assert_eq!(Atomics::wait(&buffer, 4, 8, None), WaitResult::NotEqual);
+        assert_eq!(
+            Atomics::wait(&buffer, 4, 7, Some(std::time::Duration::from_millis(10))),
+            WaitResult::TimedOut
+        );
+        assert_eq!(Atomics::notify(&buffer, 4, 1), 0);
+    }
+
+    #[test]
+    fn test_atomics_wait_notify_out_of_bounds() {
+        let buffer = SharedArrayBuffer::new(8);
+        assert_eq!(Atomics::wait(&buffer, 8, 0, None), WaitResult::NotEqual);
+        assert_eq!(Atomics::notify(&buffer, 8, 1), 0);
+        assert_eq!(Atomics::wait(&buffer, usize::MAX, 0, None), WaitResult::NotEqual);
+        assert_eq!(Atomics::notify(&buffer, usize::MAX - 1, 1), 0);
+    }
+
+    #[test]
+    fn test_atomics_wait_with_unrepresentable_timeout() {
+        let buffer = SharedArrayBuffer::new(8);
+        let shared = buffer.clone();
+        let waiter = std::thread::spawn(move || {
+            Atomics::wait(&shared, 0, 0, Some(std::time::Duration::MAX))
+        });
+
+        // Waits untimed instead of overflowing the deadline
+        while Atomics::notify(&buffer, 0, 1) == 0 {
+            assert!(!waiter.is_finished(), "waiter returned without being notified");
+            std::thread::yield_now();
+        }
+        assert_eq!(waiter.join().unwrap(), WaitResult::Ok);
+    }
 }
 
 #[cfg(test)]
@@ -552,6 +646,80 @@ mod source_map_tests {
         assert_eq!(map.mappings_count(), 3);
     }
 
+    #[test]
+    fn test_source_map_index_map_sections() {
+        let json = r#"{
+            "version": 3,
+            "file": "bundle.js",
+            "sections": [
+                {
+                    "offset": {"line": 0, "column": 0},
+                    "map": {
+                        "version": 3,
+                        "sources": ["a.js"],
+                        "names": [],
+                        "mappings": "AAAA"
+                    }
+                },
+                {
+                    "offset": {"line": 5, "column": 10},
+                    "map": {
+                        "version": 3,
+                        "sources": ["b.js"],
+                        "names": [],
+                        "mappings": "AAEE;AACA"
+                    }
+                }
+            ]
+        }"#;
+
+        let map = SourceMap::from_json(json).expect("Should parse index map");
+        assert_eq!(map.sources, vec!["a.js".to_string(), "b.js".to_string()]);
+        assert_eq!(map.mappings_count(), 3);
+
+        assert_eq!(map.original_position_for(0, 0), Some(("a.js".to_string(), 0, 0)));
+        // First line of the second section is shifted by the column offset
+        assert_eq!(map.original_position_for(5, 9), None);
+        assert_eq!(map.original_position_for(5, 12), Some(("b.js".to_string(), 2, 2)));
+        // Subsequent lines are only shifted by the line offset
+        assert_eq!(map.original_position_for(6, 0), Some(("b.js".to_string(), 3, 2)));
+    }
+
+    #[test]
+    fn test_source_map_source_root_resolution() {
+        let json = r#"{
+            "version": 3,
+            "sourceRoot": "src/",
+            "sources": ["a.js", "/abs/b.js"],
+            "names": [],
+            "mappings": "AAAA;ACAA"
+        }"#;
+
+        let map = SourceMap::from_json(json).expect("Should parse JSON");
+        assert_eq!(map.resolved_source(0), Some("src/a.js".to_string()));
+        assert_eq!(map.resolved_source(1), Some("/abs/b.js".to_string()));
+        assert_eq!(map.original_position_for(0, 0), Some(("src/a.js".to_string(), 0, 0)));
+        assert_eq!(map.original_position_for(1, 0), Some(("/abs/b.js".to_string(), 0, 0)));
+        assert!(map.generated_position_for("src/a.js", 0, 0).is_some());
+
+        let round_trip = SourceMap::from_json(&map.to_json().unwrap()).unwrap();
+        assert_eq!(round_trip.source_root, Some("src/".to_string()));
+        assert_eq!(round_trip.resolved_source(0), Some("src/a.js".to_string()));
+    }
+
+    #[test]
+    fn test_source_map_index_map_rejects_mappings() {
+        let json = r#"{
+            "version": 3,
+            "mappings": "AAAA",
+            "sections": []
+        }"#;
+        assert!(SourceMap::from_json(json).is_err());
+
+        let json = r#"{"version": 2, "sections": []}"#;
+        assert!(SourceMap::from_json(json).is_err());
+    }
+
     #[test]
     fn test_source_map_add_mapping() {
         let mut map = SourceMap::new();
@@ -814,6 +982,30 @@ mod csp_tests {
         assert!(!csp.allows_inline_script());
     }
 
+    #[test]
+    fn test_csp_blocks_inline_event_handlers_and_javascript_urls() {
+        let strict = ContentSecurityPolicy::parse("script-src 'self'").expect("Should parse");
+        assert!(!strict.allows_inline_event_handler());
+        assert!(!strict.allows_javascript_url());
+
+        let inline = ContentSecurityPolicy::parse("script-src 'self' 'unsafe-inline'")
+            .expect("Should parse");
+        assert!(inline.allows_inline_event_handler());
+        assert!(inline.allows_javascript_url());
+    }
+
+    #[test]
+    fn test_csp_nonce_cancels_unsafe_inline() {
+        let header = "script-src 'nonce-abc123' 'unsafe-inline'";
+        let csp = ContentSecurityPolicy::parse(header).expect("Should parse");
+
+        // The nonced script still runs, but nothing else inline does
+        assert!(csp.validate_nonce("script-src", "abc123"));
+        assert!(!csp.allows_inline_script());
+        assert!(!csp.allows_inline_event_handler());
+        assert!(!csp.allows_javascript_url());
+    }
+
     #[test]
     fn test_csp_allows_source_with_default_src() {
         let header = "default-src 'self' https://cdn.example.com";
@@ -967,6 +1159,88 @@ mod csp_tests {
         assert!(report.contains("script-src"));
     }
 
+    #[test]
+    fn test_csp_report_endpoints_and_body() {
+        let header = "script-src 'self'; report-uri /csp; report-to csp-group";
+        let csp = ContentSecurityPolicy::parse(header).expect("Should parse");
+        assert_eq!(csp.report_endpoints(), vec!["/csp".to_string(), "csp-group".to_string()]);
+
+        let violation = CspViolation {
+            directive: "script-src".to_string(),
+            blocked_uri: "https://malicious.com/script.js".to_string(),
+            document_uri: "https://example.com/page".to_string(),
+            violated_directive: "script-src 'self'".to_string(),
+        };
+        let report = csp.build_report(&violation);
+        let body = &report["csp-report"];
+        assert_eq!(body["document-uri"], "https://example.com/page");
+        assert_eq!(body["violated-directive"], "script-src 'self'");
+        assert_eq!(body["effective-directive"], "script-src");
+        assert_eq!(body["blocked-uri"], "https://malicious.com/script.js");
+        assert_eq!(body["original-policy"], csp.to_header());
+        assert!(body["original-policy"].as_str().unwrap().contains("report-uri /csp"));
+    }
+
+    #[test]
+    fn test_csp_strict_dynamic_ignores_host_sources() {
+        let header = "script-src 'strict-dynamic' 'nonce-abc' https://cdn.com";
+        let csp = ContentSecurityPolicy::parse(header).expect("Should parse");
+
+        assert!(csp.propagates_trust());
+        assert!(!csp.allows_script_source("https://cdn.com"));
+        assert!(csp.validate_nonce("script-src", "abc"));
+        assert!(!csp.validate_nonce("script-src", "xyz"));
+
+        let csp = ContentSecurityPolicy::parse("script-src 'nonce-abc' https://cdn.com")
+            .expect("Should parse");
+        assert!(!csp.propagates_trust());
+        assert!(csp.allows_script_source("https://cdn.com"));
+    }
+
+    #[test]
+    fn test_csp_host_source_path_matching() {
+        let header = "script-src https://cdn.example.com/js/ https://x.com/app.js";
+        let csp = ContentSecurityPolicy::parse(header).expect("Should parse");
+
+        assert!(csp.allows_script_source("https://cdn.example.com/js/app.js"));
+        assert!(csp.allows_script_source("https://cdn.example.com/js/lib/util.js?v=2"));
+        assert!(!csp.allows_script_source("https://cdn.example.com/css/app.css"));
+        assert!(csp.allows_script_source("https://x.com/app.js"));
+        assert!(!csp.allows_script_source("https://x.com/app.js.map"));
+        assert!(!csp.allows_script_source("https://x.com/other.js"));
+    }
+
+    #[test]
+    fn test_csp_host_source_default_ports() {
+        let header =
+            "script-src https://a.example.com http://b.example.com https://c.example.com:8443";
+        let csp = ContentSecurityPolicy::parse(header).expect("Should parse");
+
+        assert!(csp.allows_script_source("https://a.example.com:443/app.js"));
+        assert!(!csp.allows_script_source("https://a.example.com:8080/app.js"));
+        assert!(!csp.allows_script_source("http://a.example.com/app.js"));
+        // http sources also allow the upgraded https origin
+        assert!(csp.allows_script_source("https://b.example.com/app.js"));
+        assert!(csp.allows_script_source("http://b.example.com:80/app.js"));
+        assert!(csp.allows_script_source("https://c.example.com:8443/app.js"));
+        assert!(!csp.allows_script_source("https://c.example.com/app.js"));
+    }
+
+    #[test]
+    fn test_csp_host_source_case_insensitive() {
+        let header = "script-src HTTPS://CDN.Example.COM *.static.com";
+        let csp = ContentSecurityPolicy::parse(header).expect("Should parse");
+
+        assert!(csp.allows_script_source("https://cdn.example.com/app.js"));
+        assert!(csp.allows_script_source("https://CDN.EXAMPLE.COM/app.js"));
+        assert!(csp.allows_script_source("https://img.STATIC.com/a.js"));
+        assert!(!csp.allows_script_source("https://static.com/a.js"));
+
+        let csp = ContentSecurityPolicy::parse("script-src https:").expect("Should parse");
+        assert!(csp.allows_script_source("HTTPS://any.example.com/a.js"));
+        assert!(!csp.allows_script_source("http://any.example.com/a.js"));
+    }
+
     #[test]
     fn test_csp_allows_connect_source() {
         let header = "connect-src https://api.example.com";
@@ -1068,11 +1342,41 @@ mod csp_tests {
         assert!(csp2.has_directive("default-src"));
         assert!(csp2.has_directive("script-src"));
     }
+
+    #[test]
+    fn test_csp_sandbox_with_tokens() {
+        let csp = ContentSecurityPolicy::parse("sandbox allow-scripts Allow-Forms").unwrap();
+        let flags = csp.sandbox_flags().expect("sandbox directive present");
+
+        assert_eq!(flags.len(), 2);
+        assert!(flags.contains("allow-scripts"));
+        assert!(flags.contains("allow-forms"));
+    }
+
+    #[test]
+    fn test_csp_empty_sandbox_is_fully_sandboxed() {
+        let csp = ContentSecurityPolicy::parse("default-src 'self'; sandbox").unwrap();
+        assert_eq!(csp.sandbox_flags(), Some(std::collections::HashSet::new()));
+
+        let unsandboxed = ContentSecurityPolicy::parse("default-src 'self'").unwrap();
+        assert_eq!(unsandboxed.sandbox_flags(), None);
+    }
+
+    #[test]
+    fn test_csp_requires_trusted_types() {
+        let csp = ContentSecurityPolicy::parse("require-trusted-types-for 'script'").unwrap();
+        assert!(csp.requires_trusted_types());
+
+        let csp = ContentSecurityPolicy::parse("script-src 'self'").unwrap();
+        assert!(!csp.requires_trusted_types());
+    }
 }
 
 #[cfg(test)]
 mod devtools_tests {
     use super::*;
+    use core_types::Value;
+    use std::collections::HashMap;
 
     #[test]
     fn test_server_creation() {
@@ -1082,6 +1386,127 @@ mod devtools_tests {
         assert!(server.call_stack().is_empty());
     }
 
+    #[test]
+    fn test_console_log_emits_console_api_called() {
+        use builtins::console::ConsoleObject;
+        use builtins::value::JsValue;
+        use std::cell::RefCell;
+        use std::rc::Rc;
+        use web_platform::DevToolsConsoleObserver;
+
+        let server = Rc::new(RefCell::new(DevToolsServer::new()));
+        let mut console = ConsoleObject::new_with_output(Rc::new(RefCell::new(Vec::new())));
+        console.set_observer(Some(Rc::new(DevToolsConsoleObserver::new(server.clone()))));
+
+        console.log(&[JsValue::string("hello"), JsValue::number(42.0)]);
+        console.warn(&[JsValue::array_from(vec![JsValue::number(1.0)])]);
+
+        let events = server.borrow_mut().take_events();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].method.as_deref(), Some("Runtime.consoleAPICalled"));
+        let params = events[0].params.as_ref().unwrap();
+        assert_eq!(params["type"], "log");
+        assert!(params["timestamp"].as_f64().unwrap() > 0.0);
+        assert_eq!(params["args"][0]["type"], "string");
+        assert_eq!(params["args"][0]["value"], "hello");
+        assert_eq!(params["args"][1]["type"], "number");
+        assert_eq!(params["args"][1]["description"], "42");
+
+        let params = events[1].params.as_ref().unwrap();
+        assert_eq!(params["type"], "warning");
+        assert_eq!(params["args"][0]["type"], "object");
+        assert_eq!(params["args"][0]["description"], "Array(1)");
+        assert!(params["args"][0]["object_id"].is_string());
+    }
+
+    #[test]
+    fn test_vm_console_calls_reach_devtools() {
+        use interpreter::VM;
+        use std::cell::RefCell;
+        use std::rc::Rc;
+        use web_platform::DevToolsConsoleObserver;
+
+        let server = Rc::new(RefCell::new(DevToolsServer::new()));
+        let mut vm = VM::new();
+        vm.set_console_observer(Some(Rc::new(DevToolsConsoleObserver::new(server.clone()))));
+
+        let source = "console.log('hi', 40 + 2); console.warn('careful');";
+        let program = parser::Parser::new(source).parse().unwrap();
+        let chunk = parser::BytecodeGenerator::new().generate(&program).unwrap();
+        vm.execute(&chunk).unwrap();
+
+        let events = server.borrow_mut().take_events();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].method.as_deref(), Some("Runtime.consoleAPICalled"));
+        let params = events[0].params.as_ref().unwrap();
+        assert_eq!(params["type"], "log");
+        assert_eq!(params["args"][0]["type"], "string");
+        assert_eq!(params["args"][0]["value"], "hi");
+        assert_eq!(params["args"][1]["type"], "number");
+        assert_eq!(params["args"][1]["description"], "42");
+
+        let params = events[1].params.as_ref().unwrap();
+        assert_eq!(params["type"], "warning");
+        assert_eq!(params["args"][0]["value"], "careful");
+
+        vm.set_console_observer(None);
+        vm.execute(&chunk).unwrap();
+        assert!(server.borrow_mut().take_events().is_empty());
+    }
+
+    #[test]
+    fn test_vm_debugger_statement_pauses_devtools() {
+        use interpreter::VM;
+        use std::cell::RefCell;
+        use std::rc::Rc;
+        use web_platform::DevToolsDebuggerHook;
+
+        let server = Rc::new(RefCell::new(DevToolsServer::new()));
+        let mut vm = VM::new();
+        vm.set_debugger_hook(Some(Rc::new(DevToolsDebuggerHook::new(server.clone(), "main"))));
+
+        let source = "function outer() {\n\
+                      let limit = 10;\n\
+                      function inner(count) {\n\
+                      let label = 'total';\n\
+                      debugger;\n\
+                      return count * limit;\n\
+                      }\n\
+                      return inner(3);\n\
+                      }\n\
+                      outer();";
+        let program = parser::Parser::new(source).parse().unwrap();
+        let chunk = parser::BytecodeGenerator::new().generate(&program).unwrap();
+        vm.execute(&chunk).unwrap();
+
+        let events = server.borrow_mut().take_events();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].method.as_deref(), Some("Debugger.paused"));
+        let params = events[0].params.as_ref().unwrap();
+        assert_eq!(params["reason"], "debugger");
+        let frame = &params["callFrames"][0];
+        assert_eq!(frame["location"]["script_id"], "main");
+        assert_eq!(frame["location"]["line_number"], 4);
+
+        let scopes = &frame["scope_chain"];
+        let locals = scopes[0]["variables"].as_array().unwrap();
+        assert_eq!(locals.len(), 2);
+        assert_eq!(locals[0]["name"], "count");
+        assert_eq!(locals[0]["value"]["value"], 3);
+        assert_eq!(locals[1]["name"], "label");
+        assert_eq!(locals[1]["value"]["value"], "total");
+        assert_eq!(scopes[1]["type"], "closure");
+        assert_eq!(scopes[1]["variables"][0]["name"], "limit");
+        assert_eq!(scopes[1]["variables"][0]["value"]["value"], 10);
+        let globals = scopes[2]["variables"].as_array().unwrap();
+        assert!(globals.iter().any(|global| global["name"] == "outer"));
+
+        // Without a hook the statement does nothing
+        vm.set_debugger_hook(None);
+        vm.execute(&chunk).unwrap();
+        assert!(server.borrow_mut().take_events().is_empty());
+    }
+
     #[test]
     fn test_default_creation() {
         let server = DevToolsServer::default();
@@ -1329,6 +1754,71 @@ mod devtools_tests {
         assert!(!server.should_pause_at("script_2", 10));
     }
 
+    fn set_conditional_breakpoint(server: &mut DevToolsServer, line: u32, condition: &str) {
+        let msg = ProtocolMessage {
+            id: Some(1),
+            method: Some("Debugger.setBreakpoint".to_string()),
+            params: Some(json!({
+                "location": { "scriptId": "script_1", "lineNumber": line },
+                "condition": condition
+            })),
+            result: None,
+            error: None,
+        };
+        server.handle_message(&msg);
+    }
+
+    #[test]
+    fn test_conditional_breakpoint_pauses_only_when_true() {
+        let mut server = DevToolsServer::new();
+        set_conditional_breakpoint(&mut server, 10, "x > 10");
+
+        let mut scope = HashMap::new();
+        scope.insert("x".to_string(), Value::Smi(42));
+        assert!(server.should_pause_at_with_scope("script_1", 10, &scope));
+
+        scope.insert("x".to_string(), Value::Double(3.0));
+        assert!(!server.should_pause_at_with_scope("script_1", 10, &scope));
+        assert!(!server.should_pause_at_with_scope("script_1", 11, &scope));
+        assert!(server.condition_error("bp_1").is_none());
+    }
+
+    #[test]
+    fn test_failing_breakpoint_condition_pauses_and_records_error() {
+        let mut server = DevToolsServer::new();
+        set_conditional_breakpoint(&mut server, 10, "check(x) > 10");
+        set_conditional_breakpoint(&mut server, 20, "x >");
+
+        let mut scope = HashMap::new();
+        scope.insert("x".to_string(), Value::Smi(1));
+        assert!(server.should_pause_at_with_scope("script_1", 10, &scope));
+        assert!(server.condition_error("bp_1").unwrap().starts_with("TypeError"));
+
+        assert!(server.should_pause_at_with_scope("script_1", 20, &scope));
+        assert!(server.condition_error("bp_2").unwrap().starts_with("SyntaxError"));
+
+        // A later successful evaluation clears the error
+        scope.insert("check".to_string(), Value::NativeFunction("Math.abs".to_string()));
+        assert!(!server.should_pause_at_with_scope("script_1", 10, &scope));
+        assert!(server.condition_error("bp_1").is_none());
+    }
+
+    #[test]
+    fn test_breakpoint_condition_reads_vm_objects() {
+        let mut server = DevToolsServer::new();
+        set_conditional_breakpoint(&mut server, 10, "point.x * 2 === 8 && point.tags[1] === 'b'");
+
+        // The paused frame's bindings are live VM values, objects included
+        let program = parser::Parser::new("({ x: 4, tags: ['a', 'b'] })").parse().unwrap();
+        let chunk = parser::BytecodeGenerator::new().generate(&program).unwrap();
+        let point = interpreter::VM::new().execute(&chunk).unwrap();
+
+        let mut scope = HashMap::new();
+        scope.insert("point".to_string(), point);
+        assert!(server.should_pause_at_with_scope("script_1", 10, &scope));
+        assert!(server.condition_error("bp_1").is_none());
+    }
+
     #[test]
     fn test_call_stack_operations() {
         let mut server = DevToolsServer::new();
@@ -1391,6 +1881,7 @@ mod devtools_tests {
                         description: Some("Local".to_string()),
                         object_id: Some("scope_1".to_string()),
                     },
+                    const_bindings: vec![],
                 },
             ],
         };
@@ -1412,6 +1903,211 @@ mod devtools_tests {
         assert_eq!(json_val["column_number"], 10);
     }
 
+    fn paused_server_with_locals() -> DevToolsServer {
+        let mut server = DevToolsServer::new();
+        server.push_call_frame(CallFrame {
+            call_frame_id: "frame_1".to_string(),
+            function_name: "compute".to_string(),
+            location: Location {
+                script_id: "script_1".to_string(),
+                line_number: 3,
+                column_number: 0,
+            },
+            scope_chain: vec![Scope {
+                scope_type: "local".to_string(),
+                object: RemoteObject {
+                    object_type: "object".to_string(),
+                    value: Some(json!({ "count": 1, "LIMIT": 10 })),
+                    description: Some("Local".to_string()),
+                    object_id: Some("scope_1".to_string()),
+                },
+                const_bindings: vec!["LIMIT".to_string()],
+            }],
+        });
+        server.set_paused(true);
+        server
+    }
+
+    fn set_variable_message(name: &str, value: serde_json::Value) -> ProtocolMessage {
+        ProtocolMessage {
+            id: Some(7),
+            method: Some("Debugger.setVariableValue".to_string()),
+            params: Some(json!({
+                "scopeNumber": 0,
+                "variableName": name,
+                "newValue": { "value": value },
+                "callFrameId": "frame_1"
+            })),
+            result: None,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_set_variable_value_while_paused() {
+        let mut server = paused_server_with_locals();
+        let response = server.handle_message(&set_variable_message("count", json!(99)));
+        assert!(response.error.is_none());
+
+        let scope = &server.call_stack()[0].scope_chain[0];
+        assert_eq!(scope.object.value.as_ref().unwrap()["count"], 99);
+    }
+
+    #[test]
+    fn test_set_variable_value_rejects_const() {
+        let mut server = paused_server_with_locals();
+        let response = server.handle_message(&set_variable_message("LIMIT", json!(20)));
+        let error = response.error.unwrap();
+        assert!(error.message.contains("constant"));
+
+        let scope = &server.call_stack()[0].scope_chain[0];
+        assert_eq!(scope.object.value.as_ref().unwrap()["LIMIT"], 10);
+    }
+
+    #[test]
+    fn test_set_variable_value_requires_new_value() {
+        let mut server = paused_server_with_locals();
+        let mut message = set_variable_message("count", json!(5));
+        message.params.as_mut().unwrap()["newValue"] = json!({});
+        let response = server.handle_message(&message);
+        assert!(response.error.unwrap().message.contains("newValue"));
+
+        let scope = &server.call_stack()[0].scope_chain[0];
+        assert_eq!(scope.object.value.as_ref().unwrap()["count"], 1);
+    }
+
+    #[test]
+    fn test_set_variable_value_requires_pause() {
+        let mut server = paused_server_with_locals();
+        server.set_paused(false);
+        let response = server.handle_message(&set_variable_message("count", json!(5)));
+        assert!(response.error.is_some());
+    }
+
+    fn evaluate_message(expression: &str) -> ProtocolMessage {
+        ProtocolMessage {
+            id: Some(8),
+            method: Some("Debugger.evaluateOnCallFrame".to_string()),
+            params: Some(json!({
+                "callFrameId": "frame_1",
+                "expression": expression
+            })),
+            result: None,
+            error: None,
+        }
+    }
+
+    fn paused_server_with_nested_scopes() -> DevToolsServer {
+        let scope = |scope_type: &str, value: serde_json::Value| Scope {
+            scope_type: scope_type.to_string(),
+            object: RemoteObject {
+                object_type: "object".to_string(),
+                value: Some(value),
+                description: None,
+                object_id: None,
+            },
+            const_bindings: Vec::new(),
+        };
+        let mut server = DevToolsServer::new();
+        server.push_call_frame(CallFrame {
+            call_frame_id: "frame_1".to_string(),
+            function_name: "inner".to_string(),
+            location: Location {
+                script_id: "script_1".to_string(),
+                line_number: 5,
+                column_number: 2,
+            },
+            scope_chain: vec![
+                scope("local", json!({ "x": 42, "name": "local" })),
+                scope("global", json!({ "x": 1, "config": { "items": [1, 2, 3] } })),
+            ],
+        });
+        server.set_paused(true);
+        server
+    }
+
+    #[test]
+    fn test_evaluate_on_call_frame_uses_local_scope() {
+        let mut server = paused_server_with_nested_scopes();
+        let response = server.handle_message(&evaluate_message("x + 1"));
+        let result = response.result.unwrap();
+        assert!(result.get("exceptionDetails").is_none());
+        assert_eq!(result["result"]["type"], "number");
+        assert_eq!(result["result"]["value"], 43);
+
+        let response = server.handle_message(&evaluate_message("name + ':' + config.items[2]"));
+        assert_eq!(response.result.unwrap()["result"]["value"], "local:3");
+    }
+
+    #[test]
+    fn test_evaluate_on_call_frame_reports_exceptions() {
+        let mut server = paused_server_with_nested_scopes();
+        let response = server.handle_message(&evaluate_message("name()"));
+        assert!(response.error.is_none());
+
+        let result = response.result.unwrap();
+        let exception = &result["exceptionDetails"]["exception"];
+        assert_eq!(exception["className"], "TypeError");
+        assert!(exception["description"].as_str().unwrap().starts_with("TypeError: "));
+
+        let response = server.handle_message(&evaluate_message("x +"));
+        let result = response.result.unwrap();
+        assert_eq!(result["exceptionDetails"]["exception"]["className"], "SyntaxError");
+
+        // Short-circuiting skips the call entirely
+        let response = server.handle_message(&evaluate_message("x > 40 || name()"));
+        assert_eq!(response.result.unwrap()["result"]["value"], true);
+    }
+
+    #[test]
+    fn test_evaluate_on_call_frame_runs_statements() {
+        let mut server = paused_server_with_nested_scopes();
+        let expression = "var total = 0; \
+            for (var i = 0; i < config.items.length; i++) { total += config.items[i]; } \
+            total * x";
+        let response = server.handle_message(&evaluate_message(expression));
+        assert_eq!(response.result.unwrap()["result"]["value"], 252);
+
+        let response = server.handle_message(&evaluate_message("config.items.map(n => n * 2)"));
+        let result = response.result.unwrap()["result"].clone();
+        assert_eq!(result["value"], json!([2, 4, 6]));
+        assert_eq!(result["description"], "Array(3)");
+    }
+
+    #[test]
+    fn test_evaluate_on_call_frame_rejects_deep_nesting() {
+        let mut server = paused_server_with_nested_scopes();
+        let nested = format!("{}x{}", "(".repeat(100_000), ")".repeat(100_000));
+        let chained = vec!["x"; 100_000].join(" + ");
+        let members = format!("config{}", ".items".repeat(100_000));
+        for expression in [nested, chained, members, "-".repeat(100_000) + "x"] {
+            let response = server.handle_message(&evaluate_message(&expression));
+            let result = response.result.unwrap();
+            let exception = &result["exceptionDetails"]["exception"];
+            assert_eq!(exception["className"], "RangeError");
+        }
+
+        // Moderate nesting still evaluates
+        let expression = format!("{}x{}", "(".repeat(50), ")".repeat(50));
+        let response = server.handle_message(&evaluate_message(&expression));
+        assert_eq!(response.result.unwrap()["result"]["value"], 42);
+    }
+
+    #[test]
+    fn test_evaluate_on_call_frame_requires_pause() {
+        let mut server = paused_server_with_nested_scopes();
+        server.set_paused(false);
+        let response = server.handle_message(&evaluate_message("x"));
+        assert!(response.error.is_some());
+    }
+
+    #[test]
+    fn test_set_variable_value_unknown_variable() {
+        let mut server = paused_server_with_locals();
+        let response = server.handle_message(&set_variable_message("missing", json!(5)));
+        assert!(response.error.is_some());
+    }
+
     #[test]
     fn test_resume_clears_call_stack() {
         let mut server = DevToolsServer::new();
@@ -1438,4 +2134,224 @@ mod devtools_tests {
         server.handle_message(&msg);
         assert!(server.call_stack().is_empty());
     }
+
+    #[test]
+    fn test_network_request_lifecycle_notifications() {
+        use web_platform::service_worker::{FetchRequest, FetchResponse, RequestMethod};
+
+        let mut server = DevToolsServer::new();
+        let msg = ProtocolMessage {
+            id: Some(1),
+            method: Some("Network.enable".to_string()),
+            params: None,
+            result: None,
+            error: None,
+        };
+        let response = server.handle_message(&msg);
+        assert!(response.error.is_none());
+        assert!(server.is_network_enabled());
+
+        let request = FetchRequest::new("https://example.com/data.json", RequestMethod::Get);
+        let request_id = server.notify_request(&request);
+        server.notify_response(&request_id, &FetchResponse::new(200, b"{}".to_vec()));
+        server.notify_loading_finished(&request_id, 2);
+
+        let events = server.take_events();
+        let methods: Vec<_> = events.iter().map(|e| e.method.as_deref().unwrap()).collect();
+        assert_eq!(
+            methods,
+            vec!["Network.requestWillBeSent", "Network.responseReceived", "Network.loadingFinished"]
+        );
+        for event in &events {
+            assert_eq!(event.id, None);
+            assert_eq!(event.params.as_ref().unwrap()["requestId"], request_id.as_str());
+        }
+        assert_eq!(events[0].params.as_ref().unwrap()["request"]["method"], "GET");
+        assert_eq!(events[1].params.as_ref().unwrap()["response"]["status"], 200);
+        assert!(server.take_events().is_empty());
+    }
+
+    #[test]
+    fn test_set_breakpoint_by_url_maps_original_line() {
+        let mut server = DevToolsServer::new();
+        let script_id = server.add_script_with_url("compiled()".to_string(), "https://example.com/app.js");
+
+        let mut source_map = SourceMap::new();
+        let source = source_map.add_source("src/app.ts".to_string());
+        for (generated_line, original_line) in [(0, 0), (7, 3), (12, 5)] {
+            source_map.add_mapping(SourceMapping {
+                generated_line,
+                generated_column: 4,
+                source_index: Some(source),
+                original_line: Some(original_line),
+                original_column: Some(2),
+                name_index: None,
+            });
+        }
+        server.register_source_map(&script_id, source_map);
+
+        let msg = ProtocolMessage {
+            id: Some(1),
+            method: Some("Debugger.setBreakpointByUrl".to_string()),
+            params: Some(json!({ "urlRegex": "app\\.ts$", "lineNumber": 3 })),
+            result: None,
+            error: None,
+        };
+        let response = server.handle_message(&msg);
+        let result = response.result.unwrap();
+        let bp_id = result["breakpointId"].as_str().unwrap().to_string();
+        assert_eq!(result["locations"][0]["lineNumber"], 7);
+
+        let breakpoint = &server.breakpoints()[&bp_id];
+        assert_eq!(breakpoint.script_id, script_id);
+        assert_eq!(breakpoint.line_number, 7);
+        assert_eq!(breakpoint.column_number, Some(4));
+        assert!(server.should_pause_at(&script_id, 7));
+        assert!(!server.should_pause_at(&script_id, 3));
+
+        let remove = ProtocolMessage {
+            id: Some(2),
+            method: Some("Debugger.removeBreakpoint".to_string()),
+            params: Some(json!({ "breakpointId": bp_id })),
+            result: None,
+            error: None,
+        };
+        server.handle_message(&remove);
+        assert!(!server.should_pause_at(&script_id, 7));
+    }
+
+    #[test]
+    fn test_set_breakpoint_by_url_resolves_when_script_loads() {
+        let mut server = DevToolsServer::new();
+        for (id, params) in [
+            (1, json!({ "url": "https://example.com/app.js", "lineNumber": 4, "condition": "x > 1" })),
+            (2, json!({ "urlRegex": "vendor/.*\\.js$", "lineNumber": 9 })),
+        ] {
+            let msg = ProtocolMessage {
+                id: Some(id),
+                method: Some("Debugger.setBreakpointByUrl".to_string()),
+                params: Some(params),
+                result: None,
+                error: None,
+            };
+            let result = server.handle_message(&msg).result.unwrap();
+            assert_eq!(result["locations"], json!([]));
+        }
+        assert!(server.breakpoints().is_empty());
+
+        let (app, locations) =
+            server.add_script_with_url_resolving("run()".to_string(), "https://example.com/app.js");
+        assert_eq!(locations.len(), 1);
+        assert_eq!(locations[0].script_id, app);
+        assert_eq!(locations[0].line_number, 4);
+        let breakpoint = &server.breakpoints()["bp_1"];
+        assert_eq!(breakpoint.script_id, app);
+        assert_eq!(breakpoint.condition.as_deref(), Some("x > 1"));
+        assert!(server.should_pause_at(&app, 4));
+
+        let events = server.take_events();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].method.as_deref(), Some("Debugger.breakpointResolved"));
+        assert_eq!(events[0].params.as_ref().unwrap()["breakpointId"], "bp_1");
+
+        // Regex breakpoints resolve in every matching script
+        let lib = server.add_script_with_url("lib()".to_string(), "https://cdn.test/vendor/lib.js");
+        let other = server.add_script_with_url("x()".to_string(), "https://cdn.test/vendor/x.js");
+        assert!(server.should_pause_at(&lib, 9));
+        assert!(server.should_pause_at(&other, 9));
+        let unrelated = server.add_script_with_url("y()".to_string(), "https://cdn.test/y.js");
+        assert!(!server.should_pause_at(&unrelated, 9));
+
+        // Removed URL breakpoints no longer resolve in new scripts
+        let remove = ProtocolMessage {
+            id: Some(3),
+            method: Some("Debugger.removeBreakpoint".to_string()),
+            params: Some(json!({ "breakpointId": "bp_2" })),
+            result: None,
+            error: None,
+        };
+        server.handle_message(&remove);
+        assert!(!server.should_pause_at(&lib, 9));
+        let late = server.add_script_with_url("z()".to_string(), "https://cdn.test/vendor/z.js");
+        assert!(!server.should_pause_at(&late, 9));
+    }
+
+    #[test]
+    fn test_network_notifications_require_enable() {
+        use web_platform::service_worker::{FetchRequest, RequestMethod};
+
+        let mut server = DevToolsServer::new();
+        let request_id = server.notify_request(&FetchRequest::new("https://example.com/", RequestMethod::Get));
+        server.notify_loading_finished(&request_id, 0);
+        assert!(server.take_events().is_empty());
+    }
+
+    #[test]
+    fn test_paused_notification_lists_scope_variables() {
+        use builtins::value::JsValue;
+        use web_platform::PausedFrame;
+
+        let mut server = DevToolsServer::new();
+        let frame = PausedFrame {
+            function_name: "compute".to_string(),
+            location: Location {
+                script_id: "script_1".to_string(),
+                line_number: 4,
+                column_number: 2,
+            },
+            locals: vec![
+                ("count".to_string(), JsValue::number(3.0)),
+                ("label".to_string(), JsValue::string("total")),
+                ("pending".to_string(), JsValue::undefined()),
+            ],
+            closure: vec![("LIMIT".to_string(), JsValue::number(10.0))],
+            const_bindings: vec!["LIMIT".to_string()],
+        };
+        let globals = vec![("debug".to_string(), JsValue::boolean(true))];
+        server.pause("breakpoint", vec![frame], &globals);
+        assert!(server.is_paused());
+
+        let events = server.take_events();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].method.as_deref(), Some("Debugger.paused"));
+        let params = events[0].params.as_ref().unwrap();
+        assert_eq!(params["reason"], "breakpoint");
+        let scopes = &params["callFrames"][0]["scope_chain"];
+        assert_eq!(scopes[0]["type"], "local");
+        assert_eq!(scopes[1]["type"], "closure");
+        assert_eq!(scopes[2]["type"], "global");
+
+        let locals = scopes[0]["variables"].as_array().unwrap();
+        assert_eq!(locals.len(), 3);
+        assert_eq!(locals[0]["name"], "count");
+        assert_eq!(locals[0]["value"]["type"], "number");
+        assert_eq!(locals[0]["value"]["value"], 3);
+        assert_eq!(locals[1]["value"]["value"], "total");
+        assert_eq!(locals[2]["value"]["type"], "undefined");
+        assert_eq!(scopes[1]["variables"][0]["name"], "LIMIT");
+        assert_eq!(scopes[1]["variables"][0]["writable"], false);
+        assert_eq!(scopes[2]["variables"][0]["value"]["value"], true);
+
+        // The same variables back evaluation and Runtime.getProperties
+        let response = server.handle_message(&ProtocolMessage {
+            id: Some(1),
+            method: Some("Debugger.evaluateOnCallFrame".to_string()),
+            params: Some(json!({ "callFrameId": "frame_0", "expression": "count * LIMIT" })),
+            result: None,
+            error: None,
+        });
+        assert_eq!(response.result.unwrap()["result"]["value"], 30);
+
+        let object_id = scopes[0]["object"]["object_id"].as_str().unwrap();
+        let response = server.handle_message(&ProtocolMessage {
+            id: Some(2),
+            method: Some("Runtime.getProperties".to_string()),
+            params: Some(json!({ "objectId": object_id })),
+            result: None,
+            error: None,
+        });
+        let properties = response.result.unwrap()["result"].clone();
+        assert_eq!(properties.as_array().unwrap().len(), 3);
+        assert_eq!(properties[1]["name"], "label");
+    }
 }